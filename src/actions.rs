@@ -0,0 +1,153 @@
+use std::io;
+use std::sync::OnceLock;
+
+use crate::audit;
+use crate::batch::Target;
+use crate::docker::{self, ComposeService};
+use crate::signals::Signal;
+
+// ── Actions ──────────────────────────────────────────────────────────
+//
+// Everything portview does to a process or container, as opposed to
+// reading about it, goes through an `ActionExecutor`. The system one
+// sends real signals and runs the docker CLI; `--fixture` installs one
+// that drops rows from the fixture, tests hand the TUI one that records,
+// and a dry-run or remote executor would slot in the same way.
+
+pub(crate) trait ActionExecutor: Send + Sync {
+    /// Send `signal` to the target's process, as resolved from the rows it
+    /// was picked from. The platform's name for what was sent (`SIGTERM`,
+    /// `TerminateProcess`) on success.
+    fn signal(&self, target: &Target, signal: Signal) -> io::Result<String>;
+
+    /// `docker stop` or `docker restart` on a container; a status line
+    /// either way.
+    fn docker_action(&self, action: &str, container: &str) -> String;
+
+    /// The last few lines a container logged, or why they couldn't be read.
+    fn docker_logs(&self, container: &str) -> String;
+
+    /// `docker compose restart` for one service; a status line either way.
+    fn compose_restart(&self, compose: &ComposeService) -> String;
+}
+
+/// Real signals and the real docker CLI.
+pub(crate) struct SystemExecutor;
+
+impl ActionExecutor for SystemExecutor {
+    fn signal(&self, target: &Target, signal: Signal) -> io::Result<String> {
+        crate::send_signal(target.pid, signal)
+    }
+
+    fn docker_action(&self, action: &str, container: &str) -> String {
+        docker::run_docker_action(action, container)
+    }
+
+    fn docker_logs(&self, container: &str) -> String {
+        docker::run_docker_logs(container)
+    }
+
+    fn compose_restart(&self, compose: &ComposeService) -> String {
+        docker::compose_restart(compose)
+    }
+}
+
+/// Another executor with every kill, stop and restart written to the
+/// action log. Reading logs isn't an action, so it isn't logged.
+pub(crate) struct Audited<E>(pub(crate) E);
+
+impl<E: ActionExecutor> ActionExecutor for Audited<E> {
+    fn signal(&self, target: &Target, signal: Signal) -> io::Result<String> {
+        let result = self.0.signal(target, signal);
+        let outcome = match &result {
+            Ok(_) => "ok".to_string(),
+            Err(err) => err.to_string(),
+        };
+        audit::record(&signal.name(), &describe(target), &outcome);
+        result
+    }
+
+    fn docker_action(&self, action: &str, container: &str) -> String {
+        let msg = self.0.docker_action(action, container);
+        audit::record(
+            &format!("docker {}", action),
+            &format!("container {}", container),
+            status_outcome(&msg),
+        );
+        msg
+    }
+
+    fn docker_logs(&self, container: &str) -> String {
+        self.0.docker_logs(container)
+    }
+
+    fn compose_restart(&self, compose: &ComposeService) -> String {
+        let msg = self.0.compose_restart(compose);
+        audit::record(
+            "docker compose restart",
+            &format!("service {} ({})", compose.service, compose.project),
+            status_outcome(&msg),
+        );
+        msg
+    }
+}
+
+/// `PID 4242 (node :3000,3001)`, or just the PID when it holds no port.
+fn describe(target: &Target) -> String {
+    if target.ports.is_empty() {
+        return format!("PID {}", target.pid);
+    }
+    let ports: Vec<String> = target.ports.iter().map(u16::to_string).collect();
+    format!(
+        "PID {} ({} :{})",
+        target.pid,
+        target.process,
+        ports.join(",")
+    )
+}
+
+/// `ok` for a docker status line ending in `: OK`, the line otherwise.
+fn status_outcome(msg: &str) -> &str {
+    if msg.ends_with(": OK") {
+        "ok"
+    } else {
+        msg
+    }
+}
+
+static ACTIVE: OnceLock<Box<dyn ActionExecutor>> = OnceLock::new();
+
+/// Replace the system executor for the rest of the run. Must happen before
+/// the first action.
+pub(crate) fn install(executor: Box<dyn ActionExecutor>) -> Result<(), String> {
+    ACTIVE
+        .set(executor)
+        .map_err(|_| "an action executor is already active".to_string())
+}
+
+/// The executor for this run: an installed one, or the system one, logged.
+pub(crate) fn active() -> &'static dyn ActionExecutor {
+    ACTIVE
+        .get_or_init(|| Box::new(Audited(SystemExecutor)))
+        .as_ref()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn targets_are_described_from_their_rows() {
+        let target = Target {
+            pid: 4242,
+            process: "node".to_string(),
+            ports: vec![3000, 3001],
+        };
+        assert_eq!(describe(&target), "PID 4242 (node :3000,3001)");
+        let target = Target {
+            ports: Vec::new(),
+            ..target
+        };
+        assert_eq!(describe(&target), "PID 4242");
+    }
+}
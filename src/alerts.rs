@@ -0,0 +1,380 @@
+use std::collections::{HashMap, HashSet};
+use std::time::{Instant, SystemTime, UNIX_EPOCH};
+
+use crate::{format_bytes, json_escape, PortInfo, JSON_SCHEMA_VERSION};
+
+// ── Rules ────────────────────────────────────────────────────────────
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Metric {
+    /// Resident memory in bytes.
+    Mem,
+    /// CPU usage in percent of one core, averaged since the last refresh.
+    Cpu,
+}
+
+/// A `--alert` rule such as `mem>1GB`, `cpu>80%` or `process=node mem>512MB`.
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) struct AlertRule {
+    metric: Metric,
+    threshold: f64,
+    /// Lowercased substring of the process name.
+    process: Option<String>,
+    /// The rule as written, for messages.
+    source: String,
+}
+
+impl AlertRule {
+    pub(crate) fn parse(src: &str) -> Result<Self, String> {
+        let mut process = None;
+        let mut condition = None;
+        for token in src.split_whitespace() {
+            if let Some(name) = token.strip_prefix("process=") {
+                process = Some(name.to_lowercase());
+            } else if condition.is_none() {
+                condition = Some(token);
+            } else {
+                return Err(format!("unexpected '{}' in alert rule", token));
+            }
+        }
+        let condition =
+            condition.ok_or_else(|| "alert rule needs a condition like mem>1GB".to_string())?;
+        let (name, value) = condition
+            .split_once('>')
+            .ok_or_else(|| format!("expected metric>value in alert rule, got '{}'", condition))?;
+        let (metric, threshold) = match name {
+            "mem" => (Metric::Mem, parse_size(value)),
+            "cpu" => (
+                Metric::Cpu,
+                value
+                    .strip_suffix('%')
+                    .unwrap_or(value)
+                    .parse::<f64>()
+                    .ok()
+                    .filter(|v| *v >= 0.0),
+            ),
+            _ => return Err(format!("unknown alert metric '{}' (use mem or cpu)", name)),
+        };
+        let threshold =
+            threshold.ok_or_else(|| format!("invalid threshold '{}' in alert rule", value))?;
+        Ok(Self {
+            metric,
+            threshold,
+            process,
+            source: src.split_whitespace().collect::<Vec<_>>().join(" "),
+        })
+    }
+
+    /// The measured value when `info` breaches this rule.
+    fn check(&self, info: &PortInfo, cpu_percent: Option<f64>) -> Option<f64> {
+        if let Some(ref p) = self.process {
+            if !info.process_name.to_lowercase().contains(p) {
+                return None;
+            }
+        }
+        let value = match self.metric {
+            Metric::Mem => info.memory_bytes as f64,
+            Metric::Cpu => cpu_percent?,
+        };
+        (value > self.threshold).then_some(value)
+    }
+
+    fn format_value(&self, value: f64) -> String {
+        match self.metric {
+            Metric::Mem => format_bytes(value as u64),
+            Metric::Cpu => format!("{:.0}%", value),
+        }
+    }
+}
+
+/// The rule as written, with runs of whitespace collapsed; it parses back
+/// to the same rule.
+impl std::fmt::Display for AlertRule {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.source)
+    }
+}
+
+/// `1GB`, `512M`, `300KB` or plain bytes, in the same 1024-based units as
+/// the MEM column.
+fn parse_size(s: &str) -> Option<f64> {
+    let upper = s.to_uppercase();
+    let digits = upper.trim_end_matches('B');
+    let (number, multiplier) = match digits.chars().last()? {
+        'K' => (&digits[..digits.len() - 1], 1024.0),
+        'M' => (&digits[..digits.len() - 1], 1024.0 * 1024.0),
+        'G' => (&digits[..digits.len() - 1], 1024.0 * 1024.0 * 1024.0),
+        _ => (digits, 1.0),
+    };
+    number
+        .parse::<f64>()
+        .ok()
+        .filter(|n| *n >= 0.0)
+        .map(|n| n * multiplier)
+}
+
+// ── Monitoring ───────────────────────────────────────────────────────
+
+/// A process that just crossed an alert threshold.
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) struct Alert {
+    pub(crate) pid: u32,
+    pub(crate) process: String,
+    pub(crate) rule: String,
+    pub(crate) value: String,
+}
+
+impl Alert {
+    /// `node (PID 4242): 1.2 GB (mem>1GB)`
+    pub(crate) fn describe(&self) -> String {
+        format!(
+            "{} (PID {}): {} ({})",
+            self.process, self.pid, self.value, self.rule
+        )
+    }
+}
+
+/// Webhook payload for an alert; `timestamp` is Unix seconds.
+pub(crate) fn alert_json(alert: &Alert) -> String {
+    let timestamp = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .unwrap_or_default()
+        .as_secs();
+    format!(
+        r#"{{"schema_version":{},"event":"alert","pid":{},"process":"{}","rule":"{}","value":"{}","timestamp":{}}}"#,
+        JSON_SCHEMA_VERSION,
+        alert.pid,
+        json_escape(&alert.process),
+        json_escape(&alert.rule),
+        json_escape(&alert.value),
+        timestamp
+    )
+}
+
+/// Evaluates alert rules on each refresh.
+///
+/// Alerts are edge-triggered: one fires when a process first breaches a
+/// rule, and again only after it has dropped back below the threshold.
+pub(crate) struct AlertMonitor {
+    rules: Vec<AlertRule>,
+    prev_cpu: HashMap<u32, f64>,
+    prev_at: Option<Instant>,
+    /// (pid, rule index) pairs currently over threshold.
+    active: HashSet<(u32, usize)>,
+}
+
+impl AlertMonitor {
+    pub(crate) fn new(rules: Vec<AlertRule>) -> Self {
+        Self {
+            rules,
+            prev_cpu: HashMap::new(),
+            prev_at: None,
+            active: HashSet::new(),
+        }
+    }
+
+    pub(crate) fn is_empty(&self) -> bool {
+        self.rules.is_empty()
+    }
+
+    /// Whether any rule is currently breached by `pid`.
+    pub(crate) fn is_alerting(&self, pid: u32) -> bool {
+        self.active.iter().any(|(p, _)| *p == pid)
+    }
+
+    /// Re-evaluate all rules and return alerts that started this refresh.
+    pub(crate) fn update<'a>(
+        &mut self,
+        infos: impl IntoIterator<Item = &'a PortInfo>,
+        now: Instant,
+    ) -> Vec<Alert> {
+        let infos: Vec<&PortInfo> = infos.into_iter().collect();
+        let elapsed = self
+            .prev_at
+            .map(|at| now.saturating_duration_since(at).as_secs_f64())
+            .unwrap_or(0.0);
+
+        let mut seen = HashSet::new();
+        let mut active = HashSet::new();
+        let mut fired = Vec::new();
+        for &info in &infos {
+            if info.pid == 0 || !seen.insert(info.pid) {
+                continue;
+            }
+            let cpu_percent = self
+                .prev_cpu
+                .get(&info.pid)
+                .filter(|_| elapsed > 0.0)
+                .map(|prev| (info.cpu_seconds - prev).max(0.0) / elapsed * 100.0);
+            for (idx, rule) in self.rules.iter().enumerate() {
+                let Some(value) = rule.check(info, cpu_percent) else {
+                    continue;
+                };
+                active.insert((info.pid, idx));
+                if !self.active.contains(&(info.pid, idx)) {
+                    fired.push(Alert {
+                        pid: info.pid,
+                        process: info.process_name.clone(),
+                        rule: rule.source.clone(),
+                        value: rule.format_value(value),
+                    });
+                }
+            }
+        }
+
+        self.prev_cpu = infos
+            .iter()
+            .filter(|i| i.pid != 0)
+            .map(|i| (i.pid, i.cpu_seconds))
+            .collect();
+        self.prev_at = Some(now);
+        self.active = active;
+        fired
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use std::time::Duration;
+
+    fn info(pid: u32, name: &str, mem: u64, cpu: f64) -> PortInfo {
+        PortInfo::listener(3000, pid, name)
+            .user("mark")
+            .usage(mem, cpu)
+    }
+
+    #[test]
+    fn parse_sizes() {
+        assert_eq!(parse_size("1GB"), Some(1024.0 * 1024.0 * 1024.0));
+        assert_eq!(parse_size("512m"), Some(512.0 * 1024.0 * 1024.0));
+        assert_eq!(parse_size("300K"), Some(300.0 * 1024.0));
+        assert_eq!(parse_size("4096"), Some(4096.0));
+        assert_eq!(parse_size("lots"), None);
+    }
+
+    #[test]
+    fn parse_rules() {
+        let rule = AlertRule::parse("process=node  mem>1GB").unwrap();
+        assert_eq!(rule.metric, Metric::Mem);
+        assert_eq!(rule.process.as_deref(), Some("node"));
+        assert_eq!(rule.source, "process=node mem>1GB");
+        assert_eq!(AlertRule::parse("cpu>80%").unwrap().threshold, 80.0);
+        assert!(AlertRule::parse("disk>1GB").is_err());
+        assert!(AlertRule::parse("mem<1GB").is_err());
+        assert!(AlertRule::parse("cpu>-5").is_err());
+        assert!(AlertRule::parse("process=node").is_err());
+    }
+
+    #[test]
+    fn mem_alert_is_edge_triggered() {
+        let mut m = AlertMonitor::new(vec![AlertRule::parse("mem>1MB").unwrap()]);
+        let t0 = Instant::now();
+        let big = info(10, "node", 2 * 1024 * 1024, 0.0);
+        let fired = m.update(std::slice::from_ref(&big), t0);
+        assert_eq!(fired.len(), 1);
+        assert_eq!(fired[0].describe(), "node (PID 10): 2 MB (mem>1MB)");
+        assert!(m.is_alerting(10));
+        // Still over: no repeat
+        assert!(m.update(std::slice::from_ref(&big), t0).is_empty());
+        // Drops below, then crosses again
+        m.update(&[info(10, "node", 1024, 0.0)], t0);
+        assert!(!m.is_alerting(10));
+        assert_eq!(m.update(&[big], t0).len(), 1);
+    }
+
+    #[test]
+    fn cpu_alert_uses_delta_between_refreshes() {
+        let mut m = AlertMonitor::new(vec![AlertRule::parse("cpu>50%").unwrap()]);
+        let t0 = Instant::now();
+        // No baseline yet: CPU rules can't fire
+        assert!(m.update(&[info(7, "python3", 0, 100.0)], t0).is_empty());
+        // 1.8s of CPU over 2s = 90%
+        let fired = m.update(&[info(7, "python3", 0, 101.8)], t0 + Duration::from_secs(2));
+        assert_eq!(fired.len(), 1);
+        assert_eq!(fired[0].value, "90%");
+    }
+
+    #[test]
+    fn process_filter_limits_rule() {
+        let mut m = AlertMonitor::new(vec![AlertRule::parse("process=node mem>1K").unwrap()]);
+        let fired = m.update(
+            &[
+                info(1, "postgres", 1 << 20, 0.0),
+                info(2, "node", 1 << 20, 0.0),
+            ],
+            Instant::now(),
+        );
+        assert_eq!(fired.len(), 1);
+        assert_eq!(fired[0].pid, 2);
+    }
+}
+
+// ── Property tests ───────────────────────────────────────────────────
+//
+// `cargo test --features fuzz`: no `--alert` text panics the parser, and
+// every rule it accepts prints back to text that parses the same.
+
+#[cfg(all(test, feature = "fuzz"))]
+mod fuzz {
+    use super::*;
+    use proptest::prelude::*;
+
+    /// `mem>512MB` or `cpu>80%`, with the threshold it stands for.
+    fn condition() -> impl Strategy<Value = (String, Metric, f64)> {
+        prop_oneof![
+            (
+                0u32..100_000,
+                prop_oneof![
+                    Just(("", 1.0)),
+                    Just(("K", 1024.0)),
+                    Just(("kb", 1024.0)),
+                    Just(("M", 1024.0 * 1024.0)),
+                    Just(("MB", 1024.0 * 1024.0)),
+                    Just(("g", 1024.0 * 1024.0 * 1024.0)),
+                    Just(("GB", 1024.0 * 1024.0 * 1024.0)),
+                ],
+            )
+                .prop_map(|(n, (unit, scale))| {
+                    (format!("mem>{}{}", n, unit), Metric::Mem, n as f64 * scale)
+                }),
+            (0u32..1000, any::<bool>()).prop_map(|(n, percent)| {
+                let sign = if percent { "%" } else { "" };
+                (format!("cpu>{}{}", n, sign), Metric::Cpu, n as f64)
+            }),
+        ]
+    }
+
+    proptest! {
+        #[test]
+        fn rule_never_panics(s in "\\PC*") {
+            let _ = AlertRule::parse(&s);
+            let _ = parse_size(&s);
+        }
+
+        #[test]
+        fn rules_round_trip(
+            (text, metric, threshold) in condition(),
+            process in prop::option::of("[A-Za-z0-9._-]{0,12}"),
+            process_first in any::<bool>(),
+            separator in "[ \\t]{1,3}",
+        ) {
+            let mut tokens = vec![text];
+            if let Some(ref p) = process {
+                let token = format!("process={}", p);
+                if process_first {
+                    tokens.insert(0, token);
+                } else {
+                    tokens.push(token);
+                }
+            }
+
+            let rule = AlertRule::parse(&tokens.join(&separator)).unwrap();
+            prop_assert_eq!(rule.metric, metric);
+            prop_assert_eq!(rule.threshold, threshold);
+            prop_assert_eq!(&rule.process, &process.map(|p| p.to_lowercase()));
+            prop_assert_eq!(AlertRule::parse(&rule.to_string()), Ok(rule));
+        }
+    }
+}
@@ -0,0 +1,247 @@
+use std::fs::{self, OpenOptions};
+use std::io::{self, Write};
+use std::path::{Path, PathBuf};
+use std::sync::OnceLock;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+use crate::clock::LocalTime;
+use crate::config;
+use crate::{current_user, json_escape, write_styled};
+
+// ── Action log ───────────────────────────────────────────────────────
+//
+// Every kill, signal and container stop or restart portview carries out
+// is appended to a per-user log, one tab-separated line each: when, who,
+// what was sent, to what, and how it went. `portview log` reads it back,
+// for the "who killed my server" question on shared boxes. Nothing is
+// ever rewritten; a line that fails to parse is skipped, not fatal.
+
+/// One action as logged.
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) struct Entry {
+    /// Seconds since the epoch.
+    pub(crate) at: u64,
+    /// The account that ran portview, with the sudo caller when there is one.
+    pub(crate) user: String,
+    /// `SIGTERM`, `docker stop`, `docker compose restart`.
+    pub(crate) action: String,
+    /// `PID 4242 (node :3000)`, `container web`.
+    pub(crate) target: String,
+    /// `ok`, or what went wrong.
+    pub(crate) outcome: String,
+}
+
+impl Entry {
+    fn line(&self) -> String {
+        let field = |s: &str| s.replace(['\t', '\n', '\r'], " ");
+        format!(
+            "{}\t{}\t{}\t{}\t{}",
+            self.at,
+            field(&self.user),
+            field(&self.action),
+            field(&self.target),
+            field(&self.outcome)
+        )
+    }
+
+    fn parse(line: &str) -> Option<Self> {
+        let mut fields = line.splitn(5, '\t');
+        Some(Self {
+            at: fields.next()?.parse().ok()?,
+            user: fields.next()?.to_string(),
+            action: fields.next()?.to_string(),
+            target: fields.next()?.to_string(),
+            outcome: fields.next()?.to_string(),
+        })
+    }
+
+    pub(crate) fn succeeded(&self) -> bool {
+        self.outcome == "ok"
+    }
+}
+
+/// `actions.log` in the per-user state directory.
+pub(crate) fn default_path() -> Option<PathBuf> {
+    config::user_state_dir().map(|d| d.join("actions.log"))
+}
+
+static PATH: OnceLock<PathBuf> = OnceLock::new();
+
+/// Log actions to `path` for the rest of the run. Until this is called
+/// nothing is written, so tests and `--fixture` runs leave no trace.
+pub(crate) fn enable(path: PathBuf) {
+    let _ = PATH.set(path);
+}
+
+/// Log one action if logging is on. A log that can't be written never
+/// stops the action itself.
+pub(crate) fn record(action: &str, target: &str, outcome: &str) {
+    let Some(path) = PATH.get() else {
+        return;
+    };
+    let entry = Entry {
+        at: SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map_or(0, |d| d.as_secs()),
+        user: acting_user(),
+        action: action.to_string(),
+        target: target.to_string(),
+        outcome: outcome.to_string(),
+    };
+    let _ = append(path, &entry);
+}
+
+/// `root (sudo: alice)` under sudo, so the log names the person.
+fn acting_user() -> String {
+    let user = current_user();
+    match std::env::var("SUDO_USER") {
+        Ok(by) if !by.is_empty() && by != user => format!("{} (sudo: {})", user, by),
+        _ => user,
+    }
+}
+
+pub(crate) fn append(path: &Path, entry: &Entry) -> io::Result<()> {
+    if let Some(dir) = path.parent().filter(|d| !d.as_os_str().is_empty()) {
+        fs::create_dir_all(dir)?;
+    }
+    let mut file = OpenOptions::new().create(true).append(true).open(path)?;
+    // One write per line, so two portviews appending at once don't interleave
+    file.write_all(format!("{}\n", entry.line()).as_bytes())
+}
+
+/// The last `count` entries, oldest first. A missing log is empty.
+pub(crate) fn read(path: &Path, count: usize) -> io::Result<Vec<Entry>> {
+    let text = match fs::read_to_string(path) {
+        Ok(text) => text,
+        Err(e) if e.kind() == io::ErrorKind::NotFound => return Ok(Vec::new()),
+        Err(e) => return Err(e),
+    };
+    let entries: Vec<Entry> = text.lines().filter_map(Entry::parse).collect();
+    let skip = entries.len().saturating_sub(count);
+    Ok(entries.into_iter().skip(skip).collect())
+}
+
+// ── Output ───────────────────────────────────────────────────────────
+
+fn local_time(at: u64) -> Option<LocalTime> {
+    LocalTime::from_system(UNIX_EPOCH + Duration::from_secs(at))
+}
+
+pub(crate) fn display_log(path: &Path, entries: &[Entry], use_color: bool) {
+    let mut out = io::stdout();
+    if entries.is_empty() {
+        write_styled(
+            &mut out,
+            &format!("No actions logged in {}.\n", path.display()),
+            "dimmed",
+            use_color,
+        );
+        return;
+    }
+    let user_w = entries.iter().map(|e| e.user.len()).max().unwrap_or(0);
+    let action_w = entries.iter().map(|e| e.action.len()).max().unwrap_or(0);
+    let target_w = entries.iter().map(|e| e.target.len()).max().unwrap_or(0);
+    for entry in entries {
+        let when = local_time(entry.at).map_or_else(|| entry.at.to_string(), |t| t.display());
+        let _ = write!(out, "  ");
+        write_styled(&mut out, &when, "dimmed", use_color);
+        let _ = write!(
+            out,
+            "  {:<uw$}  {:<aw$}  {:<tw$}  ",
+            entry.user,
+            entry.action,
+            entry.target,
+            uw = user_w,
+            aw = action_w,
+            tw = target_w
+        );
+        if entry.succeeded() {
+            write_styled(&mut out, "ok", "green", use_color);
+        } else {
+            write_styled(&mut out, &entry.outcome, "red", use_color);
+        }
+        let _ = writeln!(out);
+    }
+}
+
+pub(crate) fn log_json(entries: &[Entry]) -> String {
+    let items: Vec<String> = entries
+        .iter()
+        .map(|e| {
+            let time = local_time(e.at).map_or("null".to_string(), |t| {
+                format!("\"{}\"", t.rfc3339())
+            });
+            format!(
+                r#"{{"at":{},"time":{},"user":"{}","action":"{}","target":"{}","ok":{},"outcome":"{}"}}"#,
+                e.at,
+                time,
+                json_escape(&e.user),
+                json_escape(&e.action),
+                json_escape(&e.target),
+                e.succeeded(),
+                json_escape(&e.outcome)
+            )
+        })
+        .collect();
+    format!("[{}]", items.join(","))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn entry(at: u64, target: &str, outcome: &str) -> Entry {
+        Entry {
+            at,
+            user: "alice".to_string(),
+            action: "SIGTERM".to_string(),
+            target: target.to_string(),
+            outcome: outcome.to_string(),
+        }
+    }
+
+    #[test]
+    fn line_round_trip() {
+        let e = entry(1_700_000_000, "PID 4242 (node :3000)", "No such process");
+        assert_eq!(Entry::parse(&e.line()), Some(e));
+
+        let tabbed = entry(1, "container\tweb", "failed:\nboom");
+        let parsed = Entry::parse(&tabbed.line()).unwrap();
+        assert_eq!(parsed.target, "container web");
+        assert_eq!(parsed.outcome, "failed: boom");
+        assert_eq!(Entry::parse("garbage"), None);
+    }
+
+    #[test]
+    fn appends_and_reads_the_tail() {
+        let dir = std::env::temp_dir().join(format!("portview-audit-test-{}", std::process::id()));
+        let path = dir.join("state").join("actions.log");
+        assert!(read(&path, 10).unwrap().is_empty());
+        for at in 1..=3 {
+            append(&path, &entry(at, "PID 9", "ok")).unwrap();
+        }
+        fs::OpenOptions::new()
+            .append(true)
+            .open(&path)
+            .unwrap()
+            .write_all(b"not a line\n")
+            .unwrap();
+        let tail = read(&path, 2).unwrap();
+        assert_eq!(
+            tail.iter().map(|e| e.at).collect::<Vec<_>>(),
+            [2, 3],
+            "oldest first, junk skipped"
+        );
+        fs::remove_dir_all(dir).unwrap();
+    }
+
+    #[test]
+    fn json_shape() {
+        let json = log_json(&[entry(5, "container web", "ok")]);
+        assert!(json.starts_with(r#"[{"at":5,"time":"#));
+        assert!(json.ends_with(
+            r#""user":"alice","action":"SIGTERM","target":"container web","ok":true,"outcome":"ok"}]"#
+        ));
+        assert_eq!(log_json(&[]), "[]");
+    }
+}
@@ -0,0 +1,146 @@
+use std::net::IpAddr;
+
+use crate::{collector, PortInfo, TcpState};
+
+// ── Listen backlogs ──────────────────────────────────────────────────
+//
+// The kernel clamps a listen() backlog to net.core.somaxconn without
+// telling the server, so a backlog sitting exactly at the limit usually
+// means the server asked for more. Under load that shows up as dropped
+// or slow connections with nothing in the server's own logs.
+
+/// One TCP listener's accept queue, from sock_diag on Linux.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub(crate) struct ListenQueue {
+    pub(crate) port: u16,
+    pub(crate) addr: IpAddr,
+    /// Connections waiting for accept().
+    pub(crate) queued: u32,
+    /// The effective backlog, after the kernel's clamp.
+    pub(crate) backlog: u32,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub(crate) enum BacklogIssue {
+    /// The accept queue is full; new connections are being dropped.
+    Full,
+    /// The backlog equals net.core.somaxconn, so a larger one was cut.
+    Capped,
+}
+
+impl ListenQueue {
+    pub(crate) fn issue(&self, somaxconn: Option<u32>) -> Option<BacklogIssue> {
+        if self.backlog > 0 && self.queued >= self.backlog {
+            Some(BacklogIssue::Full)
+        } else if somaxconn == Some(self.backlog) {
+            Some(BacklogIssue::Capped)
+        } else {
+            None
+        }
+    }
+
+    /// `511 (2 waiting)`, then the issue if there is one.
+    pub(crate) fn describe(&self, somaxconn: Option<u32>) -> String {
+        let base = format!("{} ({} waiting)", self.backlog, self.queued);
+        match self.issue(somaxconn) {
+            Some(BacklogIssue::Full) => {
+                format!("{} — accept queue full, new connections are dropped", base)
+            }
+            Some(BacklogIssue::Capped) => format!(
+                "{} — capped by net.core.somaxconn; a larger listen() backlog was truncated",
+                base
+            ),
+            None => base,
+        }
+    }
+}
+
+/// The queue for a TCP listener row: same port, and the same address
+/// unless the kernel reported the row's family differently.
+fn find<'a>(queues: &'a [ListenQueue], info: &PortInfo) -> Option<&'a ListenQueue> {
+    let on_port = || queues.iter().filter(|q| q.port == info.port);
+    on_port()
+        .find(|q| q.addr == info.local_addr)
+        .or_else(|| on_port().next())
+}
+
+/// The `Backlog:` detail row for a TCP listener, when the backend reports
+/// accept queues.
+pub(crate) fn detail_row(info: &PortInfo) -> Option<String> {
+    if info.state != TcpState::Listen || !info.protocol.starts_with("TCP") || info.pid == 0 {
+        return None;
+    }
+    let backend = collector::active();
+    let queues = backend.listen_queues();
+    find(&queues, info).map(|q| q.describe(backend.somaxconn()))
+}
+
+/// Ports whose listeners have `issue`, sorted and without duplicates.
+pub(crate) fn flagged(
+    queues: &[ListenQueue],
+    somaxconn: Option<u32>,
+    issue: BacklogIssue,
+) -> Vec<u16> {
+    let mut ports: Vec<u16> = queues
+        .iter()
+        .filter(|q| q.issue(somaxconn) == Some(issue))
+        .map(|q| q.port)
+        .collect();
+    ports.sort_unstable();
+    ports.dedup();
+    ports
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::net::{Ipv4Addr, Ipv6Addr};
+
+    fn queue(port: u16, queued: u32, backlog: u32) -> ListenQueue {
+        ListenQueue {
+            port,
+            addr: IpAddr::V4(Ipv4Addr::UNSPECIFIED),
+            queued,
+            backlog,
+        }
+    }
+
+    #[test]
+    fn issues_and_descriptions() {
+        assert_eq!(queue(80, 0, 511).issue(Some(4096)), None);
+        assert_eq!(
+            queue(80, 0, 4096).issue(Some(4096)),
+            Some(BacklogIssue::Capped)
+        );
+        assert_eq!(
+            queue(80, 128, 128).issue(Some(128)),
+            Some(BacklogIssue::Full)
+        );
+        assert_eq!(queue(80, 0, 0).issue(None), None);
+        assert_eq!(queue(80, 3, 511).describe(Some(4096)), "511 (3 waiting)");
+        assert!(queue(80, 0, 128)
+            .describe(Some(128))
+            .contains("capped by net.core.somaxconn"));
+    }
+
+    #[test]
+    fn flagged_ports_are_sorted_and_unique() {
+        let mut v6 = queue(8080, 0, 128);
+        v6.addr = IpAddr::V6(Ipv6Addr::UNSPECIFIED);
+        let queues = [
+            queue(9000, 0, 128),
+            queue(8080, 0, 128),
+            v6,
+            queue(3000, 511, 511),
+        ];
+        assert_eq!(
+            flagged(&queues, Some(128), BacklogIssue::Capped),
+            vec![8080, 9000]
+        );
+        assert_eq!(flagged(&queues, Some(128), BacklogIssue::Full), vec![3000]);
+        let info = PortInfo::listener(8080, 42, "api")
+            .protocol("TCP6")
+            .addr(v6.addr);
+        assert_eq!(find(&queues, &info).map(|q| q.addr), Some(v6.addr));
+    }
+}
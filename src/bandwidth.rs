@@ -0,0 +1,169 @@
+use std::collections::HashMap;
+use std::time::Instant;
+
+// ── Data types ───────────────────────────────────────────────────────
+
+/// Cumulative byte counters for one TCP socket, as reported by the kernel.
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) struct SocketTraffic {
+    /// Stable socket identity (the inode on Linux).
+    pub(crate) key: u64,
+    pub(crate) pid: u32,
+    pub(crate) rx_bytes: u64,
+    pub(crate) tx_bytes: u64,
+}
+
+/// Per-process throughput in bytes per second.
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub(crate) struct Rate {
+    pub(crate) rx: u64,
+    pub(crate) tx: u64,
+}
+
+impl Rate {
+    pub(crate) fn total(self) -> u64 {
+        self.rx.saturating_add(self.tx)
+    }
+}
+
+// ── Sampling ─────────────────────────────────────────────────────────
+
+/// Turns successive counter snapshots into per-process rates.
+///
+/// Only sockets present in both snapshots contribute, so a connection that
+/// opened mid-interval doesn't show up as a burst of its lifetime total.
+#[derive(Default)]
+pub(crate) struct BandwidthSampler {
+    prev: HashMap<u64, (u64, u64)>,
+    prev_at: Option<Instant>,
+}
+
+impl BandwidthSampler {
+    pub(crate) fn sample(&mut self, sockets: &[SocketTraffic], now: Instant) -> HashMap<u32, Rate> {
+        let mut rates: HashMap<u32, Rate> = HashMap::new();
+        let elapsed = self
+            .prev_at
+            .map(|at| now.saturating_duration_since(at).as_secs_f64())
+            .unwrap_or(0.0);
+
+        if elapsed > 0.0 {
+            for sock in sockets {
+                let Some(&(rx, tx)) = self.prev.get(&sock.key) else {
+                    continue;
+                };
+                let rate = rates.entry(sock.pid).or_default();
+                rate.rx += (sock.rx_bytes.saturating_sub(rx) as f64 / elapsed) as u64;
+                rate.tx += (sock.tx_bytes.saturating_sub(tx) as f64 / elapsed) as u64;
+            }
+        }
+
+        self.prev = sockets
+            .iter()
+            .map(|s| (s.key, (s.rx_bytes, s.tx_bytes)))
+            .collect();
+        self.prev_at = Some(now);
+        rates
+    }
+}
+
+// ── Formatting ───────────────────────────────────────────────────────
+
+/// Compact rate for a narrow column: `512`, `12K`, `1.5M`, `2.0G` (per second).
+pub(crate) fn format_rate_short(bytes_per_sec: u64) -> String {
+    const KB: f64 = 1024.0;
+    const MB: f64 = 1024.0 * KB;
+    const GB: f64 = 1024.0 * MB;
+    let b = bytes_per_sec as f64;
+    if b >= GB {
+        format!("{:.1}G", b / GB)
+    } else if b >= 10.0 * MB {
+        format!("{:.0}M", b / MB)
+    } else if b >= MB {
+        format!("{:.1}M", b / MB)
+    } else if b >= KB {
+        format!("{:.0}K", b / KB)
+    } else {
+        format!("{}", bytes_per_sec)
+    }
+}
+
+/// `↓12K ↑3K`, or `-` when the process moved no traffic.
+pub(crate) fn format_rate_pair(rate: Option<Rate>) -> String {
+    match rate {
+        Some(r) if r.total() > 0 => format!(
+            "\u{2193}{} \u{2191}{}",
+            format_rate_short(r.rx),
+            format_rate_short(r.tx)
+        ),
+        _ => "-".to_string(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::time::Duration;
+
+    fn sock(key: u64, pid: u32, rx: u64, tx: u64) -> SocketTraffic {
+        SocketTraffic {
+            key,
+            pid,
+            rx_bytes: rx,
+            tx_bytes: tx,
+        }
+    }
+
+    #[test]
+    fn sampler_first_sample_is_empty() {
+        let mut s = BandwidthSampler::default();
+        assert!(s
+            .sample(&[sock(1, 10, 500, 500)], Instant::now())
+            .is_empty());
+    }
+
+    #[test]
+    fn sampler_sums_sockets_per_pid() {
+        let mut s = BandwidthSampler::default();
+        let t0 = Instant::now();
+        s.sample(&[sock(1, 10, 1000, 0), sock(2, 10, 0, 0)], t0);
+        let rates = s.sample(
+            &[
+                sock(1, 10, 3000, 400),
+                sock(2, 10, 1000, 0),
+                // New socket: ignored until it has a baseline
+                sock(3, 10, 1_000_000, 0),
+            ],
+            t0 + Duration::from_secs(2),
+        );
+        assert_eq!(rates[&10], Rate { rx: 1500, tx: 200 });
+    }
+
+    #[test]
+    fn sampler_tolerates_counter_reset() {
+        let mut s = BandwidthSampler::default();
+        let t0 = Instant::now();
+        s.sample(&[sock(1, 10, 1000, 1000)], t0);
+        let rates = s.sample(&[sock(1, 10, 10, 10)], t0 + Duration::from_secs(1));
+        assert_eq!(rates[&10].total(), 0);
+    }
+
+    #[test]
+    fn format_rate_short_units() {
+        assert_eq!(format_rate_short(0), "0");
+        assert_eq!(format_rate_short(512), "512");
+        assert_eq!(format_rate_short(12 * 1024), "12K");
+        assert_eq!(format_rate_short(1536 * 1024), "1.5M");
+        assert_eq!(format_rate_short(20 * 1024 * 1024), "20M");
+        assert_eq!(format_rate_short(2 * 1024 * 1024 * 1024), "2.0G");
+    }
+
+    #[test]
+    fn format_rate_pair_idle_is_dash() {
+        assert_eq!(format_rate_pair(None), "-");
+        assert_eq!(format_rate_pair(Some(Rate::default())), "-");
+        assert_eq!(
+            format_rate_pair(Some(Rate { rx: 2048, tx: 10 })),
+            "\u{2193}2K \u{2191}10"
+        );
+    }
+}
@@ -0,0 +1,356 @@
+use crate::suggest::parse_port_range;
+use crate::verify::Verdict;
+use crate::{json_escape, PortInfo};
+
+// ── Batch kill ───────────────────────────────────────────────────────
+//
+// `portview kill 3000-3010` and `portview kill --filter "process=node"`
+// resolve every match up front, print the plan (one line per process,
+// with all the ports it holds) and kill the lot after one confirmation.
+// A process holding several matching ports is signalled once.
+
+/// A `--filter` rule such as `process=node proto=tcp`. Every condition
+/// given must match.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub(crate) struct KillFilter {
+    ports: Option<(u16, u16)>,
+    /// Lowercased substring of the process name.
+    process: Option<String>,
+    protocol: Option<String>,
+    user: Option<String>,
+}
+
+impl KillFilter {
+    /// Parse `key=value` pairs separated by spaces or commas. Keys: `port`
+    /// (a number or range like `3000-3010`), `process`, `proto` and `user`.
+    pub(crate) fn parse(src: &str) -> Result<Self, String> {
+        let mut filter = KillFilter::default();
+        let tokens: Vec<&str> = src
+            .split(|c: char| c.is_whitespace() || c == ',')
+            .filter(|t| !t.is_empty())
+            .collect();
+        if tokens.is_empty() {
+            return Err("empty kill filter (try process=node)".to_string());
+        }
+        for token in tokens {
+            let (key, value) = token
+                .split_once('=')
+                .ok_or_else(|| format!("expected key=value in kill filter, got '{}'", token))?;
+            match key {
+                "port" => {
+                    let range = parse_port_range(value)
+                        .ok_or_else(|| format!("invalid port '{}' in kill filter", value))?;
+                    filter.ports = Some(range);
+                }
+                "process" => filter.process = Some(value.to_lowercase()),
+                "proto" | "protocol" => filter.protocol = Some(value.to_uppercase()),
+                "user" => filter.user = Some(value.to_string()),
+                _ => {
+                    return Err(format!(
+                        "unknown key '{}' in kill filter (use port, process, proto or user)",
+                        key
+                    ))
+                }
+            }
+        }
+        Ok(filter)
+    }
+
+    pub(crate) fn matches(&self, info: &PortInfo) -> bool {
+        self.ports
+            .is_none_or(|(lo, hi)| (lo..=hi).contains(&info.port))
+            && self
+                .process
+                .as_ref()
+                .is_none_or(|p| info.process_name.to_lowercase().contains(p))
+            && self
+                .protocol
+                .as_ref()
+                .is_none_or(|p| info.protocol.starts_with(p.as_str()))
+            && self.user.as_ref().is_none_or(|u| info.user == *u)
+    }
+}
+
+/// Writes the filter back in the form [`KillFilter::parse`] reads.
+impl std::fmt::Display for KillFilter {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let mut tokens = Vec::new();
+        match self.ports {
+            Some((lo, hi)) if lo == hi => tokens.push(format!("port={}", lo)),
+            Some((lo, hi)) => tokens.push(format!("port={}-{}", lo, hi)),
+            None => {}
+        }
+        if let Some(ref p) = self.process {
+            tokens.push(format!("process={}", p));
+        }
+        if let Some(ref p) = self.protocol {
+            tokens.push(format!("proto={}", p));
+        }
+        if let Some(ref u) = self.user {
+            tokens.push(format!("user={}", u));
+        }
+        write!(f, "{}", tokens.join(" "))
+    }
+}
+
+/// One process to signal, with every matching port it holds.
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) struct Target {
+    pub(crate) pid: u32,
+    pub(crate) process: String,
+    pub(crate) ports: Vec<u16>,
+}
+
+impl Target {
+    /// `pid`, named `process`, with every port it holds among `rows`.
+    pub(crate) fn holding<'a>(
+        pid: u32,
+        process: &str,
+        rows: impl IntoIterator<Item = &'a PortInfo>,
+    ) -> Self {
+        let mut ports: Vec<u16> = rows
+            .into_iter()
+            .filter(|i| i.pid == pid)
+            .map(|i| i.port)
+            .collect();
+        ports.sort_unstable();
+        ports.dedup();
+        Self {
+            pid,
+            process: process.to_string(),
+            ports,
+        }
+    }
+}
+
+/// The processes behind the rows in `range` that pass `filter`, in port
+/// order. Rows without a PID (containers) can't be signalled and are left
+/// out.
+pub(crate) fn plan(
+    infos: &[PortInfo],
+    range: Option<(u16, u16)>,
+    filter: Option<&KillFilter>,
+) -> Vec<Target> {
+    let mut rows: Vec<&PortInfo> = infos
+        .iter()
+        .filter(|i| i.pid != 0)
+        .filter(|i| range.is_none_or(|(lo, hi)| (lo..=hi).contains(&i.port)))
+        .filter(|i| filter.is_none_or(|f| f.matches(i)))
+        .collect();
+    rows.sort_by_key(|i| i.port);
+    let mut targets: Vec<Target> = Vec::new();
+    for info in rows {
+        match targets.iter_mut().find(|t| t.pid == info.pid) {
+            Some(target) if !target.ports.contains(&info.port) => target.ports.push(info.port),
+            Some(_) => {}
+            None => targets.push(Target {
+                pid: info.pid,
+                process: info.process_name.clone(),
+                ports: vec![info.port],
+            }),
+        }
+    }
+    targets
+}
+
+/// What happened to one target.
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) enum Outcome {
+    Sent,
+    /// `--confirm-force` and the PID wasn't typed back.
+    Skipped,
+    Failed(String),
+}
+
+impl Outcome {
+    fn status(&self) -> &'static str {
+        match self {
+            Outcome::Sent => "sent",
+            Outcome::Skipped => "skipped",
+            Outcome::Failed(_) => "failed",
+        }
+    }
+}
+
+/// `{"ok":…,"signal":"SIGTERM","targets":[…]}`, one entry per process.
+/// `exited` and `port_free` come from `verdicts` and are null for
+/// processes that weren't checked.
+pub(crate) fn results_json(
+    signal: &str,
+    results: &[(Target, Outcome)],
+    verdicts: &[(u32, &Verdict)],
+) -> String {
+    let ok = results.iter().all(|(_, o)| *o == Outcome::Sent);
+    let items: Vec<String> = results
+        .iter()
+        .map(|(target, outcome)| {
+            let ports: Vec<String> = target.ports.iter().map(|p| p.to_string()).collect();
+            let error = match outcome {
+                Outcome::Failed(err) => format!("\"{}\"", json_escape(err)),
+                _ => "null".to_string(),
+            };
+            let verdict = verdicts
+                .iter()
+                .find(|(pid, _)| *pid == target.pid)
+                .map(|(_, v)| v);
+            let exited = match verdict.and_then(|v| v.exited) {
+                Some(exited) => exited.to_string(),
+                None => "null".to_string(),
+            };
+            let port_free = match verdict {
+                Some(v) => v.still_bound.is_empty().to_string(),
+                None => "null".to_string(),
+            };
+            format!(
+                r#"{{"pid":{},"process":"{}","ports":[{}],"status":"{}","error":{},"exited":{},"port_free":{}}}"#,
+                target.pid,
+                json_escape(&target.process),
+                ports.join(","),
+                outcome.status(),
+                error,
+                exited,
+                port_free
+            )
+        })
+        .collect();
+    format!(
+        r#"{{"ok":{},"signal":"{}","targets":[{}]}}"#,
+        ok,
+        json_escape(signal),
+        items.join(",")
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use std::net::{IpAddr, Ipv4Addr};
+
+    fn row(port: u16, pid: u32, name: &str) -> PortInfo {
+        PortInfo::listener(port, pid, name).addr(IpAddr::V4(Ipv4Addr::LOCALHOST))
+    }
+
+    #[test]
+    fn filter_rules() {
+        let filter = KillFilter::parse("process=Node, port=3000-3005").unwrap();
+        assert!(filter.matches(&row(3001, 1, "node")));
+        assert!(!filter.matches(&row(3006, 1, "node")));
+        assert!(!filter.matches(&row(3001, 1, "python3")));
+        assert!(KillFilter::parse("user=dev proto=udp")
+            .unwrap()
+            .matches(&row(53, 1, "dnsmasq").protocol("UDP")));
+        assert!(KillFilter::parse("").is_err());
+        assert!(KillFilter::parse("node").is_err());
+        assert!(KillFilter::parse("state=open")
+            .unwrap_err()
+            .contains("unknown key"));
+    }
+
+    #[test]
+    fn plan_groups_ports_by_process() {
+        let infos = vec![
+            row(3002, 7, "node"),
+            row(3000, 7, "node"),
+            row(3001, 8, "python3"),
+            row(3003, 0, "docker"),
+            row(4000, 9, "node"),
+        ];
+        let targets = plan(&infos, Some((3000, 3010)), None);
+        assert_eq!(
+            targets
+                .iter()
+                .map(|t| (t.pid, t.ports.clone()))
+                .collect::<Vec<_>>(),
+            vec![(7, vec![3000, 3002]), (8, vec![3001])]
+        );
+        let filter = KillFilter::parse("process=node").unwrap();
+        assert_eq!(plan(&infos, None, Some(&filter)).len(), 2);
+    }
+
+    #[test]
+    fn results_shape() {
+        let target = Target {
+            pid: 7,
+            process: "node".to_string(),
+            ports: vec![3000, 3002],
+        };
+        let other = Target {
+            pid: 8,
+            ..target.clone()
+        };
+        let verdict = Verdict {
+            exited: Some(true),
+            still_bound: vec![],
+            time_wait: 0,
+        };
+        let json = results_json(
+            "SIGTERM",
+            &[
+                (target, Outcome::Sent),
+                (
+                    other,
+                    Outcome::Failed("Operation not permitted".to_string()),
+                ),
+            ],
+            &[(7, &verdict)],
+        );
+        assert_eq!(
+            json,
+            r#"{"ok":false,"signal":"SIGTERM","targets":[{"pid":7,"process":"node","ports":[3000,3002],"status":"sent","error":null,"exited":true,"port_free":true},{"pid":8,"process":"node","ports":[3000,3002],"status":"failed","error":"Operation not permitted","exited":null,"port_free":null}]}"#
+        );
+    }
+}
+
+// ── Property tests ───────────────────────────────────────────────────
+//
+// `cargo test --features fuzz`: no `--filter` text panics the parser, and
+// every filter it accepts prints back to text that parses the same.
+
+#[cfg(all(test, feature = "fuzz"))]
+mod fuzz {
+    use super::*;
+    use proptest::prelude::*;
+
+    fn port_range() -> impl Strategy<Value = (u16, u16)> {
+        (1u16..=u16::MAX).prop_flat_map(|lo| (Just(lo), lo..=u16::MAX))
+    }
+
+    proptest! {
+        #[test]
+        fn filter_never_panics(s in "\\PC*") {
+            let _ = KillFilter::parse(&s);
+        }
+
+        #[test]
+        fn filters_round_trip(
+            ports in prop::option::of(port_range()),
+            process in prop::option::of("[A-Za-z0-9._-]{0,12}"),
+            protocol in prop::option::of("[A-Za-z0-9]{1,5}"),
+            user in prop::option::of("[A-Za-z0-9_]{1,12}"),
+            separator in "[ \\t,]{1,3}",
+        ) {
+            let mut tokens = Vec::new();
+            if let Some((lo, hi)) = ports {
+                tokens.push(format!("port={}-{}", lo, hi));
+            }
+            if let Some(ref p) = process {
+                tokens.push(format!("process={}", p));
+            }
+            if let Some(ref p) = protocol {
+                tokens.push(format!("proto={}", p));
+            }
+            if let Some(ref u) = user {
+                tokens.push(format!("user={}", u));
+            }
+            prop_assume!(!tokens.is_empty());
+
+            let filter = KillFilter::parse(&tokens.join(&separator)).unwrap();
+            prop_assert_eq!(filter.ports, ports);
+            prop_assert_eq!(&filter.process, &process.map(|p| p.to_lowercase()));
+            prop_assert_eq!(&filter.protocol, &protocol.map(|p| p.to_uppercase()));
+            prop_assert_eq!(&filter.user, &user);
+            prop_assert_eq!(KillFilter::parse(&filter.to_string()), Ok(filter));
+        }
+    }
+}
@@ -0,0 +1,223 @@
+use std::collections::HashMap;
+
+use crate::{format_bytes, format_uptime, write_styled, PortInfo};
+
+// ── Port cards ───────────────────────────────────────────────────────
+//
+// `portview watch 3000 5432 6379` draws one small card per port instead
+// of a table, for a corner terminal that keeps an eye on a dev stack:
+// whether the port is up, who holds it, and when that last changed.
+//
+//     ╭ 3000 ✓ up ─────────────────╮ ╭ 5432 ✗ down ───────────────╮
+//     │ node (PID 4242)            │ │ nothing listening          │
+//     │ alice · 2h 5m · 48 MB      │ │                            │
+//     │ changed 14:02:11           │ │ unchanged since 13:58:40   │
+//     ╰────────────────────────────╯ ╰────────────────────────────╯
+
+/// Columns inside a card's border.
+const INNER: usize = 28;
+/// A card with its border, and the gap to the next one.
+const CARD_WIDTH: usize = INNER + 2;
+const GAP: usize = 1;
+
+/// One port as of the latest refresh.
+#[derive(Debug, Clone)]
+pub(crate) struct Card {
+    pub(crate) port: u16,
+    /// One row per process holding the port, lowest PID first.
+    pub(crate) holders: Vec<PortInfo>,
+    /// When the holders last changed, or when watching began.
+    pub(crate) since: String,
+    /// Whether `since` is an observed change rather than the start.
+    pub(crate) changed: bool,
+}
+
+/// Remembers who held each port, to date the last change.
+#[derive(Default)]
+pub(crate) struct CardTracker {
+    last: HashMap<u16, (Vec<u32>, String, bool)>,
+}
+
+impl CardTracker {
+    /// Cards for `ports` in the order given. `now` is the wall-clock time
+    /// to record for a change seen in this refresh.
+    pub(crate) fn update(&mut self, ports: &[u16], infos: &[PortInfo], now: &str) -> Vec<Card> {
+        ports
+            .iter()
+            .map(|&port| {
+                let mut holders: Vec<PortInfo> =
+                    infos.iter().filter(|i| i.port == port).cloned().collect();
+                holders.sort_by_key(|i| i.pid);
+                holders.dedup_by_key(|i| i.pid);
+                let pids: Vec<u32> = holders.iter().map(|i| i.pid).collect();
+                let (_, since, changed) = self
+                    .last
+                    .entry(port)
+                    .and_modify(|(last, since, changed)| {
+                        if *last != pids {
+                            *last = pids.clone();
+                            *since = now.to_string();
+                            *changed = true;
+                        }
+                    })
+                    .or_insert_with(|| (pids.clone(), now.to_string(), false));
+                Card {
+                    port,
+                    holders,
+                    since: since.clone(),
+                    changed: *changed,
+                }
+            })
+            .collect()
+    }
+}
+
+/// The cards side by side, as many to a row as `width` allows.
+pub(crate) fn render(cards: &[Card], width: usize, use_color: bool) -> String {
+    let per_row = ((width + GAP) / (CARD_WIDTH + GAP)).max(1);
+    let mut out = Vec::new();
+    for row in cards.chunks(per_row) {
+        let drawn: Vec<Vec<Vec<u8>>> = row.iter().map(|c| card_lines(c, use_color)).collect();
+        for line in 0..drawn[0].len() {
+            for (i, card) in drawn.iter().enumerate() {
+                if i > 0 {
+                    out.extend_from_slice(&b" ".repeat(GAP));
+                }
+                out.extend_from_slice(&card[line]);
+            }
+            out.push(b'\n');
+        }
+    }
+    String::from_utf8_lossy(&out).into_owned()
+}
+
+fn card_lines(card: &Card, use_color: bool) -> Vec<Vec<u8>> {
+    let (mark, status, color) = if card.holders.is_empty() {
+        ("\u{2717}", "down", "red")
+    } else {
+        ("\u{2713}", "up", "green")
+    };
+    let body = match card.holders.first() {
+        None => ["nothing listening".to_string(), String::new()],
+        Some(first) => {
+            let more = match card.holders.len() - 1 {
+                0 => String::new(),
+                n => format!(" +{}", n),
+            };
+            let stats = if first.pid == 0 {
+                first.user.clone()
+            } else {
+                format!(
+                    "{} \u{b7} {} \u{b7} {}",
+                    first.user,
+                    format_uptime(first.start_time).trim(),
+                    format_bytes(first.memory_bytes).trim()
+                )
+            };
+            let holder = if first.pid == 0 {
+                format!("{} (container){}", first.process_name, more)
+            } else {
+                format!("{} (PID {}){}", first.process_name, first.pid, more)
+            };
+            [holder, stats]
+        }
+    };
+    let since = if card.changed {
+        format!("changed {}", card.since)
+    } else {
+        format!("unchanged since {}", card.since)
+    };
+
+    let mut lines = Vec::new();
+    let mut top = Vec::new();
+    write_styled(&mut top, "\u{256d} ", "dimmed", use_color);
+    let label = format!("{} {} ", card.port, mark);
+    top.extend_from_slice(label.as_bytes());
+    write_styled(&mut top, status, color, use_color);
+    let used = 2 + label.chars().count() + status.len();
+    let fill = format!(
+        " {}\u{256e}",
+        "\u{2500}".repeat(CARD_WIDTH.saturating_sub(used + 2))
+    );
+    write_styled(&mut top, &fill, "dimmed", use_color);
+    lines.push(top);
+
+    for text in body.iter().chain([&since]) {
+        let text = fit(text, INNER - 2);
+        let mut line = Vec::new();
+        write_styled(&mut line, "\u{2502}", "dimmed", use_color);
+        line.extend_from_slice(
+            format!(" {}{} ", text, " ".repeat(INNER - 2 - text.chars().count())).as_bytes(),
+        );
+        write_styled(&mut line, "\u{2502}", "dimmed", use_color);
+        lines.push(line);
+    }
+
+    let mut bottom = Vec::new();
+    write_styled(
+        &mut bottom,
+        &format!("\u{2570}{}\u{256f}", "\u{2500}".repeat(INNER)),
+        "dimmed",
+        use_color,
+    );
+    lines.push(bottom);
+    lines
+}
+
+/// `text` cut to `width` characters, ending in `…` when shortened.
+fn fit(text: &str, width: usize) -> String {
+    if text.chars().count() <= width {
+        return text.to_string();
+    }
+    let mut cut: String = text.chars().take(width.saturating_sub(1)).collect();
+    cut.push('\u{2026}');
+    cut
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn listener(port: u16, pid: u32, process: &str) -> PortInfo {
+        PortInfo::listener(port, pid, process)
+            .user("alice")
+            .usage(48 * 1024 * 1024, 0.0)
+    }
+
+    #[test]
+    fn tracker_dates_owner_changes() {
+        let mut tracker = CardTracker::default();
+        let cards = tracker.update(&[3000, 5432], &[listener(3000, 42, "node")], "13:58:40");
+        assert_eq!(cards[0].holders.len(), 1);
+        assert!(cards[1].holders.is_empty());
+        assert!(!cards[0].changed && cards[0].since == "13:58:40");
+
+        // Same owner: nothing changes, even on another socket
+        let mut v6 = listener(3000, 42, "node");
+        v6.protocol = "TCP6".to_string();
+        let cards = tracker.update(&[3000, 5432], &[listener(3000, 42, "node"), v6], "13:59:00");
+        assert_eq!(cards[0].holders.len(), 1);
+        assert!(!cards[0].changed);
+
+        // node restarted under a new PID, postgres came up
+        let infos = [listener(3000, 43, "node"), listener(5432, 7, "postgres")];
+        let cards = tracker.update(&[3000, 5432], &infos, "14:02:11");
+        assert!(cards.iter().all(|c| c.changed && c.since == "14:02:11"));
+    }
+
+    #[test]
+    fn cards_wrap_to_the_width() {
+        let mut tracker = CardTracker::default();
+        let cards = tracker.update(&[3000, 5432], &[listener(3000, 42, "node")], "13:58:40");
+        assert_eq!(
+            render(&cards, 80, false),
+            "╭ 3000 ✓ up ─────────────────╮ ╭ 5432 ✗ down ───────────────╮\n\
+             │ node (PID 42)              │ │ nothing listening          │\n\
+             │ alice · - · 48 MB          │ │                            │\n\
+             │ unchanged since 13:58:40   │ │ unchanged since 13:58:40   │\n\
+             ╰────────────────────────────╯ ╰────────────────────────────╯\n"
+        );
+        let narrow = render(&cards, 60, false);
+        assert_eq!(narrow.lines().count(), 10, "one card per row");
+    }
+}
@@ -0,0 +1,104 @@
+use std::io::{self, Write};
+use std::process::{Command, Stdio};
+
+// ── Clipboard ────────────────────────────────────────────────────────
+//
+// Local sessions go through the platform's clipboard tool. Over SSH, or
+// when no tool is installed, the text is sent as an OSC 52 escape so the
+// terminal emulator on the user's machine sets its clipboard instead.
+
+/// Copy `text`, returning how it was delivered for the status line.
+pub(crate) fn copy(text: &str) -> io::Result<&'static str> {
+    let remote =
+        std::env::var_os("SSH_CONNECTION").is_some() || std::env::var_os("SSH_TTY").is_some();
+    if !remote {
+        for (program, args) in helpers() {
+            if pipe_to(program, args, text) {
+                return Ok(program);
+            }
+        }
+    }
+    write_osc52(&mut io::stdout(), text)?;
+    Ok("OSC 52")
+}
+
+fn helpers() -> Vec<(&'static str, &'static [&'static str])> {
+    let mut helpers: Vec<(&'static str, &'static [&'static str])> = Vec::new();
+    if cfg!(target_os = "macos") {
+        helpers.push(("pbcopy", &[]));
+    } else if cfg!(windows) {
+        helpers.push(("clip", &[]));
+    } else {
+        if std::env::var_os("WAYLAND_DISPLAY").is_some() {
+            helpers.push(("wl-copy", &[]));
+        }
+        if std::env::var_os("DISPLAY").is_some() {
+            helpers.push(("xclip", &["-selection", "clipboard"]));
+            helpers.push(("xsel", &["--clipboard", "--input"]));
+        }
+    }
+    helpers
+}
+
+fn pipe_to(program: &str, args: &[&str], text: &str) -> bool {
+    let child = Command::new(program)
+        .args(args)
+        .stdin(Stdio::piped())
+        .stdout(Stdio::null())
+        .stderr(Stdio::null())
+        .spawn();
+    let Ok(mut child) = child else {
+        return false;
+    };
+    let wrote = child
+        .stdin
+        .take()
+        .is_some_and(|mut stdin| stdin.write_all(text.as_bytes()).is_ok());
+    child.wait().is_ok_and(|status| status.success()) && wrote
+}
+
+/// Inside tmux the sequence must be wrapped in a passthrough escape.
+fn write_osc52(out: &mut impl Write, text: &str) -> io::Result<()> {
+    let seq = format!("\x1b]52;c;{}\x07", base64(text.as_bytes()));
+    if std::env::var_os("TMUX").is_some() {
+        write!(out, "\x1bPtmux;{}\x1b\\", seq.replace('\x1b', "\x1b\x1b"))?;
+    } else {
+        write!(out, "{}", seq)?;
+    }
+    out.flush()
+}
+
+fn base64(data: &[u8]) -> String {
+    const ALPHABET: &[u8; 64] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789+/";
+    let mut out = String::with_capacity(data.len().div_ceil(3) * 4);
+    for chunk in data.chunks(3) {
+        let b = [
+            chunk[0],
+            chunk.get(1).copied().unwrap_or(0),
+            chunk.get(2).copied().unwrap_or(0),
+        ];
+        let n = (u32::from(b[0]) << 16) | (u32::from(b[1]) << 8) | u32::from(b[2]);
+        for (i, shift) in [18, 12, 6, 0].into_iter().enumerate() {
+            if i <= chunk.len() {
+                out.push(ALPHABET[((n >> shift) & 63) as usize] as char);
+            } else {
+                out.push('=');
+            }
+        }
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn base64_pads() {
+        assert_eq!(base64(b""), "");
+        assert_eq!(base64(b"f"), "Zg==");
+        assert_eq!(base64(b"fo"), "Zm8=");
+        assert_eq!(base64(b"foo"), "Zm9v");
+        assert_eq!(base64(b"node server.js"), "bm9kZSBzZXJ2ZXIuanM=");
+    }
+}
@@ -0,0 +1,232 @@
+use std::sync::OnceLock;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+// ── Timestamp style ──────────────────────────────────────────────────
+
+/// How process start times are shown: `--timestamps relative|absolute`.
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub(crate) enum TimestampStyle {
+    /// `3h 12m` of uptime.
+    #[default]
+    Relative,
+    /// `2026-10-16 14:03:22` in the local timezone.
+    Absolute,
+}
+
+impl TimestampStyle {
+    pub(crate) fn parse(s: &str) -> Result<Self, String> {
+        match s {
+            "relative" => Ok(TimestampStyle::Relative),
+            "absolute" => Ok(TimestampStyle::Absolute),
+            _ => Err(format!(
+                "unknown timestamp style '{}' (expected relative or absolute)",
+                s
+            )),
+        }
+    }
+}
+
+static ACTIVE: OnceLock<TimestampStyle> = OnceLock::new();
+
+/// Set the style for the rest of the run; the first call wins.
+pub(crate) fn install(style: TimestampStyle) {
+    let _ = ACTIVE.set(style);
+}
+
+pub(crate) fn active() -> TimestampStyle {
+    ACTIVE.get().copied().unwrap_or_default()
+}
+
+// ── Local time ───────────────────────────────────────────────────────
+
+/// A wall-clock time and its offset from UTC.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub(crate) struct LocalTime {
+    year: i64,
+    month: u32,
+    day: u32,
+    hour: u32,
+    minute: u32,
+    second: u32,
+    offset_secs: i64,
+}
+
+impl LocalTime {
+    /// `t` in the system timezone; `None` before 1970 or if the OS
+    /// conversion fails.
+    pub(crate) fn from_system(t: SystemTime) -> Option<Self> {
+        let secs = i64::try_from(t.duration_since(UNIX_EPOCH).ok()?.as_secs()).ok()?;
+        local_time(secs)
+    }
+
+    #[cfg(any(windows, test))]
+    fn utc(secs: i64) -> Self {
+        let (year, month, day) = civil_from_days(secs.div_euclid(86_400));
+        let tod = secs.rem_euclid(86_400);
+        Self {
+            year,
+            month,
+            day,
+            hour: (tod / 3600) as u32,
+            minute: ((tod / 60) % 60) as u32,
+            second: (tod % 60) as u32,
+            offset_secs: 0,
+        }
+    }
+
+    /// `2026-10-16 14:03:22`, for tables and detail views.
+    pub(crate) fn display(&self) -> String {
+        format!(
+            "{:04}-{:02}-{:02} {:02}:{:02}:{:02}",
+            self.year, self.month, self.day, self.hour, self.minute, self.second
+        )
+    }
+
+    /// `20261016-140322`, for file names.
+    pub(crate) fn file_stamp(&self) -> String {
+        format!(
+            "{:04}{:02}{:02}-{:02}{:02}{:02}",
+            self.year, self.month, self.day, self.hour, self.minute, self.second
+        )
+    }
+
+    /// `2026-10-16T14:03:22+02:00`, for JSON.
+    pub(crate) fn rfc3339(&self) -> String {
+        let sign = if self.offset_secs < 0 { '-' } else { '+' };
+        let offset = self.offset_secs.abs();
+        format!(
+            "{:04}-{:02}-{:02}T{:02}:{:02}:{:02}{}{:02}:{:02}",
+            self.year,
+            self.month,
+            self.day,
+            self.hour,
+            self.minute,
+            self.second,
+            sign,
+            offset / 3600,
+            (offset / 60) % 60
+        )
+    }
+}
+
+/// Days since 1970-01-01 to a proleptic Gregorian date (Howard Hinnant's
+/// `civil_from_days`).
+#[cfg(any(windows, test))]
+fn civil_from_days(days: i64) -> (i64, u32, u32) {
+    let z = days + 719_468;
+    let era = z.div_euclid(146_097);
+    let doe = z.rem_euclid(146_097);
+    let yoe = (doe - doe / 1460 + doe / 36_524 - doe / 146_096) / 365;
+    let doy = doe - (365 * yoe + yoe / 4 - yoe / 100);
+    let mp = (5 * doy + 2) / 153;
+    let day = (doy - (153 * mp + 2) / 5 + 1) as u32;
+    let month = if mp < 10 { mp + 3 } else { mp - 9 } as u32;
+    let year = yoe + era * 400 + i64::from(month <= 2);
+    (year, month, day)
+}
+
+/// The inverse of [`civil_from_days`].
+#[cfg(any(windows, test))]
+fn days_from_civil(year: i64, month: u32, day: u32) -> i64 {
+    let y = if month <= 2 { year - 1 } else { year };
+    let era = y.div_euclid(400);
+    let yoe = y.rem_euclid(400);
+    let m = i64::from(month);
+    let doy = (153 * (if m > 2 { m - 3 } else { m + 9 }) + 2) / 5 + i64::from(day) - 1;
+    let doe = yoe * 365 + yoe / 4 - yoe / 100 + doy;
+    era * 146_097 + doe - 719_468
+}
+
+#[cfg(unix)]
+fn local_time(secs: i64) -> Option<LocalTime> {
+    let t = secs as libc::time_t;
+    // SAFETY: `tm` is plain data, and localtime_r only writes into it.
+    let mut tm: libc::tm = unsafe { std::mem::zeroed() };
+    if unsafe { libc::localtime_r(&t, &mut tm) }.is_null() {
+        return None;
+    }
+    Some(LocalTime {
+        year: i64::from(tm.tm_year) + 1900,
+        month: (tm.tm_mon + 1) as u32,
+        day: tm.tm_mday as u32,
+        hour: tm.tm_hour as u32,
+        minute: tm.tm_min as u32,
+        second: tm.tm_sec as u32,
+        offset_secs: tm.tm_gmtoff as i64,
+    })
+}
+
+#[cfg(windows)]
+fn local_time(secs: i64) -> Option<LocalTime> {
+    use windows_sys::Win32::Foundation::SYSTEMTIME;
+    use windows_sys::Win32::System::Time::SystemTimeToTzSpecificLocalTime;
+
+    let utc = LocalTime::utc(secs);
+    let st = SYSTEMTIME {
+        wYear: u16::try_from(utc.year).ok()?,
+        wMonth: utc.month as u16,
+        wDayOfWeek: 0,
+        wDay: utc.day as u16,
+        wHour: utc.hour as u16,
+        wMinute: utc.minute as u16,
+        wSecond: utc.second as u16,
+        wMilliseconds: 0,
+    };
+    // SAFETY: SYSTEMTIME is plain data; a null timezone means the current one.
+    let mut local: SYSTEMTIME = unsafe { std::mem::zeroed() };
+    if unsafe { SystemTimeToTzSpecificLocalTime(std::ptr::null(), &st, &mut local) } == 0 {
+        return None;
+    }
+    let local_secs = days_from_civil(
+        i64::from(local.wYear),
+        u32::from(local.wMonth),
+        u32::from(local.wDay),
+    ) * 86_400
+        + i64::from(local.wHour) * 3600
+        + i64::from(local.wMinute) * 60
+        + i64::from(local.wSecond);
+    Some(LocalTime {
+        year: i64::from(local.wYear),
+        month: u32::from(local.wMonth),
+        day: u32::from(local.wDay),
+        hour: u32::from(local.wHour),
+        minute: u32::from(local.wMinute),
+        second: u32::from(local.wSecond),
+        offset_secs: local_secs - secs,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn civil_dates_round_trip() {
+        assert_eq!(civil_from_days(0), (1970, 1, 1));
+        assert_eq!(civil_from_days(19_723), (2024, 1, 1));
+        assert_eq!(civil_from_days(11_016), (2000, 2, 29));
+        for days in [-1, 0, 59, 10_957, 20_742, 50_000] {
+            let (y, m, d) = civil_from_days(days);
+            assert_eq!(days_from_civil(y, m, d), days);
+        }
+    }
+
+    #[test]
+    fn format_local_times() {
+        let mut t = LocalTime::utc(1_700_000_000);
+        assert_eq!(t.display(), "2023-11-14 22:13:20");
+        assert_eq!(t.file_stamp(), "20231114-221320");
+        assert_eq!(t.rfc3339(), "2023-11-14T22:13:20+00:00");
+        t.offset_secs = -(5 * 3600 + 30 * 60);
+        assert_eq!(t.rfc3339(), "2023-11-14T22:13:20-05:30");
+    }
+
+    #[test]
+    fn parse_style() {
+        assert_eq!(
+            TimestampStyle::parse("absolute"),
+            Ok(TimestampStyle::Absolute)
+        );
+        assert!(TimestampStyle::parse("iso").is_err());
+    }
+}
@@ -0,0 +1,457 @@
+use std::collections::{HashMap, HashSet};
+use std::sync::OnceLock;
+
+use crate::backlog::ListenQueue;
+use crate::bandwidth::SocketTraffic;
+use crate::ephemeral::LocalPorts;
+use crate::orphan::Lineage;
+use crate::quic::{self, Quic};
+use crate::refresh::ListenerFeed;
+use crate::sockopts::SocketOptions;
+use crate::udpservice::{self, ServiceReply, UdpService};
+use crate::{os, ConnInfo, PortInfo, ProcessDetail, TcpState};
+
+// ── Collector interface ──────────────────────────────────────────────
+
+/// What a backend can report, so callers can explain missing data instead
+/// of showing empty columns.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub(crate) struct Capabilities {
+    /// Connections carry the remote address (peer lists work).
+    pub(crate) supports_remote_addr: bool,
+    /// UDP sockets distinguish bound from connected.
+    pub(crate) supports_udp_state: bool,
+    /// Other users' processes are only visible with elevated privileges.
+    pub(crate) needs_privileges: bool,
+}
+
+impl Capabilities {
+    /// `remote addresses, UDP state; needs sudo for other users`
+    pub(crate) fn describe(&self) -> String {
+        let mut parts = Vec::new();
+        if self.supports_remote_addr {
+            parts.push("remote addresses");
+        }
+        if self.supports_udp_state {
+            parts.push("UDP state");
+        }
+        let mut text = if parts.is_empty() {
+            "local ports only".to_string()
+        } else {
+            parts.join(", ")
+        };
+        if self.needs_privileges {
+            text.push_str("; other users' processes need elevated privileges");
+        }
+        text
+    }
+}
+
+/// A source of ports and process details. The platform modules sit behind
+/// [`OsCollector`]; `--fixture` installs a file-backed one instead.
+pub(crate) trait PortCollector: Send + Sync {
+    /// Short backend name for diagnostics.
+    fn name(&self) -> &'static str;
+
+    fn capabilities(&self) -> Capabilities;
+
+    /// Every port, or only listeners and bound UDP sockets.
+    fn port_infos(&self, filter_listening: bool) -> Vec<PortInfo>;
+
+    /// Every TCP/UDP socket, including ones with no owning process.
+    fn connections(&self) -> Vec<ConnInfo>;
+
+    /// The account `--user` names, spelled as the USER column shows it: a
+    /// numeric uid comes back as its account name. `None` when there's no
+    /// such account.
+    fn resolve_user(&self, user: &str) -> Option<String> {
+        Some(user.to_string())
+    }
+
+    /// [`port_infos`](Self::port_infos) for one account's processes only;
+    /// `user` has been through [`resolve_user`](Self::resolve_user).
+    /// Backends override this when they can skip the others while scanning.
+    fn user_port_infos(&self, filter_listening: bool, user: &str) -> Vec<PortInfo> {
+        let mut infos = self.port_infos(filter_listening);
+        infos.retain(|i| i.user == user);
+        infos
+    }
+
+    /// [`port_infos`](Self::port_infos) (or one account's, with `user`)
+    /// handed to `emit` unsorted as they're read, for `--stream`. `emit`
+    /// returns false to stop early. Backends override this when they can
+    /// produce rows before the whole scan is done.
+    fn stream_port_infos(
+        &self,
+        filter_listening: bool,
+        user: Option<&str>,
+        emit: &mut dyn FnMut(PortInfo) -> bool,
+    ) {
+        let infos = match user {
+            Some(user) => self.user_port_infos(filter_listening, user),
+            None => self.port_infos(filter_listening),
+        };
+        for info in infos {
+            if !emit(info) {
+                break;
+            }
+        }
+    }
+
+    /// Ports with a listener; backends override this when they can skip
+    /// per-process stats.
+    fn listening_ports(&self) -> HashSet<u16> {
+        self.port_infos(true)
+            .iter()
+            .filter(|i| i.state == TcpState::Listen || i.protocol.starts_with("UDP"))
+            .map(|i| i.port)
+            .collect()
+    }
+
+    /// Cumulative per-socket byte counters; empty when not collected.
+    fn socket_traffic(&self) -> Vec<SocketTraffic> {
+        Vec::new()
+    }
+
+    /// A hash of the current listeners that changes whenever one opens or
+    /// closes, cheap enough to check on every [`Self::listener_changes`]
+    /// wake. `None` means watch mode can only refresh on its timer.
+    fn listener_signature(&self) -> Option<u64> {
+        None
+    }
+
+    /// Wakes from the OS when sockets may have opened or closed. `None`
+    /// means watch mode can only refresh on its timer.
+    fn listener_changes(&self) -> Option<ListenerFeed> {
+        None
+    }
+
+    /// Accept queues of TCP listeners; empty when not collected.
+    fn listen_queues(&self) -> Vec<ListenQueue> {
+        Vec::new()
+    }
+
+    /// The kernel's cap on listen() backlogs, when known.
+    fn somaxconn(&self) -> Option<u32> {
+        None
+    }
+
+    /// The ephemeral port range and reserved ports, when known.
+    fn local_ports(&self) -> Option<LocalPorts> {
+        None
+    }
+
+    /// Direct children of a process; empty when not known.
+    fn child_pids(&self, _pid: u32) -> Vec<u32> {
+        Vec::new()
+    }
+
+    /// Whether the process still exists; `None` when the backend can't
+    /// tell.
+    fn process_alive(&self, _pid: u32) -> Option<bool> {
+        None
+    }
+
+    /// Zombie state, parent and session, for orphan checks; `None` when
+    /// unknown.
+    fn process_lineage(&self, _pid: u32) -> Option<Lineage> {
+        None
+    }
+
+    /// The parent process, for `--session`. Backends that can't report
+    /// the full lineage override this.
+    fn parent_pid(&self, pid: u32) -> Option<u32> {
+        self.process_lineage(pid).map(|l| l.ppid)
+    }
+
+    fn process_cwd(&self, _pid: u32) -> Option<String> {
+        None
+    }
+
+    /// Options on the socket behind a row; `None` when they can't be read.
+    fn socket_options(&self, _info: &PortInfo) -> Option<SocketOptions> {
+        None
+    }
+
+    /// The QUIC server behind a UDP row, if it answers one; `None` when
+    /// it doesn't or the backend can't send probes.
+    fn probe_quic(&self, _info: &PortInfo) -> Option<Quic> {
+        None
+    }
+
+    /// The reply of the well-known service behind a UDP row; `None` when
+    /// it doesn't answer or the backend can't send probes.
+    fn probe_udp_service(&self, _info: &PortInfo, _service: UdpService) -> Option<ServiceReply> {
+        None
+    }
+
+    fn process_detail(&self, pid: u32) -> ProcessDetail {
+        ProcessDetail {
+            threads: None,
+            nice: None,
+            cwd: self.process_cwd(pid),
+            env: Vec::new(),
+            repo: None,
+            orphan: None,
+        }
+    }
+}
+
+// ── Selection ────────────────────────────────────────────────────────
+
+/// The platform module for this OS.
+pub(crate) struct OsCollector;
+
+impl PortCollector for OsCollector {
+    fn name(&self) -> &'static str {
+        os::BACKEND_NAME
+    }
+
+    fn capabilities(&self) -> Capabilities {
+        os::CAPABILITIES
+    }
+
+    fn port_infos(&self, filter_listening: bool) -> Vec<PortInfo> {
+        os::get_port_infos(filter_listening)
+    }
+
+    fn connections(&self) -> Vec<ConnInfo> {
+        os::get_connections()
+    }
+
+    fn resolve_user(&self, user: &str) -> Option<String> {
+        os::resolve_user(user)
+    }
+
+    fn user_port_infos(&self, filter_listening: bool, user: &str) -> Vec<PortInfo> {
+        os::get_user_port_infos(filter_listening, user)
+    }
+
+    #[cfg(target_os = "linux")]
+    fn stream_port_infos(
+        &self,
+        filter_listening: bool,
+        user: Option<&str>,
+        emit: &mut dyn FnMut(PortInfo) -> bool,
+    ) {
+        os::stream_port_infos(filter_listening, user, emit)
+    }
+
+    fn listening_ports(&self) -> HashSet<u16> {
+        os::get_listening_ports()
+    }
+
+    fn socket_traffic(&self) -> Vec<SocketTraffic> {
+        os::get_socket_traffic()
+    }
+
+    fn listener_signature(&self) -> Option<u64> {
+        os::listener_signature()
+    }
+
+    fn listener_changes(&self) -> Option<ListenerFeed> {
+        os::listener_changes()
+    }
+
+    fn listen_queues(&self) -> Vec<ListenQueue> {
+        os::get_listen_queues()
+    }
+
+    fn somaxconn(&self) -> Option<u32> {
+        os::somaxconn()
+    }
+
+    fn local_ports(&self) -> Option<LocalPorts> {
+        os::local_ports()
+    }
+
+    fn child_pids(&self, pid: u32) -> Vec<u32> {
+        os::get_child_pids(pid)
+    }
+
+    fn process_alive(&self, pid: u32) -> Option<bool> {
+        Some(os::process_alive(pid))
+    }
+
+    fn process_lineage(&self, pid: u32) -> Option<Lineage> {
+        os::get_lineage(pid)
+    }
+
+    fn parent_pid(&self, pid: u32) -> Option<u32> {
+        os::get_parent_pid(pid)
+    }
+
+    fn process_cwd(&self, pid: u32) -> Option<String> {
+        os::get_process_cwd(pid)
+    }
+
+    fn socket_options(&self, info: &PortInfo) -> Option<SocketOptions> {
+        os::get_socket_options(info)
+    }
+
+    fn probe_quic(&self, info: &PortInfo) -> Option<Quic> {
+        quic::probe(info)
+    }
+
+    fn probe_udp_service(&self, info: &PortInfo, service: UdpService) -> Option<ServiceReply> {
+        udpservice::probe_local(info, service)
+    }
+
+    fn process_detail(&self, pid: u32) -> ProcessDetail {
+        os::get_process_detail(pid)
+    }
+}
+
+static ACTIVE: OnceLock<Box<dyn PortCollector>> = OnceLock::new();
+
+/// Replace the OS backend for the rest of the run. Must happen before the
+/// first data read.
+pub(crate) fn install(collector: Box<dyn PortCollector>) -> Result<(), String> {
+    ACTIVE
+        .set(collector)
+        .map_err(|_| "a collector is already active".to_string())
+}
+
+pub(crate) fn active() -> &'static dyn PortCollector {
+    ACTIVE.get_or_init(|| Box::new(OsCollector)).as_ref()
+}
+
+// ── User filter ──────────────────────────────────────────────────────
+
+static USER: OnceLock<String> = OnceLock::new();
+
+/// `--user`/`--mine`: limit the port list to one account's processes for
+/// the rest of the run. The first call wins.
+pub(crate) fn restrict_to_user(name: String) {
+    let _ = USER.set(name);
+}
+
+pub(crate) fn user_filter() -> Option<&'static str> {
+    USER.get().map(String::as_str)
+}
+
+// ── Session filter ───────────────────────────────────────────────────
+
+static SESSION: OnceLock<u32> = OnceLock::new();
+
+/// `--session`: limit the port list to processes started, directly or
+/// not, from the `shell` process for the rest of the run. The first call
+/// wins.
+pub(crate) fn restrict_to_session(shell: u32) {
+    let _ = SESSION.set(shell);
+}
+
+pub(crate) fn session_filter() -> Option<u32> {
+    SESSION.get().copied()
+}
+
+/// Remembers which PIDs passed `--session` during one scan, since a
+/// process with several sockets shows up once per socket.
+#[derive(Default)]
+pub(crate) struct SessionCheck {
+    known: HashMap<u32, bool>,
+}
+
+impl SessionCheck {
+    /// Whether `pid` may be listed: always without `--session`.
+    pub(crate) fn admits(&mut self, backend: &dyn PortCollector, pid: u32) -> bool {
+        let Some(shell) = session_filter() else {
+            return true;
+        };
+        *self
+            .known
+            .entry(pid)
+            .or_insert_with(|| descends_from(pid, shell, |p| backend.parent_pid(p)))
+    }
+}
+
+/// Whether `root` is `pid` or one of its ancestors. The walk stops at
+/// init, at an unknown parent, and at a PID it has already seen, since a
+/// reused PID can make the chain loop.
+pub(crate) fn descends_from(
+    pid: u32,
+    root: u32,
+    mut parent_of: impl FnMut(u32) -> Option<u32>,
+) -> bool {
+    let mut seen = Vec::new();
+    let mut current = pid;
+    while current > 1 && !seen.contains(&current) {
+        if current == root {
+            return true;
+        }
+        seen.push(current);
+        match parent_of(current) {
+            Some(parent) => current = parent,
+            None => return false,
+        }
+    }
+    false
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn describe_capabilities() {
+        let caps = Capabilities {
+            supports_remote_addr: true,
+            supports_udp_state: false,
+            needs_privileges: true,
+        };
+        assert_eq!(
+            caps.describe(),
+            "remote addresses; other users' processes need elevated privileges"
+        );
+        let none = Capabilities {
+            supports_remote_addr: false,
+            supports_udp_state: false,
+            needs_privileges: false,
+        };
+        assert_eq!(none.describe(), "local ports only");
+    }
+
+    #[test]
+    fn default_listening_ports_come_from_port_infos() {
+        struct Fake;
+        impl PortCollector for Fake {
+            fn name(&self) -> &'static str {
+                "fake"
+            }
+            fn capabilities(&self) -> Capabilities {
+                Capabilities {
+                    supports_remote_addr: false,
+                    supports_udp_state: false,
+                    needs_privileges: false,
+                }
+            }
+            fn port_infos(&self, _filter_listening: bool) -> Vec<PortInfo> {
+                use std::net::{IpAddr, Ipv4Addr};
+                vec![PortInfo::listener(6379, 9, "redis-server")
+                    .user("redis")
+                    .addr(IpAddr::V4(Ipv4Addr::LOCALHOST))]
+            }
+            fn connections(&self) -> Vec<ConnInfo> {
+                Vec::new()
+            }
+        }
+        assert_eq!(Fake.listening_ports(), HashSet::from([6379]));
+        assert_eq!(Fake.user_port_infos(true, "redis").len(), 1);
+        assert!(Fake.user_port_infos(true, "root").is_empty());
+        assert_eq!(Fake.process_detail(9).cwd, None);
+    }
+
+    #[test]
+    fn descends_from_walks_up_to_the_shell() {
+        // 1 ─ 100 (shell) ─ 110 (npm) ─ 120 (node); 1 ─ 200
+        let parents = HashMap::from([(100, 1), (110, 100), (120, 110), (200, 1)]);
+        let parent_of = |p: u32| parents.get(&p).copied();
+        assert!(descends_from(120, 100, parent_of));
+        assert!(descends_from(100, 100, parent_of));
+        assert!(!descends_from(200, 100, parent_of));
+        assert!(!descends_from(300, 100, parent_of), "unknown parent");
+
+        let looped = HashMap::from([(7, 8), (8, 7)]);
+        assert!(!descends_from(7, 100, |p| looped.get(&p).copied()));
+    }
+}
@@ -0,0 +1,749 @@
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use std::sync::OnceLock;
+
+use crossterm::event::KeyCode;
+
+use crate::keymap::{self, Action, Keymap, Preset};
+use crate::tui::{SortColumn, SortDirection};
+
+// ── Config file ──────────────────────────────────────────────────────
+//
+// A small TOML subset: `[section]` and `[[array]]` headers, `key = value`
+// pairs with string, integer and boolean values, and `#` comments.
+// Sections this version doesn't know are skipped so newer shared configs
+// still load; an unknown key in a known section is an error, so a typo
+// doesn't silently leave a setting at its default.
+//
+//     [labels]
+//     3000 = "storefront"
+//     8025 = "mailhog UI"
+//
+//     [warnings]
+//     legacy_flags = false
+//
+//     [pager]
+//     paginate = true
+//     command = "less -RS"
+//
+//     [keys]
+//     kill = "x"
+//
+//     [kill]
+//     confirm = false
+//     confirm_force = true
+//
+//     [docker]
+//     host = "unix:///run/user/1000/docker.sock"
+//     cli = "/usr/local/bin/docker"
+//
+//     [tui]
+//     default_sort = "mem desc"
+//     remember = false
+
+/// Settings read from the user's config file, plus the labels from the
+/// project's.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub(crate) struct Config {
+    /// Port → description, shown in a LABEL column and the detail view.
+    pub(crate) labels: HashMap<u16, String>,
+    /// `[warnings] legacy_flags`: hint at the subcommands that replace
+    /// `--watch` and `--kill`. Unset means yes.
+    pub(crate) legacy_flag_warnings: Option<bool>,
+    /// `[pager] paginate`: send long tables through the pager without
+    /// `--paginate`. Unset means no.
+    pub(crate) paginate: Option<bool>,
+    /// `[pager] command`: used when `PORTVIEW_PAGER` isn't set, before
+    /// `$PAGER`.
+    pub(crate) pager: Option<String>,
+    /// `[kill] confirm`: ask before a TUI kill. Unset means yes.
+    pub(crate) kill_confirm: Option<bool>,
+    /// `[kill] confirm_force`: type the PID back before SIGKILL. Unset
+    /// means no.
+    pub(crate) kill_confirm_force: Option<bool>,
+    /// `[kill] confirm_shared`: type the port or PID back before SIGKILL
+    /// of root's or another user's process. Unset means yes.
+    pub(crate) kill_confirm_shared: Option<bool>,
+    /// `[keys] preset`.
+    pub(crate) key_preset: Option<Preset>,
+    /// `[keys]` entries: actions whose default keys are replaced.
+    pub(crate) keys: Vec<(Action, Vec<KeyCode>)>,
+    /// `[docker] host`: the daemon to ask, ahead of `DOCKER_HOST`.
+    pub(crate) docker_host: Option<String>,
+    /// `[docker] cli`: the docker binary, when it isn't `docker` on PATH.
+    pub(crate) docker_cli: Option<String>,
+    /// `[tui] default_sort`: the TUI's sort when nothing is remembered.
+    pub(crate) default_sort: Option<(SortColumn, SortDirection)>,
+    /// `[tui] remember`: restore the last session's sort and toggles.
+    /// Unset means yes.
+    pub(crate) tui_remember: Option<bool>,
+}
+
+impl Config {
+    /// Parse one file. Errors carry the 1-based line number.
+    pub(crate) fn parse(text: &str) -> Result<Self, String> {
+        let mut config = Self::default();
+        for table in parse_tables(text)? {
+            if table.array {
+                continue;
+            }
+            match table.name.as_str() {
+                "labels" => config.parse_labels(table.entries)?,
+                "keys" => config.parse_keys(table.entries)?,
+                "warnings" => {
+                    for entry in table.entries {
+                        match entry.key.as_str() {
+                            "legacy_flags" => config.legacy_flag_warnings = Some(entry.get()?),
+                            _ => return Err(entry.unknown_key("warnings", "legacy_flags")),
+                        }
+                    }
+                }
+                "pager" => {
+                    for entry in table.entries {
+                        match entry.key.as_str() {
+                            "paginate" => config.paginate = Some(entry.get()?),
+                            "command" => config.pager = Some(entry.get()?),
+                            _ => return Err(entry.unknown_key("pager", "paginate or command")),
+                        }
+                    }
+                }
+                "kill" => {
+                    for entry in table.entries {
+                        let slot = match entry.key.as_str() {
+                            "confirm" => &mut config.kill_confirm,
+                            "confirm_force" => &mut config.kill_confirm_force,
+                            "confirm_shared" => &mut config.kill_confirm_shared,
+                            _ => {
+                                return Err(entry.unknown_key(
+                                    "kill",
+                                    "confirm, confirm_force or confirm_shared",
+                                ))
+                            }
+                        };
+                        *slot = Some(entry.get()?);
+                    }
+                }
+                "docker" => {
+                    for entry in table.entries {
+                        let slot = match entry.key.as_str() {
+                            "host" => &mut config.docker_host,
+                            "cli" => &mut config.docker_cli,
+                            _ => return Err(entry.unknown_key("docker", "host or cli")),
+                        };
+                        *slot = Some(entry.get()?);
+                    }
+                }
+                "tui" => {
+                    for entry in table.entries {
+                        match entry.key.as_str() {
+                            "default_sort" => {
+                                let value: String = entry.get()?;
+                                let sort = SortColumn::parse_sort(&value).ok_or_else(|| {
+                                    entry.error(format!(
+                                        "unknown sort '{}' (expected a column like mem, optionally followed by asc or desc)",
+                                        value
+                                    ))
+                                })?;
+                                config.default_sort = Some(sort);
+                            }
+                            "remember" => config.tui_remember = Some(entry.get()?),
+                            _ => return Err(entry.unknown_key("tui", "default_sort or remember")),
+                        }
+                    }
+                }
+                _ => {}
+            }
+        }
+        Ok(config)
+    }
+
+    fn parse_labels(&mut self, entries: Vec<Entry>) -> Result<(), String> {
+        for entry in entries {
+            let port = entry.key.parse::<u16>().map_err(|_| {
+                entry.error(format!("label key '{}' is not a port number", entry.key))
+            })?;
+            let Value::Str(label) = entry.value else {
+                return Err(entry.error(format!("label for {} must be a string", port)));
+            };
+            self.labels.insert(port, label);
+        }
+        Ok(())
+    }
+
+    fn parse_keys(&mut self, entries: Vec<Entry>) -> Result<(), String> {
+        for entry in entries {
+            let value: String = entry.get()?;
+            if entry.key == "preset" {
+                let preset = Preset::parse(&value).ok_or_else(|| {
+                    entry.error(format!(
+                        "unknown key preset '{}' (expected default or arrows)",
+                        value
+                    ))
+                })?;
+                self.key_preset = Some(preset);
+                continue;
+            }
+            let action = Action::parse(&entry.key)
+                .ok_or_else(|| entry.error(format!("unknown action '{}'", entry.key)))?;
+            let keys = keymap::parse_keys(&value).map_err(|e| entry.error(e))?;
+            self.keys.retain(|(a, _)| *a != action);
+            self.keys.push((action, keys));
+        }
+        self.keymap().map(|_| ())
+    }
+
+    /// The TUI bindings: the preset with the `[keys]` entries applied.
+    pub(crate) fn keymap(&self) -> Result<Keymap, String> {
+        Keymap::build(self.key_preset.unwrap_or_default(), &self.keys)
+            .map_err(|e| format!("[keys] {}", e))
+    }
+
+    /// A project file's config: its labels alone. The file comes with
+    /// whatever repository was cloned, so anything else it could set, such
+    /// as the pager command, is an error rather than taking effect.
+    pub(crate) fn parse_project(text: &str) -> Result<Self, String> {
+        let tables = parse_tables(text)?;
+        if let Some(table) = tables
+            .iter()
+            .find(|t| !t.array && USER_ONLY_SECTIONS.contains(&t.name.as_str()))
+        {
+            return Err(format!(
+                "line {}: [{}] is only read from the user config",
+                table.line, table.name
+            ));
+        }
+        let labels = Self::parse(text)?.labels;
+        Ok(Self {
+            labels,
+            ..Self::default()
+        })
+    }
+
+    /// Later files win for the same port.
+    fn merge(&mut self, other: Config) {
+        self.labels.extend(other.labels);
+        self.legacy_flag_warnings = other.legacy_flag_warnings.or(self.legacy_flag_warnings);
+        self.paginate = other.paginate.or(self.paginate);
+        self.pager = other.pager.or(self.pager.take());
+        self.kill_confirm = other.kill_confirm.or(self.kill_confirm);
+        self.kill_confirm_force = other.kill_confirm_force.or(self.kill_confirm_force);
+        self.kill_confirm_shared = other.kill_confirm_shared.or(self.kill_confirm_shared);
+        self.key_preset = other.key_preset.or(self.key_preset);
+        self.docker_host = other.docker_host.or(self.docker_host.take());
+        self.docker_cli = other.docker_cli.or(self.docker_cli.take());
+        self.default_sort = other.default_sort.or(self.default_sort);
+        self.tui_remember = other.tui_remember.or(self.tui_remember);
+        for (action, keys) in other.keys {
+            self.keys.retain(|(a, _)| *a != action);
+            self.keys.push((action, keys));
+        }
+    }
+
+    pub(crate) fn warn_legacy_flags(&self) -> bool {
+        self.legacy_flag_warnings.unwrap_or(true)
+    }
+
+    pub(crate) fn paginate(&self) -> bool {
+        self.paginate.unwrap_or(false)
+    }
+
+    pub(crate) fn label(&self, port: u16) -> Option<&str> {
+        self.labels.get(&port).map(String::as_str)
+    }
+}
+
+// ── TOML subset ──────────────────────────────────────────────────────
+
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) enum Value {
+    Str(String),
+    Int(i64),
+    Bool(bool),
+}
+
+impl Value {
+    /// `string`, `integer` or `boolean`, for type errors.
+    pub(crate) fn type_name(&self) -> &'static str {
+        match self {
+            Value::Str(_) => "string",
+            Value::Int(_) => "integer",
+            Value::Bool(_) => "boolean",
+        }
+    }
+}
+
+/// One `key = value` line.
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) struct Entry {
+    pub(crate) key: String,
+    pub(crate) value: Value,
+    /// 1-based, for error messages.
+    pub(crate) line: usize,
+}
+
+impl Entry {
+    /// `line N: msg`, pointing at this entry.
+    pub(crate) fn error(&self, msg: String) -> String {
+        format!("line {}: {}", self.line, msg)
+    }
+
+    /// The value as a `T`, or `line N: key must be a T, not a U`.
+    pub(crate) fn get<T: FromValue>(&self) -> Result<T, String> {
+        T::from_value(&self.value).ok_or_else(|| {
+            self.error(format!(
+                "{} must be a {}, not a {}",
+                self.key,
+                T::TYPE_NAME,
+                self.value.type_name()
+            ))
+        })
+    }
+
+    /// `line N: unknown key 'k' in [section] (expected …)`.
+    fn unknown_key(&self, section: &str, expected: &str) -> String {
+        self.error(format!(
+            "unknown key '{}' in [{}] (expected {})",
+            self.key, section, expected
+        ))
+    }
+}
+
+/// A Rust type a [`Value`] can be read as, for [`Entry::get`].
+pub(crate) trait FromValue: Sized {
+    /// As [`Value::type_name`] reports it.
+    const TYPE_NAME: &'static str;
+
+    fn from_value(value: &Value) -> Option<Self>;
+}
+
+impl FromValue for bool {
+    const TYPE_NAME: &'static str = "boolean";
+
+    fn from_value(value: &Value) -> Option<Self> {
+        match value {
+            Value::Bool(on) => Some(*on),
+            _ => None,
+        }
+    }
+}
+
+impl FromValue for String {
+    const TYPE_NAME: &'static str = "string";
+
+    fn from_value(value: &Value) -> Option<Self> {
+        match value {
+            Value::Str(s) => Some(s.clone()),
+            _ => None,
+        }
+    }
+}
+
+/// The keys under one header. Keys before the first header form a table
+/// with an empty name.
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) struct Table {
+    pub(crate) name: String,
+    /// `[[name]]`: one element of an array of tables.
+    pub(crate) array: bool,
+    /// 1-based line of the header; 0 for the leading table.
+    pub(crate) line: usize,
+    pub(crate) entries: Vec<Entry>,
+}
+
+/// Split a file into its tables, in file order.
+pub(crate) fn parse_tables(text: &str) -> Result<Vec<Table>, String> {
+    let mut tables = vec![Table {
+        name: String::new(),
+        array: false,
+        line: 0,
+        entries: Vec::new(),
+    }];
+    for (i, raw) in text.lines().enumerate() {
+        let at = |msg: String| format!("line {}: {}", i + 1, msg);
+        let line = strip_comment(raw).trim();
+        if line.is_empty() {
+            continue;
+        }
+        if let Some(header) = line.strip_prefix('[') {
+            let (name, array) = match header.strip_prefix('[') {
+                Some(inner) => (inner.strip_suffix("]]"), true),
+                None => (header.strip_suffix(']'), false),
+            };
+            let name = name.ok_or_else(|| at("unclosed section header".to_string()))?;
+            tables.push(Table {
+                name: name.trim().to_string(),
+                array,
+                line: i + 1,
+                entries: Vec::new(),
+            });
+            continue;
+        }
+        let (key, value) = line
+            .split_once('=')
+            .ok_or_else(|| at(format!("expected `key = value`, got `{}`", line)))?;
+        let entry = Entry {
+            key: unquote(key.trim()).map_err(at)?,
+            value: parse_value(value.trim()).map_err(at)?,
+            line: i + 1,
+        };
+        if let Some(table) = tables.last_mut() {
+            table.entries.push(entry);
+        }
+    }
+    Ok(tables)
+}
+
+/// `true`/`false`, an integer (`_` separators allowed), or a string.
+fn parse_value(s: &str) -> Result<Value, String> {
+    match s {
+        "true" => return Ok(Value::Bool(true)),
+        "false" => return Ok(Value::Bool(false)),
+        _ => {}
+    }
+    if s.starts_with(|c: char| c.is_ascii_digit() || c == '-' || c == '+') {
+        if let Ok(n) = s.replace('_', "").parse::<i64>() {
+            return Ok(Value::Int(n));
+        }
+    }
+    unquote(s).map(Value::Str)
+}
+
+/// Drop a trailing `# comment`, ignoring `#` inside quotes.
+fn strip_comment(line: &str) -> &str {
+    let mut quote = None;
+    let mut escaped = false;
+    for (i, c) in line.char_indices() {
+        match (quote, c) {
+            (Some('"'), '\\') if !escaped => {
+                escaped = true;
+                continue;
+            }
+            (Some(q), c) if c == q && !escaped => quote = None,
+            (None, '"' | '\'') => quote = Some(c),
+            (None, '#') => return &line[..i],
+            _ => {}
+        }
+        escaped = false;
+    }
+    line
+}
+
+/// A bare word, `"basic string"` (with `\"`, `\\`, `\n`, `\t`) or
+/// `'literal string'`.
+fn unquote(s: &str) -> Result<String, String> {
+    if let Some(inner) = s.strip_prefix('\'') {
+        return inner
+            .strip_suffix('\'')
+            .map(str::to_string)
+            .ok_or_else(|| format!("unterminated string {}", s));
+    }
+    let Some(inner) = s.strip_prefix('"') else {
+        return Ok(s.to_string());
+    };
+    let inner = inner
+        .strip_suffix('"')
+        .filter(|body| !body.ends_with('\\') || body.ends_with("\\\\"))
+        .ok_or_else(|| format!("unterminated string {}", s))?;
+    let mut out = String::with_capacity(inner.len());
+    let mut chars = inner.chars();
+    while let Some(c) = chars.next() {
+        if c != '\\' {
+            out.push(c);
+            continue;
+        }
+        match chars.next() {
+            Some('n') => out.push('\n'),
+            Some('t') => out.push('\t'),
+            Some(c @ ('"' | '\\')) => out.push(c),
+            other => {
+                return Err(format!(
+                    "unsupported escape \\{} in {}",
+                    other.map(String::from).unwrap_or_default(),
+                    s
+                ))
+            }
+        }
+    }
+    Ok(out)
+}
+
+// ── Locations ────────────────────────────────────────────────────────
+
+/// File name looked up in the current directory and its parents, so a
+/// repository can ship labels for its dev environment.
+pub(crate) const PROJECT_FILE: &str = ".portview.toml";
+
+/// Sections a project file may not set; see [`Config::parse_project`].
+const USER_ONLY_SECTIONS: &[&str] = &["warnings", "pager", "kill", "keys", "docker", "tui"];
+
+/// `PORTVIEW_CONFIG` when set; otherwise the per-user file.
+pub(crate) fn user_config_path() -> Option<PathBuf> {
+    if let Some(path) = std::env::var_os("PORTVIEW_CONFIG") {
+        return Some(PathBuf::from(path));
+    }
+    let base = if cfg!(windows) {
+        std::env::var_os("APPDATA").map(PathBuf::from)
+    } else {
+        std::env::var_os("XDG_CONFIG_HOME")
+            .map(PathBuf::from)
+            .or_else(|| std::env::var_os("HOME").map(|h| Path::new(&h).join(".config")))
+    };
+    base.map(|b| b.join("portview").join("config.toml"))
+}
+
+/// Where portview keeps what it writes for itself: the action log and
+/// the TUI's remembered settings. `$XDG_STATE_HOME/portview`,
+/// `~/.local/state/portview` without it, or `%LOCALAPPDATA%\portview`
+/// on Windows.
+pub(crate) fn user_state_dir() -> Option<PathBuf> {
+    let base = if cfg!(windows) {
+        std::env::var_os("LOCALAPPDATA").map(PathBuf::from)
+    } else {
+        std::env::var_os("XDG_STATE_HOME")
+            .filter(|d| !d.is_empty())
+            .map(PathBuf::from)
+            .or_else(|| std::env::var_os("HOME").map(|h| Path::new(&h).join(".local/state")))
+    };
+    base.map(|b| b.join("portview"))
+}
+
+/// The nearest `.portview.toml` at or above `dir`.
+pub(crate) fn find_project_config(dir: &Path) -> Option<PathBuf> {
+    dir.ancestors()
+        .map(|d| d.join(PROJECT_FILE))
+        .find(|p| p.is_file())
+}
+
+/// The user's config, then the project's labels on top. A missing file is
+/// fine; one that can't be parsed is an error naming the file.
+pub(crate) fn load() -> Result<Config, String> {
+    let explicit = std::env::var_os("PORTVIEW_CONFIG").is_some();
+    let project = std::env::current_dir()
+        .ok()
+        .and_then(|cwd| find_project_config(&cwd));
+    let mut config = Config::default();
+    for (path, required, is_project) in
+        [(user_config_path(), explicit, false), (project, true, true)]
+    {
+        let Some(path) = path else {
+            continue;
+        };
+        let text = match std::fs::read_to_string(&path) {
+            Ok(text) => text,
+            Err(_) if !required && !path.exists() => continue,
+            Err(e) => return Err(format!("{}: {}", path.display(), e)),
+        };
+        let parsed = if is_project {
+            Config::parse_project(&text)
+        } else {
+            Config::parse(&text)
+        };
+        config.merge(parsed.map_err(|e| format!("{}: {}", path.display(), e))?);
+    }
+    // Each file's keys are valid alone, but together they may collide
+    config.keymap()?;
+    Ok(config)
+}
+
+static ACTIVE: OnceLock<Config> = OnceLock::new();
+
+/// Set the config for the rest of the run; the first call wins.
+pub(crate) fn install(config: Config) {
+    let _ = ACTIVE.set(config);
+}
+
+pub(crate) fn active() -> &'static Config {
+    ACTIVE.get_or_init(Config::default)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_labels_and_skip_other_sections() {
+        let config = Config::parse(
+            r#"
+# shared dev environment
+[labels]
+3000 = "storefront"   # next.js
+"8025" = 'mailhog UI'
+5432 = "db \"main\" # primary"
+
+[future]
+anything = "goes"
+"#,
+        )
+        .unwrap();
+        assert_eq!(config.label(3000), Some("storefront"));
+        assert_eq!(config.label(8025), Some("mailhog UI"));
+        assert_eq!(config.label(5432), Some(r#"db "main" # primary"#));
+        assert_eq!(config.labels.len(), 3);
+    }
+
+    #[test]
+    fn parse_errors_name_the_line() {
+        let err = Config::parse("[labels]\nweb = \"x\"").unwrap_err();
+        assert_eq!(err, "line 2: label key 'web' is not a port number");
+        assert!(Config::parse("[labels]\n3000 \"x\"")
+            .unwrap_err()
+            .starts_with("line 2: expected"));
+        assert!(Config::parse("[labels\n").is_err());
+        assert!(Config::parse("[labels]\n3000 = \"x").is_err());
+        assert_eq!(
+            Config::parse("[labels]\n3000 = 1").unwrap_err(),
+            "line 2: label for 3000 must be a string"
+        );
+    }
+
+    #[test]
+    fn parse_array_tables_and_values() {
+        let tables = parse_tables(
+            "top = 1\n[[service]]\nname = \"web\"\nport = 3_000\n\n[[service]]\nenabled = false\n",
+        )
+        .unwrap();
+        assert_eq!(tables.len(), 3);
+        assert_eq!(tables[0].entries[0].value, Value::Int(1));
+        assert!(tables[1].array && tables[2].array);
+        assert_eq!(tables[1].name, "service");
+        assert_eq!(tables[1].line, 2);
+        assert_eq!(tables[1].entries[0].value, Value::Str("web".to_string()));
+        assert_eq!(tables[1].entries[1].value, Value::Int(3000));
+        assert_eq!(tables[2].entries[0].value, Value::Bool(false));
+        assert_eq!(tables[2].entries[0].line, 7);
+        assert!(parse_tables("[[service]\n").is_err());
+    }
+
+    #[test]
+    fn later_files_win() {
+        let mut config = Config::parse("[labels]\n3000 = \"mine\"\n6379 = \"cache\"").unwrap();
+        config.merge(Config::parse("[labels]\n3000 = \"team\"").unwrap());
+        assert_eq!(config.label(3000), Some("team"));
+        assert_eq!(config.label(6379), Some("cache"));
+    }
+
+    #[test]
+    fn legacy_flag_warnings_setting() {
+        let mut config = Config::parse("[labels]\n3000 = \"web\"").unwrap();
+        assert!(config.warn_legacy_flags());
+        config.merge(Config::parse("[warnings]\nlegacy_flags = false").unwrap());
+        assert!(!config.warn_legacy_flags());
+        config.merge(Config::default());
+        assert!(!config.warn_legacy_flags());
+        assert_eq!(
+            Config::parse("[warnings]\nlegacy_flags = \"off\"").unwrap_err(),
+            "line 2: legacy_flags must be a boolean, not a string"
+        );
+        assert_eq!(
+            Config::parse("[warnings]\nlegacy = false").unwrap_err(),
+            "line 2: unknown key 'legacy' in [warnings] (expected legacy_flags)"
+        );
+    }
+
+    #[test]
+    fn pager_settings() {
+        let mut config = Config::parse("[pager]\npaginate = true\ncommand = \"less -RS\"").unwrap();
+        assert!(config.paginate());
+        config.merge(Config::parse("[pager]\npaginate = false").unwrap());
+        assert!(!config.paginate());
+        assert_eq!(config.pager.as_deref(), Some("less -RS"));
+        assert!(!Config::default().paginate());
+        assert_eq!(
+            Config::parse("[pager]\ncommand = true").unwrap_err(),
+            "line 2: command must be a string, not a boolean"
+        );
+        assert_eq!(
+            Config::parse("[pager]\npaginat = true").unwrap_err(),
+            "line 2: unknown key 'paginat' in [pager] (expected paginate or command)"
+        );
+    }
+
+    #[test]
+    fn project_files_set_labels_only() {
+        let config = Config::parse_project(
+            "[labels]\n3000 = \"web\"\n[[service]]\nname = \"web\"\nport = 3000",
+        )
+        .unwrap();
+        assert_eq!(config.label(3000), Some("web"));
+        assert_eq!(
+            config,
+            Config {
+                labels: config.labels.clone(),
+                ..Config::default()
+            }
+        );
+        assert_eq!(
+            Config::parse_project("[labels]\n3000 = \"web\"\n\n[pager]\npaginate = true")
+                .unwrap_err(),
+            "line 4: [pager] is only read from the user config"
+        );
+        assert!(Config::parse_project("[tui]\nremember = false").is_err());
+        // A repo-relative docker binary would run on every Docker lookup
+        assert_eq!(
+            Config::parse_project("[docker]\ncli = \"./bin/docker\"").unwrap_err(),
+            "line 1: [docker] is only read from the user config"
+        );
+        // Nor can a parent directory turn kill confirmations off
+        assert_eq!(
+            Config::parse_project("[kill]\nconfirm = false").unwrap_err(),
+            "line 1: [kill] is only read from the user config"
+        );
+    }
+
+    #[test]
+    fn docker_endpoint_settings() {
+        let mut config = Config::parse("[docker]\nhost = \"tcp://build:2376\"").unwrap();
+        config.merge(Config::parse("[docker]\ncli = \"/opt/docker/bin/docker\"").unwrap());
+        assert_eq!(config.docker_host.as_deref(), Some("tcp://build:2376"));
+        assert_eq!(config.docker_cli.as_deref(), Some("/opt/docker/bin/docker"));
+        assert_eq!(
+            Config::parse("[docker]\nhost = true").unwrap_err(),
+            "line 2: host must be a string, not a boolean"
+        );
+        assert!(Config::parse("[docker]\nsocket = \"x\"")
+            .unwrap_err()
+            .starts_with("line 2: unknown key 'socket' in [docker]"));
+    }
+
+    #[test]
+    fn tui_settings() {
+        let mut config = Config::parse("[tui]\ndefault_sort = \"mem desc\"").unwrap();
+        config.merge(Config::parse("[tui]\nremember = false").unwrap());
+        assert_eq!(
+            config.default_sort,
+            Some((SortColumn::Mem, SortDirection::Desc))
+        );
+        assert_eq!(config.tui_remember, Some(false));
+        assert_eq!(
+            Config::parse("[tui]\ndefault_sort = \"memory\"").unwrap_err(),
+            "line 2: unknown sort 'memory' (expected a column like mem, optionally followed by asc or desc)"
+        );
+        assert_eq!(
+            Config::parse("[tui]\nremember = \"no\"").unwrap_err(),
+            "line 2: remember must be a boolean, not a string"
+        );
+        assert!(Config::parse("[tui]\nsort = \"mem\"")
+            .unwrap_err()
+            .starts_with("line 2: unknown key 'sort' in [tui]"));
+    }
+
+    #[test]
+    fn key_bindings() {
+        let config = Config::parse("[keys]\npreset = \"arrows\"\nkill = \"x\"").unwrap();
+        let keymap = config.keymap().unwrap();
+        assert_eq!(keymap.action(KeyCode::Char('x')), Some(Action::Kill));
+        assert_eq!(keymap.action(KeyCode::Char('j')), None);
+        assert_eq!(
+            Config::parse("[keys]\nnuke = \"x\"").unwrap_err(),
+            "line 2: unknown action 'nuke'"
+        );
+        assert_eq!(
+            Config::parse("[keys]\nkill = \"q\"").unwrap_err(),
+            "[keys] 'q' is bound to both kill and quit"
+        );
+        assert_eq!(
+            Config::parse("[keys]\nkill = true").unwrap_err(),
+            "line 2: kill must be a string, not a boolean"
+        );
+    }
+}
@@ -0,0 +1,198 @@
+use std::sync::OnceLock;
+
+use crate::config;
+
+// ── Kill confirmation ────────────────────────────────────────────────
+//
+// How much a kill asks first, tuned per team. `--no-confirm` (or
+// `[kill] confirm = false`) drops the TUI's y/n popup, so `d` kills at
+// once. `--confirm-force` (or `[kill] confirm_force = true`) goes the
+// other way for SIGKILL: the PID has to be typed back, in the TUI, at the
+// inspect prompt and for `kill --force`. Both together mean SIGTERM is
+// one key and SIGKILL still needs the PID.
+//
+// Whatever those say, an interactive SIGKILL of a process owned by root
+// or another account needs its port or PID typed back, so a stray `D` on
+// a shared box can't take down someone else's service. `[kill]
+// confirm_shared = false` turns that off.
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub(crate) struct Confirm {
+    /// Ask y/n before a TUI kill.
+    pub(crate) kill: bool,
+    /// Make a force kill type the PID back.
+    pub(crate) typed_force: bool,
+    /// Make a force kill of root's or another user's process type the
+    /// port or PID back.
+    pub(crate) shared_force: bool,
+}
+
+impl Default for Confirm {
+    fn default() -> Self {
+        Self {
+            kill: true,
+            typed_force: false,
+            shared_force: true,
+        }
+    }
+}
+
+/// What an interactive kill must have typed back before it goes ahead.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub(crate) enum TypeBack {
+    /// The PID, for `--confirm-force`.
+    Pid,
+    /// The port or the PID, for a process that isn't the user's own.
+    PortOrPid,
+}
+
+impl TypeBack {
+    /// `the PID`, for prompts.
+    pub(crate) fn what(self) -> &'static str {
+        match self {
+            TypeBack::Pid => "the PID",
+            TypeBack::PortOrPid => "the port or PID",
+        }
+    }
+
+    pub(crate) fn matches(self, input: &str, pid: u32, port: u16) -> bool {
+        match self {
+            TypeBack::Pid => pid_matches(input, pid),
+            TypeBack::PortOrPid => {
+                pid_matches(input, pid) || input.trim().parse::<u16>() == Ok(port)
+            }
+        }
+    }
+}
+
+impl Confirm {
+    /// The command-line flags, falling back to the config file.
+    pub(crate) fn resolve(no_confirm: bool, confirm_force: bool, config: &config::Config) -> Self {
+        Self {
+            kill: !no_confirm && config.kill_confirm.unwrap_or(true),
+            typed_force: confirm_force || config.kill_confirm_force.unwrap_or(false),
+            shared_force: config.kill_confirm_shared.unwrap_or(true),
+        }
+    }
+
+    /// What an interactive kill of a process owned by `owner` must have
+    /// typed back, when portview runs as `me`. `--confirm-force` wants the
+    /// PID whoever owns the process; the port only stands in for it when
+    /// the shared-process check alone applies.
+    pub(crate) fn type_back(&self, force: bool, owner: &str, me: &str) -> Option<TypeBack> {
+        if !force {
+            None
+        } else if self.typed_force {
+            Some(TypeBack::Pid)
+        } else if self.shared_force && !owns(owner, me) {
+            Some(TypeBack::PortOrPid)
+        } else {
+            None
+        }
+    }
+
+    /// Whether a kill that wants `type_back` must wait for the user.
+    pub(crate) fn asks(&self, type_back: Option<TypeBack>) -> bool {
+        self.kill || type_back.is_some()
+    }
+}
+
+static CONFIRM: OnceLock<Confirm> = OnceLock::new();
+
+pub(crate) fn install(confirm: Confirm) {
+    let _ = CONFIRM.set(confirm);
+}
+
+pub(crate) fn active() -> Confirm {
+    CONFIRM.get().copied().unwrap_or_default()
+}
+
+/// Whether `me` owns a process run by `owner`. An unknown owner, and a
+/// system account even when portview runs as it, count as someone else.
+fn owns(owner: &str, me: &str) -> bool {
+    let account = owner.rsplit('\\').next().unwrap_or(owner);
+    let system = ["root", "SYSTEM", "LOCAL SERVICE", "NETWORK SERVICE"]
+        .iter()
+        .any(|s| account.eq_ignore_ascii_case(s));
+    !owner.is_empty() && owner == me && !system
+}
+
+/// Whether `input` is `pid`, typed back to confirm a force kill.
+pub(crate) fn pid_matches(input: &str, pid: u32) -> bool {
+    input.trim().parse::<u32>() == Ok(pid)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn flags_override_the_config() {
+        let config = config::Config::parse("[kill]\nconfirm = false").unwrap();
+        let confirm = Confirm::resolve(false, false, &config);
+        assert!(!confirm.kill);
+        assert!(!confirm.asks(confirm.type_back(true, "alice", "alice")));
+
+        let confirm = Confirm::resolve(true, true, &config::Config::default());
+        assert!(!confirm.asks(confirm.type_back(false, "alice", "alice")));
+        assert!(confirm.asks(confirm.type_back(true, "alice", "alice")));
+        assert!(confirm.shared_force);
+
+        let config = config::Config::parse("[kill]\nconfirm_shared = false").unwrap();
+        assert!(!Confirm::resolve(false, false, &config).shared_force);
+
+        let config = config::Config::parse("[kill]\nconfirm_force = true").unwrap();
+        assert!(Confirm::resolve(false, false, &config).typed_force);
+        assert_eq!(
+            Confirm::resolve(false, false, &config::Config::default()),
+            Confirm::default()
+        );
+    }
+
+    #[test]
+    fn other_users_processes_need_the_port_or_pid() {
+        let confirm = Confirm::default();
+        assert_eq!(confirm.type_back(false, "postgres", "alice"), None);
+        assert_eq!(confirm.type_back(true, "alice", "alice"), None);
+        for owner in ["postgres", "root", "", "NT AUTHORITY\\SYSTEM"] {
+            assert_eq!(
+                confirm.type_back(true, owner, "alice"),
+                Some(TypeBack::PortOrPid),
+                "{}",
+                owner
+            );
+        }
+        assert_eq!(
+            confirm.type_back(true, "root", "root"),
+            Some(TypeBack::PortOrPid)
+        );
+
+        let typed = Confirm {
+            typed_force: true,
+            shared_force: false,
+            ..Confirm::default()
+        };
+        assert_eq!(typed.type_back(true, "root", "alice"), Some(TypeBack::Pid));
+        // With both on, the stricter PID wins for someone else's process too
+        let both = Confirm {
+            typed_force: true,
+            ..Confirm::default()
+        };
+        assert_eq!(
+            both.type_back(true, "postgres", "alice"),
+            Some(TypeBack::Pid)
+        );
+        assert_eq!(both.type_back(true, "alice", "alice"), Some(TypeBack::Pid));
+
+        assert!(TypeBack::PortOrPid.matches("5432\n", 1203, 5432));
+        assert!(TypeBack::PortOrPid.matches("1203", 1203, 5432));
+        assert!(!TypeBack::Pid.matches("5432", 1203, 5432));
+    }
+
+    #[test]
+    fn typed_pid() {
+        assert!(pid_matches(" 4242\n", 4242));
+        assert!(!pid_matches("4243", 4242));
+        assert!(!pid_matches("y", 4242));
+    }
+}
@@ -0,0 +1,92 @@
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+use crate::JSON_SCHEMA_VERSION;
+
+// ── Status file ──────────────────────────────────────────────────────
+//
+// `portview daemon` samples in the background and keeps the latest
+// snapshot in one JSON file, so an editor or status bar can read current
+// port state without running a scan of its own. Each write goes to a
+// temporary file beside the target and is renamed over it, so readers
+// never see half a snapshot.
+
+/// `$XDG_RUNTIME_DIR/portview/status.json`, or a per-user file in the
+/// temp directory when there's no runtime directory.
+pub(crate) fn default_path() -> PathBuf {
+    match std::env::var_os("XDG_RUNTIME_DIR").filter(|d| !d.is_empty()) {
+        Some(dir) => Path::new(&dir).join("portview").join("status.json"),
+        None => std::env::temp_dir().join(format!("portview-{}-status.json", user_tag())),
+    }
+}
+
+#[cfg(unix)]
+fn user_tag() -> String {
+    unsafe { libc::geteuid() }.to_string()
+}
+
+#[cfg(not(unix))]
+fn user_tag() -> String {
+    std::env::var("USERNAME").unwrap_or_else(|_| "user".to_string())
+}
+
+/// The file's contents: when it was sampled, how often to expect the
+/// next one, and the ports as `--json` prints them.
+pub(crate) fn snapshot_json(ports_json: &str, at: SystemTime, interval: Duration) -> String {
+    format!(
+        r#"{{"schema_version":{},"updated":{},"interval_ms":{},"daemon_pid":{},"ports":{}}}"#,
+        JSON_SCHEMA_VERSION,
+        at.duration_since(UNIX_EPOCH).map_or(0, |d| d.as_secs()),
+        interval.as_millis(),
+        std::process::id(),
+        ports_json
+    )
+}
+
+/// Replace `path` with `contents` in one rename.
+pub(crate) fn write_atomic(path: &Path, contents: &str) -> io::Result<()> {
+    if let Some(dir) = path.parent().filter(|d| !d.as_os_str().is_empty()) {
+        fs::create_dir_all(dir)?;
+    }
+    let name = path
+        .file_name()
+        .ok_or_else(|| io::Error::new(io::ErrorKind::InvalidInput, "not a file path"))?;
+    let mut tmp_name = std::ffi::OsString::from(".");
+    tmp_name.push(name);
+    tmp_name.push(format!(".{}.tmp", std::process::id()));
+    let tmp = path.with_file_name(tmp_name);
+    fs::write(&tmp, contents)?;
+    fs::rename(&tmp, path).inspect_err(|_| {
+        let _ = fs::remove_file(&tmp);
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn snapshot_shape() {
+        let at = UNIX_EPOCH + Duration::from_secs(1_700_000_000);
+        let json = snapshot_json("[]", at, Duration::from_secs(2));
+        assert!(json.starts_with(&format!(
+            r#"{{"schema_version":{},"updated":1700000000,"interval_ms":2000,"daemon_pid":"#,
+            JSON_SCHEMA_VERSION
+        )));
+        assert!(json.ends_with(r#","ports":[]}"#));
+    }
+
+    #[test]
+    fn write_replaces_whole_file() {
+        let dir = std::env::temp_dir().join(format!("portview-daemon-test-{}", std::process::id()));
+        let path = dir.join("nested").join("status.json");
+        write_atomic(&path, "first").unwrap();
+        write_atomic(&path, "second").unwrap();
+        assert_eq!(fs::read_to_string(&path).unwrap(), "second");
+        let leftovers = fs::read_dir(path.parent().unwrap()).unwrap().count();
+        assert_eq!(leftovers, 1);
+        fs::remove_dir_all(dir).unwrap();
+    }
+}
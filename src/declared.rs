@@ -0,0 +1,289 @@
+use std::path::Path;
+
+use crate::config;
+use crate::expect::Manifest;
+
+// ── Declared ports ───────────────────────────────────────────────────
+//
+// The ports a project says it will use, read from the files that usually
+// name them. None of these formats is parsed properly (there's no YAML or
+// JSON parser here); each is scanned for the handful of shapes a port
+// takes in it, which is enough to tell `portview here` and the shell hook
+// what to look for.
+
+/// A port a project says it will use, and where it says so.
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) struct DeclaredPort {
+    pub(crate) port: u16,
+    /// The file, plus the service or process within it when there is one:
+    /// `.env`, `Procfile (web)`.
+    pub(crate) source: String,
+}
+
+const COMPOSE_FILES: &[&str] = &[
+    "compose.yaml",
+    "compose.yml",
+    "docker-compose.yaml",
+    "docker-compose.yml",
+];
+
+/// Every file [`declared_ports`] looks at, for "nothing found" messages.
+pub(crate) const SOURCES: &str = ".portview.toml, .env, package.json, docker-compose.yml, Procfile";
+
+/// `PORT=3000` in a dotenv file, with or without `export` and quotes.
+pub(crate) fn env_port(text: &str) -> Option<u16> {
+    text.lines().find_map(|line| {
+        let line = line.trim();
+        let line = line.strip_prefix("export ").unwrap_or(line);
+        let (key, value) = line.split_once('=')?;
+        if key.trim() != "PORT" {
+            return None;
+        }
+        value
+            .trim()
+            .trim_matches(|c| c == '"' || c == '\'')
+            .parse()
+            .ok()
+            .filter(|&p| p != 0)
+    })
+}
+
+/// Ports after `--port`, `-p` or `PORT=` in a command line.
+fn command_ports(text: &str, markers: &[&str]) -> Vec<u16> {
+    let mut ports = Vec::new();
+    for marker in markers {
+        for (at, _) in text.match_indices(marker) {
+            let rest = text[at + marker.len()..].trim_start_matches([' ', ':', '=']);
+            let digits: String = rest.chars().take_while(char::is_ascii_digit).collect();
+            if let Ok(port @ 1..) = digits.parse::<u16>() {
+                if !ports.contains(&port) {
+                    ports.push(port);
+                }
+            }
+        }
+    }
+    ports
+}
+
+/// Ports named in a package.json: `"port": 3000` (e.g. under `config`)
+/// and `--port 3000`, `-p 3000` or `PORT=3000` in scripts. A text scan,
+/// so it doesn't care where in the file they appear.
+pub(crate) fn package_json_ports(text: &str) -> Vec<u16> {
+    command_ports(text, &["\"port\"", "--port", "-p ", "PORT="])
+}
+
+/// `web: bundle exec rails s -p 3000` → [("web", 3000)].
+pub(crate) fn procfile_ports(text: &str) -> Vec<(String, u16)> {
+    text.lines()
+        .filter_map(|line| line.split_once(':'))
+        .filter(|(name, _)| !name.trim().is_empty() && !name.trim_start().starts_with('#'))
+        .flat_map(|(name, command)| {
+            command_ports(command, &["--port", "-p ", "PORT="])
+                .into_iter()
+                .map(move |port| (name.trim().to_string(), port))
+        })
+        .collect()
+}
+
+/// The host side of a compose `ports:` entry: `8080` in `"8080:80"`,
+/// `127.0.0.1:8080:80` or `8080:80/udp`, the first port of a range.
+/// `None` for a bare container port, which Docker publishes on a random
+/// host port.
+fn compose_host_port(entry: &str) -> Option<u16> {
+    let entry = entry.trim().trim_matches(|c| c == '"' || c == '\'');
+    let entry = entry.split('/').next()?;
+    let mut parts = entry.rsplit(':');
+    parts.next()?;
+    let host = parts.next()?;
+    host.split('-').next()?.parse().ok().filter(|&p| p != 0)
+}
+
+/// Host ports published in a compose file, with their service: both the
+/// short `- "8080:80"` syntax and the long syntax's `published: 8080`.
+pub(crate) fn compose_ports(text: &str) -> Vec<(String, u16)> {
+    let mut found = Vec::new();
+    let mut services_indent = None;
+    let mut service_indent = None;
+    let mut service = String::new();
+    let mut ports_indent = None;
+    for line in text.lines() {
+        let trimmed = line.trim();
+        if trimmed.is_empty() || trimmed.starts_with('#') {
+            continue;
+        }
+        let indent = line.len() - line.trim_start().len();
+
+        // List items may sit at the same indent as their key
+        if let Some(pi) = ports_indent {
+            if indent > pi || (indent == pi && trimmed.starts_with('-')) {
+                let item = trimmed.trim_start_matches('-').trim();
+                let port = match item.split_once(": ") {
+                    Some(("published", value)) => value
+                        .trim()
+                        .trim_matches(|c| c == '"' || c == '\'')
+                        .parse()
+                        .ok(),
+                    Some(_) => None,
+                    None => compose_host_port(item),
+                };
+                found.extend(port.map(|p| (service.clone(), p)));
+                continue;
+            }
+            ports_indent = None;
+        }
+
+        if trimmed == "services:" {
+            services_indent = Some(indent);
+            service_indent = None;
+            continue;
+        }
+        if let Some(si) = services_indent {
+            if indent <= si {
+                services_indent = None;
+            } else if service_indent.is_none_or(|s| s == indent) && trimmed.ends_with(':') {
+                service_indent = Some(indent);
+                service = trimmed.trim_end_matches(':').trim_matches('"').to_string();
+                continue;
+            }
+        }
+        if trimmed == "ports:" {
+            ports_indent = Some(indent);
+        }
+    }
+    found
+}
+
+/// What the project in `dir` declares, first source winning for a port
+/// named twice. Unreadable or malformed files are skipped.
+pub(crate) fn declared_ports(dir: &Path) -> Vec<DeclaredPort> {
+    let read = |name: &str| std::fs::read_to_string(dir.join(name)).ok();
+    let mut found: Vec<DeclaredPort> = Vec::new();
+    let mut add = |port: u16, source: String| {
+        if !found.iter().any(|d| d.port == port) {
+            found.push(DeclaredPort { port, source });
+        }
+    };
+    let with_name = |file: &str, name: &str| {
+        if name.is_empty() {
+            file.to_string()
+        } else {
+            format!("{} ({})", file, name)
+        }
+    };
+
+    if let Some(manifest) = read(config::PROJECT_FILE).and_then(|t| Manifest::parse(&t).ok()) {
+        for service in &manifest.services {
+            add(service.port, with_name(config::PROJECT_FILE, &service.name));
+        }
+    }
+    for name in [".env", ".env.local"] {
+        if let Some(port) = read(name).as_deref().and_then(env_port) {
+            add(port, name.to_string());
+        }
+    }
+    for port in read("package.json")
+        .as_deref()
+        .map_or_else(Vec::new, package_json_ports)
+    {
+        add(port, "package.json".to_string());
+    }
+    for file in COMPOSE_FILES {
+        for (service, port) in read(file).as_deref().map_or_else(Vec::new, compose_ports) {
+            add(port, with_name(file, &service));
+        }
+    }
+    for (process, port) in read("Procfile")
+        .as_deref()
+        .map_or_else(Vec::new, procfile_ports)
+    {
+        add(port, with_name("Procfile", &process));
+    }
+    found
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn dotenv_port() {
+        assert_eq!(env_port("NODE_ENV=dev\nPORT=3000\n"), Some(3000));
+        assert_eq!(env_port("export PORT=\"8080\""), Some(8080));
+        assert_eq!(env_port("DB_PORT=5432\n# PORT=1"), None);
+    }
+
+    #[test]
+    fn package_json_scan() {
+        let text = r#"{
+  "config": { "port": 4000 },
+  "scripts": {
+    "build": "mkdir -p dist && tsc -p tsconfig.json",
+    "dev": "next dev --port 3001",
+    "api": "PORT=8080 node api.js",
+    "serve": "vite --port=3001"
+  }
+}"#;
+        assert_eq!(package_json_ports(text), vec![4000, 3001, 8080]);
+        assert!(package_json_ports(r#"{"scripts":{"dev":"vite"}}"#).is_empty());
+    }
+
+    #[test]
+    fn procfile_scan() {
+        let text = "web: bundle exec rails server -p 3000\nworker: bundle exec sidekiq\n# api: node api.js --port 1\n";
+        assert_eq!(procfile_ports(text), vec![("web".to_string(), 3000)]);
+    }
+
+    #[test]
+    fn compose_scan() {
+        let text = r#"
+services:
+  web:
+    image: nginx
+    ports:
+      - "8080:80"
+      - 127.0.0.1:8443:443/tcp
+      - "9229"
+    environment:
+      - "DEBUG=1:2"
+  db:
+    image: postgres
+    ports:
+    - target: 5432
+      published: "5433"
+volumes:
+  data:
+"#;
+        assert_eq!(
+            compose_ports(text),
+            vec![
+                ("web".to_string(), 8080),
+                ("web".to_string(), 8443),
+                ("db".to_string(), 5433),
+            ]
+        );
+    }
+
+    #[test]
+    fn declared_in_project_dir() {
+        let dir = std::env::temp_dir().join(format!("portview-declared-{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        std::fs::write(dir.join(".env"), "PORT=3000\n").unwrap();
+        std::fs::write(
+            dir.join("package.json"),
+            r#"{"scripts":{"dev":"PORT=3000 vite --port 5173"}}"#,
+        )
+        .unwrap();
+        std::fs::write(dir.join("Procfile"), "web: node server.js --port 4000\n").unwrap();
+        let found = declared_ports(&dir);
+        std::fs::remove_dir_all(&dir).unwrap();
+        let found: Vec<(u16, &str)> = found.iter().map(|d| (d.port, d.source.as_str())).collect();
+        assert_eq!(
+            found,
+            [
+                (3000, ".env"),
+                (5173, "package.json"),
+                (4000, "Procfile (web)")
+            ]
+        );
+    }
+}
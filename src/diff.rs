@@ -0,0 +1,249 @@
+use std::collections::BTreeMap;
+use std::io::{self, Write};
+
+use crate::{json_escape, write_styled, PortInfo, TcpState};
+
+// ── Snapshot diff ────────────────────────────────────────────────────
+//
+// `portview diff a.json b.json` compares two saved `--json` outputs. A
+// listener is its port, protocol and state; it's changed when the
+// processes holding it differ. CPU and memory always move, so they're
+// left out.
+
+/// Who holds a listener: PID and process name.
+pub(crate) type Owner = (u32, String);
+
+type Key = (u16, String, &'static str);
+
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) enum Change {
+    Added(Listener),
+    Removed(Listener),
+    Changed { before: Listener, after: Listener },
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) struct Listener {
+    pub(crate) port: u16,
+    pub(crate) protocol: String,
+    pub(crate) state: TcpState,
+    /// Sorted, so two snapshots compare equal whatever their row order.
+    pub(crate) owners: Vec<Owner>,
+}
+
+impl Listener {
+    fn label(&self) -> String {
+        let mut label = format!("{}/{}", self.port, self.protocol);
+        if self.state != TcpState::Listen {
+            label.push_str(&format!(" {}", self.state));
+        }
+        label
+    }
+
+    fn owners_text(&self) -> String {
+        let owners: Vec<String> = self
+            .owners
+            .iter()
+            .map(|(pid, name)| {
+                if *pid == 0 {
+                    "-".to_string()
+                } else {
+                    format!("{} (pid {})", name, pid)
+                }
+            })
+            .collect();
+        owners.join(", ")
+    }
+}
+
+fn group(infos: &[PortInfo]) -> BTreeMap<Key, Listener> {
+    let mut map: BTreeMap<Key, Listener> = BTreeMap::new();
+    for info in infos {
+        let key = (info.port, info.protocol.clone(), info.state.as_str());
+        let listener = map.entry(key).or_insert_with(|| Listener {
+            port: info.port,
+            protocol: info.protocol.clone(),
+            state: info.state,
+            owners: Vec::new(),
+        });
+        let owner = (info.pid, info.process_name.clone());
+        if !listener.owners.contains(&owner) {
+            listener.owners.push(owner);
+        }
+    }
+    for listener in map.values_mut() {
+        listener.owners.sort();
+    }
+    map
+}
+
+/// What changed from `before` to `after`, by port. With `ports_only`, a
+/// listener that changed hands isn't reported.
+pub(crate) fn diff(before: &[PortInfo], after: &[PortInfo], ports_only: bool) -> Vec<Change> {
+    let before = group(before);
+    let mut after = group(after);
+    let mut changes = Vec::new();
+    for (key, old) in before {
+        match after.remove(&key) {
+            None => changes.push(Change::Removed(old)),
+            Some(new) if !ports_only && new.owners != old.owners => changes.push(Change::Changed {
+                before: old,
+                after: new,
+            }),
+            Some(_) => {}
+        }
+    }
+    changes.extend(after.into_values().map(Change::Added));
+    changes.sort_by_key(|c| {
+        let l = match c {
+            Change::Added(l) | Change::Removed(l) => l,
+            Change::Changed { after, .. } => after,
+        };
+        (l.port, l.protocol.clone())
+    });
+    changes
+}
+
+// ── Output ───────────────────────────────────────────────────────────
+
+pub(crate) fn display_diff(changes: &[Change], use_color: bool) {
+    let mut out = io::stdout();
+    if changes.is_empty() {
+        write_styled(&mut out, "No differences.\n", "dimmed", use_color);
+        return;
+    }
+    let label_w = changes
+        .iter()
+        .map(|c| match c {
+            Change::Added(l) | Change::Removed(l) => l.label().len(),
+            Change::Changed { after, .. } => after.label().len(),
+        })
+        .max()
+        .unwrap_or(0);
+    let _ = writeln!(out);
+    let (mut added, mut removed, mut changed) = (0, 0, 0);
+    for change in changes {
+        let (mark, color, listener, text) = match change {
+            Change::Added(l) => {
+                added += 1;
+                ("+", "green", l, l.owners_text())
+            }
+            Change::Removed(l) => {
+                removed += 1;
+                ("-", "red", l, l.owners_text())
+            }
+            Change::Changed { before, after } => {
+                changed += 1;
+                (
+                    "~",
+                    "yellow",
+                    after,
+                    format!("{} → {}", before.owners_text(), after.owners_text()),
+                )
+            }
+        };
+        let _ = write!(out, "  ");
+        write_styled(
+            &mut out,
+            &format!("{} {:<w$}", mark, listener.label(), w = label_w),
+            color,
+            use_color,
+        );
+        let _ = writeln!(out, "  {}", text);
+    }
+    let _ = writeln!(out);
+    let _ = writeln!(
+        out,
+        "{} added, {} removed, {} changed.",
+        added, removed, changed
+    );
+}
+
+fn listener_json(l: &Listener) -> String {
+    let owners: Vec<String> = l
+        .owners
+        .iter()
+        .map(|(pid, name)| format!(r#"{{"pid":{},"process":"{}"}}"#, pid, json_escape(name)))
+        .collect();
+    format!(
+        r#"{{"port":{},"protocol":"{}","state":"{}","owners":[{}]}}"#,
+        l.port,
+        json_escape(&l.protocol),
+        l.state,
+        owners.join(",")
+    )
+}
+
+pub(crate) fn diff_json(changes: &[Change]) -> String {
+    let pick = |f: fn(&Change) -> Option<String>| -> String {
+        changes.iter().filter_map(f).collect::<Vec<_>>().join(",")
+    };
+    let added = pick(|c| match c {
+        Change::Added(l) => Some(listener_json(l)),
+        _ => None,
+    });
+    let removed = pick(|c| match c {
+        Change::Removed(l) => Some(listener_json(l)),
+        _ => None,
+    });
+    let changed = pick(|c| match c {
+        Change::Changed { before, after } => Some(format!(
+            r#"{{"before":{},"after":{}}}"#,
+            listener_json(before),
+            listener_json(after)
+        )),
+        _ => None,
+    });
+    format!(
+        r#"{{"same":{},"added":[{}],"removed":[{}],"changed":[{}]}}"#,
+        changes.is_empty(),
+        added,
+        removed,
+        changed
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn info(port: u16, pid: u32, process: &str) -> PortInfo {
+        PortInfo::listener(port, pid, process).user("mark")
+    }
+
+    #[test]
+    fn added_removed_changed() {
+        let before = [info(3000, 10, "node"), info(5432, 20, "postgres")];
+        let after = [
+            info(8080, 30, "python3"),
+            info(3000, 11, "node"),
+            PortInfo {
+                memory_bytes: 1 << 30,
+                ..info(3000, 11, "node")
+            },
+        ];
+        let changes = diff(&before, &after, false);
+        assert_eq!(changes.len(), 3);
+        assert!(matches!(&changes[0], Change::Changed { before, after }
+            if before.owners == [(10, "node".to_string())] && after.owners == [(11, "node".to_string())]));
+        assert!(matches!(&changes[1], Change::Removed(l) if l.port == 5432));
+        assert!(matches!(&changes[2], Change::Added(l) if l.port == 8080));
+
+        let ports = diff(&before, &after, true);
+        assert_eq!(ports.len(), 2);
+        assert!(diff(&before, &before, false).is_empty());
+    }
+
+    #[test]
+    fn json_shape() {
+        let changes = diff(&[], &[info(8080, 30, "python3")], false);
+        assert_eq!(
+            diff_json(&changes),
+            r#"{"same":false,"added":[{"port":8080,"protocol":"TCP","state":"LISTEN","owners":[{"pid":30,"process":"python3"}]}],"removed":[],"changed":[]}"#
+        );
+        assert_eq!(
+            diff_json(&[]),
+            r#"{"same":true,"added":[],"removed":[],"changed":[]}"#
+        );
+    }
+}
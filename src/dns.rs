@@ -0,0 +1,386 @@
+use std::collections::{HashMap, HashSet};
+use std::net::IpAddr;
+use std::sync::{Mutex, OnceLock};
+use std::time::{Duration, Instant};
+
+// ── Reverse-DNS cache ────────────────────────────────────────────────
+
+/// How long a one-shot command waits for PTR lookups before printing raw IPs.
+pub(crate) const LOOKUP_TIMEOUT: Duration = Duration::from_millis(800);
+
+/// Lookups started per call; the rest are picked up on the next refresh.
+const MAX_CONCURRENT_LOOKUPS: usize = 32;
+
+#[derive(Default)]
+struct Cache {
+    /// `None` records a finished lookup with no PTR record, so it isn't retried.
+    names: HashMap<IpAddr, Option<String>>,
+    pending: HashSet<IpAddr>,
+}
+
+fn cache() -> &'static Mutex<Cache> {
+    static CACHE: OnceLock<Mutex<Cache>> = OnceLock::new();
+    CACHE.get_or_init(|| Mutex::new(Cache::default()))
+}
+
+/// Resolve `addrs` in background threads, waiting at most `timeout` for them.
+///
+/// Results land in a process-wide cache, so lookups that outlive the timeout
+/// still show up on the next call (the TUI passes a zero timeout and picks
+/// names up on later refreshes). Addresses with no PTR record are omitted.
+pub(crate) fn resolve_all(addrs: &[IpAddr], timeout: Duration) -> HashMap<IpAddr, String> {
+    let mut waiting = Vec::new();
+    if let Ok(mut c) = cache().lock() {
+        for &addr in addrs {
+            if waiting.len() >= MAX_CONCURRENT_LOOKUPS {
+                break;
+            }
+            if addr.is_unspecified() || c.names.contains_key(&addr) {
+                continue;
+            }
+            if c.pending.insert(addr) {
+                std::thread::spawn(move || {
+                    let name = lookup(addr);
+                    if let Ok(mut c) = cache().lock() {
+                        c.pending.remove(&addr);
+                        c.names.insert(addr, name);
+                    }
+                });
+            }
+            waiting.push(addr);
+        }
+    }
+
+    let deadline = Instant::now() + timeout;
+    while !waiting.is_empty() && Instant::now() < deadline {
+        std::thread::sleep(Duration::from_millis(10));
+        if let Ok(c) = cache().lock() {
+            waiting.retain(|a| c.pending.contains(a));
+        }
+    }
+
+    let Ok(c) = cache().lock() else {
+        return HashMap::new();
+    };
+    addrs
+        .iter()
+        .filter_map(|a| {
+            c.names
+                .get(a)
+                .and_then(|n| n.clone())
+                .map(|name| (*a, name))
+        })
+        .collect()
+}
+
+// ── Address scope ────────────────────────────────────────────────────
+
+/// Coarse "where is this peer" hint that needs no lookup: loopback, LAN
+/// (private, link-local, unique-local) or `None` for public addresses.
+pub(crate) fn scope_hint(addr: &IpAddr) -> Option<&'static str> {
+    match addr {
+        _ if addr.is_loopback() => Some("loopback"),
+        IpAddr::V4(v4) if v4.is_private() || v4.is_link_local() => Some("LAN"),
+        // 100.64.0.0/10: carrier-grade NAT, also used by Tailscale
+        IpAddr::V4(v4) if v4.octets()[0] == 100 && (v4.octets()[1] & 0xc0) == 64 => Some("CGNAT"),
+        IpAddr::V6(v6) if (v6.segments()[0] & 0xfe00) == 0xfc00 => Some("LAN"),
+        IpAddr::V6(v6) if (v6.segments()[0] & 0xffc0) == 0xfe80 => Some("LAN"),
+        _ => None,
+    }
+}
+
+/// Even coarser hint for public addresses, also without a lookup: the
+/// region of the registry IANA handed the address block to. Blocks move
+/// between countries after allocation, so this is a rough guide only.
+pub(crate) fn region_hint(addr: &IpAddr) -> Option<&'static str> {
+    if scope_hint(addr).is_some() {
+        return None;
+    }
+    let registry = match addr {
+        IpAddr::V4(v4) => {
+            let [a, b, c, _] = v4.octets();
+            // Documentation ranges (RFC 5737)
+            if matches!((a, b, c), (192, 0, 2) | (198, 51, 100) | (203, 0, 113)) {
+                return None;
+            }
+            v4_registry(a)?
+        }
+        IpAddr::V6(v6) => {
+            let [first, second, ..] = v6.segments();
+            // Documentation range (RFC 3849)
+            if (first, second) == (0x2001, 0x0db8) {
+                return None;
+            }
+            v6_registry(first, second)?
+        }
+    };
+    Some(match registry {
+        Registry::Afrinic => "Africa",
+        Registry::Apnic => "Asia-Pacific",
+        Registry::Arin => "North America",
+        Registry::Lacnic => "Latin America",
+        Registry::Ripe => "Europe",
+    })
+}
+
+enum Registry {
+    Afrinic,
+    Apnic,
+    Arin,
+    Lacnic,
+    Ripe,
+}
+
+/// The registry responsible for an IPv4 /8, from IANA's IPv4 address space
+/// registry. Legacy blocks count for the registry that administers them.
+fn v4_registry(first_octet: u8) -> Option<Registry> {
+    Some(match first_octet {
+        0 | 10 | 127 | 224..=255 => return None,
+        41 | 102 | 105 | 154 | 196 | 197 => Registry::Afrinic,
+        1
+        | 14
+        | 27
+        | 36
+        | 39
+        | 42
+        | 43
+        | 49
+        | 58..=61
+        | 101
+        | 103
+        | 106
+        | 110..=126
+        | 133
+        | 150
+        | 153
+        | 163
+        | 171
+        | 175
+        | 180
+        | 182
+        | 183
+        | 202
+        | 203
+        | 210
+        | 211
+        | 218..=223 => Registry::Apnic,
+        177 | 179 | 181 | 186 | 187 | 189..=191 | 200 | 201 => Registry::Lacnic,
+        2
+        | 5
+        | 25
+        | 31
+        | 37
+        | 46
+        | 51
+        | 53
+        | 57
+        | 62
+        | 77..=95
+        | 109
+        | 141
+        | 145
+        | 151
+        | 176
+        | 178
+        | 185
+        | 188
+        | 193..=195
+        | 212
+        | 213
+        | 217 => Registry::Ripe,
+        _ => Registry::Arin,
+    })
+}
+
+/// The registry for a global unicast IPv6 address, from IANA's IPv6
+/// unicast assignments: /12s from 2400::, plus the older /23s in 2001::.
+fn v6_registry(first: u16, second: u16) -> Option<Registry> {
+    Some(match (first, second) {
+        (0x2001, 0x0200..=0x03ff | 0x0c00..=0x0fff | 0x4400..=0x45ff | 0x8000..=0xbfff) => {
+            Registry::Apnic
+        }
+        (0x2001, 0x0400..=0x05ff | 0x1800..=0x19ff | 0x4800..=0x49ff) => Registry::Arin,
+        (0x2001, 0x1200..=0x13ff) => Registry::Lacnic,
+        (0x2001, 0x4200..=0x43ff) => Registry::Afrinic,
+        (
+            0x2001,
+            0x0600..=0x0bff | 0x1400..=0x17ff | 0x1a00..=0x41ff | 0x4600..=0x47ff | 0x4a00..=0x5fff,
+        ) => Registry::Ripe,
+        (0x2003, 0x0000..=0x3fff) => Registry::Ripe,
+        (0x2400..=0x24ff, _) => Registry::Apnic,
+        (0x2600..=0x26ff, _) => Registry::Arin,
+        (0x2800..=0x28ff, _) => Registry::Lacnic,
+        (0x2a00..=0x2a1f, _) => Registry::Ripe,
+        (0x2c00..=0x2c0f, _) => Registry::Afrinic,
+        _ => return None,
+    })
+}
+
+// ── Platform lookup ──────────────────────────────────────────────────
+
+#[cfg(unix)]
+fn lookup(addr: IpAddr) -> Option<String> {
+    let mut host = [0 as libc::c_char; libc::NI_MAXHOST as usize];
+    let rc = match addr {
+        IpAddr::V4(v4) => {
+            let mut sa: libc::sockaddr_in = unsafe { std::mem::zeroed() };
+            sa.sin_family = libc::AF_INET as libc::sa_family_t;
+            sa.sin_addr.s_addr = u32::from_ne_bytes(v4.octets());
+            #[cfg(target_os = "macos")]
+            {
+                sa.sin_len = std::mem::size_of::<libc::sockaddr_in>() as u8;
+            }
+            unsafe {
+                libc::getnameinfo(
+                    &sa as *const _ as *const libc::sockaddr,
+                    std::mem::size_of::<libc::sockaddr_in>() as libc::socklen_t,
+                    host.as_mut_ptr(),
+                    host.len() as libc::socklen_t,
+                    std::ptr::null_mut(),
+                    0,
+                    libc::NI_NAMEREQD,
+                )
+            }
+        }
+        IpAddr::V6(v6) => {
+            let mut sa: libc::sockaddr_in6 = unsafe { std::mem::zeroed() };
+            sa.sin6_family = libc::AF_INET6 as libc::sa_family_t;
+            sa.sin6_addr.s6_addr = v6.octets();
+            #[cfg(target_os = "macos")]
+            {
+                sa.sin6_len = std::mem::size_of::<libc::sockaddr_in6>() as u8;
+            }
+            unsafe {
+                libc::getnameinfo(
+                    &sa as *const _ as *const libc::sockaddr,
+                    std::mem::size_of::<libc::sockaddr_in6>() as libc::socklen_t,
+                    host.as_mut_ptr(),
+                    host.len() as libc::socklen_t,
+                    std::ptr::null_mut(),
+                    0,
+                    libc::NI_NAMEREQD,
+                )
+            }
+        }
+    };
+    if rc != 0 {
+        return None;
+    }
+    let name = unsafe { std::ffi::CStr::from_ptr(host.as_ptr()) };
+    let name = name.to_string_lossy().trim_end_matches('.').to_string();
+    (!name.is_empty()).then_some(name)
+}
+
+#[cfg(windows)]
+fn lookup(addr: IpAddr) -> Option<String> {
+    use windows_sys::Win32::Networking::WinSock::{
+        getnameinfo, WSAStartup, AF_INET, AF_INET6, NI_MAXHOST, NI_NAMEREQD, SOCKADDR, SOCKADDR_IN,
+        SOCKADDR_IN6, WSADATA,
+    };
+
+    static WSA_INIT: std::sync::Once = std::sync::Once::new();
+    WSA_INIT.call_once(|| {
+        let mut data: WSADATA = unsafe { std::mem::zeroed() };
+        unsafe { WSAStartup(0x0202, &mut data) };
+    });
+
+    let mut host = [0u8; NI_MAXHOST as usize];
+    let rc = match addr {
+        IpAddr::V4(v4) => {
+            let mut sa: SOCKADDR_IN = unsafe { std::mem::zeroed() };
+            sa.sin_family = AF_INET;
+            sa.sin_addr.S_un.S_addr = u32::from_ne_bytes(v4.octets());
+            unsafe {
+                getnameinfo(
+                    &sa as *const _ as *const SOCKADDR,
+                    std::mem::size_of::<SOCKADDR_IN>() as i32,
+                    host.as_mut_ptr(),
+                    host.len() as u32,
+                    std::ptr::null_mut(),
+                    0,
+                    NI_NAMEREQD as i32,
+                )
+            }
+        }
+        IpAddr::V6(v6) => {
+            let mut sa: SOCKADDR_IN6 = unsafe { std::mem::zeroed() };
+            sa.sin6_family = AF_INET6;
+            sa.sin6_addr.u.Byte = v6.octets();
+            unsafe {
+                getnameinfo(
+                    &sa as *const _ as *const SOCKADDR,
+                    std::mem::size_of::<SOCKADDR_IN6>() as i32,
+                    host.as_mut_ptr(),
+                    host.len() as u32,
+                    std::ptr::null_mut(),
+                    0,
+                    NI_NAMEREQD as i32,
+                )
+            }
+        }
+    };
+    if rc != 0 {
+        return None;
+    }
+    let len = host.iter().position(|&b| b == 0).unwrap_or(host.len());
+    let name = String::from_utf8_lossy(&host[..len])
+        .trim_end_matches('.')
+        .to_string();
+    (!name.is_empty()).then_some(name)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::net::{Ipv4Addr, Ipv6Addr};
+
+    #[test]
+    fn scope_hint_classifies_ranges() {
+        let v4 = |a, b, c, d| IpAddr::V4(Ipv4Addr::new(a, b, c, d));
+        assert_eq!(scope_hint(&v4(127, 0, 0, 1)), Some("loopback"));
+        assert_eq!(scope_hint(&v4(192, 168, 1, 20)), Some("LAN"));
+        assert_eq!(scope_hint(&v4(10, 1, 2, 3)), Some("LAN"));
+        assert_eq!(scope_hint(&v4(169, 254, 0, 1)), Some("LAN"));
+        assert_eq!(scope_hint(&v4(100, 101, 0, 1)), Some("CGNAT"));
+        assert_eq!(scope_hint(&v4(100, 128, 0, 1)), None);
+        assert_eq!(scope_hint(&v4(8, 8, 8, 8)), None);
+        assert_eq!(
+            scope_hint(&IpAddr::V6(Ipv6Addr::LOCALHOST)),
+            Some("loopback")
+        );
+        assert_eq!(
+            scope_hint(&"fd00::1".parse::<IpAddr>().unwrap()),
+            Some("LAN")
+        );
+        assert_eq!(
+            scope_hint(&"fe80::1".parse::<IpAddr>().unwrap()),
+            Some("LAN")
+        );
+        assert_eq!(scope_hint(&"2001:db8::1".parse::<IpAddr>().unwrap()), None);
+    }
+
+    #[test]
+    fn region_hint_names_the_registry_region() {
+        let hint = |s: &str| region_hint(&s.parse::<IpAddr>().unwrap());
+        assert_eq!(hint("8.8.8.8"), Some("North America"));
+        assert_eq!(hint("193.0.6.139"), Some("Europe"));
+        assert_eq!(hint("1.1.1.1"), Some("Asia-Pacific"));
+        assert_eq!(hint("200.160.2.3"), Some("Latin America"));
+        assert_eq!(hint("196.216.2.1"), Some("Africa"));
+        assert_eq!(hint("2001:4860:4860::8888"), Some("North America"));
+        assert_eq!(hint("2a00:1450::1"), Some("Europe"));
+        assert_eq!(hint("2400:cb00::1"), Some("Asia-Pacific"));
+        // Loopback, LAN, documentation and unallocated space get no region
+        assert_eq!(hint("127.0.0.1"), None);
+        assert_eq!(hint("192.168.1.20"), None);
+        assert_eq!(hint("203.0.113.9"), None);
+        assert_eq!(hint("2001:db8::1"), None);
+        assert_eq!(hint("3000::1"), None);
+    }
+
+    #[test]
+    fn resolve_all_skips_unspecified() {
+        let names = resolve_all(&[IpAddr::V4(Ipv4Addr::UNSPECIFIED)], Duration::ZERO);
+        assert!(names.is_empty());
+    }
+}
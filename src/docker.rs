@@ -1,23 +1,279 @@
-use std::collections::HashMap;
-use std::process::Command;
+use std::collections::{HashMap, HashSet};
+use std::io::BufRead;
+use std::path::Path;
+use std::process::{Child, Command, Stdio};
+use std::sync::{Arc, Mutex, OnceLock};
+use std::time::{Duration, Instant};
 
-#[derive(Debug, Clone, PartialEq, Eq)]
+use crate::udpservice::DetailRows;
+use crate::PortInfo;
+
+#[derive(Debug, Clone, PartialEq)]
 pub(crate) struct DockerPortOwner {
     pub(crate) container_id: String,
     pub(crate) container_name: String,
     pub(crate) image: String,
     pub(crate) container_port: u16,
     pub(crate) protocol: String,
+    /// Docker's `State`: `running`, `restarting`, `paused`, …
+    pub(crate) state: String,
+    /// `healthy`, `unhealthy` or `starting` for containers with a
+    /// HEALTHCHECK.
+    pub(crate) health: Option<String>,
+    /// CPU and memory from `docker stats`, once [`attach_stats`] has run.
+    pub(crate) stats: Option<ContainerStats>,
+    /// Set for containers started by `docker compose`.
+    pub(crate) compose: Option<ComposeService>,
+}
+
+impl DockerPortOwner {
+    pub(crate) fn container_state(&self) -> ContainerState {
+        ContainerState::from_ps(&self.state, self.health.as_deref())
+    }
 }
 
 pub(crate) type DockerPortMap = HashMap<u16, Vec<DockerPortOwner>>;
 
+/// The compose project and service a container belongs to, from the
+/// `com.docker.compose.*` labels.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) struct ComposeService {
+    pub(crate) project: String,
+    pub(crate) service: String,
+    /// Where `docker compose up` ran; compose commands run from here.
+    pub(crate) working_dir: String,
+    pub(crate) config_files: Vec<String>,
+}
+
+// ── Container state ──────────────────────────────────────────────────
+
+/// State and health folded into one value, ordered from fine to broken so
+/// the worst of several containers on a port can be picked with `max`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub(crate) enum ContainerState {
+    Healthy,
+    /// Running with no HEALTHCHECK.
+    Running,
+    /// Health check hasn't passed yet.
+    Starting,
+    Paused,
+    Unhealthy,
+    Restarting,
+    /// Exited, dead, or anything else that isn't serving.
+    Down,
+}
+
+impl ContainerState {
+    fn from_ps(state: &str, health: Option<&str>) -> Self {
+        match (state, health) {
+            ("running", Some("healthy")) => ContainerState::Healthy,
+            ("running", Some("unhealthy")) => ContainerState::Unhealthy,
+            ("running", Some("starting")) => ContainerState::Starting,
+            ("running", _) => ContainerState::Running,
+            ("restarting", _) => ContainerState::Restarting,
+            ("paused", _) => ContainerState::Paused,
+            _ => ContainerState::Down,
+        }
+    }
+
+    /// Text for the HEALTH column.
+    pub(crate) fn as_str(self) -> &'static str {
+        match self {
+            ContainerState::Healthy => "healthy",
+            ContainerState::Running => "running",
+            ContainerState::Starting => "starting",
+            ContainerState::Paused => "paused",
+            ContainerState::Unhealthy => "unhealthy",
+            ContainerState::Restarting => "restarting",
+            ContainerState::Down => "down",
+        }
+    }
+
+    /// Color name for the one-shot table.
+    pub(crate) fn color(self) -> &'static str {
+        match self {
+            ContainerState::Healthy | ContainerState::Running => "green",
+            ContainerState::Starting | ContainerState::Paused => "yellow",
+            ContainerState::Unhealthy | ContainerState::Restarting | ContainerState::Down => "red",
+        }
+    }
+
+    /// Whether the container needs attention.
+    pub(crate) fn is_bad(self) -> bool {
+        self >= ContainerState::Unhealthy
+    }
+}
+
+/// The worst state among the containers publishing `port`.
+pub(crate) fn port_state(docker_map: &DockerPortMap, port: u16) -> Option<ContainerState> {
+    docker_map
+        .get(&port)?
+        .iter()
+        .map(DockerPortOwner::container_state)
+        .max()
+}
+
+impl DockerPortOwner {
+    /// `8080->80/tcp`, as `docker ps` writes it, for JSON; the table
+    /// uses `→`.
+    pub(crate) fn mapping(&self, host_port: u16, arrow: &str) -> String {
+        format!(
+            "{}{}{}/{}",
+            host_port,
+            arrow,
+            self.container_port,
+            self.protocol.to_lowercase()
+        )
+    }
+}
+
+/// The MAPPING cell for `port`: each container's mapping and name, e.g.
+/// `8080→80/tcp (web)`.
+pub(crate) fn port_mappings(docker_map: &DockerPortMap, port: u16) -> Option<String> {
+    let owners = docker_map.get(&port).filter(|o| !o.is_empty())?;
+    let cells: Vec<String> = owners
+        .iter()
+        .map(|o| format!("{} ({})", o.mapping(port, "→"), o.container_name))
+        .collect();
+    Some(cells.join(", "))
+}
+
+/// `Up 3 hours (healthy)` → `healthy`; `Up 5 seconds (health: starting)`
+/// → `starting`.
+fn parse_health(status: &str) -> Option<String> {
+    let inner = status.rsplit_once('(')?.1.strip_suffix(')')?;
+    let health = inner.strip_prefix("health: ").unwrap_or(inner);
+    matches!(health, "healthy" | "unhealthy" | "starting").then(|| health.to_string())
+}
+
+// ── Endpoint ─────────────────────────────────────────────────────────
+//
+// Every Docker query goes through the `docker` CLI. Which daemon it asks
+// comes from `[docker] host` in the config, then `DOCKER_HOST`, then a
+// rootless daemon's socket when the system one is missing, and otherwise
+// the CLI's own default (including `docker context use`).
+
+/// Where the daemon address came from, for `portview doctor`.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub(crate) enum EndpointSource {
+    Config,
+    Env,
+    Rootless,
+    /// Left to the CLI.
+    Default,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) struct Endpoint {
+    /// The docker binary to run.
+    pub(crate) cli: String,
+    /// `unix://…`, `tcp://…`, `ssh://…`; `None` leaves it to the CLI.
+    pub(crate) host: Option<String>,
+    pub(crate) source: EndpointSource,
+}
+
+impl Endpoint {
+    /// `unix:///run/user/1000/docker.sock (rootless socket)`.
+    pub(crate) fn describe(&self) -> String {
+        let Some(ref host) = self.host else {
+            return "the docker CLI's current context".to_string();
+        };
+        let from = match self.source {
+            EndpointSource::Config => "[docker] host in config",
+            EndpointSource::Env => "DOCKER_HOST",
+            EndpointSource::Rootless => "rootless socket",
+            EndpointSource::Default => "default",
+        };
+        format!("{} ({})", host, from)
+    }
+}
+
+fn resolve_endpoint(
+    cli: Option<&str>,
+    configured: Option<&str>,
+    env: Option<String>,
+    rootless: Option<String>,
+) -> Endpoint {
+    let (host, source) = match (configured, env, rootless) {
+        (Some(host), _, _) => (Some(host.to_string()), EndpointSource::Config),
+        (None, Some(host), _) if !host.is_empty() => (Some(host), EndpointSource::Env),
+        (None, _, Some(socket)) => (Some(socket), EndpointSource::Rootless),
+        _ => (None, EndpointSource::Default),
+    };
+    Endpoint {
+        cli: cli.unwrap_or("docker").to_string(),
+        host,
+        source,
+    }
+}
+
+/// A rootless daemon's socket, when the system socket is missing and no
+/// context has been picked with `DOCKER_CONTEXT`.
+fn rootless_socket() -> Option<String> {
+    if !cfg!(unix)
+        || std::env::var_os("DOCKER_CONTEXT").is_some()
+        || Path::new("/var/run/docker.sock").exists()
+    {
+        return None;
+    }
+    let runtime = std::env::var_os("XDG_RUNTIME_DIR").map(|d| Path::new(&d).join("docker.sock"));
+    // Docker Desktop on Linux and recent macOS versions
+    let desktop = std::env::var_os("HOME").map(|h| Path::new(&h).join(".docker/run/docker.sock"));
+    [runtime, desktop]
+        .into_iter()
+        .flatten()
+        .find(|p| p.exists())
+        .map(|p| format!("unix://{}", p.display()))
+}
+
+static ENDPOINT: OnceLock<Endpoint> = OnceLock::new();
+
+pub(crate) fn endpoint() -> &'static Endpoint {
+    ENDPOINT.get_or_init(|| {
+        let config = crate::config::active();
+        resolve_endpoint(
+            config.docker_cli.as_deref(),
+            config.docker_host.as_deref(),
+            std::env::var("DOCKER_HOST").ok(),
+            rootless_socket(),
+        )
+    })
+}
+
+/// A `docker` command aimed at the chosen daemon.
+pub(crate) fn docker_command() -> Command {
+    let endpoint = endpoint();
+    let mut cmd = Command::new(&endpoint.cli);
+    if let Some(ref host) = endpoint.host {
+        cmd.env("DOCKER_HOST", host);
+    }
+    cmd
+}
+
+// ── Port map ─────────────────────────────────────────────────────────
+
 pub(crate) fn get_docker_port_map() -> DockerPortMap {
-    let output = match Command::new("docker")
+    // Restarting and paused containers are listed too, so a crash loop
+    // shows up instead of the port silently vanishing.
+    let output = match docker_command()
         .args([
             "ps",
+            "--all",
+            "--filter",
+            "status=running",
+            "--filter",
+            "status=restarting",
+            "--filter",
+            "status=paused",
             "--format",
-            "{{.ID}}\t{{.Names}}\t{{.Image}}\t{{.Ports}}",
+            concat!(
+                "{{.ID}}\t{{.Names}}\t{{.Image}}\t{{.State}}\t{{.Status}}\t",
+                "{{.Label \"com.docker.compose.project\"}}\t",
+                "{{.Label \"com.docker.compose.service\"}}\t",
+                "{{.Label \"com.docker.compose.project.working_dir\"}}\t",
+                "{{.Label \"com.docker.compose.project.config_files\"}}\t",
+                "{{.Ports}}",
+            ),
         ])
         .output()
     {
@@ -30,50 +286,227 @@ pub(crate) fn get_docker_port_map() -> DockerPortMap {
     }
 
     let stdout = String::from_utf8_lossy(&output.stdout);
-    parse_ps_output(&stdout)
+    let mut map = parse_ps_output(&stdout);
+    add_unpublished_bindings(&stdout, &mut map);
+    map
+}
+
+/// How long watch mode reuses a `docker ps` read unless told otherwise.
+pub(crate) const PORT_MAP_REFRESH: Duration = Duration::from_secs(5);
+
+/// The last read of a slow `docker` command, when it was taken, and
+/// whether a newer one is on its way.
+struct Refreshing<T> {
+    latest: Option<(T, Instant)>,
+    refreshing: bool,
+    /// Bumped by [`invalidate_port_map`] so a read started before a stop
+    /// or restart can't put the old containers back.
+    generation: u64,
+}
+
+type Cache<T> = OnceLock<Mutex<Refreshing<T>>>;
+
+static PORT_MAP: Cache<DockerPortMap> = OnceLock::new();
+static STATS: Cache<HashMap<String, ContainerStats>> = OnceLock::new();
+
+/// Only the first call waits for `read`; after that the cached value is
+/// returned, and once it is older than `interval` a new one is read in the
+/// background.
+fn cached<T: Clone + Send + 'static>(
+    cache: &'static Cache<T>,
+    interval: Duration,
+    read: fn() -> T,
+) -> T {
+    let cache = cache.get_or_init(|| {
+        Mutex::new(Refreshing {
+            latest: None,
+            refreshing: false,
+            generation: 0,
+        })
+    });
+    let Ok(mut guard) = cache.lock() else {
+        return read();
+    };
+    let Some((value, read_at)) = guard.latest.clone() else {
+        let value = read();
+        guard.latest = Some((value.clone(), Instant::now()));
+        return value;
+    };
+    if read_at.elapsed() >= interval && !guard.refreshing {
+        guard.refreshing = true;
+        let generation = guard.generation;
+        std::thread::spawn(move || {
+            let value = read();
+            if let Ok(mut guard) = cache.lock() {
+                guard.refreshing = false;
+                if guard.generation == generation {
+                    guard.latest = Some((value, Instant::now()));
+                }
+            }
+        });
+    }
+    value
+}
+
+fn invalidate<T>(cache: &Cache<T>) {
+    let Some(cache) = cache.get() else {
+        return;
+    };
+    if let Ok(mut guard) = cache.lock() {
+        guard.latest = None;
+        guard.generation += 1;
+    }
+}
+
+/// [`get_docker_port_map`] for callers that refresh every tick, read again
+/// in the background once the last read is older than `interval`.
+pub(crate) fn cached_port_map(interval: Duration) -> DockerPortMap {
+    cached(&PORT_MAP, interval, get_docker_port_map)
+}
+
+/// Drop the cached map and stats after stopping or restarting a
+/// container, so the next [`cached_port_map`] reads `docker ps` again
+/// before returning.
+pub(crate) fn invalidate_port_map() {
+    invalidate(&PORT_MAP);
+    invalidate(&STATS);
 }
 
 fn parse_ps_output(stdout: &str) -> DockerPortMap {
     let mut result: DockerPortMap = HashMap::new();
 
     for line in stdout.lines() {
-        let mut fields = line.splitn(4, '\t');
-        let (Some(container_id), Some(container_name), Some(image), Some(ports_raw)) =
-            (fields.next(), fields.next(), fields.next(), fields.next())
-        else {
+        let Some(row) = PsRow::parse(line) else {
             continue;
         };
 
-        if ports_raw.trim().is_empty() {
-            continue;
-        }
-
-        for segment in ports_raw.split(',') {
+        for segment in row.ports.split(',') {
             let Some((host_port, container_port, protocol)) = parse_port_segment(segment) else {
                 continue;
             };
+            insert_owner(&mut result, host_port, row.owner(container_port, protocol));
+        }
+    }
 
-            let owner = DockerPortOwner {
-                container_id: container_id.to_string(),
-                container_name: container_name.to_string(),
-                image: image.to_string(),
-                container_port,
-                protocol,
-            };
+    result
+}
 
-            let entry = result.entry(host_port).or_default();
-            let exists = entry.iter().any(|existing| {
-                existing.container_id == owner.container_id
-                    && existing.container_port == owner.container_port
-                    && existing.protocol == owner.protocol
-            });
-            if !exists {
-                entry.push(owner);
-            }
+/// One line of `docker ps` output.
+struct PsRow<'a> {
+    id: &'a str,
+    name: &'a str,
+    image: &'a str,
+    state: &'a str,
+    status: &'a str,
+    compose: Option<ComposeService>,
+    ports: &'a str,
+}
+
+impl<'a> PsRow<'a> {
+    fn parse(line: &'a str) -> Option<Self> {
+        let mut fields = line.splitn(10, '\t');
+        let id = fields.next()?;
+        let name = fields.next()?;
+        let image = fields.next()?;
+        let state = fields.next()?;
+        let status = fields.next()?;
+        let (project, service, working_dir, config_files) = (
+            fields.next()?,
+            fields.next()?,
+            fields.next()?,
+            fields.next()?,
+        );
+        let compose = (!project.is_empty() && !service.is_empty()).then(|| ComposeService {
+            project: project.to_string(),
+            service: service.to_string(),
+            working_dir: working_dir.to_string(),
+            config_files: config_files
+                .split(',')
+                .filter(|f| !f.is_empty())
+                .map(str::to_string)
+                .collect(),
+        });
+        Some(Self {
+            id,
+            name,
+            image,
+            state,
+            status,
+            compose,
+            ports: fields.next()?.trim(),
+        })
+    }
+
+    fn owner(&self, container_port: u16, protocol: String) -> DockerPortOwner {
+        DockerPortOwner {
+            container_id: self.id.to_string(),
+            container_name: self.name.to_string(),
+            image: self.image.to_string(),
+            container_port,
+            protocol,
+            state: self.state.to_string(),
+            health: parse_health(self.status),
+            stats: None,
+            compose: self.compose.clone(),
         }
     }
+}
 
-    result
+/// Docker drops the Ports column while a container restarts; read its
+/// configured bindings instead so the row stays visible.
+fn add_unpublished_bindings(stdout: &str, map: &mut DockerPortMap) {
+    let idle: Vec<PsRow> = stdout
+        .lines()
+        .filter_map(PsRow::parse)
+        .filter(|row| row.ports.is_empty() && row.state != "running")
+        .collect();
+    if idle.is_empty() {
+        return;
+    }
+    let mut args = vec![
+        "inspect",
+        "--format",
+        "{{.Id}}\t{{range $p, $b := .HostConfig.PortBindings}}{{range $b}}{{.HostPort}}->{{$p}},{{end}}{{end}}",
+    ];
+    args.extend(idle.iter().map(|row| row.id));
+    let Ok(output) = docker_command().args(&args).output() else {
+        return;
+    };
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    for line in stdout.lines() {
+        let Some((full_id, bindings)) = line.split_once('\t') else {
+            continue;
+        };
+        let Some(row) = idle.iter().find(|row| full_id.starts_with(row.id)) else {
+            continue;
+        };
+        for (host_port, container_port, protocol) in parse_bindings(bindings) {
+            insert_owner(map, host_port, row.owner(container_port, protocol));
+        }
+    }
+}
+
+/// `8080->80/tcp,8443->443/tcp,` from the inspect template above.
+fn parse_bindings(bindings: &str) -> Vec<(u16, u16, String)> {
+    bindings
+        .split(',')
+        .filter(|b| !b.is_empty())
+        .filter_map(parse_port_segment)
+        .collect()
+}
+
+/// Add `owner` unless the same container mapping is already listed (IPv4
+/// and IPv6 bindings of one port).
+fn insert_owner(map: &mut DockerPortMap, host_port: u16, owner: DockerPortOwner) {
+    let entry = map.entry(host_port).or_default();
+    let exists = entry.iter().any(|existing| {
+        existing.container_id == owner.container_id
+            && existing.container_port == owner.container_port
+            && existing.protocol == owner.protocol
+    });
+    if !exists {
+        entry.push(owner);
+    }
 }
 
 fn parse_port_segment(segment: &str) -> Option<(u16, u16, String)> {
@@ -98,10 +531,7 @@ fn parse_first_port(raw: &str) -> Option<u16> {
 /// Run a Docker action (stop or restart) on a container by name.
 /// Returns a status message string.
 pub(crate) fn run_docker_action(action: &str, container_name: &str) -> String {
-    let output = match Command::new("docker")
-        .args([action, container_name])
-        .output()
-    {
+    let output = match docker_command().args([action, container_name]).output() {
         Ok(out) => out,
         Err(e) => return format!("Failed to run docker {}: {}", action, e),
     };
@@ -121,7 +551,7 @@ pub(crate) fn run_docker_action(action: &str, container_name: &str) -> String {
 
 /// Fetch the last few lines of logs from a Docker container.
 pub(crate) fn run_docker_logs(container_name: &str) -> String {
-    let output = match Command::new("docker")
+    let output = match docker_command()
         .args(["logs", "--tail", "20", container_name])
         .output()
     {
@@ -142,9 +572,736 @@ pub(crate) fn run_docker_logs(container_name: &str) -> String {
     lines[start..].join("\n")
 }
 
+// ── Container targets ────────────────────────────────────────────────
+//
+// `portview nginx:latest --docker` should find the ports a container
+// publishes even when nothing on the host side mentions the image, so a
+// name search also checks the containers behind each port.
+
+/// `nginx:1.27` → (`nginx`, `:1.27`); `docker.io/library/redis` →
+/// (`redis`, ``). Digests count as the tag.
+fn split_image(image: &str) -> (&str, &str) {
+    let short = image
+        .strip_prefix("docker.io/")
+        .unwrap_or(image)
+        .trim_start_matches("library/");
+    let name_start = short.rfind('/').map_or(0, |i| i + 1);
+    match short[name_start..].find([':', '@']) {
+        Some(end) => short.split_at(name_start + end),
+        None => (short, ""),
+    }
+}
+
+impl DockerPortOwner {
+    /// Whether `target` names this container: its name, an ID prefix of at
+    /// least four characters, its compose service, or its image. An image
+    /// matches by its last path segments, and without a tag every tag does.
+    pub(crate) fn matches_target(&self, target: &str) -> bool {
+        let t = target.to_lowercase();
+        if self.container_name.eq_ignore_ascii_case(&t)
+            || (t.len() >= 4 && self.container_id.starts_with(&t))
+            || self
+                .compose
+                .as_ref()
+                .is_some_and(|c| c.service.eq_ignore_ascii_case(&t))
+        {
+            return true;
+        }
+        let image = self.image.to_lowercase();
+        let (repo, tag) = split_image(&image);
+        let (wanted_repo, wanted_tag) = split_image(&t);
+        let tag = if tag.is_empty() { ":latest" } else { tag };
+        (repo == wanted_repo || repo.ends_with(&format!("/{}", wanted_repo)))
+            && (wanted_tag.is_empty() || wanted_tag == tag)
+    }
+}
+
+/// Host ports published by containers that `target` names.
+pub(crate) fn ports_for_target(map: &DockerPortMap, target: &str) -> HashSet<u16> {
+    map.iter()
+        .filter(|(_, owners)| owners.iter().any(|o| o.matches_target(target)))
+        .map(|(&port, _)| port)
+        .collect()
+}
+
+// ── Networks ─────────────────────────────────────────────────────────
+//
+// A published port says how the host reaches a container; its networks
+// say how other containers do. Listing both shows whether a service is
+// meant for the host or only for its compose project.
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) struct ContainerNetwork {
+    pub(crate) name: String,
+    /// IPv4 then global IPv6, whichever the network assigned.
+    pub(crate) addresses: Vec<String>,
+}
+
+impl ContainerNetwork {
+    /// `myapp_default 172.18.0.3:80`, for the container port a row maps
+    /// to.
+    pub(crate) fn describe(&self, container_port: u16) -> String {
+        match self.name.as_str() {
+            "host" => "host (shares the host's network; no NAT)".to_string(),
+            "none" => "none (no network; only reachable inside the container)".to_string(),
+            _ if self.addresses.is_empty() => self.name.clone(),
+            _ => {
+                let addresses: Vec<String> = self
+                    .addresses
+                    .iter()
+                    .map(|a| {
+                        if a.contains(':') {
+                            format!("[{}]:{}", a, container_port)
+                        } else {
+                            format!("{}:{}", a, container_port)
+                        }
+                    })
+                    .collect();
+                format!("{} {}", self.name, addresses.join(", "))
+            }
+        }
+    }
+}
+
+/// Networks per container, keyed by the IDs passed in (short or full),
+/// from a single `docker inspect`.
+pub(crate) fn container_networks(ids: &[&str]) -> HashMap<String, Vec<ContainerNetwork>> {
+    if ids.is_empty() {
+        return HashMap::new();
+    }
+    let mut args = vec![
+        "inspect",
+        "--format",
+        "{{.Id}}{{range $name, $net := .NetworkSettings.Networks}}\t{{$name}} {{$net.IPAddress}} {{$net.GlobalIPv6Address}}{{end}}",
+    ];
+    args.extend(ids);
+    let Ok(output) = docker_command().args(&args).output() else {
+        return HashMap::new();
+    };
+    let inspected = parse_networks_output(&String::from_utf8_lossy(&output.stdout));
+    ids.iter()
+        .filter_map(|id| {
+            let (_, networks) = inspected.iter().find(|(full, _)| full.starts_with(id))?;
+            Some((id.to_string(), networks.clone()))
+        })
+        .collect()
+}
+
+fn parse_networks_output(stdout: &str) -> Vec<(String, Vec<ContainerNetwork>)> {
+    stdout
+        .lines()
+        .filter_map(|line| {
+            let mut fields = line.split('\t');
+            let id = fields.next()?.trim();
+            if id.is_empty() {
+                return None;
+            }
+            let networks = fields
+                .filter_map(|field| {
+                    let mut parts = field.split_whitespace();
+                    Some(ContainerNetwork {
+                        name: parts.next()?.to_string(),
+                        addresses: parts.map(str::to_string).collect(),
+                    })
+                })
+                .collect();
+            Some((id.to_string(), networks))
+        })
+        .collect()
+}
+
+// ── Port forwarders ──────────────────────────────────────────────────
+//
+// Published ports are held on the host by whatever forwards them into the
+// container: `docker-proxy` on Linux with the userland proxy enabled, or
+// the VM's helper process with Docker Desktop, Colima, Rancher Desktop
+// and OrbStack on macOS. Those rows say nothing about the container, so
+// they are looked up in `docker ps` even without `--docker`.
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum PortForwarder {
+    Proxy,
+    DockerDesktop,
+    Colima,
+    RancherDesktop,
+    OrbStack,
+    /// A Lima VM that isn't Colima's or Rancher Desktop's.
+    Lima,
+}
+
+impl PortForwarder {
+    /// Recognised from the process name; Lima-based VMs all forward
+    /// through `limactl` (or `ssh` on older versions), so the instance
+    /// path in the command line tells them apart.
+    pub(crate) fn detect(info: &PortInfo) -> Option<Self> {
+        let command = info.command.as_str();
+        let lima = command.contains("lima");
+        match info.process_name.as_str() {
+            "docker-proxy" => Some(PortForwarder::Proxy),
+            "com.docker.backend" | "com.docker.vpnkit" | "vpnkit-bridge" => {
+                Some(PortForwarder::DockerDesktop)
+            }
+            name if name.starts_with("OrbStack") => Some(PortForwarder::OrbStack),
+            "limactl" | "ssh" if lima && command.contains("colima") => Some(PortForwarder::Colima),
+            "limactl" | "ssh" if lima && command.contains("rancher-desktop") => {
+                Some(PortForwarder::RancherDesktop)
+            }
+            "limactl" => Some(PortForwarder::Lima),
+            "ssh" if lima => Some(PortForwarder::Lima),
+            _ => None,
+        }
+    }
+
+    pub(crate) fn label(self) -> &'static str {
+        match self {
+            PortForwarder::Proxy => "docker-proxy",
+            PortForwarder::DockerDesktop => "Docker Desktop VM",
+            PortForwarder::Colima => "Colima VM",
+            PortForwarder::RancherDesktop => "Rancher Desktop VM",
+            PortForwarder::OrbStack => "OrbStack VM",
+            PortForwarder::Lima => "Lima VM",
+        }
+    }
+}
+
+/// A listener held by a forwarder, and the container behind it when
+/// Docker could say.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) struct Forwarded {
+    pub(crate) via: PortForwarder,
+    pub(crate) container: Option<String>,
+}
+
+impl Forwarded {
+    /// `docker:web`, or the forwarder when the container is unknown.
+    pub(crate) fn tag(&self) -> String {
+        match &self.container {
+            Some(name) => format!("docker:{}", name),
+            None => self.via.label().to_string(),
+        }
+    }
+}
+
+/// `-container-ip 172.17.0.2 -container-port 80` from the proxy's
+/// command line.
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct ProxyTarget {
+    container_ip: String,
+    container_port: u16,
+}
+
+fn parse_proxy_command(command: &str) -> Option<ProxyTarget> {
+    let mut ip = None;
+    let mut port = None;
+    let mut args = command.split_whitespace();
+    while let Some(arg) = args.next() {
+        match arg.trim_start_matches('-') {
+            "container-ip" => ip = args.next(),
+            "container-port" => port = args.next().and_then(|p| p.parse().ok()),
+            _ => {}
+        }
+    }
+    Some(ProxyTarget {
+        container_ip: ip?.to_string(),
+        container_port: port?,
+    })
+}
+
+/// Forwarder listeners keyed by (port, PID). Runs `docker ps` only if
+/// there is one to explain.
+pub(crate) fn forwarded_ports(infos: &[PortInfo]) -> HashMap<(u16, u32), Forwarded> {
+    let forwarders: Vec<(&PortInfo, PortForwarder)> = infos
+        .iter()
+        .filter_map(|i| Some((i, PortForwarder::detect(i)?)))
+        .collect();
+    if forwarders.is_empty() {
+        return HashMap::new();
+    }
+    let map = get_docker_port_map();
+    forwarders
+        .into_iter()
+        .map(|(info, via)| {
+            let container = forwarded_container(info, via, &map);
+            ((info.port, info.pid), Forwarded { via, container })
+        })
+        .collect()
+}
+
+/// The container on the forwarder's port. A proxy names its container
+/// port, which picks between containers and, when Docker can't be
+/// queried, stands in for the name along with the container address.
+fn forwarded_container(info: &PortInfo, via: PortForwarder, map: &DockerPortMap) -> Option<String> {
+    let target = (via == PortForwarder::Proxy)
+        .then(|| parse_proxy_command(&info.command))
+        .flatten();
+    let owner = map.get(&info.port).and_then(|owners| {
+        owners.iter().find(|o| {
+            target
+                .as_ref()
+                .is_none_or(|t| t.container_port == o.container_port)
+        })
+    });
+    match (owner, target) {
+        (Some(owner), _) => Some(owner.container_name.clone()),
+        (None, Some(t)) => Some(format!("{}:{}", t.container_ip, t.container_port)),
+        (None, None) => None,
+    }
+}
+
+// ── Conflicts ────────────────────────────────────────────────────────
+//
+// Docker publishes ports with NAT rules as well as (or instead of) a
+// listening proxy, so a host process can bind a port a container already
+// publishes and neither side notices until connections go to the wrong
+// one. A proxy can also outlive its container when the daemon loses
+// track of it, holding the port for nothing.
+
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) enum PortConflict {
+    /// A host process listens on a port these containers publish.
+    SharedPort(Vec<String>),
+    /// A `docker-proxy` whose container is no longer running.
+    DeadContainer,
+}
+
+impl PortConflict {
+    /// The table badge, after a `⚠`.
+    pub(crate) fn badge(&self) -> &'static str {
+        match self {
+            PortConflict::SharedPort(_) => "conflict",
+            PortConflict::DeadContainer => "dead container",
+        }
+    }
+
+    pub(crate) fn explain(&self, info: &PortInfo) -> String {
+        match self {
+            PortConflict::SharedPort(containers) => format!(
+                "{} also publishes port {}, so connections may reach the container instead of {}",
+                containers.join(", "),
+                info.port,
+                info.process_name
+            ),
+            PortConflict::DeadContainer => format!(
+                "docker-proxy holds port {} for a container that is no longer running",
+                info.port
+            ),
+        }
+    }
+
+    pub(crate) fn remedy(&self) -> &'static str {
+        match self {
+            PortConflict::SharedPort(_) => {
+                "stop one of them, or publish the container on another host port (-p 8081:80)"
+            }
+            PortConflict::DeadContainer => {
+                "start the container again, or restart Docker (`systemctl restart docker`) to release the port"
+            }
+        }
+    }
+
+    /// The `Conflict:` and `Fix:` detail rows.
+    pub(crate) fn rows(&self, info: &PortInfo) -> DetailRows {
+        vec![
+            ("Conflict:", self.explain(info)),
+            ("Fix:", self.remedy().to_string()),
+        ]
+    }
+}
+
+/// How a host row clashes with Docker, if it does. Synthetic container
+/// rows never do, and forwarders other than `docker-proxy` hold ports for
+/// containers by design.
+pub(crate) fn port_conflict(info: &PortInfo, docker_map: &DockerPortMap) -> Option<PortConflict> {
+    if info.pid == 0 {
+        return None;
+    }
+    let containers: Vec<String> = docker_map
+        .get(&info.port)
+        .into_iter()
+        .flatten()
+        .filter(|o| info.protocol.starts_with(o.protocol.as_str()))
+        .map(|o| o.container_name.clone())
+        .collect();
+    match PortForwarder::detect(info) {
+        None if !containers.is_empty() => Some(PortConflict::SharedPort(containers)),
+        // An empty map may only mean `docker ps` failed
+        Some(PortForwarder::Proxy) if containers.is_empty() && !docker_map.is_empty() => {
+            Some(PortConflict::DeadContainer)
+        }
+        _ => None,
+    }
+}
+
+/// The conflict rows for a detail view; empty when there is none.
+pub(crate) fn conflict_rows(info: &PortInfo, docker_map: &DockerPortMap) -> DetailRows {
+    port_conflict(info, docker_map).map_or_else(Vec::new, |c| c.rows(info))
+}
+
+// ── Compose ──────────────────────────────────────────────────────────
+
+impl ComposeService {
+    /// `docker compose -p <project> -f <file>… <args>`, run from the
+    /// project directory so relative paths in the compose file resolve.
+    fn command(&self, args: &[&str]) -> Result<Command, String> {
+        if !Path::new(&self.working_dir).is_dir() {
+            return Err(format!(
+                "Compose project directory {} not found",
+                self.working_dir
+            ));
+        }
+        let mut cmd = docker_command();
+        cmd.current_dir(&self.working_dir)
+            .args(["compose", "-p", &self.project]);
+        for file in &self.config_files {
+            cmd.args(["-f", file]);
+        }
+        cmd.args(args);
+        Ok(cmd)
+    }
+}
+
+/// `docker compose restart <service>`: every replica of the service,
+/// not just the container on this port.
+pub(crate) fn compose_restart(compose: &ComposeService) -> String {
+    let output = match compose.command(&["restart", &compose.service]) {
+        Ok(mut cmd) => cmd.output(),
+        Err(msg) => return msg,
+    };
+    match output {
+        Err(e) => format!("Failed to run docker compose: {}", e),
+        Ok(out) if out.status.success() => {
+            format!("docker compose restart {}: OK", compose.service)
+        }
+        Ok(out) => format!(
+            "docker compose restart {} failed: {}",
+            compose.service,
+            String::from_utf8_lossy(&out.stderr).trim()
+        ),
+    }
+}
+
+/// Print `docker compose ps` for the whole project on the current
+/// terminal and wait for Enter. Returns a status message.
+pub(crate) fn view_compose_project(compose: &ComposeService) -> String {
+    let mut cmd = match compose.command(&["ps", "--all"]) {
+        Ok(cmd) => cmd,
+        Err(msg) => return msg,
+    };
+    println!(
+        "Compose project {} ({})\n",
+        compose.project, compose.working_dir
+    );
+    if let Err(e) = cmd.status() {
+        return format!("Failed to run docker compose: {}", e);
+    }
+    println!("\nPress Enter to return to portview");
+    let _ = std::io::stdin().lock().read_line(&mut String::new());
+    format!("Viewed compose project {}", compose.project)
+}
+
+// ── Resource usage ───────────────────────────────────────────────────
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub(crate) struct ContainerStats {
+    pub(crate) cpu_percent: f64,
+    pub(crate) memory_bytes: u64,
+}
+
+/// Fill in `stats` for every container in the map. `docker stats
+/// --no-stream` takes a second or two to sample, so only the first call
+/// waits for it; later calls use the previous sample, and a new one is
+/// taken in the background on the same `interval` as the port map.
+pub(crate) fn attach_stats(map: &mut DockerPortMap, interval: Duration) {
+    if map.is_empty() {
+        return;
+    }
+    let stats = cached(&STATS, interval, sample_stats);
+    for owner in map.values_mut().flatten() {
+        owner.stats = stats
+            .iter()
+            .find(|(id, _)| owner.container_id.starts_with(id.as_str()))
+            .map(|(_, s)| *s);
+    }
+}
+
+fn sample_stats() -> HashMap<String, ContainerStats> {
+    let output = docker_command()
+        .args([
+            "stats",
+            "--no-stream",
+            "--format",
+            "{{.ID}}\t{{.CPUPerc}}\t{{.MemUsage}}",
+        ])
+        .output();
+    match output {
+        Ok(out) if out.status.success() => {
+            parse_stats_output(&String::from_utf8_lossy(&out.stdout))
+        }
+        _ => HashMap::new(),
+    }
+}
+
+fn parse_stats_output(stdout: &str) -> HashMap<String, ContainerStats> {
+    stdout
+        .lines()
+        .filter_map(|line| {
+            let mut fields = line.split('\t');
+            let id = fields.next()?.trim();
+            let cpu = fields.next()?.trim().strip_suffix('%')?.parse().ok()?;
+            let used = fields.next()?.split(" / ").next()?;
+            let stats = ContainerStats {
+                cpu_percent: cpu,
+                memory_bytes: parse_size(used)?,
+            };
+            Some((id.to_string(), stats))
+        })
+        .collect()
+}
+
+/// `12.5MiB`, `1.2GB`, `512B` → bytes.
+fn parse_size(s: &str) -> Option<u64> {
+    let s = s.trim();
+    let split = s
+        .find(|c: char| !c.is_ascii_digit() && c != '.')
+        .unwrap_or(s.len());
+    let (num, unit) = s.split_at(split);
+    let n: f64 = num.parse().ok()?;
+    let multiplier = match unit.trim() {
+        "B" | "" => 1.0,
+        "KiB" => 1024.0,
+        "MiB" => 1024.0 * 1024.0,
+        "GiB" => 1024.0 * 1024.0 * 1024.0,
+        "TiB" => 1024.0 * 1024.0 * 1024.0 * 1024.0,
+        "kB" | "KB" => 1e3,
+        "MB" => 1e6,
+        "GB" => 1e9,
+        "TB" => 1e12,
+        _ => return None,
+    };
+    Some((n * multiplier).round() as u64)
+}
+
+// ── Lifecycle events ─────────────────────────────────────────────────
+//
+// Watch mode learns about containers starting and stopping from `docker
+// events` as they happen, instead of on the next `docker ps` read, which
+// may be several seconds away.
+
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) struct ContainerEvent {
+    /// `start`, `die`, `pause` or `unpause`.
+    pub(crate) action: String,
+    pub(crate) container_name: String,
+    /// Set for `die`.
+    pub(crate) exit_code: Option<i32>,
+}
+
+impl ContainerEvent {
+    /// `container web exited (code 137)`.
+    pub(crate) fn describe(&self) -> String {
+        let what = match self.action.as_str() {
+            "start" => "started".to_string(),
+            "die" => match self.exit_code {
+                Some(code) if code != 0 => format!("exited (code {})", code),
+                _ => "exited".to_string(),
+            },
+            "pause" => "paused".to_string(),
+            "unpause" => "unpaused".to_string(),
+            other => other.to_string(),
+        };
+        format!("container {} {}", self.container_name, what)
+    }
+}
+
+fn parse_event_line(line: &str) -> Option<ContainerEvent> {
+    let mut fields = line.split('\t');
+    let action = fields.next()?.trim();
+    let name = fields.next()?.trim();
+    if action.is_empty() || name.is_empty() {
+        return None;
+    }
+    Some(ContainerEvent {
+        action: action.to_string(),
+        container_name: name.to_string(),
+        // `<no value>` for everything but `die`
+        exit_code: fields.next().and_then(|c| c.trim().parse().ok()),
+    })
+}
+
+/// A running `docker events`, collecting container lifecycle events until
+/// dropped.
+pub(crate) struct EventWatcher {
+    events: Arc<Mutex<Vec<ContainerEvent>>>,
+    child: Child,
+}
+
+impl EventWatcher {
+    /// `None` when `docker` can't be started; callers keep polling alone.
+    pub(crate) fn spawn() -> Option<Self> {
+        let mut child = docker_command()
+            .args([
+                "events",
+                "--filter",
+                "type=container",
+                "--filter",
+                "event=start",
+                "--filter",
+                "event=die",
+                "--filter",
+                "event=pause",
+                "--filter",
+                "event=unpause",
+                "--format",
+                "{{.Action}}\t{{.Actor.Attributes.name}}\t{{.Actor.Attributes.exitCode}}",
+            ])
+            .stdin(Stdio::null())
+            .stdout(Stdio::piped())
+            .stderr(Stdio::null())
+            .spawn()
+            .ok()?;
+        let stdout = child.stdout.take()?;
+        let events = Arc::new(Mutex::new(Vec::new()));
+        let thread_events = Arc::clone(&events);
+        std::thread::spawn(move || {
+            // Ends when the daemon goes away or the child is killed
+            for line in std::io::BufReader::new(stdout).lines() {
+                let Ok(line) = line else {
+                    break;
+                };
+                let Some(event) = parse_event_line(&line) else {
+                    continue;
+                };
+                if let Ok(mut events) = thread_events.lock() {
+                    events.push(event);
+                }
+            }
+        });
+        Some(Self { events, child })
+    }
+
+    /// Whether events arrived since the last [`EventWatcher::take`].
+    pub(crate) fn pending(&self) -> bool {
+        self.events.lock().is_ok_and(|events| !events.is_empty())
+    }
+
+    /// Events since the last call, oldest first.
+    pub(crate) fn take(&self) -> Vec<ContainerEvent> {
+        self.events
+            .lock()
+            .map(|mut events| std::mem::take(&mut *events))
+            .unwrap_or_default()
+    }
+}
+
+impl Drop for EventWatcher {
+    fn drop(&mut self) {
+        let _ = self.child.kill();
+        let _ = self.child.wait();
+    }
+}
+
+/// Run an interactive shell in the container on the current terminal,
+/// preferring bash. Returns a status message once it exits.
+pub(crate) fn exec_shell(container_name: &str) -> String {
+    println!(
+        "Opening a shell in {} (exit to return to portview)",
+        container_name
+    );
+    let status = docker_command()
+        .args([
+            "exec",
+            "-it",
+            container_name,
+            "sh",
+            "-c",
+            "command -v bash >/dev/null 2>&1 && exec bash || exec sh",
+        ])
+        .status();
+    match status {
+        Err(e) => format!("Failed to run docker exec: {}", e),
+        Ok(s) if s.success() => format!("Shell in {} closed", container_name),
+        // 126/127: the image has no `sh` (distroless, scratch)
+        Ok(s) if matches!(s.code(), Some(126 | 127)) => {
+            format!("No shell found in {}", container_name)
+        }
+        Ok(s) => format!(
+            "Shell in {} exited with status {}",
+            container_name,
+            s.code()
+                .map_or_else(|| "unknown".to_string(), |c| c.to_string())
+        ),
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    // ── cached reads ────────────────────────────────────────────────
+
+    /// Wait for a background read to land, up to a second.
+    fn settle<T>(cache: &Cache<T>) {
+        for _ in 0..100 {
+            if !cache.get().unwrap().lock().unwrap().refreshing {
+                return;
+            }
+            std::thread::sleep(Duration::from_millis(10));
+        }
+        panic!("background read never finished");
+    }
+
+    #[test]
+    fn cached_reads_once_per_interval() {
+        static CACHE: Cache<usize> = OnceLock::new();
+        static READS: AtomicUsize = AtomicUsize::new(0);
+        fn read() -> usize {
+            READS.fetch_add(1, Ordering::SeqCst) + 1
+        }
+        assert_eq!(cached(&CACHE, Duration::from_secs(60), read), 1);
+        assert_eq!(cached(&CACHE, Duration::from_secs(60), read), 1);
+        assert_eq!(READS.load(Ordering::SeqCst), 1, "fresh, so not read again");
+    }
+
+    #[test]
+    fn stale_value_is_served_while_a_new_one_is_read() {
+        static CACHE: Cache<usize> = OnceLock::new();
+        static READS: AtomicUsize = AtomicUsize::new(0);
+        fn read() -> usize {
+            READS.fetch_add(1, Ordering::SeqCst) + 1
+        }
+        assert_eq!(cached(&CACHE, Duration::ZERO, read), 1);
+        // Stale: the old value comes back at once, a read starts behind it
+        assert_eq!(cached(&CACHE, Duration::ZERO, read), 1);
+        settle(&CACHE);
+        assert_eq!(cached(&CACHE, Duration::from_secs(60), read), 2);
+        assert_eq!(READS.load(Ordering::SeqCst), 2);
+    }
+
+    #[test]
+    fn invalidate_reads_again_and_drops_older_reads() {
+        static CACHE: Cache<usize> = OnceLock::new();
+        static READS: AtomicUsize = AtomicUsize::new(0);
+        fn slow_read() -> usize {
+            let n = READS.fetch_add(1, Ordering::SeqCst) + 1;
+            std::thread::sleep(Duration::from_millis(50));
+            n
+        }
+        invalidate(&CACHE); // before any read: nothing to drop
+        assert_eq!(cached(&CACHE, Duration::ZERO, slow_read), 1);
+        // Starts a background read, then a container is stopped
+        cached(&CACHE, Duration::ZERO, slow_read);
+        invalidate(&CACHE);
+        let fresh = cached(&CACHE, Duration::from_secs(60), slow_read);
+        assert_ne!(fresh, 1);
+        settle(&CACHE);
+        assert_eq!(
+            cached(&CACHE, Duration::from_secs(60), slow_read),
+            fresh,
+            "the read from before the stop was dropped"
+        );
+        assert_eq!(READS.load(Ordering::SeqCst), 3);
+    }
 
     #[test]
     fn parse_port_segment_ipv4() {
@@ -173,9 +1330,9 @@ mod tests {
     #[test]
     fn parse_ps_output_builds_map_and_deduplicates_ipv4_ipv6_entries() {
         let input = "\
-abc123\tweb\tnginx:latest\t0.0.0.0:8080->80/tcp, :::8080->80/tcp
-def456\tdb\tpostgres:16\t127.0.0.1:5432->5432/tcp
-ghi789\tworker\tworker:latest\t
+abc123\tweb\tnginx:latest\trunning\tUp 3 hours\t\t\t\t\t0.0.0.0:8080->80/tcp, :::8080->80/tcp
+def456\tdb\tpostgres:16\trunning\tUp 2 days (healthy)\t\t\t\t\t127.0.0.1:5432->5432/tcp
+ghi789\tworker\tworker:latest\trunning\tUp 1 minute\t\t\t\t\t
 ";
         let map = parse_ps_output(input);
 
@@ -190,5 +1347,385 @@ ghi789\tworker\tworker:latest\t
         assert_eq!(db.len(), 1);
         assert_eq!(db[0].container_name, "db");
         assert_eq!(db[0].image, "postgres:16");
+        assert_eq!(db[0].container_state(), ContainerState::Healthy);
+        assert_eq!(web[0].container_state(), ContainerState::Running);
+
+        assert_eq!(web[0].mapping(8080, "->"), "8080->80/tcp");
+        assert_eq!(
+            port_mappings(&map, 8080).as_deref(),
+            Some("8080→80/tcp (web)")
+        );
+        assert_eq!(port_mappings(&map, 9999), None);
+    }
+
+    #[test]
+    fn health_from_status_text() {
+        assert_eq!(
+            parse_health("Up 3 hours (healthy)").as_deref(),
+            Some("healthy")
+        );
+        assert_eq!(
+            parse_health("Up 5 seconds (health: starting)").as_deref(),
+            Some("starting")
+        );
+        assert_eq!(
+            parse_health("Up 2 minutes (unhealthy)").as_deref(),
+            Some("unhealthy")
+        );
+        assert_eq!(parse_health("Restarting (1) 3 seconds ago"), None);
+        assert_eq!(parse_health("Up 3 hours"), None);
+    }
+
+    #[test]
+    fn port_state_picks_the_worst_container() {
+        let owner = |id: &str, state: &str, health: Option<&str>| DockerPortOwner {
+            container_id: id.to_string(),
+            container_name: id.to_string(),
+            image: "app".to_string(),
+            container_port: 80,
+            protocol: "TCP".to_string(),
+            state: state.to_string(),
+            health: health.map(str::to_string),
+            stats: None,
+            compose: None,
+        };
+        let mut map = DockerPortMap::new();
+        insert_owner(&mut map, 8080, owner("a", "running", Some("healthy")));
+        assert_eq!(port_state(&map, 8080), Some(ContainerState::Healthy));
+        insert_owner(&mut map, 8080, owner("b", "restarting", None));
+        assert_eq!(port_state(&map, 8080), Some(ContainerState::Restarting));
+        assert!(ContainerState::Restarting.is_bad());
+        assert!(!ContainerState::Starting.is_bad());
+        assert_eq!(port_state(&map, 9090), None);
+    }
+
+    #[test]
+    fn container_targets() {
+        let mut web = PsRow::parse(
+            "4f2a9c81d0e3\tshop-web-1\tnginx:1.27\trunning\tUp\tshop\tweb\t/srv\t\t0.0.0.0:8080->80/tcp",
+        )
+        .unwrap()
+        .owner(80, "TCP".to_string());
+        for target in [
+            "shop-web-1",
+            "Shop-Web-1",
+            "4f2a",
+            "web",
+            "nginx",
+            "nginx:1.27",
+        ] {
+            assert!(web.matches_target(target), "{}", target);
+        }
+        for target in ["shop", "4f2", "nginx:latest", "ngin", "1.27"] {
+            assert!(!web.matches_target(target), "{}", target);
+        }
+        web.image = "docker.io/library/redis".to_string();
+        assert!(web.matches_target("redis:latest"));
+        assert!(web.matches_target("redis"));
+        web.image = "ghcr.io/acme/api:v2".to_string();
+        assert!(web.matches_target("api"));
+        assert!(web.matches_target("acme/api"));
+        assert!(web.matches_target("ghcr.io/acme/api:v2"));
+        assert!(web.matches_target("api:v2"));
+        assert!(!web.matches_target("api:v1"));
+
+        let mut map = DockerPortMap::new();
+        insert_owner(&mut map, 8080, web);
+        assert_eq!(ports_for_target(&map, "api"), HashSet::from([8080]));
+        assert!(ports_for_target(&map, "postgres").is_empty());
+    }
+
+    #[test]
+    fn parse_compose_labels() {
+        let row = PsRow::parse(
+            "abc123\tshop-web-1\tshop-web\trunning\tUp 1 hour\tshop\tweb\t/home/dev/shop\t/home/dev/shop/compose.yaml,/home/dev/shop/compose.override.yaml\t0.0.0.0:3000->3000/tcp",
+        )
+        .unwrap();
+        let compose = row.owner(3000, "TCP".to_string()).compose.unwrap();
+        assert_eq!(compose.project, "shop");
+        assert_eq!(compose.service, "web");
+        assert_eq!(compose.working_dir, "/home/dev/shop");
+        assert_eq!(compose.config_files.len(), 2);
+        assert_eq!(row.ports, "0.0.0.0:3000->3000/tcp");
+
+        let plain = PsRow::parse("def456\tdb\tpostgres:16\trunning\tUp\t\t\t\t\t").unwrap();
+        assert_eq!(plain.compose, None);
+        assert!(ComposeService {
+            working_dir: "/nonexistent/portview-test".to_string(),
+            ..compose
+        }
+        .command(&["ps"])
+        .is_err());
+    }
+
+    fn listener(name: &str, command: &str) -> PortInfo {
+        PortInfo::listener(8080, 812, name)
+            .command(command)
+            .user("root")
+    }
+
+    #[test]
+    fn proxy_rows_name_their_container() {
+        let proxy = listener(
+            "docker-proxy",
+            "/usr/bin/docker-proxy -proto tcp -host-ip 0.0.0.0 -host-port 8080 -container-ip 172.17.0.2 -container-port 80",
+        );
+        assert_eq!(
+            parse_proxy_command(&proxy.command),
+            Some(ProxyTarget {
+                container_ip: "172.17.0.2".to_string(),
+                container_port: 80,
+            })
+        );
+        let via = PortForwarder::Proxy;
+        let mut map =
+            parse_ps_output("abc123\tapi\tapi:dev\trunning\tUp\t\t\t\t\t0.0.0.0:8080->8000/tcp\n");
+        // Only the address is known until Docker reports the container
+        assert_eq!(
+            forwarded_container(&proxy, via, &map).as_deref(),
+            Some("172.17.0.2:80")
+        );
+        map.extend(parse_ps_output(
+            "def456\tweb\tnginx\trunning\tUp\t\t\t\t\t0.0.0.0:8080->80/tcp\n",
+        ));
+        assert_eq!(
+            forwarded_container(&proxy, via, &map).as_deref(),
+            Some("web")
+        );
+        assert_eq!(parse_proxy_command("docker-proxy -proto tcp"), None);
+    }
+
+    #[test]
+    fn detect_vm_forwarders() {
+        let cases = [
+            ("com.docker.backend", "", Some(PortForwarder::DockerDesktop)),
+            (
+                "limactl",
+                "/opt/homebrew/bin/limactl hostagent --pidfile /Users/dev/.colima/_lima/colima/ha.pid colima",
+                Some(PortForwarder::Colima),
+            ),
+            (
+                "limactl",
+                "limactl hostagent --pidfile /Users/dev/Library/Application Support/rancher-desktop/lima/0/ha.pid 0",
+                Some(PortForwarder::RancherDesktop),
+            ),
+            ("limactl", "limactl hostagent default", Some(PortForwarder::Lima)),
+            ("OrbStack Helper", "", Some(PortForwarder::OrbStack)),
+            ("ssh", "ssh dev@example.com", None),
+            ("node", "node server.js", None),
+        ];
+        for (name, command, expected) in cases {
+            assert_eq!(
+                PortForwarder::detect(&listener(name, command)),
+                expected,
+                "{}",
+                name
+            );
+        }
+
+        let vm = listener("com.docker.backend", "");
+        let via = PortForwarder::DockerDesktop;
+        let map =
+            parse_ps_output("abc123\tweb\tnginx\trunning\tUp\t\t\t\t\t0.0.0.0:8080->80/tcp\n");
+        let found = Forwarded {
+            via,
+            container: forwarded_container(&vm, via, &map),
+        };
+        assert_eq!(found.tag(), "docker:web");
+        let unknown = Forwarded {
+            via,
+            container: forwarded_container(&vm, via, &DockerPortMap::new()),
+        };
+        assert_eq!(unknown.tag(), "Docker Desktop VM");
+    }
+
+    #[test]
+    fn host_processes_conflict_with_published_ports() {
+        let map =
+            parse_ps_output("abc123\tweb\tnginx\trunning\tUp\t\t\t\t\t0.0.0.0:8080->80/tcp\n");
+        let nginx = listener("nginx", "nginx: master process");
+        let conflict = port_conflict(&nginx, &map).unwrap();
+        assert_eq!(conflict, PortConflict::SharedPort(vec!["web".to_string()]));
+        assert!(conflict
+            .explain(&nginx)
+            .starts_with("web also publishes port 8080"));
+        // The proxy forwarding to web is how the port should be held
+        let proxy = listener("docker-proxy", "docker-proxy -container-port 80");
+        assert_eq!(port_conflict(&proxy, &map), None);
+        let udp = nginx.clone().protocol("UDP");
+        assert_eq!(port_conflict(&udp, &map), None);
+
+        let elsewhere =
+            parse_ps_output("def456\tapi\tapi\trunning\tUp\t\t\t\t\t0.0.0.0:9000->9000/tcp\n");
+        assert_eq!(
+            port_conflict(&proxy, &elsewhere),
+            Some(PortConflict::DeadContainer)
+        );
+        assert_eq!(port_conflict(&proxy, &DockerPortMap::new()), None);
+        assert_eq!(port_conflict(&nginx, &elsewhere), None);
+    }
+
+    #[test]
+    fn parse_stats_lines() {
+        let stats = parse_stats_output(
+            "abc123def456\t1.25%\t48.5MiB / 7.667GiB\nfff000\t--\t-- / --\nbad line\n",
+        );
+        assert_eq!(stats.len(), 1);
+        assert_eq!(
+            stats["abc123def456"],
+            ContainerStats {
+                cpu_percent: 1.25,
+                memory_bytes: 50_855_936,
+            }
+        );
+        assert_eq!(parse_size("512B"), Some(512));
+        assert_eq!(parse_size("1.5kB"), Some(1500));
+        assert_eq!(parse_size("2GiB"), Some(2 * 1024 * 1024 * 1024));
+        assert_eq!(parse_size("lots"), None);
+    }
+
+    #[test]
+    fn parse_container_events() {
+        let died = parse_event_line("die\tweb\t137").unwrap();
+        assert_eq!(died.exit_code, Some(137));
+        assert_eq!(died.describe(), "container web exited (code 137)");
+        assert_eq!(
+            parse_event_line("die\tweb\t0").unwrap().describe(),
+            "container web exited"
+        );
+        let started = parse_event_line("start\tpv-nginx\t<no value>").unwrap();
+        assert_eq!(started.exit_code, None);
+        assert_eq!(started.describe(), "container pv-nginx started");
+        assert_eq!(parse_event_line("start\t\t"), None);
+    }
+
+    #[test]
+    fn parse_networks() {
+        let inspected = parse_networks_output(concat!(
+            "abc123def456\tmyapp_default 172.18.0.3 \tbridge 172.17.0.2 fd00::2\n",
+            "fff000\thost  \n",
+        ));
+        assert_eq!(inspected.len(), 2);
+        let (id, networks) = &inspected[0];
+        assert_eq!(id, "abc123def456");
+        assert_eq!(networks[0].describe(80), "myapp_default 172.18.0.3:80");
+        assert_eq!(
+            networks[1].describe(80),
+            "bridge 172.17.0.2:80, [fd00::2]:80"
+        );
+        assert!(inspected[1].1[0].describe(80).starts_with("host ("));
+    }
+
+    #[test]
+    fn endpoint_precedence() {
+        let rootless = || Some("unix:///run/user/1000/docker.sock".to_string());
+        let env = || Some("tcp://ci:2375".to_string());
+        let configured = resolve_endpoint(None, Some("ssh://dev@box"), env(), rootless());
+        assert_eq!(configured.host.as_deref(), Some("ssh://dev@box"));
+        assert_eq!(configured.source, EndpointSource::Config);
+        assert_eq!(configured.cli, "docker");
+        let from_env = resolve_endpoint(None, None, env(), rootless());
+        assert_eq!(from_env.describe(), "tcp://ci:2375 (DOCKER_HOST)");
+        let found = resolve_endpoint(
+            Some("/opt/bin/docker"),
+            None,
+            Some(String::new()),
+            rootless(),
+        );
+        assert_eq!(found.source, EndpointSource::Rootless);
+        assert_eq!(found.cli, "/opt/bin/docker");
+        let default = resolve_endpoint(None, None, None, None);
+        assert_eq!(default.host, None);
+        assert_eq!(default.describe(), "the docker CLI's current context");
+    }
+
+    #[test]
+    fn parse_inspect_bindings() {
+        assert_eq!(
+            parse_bindings("8080->80/tcp,->443/tcp,5353->53/udp,"),
+            vec![(8080, 80, "TCP".to_string()), (5353, 53, "UDP".to_string())]
+        );
+    }
+}
+
+// ── Property tests ───────────────────────────────────────────────────
+//
+// `cargo test --features fuzz`: whatever `docker ps` prints, including
+// port strings no daemon has produced yet, the map is built without
+// panicking.
+
+#[cfg(all(test, feature = "fuzz"))]
+mod fuzz {
+    use super::*;
+    use proptest::prelude::*;
+
+    /// One Ports entry: `0.0.0.0:8080->80/tcp`, `[::]:8080-8081->80-81/udp`.
+    fn port_segment() -> impl Strategy<Value = (String, u16, u16, String)> {
+        (
+            prop_oneof![
+                Just(""),
+                Just("0.0.0.0:"),
+                Just("127.0.0.1:"),
+                Just("[::]:"),
+                Just(":::")
+            ],
+            any::<u16>(),
+            any::<u16>(),
+            prop::option::of(1u16..100),
+            "tcp|udp|sctp",
+        )
+            .prop_map(|(ip, host, container, span, proto)| {
+                let text = match span {
+                    Some(n) => format!(
+                        "{}{}-{}->{}-{}/{}",
+                        ip,
+                        host,
+                        host.saturating_add(n),
+                        container,
+                        container.saturating_add(n),
+                        proto
+                    ),
+                    None => format!("{}{}->{}/{}", ip, host, container, proto),
+                };
+                (text, host, container, proto.to_ascii_uppercase())
+            })
+    }
+
+    proptest! {
+        #[test]
+        fn port_segment_never_panics(s in "\\PC*") {
+            parse_port_segment(&s);
+            parse_bindings(&s);
+            parse_health(&s);
+        }
+
+        #[test]
+        fn port_segments_read_back((text, host, container, proto) in port_segment()) {
+            prop_assert_eq!(parse_port_segment(&text), Some((host, container, proto)));
+        }
+
+        #[test]
+        fn ps_output_never_panics(
+            rows in prop::collection::vec(
+                prop::collection::vec("[^\\t\\n]{0,40}", 0..12).prop_map(|f| f.join("\t")),
+                0..6,
+            ),
+        ) {
+            parse_ps_output(&rows.join("\n"));
+        }
+
+        #[test]
+        fn ps_rows_map_their_host_ports(
+            segments in prop::collection::vec(port_segment(), 1..5),
+            status in "Up [0-9]+ (seconds|hours)( \\((healthy|unhealthy|health: starting)\\))?",
+        ) {
+            let ports: Vec<&str> = segments.iter().map(|s| s.0.as_str()).collect();
+            let line = format!("abc123\tweb\tnginx\trunning\t{}\t\t\t\t\t{}", status, ports.join(", "));
+            let map = parse_ps_output(&line);
+            for (_, host, container, proto) in &segments {
+                let owners = map.get(host);
+                prop_assert!(owners.is_some_and(|o| o.iter().any(|o| o.container_port == *container && &o.protocol == proto)));
+            }
+        }
     }
 }
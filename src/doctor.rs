@@ -0,0 +1,283 @@
+use std::io::{self, Write};
+use std::path::PathBuf;
+use std::process::Stdio;
+
+use crate::docker;
+use crate::{json_escape, write_styled};
+
+// ── Checks ───────────────────────────────────────────────────────────
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub(crate) enum CheckStatus {
+    /// The data source works.
+    Ok,
+    /// Works partially; some rows or columns will be missing.
+    Warn,
+    /// Broken: portview can't show anything from this source.
+    Fail,
+    /// Optional and not set up; only matters for the flags it backs.
+    Skip,
+}
+
+impl CheckStatus {
+    fn as_str(self) -> &'static str {
+        match self {
+            CheckStatus::Ok => "ok",
+            CheckStatus::Warn => "warning",
+            CheckStatus::Fail => "error",
+            CheckStatus::Skip => "skipped",
+        }
+    }
+
+    fn icon(self) -> (&'static str, &'static str) {
+        match self {
+            CheckStatus::Ok => ("✓", "green"),
+            CheckStatus::Warn => ("!", "yellow"),
+            CheckStatus::Fail => ("✗", "red"),
+            CheckStatus::Skip => ("○", "dimmed"),
+        }
+    }
+}
+
+/// The outcome of probing one data source, with a fix when it isn't ok.
+#[derive(Debug, Clone)]
+pub(crate) struct Check {
+    pub(crate) name: &'static str,
+    pub(crate) status: CheckStatus,
+    pub(crate) detail: String,
+    pub(crate) hint: Option<String>,
+}
+
+impl Check {
+    pub(crate) fn ok(name: &'static str, detail: String) -> Self {
+        Self {
+            name,
+            status: CheckStatus::Ok,
+            detail,
+            hint: None,
+        }
+    }
+
+    pub(crate) fn warn(name: &'static str, detail: String, hint: &str) -> Self {
+        Self {
+            name,
+            status: CheckStatus::Warn,
+            detail,
+            hint: Some(hint.to_string()),
+        }
+    }
+
+    pub(crate) fn fail(name: &'static str, detail: String, hint: &str) -> Self {
+        Self {
+            name,
+            status: CheckStatus::Fail,
+            detail,
+            hint: Some(hint.to_string()),
+        }
+    }
+
+    pub(crate) fn skip(name: &'static str, detail: String) -> Self {
+        Self {
+            name,
+            status: CheckStatus::Skip,
+            detail,
+            hint: None,
+        }
+    }
+}
+
+// ── Portable checks ──────────────────────────────────────────────────
+
+/// Docker is optional, so a missing CLI is a skip rather than a failure.
+/// The endpoint in use is named either way, since a wrong one looks just
+/// like a stopped daemon.
+pub(crate) fn check_docker() -> Check {
+    const NAME: &str = "docker";
+    let endpoint = docker::endpoint();
+    let output = docker::docker_command()
+        .args(["version", "--format", "{{.Server.Version}}"])
+        .stdin(Stdio::null())
+        .output();
+    match output {
+        Err(_) => Check::skip(
+            NAME,
+            format!(
+                "{} CLI not found; --docker will show nothing extra",
+                endpoint.cli
+            ),
+        ),
+        Ok(out) if out.status.success() => Check::ok(
+            NAME,
+            format!(
+                "daemon reachable at {} (server {})",
+                endpoint.describe(),
+                String::from_utf8_lossy(&out.stdout).trim()
+            ),
+        ),
+        Ok(out) => {
+            let stderr = String::from_utf8_lossy(&out.stderr);
+            let reason = stderr
+                .lines()
+                .rev()
+                .find(|l| !l.trim().is_empty())
+                .unwrap_or("unknown error")
+                .trim();
+            let hint = if stderr.contains("permission denied") {
+                "Add your user to the 'docker' group (then log in again), or run portview with sudo."
+            } else {
+                "Start Docker Desktop or the docker daemon, or point portview at it with DOCKER_HOST or `[docker] host` in the config."
+            };
+            Check::warn(
+                NAME,
+                format!(
+                    "daemon not reachable at {}: {}",
+                    endpoint.describe(),
+                    reason
+                ),
+                hint,
+            )
+        }
+    }
+}
+
+/// Helper programs that back optional watch features.
+pub(crate) fn check_helpers() -> Vec<Check> {
+    #[cfg(target_os = "linux")]
+    let notifier = "notify-send";
+    #[cfg(target_os = "macos")]
+    let notifier = "osascript";
+    #[cfg(target_os = "windows")]
+    let notifier = "powershell";
+
+    [
+        ("notifications", notifier, "watch --notify/--alert-notify"),
+        ("webhooks", "curl", "watch --webhook"),
+    ]
+    .into_iter()
+    .map(|(name, program, feature)| match find_in_path(program) {
+        Some(path) => Check::ok(name, format!("{} found at {}", program, path.display())),
+        None => Check::skip(
+            name,
+            format!("{} not found in PATH; needed for {}", program, feature),
+        ),
+    })
+    .collect()
+}
+
+fn find_in_path(program: &str) -> Option<PathBuf> {
+    let path = std::env::var_os("PATH")?;
+    let names: Vec<String> = if cfg!(windows) {
+        vec![format!("{}.exe", program), program.to_string()]
+    } else {
+        vec![program.to_string()]
+    };
+    std::env::split_paths(&path)
+        .flat_map(|dir| names.iter().map(move |n| dir.join(n)))
+        .find(|candidate| candidate.is_file())
+}
+
+// ── Output ───────────────────────────────────────────────────────────
+
+/// Whether any check found a source portview can't work without.
+pub(crate) fn has_failures(checks: &[Check]) -> bool {
+    checks.iter().any(|c| c.status == CheckStatus::Fail)
+}
+
+pub(crate) fn display_checks(title: &str, checks: &[Check], use_color: bool) {
+    let mut out = io::stdout();
+    let _ = writeln!(out);
+    if use_color {
+        write_styled(&mut out, title, "bold", true);
+        let _ = writeln!(out);
+    } else {
+        let _ = writeln!(out, "{}", title);
+    }
+
+    let name_w = checks.iter().map(|c| c.name.len()).max().unwrap_or(0);
+    for check in checks {
+        let (icon, color) = check.status.icon();
+        let _ = write!(out, "  ");
+        write_styled(&mut out, icon, color, use_color);
+        let _ = writeln!(out, " {:<w$}  {}", check.name, check.detail, w = name_w);
+        if let Some(ref hint) = check.hint {
+            let _ = write!(out, "    {:w$}  ", "", w = name_w);
+            write_styled(&mut out, &format!("→ {}", hint), "dimmed", use_color);
+            let _ = writeln!(out);
+        }
+    }
+
+    let problems = checks
+        .iter()
+        .filter(|c| matches!(c.status, CheckStatus::Warn | CheckStatus::Fail))
+        .count();
+    let _ = writeln!(out);
+    if problems == 0 {
+        let _ = writeln!(out, "All data sources are working.");
+    } else {
+        let _ = writeln!(
+            out,
+            "{} problem{} found.",
+            problems,
+            if problems == 1 { "" } else { "s" }
+        );
+    }
+}
+
+pub(crate) fn checks_json(checks: &[Check]) -> String {
+    let items: Vec<String> = checks
+        .iter()
+        .map(|c| {
+            let hint = c
+                .hint
+                .as_deref()
+                .map(|h| format!("\"{}\"", json_escape(h)))
+                .unwrap_or_else(|| "null".to_string());
+            format!(
+                r#"{{"name":"{}","status":"{}","detail":"{}","hint":{}}}"#,
+                json_escape(c.name),
+                c.status.as_str(),
+                json_escape(&c.detail),
+                hint
+            )
+        })
+        .collect();
+    format!(
+        r#"{{"ok":{},"checks":[{}]}}"#,
+        !has_failures(checks),
+        items.join(",")
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn failures_only_count_errors() {
+        let mut checks = vec![
+            Check::ok("socket tables", "4 tables".to_string()),
+            Check::warn("process access", "2 hidden".to_string(), "use sudo"),
+            Check::skip("docker", "not installed".to_string()),
+        ];
+        assert!(!has_failures(&checks));
+        checks.push(Check::fail("procfs", "missing".to_string(), "mount /proc"));
+        assert!(has_failures(&checks));
+    }
+
+    #[test]
+    fn json_shape() {
+        let checks = vec![
+            Check::ok("docker", "daemon reachable".to_string()),
+            Check::warn("process access", "1 \"hidden\"".to_string(), "use sudo"),
+        ];
+        assert_eq!(
+            checks_json(&checks),
+            r#"{"ok":true,"checks":[{"name":"docker","status":"ok","detail":"daemon reachable","hint":null},{"name":"process access","status":"warning","detail":"1 \"hidden\"","hint":"use sudo"}]}"#
+        );
+    }
+
+    #[test]
+    fn find_in_path_misses_unknown_program() {
+        assert!(find_in_path("portview-no-such-helper").is_none());
+    }
+}
@@ -0,0 +1,466 @@
+use std::fs;
+use std::io;
+use std::os::fd::{AsRawFd, FromRawFd, OwnedFd};
+
+use crate::tcpstats::Totals;
+
+// ── eBPF loader ──────────────────────────────────────────────────────
+//
+// Two hand-assembled programs, loaded with the raw bpf() syscall so the
+// feature needs no extra crates. Each one runs on a TCP tracepoint, reads
+// the local port from the event and adds to an array map indexed by port:
+// tcp_probe adds the smoothed RTT and a sample count, tcp_retransmit_skb
+// adds one per retransmitted segment. Field offsets come from the
+// tracepoint's format file, since they change between kernels. Dropping
+// `Probes` closes every fd, which detaches the programs.
+
+const BPF_MAP_CREATE: libc::c_long = 0;
+const BPF_MAP_LOOKUP_ELEM: libc::c_long = 1;
+const BPF_PROG_LOAD: libc::c_long = 5;
+const BPF_MAP_TYPE_ARRAY: u32 = 2;
+const BPF_PROG_TYPE_TRACEPOINT: u32 = 5;
+const BPF_PSEUDO_MAP_FD: u8 = 1;
+const BPF_FUNC_MAP_LOOKUP_ELEM: i32 = 1;
+
+const PERF_TYPE_TRACEPOINT: u32 = 2;
+const PERF_FLAG_FD_CLOEXEC: libc::c_ulong = 8;
+/// `_IOW('$', 8, u32)` and `_IO('$', 0)`.
+const PERF_EVENT_IOC_SET_BPF: libc::c_ulong = 0x4004_2408;
+const PERF_EVENT_IOC_ENABLE: libc::c_ulong = 0x2400;
+
+/// One entry per possible port.
+const PORTS: u32 = 65536;
+
+const TRACEFS_ROOTS: &[&str] = &["/sys/kernel/tracing", "/sys/kernel/debug/tracing"];
+
+#[repr(C)]
+struct MapCreateAttr {
+    map_type: u32,
+    key_size: u32,
+    value_size: u32,
+    max_entries: u32,
+    map_flags: u32,
+}
+
+#[repr(C)]
+struct MapLookupAttr {
+    map_fd: u32,
+    _pad: u32,
+    key: u64,
+    value: u64,
+    flags: u64,
+}
+
+#[repr(C)]
+struct ProgLoadAttr {
+    prog_type: u32,
+    insn_cnt: u32,
+    insns: u64,
+    license: u64,
+    log_level: u32,
+    log_size: u32,
+    log_buf: u64,
+    kern_version: u32,
+    prog_flags: u32,
+}
+
+/// The first 64 bytes of perf_event_attr (PERF_ATTR_SIZE_VER0).
+#[repr(C)]
+struct PerfEventAttr {
+    event_type: u32,
+    size: u32,
+    config: u64,
+    sample_period: u64,
+    sample_type: u64,
+    read_format: u64,
+    flags: u64,
+    wakeup_events: u32,
+    bp_type: u32,
+    config1: u64,
+}
+
+fn bpf<T>(cmd: libc::c_long, attr: &T) -> io::Result<OwnedFd> {
+    let fd = unsafe {
+        libc::syscall(
+            libc::SYS_bpf,
+            cmd,
+            attr as *const T,
+            std::mem::size_of::<T>() as libc::c_uint,
+        )
+    };
+    if fd < 0 {
+        return Err(io::Error::last_os_error());
+    }
+    Ok(unsafe { OwnedFd::from_raw_fd(fd as i32) })
+}
+
+// ── Instructions ─────────────────────────────────────────────────────
+
+type Insn = [u8; 8];
+
+fn insn(code: u8, dst: u8, src: u8, off: i16, imm: i32) -> Insn {
+    let mut bytes = [0u8; 8];
+    bytes[0] = code;
+    // dst_reg and src_reg are 4-bit fields, in bit order
+    bytes[1] = if cfg!(target_endian = "big") {
+        (dst << 4) | src
+    } else {
+        (src << 4) | dst
+    };
+    bytes[2..4].copy_from_slice(&off.to_ne_bytes());
+    bytes[4..8].copy_from_slice(&imm.to_ne_bytes());
+    bytes
+}
+
+fn mov_reg(dst: u8, src: u8) -> Insn {
+    insn(0xbf, dst, src, 0, 0)
+}
+
+fn mov_imm(dst: u8, imm: i32) -> Insn {
+    insn(0xb7, dst, 0, 0, imm)
+}
+
+fn add_imm(dst: u8, imm: i32) -> Insn {
+    insn(0x07, dst, 0, 0, imm)
+}
+
+fn load_u16(dst: u8, src: u8, off: i16) -> Insn {
+    insn(0x69, dst, src, off, 0)
+}
+
+fn load_u32(dst: u8, src: u8, off: i16) -> Insn {
+    insn(0x61, dst, src, off, 0)
+}
+
+fn store_u32(dst: u8, off: i16, src: u8) -> Insn {
+    insn(0x63, dst, src, off, 0)
+}
+
+/// `lock *(u64 *)(dst + off) += src`
+fn atomic_add_u64(dst: u8, off: i16, src: u8) -> Insn {
+    insn(0xdb, dst, src, off, 0)
+}
+
+fn load_map_fd(dst: u8, fd: i32) -> [Insn; 2] {
+    [insn(0x18, dst, BPF_PSEUDO_MAP_FD, 0, fd), [0; 8]]
+}
+
+fn call(helper: i32) -> Insn {
+    insn(0x85, 0, 0, 0, helper)
+}
+
+fn jump_if_zero(reg: u8, skip: i16) -> Insn {
+    insn(0x15, reg, 0, skip, 0)
+}
+
+fn exit() -> Insn {
+    insn(0x95, 0, 0, 0, 0)
+}
+
+const R0: u8 = 0;
+const R1: u8 = 1;
+const R2: u8 = 2;
+const R6: u8 = 6;
+const R7: u8 = 7;
+const FP: u8 = 10;
+
+/// `value = lookup(map, &sport)`, with the event in r6 and `sport` at
+/// `port_off`. Leaves r7 untouched.
+fn lookup_by_port(map: i32, port_off: i16) -> Vec<Insn> {
+    let mut prog = vec![load_u16(R1, R6, port_off), store_u32(FP, -4, R1)];
+    prog.extend(load_map_fd(R1, map));
+    prog.extend([
+        mov_reg(R2, FP),
+        add_imm(R2, -4),
+        call(BPF_FUNC_MAP_LOOKUP_ELEM),
+    ]);
+    prog
+}
+
+/// Count one retransmit for the local port.
+fn retransmit_program(map: i32, port_off: i16) -> Vec<Insn> {
+    let mut prog = vec![mov_reg(R6, R1)];
+    prog.extend(lookup_by_port(map, port_off));
+    prog.extend([
+        jump_if_zero(R0, 2),
+        mov_imm(R1, 1),
+        atomic_add_u64(R0, 0, R1),
+        mov_imm(R0, 0),
+        exit(),
+    ]);
+    prog
+}
+
+/// Add the smoothed RTT (µs) and one sample for the local port.
+fn rtt_program(map: i32, port_off: i16, srtt_off: i16) -> Vec<Insn> {
+    let mut prog = vec![mov_reg(R6, R1), load_u32(R7, R6, srtt_off)];
+    prog.extend(lookup_by_port(map, port_off));
+    prog.extend([
+        jump_if_zero(R0, 3),
+        atomic_add_u64(R0, 0, R7),
+        mov_imm(R1, 1),
+        atomic_add_u64(R0, 8, R1),
+        mov_imm(R0, 0),
+        exit(),
+    ]);
+    prog
+}
+
+// ── Tracepoints ──────────────────────────────────────────────────────
+
+/// The offset of `name` in a tracepoint format file, checking its size.
+fn field_offset(format: &str, name: &str, size: usize) -> Option<i16> {
+    format.lines().find_map(|line| {
+        let mut parts = line.trim().split(';').map(str::trim);
+        let decl = parts.next()?.strip_prefix("field:")?;
+        if decl.rsplit(' ').next()? != name {
+            return None;
+        }
+        let offset = parts.next()?.strip_prefix("offset:")?.parse().ok()?;
+        let field_size: usize = parts.next()?.strip_prefix("size:")?.parse().ok()?;
+        (field_size == size).then_some(offset)
+    })
+}
+
+struct Tracepoint {
+    id: u64,
+    format: String,
+}
+
+fn tracepoint(name: &str) -> Result<Tracepoint, String> {
+    for root in TRACEFS_ROOTS {
+        let dir = format!("{}/events/tcp/{}", root, name);
+        let (Ok(id), Ok(format)) = (
+            fs::read_to_string(format!("{}/id", dir)),
+            fs::read_to_string(format!("{}/format", dir)),
+        ) else {
+            continue;
+        };
+        let id = id
+            .trim()
+            .parse()
+            .map_err(|_| format!("unreadable id for tcp:{}", name))?;
+        return Ok(Tracepoint { id, format });
+    }
+    Err(format!(
+        "tracepoint tcp:{} not found (is tracefs mounted at /sys/kernel/tracing?)",
+        name
+    ))
+}
+
+fn load_program(insns: &[Insn]) -> Result<OwnedFd, String> {
+    let license = b"GPL\0";
+    let attr = |log: &mut [u8]| ProgLoadAttr {
+        prog_type: BPF_PROG_TYPE_TRACEPOINT,
+        insn_cnt: insns.len() as u32,
+        insns: insns.as_ptr() as u64,
+        license: license.as_ptr() as u64,
+        log_level: u32::from(!log.is_empty()),
+        log_size: log.len() as u32,
+        // The kernel refuses a buffer pointer with no size
+        log_buf: if log.is_empty() {
+            0
+        } else {
+            log.as_mut_ptr() as u64
+        },
+        kern_version: 0,
+        prog_flags: 0,
+    };
+    let err = match bpf(BPF_PROG_LOAD, &attr(&mut [])) {
+        Ok(fd) => return Ok(fd),
+        Err(err) => err,
+    };
+    // Load again with the verifier log to say why
+    let mut log = vec![0u8; 64 * 1024];
+    let _ = bpf(BPF_PROG_LOAD, &attr(&mut log));
+    let log = String::from_utf8_lossy(&log);
+    match log
+        .trim_end_matches('\0')
+        .lines()
+        .rfind(|l| !l.trim().is_empty())
+    {
+        Some(last) => Err(format!("the kernel rejected the probe: {}", last)),
+        None => Err(describe_error("loading the probe", &err)),
+    }
+}
+
+/// Open the tracepoint as a perf event and run `prog` on it.
+fn attach(tp: &Tracepoint, prog: &OwnedFd) -> Result<OwnedFd, String> {
+    let attr = PerfEventAttr {
+        event_type: PERF_TYPE_TRACEPOINT,
+        size: std::mem::size_of::<PerfEventAttr>() as u32,
+        config: tp.id,
+        sample_period: 1,
+        sample_type: 0,
+        read_format: 0,
+        flags: 0,
+        wakeup_events: 1,
+        bp_type: 0,
+        config1: 0,
+    };
+    // A tracepoint program runs on every CPU whichever one the event is
+    // opened on
+    let fd = unsafe {
+        libc::syscall(
+            libc::SYS_perf_event_open,
+            &attr as *const PerfEventAttr,
+            -1,
+            0,
+            -1,
+            PERF_FLAG_FD_CLOEXEC,
+        )
+    };
+    if fd < 0 {
+        return Err(describe_error(
+            "opening the tracepoint",
+            &io::Error::last_os_error(),
+        ));
+    }
+    let event = unsafe { OwnedFd::from_raw_fd(fd as i32) };
+    let ok = unsafe {
+        libc::ioctl(event.as_raw_fd(), PERF_EVENT_IOC_SET_BPF, prog.as_raw_fd()) == 0
+            && libc::ioctl(event.as_raw_fd(), PERF_EVENT_IOC_ENABLE, 0) == 0
+    };
+    if !ok {
+        return Err(describe_error(
+            "attaching the probe",
+            &io::Error::last_os_error(),
+        ));
+    }
+    Ok(event)
+}
+
+fn describe_error(step: &str, err: &io::Error) -> String {
+    match err.raw_os_error() {
+        Some(libc::EPERM) | Some(libc::EACCES) => {
+            format!("{}: needs root, or CAP_BPF and CAP_PERFMON", step)
+        }
+        Some(libc::ENOSYS) => "this kernel has no BPF support".to_string(),
+        _ => format!("{}: {}", step, err),
+    }
+}
+
+fn array_map(value_size: u32) -> Result<OwnedFd, String> {
+    let attr = MapCreateAttr {
+        map_type: BPF_MAP_TYPE_ARRAY,
+        key_size: 4,
+        value_size,
+        max_entries: PORTS,
+        map_flags: 0,
+    };
+    bpf(BPF_MAP_CREATE, &attr).map_err(|e| describe_error("creating a BPF map", &e))
+}
+
+fn lookup<const N: usize>(map: &OwnedFd, port: u16) -> Option<[u64; N]> {
+    let key = port as u32;
+    let mut value = [0u64; N];
+    let attr = MapLookupAttr {
+        map_fd: map.as_raw_fd() as u32,
+        _pad: 0,
+        key: &key as *const u32 as u64,
+        value: value.as_mut_ptr() as u64,
+        flags: 0,
+    };
+    let rc = unsafe {
+        libc::syscall(
+            libc::SYS_bpf,
+            BPF_MAP_LOOKUP_ELEM,
+            &attr as *const MapLookupAttr,
+            std::mem::size_of::<MapLookupAttr>() as libc::c_uint,
+        )
+    };
+    (rc == 0).then_some(value)
+}
+
+/// The attached programs and their maps.
+pub(crate) struct Probes {
+    /// [sum of RTT in µs, samples] per port.
+    rtt: OwnedFd,
+    /// [retransmits] per port.
+    retransmits: OwnedFd,
+    _attached: Vec<OwnedFd>,
+}
+
+impl Probes {
+    pub(crate) fn attach() -> Result<Self, String> {
+        let probe = tracepoint("tcp_probe")?;
+        let retransmit = tracepoint("tcp_retransmit_skb")?;
+        let missing = |tp: &str, field: &str| format!("tcp:{} has no {} field", tp, field);
+        let probe_port =
+            field_offset(&probe.format, "sport", 2).ok_or_else(|| missing("tcp_probe", "sport"))?;
+        let srtt =
+            field_offset(&probe.format, "srtt", 4).ok_or_else(|| missing("tcp_probe", "srtt"))?;
+        let retransmit_port = field_offset(&retransmit.format, "sport", 2)
+            .ok_or_else(|| missing("tcp_retransmit_skb", "sport"))?;
+
+        // Kernels before 5.11 charge maps against RLIMIT_MEMLOCK
+        let unlimited = libc::rlimit {
+            rlim_cur: libc::RLIM_INFINITY,
+            rlim_max: libc::RLIM_INFINITY,
+        };
+        unsafe { libc::setrlimit(libc::RLIMIT_MEMLOCK, &unlimited) };
+
+        let rtt = array_map(16)?;
+        let retransmits = array_map(8)?;
+        let rtt_prog = load_program(&rtt_program(rtt.as_raw_fd(), probe_port, srtt))?;
+        let retransmit_prog = load_program(&retransmit_program(
+            retransmits.as_raw_fd(),
+            retransmit_port,
+        ))?;
+        let attached = vec![
+            attach(&probe, &rtt_prog)?,
+            attach(&retransmit, &retransmit_prog)?,
+            rtt_prog,
+            retransmit_prog,
+        ];
+        Ok(Self {
+            rtt,
+            retransmits,
+            _attached: attached,
+        })
+    }
+
+    /// Counts for `port` since the probes were attached.
+    pub(crate) fn totals(&self, port: u16) -> Totals {
+        let [rtt_sum_us, rtt_samples] = lookup::<2>(&self.rtt, port).unwrap_or_default();
+        let [retransmits] = lookup::<1>(&self.retransmits, port).unwrap_or_default();
+        Totals {
+            rtt_sum_us,
+            rtt_samples,
+            retransmits,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const FORMAT: &str = "name: tcp_retransmit_skb
+ID: 2181
+format:
+\tfield:unsigned short common_type;\toffset:0;\tsize:2;\tsigned:0;
+\tfield:int state;\toffset:24;\tsize:4;\tsigned:1;
+\tfield:__u16 sport;\toffset:28;\tsize:2;\tsigned:0;
+\tfield:__u8 saddr_v6[16];\toffset:42;\tsize:16;\tsigned:0;
+";
+
+    #[test]
+    fn field_offsets_come_from_the_format() {
+        assert_eq!(field_offset(FORMAT, "sport", 2), Some(28));
+        assert_eq!(field_offset(FORMAT, "sport", 4), None);
+        assert_eq!(field_offset(FORMAT, "dport", 2), None);
+    }
+
+    #[test]
+    fn programs_end_in_exit() {
+        let prog = rtt_program(3, 64, 100);
+        assert_eq!(prog.len(), 15);
+        assert_eq!(prog[1], load_u32(R7, R6, 100));
+        assert_eq!(prog[14][0], 0x95);
+        // The null check skips to `r0 = 0`
+        assert_eq!(prog[9], jump_if_zero(R0, 3));
+        assert_eq!(prog[13], mov_imm(R0, 0));
+        assert_eq!(retransmit_program(3, 28).len(), 13);
+    }
+}
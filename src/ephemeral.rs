@@ -0,0 +1,165 @@
+use crate::{collector, PortInfo};
+
+// ── Ephemeral ports ──────────────────────────────────────────────────
+//
+// Outgoing connections take their local port from
+// net.ipv4.ip_local_port_range. A server whose port sits in that range
+// can find it held by some client's connection when it restarts, which
+// looks like a random EADDRINUSE. Ports listed in
+// net.ipv4.ip_local_reserved_ports are skipped by that automatic pick.
+
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) struct LocalPorts {
+    /// net.ipv4.ip_local_port_range, inclusive.
+    pub(crate) ephemeral: (u16, u16),
+    /// net.ipv4.ip_local_reserved_ports, as inclusive ranges.
+    pub(crate) reserved: Vec<(u16, u16)>,
+}
+
+/// `32768\t60999` → (32768, 60999).
+#[cfg(target_os = "linux")]
+pub(crate) fn parse_range(text: &str) -> Option<(u16, u16)> {
+    let mut fields = text.split_whitespace();
+    let low = fields.next()?.parse().ok()?;
+    let high = fields.next()?.parse().ok()?;
+    Some((low, high))
+}
+
+/// `8080,9000-9100` → [(8080, 8080), (9000, 9100)]; empty when unset.
+#[cfg(target_os = "linux")]
+pub(crate) fn parse_reserved(text: &str) -> Vec<(u16, u16)> {
+    text.trim()
+        .split(',')
+        .filter_map(|part| match part.split_once('-') {
+            Some((low, high)) => Some((low.trim().parse().ok()?, high.trim().parse().ok()?)),
+            None => {
+                let port = part.trim().parse().ok()?;
+                Some((port, port))
+            }
+        })
+        .collect()
+}
+
+impl LocalPorts {
+    pub(crate) fn is_ephemeral(&self, port: u16) -> bool {
+        (self.ephemeral.0..=self.ephemeral.1).contains(&port)
+    }
+
+    pub(crate) fn is_reserved(&self, port: u16) -> bool {
+        self.reserved
+            .iter()
+            .any(|&(low, high)| (low..=high).contains(&port))
+    }
+
+    fn range(&self) -> String {
+        format!("{}-{}", self.ephemeral.0, self.ephemeral.1)
+    }
+
+    /// The sysctl that adds `port` to the reserved list, keeping what's
+    /// already there.
+    pub(crate) fn reserve_command(&self, port: u16) -> String {
+        let mut entries: Vec<String> = self
+            .reserved
+            .iter()
+            .map(|&(low, high)| {
+                if low == high {
+                    low.to_string()
+                } else {
+                    format!("{}-{}", low, high)
+                }
+            })
+            .collect();
+        entries.push(port.to_string());
+        format!(
+            "sysctl -w net.ipv4.ip_local_reserved_ports={}",
+            entries.join(",")
+        )
+    }
+
+    /// The `Ephemeral:` detail row value; `None` outside the range.
+    pub(crate) fn describe(&self, port: u16) -> Option<String> {
+        if !self.is_ephemeral(port) {
+            return None;
+        }
+        Some(if self.is_reserved(port) {
+            format!(
+                "in the ephemeral range {}, but reserved in ip_local_reserved_ports",
+                self.range()
+            )
+        } else {
+            format!(
+                "in the ephemeral range {}; outgoing connections can take it while the server is down",
+                self.range()
+            )
+        })
+    }
+
+    /// `portview why`'s explanation; `None` outside the range.
+    pub(crate) fn explain(&self, port: u16) -> Option<(bool, String)> {
+        if !self.is_ephemeral(port) {
+            return None;
+        }
+        if self.is_reserved(port) {
+            return Some((
+                false,
+                format!(
+                    "Port {} is in the ephemeral range ({}) but listed in net.ipv4.ip_local_reserved_ports, so outgoing connections won't take it.",
+                    port,
+                    self.range()
+                ),
+            ));
+        }
+        Some((
+            true,
+            format!(
+                "Port {} is in the ephemeral range ({}). Outgoing connections pick their local port from it, so one can hold :{} when the server starts. Reserve it with `{}` (add it to /etc/sysctl.d to keep it), or use a port below {}.",
+                port,
+                self.range(),
+                port,
+                self.reserve_command(port),
+                self.ephemeral.0
+            ),
+        ))
+    }
+}
+
+/// The `Ephemeral:` detail row for a host row, when the backend knows
+/// the range and the port is in it.
+pub(crate) fn detail_row(info: &PortInfo) -> Option<String> {
+    if info.pid == 0 {
+        return None;
+    }
+    collector::active().local_ports()?.describe(info.port)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[cfg(target_os = "linux")]
+    #[test]
+    fn parse_sysctls() {
+        assert_eq!(parse_range("32768\t60999\n"), Some((32768, 60999)));
+        assert_eq!(
+            parse_reserved("8080,45000-45010\n"),
+            vec![(8080, 8080), (45000, 45010)]
+        );
+        assert!(parse_reserved("\n").is_empty());
+    }
+
+    #[test]
+    fn describe_and_explain() {
+        let ports = LocalPorts {
+            ephemeral: (32768, 60999),
+            reserved: vec![(8080, 8080), (45000, 45010)],
+        };
+        assert_eq!(ports.describe(3000), None);
+        assert!(ports.describe(45005).unwrap().contains("but reserved"));
+        let (warn, message) = ports.explain(50000).unwrap();
+        assert!(warn);
+        assert!(
+            message.contains("sysctl -w net.ipv4.ip_local_reserved_ports=8080,45000-45010,50000")
+        );
+        assert!(!ports.explain(45000).unwrap().0);
+    }
+}
@@ -0,0 +1,107 @@
+use std::sync::atomic::{AtomicBool, Ordering};
+
+use crate::json_escape;
+
+// ── Errors ───────────────────────────────────────────────────────────
+//
+// With `--json`, failures are reported as one JSON object on stderr so a
+// wrapper can tell "nothing there" from "needs sudo" without parsing
+// prose:
+//
+//     {"error":"nothing on port 3000","code":"no_such_port"}
+//
+// The codes are part of the output format; new ones may be added, but
+// existing ones keep their meaning.
+
+/// Stable, machine-readable error kinds.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum ErrorCode {
+    /// Nothing is bound to the port.
+    NoSuchPort,
+    /// A name, PID or filter matched no ports.
+    NoMatch,
+    /// Something holds the port, but its process can't be seen or
+    /// signalled without elevated privileges.
+    PermissionDenied,
+    /// `--user` names an account that doesn't exist.
+    NoSuchUser,
+    /// A flag or its value was rejected.
+    InvalidArgument,
+    /// A config file couldn't be read or parsed.
+    ConfigError,
+    /// Writing output or reading a file failed.
+    IoError,
+}
+
+impl ErrorCode {
+    pub(crate) fn as_str(self) -> &'static str {
+        match self {
+            ErrorCode::NoSuchPort => "no_such_port",
+            ErrorCode::NoMatch => "no_match",
+            ErrorCode::PermissionDenied => "permission_denied",
+            ErrorCode::NoSuchUser => "no_such_user",
+            ErrorCode::InvalidArgument => "invalid_argument",
+            ErrorCode::ConfigError => "config_error",
+            ErrorCode::IoError => "io_error",
+        }
+    }
+
+    /// Usage problems exit 2; everything else exits 1.
+    pub(crate) fn exit_status(self) -> i32 {
+        match self {
+            ErrorCode::InvalidArgument | ErrorCode::NoSuchUser | ErrorCode::ConfigError => 2,
+            _ => 1,
+        }
+    }
+}
+
+/// `{"error":"…","code":"…"}`
+pub(crate) fn error_json(code: ErrorCode, message: &str) -> String {
+    format!(
+        "{{\"error\":\"{}\",\"code\":\"{}\"}}",
+        json_escape(message),
+        code.as_str()
+    )
+}
+
+static JSON: AtomicBool = AtomicBool::new(false);
+
+/// Report errors as JSON for the rest of the run.
+pub(crate) fn install_json(json: bool) {
+    JSON.store(json, Ordering::Relaxed);
+}
+
+/// Print an error to stderr: `error: message`, or the JSON object under
+/// `--json`.
+pub(crate) fn report(code: ErrorCode, message: &str) {
+    if JSON.load(Ordering::Relaxed) {
+        eprintln!("{}", error_json(code, message));
+    } else {
+        eprintln!("error: {}", message);
+    }
+}
+
+/// [`report`], then exit with the code's status.
+pub(crate) fn fail(code: ErrorCode, message: &str) -> ! {
+    report(code, message);
+    std::process::exit(code.exit_status());
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn error_objects() {
+        assert_eq!(
+            error_json(ErrorCode::NoSuchPort, "nothing on port 3000"),
+            r#"{"error":"nothing on port 3000","code":"no_such_port"}"#
+        );
+        assert_eq!(
+            error_json(ErrorCode::InvalidArgument, "bad \"sort\""),
+            r#"{"error":"bad \"sort\"","code":"invalid_argument"}"#
+        );
+        assert_eq!(ErrorCode::NoSuchUser.exit_status(), 2);
+        assert_eq!(ErrorCode::PermissionDenied.exit_status(), 1);
+    }
+}
@@ -0,0 +1,190 @@
+use std::collections::HashMap;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use crate::{json_escape, PortInfo, TcpState, JSON_SCHEMA_VERSION};
+
+// ── Port events ──────────────────────────────────────────────────────
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub(crate) enum PortEventKind {
+    Opened,
+    Closed,
+}
+
+impl PortEventKind {
+    pub(crate) fn as_str(self) -> &'static str {
+        match self {
+            PortEventKind::Opened => "opened",
+            PortEventKind::Closed => "closed",
+        }
+    }
+}
+
+/// A listener that appeared or went away between two watch refreshes.
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) struct PortEvent {
+    pub(crate) kind: PortEventKind,
+    pub(crate) port: u16,
+    pub(crate) protocol: String,
+    pub(crate) pid: u32,
+    pub(crate) process: String,
+}
+
+impl PortEvent {
+    /// `Port 22 (TCP) opened by sshd (PID 812)` or
+    /// `Port 22 (TCP) closed, was sshd (PID 812)`.
+    pub(crate) fn describe(&self) -> String {
+        let owner = if self.pid == 0 {
+            self.process.clone()
+        } else {
+            format!("{} (PID {})", self.process, self.pid)
+        };
+        let joiner = match self.kind {
+            PortEventKind::Opened => " by",
+            PortEventKind::Closed => ", was",
+        };
+        format!(
+            "Port {} ({}) {}{} {}",
+            self.port,
+            self.protocol,
+            self.kind.as_str(),
+            joiner,
+            owner
+        )
+    }
+}
+
+/// Webhook payload for one event; `timestamp` is Unix seconds.
+pub(crate) fn event_json(event: &PortEvent) -> String {
+    let timestamp = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .unwrap_or_default()
+        .as_secs();
+    format!(
+        r#"{{"schema_version":{},"event":"{}","port":{},"protocol":"{}","pid":{},"process":"{}","timestamp":{}}}"#,
+        JSON_SCHEMA_VERSION,
+        event.kind.as_str(),
+        event.port,
+        json_escape(&event.protocol),
+        event.pid,
+        json_escape(&event.process),
+        timestamp
+    )
+}
+
+type ListenerKey = (u16, String, u32);
+
+/// Diffs successive port lists into opened/closed events.
+///
+/// The first update only records a baseline, so starting `watch` doesn't
+/// report every existing listener as new. A process restarting on the same
+/// port between refreshes shows up as a close followed by an open.
+#[derive(Default)]
+pub(crate) struct PortTracker {
+    prev: Option<HashMap<ListenerKey, String>>,
+}
+
+impl PortTracker {
+    pub(crate) fn update<'a>(
+        &mut self,
+        infos: impl IntoIterator<Item = &'a PortInfo>,
+    ) -> Vec<PortEvent> {
+        let current: HashMap<ListenerKey, String> = infos
+            .into_iter()
+            .filter(|i| i.state == TcpState::Listen || i.protocol.starts_with("UDP"))
+            .map(|i| ((i.port, i.protocol.clone(), i.pid), i.process_name.clone()))
+            .collect();
+
+        let mut events = Vec::new();
+        if let Some(ref prev) = self.prev {
+            let event = |kind, key: &ListenerKey, process: &String| PortEvent {
+                kind,
+                port: key.0,
+                protocol: key.1.clone(),
+                pid: key.2,
+                process: process.clone(),
+            };
+            for (key, process) in prev {
+                if !current.contains_key(key) {
+                    events.push(event(PortEventKind::Closed, key, process));
+                }
+            }
+            for (key, process) in &current {
+                if !prev.contains_key(key) {
+                    events.push(event(PortEventKind::Opened, key, process));
+                }
+            }
+            events.sort_by(|a, b| {
+                a.port
+                    .cmp(&b.port)
+                    .then_with(|| a.protocol.cmp(&b.protocol))
+                    // Closed before opened, so a restart reads in order
+                    .then_with(|| {
+                        (a.kind == PortEventKind::Opened).cmp(&(b.kind == PortEventKind::Opened))
+                    })
+            });
+        }
+        self.prev = Some(current);
+        events
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn listener(port: u16, pid: u32, name: &str) -> PortInfo {
+        PortInfo::listener(port, pid, name).user("root")
+    }
+
+    #[test]
+    fn first_update_is_baseline() {
+        let mut t = PortTracker::default();
+        assert!(t.update(&[listener(22, 812, "sshd")]).is_empty());
+        assert!(t.update(&[listener(22, 812, "sshd")]).is_empty());
+    }
+
+    #[test]
+    fn reports_opened_and_closed() {
+        let mut t = PortTracker::default();
+        t.update(&[listener(22, 812, "sshd"), listener(3000, 100, "node")]);
+        let events = t.update(&[listener(22, 812, "sshd"), listener(8080, 200, "python3")]);
+        assert_eq!(events.len(), 2);
+        assert_eq!(events[0].kind, PortEventKind::Closed);
+        assert_eq!(events[0].port, 3000);
+        assert_eq!(events[1].kind, PortEventKind::Opened);
+        assert_eq!(
+            events[1].describe(),
+            "Port 8080 (TCP) opened by python3 (PID 200)"
+        );
+    }
+
+    #[test]
+    fn restart_is_close_then_open() {
+        let mut t = PortTracker::default();
+        t.update(&[listener(3000, 100, "node")]);
+        let events = t.update(&[listener(3000, 101, "node")]);
+        let kinds: Vec<_> = events.iter().map(|e| e.kind).collect();
+        assert_eq!(kinds, vec![PortEventKind::Closed, PortEventKind::Opened]);
+    }
+
+    #[test]
+    fn event_json_shape() {
+        let mut t = PortTracker::default();
+        t.update(&[]);
+        let events = t.update(&[listener(22, 812, "sshd")]);
+        let json = event_json(&events[0]);
+        assert!(json.starts_with(
+            r#"{"schema_version":1,"event":"opened","port":22,"protocol":"TCP","pid":812,"process":"sshd","timestamp":"#
+        ));
+    }
+
+    #[test]
+    fn ignores_non_listening_tcp() {
+        let mut t = PortTracker::default();
+        t.update(&[]);
+        let mut conn = listener(5432, 300, "postgres");
+        conn.state = TcpState::Established;
+        assert!(t.update(&[conn]).is_empty());
+    }
+}
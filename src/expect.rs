@@ -0,0 +1,748 @@
+use std::io::{self, Write};
+use std::net::{TcpStream, ToSocketAddrs};
+use std::path::Path;
+use std::process::{Command, Stdio};
+use std::time::{Duration, Instant};
+
+use crate::config::{parse_tables, Entry, Value};
+use crate::{json_escape, parse_duration, write_styled, PortInfo, TcpState};
+
+// ── Manifest ─────────────────────────────────────────────────────────
+//
+// The services a dev environment should be running, one `[[service]]`
+// table each. It can live in its own file or in the project's
+// `.portview.toml` next to `[labels]`:
+//
+//     [[service]]
+//     name = "web"
+//     port = 3000
+//     process = "node"                  # optional: expected owner
+//     health = "http://localhost:3000/" # optional: must answer 2xx/3xx
+//     expect_status = 204               # optional: exact HTTP status
+//     timeout = "500ms"                 # optional: default 2s
+//
+//     [[service]]
+//     name = "db"
+//     port = 5432
+//     health = "tcp"                    # connect to localhost:5432
+//
+//     [[service]]
+//     name = "dns"
+//     port = 5353
+//     proto = "udp"
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub(crate) enum Proto {
+    Tcp,
+    Udp,
+}
+
+impl Proto {
+    pub(crate) fn as_str(self) -> &'static str {
+        match self {
+            Proto::Tcp => "tcp",
+            Proto::Udp => "udp",
+        }
+    }
+
+    /// TCP services must be in LISTEN; any bound UDP socket counts.
+    fn matches(self, info: &PortInfo) -> bool {
+        match self {
+            Proto::Tcp => info.protocol.starts_with("TCP") && info.state == TcpState::Listen,
+            Proto::Udp => info.protocol.starts_with("UDP"),
+        }
+    }
+}
+
+/// What a health check connects to.
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) enum Probe {
+    /// GET the URL; any 2xx/3xx passes unless `status` names the one
+    /// expected.
+    Http { url: String, status: Option<u16> },
+    /// Open a TCP connection to `host:port`.
+    Tcp { addr: String },
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) struct Health {
+    pub(crate) probe: Probe,
+    pub(crate) timeout: Duration,
+}
+
+const DEFAULT_TIMEOUT: Duration = Duration::from_secs(2);
+
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) struct Service {
+    pub(crate) name: String,
+    pub(crate) port: u16,
+    pub(crate) proto: Proto,
+    /// Matched case-insensitively against the owner's name and command.
+    pub(crate) process: Option<String>,
+    pub(crate) health: Option<Health>,
+    /// 1-based line of its `[[service]]` header, for editor diagnostics.
+    pub(crate) line: usize,
+}
+
+#[derive(Debug, Clone, Default, PartialEq)]
+pub(crate) struct Manifest {
+    pub(crate) services: Vec<Service>,
+}
+
+const SERVICE_KEYS: &str = "name, port, proto, process, health, expect_status, timeout";
+
+impl Manifest {
+    /// Parse the `[[service]]` tables; other sections are ignored.
+    pub(crate) fn parse(text: &str) -> Result<Self, String> {
+        let mut services = Vec::new();
+        for table in parse_tables(text)? {
+            if table.name != "service" {
+                continue;
+            }
+            if !table.array {
+                return Err(format!(
+                    "line {}: services are `[[service]]` tables (double brackets)",
+                    table.line
+                ));
+            }
+            let mut name = None;
+            let mut port = None;
+            let mut proto = Proto::Tcp;
+            let mut process = None;
+            let mut health = None;
+            let mut expect_status = None;
+            let mut timeout = DEFAULT_TIMEOUT;
+            for entry in &table.entries {
+                match entry.key.as_str() {
+                    "name" => name = Some(string(entry)?),
+                    "port" => port = Some(port_number(entry)?),
+                    "proto" => {
+                        proto = match string(entry)?.to_ascii_lowercase().as_str() {
+                            "tcp" => Proto::Tcp,
+                            "udp" => Proto::Udp,
+                            other => {
+                                return Err(entry.error(format!(
+                                    "unknown proto '{}' (expected tcp or udp)",
+                                    other
+                                )))
+                            }
+                        }
+                    }
+                    "process" => process = Some(string(entry)?),
+                    "health" => health = Some((string(entry)?, entry)),
+                    "expect_status" => match entry.value {
+                        Value::Int(n @ 100..=599) => expect_status = Some((n as u16, entry)),
+                        _ => {
+                            return Err(entry
+                                .error("`expect_status` must be an HTTP status code".to_string()))
+                        }
+                    },
+                    "timeout" => timeout = duration(entry)?,
+                    other => {
+                        return Err(entry.error(format!(
+                            "unknown service key '{}' (expected {})",
+                            other, SERVICE_KEYS
+                        )))
+                    }
+                }
+            }
+            let port =
+                port.ok_or_else(|| format!("line {}: service is missing `port`", table.line))?;
+            let probe = match health {
+                Some((target, entry)) => Some(parse_probe(&target, port, entry)?),
+                None => None,
+            };
+            let health = match (probe, expect_status) {
+                (Some(Probe::Http { url, .. }), status) => Some(Probe::Http {
+                    url,
+                    status: status.map(|(code, _)| code),
+                }),
+                (_, Some((_, entry))) => {
+                    return Err(
+                        entry.error("`expect_status` needs an http:// health URL".to_string())
+                    )
+                }
+                (probe, None) => probe,
+            }
+            .map(|probe| Health { probe, timeout });
+            services.push(Service {
+                name: name.unwrap_or_else(|| port.to_string()),
+                port,
+                proto,
+                process,
+                health,
+                line: table.line,
+            });
+        }
+        if services.is_empty() {
+            return Err("no [[service]] entries".to_string());
+        }
+        Ok(Self { services })
+    }
+
+    /// Read and parse `path`; errors name the file.
+    pub(crate) fn load(path: &Path) -> Result<Self, String> {
+        let text =
+            std::fs::read_to_string(path).map_err(|e| format!("{}: {}", path.display(), e))?;
+        Self::parse(&text).map_err(|e| format!("{}: {}", path.display(), e))
+    }
+}
+
+fn string(entry: &Entry) -> Result<String, String> {
+    match &entry.value {
+        Value::Str(s) => Ok(s.clone()),
+        other => Err(entry.error(format!(
+            "`{}` must be a string, not {}",
+            entry.key,
+            other.type_name()
+        ))),
+    }
+}
+
+/// `"500ms"`/`"2s"`, or a bare number of seconds.
+fn duration(entry: &Entry) -> Result<Duration, String> {
+    let parsed = match &entry.value {
+        Value::Int(n) => u64::try_from(*n).ok().map(Duration::from_secs),
+        Value::Str(s) => parse_duration(s),
+        Value::Bool(_) => None,
+    };
+    parsed
+        .filter(|d| !d.is_zero())
+        .ok_or_else(|| entry.error(format!("invalid `{}` (try \"500ms\" or \"2s\")", entry.key)))
+}
+
+/// `http(s)://…`, `tcp://host:port`, or `tcp` for the service's own port on
+/// localhost.
+fn parse_probe(target: &str, port: u16, entry: &Entry) -> Result<Probe, String> {
+    if target.starts_with("http://") || target.starts_with("https://") {
+        Ok(Probe::Http {
+            url: target.to_string(),
+            status: None,
+        })
+    } else if target == "tcp" {
+        Ok(Probe::Tcp {
+            addr: format!("localhost:{}", port),
+        })
+    } else if let Some(addr) = target.strip_prefix("tcp://") {
+        Ok(Probe::Tcp {
+            addr: addr.trim_end_matches('/').to_string(),
+        })
+    } else {
+        Err(entry.error(format!(
+            "health '{}' must be an http(s):// URL, tcp://host:port or \"tcp\"",
+            target
+        )))
+    }
+}
+
+fn port_number(entry: &Entry) -> Result<u16, String> {
+    match entry.value {
+        Value::Int(n) if (1..=65535).contains(&n) => Ok(n as u16),
+        _ => Err(entry.error("`port` must be a number from 1 to 65535".to_string())),
+    }
+}
+
+// ── Checking ─────────────────────────────────────────────────────────
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub(crate) enum ServiceStatus {
+    Up,
+    /// Nothing is listening on the port.
+    Missing,
+    /// Something is listening, but not the expected process.
+    Unexpected,
+    /// The expected process is listening but its health URL failed.
+    Unhealthy,
+}
+
+impl ServiceStatus {
+    pub(crate) fn as_str(self) -> &'static str {
+        match self {
+            ServiceStatus::Up => "up",
+            ServiceStatus::Missing => "missing",
+            ServiceStatus::Unexpected => "unexpected",
+            ServiceStatus::Unhealthy => "unhealthy",
+        }
+    }
+
+    pub(crate) fn icon(self) -> (&'static str, &'static str) {
+        match self {
+            ServiceStatus::Up => ("✓", "green"),
+            ServiceStatus::Missing => ("✗", "red"),
+            ServiceStatus::Unexpected | ServiceStatus::Unhealthy => ("!", "yellow"),
+        }
+    }
+}
+
+/// How one service fared, with the listener found on its port.
+#[derive(Debug, Clone)]
+pub(crate) struct Outcome<'a> {
+    pub(crate) service: &'a Service,
+    pub(crate) status: ServiceStatus,
+    /// PID and process name of the listener, when there is one.
+    pub(crate) owner: Option<(u32, String)>,
+    pub(crate) detail: String,
+}
+
+/// Compare the manifest with the current listeners. `probe` runs a health
+/// check and returns how long it took, or why it failed.
+pub(crate) fn check<'a>(
+    manifest: &'a Manifest,
+    infos: &[PortInfo],
+    probe: impl Fn(&Health) -> Result<Duration, String>,
+) -> Vec<Outcome<'a>> {
+    manifest
+        .services
+        .iter()
+        .map(|service| {
+            let listeners: Vec<&PortInfo> = infos
+                .iter()
+                .filter(|i| i.port == service.port && service.proto.matches(i))
+                .collect();
+            let expected = service.process.as_deref().map(str::to_lowercase);
+            let is_expected = |i: &PortInfo| {
+                expected.as_deref().is_none_or(|want| {
+                    i.process_name.to_lowercase().contains(want)
+                        || i.command.to_lowercase().contains(want)
+                })
+            };
+            let matched = listeners.iter().copied().find(|i| is_expected(i));
+            let owner = matched
+                .or(listeners.first().copied())
+                .map(|i| (i.pid, i.process_name.clone()));
+            let (status, detail) = match (&owner, matched) {
+                (None, _) => (ServiceStatus::Missing, "not listening".to_string()),
+                (Some(_), None) => (
+                    ServiceStatus::Unexpected,
+                    format!(
+                        "expected {}",
+                        service.process.as_deref().unwrap_or_default()
+                    ),
+                ),
+                _ => match service.health.as_ref().map(&probe) {
+                    Some(Err(reason)) => (
+                        ServiceStatus::Unhealthy,
+                        format!("health check failed: {}", reason),
+                    ),
+                    Some(Ok(took)) => (
+                        ServiceStatus::Up,
+                        format!("healthy in {}ms", took.as_millis()),
+                    ),
+                    None => (ServiceStatus::Up, String::new()),
+                },
+            };
+            Outcome {
+                service,
+                status,
+                owner,
+                detail,
+            }
+        })
+        .collect()
+}
+
+/// Run one health check, timing it.
+pub(crate) fn run_probe(health: &Health) -> Result<Duration, String> {
+    let started = Instant::now();
+    match &health.probe {
+        Probe::Http { url, status } => probe_http(url, *status, health.timeout)?,
+        Probe::Tcp { addr } => probe_tcp(addr, health.timeout)?,
+    }
+    Ok(started.elapsed())
+}
+
+/// GET `url` via `curl`, as webhooks do, so HTTPS needs no TLS dependency.
+fn probe_http(url: &str, expected: Option<u16>, timeout: Duration) -> Result<(), String> {
+    let null = if cfg!(windows) { "NUL" } else { "/dev/null" };
+    let max_time = format!("{:.3}", timeout.as_secs_f64());
+    let output = Command::new("curl")
+        .args([
+            "-s",
+            "-o",
+            null,
+            "-w",
+            "%{http_code}",
+            "--max-time",
+            &max_time,
+            url,
+        ])
+        .stdin(Stdio::null())
+        .stderr(Stdio::null())
+        .output()
+        .map_err(|e| format!("cannot run curl: {}", e))?;
+    let code: u16 = String::from_utf8_lossy(&output.stdout)
+        .trim()
+        .parse()
+        .unwrap_or(0);
+    // curl exits 28 when --max-time runs out
+    match (code, expected) {
+        (0, _) if output.status.code() == Some(28) => Err("timed out".to_string()),
+        (0, _) => Err("no response".to_string()),
+        (code, Some(want)) if code != want => Err(format!("HTTP {}, expected {}", code, want)),
+        (_, Some(_)) | (200..=399, None) => Ok(()),
+        (code, None) => Err(format!("HTTP {}", code)),
+    }
+}
+
+/// Try each address `addr` resolves to until one accepts.
+fn probe_tcp(addr: &str, timeout: Duration) -> Result<(), String> {
+    let addrs = addr
+        .to_socket_addrs()
+        .map_err(|e| format!("cannot resolve {}: {}", addr, e))?;
+    let mut last_err = format!("{} did not resolve", addr);
+    for sock in addrs {
+        match TcpStream::connect_timeout(&sock, timeout) {
+            Ok(_) => return Ok(()),
+            Err(e) => last_err = e.to_string(),
+        }
+    }
+    Err(last_err)
+}
+
+// ── Output ───────────────────────────────────────────────────────────
+
+pub(crate) fn all_up(outcomes: &[Outcome]) -> bool {
+    outcomes.iter().all(|o| o.status == ServiceStatus::Up)
+}
+
+pub(crate) fn display_outcomes(outcomes: &[Outcome], use_color: bool) {
+    let mut out = io::stdout();
+    let name_w = outcomes
+        .iter()
+        .map(|o| o.service.name.len())
+        .max()
+        .unwrap_or(0);
+    let addrs: Vec<String> = outcomes
+        .iter()
+        .map(|o| format!("{}/{}", o.service.port, o.service.proto.as_str()))
+        .collect();
+    let addr_w = addrs.iter().map(String::len).max().unwrap_or(0);
+    let _ = writeln!(out);
+    for (outcome, addr) in outcomes.iter().zip(&addrs) {
+        let (icon, color) = outcome.status.icon();
+        let _ = write!(out, "  ");
+        write_styled(&mut out, icon, color, use_color);
+        let _ = write!(
+            out,
+            " {:<nw$}  {:<aw$}",
+            outcome.service.name,
+            addr,
+            nw = name_w,
+            aw = addr_w
+        );
+        if let Some((pid, ref name)) = outcome.owner {
+            let _ = write!(out, "  {} (pid {})", name, pid);
+        }
+        if !outcome.detail.is_empty() {
+            let sep = if outcome.owner.is_some() { ", " } else { "  " };
+            let _ = write!(out, "{}", sep);
+            let color = if outcome.status == ServiceStatus::Up {
+                "dimmed"
+            } else {
+                color
+            };
+            write_styled(&mut out, &outcome.detail, color, use_color);
+        }
+        let _ = writeln!(out);
+    }
+    let up = outcomes
+        .iter()
+        .filter(|o| o.status == ServiceStatus::Up)
+        .count();
+    let _ = writeln!(out);
+    let _ = writeln!(out, "{} of {} services up.", up, outcomes.len());
+}
+
+// ── Editor diagnostics ───────────────────────────────────────────────
+//
+// `--format vscode` prints one `file:line:col: severity: message` line
+// per failing service, pointing at its `[[service]]` table, so a VS Code
+// task's problem matcher (or any compiler-style matcher) lists them in
+// the Problems panel.
+
+/// `--format` for `portview expect`.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub(crate) enum ExpectFormat {
+    Text,
+    Json,
+    Vscode,
+}
+
+impl ExpectFormat {
+    pub(crate) fn parse(s: &str) -> Result<Self, String> {
+        match s.to_ascii_lowercase().as_str() {
+            "text" => Ok(ExpectFormat::Text),
+            "json" => Ok(ExpectFormat::Json),
+            "vscode" => Ok(ExpectFormat::Vscode),
+            _ => Err(format!("unknown format '{}' (text, json, vscode)", s)),
+        }
+    }
+}
+
+fn diagnostic(path: &str, line: usize, severity: &str, message: &str) -> String {
+    format!("{}:{}:1: {}: {}", path, line.max(1), severity, message)
+}
+
+/// One diagnostic per service that isn't up; nothing when all are.
+pub(crate) fn outcomes_vscode(path: &str, outcomes: &[Outcome]) -> String {
+    outcomes
+        .iter()
+        .filter(|o| o.status != ServiceStatus::Up)
+        .map(|o| {
+            let what = match (o.status, &o.owner) {
+                (ServiceStatus::Unexpected, Some((pid, name))) => {
+                    format!("held by {} (pid {}), {}", name, pid, o.detail)
+                }
+                _ => o.detail.clone(),
+            };
+            let severity = if o.status == ServiceStatus::Unhealthy {
+                "warning"
+            } else {
+                "error"
+            };
+            let message = format!(
+                "{} ({}/{}) {}: {}",
+                o.service.name,
+                o.service.port,
+                o.service.proto.as_str(),
+                o.status.as_str(),
+                what
+            );
+            diagnostic(path, o.service.line, severity, &message) + "\n"
+        })
+        .collect()
+}
+
+/// A manifest error from [`Manifest::parse`] as a diagnostic, at the line
+/// it names.
+pub(crate) fn parse_error_vscode(path: &str, err: &str) -> String {
+    let located = err
+        .strip_prefix("line ")
+        .and_then(|rest| rest.split_once(": "))
+        .and_then(|(n, msg)| Some((n.parse().ok()?, msg)));
+    let (line, msg) = located.unwrap_or((1, err));
+    diagnostic(path, line, "error", msg)
+}
+
+pub(crate) fn outcomes_json(outcomes: &[Outcome]) -> String {
+    let items: Vec<String> = outcomes
+        .iter()
+        .map(|o| {
+            let (pid, process) = match o.owner {
+                Some((pid, ref name)) => (pid.to_string(), format!("\"{}\"", json_escape(name))),
+                None => ("null".to_string(), "null".to_string()),
+            };
+            format!(
+                r#"{{"name":"{}","port":{},"proto":"{}","status":"{}","pid":{},"process":{},"detail":"{}"}}"#,
+                json_escape(&o.service.name),
+                o.service.port,
+                o.service.proto.as_str(),
+                o.status.as_str(),
+                pid,
+                process,
+                json_escape(&o.detail)
+            )
+        })
+        .collect();
+    format!(
+        r#"{{"ok":{},"services":[{}]}}"#,
+        all_up(outcomes),
+        items.join(",")
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::net::{IpAddr, Ipv4Addr};
+
+    const MANIFEST: &str = r#"
+[labels]
+3000 = "storefront"
+
+[[service]]
+name = "web"
+port = 3000
+process = "node"
+health = "http://localhost:3000/health"
+
+[[service]]
+name = "api"
+port = 8080
+process = "java"
+
+[[service]]
+name = "mail"
+port = 8025
+
+[[service]]
+port = 5353
+proto = "udp"
+"#;
+
+    fn info(port: u16, protocol: &str, pid: u32, process: &str) -> PortInfo {
+        PortInfo::listener(port, pid, process)
+            .protocol(protocol)
+            .command(&format!("{} --serve", process))
+            .addr(IpAddr::V4(Ipv4Addr::LOCALHOST))
+    }
+
+    #[test]
+    fn parse_services() {
+        let manifest = Manifest::parse(MANIFEST).unwrap();
+        assert_eq!(manifest.services.len(), 4);
+        let web = &manifest.services[0];
+        assert_eq!(
+            (web.name.as_str(), web.port, web.proto),
+            ("web", 3000, Proto::Tcp)
+        );
+        assert_eq!(web.process.as_deref(), Some("node"));
+        assert_eq!(manifest.services[3].name, "5353");
+        assert_eq!(manifest.services[3].proto, Proto::Udp);
+    }
+
+    #[test]
+    fn parse_errors() {
+        let err = Manifest::parse("[[service]]\nname = \"web\"\nport = 70000").unwrap_err();
+        assert_eq!(err, "line 3: `port` must be a number from 1 to 65535");
+        let err = Manifest::parse("[[service]]\nport = 1\nhost = \"x\"").unwrap_err();
+        assert!(err.starts_with("line 3: unknown service key 'host'"));
+        let err = Manifest::parse("[[service]]\nname = \"web\"").unwrap_err();
+        assert_eq!(err, "line 1: service is missing `port`");
+        assert!(Manifest::parse("[service]\nport = 1").is_err());
+        assert!(Manifest::parse("[labels]\n3000 = \"x\"").is_err());
+        let err = Manifest::parse("[[service]]\nport = 1\nhealth = \"tcp\"\nexpect_status = 200")
+            .unwrap_err();
+        assert_eq!(err, "line 4: `expect_status` needs an http:// health URL");
+        assert!(Manifest::parse("[[service]]\nport = 1\nhealth = \"ftp://x\"").is_err());
+        assert!(Manifest::parse("[[service]]\nport = 1\ntimeout = \"soon\"").is_err());
+    }
+
+    #[test]
+    fn parse_health_checks() {
+        let manifest = Manifest::parse(
+            r#"
+[[service]]
+port = 3000
+health = "http://localhost:3000/ready"
+expect_status = 204
+timeout = "500ms"
+
+[[service]]
+port = 5432
+health = "tcp"
+timeout = 5
+
+[[service]]
+port = 6379
+health = "tcp://cache.local:6379"
+"#,
+        )
+        .unwrap();
+        let health: Vec<&Health> = manifest
+            .services
+            .iter()
+            .map(|s| s.health.as_ref().unwrap())
+            .collect();
+        assert_eq!(
+            health[0],
+            &Health {
+                probe: Probe::Http {
+                    url: "http://localhost:3000/ready".to_string(),
+                    status: Some(204),
+                },
+                timeout: Duration::from_millis(500),
+            }
+        );
+        assert_eq!(
+            health[1].probe,
+            Probe::Tcp {
+                addr: "localhost:5432".to_string()
+            }
+        );
+        assert_eq!(health[1].timeout, Duration::from_secs(5));
+        assert_eq!(
+            health[2].probe,
+            Probe::Tcp {
+                addr: "cache.local:6379".to_string()
+            }
+        );
+        assert_eq!(health[2].timeout, DEFAULT_TIMEOUT);
+    }
+
+    #[test]
+    fn tcp_probe_connects() {
+        let listener = std::net::TcpListener::bind("127.0.0.1:0").unwrap();
+        let addr = listener.local_addr().unwrap().to_string();
+        assert!(probe_tcp(&addr, Duration::from_secs(1)).is_ok());
+        drop(listener);
+        assert!(probe_tcp(&addr, Duration::from_secs(1)).is_err());
+    }
+
+    #[test]
+    fn check_reports_each_status() {
+        let manifest = Manifest::parse(MANIFEST).unwrap();
+        let infos = vec![
+            info(3000, "TCP", 42, "node"),
+            info(8080, "TCP6", 51, "python3"),
+            info(5353, "UDP", 9, "avahi-daemon"),
+        ];
+        let outcomes = check(&manifest, &infos, |_| Err("HTTP 503".to_string()));
+        let statuses: Vec<ServiceStatus> = outcomes.iter().map(|o| o.status).collect();
+        assert_eq!(
+            statuses,
+            [
+                ServiceStatus::Unhealthy,
+                ServiceStatus::Unexpected,
+                ServiceStatus::Missing,
+                ServiceStatus::Up,
+            ]
+        );
+        assert_eq!(outcomes[0].detail, "health check failed: HTTP 503");
+        assert_eq!(outcomes[1].detail, "expected java");
+        assert_eq!(outcomes[1].owner, Some((51, "python3".to_string())));
+        assert!(!all_up(&outcomes));
+
+        let outcomes = check(&manifest, &infos, |_| Ok(Duration::from_millis(12)));
+        assert_eq!(outcomes[0].status, ServiceStatus::Up);
+        assert_eq!(outcomes[0].detail, "healthy in 12ms");
+    }
+
+    #[test]
+    fn vscode_diagnostics() {
+        let manifest = Manifest::parse(MANIFEST).unwrap();
+        let infos = vec![
+            info(3000, "TCP", 42, "node"),
+            info(8080, "TCP", 51, "python3"),
+        ];
+        let outcomes = check(&manifest, &infos, |_| Err("HTTP 503".to_string()));
+        assert_eq!(
+            outcomes_vscode("dev.toml", &outcomes),
+            "dev.toml:5:1: warning: web (3000/tcp) unhealthy: health check failed: HTTP 503\n\
+             dev.toml:11:1: error: api (8080/tcp) unexpected: held by python3 (pid 51), expected java\n\
+             dev.toml:16:1: error: mail (8025/tcp) missing: not listening\n\
+             dev.toml:20:1: error: 5353 (5353/udp) missing: not listening\n"
+        );
+        assert_eq!(
+            parse_error_vscode(
+                "dev.toml",
+                "line 3: `port` must be a number from 1 to 65535"
+            ),
+            "dev.toml:3:1: error: `port` must be a number from 1 to 65535"
+        );
+        assert_eq!(
+            parse_error_vscode("dev.toml", "no [[service]] entries"),
+            "dev.toml:1:1: error: no [[service]] entries"
+        );
+    }
+
+    #[test]
+    fn json_shape() {
+        let manifest = Manifest::parse("[[service]]\nname = \"db\"\nport = 5432").unwrap();
+        let outcomes = check(&manifest, &[], |_| Ok(Duration::ZERO));
+        assert_eq!(
+            outcomes_json(&outcomes),
+            r#"{"ok":false,"services":[{"name":"db","port":5432,"proto":"tcp","status":"missing","pid":null,"process":null,"detail":"not listening"}]}"#
+        );
+    }
+}
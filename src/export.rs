@@ -0,0 +1,151 @@
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use crate::clock::LocalTime;
+use crate::docker::DockerPortMap;
+use crate::{export_json, PortInfo};
+
+// ── View export ──────────────────────────────────────────────────────
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub(crate) enum ExportFormat {
+    Json,
+    Csv,
+}
+
+impl ExportFormat {
+    /// CSV for a `.csv` path, JSON otherwise.
+    pub(crate) fn for_path(path: &Path) -> Self {
+        match path.extension().and_then(|e| e.to_str()) {
+            Some(ext) if ext.eq_ignore_ascii_case("csv") => ExportFormat::Csv,
+            _ => ExportFormat::Json,
+        }
+    }
+
+    pub(crate) fn as_str(self) -> &'static str {
+        match self {
+            ExportFormat::Json => "JSON",
+            ExportFormat::Csv => "CSV",
+        }
+    }
+}
+
+/// `portview-20261016-140322.json` in the current directory.
+pub(crate) fn default_path() -> String {
+    let stamp = LocalTime::from_system(SystemTime::now())
+        .map(|t| t.file_stamp())
+        .unwrap_or_else(|| {
+            SystemTime::now()
+                .duration_since(UNIX_EPOCH)
+                .unwrap_or_default()
+                .as_secs()
+                .to_string()
+        });
+    format!("portview-{}.json", stamp)
+}
+
+/// Expand a leading `~/` to the home directory.
+pub(crate) fn expand_path(path: &str) -> PathBuf {
+    let home = std::env::var_os("HOME").or_else(|| std::env::var_os("USERPROFILE"));
+    match (path.strip_prefix("~/"), home) {
+        (Some(rest), Some(home)) => PathBuf::from(home).join(rest),
+        _ => PathBuf::from(path),
+    }
+}
+
+/// Write the rows in view order; returns the format used.
+pub(crate) fn write_view(
+    path: &Path,
+    infos: &[&PortInfo],
+    docker_map: Option<&DockerPortMap>,
+    cwds: &HashMap<u32, String>,
+) -> std::io::Result<ExportFormat> {
+    let format = ExportFormat::for_path(path);
+    let mut body = match format {
+        ExportFormat::Json => export_json(infos, docker_map, cwds),
+        ExportFormat::Csv => to_csv(infos, cwds),
+    };
+    if !body.ends_with('\n') {
+        body.push('\n');
+    }
+    std::fs::write(path, body)?;
+    Ok(format)
+}
+
+const CSV_HEADER: &str = "port,protocol,pid,process,user,state,memory_bytes,cpu_seconds,start_time,children,addr,cwd,command";
+
+fn to_csv(infos: &[&PortInfo], cwds: &HashMap<u32, String>) -> String {
+    let mut out = String::from(CSV_HEADER);
+    out.push('\n');
+    for info in infos {
+        let start = info
+            .start_time
+            .and_then(|t| t.duration_since(UNIX_EPOCH).ok())
+            .map(|d| d.as_secs().to_string())
+            .unwrap_or_default();
+        let fields = [
+            info.port.to_string(),
+            csv_field(&info.protocol),
+            info.pid.to_string(),
+            csv_field(&info.process_name),
+            csv_field(&info.user),
+            info.state.as_str().to_string(),
+            info.memory_bytes.to_string(),
+            format!("{:.1}", info.cpu_seconds),
+            start,
+            info.children.to_string(),
+            info.local_addr.to_string(),
+            csv_field(cwds.get(&info.pid).map(String::as_str).unwrap_or("")),
+            csv_field(&info.command),
+        ];
+        out.push_str(&fields.join(","));
+        out.push('\n');
+    }
+    out
+}
+
+/// RFC 4180 quoting: only fields with commas, quotes or newlines.
+fn csv_field(s: &str) -> String {
+    if s.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", s.replace('"', "\"\""))
+    } else {
+        s.to_string()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use std::net::{IpAddr, Ipv4Addr};
+
+    #[test]
+    fn csv_quotes_only_when_needed() {
+        let info = PortInfo::listener(3000, 42, "node")
+            .command(r#"node -e "a,b""#)
+            .usage(2048, 1.25)
+            .addr(IpAddr::V4(Ipv4Addr::LOCALHOST));
+        let cwds = HashMap::from([(42, "/srv/app".to_string())]);
+        let csv = to_csv(&[&info], &cwds);
+        let mut lines = csv.lines();
+        assert_eq!(lines.next(), Some(CSV_HEADER));
+        assert_eq!(
+            lines.next(),
+            Some(r#"3000,TCP,42,node,dev,LISTEN,2048,1.2,,0,127.0.0.1,/srv/app,"node -e ""a,b""""#)
+        );
+    }
+
+    #[test]
+    fn format_follows_extension() {
+        assert_eq!(
+            ExportFormat::for_path(Path::new("ports.CSV")),
+            ExportFormat::Csv
+        );
+        assert_eq!(
+            ExportFormat::for_path(Path::new("ports")),
+            ExportFormat::Json
+        );
+        assert!(default_path().starts_with("portview-"));
+    }
+}
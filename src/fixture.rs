@@ -0,0 +1,715 @@
+use std::io;
+use std::net::{IpAddr, Ipv4Addr};
+use std::sync::{Arc, Mutex, MutexGuard};
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+use crate::actions::{ActionExecutor, SystemExecutor};
+use crate::batch::Target;
+use crate::collector::{Capabilities, PortCollector};
+use crate::docker::ComposeService;
+use crate::orphan::Lineage;
+use crate::quic::{self, Quic};
+use crate::signals::Signal;
+use crate::sockopts::SocketOptions;
+use crate::udpservice::{ServiceReply, UdpService};
+use crate::{ConnInfo, PortInfo, TcpState};
+
+// ── Fixture backend ──────────────────────────────────────────────────
+//
+// `--fixture FILE` replaces the OS with a JSON array of port objects, in
+// the same shape `--json` prints, so `portview --json > f.json` captures a
+// machine for later. Hand-written rows may also set `addr`, `uptime_seconds`
+// (instead of `start_time`), `uid` for `--user 1000`, `cwd`, `ppid`,
+// `session` and `zombie` for orphan checks, and `socket_options`, `quic` and `service_reply` in the
+// shape an inspected port's JSON prints. Kills go through a
+// [`FixtureExecutor`] that only removes rows; nothing is signalled.
+
+#[derive(Debug, Clone)]
+struct FixtureRow {
+    info: PortInfo,
+    uid: Option<u32>,
+    cwd: Option<String>,
+    lineage: Option<Lineage>,
+    socket_options: Option<SocketOptions>,
+    quic: Option<Quic>,
+    service_reply: Option<ServiceReply>,
+}
+
+/// Serves ports from a `--fixture` file instead of the OS.
+pub(crate) struct FixtureCollector {
+    rows: Arc<Mutex<Vec<FixtureRow>>>,
+}
+
+impl FixtureCollector {
+    pub(crate) fn parse(text: &str) -> Result<Self, String> {
+        Ok(Self {
+            rows: Arc::new(Mutex::new(parse_rows(text, SystemTime::now())?)),
+        })
+    }
+
+    /// Kills against this fixture's rows.
+    pub(crate) fn executor(&self) -> FixtureExecutor {
+        FixtureExecutor {
+            rows: Arc::clone(&self.rows),
+        }
+    }
+
+    /// A copy of the row behind `info`: same PID, port and protocol.
+    fn row_for(&self, info: &PortInfo) -> Option<FixtureRow> {
+        self.rows()
+            .iter()
+            .find(|r| {
+                r.info.pid == info.pid
+                    && r.info.port == info.port
+                    && r.info.protocol == info.protocol
+            })
+            .cloned()
+    }
+
+    fn rows(&self) -> MutexGuard<'_, Vec<FixtureRow>> {
+        self.rows.lock().unwrap_or_else(|e| e.into_inner())
+    }
+}
+
+impl PortCollector for FixtureCollector {
+    fn name(&self) -> &'static str {
+        "fixture"
+    }
+
+    fn capabilities(&self) -> Capabilities {
+        Capabilities {
+            supports_remote_addr: false,
+            supports_udp_state: true,
+            needs_privileges: false,
+        }
+    }
+
+    /// Accounts are the ones the rows run as; a number matches a row's
+    /// `uid`.
+    fn resolve_user(&self, user: &str) -> Option<String> {
+        let rows = self.rows();
+        let uid: Option<u32> = user.parse().ok();
+        rows.iter()
+            .find(|r| match uid {
+                Some(uid) => r.uid == Some(uid),
+                None => r.info.user == user,
+            })
+            .map(|r| r.info.user.clone())
+    }
+
+    /// Sorted and deduplicated the way the OS backends return rows.
+    fn port_infos(&self, filter_listening: bool) -> Vec<PortInfo> {
+        let mut infos: Vec<PortInfo> = self
+            .rows()
+            .iter()
+            .map(|r| r.info.clone())
+            .filter(|i| {
+                !filter_listening || i.state == TcpState::Listen || i.protocol.starts_with("UDP")
+            })
+            .collect();
+        infos.sort_by(|a, b| {
+            a.port
+                .cmp(&b.port)
+                .then_with(|| a.protocol.cmp(&b.protocol))
+                .then_with(|| a.pid.cmp(&b.pid))
+        });
+        infos.dedup_by(|a, b| a.port == b.port && a.protocol == b.protocol && a.pid == b.pid);
+        infos
+    }
+
+    /// Fixture rows carry no remote end, so connections report an
+    /// unspecified peer.
+    fn connections(&self) -> Vec<ConnInfo> {
+        self.port_infos(false)
+            .into_iter()
+            .map(|i| ConnInfo {
+                protocol: i.protocol,
+                local_port: i.port,
+                remote_addr: IpAddr::V4(Ipv4Addr::UNSPECIFIED),
+                remote_port: 0,
+                state: i.state,
+                pid: (i.pid != 0).then_some(i.pid),
+            })
+            .collect()
+    }
+
+    fn process_alive(&self, pid: u32) -> Option<bool> {
+        Some(self.rows().iter().any(|r| r.info.pid == pid))
+    }
+
+    fn process_lineage(&self, pid: u32) -> Option<Lineage> {
+        self.rows()
+            .iter()
+            .find(|r| r.info.pid == pid)
+            .and_then(|r| r.lineage)
+    }
+
+    fn process_cwd(&self, pid: u32) -> Option<String> {
+        self.rows()
+            .iter()
+            .find(|r| r.info.pid == pid)
+            .and_then(|r| r.cwd.clone())
+    }
+
+    fn socket_options(&self, info: &PortInfo) -> Option<SocketOptions> {
+        self.row_for(info).and_then(|r| r.socket_options)
+    }
+
+    fn probe_quic(&self, info: &PortInfo) -> Option<Quic> {
+        self.row_for(info).and_then(|r| r.quic)
+    }
+
+    fn probe_udp_service(&self, info: &PortInfo, _service: UdpService) -> Option<ServiceReply> {
+        self.row_for(info).and_then(|r| r.service_reply)
+    }
+}
+
+/// Signals that drop the process's rows from the fixture it came from.
+/// Docker actions still go to the real CLI.
+pub(crate) struct FixtureExecutor {
+    rows: Arc<Mutex<Vec<FixtureRow>>>,
+}
+
+impl ActionExecutor for FixtureExecutor {
+    /// Drop the process's rows if the signal stops it, and report the
+    /// signal the real platform would have sent.
+    fn signal(&self, target: &Target, signal: Signal) -> io::Result<String> {
+        if target.pid == 0 {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidInput,
+                "Refusing to signal PID 0",
+            ));
+        }
+        #[cfg(windows)]
+        if !signal.supported_on_windows() {
+            return Err(io::Error::new(
+                io::ErrorKind::Unsupported,
+                crate::signals::windows_fallback(signal),
+            ));
+        }
+        let mut rows = self.rows.lock().unwrap_or_else(|e| e.into_inner());
+        if !rows.iter().any(|r| r.info.pid == target.pid) {
+            return Err(io::Error::from(io::ErrorKind::NotFound));
+        }
+        if signal.stops_process() {
+            rows.retain(|r| r.info.pid != target.pid);
+        }
+        Ok(if cfg!(windows) {
+            "TerminateProcess".to_string()
+        } else {
+            signal.name()
+        })
+    }
+
+    fn docker_action(&self, action: &str, container: &str) -> String {
+        SystemExecutor.docker_action(action, container)
+    }
+
+    fn docker_logs(&self, container: &str) -> String {
+        SystemExecutor.docker_logs(container)
+    }
+
+    fn compose_restart(&self, compose: &ComposeService) -> String {
+        SystemExecutor.compose_restart(compose)
+    }
+}
+
+// ── Row decoding ─────────────────────────────────────────────────────
+
+/// The ports in a saved snapshot: a `--json` array, or a `portview
+/// daemon` status file with the array under `ports`.
+pub(crate) fn parse_snapshot(text: &str) -> Result<Vec<PortInfo>, String> {
+    let items = match parse_json(text)? {
+        Json::Arr(items) => items,
+        Json::Obj(fields) => match fields.into_iter().find(|(k, _)| k == "ports") {
+            Some((_, Json::Arr(items))) => items,
+            _ => return Err("expected a JSON array of ports or an object with `ports`".to_string()),
+        },
+        _ => return Err("expected a JSON array of ports".to_string()),
+    };
+    let now = SystemTime::now();
+    items
+        .iter()
+        .enumerate()
+        .map(|(idx, item)| {
+            decode_row(item, now)
+                .map(|row| row.info)
+                .map_err(|e| format!("row {}: {}", idx, e))
+        })
+        .collect()
+}
+
+fn parse_rows(text: &str, now: SystemTime) -> Result<Vec<FixtureRow>, String> {
+    let Json::Arr(items) = parse_json(text)? else {
+        return Err("fixture must be a JSON array of port objects".to_string());
+    };
+    items
+        .iter()
+        .enumerate()
+        .map(|(idx, item)| decode_row(item, now).map_err(|e| format!("row {}: {}", idx, e)))
+        .collect()
+}
+
+fn decode_row(item: &Json, now: SystemTime) -> Result<FixtureRow, String> {
+    let Json::Obj(fields) = item else {
+        return Err("expected an object".to_string());
+    };
+    let get = |key: &str| fields.iter().find(|(k, _)| k == key).map(|(_, v)| v);
+    let num = |key: &str| match get(key) {
+        Some(Json::Num(n)) => Ok(Some(*n)),
+        None | Some(Json::Null) => Ok(None),
+        Some(_) => Err(format!("'{}' must be a number", key)),
+    };
+    let text = |key: &str| match get(key) {
+        Some(Json::Str(s)) => Ok(Some(s.clone())),
+        None | Some(Json::Null) => Ok(None),
+        Some(_) => Err(format!("'{}' must be a string", key)),
+    };
+
+    let port = num("port")?
+        .filter(|p| (0.0..=65535.0).contains(p) && p.fract() == 0.0)
+        .ok_or("'port' must be 0-65535")? as u16;
+    let process_name = text("process")?.unwrap_or_default();
+    let local_addr = match text("addr")? {
+        Some(a) => a
+            .parse()
+            .map_err(|_| format!("'addr' is not an IP address: {}", a))?,
+        None => IpAddr::V4(Ipv4Addr::UNSPECIFIED),
+    };
+    let info = PortInfo {
+        port,
+        // The OS backends report TCP6/UDP6 sockets as TCP/UDP; the address
+        // tells the families apart
+        protocol: text("protocol")?
+            .map(|p| p.strip_suffix('6').unwrap_or(&p).to_string())
+            .unwrap_or_else(|| "TCP".to_string()),
+        pid: num("pid")?.unwrap_or(0.0) as u32,
+        command: text("command")?.unwrap_or_else(|| process_name.clone()),
+        process_name,
+        user: text("user")?.unwrap_or_default(),
+        state: text("state")?
+            .map(|s| TcpState::from_name(&s))
+            .unwrap_or(TcpState::Listen),
+        memory_bytes: num("memory_bytes")?.unwrap_or(0.0) as u64,
+        cpu_seconds: num("cpu_seconds")?.unwrap_or(0.0),
+        // `start_time` as printed by --json; `uptime_seconds` keeps
+        // hand-written fixtures independent of the clock
+        start_time: match num("start_time")? {
+            Some(epoch) => Some(UNIX_EPOCH + Duration::from_secs_f64(epoch.max(0.0))),
+            None => num("uptime_seconds")?
+                .and_then(|secs| now.checked_sub(Duration::from_secs_f64(secs.max(0.0)))),
+        },
+        children: num("children")?.unwrap_or(0.0) as u32,
+        local_addr,
+    };
+    let lineage = match num("ppid")? {
+        Some(ppid) => Some(Lineage {
+            zombie: match get("zombie") {
+                Some(Json::Bool(zombie)) => *zombie,
+                None | Some(Json::Null) => false,
+                Some(_) => return Err("'zombie' must be a boolean".to_string()),
+            },
+            ppid: ppid as u32,
+            session: num("session")?.unwrap_or(0.0) as u32,
+        }),
+        None => None,
+    };
+    let socket_options = match get("socket_options") {
+        Some(Json::Obj(opts)) => Some(decode_socket_options(opts)?),
+        None | Some(Json::Null) => None,
+        Some(_) => return Err("'socket_options' must be an object".to_string()),
+    };
+    let quic = match get("quic") {
+        Some(Json::Obj(quic)) => Some(decode_quic(quic)?),
+        None | Some(Json::Null) => None,
+        Some(_) => return Err("'quic' must be an object".to_string()),
+    };
+    let service_reply = match get("service_reply") {
+        Some(Json::Obj(reply)) => Some(decode_service_reply(reply)?),
+        None | Some(Json::Null) => None,
+        Some(_) => return Err("'service_reply' must be an object".to_string()),
+    };
+    Ok(FixtureRow {
+        info,
+        uid: num("uid")?.map(|uid| uid as u32),
+        cwd: text("cwd")?,
+        lineage,
+        socket_options,
+        quic,
+        service_reply,
+    })
+}
+
+fn decode_service_reply(fields: &[(String, Json)]) -> Result<ServiceReply, String> {
+    let get = |key: &str| fields.iter().find(|(k, _)| k == key).map(|(_, v)| v);
+    let server = match get("server") {
+        Some(Json::Str(s)) => Some(s.clone()),
+        None | Some(Json::Null) => None,
+        Some(_) => return Err("'service_reply.server' must be a string".to_string()),
+    };
+    let advertised = match get("advertised") {
+        Some(Json::Arr(items)) => items
+            .iter()
+            .map(|v| match v {
+                Json::Str(s) => Ok(s.clone()),
+                _ => Err("'service_reply.advertised' must hold strings".to_string()),
+            })
+            .collect::<Result<_, _>>()?,
+        None | Some(Json::Null) => Vec::new(),
+        Some(_) => return Err("'service_reply.advertised' must be an array".to_string()),
+    };
+    Ok(ServiceReply { server, advertised })
+}
+
+fn decode_quic(fields: &[(String, Json)]) -> Result<Quic, String> {
+    let versions = match fields.iter().find(|(k, _)| k == "versions").map(|(_, v)| v) {
+        Some(Json::Arr(items)) => items,
+        None | Some(Json::Null) => {
+            return Ok(Quic {
+                versions: Vec::new(),
+            })
+        }
+        Some(_) => return Err("'quic.versions' must be an array".to_string()),
+    };
+    let versions = versions
+        .iter()
+        .map(|v| match v {
+            Json::Str(name) => {
+                quic::parse_version(name).ok_or_else(|| format!("unknown QUIC version '{}'", name))
+            }
+            _ => Err("'quic.versions' must hold strings".to_string()),
+        })
+        .collect::<Result<_, _>>()?;
+    Ok(Quic { versions })
+}
+
+fn decode_socket_options(fields: &[(String, Json)]) -> Result<SocketOptions, String> {
+    let get = |key: &str| fields.iter().find(|(k, _)| k == key).map(|(_, v)| v);
+    let flag = |key: &str| match get(key) {
+        Some(Json::Bool(b)) => Ok(Some(*b)),
+        None | Some(Json::Null) => Ok(None),
+        Some(_) => Err(format!("'socket_options.{}' must be a boolean", key)),
+    };
+    let num = |key: &str| match get(key) {
+        Some(Json::Num(n)) if (0.0..=u32::MAX as f64).contains(n) && n.fract() == 0.0 => {
+            Ok(Some(*n as u32))
+        }
+        None | Some(Json::Null) => Ok(None),
+        Some(_) => Err(format!(
+            "'socket_options.{}' must be a non-negative integer",
+            key
+        )),
+    };
+    Ok(SocketOptions {
+        reuse_addr: flag("reuseaddr")?,
+        reuse_port: flag("reuseport")?,
+        no_delay: flag("nodelay")?,
+        keepalive: flag("keepalive")?,
+        keepalive_idle: num("keepalive_idle")?,
+        keepalive_interval: num("keepalive_interval")?,
+        keepalive_count: num("keepalive_count")?,
+        recv_buffer: num("recv_buffer")?,
+        send_buffer: num("send_buffer")?,
+    })
+}
+
+// ── Minimal JSON reader ──────────────────────────────────────────────
+
+#[derive(Debug, Clone, PartialEq)]
+enum Json {
+    Null,
+    Bool(bool),
+    Num(f64),
+    Str(String),
+    Arr(Vec<Json>),
+    Obj(Vec<(String, Json)>),
+}
+
+fn parse_json(text: &str) -> Result<Json, String> {
+    let mut p = Parser {
+        chars: text.chars().collect(),
+        pos: 0,
+    };
+    let value = p.value()?;
+    p.skip_ws();
+    if p.pos < p.chars.len() {
+        return Err(p.error("trailing characters"));
+    }
+    Ok(value)
+}
+
+struct Parser {
+    chars: Vec<char>,
+    pos: usize,
+}
+
+impl Parser {
+    fn error(&self, msg: &str) -> String {
+        format!("invalid JSON at offset {}: {}", self.pos, msg)
+    }
+
+    fn skip_ws(&mut self) {
+        while self.chars.get(self.pos).is_some_and(|c| c.is_whitespace()) {
+            self.pos += 1;
+        }
+    }
+
+    fn eat(&mut self, c: char) -> bool {
+        self.skip_ws();
+        if self.chars.get(self.pos) == Some(&c) {
+            self.pos += 1;
+            true
+        } else {
+            false
+        }
+    }
+
+    fn literal(&mut self, word: &str, value: Json) -> Result<Json, String> {
+        let end = self.pos + word.len();
+        if self
+            .chars
+            .get(self.pos..end)
+            .is_some_and(|s| s.iter().copied().eq(word.chars()))
+        {
+            self.pos = end;
+            Ok(value)
+        } else {
+            Err(self.error("unexpected token"))
+        }
+    }
+
+    fn value(&mut self) -> Result<Json, String> {
+        self.skip_ws();
+        match self.chars.get(self.pos) {
+            Some('{') => self.object(),
+            Some('[') => self.array(),
+            Some('"') => self.string().map(Json::Str),
+            Some('t') => self.literal("true", Json::Bool(true)),
+            Some('f') => self.literal("false", Json::Bool(false)),
+            Some('n') => self.literal("null", Json::Null),
+            Some(c) if *c == '-' || c.is_ascii_digit() => self.number(),
+            Some(_) => Err(self.error("unexpected character")),
+            None => Err(self.error("unexpected end of input")),
+        }
+    }
+
+    fn object(&mut self) -> Result<Json, String> {
+        self.pos += 1;
+        let mut fields = Vec::new();
+        if self.eat('}') {
+            return Ok(Json::Obj(fields));
+        }
+        loop {
+            self.skip_ws();
+            if self.chars.get(self.pos) != Some(&'"') {
+                return Err(self.error("expected a key"));
+            }
+            let key = self.string()?;
+            if !self.eat(':') {
+                return Err(self.error("expected ':'"));
+            }
+            fields.push((key, self.value()?));
+            if self.eat('}') {
+                return Ok(Json::Obj(fields));
+            }
+            if !self.eat(',') {
+                return Err(self.error("expected ',' or '}'"));
+            }
+        }
+    }
+
+    fn array(&mut self) -> Result<Json, String> {
+        self.pos += 1;
+        let mut items = Vec::new();
+        if self.eat(']') {
+            return Ok(Json::Arr(items));
+        }
+        loop {
+            items.push(self.value()?);
+            if self.eat(']') {
+                return Ok(Json::Arr(items));
+            }
+            if !self.eat(',') {
+                return Err(self.error("expected ',' or ']'"));
+            }
+        }
+    }
+
+    fn string(&mut self) -> Result<String, String> {
+        self.pos += 1; // opening quote
+        let mut out = String::new();
+        loop {
+            let Some(&c) = self.chars.get(self.pos) else {
+                return Err(self.error("unterminated string"));
+            };
+            self.pos += 1;
+            match c {
+                '"' => return Ok(out),
+                '\\' => {
+                    let Some(&esc) = self.chars.get(self.pos) else {
+                        return Err(self.error("unterminated escape"));
+                    };
+                    self.pos += 1;
+                    match esc {
+                        'n' => out.push('\n'),
+                        't' => out.push('\t'),
+                        'r' => out.push('\r'),
+                        'b' => out.push('\u{8}'),
+                        'f' => out.push('\u{c}'),
+                        'u' => {
+                            let hex: String = self
+                                .chars
+                                .get(self.pos..self.pos + 4)
+                                .ok_or_else(|| self.error("short \\u escape"))?
+                                .iter()
+                                .collect();
+                            let code = u32::from_str_radix(&hex, 16)
+                                .map_err(|_| self.error("bad \\u escape"))?;
+                            self.pos += 4;
+                            out.push(char::from_u32(code).unwrap_or('\u{fffd}'));
+                        }
+                        other => out.push(other),
+                    }
+                }
+                _ => out.push(c),
+            }
+        }
+    }
+
+    fn number(&mut self) -> Result<Json, String> {
+        let start = self.pos;
+        while self
+            .chars
+            .get(self.pos)
+            .is_some_and(|c| c.is_ascii_digit() || matches!(c, '-' | '+' | '.' | 'e' | 'E'))
+        {
+            self.pos += 1;
+        }
+        let text: String = self.chars[start..self.pos].iter().collect();
+        text.parse()
+            .map(Json::Num)
+            .map_err(|_| self.error("bad number"))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_json_values() {
+        assert_eq!(
+            parse_json(r#" {"a": [1, -2.5e1, true, null], "b": "x\"é\n"} "#).unwrap(),
+            Json::Obj(vec![
+                (
+                    "a".to_string(),
+                    Json::Arr(vec![
+                        Json::Num(1.0),
+                        Json::Num(-25.0),
+                        Json::Bool(true),
+                        Json::Null
+                    ])
+                ),
+                ("b".to_string(), Json::Str("x\"é\n".to_string())),
+            ])
+        );
+        assert!(parse_json("[1,]").is_err());
+        assert!(parse_json("{\"a\" 1}").is_err());
+        assert!(parse_json("[1] x").is_err());
+    }
+
+    #[test]
+    fn rows_accept_json_output_shape() {
+        let now = SystemTime::now();
+        let text = r#"[
+            {"schema_version":1,"port":3000,"protocol":"TCP","pid":4242,"process":"node",
+             "command":"node server.js","user":"mark","state":"LISTEN",
+             "memory_bytes":1048576,"cpu_seconds":1.5,"children":2,
+             "socket_options":{"reuseaddr":true,"nodelay":null,"recv_buffer":131072}},
+            {"port":5353,"protocol":"UDP","process":"mdns","addr":"127.0.0.1",
+             "uptime_seconds":60,"cwd":"/srv","ppid":1,"session":900,"zombie":true,
+             "quic":{"versions":["v1","draft-29"]},
+             "service_reply":{"advertised":["laptop (_ssh._tcp)"]}}
+        ]"#;
+        let rows = parse_rows(text, now).unwrap();
+        assert_eq!(rows.len(), 2);
+        assert_eq!(rows[0].info.command, "node server.js");
+        assert_eq!(rows[0].info.state, TcpState::Listen);
+        assert_eq!(rows[0].info.children, 2);
+        assert_eq!(rows[1].info.command, "mdns");
+        assert_eq!(rows[1].info.local_addr, IpAddr::V4(Ipv4Addr::LOCALHOST));
+        assert_eq!(
+            rows[1].info.start_time,
+            now.checked_sub(Duration::from_secs(60))
+        );
+        assert_eq!(rows[1].cwd.as_deref(), Some("/srv"));
+        assert_eq!(rows[0].lineage, None);
+        assert_eq!(
+            rows[0].socket_options,
+            Some(SocketOptions {
+                reuse_addr: Some(true),
+                recv_buffer: Some(131072),
+                ..Default::default()
+            })
+        );
+        assert_eq!(rows[1].socket_options, None);
+        assert_eq!(rows[0].quic, None);
+        assert_eq!(
+            rows[1].service_reply.as_ref().unwrap().advertised,
+            vec!["laptop (_ssh._tcp)".to_string()]
+        );
+        assert_eq!(
+            rows[1].quic,
+            Some(Quic {
+                versions: vec![1, 0xff00_001d]
+            })
+        );
+        assert_eq!(
+            rows[1].lineage,
+            Some(Lineage {
+                zombie: true,
+                ppid: 1,
+                session: 900
+            })
+        );
+    }
+
+    #[test]
+    fn snapshot_accepts_daemon_status_file() {
+        let array = parse_snapshot(r#"[{"port":3000,"pid":1,"process":"node"}]"#).unwrap();
+        let status =
+            parse_snapshot(r#"{"updated":1,"ports":[{"port":3000,"pid":1,"process":"node"}]}"#)
+                .unwrap();
+        assert_eq!(array[0].port, status[0].port);
+        assert!(parse_snapshot(r#"{"updated":1}"#).is_err());
+    }
+
+    #[test]
+    fn rows_report_bad_fields() {
+        let now = SystemTime::now();
+        assert!(parse_rows(r#"{"port":1}"#, now).is_err());
+        let err = parse_rows(r#"[{"port":1},{"port":"80"}]"#, now).unwrap_err();
+        assert!(err.starts_with("row 1:"), "{}", err);
+        assert!(parse_rows(r#"[{"port":70000}]"#, now).is_err());
+        assert!(parse_rows(r#"[{"port":1,"addr":"nope"}]"#, now).is_err());
+        assert!(parse_rows(r#"[{"port":1,"socket_options":{"recv_buffer":-1}}]"#, now).is_err());
+        assert!(parse_rows(r#"[{"port":1,"quic":{"versions":["h3"]}}]"#, now).is_err());
+        assert!(parse_rows(r#"[{"port":1,"service_reply":{"advertised":"x"}}]"#, now).is_err());
+    }
+
+    #[test]
+    fn executor_drops_the_signalled_process() {
+        let fixture = FixtureCollector::parse(
+            r#"[{"port":3000,"pid":7,"process":"node"},{"port":3001,"pid":7,"process":"node"},
+                {"port":5432,"pid":8,"process":"postgres"}]"#,
+        )
+        .unwrap();
+        let executor = fixture.executor();
+        let node = Target::holding(7, "node", &fixture.port_infos(true));
+        executor.signal(&node, Signal::Term).unwrap();
+        let ports: Vec<u16> = fixture.port_infos(true).iter().map(|i| i.port).collect();
+        assert_eq!(ports, [5432]);
+        let err = executor.signal(&node, Signal::Term).unwrap_err();
+        assert_eq!(err.kind(), io::ErrorKind::NotFound);
+    }
+}
@@ -0,0 +1,84 @@
+use std::sync::OnceLock;
+
+// ── Fuzzy name matching ──────────────────────────────────────────────
+//
+// A name target that isn't a substring can still match as a subsequence,
+// fzf-style, so `pstgr` finds `postgres`. Scores reward runs of
+// consecutive characters and characters that start a word, and charge
+// for the gaps in between, so the closest names sort first.
+
+const MATCH: i32 = 16;
+const CONSECUTIVE: i32 = 12;
+const WORD_START: i32 = 10;
+const GAP: i32 = 2;
+
+static EXACT: OnceLock<bool> = OnceLock::new();
+
+/// `--exact`: substring matching only, for the rest of the run.
+pub(crate) fn install_exact(exact: bool) {
+    let _ = EXACT.set(exact);
+}
+
+pub(crate) fn enabled() -> bool {
+    !EXACT.get().copied().unwrap_or(false)
+}
+
+/// How well `pattern` matches `text` as a case-insensitive subsequence;
+/// `None` when it doesn't. Higher is better.
+pub(crate) fn score(pattern: &str, text: &str) -> Option<u32> {
+    let pattern: Vec<char> = pattern.to_lowercase().chars().collect();
+    let text: Vec<char> = text.to_lowercase().chars().collect();
+    let first = *pattern.first()?;
+    (0..text.len())
+        .filter(|&start| text[start] == first)
+        .filter_map(|start| score_from(&pattern, &text, start))
+        .max()
+}
+
+/// Match greedily from `start`, where the first pattern character sits.
+fn score_from(pattern: &[char], text: &[char], start: usize) -> Option<u32> {
+    let mut total = 0;
+    let mut next = 0;
+    let mut prev: Option<usize> = None;
+    for (i, &c) in text.iter().enumerate().skip(start) {
+        if next == pattern.len() {
+            break;
+        }
+        if c != pattern[next] {
+            continue;
+        }
+        total += MATCH;
+        if i == 0 || !text[i - 1].is_alphanumeric() {
+            total += WORD_START;
+        }
+        match prev {
+            Some(j) if j + 1 == i => total += CONSECUTIVE,
+            Some(j) => total -= GAP * (i - j - 1) as i32,
+            None => {}
+        }
+        prev = Some(i);
+        next += 1;
+    }
+    (next == pattern.len()).then(|| total.max(1) as u32)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn subsequences_match_and_rank() {
+        assert!(score("pstgr", "postgres").is_some());
+        assert!(score("PSTGR", "postgres").is_some());
+        assert_eq!(score("rgtsp", "postgres"), None);
+        assert_eq!(score("", "postgres"), None);
+        assert_eq!(score("redis", "red"), None);
+
+        // Tighter and word-initial matches score higher
+        assert!(score("post", "postgres") > score("pstg", "postgres"));
+        assert!(score("gres", "postgres") < score("post", "postgres"));
+        assert!(score("pg", "pg_ctl") > score("pg", "nginx-upgrade"));
+        // The best start wins, not the first
+        assert_eq!(score("web", "w-web"), score("web", "web"));
+    }
+}
@@ -0,0 +1,162 @@
+use std::io::{self, Write};
+use std::path::Path;
+
+use crate::declared::DeclaredPort;
+use crate::{json_escape, write_styled};
+
+// ── Project ports ────────────────────────────────────────────────────
+//
+// `portview here` puts the ports the current project declares next to
+// what's listening on them, so "is my stack up, and is anything else in
+// the way?" takes one command.
+
+/// Who holds a declared port.
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) struct Holder {
+    pub(crate) pid: u32,
+    pub(crate) process: String,
+    /// Whether its working directory is inside the project.
+    pub(crate) in_project: bool,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) struct PortStatus {
+    pub(crate) declared: DeclaredPort,
+    pub(crate) holder: Option<Holder>,
+}
+
+impl PortStatus {
+    fn describe(&self) -> String {
+        match &self.holder {
+            Some(h) if h.in_project => format!("{} (pid {}), this project", h.process, h.pid),
+            Some(h) => format!("{} (pid {})", h.process, h.pid),
+            None => "free".to_string(),
+        }
+    }
+
+    /// ● in green for the project's own server, yellow for anything else
+    /// on its port; ○ when free.
+    fn icon(&self) -> (&'static str, &'static str) {
+        match &self.holder {
+            Some(h) if h.in_project => ("●", "green"),
+            Some(_) => ("●", "yellow"),
+            None => ("○", "dimmed"),
+        }
+    }
+}
+
+pub(crate) fn display_here(dir: &Path, rows: &[PortStatus], use_color: bool) {
+    let mut out = io::stdout();
+    let _ = writeln!(out);
+    let _ = write!(out, "  Ports for ");
+    write_styled(&mut out, &dir.display().to_string(), "bold", use_color);
+    let _ = writeln!(out);
+    let _ = writeln!(out);
+
+    let port_w = rows
+        .iter()
+        .map(|r| r.declared.port.to_string().len())
+        .max()
+        .unwrap_or(0);
+    let source_w = rows
+        .iter()
+        .map(|r| r.declared.source.chars().count())
+        .max()
+        .unwrap_or(0);
+    for row in rows {
+        let (icon, color) = row.icon();
+        let _ = write!(out, "  ");
+        write_styled(&mut out, icon, color, use_color);
+        let _ = write!(out, " ");
+        write_styled(
+            &mut out,
+            &format!("{:>w$}", row.declared.port, w = port_w),
+            "cyan",
+            use_color,
+        );
+        let _ = write!(out, "  {:<w$}  ", row.declared.source, w = source_w);
+        let status_color = if row.holder.is_none() {
+            "dimmed"
+        } else {
+            color
+        };
+        write_styled(&mut out, &row.describe(), status_color, use_color);
+        let _ = writeln!(out);
+    }
+
+    let listening = rows.iter().filter(|r| r.holder.is_some()).count();
+    let foreign = rows
+        .iter()
+        .filter(|r| r.holder.as_ref().is_some_and(|h| !h.in_project))
+        .count();
+    let _ = writeln!(out);
+    let _ = write!(out, "{} of {} ports listening", listening, rows.len());
+    if foreign > 0 {
+        let _ = write!(out, "; {} held by processes outside this project", foreign);
+    }
+    let _ = writeln!(out, ".");
+}
+
+pub(crate) fn here_json(dir: &Path, rows: &[PortStatus]) -> String {
+    let items: Vec<String> = rows
+        .iter()
+        .map(|r| {
+            let (pid, process, in_project) = match &r.holder {
+                Some(h) => (
+                    h.pid.to_string(),
+                    format!("\"{}\"", json_escape(&h.process)),
+                    h.in_project.to_string(),
+                ),
+                None => ("null".to_string(), "null".to_string(), "null".to_string()),
+            };
+            format!(
+                r#"{{"port":{},"source":"{}","listening":{},"pid":{},"process":{},"in_project":{}}}"#,
+                r.declared.port,
+                json_escape(&r.declared.source),
+                r.holder.is_some(),
+                pid,
+                process,
+                in_project
+            )
+        })
+        .collect();
+    format!(
+        r#"{{"dir":"{}","ports":[{}]}}"#,
+        json_escape(&dir.display().to_string()),
+        items.join(",")
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn json_shape() {
+        let rows = [
+            PortStatus {
+                declared: DeclaredPort {
+                    port: 3000,
+                    source: ".env".to_string(),
+                },
+                holder: Some(Holder {
+                    pid: 4242,
+                    process: "node".to_string(),
+                    in_project: true,
+                }),
+            },
+            PortStatus {
+                declared: DeclaredPort {
+                    port: 5173,
+                    source: "package.json".to_string(),
+                },
+                holder: None,
+            },
+        ];
+        assert_eq!(rows[0].describe(), "node (pid 4242), this project");
+        assert_eq!(
+            here_json(Path::new("/srv/shop"), &rows),
+            r#"{"dir":"/srv/shop","ports":[{"port":3000,"source":".env","listening":true,"pid":4242,"process":"node","in_project":true},{"port":5173,"source":"package.json","listening":false,"pid":null,"process":null,"in_project":null}]}"#
+        );
+    }
+}
@@ -0,0 +1,194 @@
+use std::io::{self, Write};
+use std::net::{IpAddr, SocketAddr, TcpListener, UdpSocket};
+use std::time::{Duration, Instant};
+
+use crate::{
+    atty_stdin, errors, format_addr, install_interrupt_handler, is_running, stop_running,
+    write_styled,
+};
+
+// ── Binding ──────────────────────────────────────────────────────────
+
+#[derive(Debug)]
+enum HeldSocket {
+    Tcp(#[allow(dead_code)] TcpListener),
+    Udp(#[allow(dead_code)] UdpSocket),
+}
+
+/// A socket bound on behalf of the user. The port stays reserved for as long
+/// as this value is alive.
+#[derive(Debug)]
+pub(crate) struct HeldPort {
+    pub(crate) port: u16,
+    pub(crate) protocol: &'static str,
+    _socket: HeldSocket,
+}
+
+/// A bind that failed, with enough context to explain it to the user.
+#[derive(Debug)]
+pub(crate) struct BindFailure {
+    pub(crate) port: u16,
+    pub(crate) protocol: &'static str,
+    pub(crate) error: io::Error,
+}
+
+/// Bind every requested port/protocol pair. All-or-nothing: if any bind fails
+/// the sockets acquired so far are dropped and the failure is returned.
+pub(crate) fn bind_ports(
+    ports: &[u16],
+    addr: IpAddr,
+    tcp: bool,
+    udp: bool,
+) -> Result<Vec<HeldPort>, BindFailure> {
+    let mut held = Vec::new();
+    for &port in ports {
+        let sock_addr = SocketAddr::new(addr, port);
+        if tcp {
+            let listener = TcpListener::bind(sock_addr).map_err(|error| BindFailure {
+                port,
+                protocol: "TCP",
+                error,
+            })?;
+            held.push(HeldPort {
+                port,
+                protocol: "TCP",
+                _socket: HeldSocket::Tcp(listener),
+            });
+        }
+        if udp {
+            let socket = UdpSocket::bind(sock_addr).map_err(|error| BindFailure {
+                port,
+                protocol: "UDP",
+                error,
+            })?;
+            held.push(HeldPort {
+                port,
+                protocol: "UDP",
+                _socket: HeldSocket::Udp(socket),
+            });
+        }
+    }
+    Ok(held)
+}
+
+// ── Entry point ──────────────────────────────────────────────────────
+
+pub(crate) fn run_hold_mode(
+    ports: &[u16],
+    addr: IpAddr,
+    tcp: bool,
+    udp: bool,
+    duration: Option<Duration>,
+    use_color: bool,
+) {
+    if ports.contains(&0) {
+        errors::fail(
+            errors::ErrorCode::InvalidArgument,
+            "cannot hold port 0 (the OS would pick a random port)",
+        );
+    }
+
+    let held = match bind_ports(ports, addr, tcp, udp) {
+        Ok(h) => h,
+        Err(failure) => {
+            let mut out = io::stderr();
+            write_styled(&mut out, "  ✗", "red", use_color);
+            if failure.error.kind() == io::ErrorKind::AddrInUse {
+                let _ = writeln!(
+                    out,
+                    " {}/{} is already in use — run `portview {}` to see who owns it",
+                    failure.port, failure.protocol, failure.port
+                );
+            } else {
+                let _ = writeln!(
+                    out,
+                    " Failed to bind {}/{}: {}",
+                    failure.port, failure.protocol, failure.error
+                );
+            }
+            std::process::exit(1);
+        }
+    };
+
+    let mut out = io::stdout();
+    let bind_host = format_addr(&addr);
+    for h in &held {
+        write_styled(&mut out, "  ●", "green", use_color);
+        let _ = write!(out, " Holding ");
+        write_styled(
+            &mut out,
+            &format!("{}:{}", bind_host, h.port),
+            "cyan",
+            use_color,
+        );
+        let _ = writeln!(out, " ({})", h.protocol);
+    }
+
+    install_interrupt_handler();
+    let interactive = atty_stdin();
+    if interactive {
+        // Any line on stdin (typically just Enter) releases the ports
+        std::thread::spawn(|| {
+            let mut line = String::new();
+            let _ = io::stdin().read_line(&mut line);
+            stop_running();
+        });
+    }
+
+    let hint = match (duration, interactive) {
+        (Some(d), true) => format!("Releasing in {}s, or press Enter / Ctrl+C", d.as_secs()),
+        (Some(d), false) => format!("Releasing in {}s, or press Ctrl+C", d.as_secs()),
+        (None, true) => "Press Enter or Ctrl+C to release".to_string(),
+        (None, false) => "Press Ctrl+C to release".to_string(),
+    };
+    write_styled(&mut out, &format!("  {}\n", hint), "dimmed", use_color);
+    let _ = out.flush();
+
+    let deadline = duration.map(|d| Instant::now() + d);
+    while is_running() {
+        if deadline.is_some_and(|d| Instant::now() >= d) {
+            break;
+        }
+        std::thread::sleep(Duration::from_millis(50));
+    }
+
+    drop(held);
+    write_styled(&mut out, "  ✓", "green", use_color);
+    let _ = writeln!(out, " Released");
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::net::Ipv4Addr;
+
+    fn free_port() -> u16 {
+        TcpListener::bind((Ipv4Addr::LOCALHOST, 0))
+            .and_then(|l| l.local_addr())
+            .map(|a| a.port())
+            .expect("ephemeral port")
+    }
+
+    #[test]
+    fn bind_ports_holds_tcp_and_udp() {
+        let port = free_port();
+        let addr = IpAddr::V4(Ipv4Addr::LOCALHOST);
+        let held = bind_ports(&[port], addr, true, true).expect("bind should succeed");
+        assert_eq!(held.len(), 2);
+        assert_eq!(held[0].protocol, "TCP");
+        assert_eq!(held[1].protocol, "UDP");
+
+        // While held, a second bind must fail with EADDRINUSE
+        let err = bind_ports(&[port], addr, true, false).expect_err("port should be taken");
+        assert_eq!(err.port, port);
+        assert_eq!(err.error.kind(), io::ErrorKind::AddrInUse);
+    }
+
+    #[test]
+    fn bind_ports_releases_on_drop() {
+        let port = free_port();
+        let addr = IpAddr::V4(Ipv4Addr::LOCALHOST);
+        drop(bind_ports(&[port], addr, true, false).expect("first bind"));
+        assert!(bind_ports(&[port], addr, true, false).is_ok());
+    }
+}
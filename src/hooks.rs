@@ -0,0 +1,171 @@
+use std::io::Write;
+use std::process::{Command, Stdio};
+
+use crate::alerts::{alert_json, Alert};
+use crate::events::{event_json, PortEvent, PortEventKind};
+
+// ── Event hooks ──────────────────────────────────────────────────────
+
+/// Commands and webhook run by `watch` when a listener opens or closes, or
+/// a process crosses an `--alert` threshold.
+#[derive(Debug, Clone, Default)]
+pub(crate) struct EventHooks {
+    pub(crate) on_open: Option<String>,
+    pub(crate) on_close: Option<String>,
+    pub(crate) on_alert: Option<String>,
+    pub(crate) webhook: Option<String>,
+}
+
+impl EventHooks {
+    pub(crate) fn is_empty(&self) -> bool {
+        self.on_open.is_none()
+            && self.on_close.is_none()
+            && self.on_alert.is_none()
+            && self.webhook.is_none()
+    }
+
+    /// Run hooks for `events` on a background thread, in event order, so a
+    /// restart's close hook finishes before its open hook starts. Hook
+    /// output is discarded; it would otherwise corrupt the TUI or JSON stream.
+    pub(crate) fn fire(&self, events: &[PortEvent]) {
+        if self.is_empty() || events.is_empty() {
+            return;
+        }
+        let hooks = self.clone();
+        let events = events.to_vec();
+        std::thread::spawn(move || {
+            for event in &events {
+                let cmd = match event.kind {
+                    PortEventKind::Opened => hooks.on_open.as_deref(),
+                    PortEventKind::Closed => hooks.on_close.as_deref(),
+                };
+                if let Some(cmd) = cmd {
+                    run_command(cmd, &hook_env(event));
+                }
+                if let Some(ref url) = hooks.webhook {
+                    post_webhook(url, &event_json(event));
+                }
+            }
+        });
+    }
+
+    /// Run `--on-alert` and the webhook for alerts that just started.
+    pub(crate) fn fire_alerts(&self, alerts: &[Alert]) {
+        if (self.on_alert.is_none() && self.webhook.is_none()) || alerts.is_empty() {
+            return;
+        }
+        let hooks = self.clone();
+        let alerts = alerts.to_vec();
+        std::thread::spawn(move || {
+            for alert in &alerts {
+                if let Some(ref cmd) = hooks.on_alert {
+                    run_command(cmd, &alert_env(alert));
+                }
+                if let Some(ref url) = hooks.webhook {
+                    post_webhook(url, &alert_json(alert));
+                }
+            }
+        });
+    }
+}
+
+/// Variables describing the event, set for `--on-open`/`--on-close` commands.
+fn hook_env(event: &PortEvent) -> [(&'static str, String); 5] {
+    [
+        ("PORTVIEW_EVENT", event.kind.as_str().to_string()),
+        ("PORTVIEW_PORT", event.port.to_string()),
+        ("PORTVIEW_PROTO", event.protocol.clone()),
+        ("PORTVIEW_PID", event.pid.to_string()),
+        ("PORTVIEW_PROCESS", event.process.clone()),
+    ]
+}
+
+/// Variables describing the alert, set for `--on-alert` commands.
+fn alert_env(alert: &Alert) -> [(&'static str, String); 5] {
+    [
+        ("PORTVIEW_EVENT", "alert".to_string()),
+        ("PORTVIEW_PID", alert.pid.to_string()),
+        ("PORTVIEW_PROCESS", alert.process.clone()),
+        ("PORTVIEW_RULE", alert.rule.clone()),
+        ("PORTVIEW_VALUE", alert.value.clone()),
+    ]
+}
+
+fn run_command(cmd: &str, env: &[(&'static str, String)]) {
+    #[cfg(unix)]
+    let mut command = {
+        let mut c = Command::new("sh");
+        c.args(["-c", cmd]);
+        c
+    };
+    #[cfg(windows)]
+    let mut command = {
+        let mut c = Command::new("cmd");
+        c.args(["/C", cmd]);
+        c
+    };
+    let _ = command
+        .envs(env.iter().map(|(k, v)| (k, v)))
+        .stdin(Stdio::null())
+        .stdout(Stdio::null())
+        .stderr(Stdio::null())
+        .status();
+}
+
+/// POST the event as JSON via `curl`, which ships with macOS, Windows 10+
+/// and nearly every Linux install, so HTTPS works without a TLS dependency.
+fn post_webhook(url: &str, body: &str) {
+    let child = Command::new("curl")
+        .args([
+            "-fsS",
+            "--max-time",
+            "10",
+            "-X",
+            "POST",
+            "-H",
+            "Content-Type: application/json",
+            "--data-binary",
+            "@-",
+            url,
+        ])
+        .stdin(Stdio::piped())
+        .stdout(Stdio::null())
+        .stderr(Stdio::null())
+        .spawn();
+    if let Ok(mut child) = child {
+        if let Some(mut stdin) = child.stdin.take() {
+            let _ = stdin.write_all(body.as_bytes());
+        }
+        let _ = child.wait();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn hook_env_describes_event() {
+        let event = PortEvent {
+            kind: PortEventKind::Closed,
+            port: 8080,
+            protocol: "TCP".to_string(),
+            pid: 77,
+            process: "backend".to_string(),
+        };
+        let env = hook_env(&event);
+        assert_eq!(env[0], ("PORTVIEW_EVENT", "closed".to_string()));
+        assert_eq!(env[1], ("PORTVIEW_PORT", "8080".to_string()));
+        assert_eq!(env[4], ("PORTVIEW_PROCESS", "backend".to_string()));
+    }
+
+    #[test]
+    fn empty_hooks() {
+        assert!(EventHooks::default().is_empty());
+        let hooks = EventHooks {
+            webhook: Some("http://localhost:9/hook".to_string()),
+            ..Default::default()
+        };
+        assert!(!hooks.is_empty());
+    }
+}
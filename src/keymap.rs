@@ -0,0 +1,508 @@
+use crossterm::event::KeyCode;
+
+// ── Key bindings ─────────────────────────────────────────────────────
+//
+// TUI table keys can be remapped under `[keys]` in the config file. Each
+// entry names an action and lists its keys, separated by spaces; setting
+// an action replaces its default keys, and an empty string unbinds it:
+//
+//     [keys]
+//     preset = "arrows"    # arrows, Home and End only; no j/k/g/G/h/l
+//     kill = "x"
+//     force_kill = ""      # no SIGKILL shortcut
+//
+// A key may belong to one action only, and 1-9 stay reserved for sorting
+// by column. Popups, prompts and replay keep their fixed keys.
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub(crate) enum Action {
+    Down,
+    Up,
+    Top,
+    Bottom,
+    Inspect,
+    Kill,
+    ForceKill,
+    Signal,
+    ToggleGroup,
+    Filter,
+    Search,
+    NextMatch,
+    PrevMatch,
+    Copy,
+    Export,
+    Command,
+    ToggleAll,
+    SortPrev,
+    SortNext,
+    ReverseSort,
+    ToggleCwd,
+    ToggleRuntime,
+    ToggleDocker,
+    Summary,
+    SplitView,
+    CommandView,
+    ScrollLeft,
+    ScrollRight,
+    Help,
+    Quit,
+}
+
+/// Config name, help text and default keys, in help-overlay order.
+const ACTIONS: &[(Action, &str, &str, &[KeyCode])] = &[
+    (
+        Action::Down,
+        "down",
+        "Next row (scroll down in the detail view)",
+        &[KeyCode::Char('j'), KeyCode::Down],
+    ),
+    (
+        Action::Up,
+        "up",
+        "Previous row (scroll up in the detail view)",
+        &[KeyCode::Char('k'), KeyCode::Up],
+    ),
+    (
+        Action::Top,
+        "top",
+        "First row",
+        &[KeyCode::Char('g'), KeyCode::Home],
+    ),
+    (
+        Action::Bottom,
+        "bottom",
+        "Last row",
+        &[KeyCode::Char('G'), KeyCode::End],
+    ),
+    (
+        Action::Inspect,
+        "inspect",
+        "Open the detail view",
+        &[KeyCode::Enter],
+    ),
+    (
+        Action::Kill,
+        "kill",
+        "Kill the process or manage the container",
+        &[KeyCode::Char('d')],
+    ),
+    (
+        Action::ForceKill,
+        "force_kill",
+        "Force-kill the process",
+        &[KeyCode::Char('D')],
+    ),
+    (
+        Action::Signal,
+        "signal",
+        "Send another signal (HUP, USR1, …)",
+        &[KeyCode::Char('K')],
+    ),
+    (
+        Action::ToggleGroup,
+        "toggle_group",
+        "Expand or collapse a SO_REUSEPORT group",
+        &[KeyCode::Char(' ')],
+    ),
+    (
+        Action::Filter,
+        "filter",
+        "Filter rows",
+        &[KeyCode::Char('/')],
+    ),
+    (
+        Action::Search,
+        "search",
+        "Search without hiding rows",
+        &[KeyCode::Char('s')],
+    ),
+    (
+        Action::NextMatch,
+        "next_match",
+        "Next search match",
+        &[KeyCode::Char('n')],
+    ),
+    (
+        Action::PrevMatch,
+        "prev_match",
+        "Previous search match",
+        &[KeyCode::Char('N')],
+    ),
+    (
+        Action::Copy,
+        "copy",
+        "Copy the row, command or port",
+        &[KeyCode::Char('y')],
+    ),
+    (
+        Action::Export,
+        "export",
+        "Export the view to a file",
+        &[KeyCode::Char('e')],
+    ),
+    (
+        Action::Command,
+        "command",
+        "Command prompt",
+        &[KeyCode::Char(':')],
+    ),
+    (
+        Action::ToggleAll,
+        "toggle_all",
+        "All ports or listening only",
+        &[KeyCode::Char('a')],
+    ),
+    (
+        Action::SortPrev,
+        "sort_prev",
+        "Sort by the previous column",
+        &[KeyCode::Char('<')],
+    ),
+    (
+        Action::SortNext,
+        "sort_next",
+        "Sort by the next column",
+        &[KeyCode::Char('>')],
+    ),
+    (
+        Action::ReverseSort,
+        "reverse_sort",
+        "Reverse the sort",
+        &[KeyCode::Char('r')],
+    ),
+    (
+        Action::ToggleCwd,
+        "toggle_cwd",
+        "Show or hide the CWD column",
+        &[KeyCode::Char('c')],
+    ),
+    (
+        Action::ToggleRuntime,
+        "toggle_runtime",
+        "Show or hide the RUNTIME column",
+        &[KeyCode::Char('t')],
+    ),
+    (
+        Action::ToggleDocker,
+        "toggle_docker",
+        "Docker ownership context on or off",
+        &[KeyCode::Char('o')],
+    ),
+    (
+        Action::Summary,
+        "summary",
+        "Summary by user, process and state",
+        &[KeyCode::Char('T')],
+    ),
+    (
+        Action::SplitView,
+        "split_view",
+        "Detail pane beside the table (wide terminals)",
+        &[KeyCode::Char('v')],
+    ),
+    (
+        Action::CommandView,
+        "command_view",
+        "Truncate, wrap or scroll commands",
+        &[KeyCode::Char('w')],
+    ),
+    (
+        Action::ScrollLeft,
+        "scroll_left",
+        "Scroll commands left",
+        &[KeyCode::Char('h'), KeyCode::Left],
+    ),
+    (
+        Action::ScrollRight,
+        "scroll_right",
+        "Scroll commands right",
+        &[KeyCode::Char('l'), KeyCode::Right],
+    ),
+    (Action::Help, "help", "This help", &[KeyCode::Char('?')]),
+    (
+        Action::Quit,
+        "quit",
+        "Quit",
+        &[KeyCode::Char('q'), KeyCode::Esc],
+    ),
+];
+
+/// Named keys accepted in the config, besides single characters.
+const NAMED_KEYS: &[(&str, KeyCode)] = &[
+    ("Enter", KeyCode::Enter),
+    ("Esc", KeyCode::Esc),
+    ("Space", KeyCode::Char(' ')),
+    ("Tab", KeyCode::Tab),
+    ("Backspace", KeyCode::Backspace),
+    ("Delete", KeyCode::Delete),
+    ("Up", KeyCode::Up),
+    ("Down", KeyCode::Down),
+    ("Left", KeyCode::Left),
+    ("Right", KeyCode::Right),
+    ("Home", KeyCode::Home),
+    ("End", KeyCode::End),
+    ("PageUp", KeyCode::PageUp),
+    ("PageDown", KeyCode::PageDown),
+];
+
+impl Action {
+    pub(crate) fn parse(name: &str) -> Option<Self> {
+        ACTIONS
+            .iter()
+            .find(|(_, n, _, _)| *n == name)
+            .map(|(action, ..)| *action)
+    }
+
+    pub(crate) fn name(self) -> &'static str {
+        ACTIONS.iter().find(|(a, ..)| *a == self).unwrap().1
+    }
+}
+
+/// `[keys] preset`: the defaults that config entries start from.
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub(crate) enum Preset {
+    /// Letters and arrows, vim-style.
+    #[default]
+    Default,
+    /// Arrows, Home and End only; frees j/k/g/G/h/l.
+    Arrows,
+}
+
+impl Preset {
+    pub(crate) fn parse(name: &str) -> Option<Self> {
+        match name {
+            "default" => Some(Preset::Default),
+            "arrows" => Some(Preset::Arrows),
+            _ => None,
+        }
+    }
+}
+
+/// `j`, `Down`, `Space`: one key as written in the config.
+pub(crate) fn parse_key(name: &str) -> Result<KeyCode, String> {
+    if let Some((_, code)) = NAMED_KEYS
+        .iter()
+        .find(|(n, _)| n.eq_ignore_ascii_case(name))
+    {
+        return Ok(*code);
+    }
+    if let Some(n) = name
+        .strip_prefix(['F', 'f'])
+        .and_then(|n| n.parse::<u8>().ok())
+        .filter(|n| (1..=12).contains(n))
+    {
+        return Ok(KeyCode::F(n));
+    }
+    let mut chars = name.chars();
+    match (chars.next(), chars.next()) {
+        (Some(c), None) if c.is_ascii_digit() && c != '0' => {
+            Err(format!("'{}' is reserved for sorting by column {}", c, c))
+        }
+        (Some(c), None) => Ok(KeyCode::Char(c)),
+        _ => Err(format!("unknown key '{}'", name)),
+    }
+}
+
+/// Space-separated keys; empty unbinds the action.
+pub(crate) fn parse_keys(value: &str) -> Result<Vec<KeyCode>, String> {
+    value.split_whitespace().map(parse_key).collect()
+}
+
+/// How a key is shown in hints and the help overlay.
+pub(crate) fn key_label(code: KeyCode) -> String {
+    match code {
+        KeyCode::Char(' ') => "Space".to_string(),
+        KeyCode::Char(c) => c.to_string(),
+        KeyCode::Up => "\u{2191}".to_string(),
+        KeyCode::Down => "\u{2193}".to_string(),
+        KeyCode::Left => "\u{2190}".to_string(),
+        KeyCode::Right => "\u{2192}".to_string(),
+        KeyCode::PageUp => "PgUp".to_string(),
+        KeyCode::PageDown => "PgDn".to_string(),
+        KeyCode::F(n) => format!("F{}", n),
+        other => NAMED_KEYS
+            .iter()
+            .find(|(_, code)| *code == other)
+            .map(|(name, _)| name.to_string())
+            .unwrap_or_else(|| format!("{:?}", other)),
+    }
+}
+
+/// The active bindings, one entry per action in [`ACTIONS`] order.
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) struct Keymap {
+    bindings: Vec<(Action, Vec<KeyCode>)>,
+}
+
+impl Default for Keymap {
+    fn default() -> Self {
+        Self::preset(Preset::Default)
+    }
+}
+
+impl Keymap {
+    fn preset(preset: Preset) -> Self {
+        let bindings = ACTIONS
+            .iter()
+            .map(|(action, _, _, keys)| {
+                let keys = keys
+                    .iter()
+                    .copied()
+                    .filter(|k| preset == Preset::Default || !is_nav_letter(*action, *k))
+                    .collect();
+                (*action, keys)
+            })
+            .collect();
+        Self { bindings }
+    }
+
+    /// The preset with `overrides` applied, rejecting a key bound to two
+    /// actions.
+    pub(crate) fn build(
+        preset: Preset,
+        overrides: &[(Action, Vec<KeyCode>)],
+    ) -> Result<Self, String> {
+        let mut keymap = Self::preset(preset);
+        for (action, keys) in overrides {
+            if let Some(slot) = keymap.bindings.iter_mut().find(|(a, _)| a == action) {
+                slot.1 = keys.clone();
+            }
+        }
+        for (i, (action, keys)) in keymap.bindings.iter().enumerate() {
+            for key in keys {
+                if let Some((other, _)) = keymap.bindings[i + 1..]
+                    .iter()
+                    .find(|(_, keys)| keys.contains(key))
+                {
+                    return Err(format!(
+                        "'{}' is bound to both {} and {}",
+                        key_label(*key),
+                        action.name(),
+                        other.name()
+                    ));
+                }
+            }
+        }
+        Ok(keymap)
+    }
+
+    pub(crate) fn action(&self, code: KeyCode) -> Option<Action> {
+        self.bindings
+            .iter()
+            .find(|(_, keys)| keys.contains(&code))
+            .map(|(action, _)| *action)
+    }
+
+    pub(crate) fn keys(&self, action: Action) -> &[KeyCode] {
+        self.bindings
+            .iter()
+            .find(|(a, _)| *a == action)
+            .map(|(_, keys)| keys.as_slice())
+            .unwrap_or(&[])
+    }
+
+    /// `j/k` for a footer hint: the first key of each action, skipping
+    /// unbound ones. Empty when none are bound.
+    pub(crate) fn hint(&self, actions: &[Action]) -> String {
+        actions
+            .iter()
+            .filter_map(|a| self.keys(*a).first())
+            .map(|k| key_label(*k))
+            .collect::<Vec<_>>()
+            .join("/")
+    }
+
+    /// Keys and description for every bound action, for the help overlay.
+    pub(crate) fn help_rows(&self) -> Vec<(String, &'static str)> {
+        ACTIONS
+            .iter()
+            .filter_map(|(action, _, help, _)| {
+                let keys = self.keys(*action);
+                (!keys.is_empty()).then(|| {
+                    let labels: Vec<String> = keys.iter().map(|k| key_label(*k)).collect();
+                    (labels.join(", "), *help)
+                })
+            })
+            .collect()
+    }
+}
+
+/// The vim-style letters that the arrows preset drops.
+fn is_nav_letter(action: Action, key: KeyCode) -> bool {
+    matches!(
+        action,
+        Action::Down
+            | Action::Up
+            | Action::Top
+            | Action::Bottom
+            | Action::ScrollLeft
+            | Action::ScrollRight
+    ) && matches!(key, KeyCode::Char(_))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_keys_and_names() {
+        assert_eq!(
+            parse_keys("j Down space F5").unwrap(),
+            vec![
+                KeyCode::Char('j'),
+                KeyCode::Down,
+                KeyCode::Char(' '),
+                KeyCode::F(5)
+            ]
+        );
+        assert_eq!(parse_keys("").unwrap(), vec![]);
+        assert_eq!(parse_key("Ctrl-x").unwrap_err(), "unknown key 'Ctrl-x'");
+        assert!(parse_key("3").unwrap_err().contains("reserved"));
+        assert_eq!(Action::parse("force_kill"), Some(Action::ForceKill));
+        assert_eq!(Action::parse("nuke"), None);
+        assert_eq!(key_label(KeyCode::PageDown), "PgDn");
+    }
+
+    #[test]
+    fn overrides_presets_and_conflicts() {
+        let keymap = Keymap::default();
+        assert_eq!(keymap.action(KeyCode::Char('d')), Some(Action::Kill));
+        assert_eq!(keymap.hint(&[Action::Down, Action::Up]), "j/k");
+
+        let keymap = Keymap::build(
+            Preset::Default,
+            &[
+                (Action::Kill, vec![KeyCode::Char('x')]),
+                (Action::ForceKill, vec![]),
+            ],
+        )
+        .unwrap();
+        assert_eq!(keymap.action(KeyCode::Char('x')), Some(Action::Kill));
+        assert_eq!(keymap.action(KeyCode::Char('d')), None);
+        assert_eq!(keymap.action(KeyCode::Char('D')), None);
+        assert!(!keymap.help_rows().iter().any(|(_, h)| h.contains("Force")));
+
+        let arrows = Keymap::build(Preset::Arrows, &[]).unwrap();
+        assert_eq!(arrows.action(KeyCode::Char('j')), None);
+        assert_eq!(arrows.action(KeyCode::Down), Some(Action::Down));
+        assert_eq!(
+            arrows.hint(&[Action::Down, Action::Up]),
+            "\u{2193}/\u{2191}"
+        );
+
+        assert_eq!(
+            Keymap::build(Preset::Default, &[(Action::Kill, vec![KeyCode::Char('k')])])
+                .unwrap_err(),
+            "'k' is bound to both up and kill"
+        );
+        // Rebinding the other action too resolves it
+        assert!(Keymap::build(
+            Preset::Default,
+            &[
+                (Action::Kill, vec![KeyCode::Char('k')]),
+                (Action::Up, vec![KeyCode::Up]),
+            ],
+        )
+        .is_ok());
+    }
+}
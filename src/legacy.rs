@@ -0,0 +1,177 @@
+use std::io;
+
+use crate::{config, docker, write_styled, Cli, Command, OutputFormat};
+
+// ── Legacy flags ─────────────────────────────────────────────────────
+//
+// `--watch` and `--kill` predate the subcommands. They keep working by
+// being rewritten into the subcommand they stand for before dispatch, so
+// both spellings run the same code. Each use prints a note naming the
+// replacement, unless the config sets `[warnings] legacy_flags = false`.
+// `--kill` takes one port and nothing else that narrows it down: the list
+// filters are rejected by clap rather than dropped, since they'd suggest
+// only matching processes get signalled.
+
+/// A legacy flag on the command line and the command that replaces it.
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) struct Notice {
+    pub(crate) flag: &'static str,
+    pub(crate) replacement: String,
+}
+
+/// The subcommand the legacy flags stand for, if any were given.
+pub(crate) fn rewrite(cli: &Cli) -> Option<Command> {
+    let list = &cli.list;
+    if let Some(port) = cli.kill {
+        return Some(Command::Kill {
+            ports: Some((port, port)),
+            filter: None,
+            yes: false,
+            json: list.json || list.format.is_some_and(OutputFormat::is_json),
+            force: cli.force,
+            signal: None,
+            docker: list.docker,
+            respawn: false,
+            no_color: list.no_color,
+        });
+    }
+    if !cli.watch {
+        return None;
+    }
+    Some(Command::Watch {
+        target: cli.target.clone(),
+        more_ports: Vec::new(),
+        all: list.all,
+        json: list.json,
+        format: list.format,
+        docker: list.docker,
+        docker_refresh: docker::PORT_MAP_REFRESH,
+        force: cli.force,
+        wide: list.wide,
+        no_dns: cli.no_dns,
+        cwd: list.cwd,
+        runtime: list.runtime,
+        tcp_stats: false,
+        user: list.user.clone(),
+        mine: list.mine,
+        session: list.session,
+        exact: list.exact,
+        notify: Vec::new(),
+        on_open: None,
+        on_close: None,
+        webhook: None,
+        alert: Vec::new(),
+        alert_notify: false,
+        on_alert: None,
+        bell: false,
+        title: false,
+        record: None,
+        changes_only: false,
+        plain: false,
+        plain_table: list.plain_table,
+        header_per_row: list.header_per_row,
+        no_color: list.no_color,
+    })
+}
+
+/// One notice per legacy flag used, spelling out the equivalent command.
+pub(crate) fn notices(cli: &Cli) -> Vec<Notice> {
+    let mut notices = Vec::new();
+    if cli.watch {
+        let target = cli
+            .target
+            .as_deref()
+            .map(|t| format!(" {}", t))
+            .unwrap_or_default();
+        notices.push(Notice {
+            flag: "--watch",
+            replacement: format!("portview watch{}", target),
+        });
+    }
+    if let Some(port) = cli.kill {
+        let force = if cli.force { " --force" } else { "" };
+        let json = match rewrite(cli) {
+            Some(Command::Kill { json: true, .. }) => " --json",
+            _ => "",
+        };
+        notices.push(Notice {
+            flag: "--kill",
+            replacement: format!("portview kill {}{}{}", port, force, json),
+        });
+    }
+    notices
+}
+
+/// Print the notices to stderr, so piped output stays clean.
+pub(crate) fn warn(notices: &[Notice], use_color: bool) {
+    if notices.is_empty() || !config::active().warn_legacy_flags() {
+        return;
+    }
+    let mut err = io::stderr();
+    for notice in notices {
+        write_styled(
+            &mut err,
+            &format!(
+                "note: {} is a legacy flag; use `{}` instead\n",
+                notice.flag, notice.replacement
+            ),
+            "yellow",
+            use_color,
+        );
+    }
+    write_styled(
+        &mut err,
+        "      (silence with `[warnings] legacy_flags = false` in the config file)\n",
+        "dimmed",
+        use_color,
+    );
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use clap::Parser;
+
+    fn parse(args: &[&str]) -> Cli {
+        Cli::try_parse_from(args).unwrap()
+    }
+
+    #[test]
+    fn legacy_flags_rewrite_to_subcommands() {
+        let legacy = parse(&["portview", "node", "--watch", "-a", "--docker", "--wide"]);
+        let modern = parse(&["portview", "watch", "node", "-a", "--docker", "--wide"]);
+        assert_eq!(rewrite(&legacy), modern.command);
+
+        let legacy = parse(&["portview", "--kill", "3000", "--force"]);
+        let modern = parse(&["portview", "kill", "3000", "--force"]);
+        assert_eq!(rewrite(&legacy), modern.command);
+
+        let legacy = parse(&["portview", "--kill", "3000", "--json"]);
+        let modern = parse(&["portview", "kill", "3000", "--json"]);
+        assert_eq!(rewrite(&legacy), modern.command);
+        assert_eq!(notices(&legacy)[0].replacement, "portview kill 3000 --json");
+
+        // Filters the kill subcommand can't take are refused, not dropped
+        for filter in [&["node"][..], &["--user", "dev"], &["--mine"], &["-a"]] {
+            let args = [&["portview", "--kill", "3000"][..], filter].concat();
+            assert!(Cli::try_parse_from(&args).is_err(), "{:?}", args);
+        }
+
+        assert_eq!(rewrite(&parse(&["portview", "node"])), None);
+    }
+
+    #[test]
+    fn notices_name_the_replacement() {
+        let cli = parse(&["portview", "node", "--watch"]);
+        assert_eq!(
+            notices(&cli),
+            vec![Notice {
+                flag: "--watch",
+                replacement: "portview watch node".to_string(),
+            }]
+        );
+        let cli = parse(&["portview", "-k", "8080", "-f"]);
+        assert_eq!(notices(&cli)[0].replacement, "portview kill 8080 --force");
+        assert!(notices(&parse(&["portview"])).is_empty());
+    }
+}
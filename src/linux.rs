@@ -1,14 +1,41 @@
-use std::collections::HashMap;
+use std::collections::hash_map::DefaultHasher;
+use std::collections::{HashMap, HashSet};
 use std::fs;
+use std::hash::{Hash, Hasher};
 use std::net::{IpAddr, Ipv4Addr, Ipv6Addr};
+use std::os::fd::{AsRawFd, FromRawFd, OwnedFd};
+use std::os::unix::fs::MetadataExt;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{mpsc, Arc};
 use std::time::{Duration, SystemTime, UNIX_EPOCH};
 
-use crate::{get_clock_ticks, get_username, PortInfo, TcpState};
+use crate::backlog::ListenQueue;
+use crate::bandwidth::SocketTraffic;
+use crate::collector::Capabilities;
+use crate::doctor::Check;
+use crate::ephemeral::{self, LocalPorts};
+use crate::orphan::Lineage;
+use crate::refresh::ListenerFeed;
+use crate::sockopts::SocketOptions;
+use crate::{
+    get_clock_ticks, get_username, lookup_uid, parse_env_hints, ConnInfo, PortInfo, ProcessDetail,
+    TcpState,
+};
+
+// ── Backend ──────────────────────────────────────────────────────────
+
+pub const BACKEND_NAME: &str = "procfs";
+
+/// Linux reads /proc/net and each process's fd table.
+pub const CAPABILITIES: Capabilities = Capabilities {
+    supports_remote_addr: true,
+    supports_udp_state: true,
+    needs_privileges: true,
+};
 
 // ── Data types ───────────────────────────────────────────────────────
 
 #[derive(Debug, Clone)]
-#[allow(dead_code)]
 struct SocketEntry {
     protocol: String,
     local_addr: IpAddr,
@@ -27,7 +54,8 @@ fn parse_hex_addr_v4(hex: &str) -> IpAddr {
 }
 
 fn parse_hex_addr_v6(hex: &str) -> IpAddr {
-    if hex.len() != 32 {
+    // Byte offsets below; a multi-byte character would split mid-char
+    if hex.len() != 32 || !hex.is_ascii() {
         return IpAddr::V6(Ipv6Addr::UNSPECIFIED);
     }
     // Linux stores IPv6 as 4 groups of little-endian 32-bit integers
@@ -66,11 +94,14 @@ fn parse_addr_port(s: &str, ipv6: bool) -> (IpAddr, u16) {
 }
 
 fn parse_proc_net(path: &str, protocol: &str, ipv6: bool) -> Vec<SocketEntry> {
-    let content = match fs::read_to_string(path) {
-        Ok(c) => c,
-        Err(_) => return vec![],
-    };
+    match fs::read_to_string(path) {
+        Ok(content) => parse_proc_net_text(&content, protocol, ipv6),
+        Err(_) => vec![],
+    }
+}
 
+/// The rows of a /proc/net/{tcp,udp}[6] table; malformed lines are skipped.
+fn parse_proc_net_text(content: &str, protocol: &str, ipv6: bool) -> Vec<SocketEntry> {
     let is_udp = protocol.starts_with("UDP");
 
     content
@@ -93,12 +124,9 @@ fn parse_proc_net(path: &str, protocol: &str, ipv6: bool) -> Vec<SocketEntry> {
             } else {
                 TcpState::from_hex(fields[3])
             };
+            // inode 0 means no owning fd (e.g. TIME_WAIT); kept for connection views
             let inode = fields[9].parse::<u64>().unwrap_or(0);
 
-            if inode == 0 {
-                return None;
-            }
-
             Some(SocketEntry {
                 protocol: protocol.to_string(),
                 local_addr,
@@ -121,7 +149,9 @@ fn get_all_sockets() -> Vec<SocketEntry> {
     sockets
 }
 
-fn build_inode_to_pid_map() -> HashMap<u64, u32> {
+/// Socket inode → owning pid. With `owner`, other accounts' processes are
+/// skipped before their fd tables are read.
+fn build_inode_to_pid_map(owner: Option<u32>) -> HashMap<u64, u32> {
     let mut map = HashMap::new();
 
     let proc_dir = match fs::read_dir("/proc") {
@@ -134,6 +164,11 @@ fn build_inode_to_pid_map() -> HashMap<u64, u32> {
             Ok(p) => p,
             Err(_) => continue,
         };
+        if let Some(uid) = owner {
+            if entry.metadata().ok().map(|m| m.uid()) != Some(uid) {
+                continue;
+            }
+        }
 
         let fd_path = format!("/proc/{}/fd", pid);
         let fd_dir = match fs::read_dir(&fd_path) {
@@ -262,16 +297,157 @@ fn count_children(pid: u32) -> u32 {
     children.split_whitespace().count() as u32
 }
 
+pub fn process_alive(pid: u32) -> bool {
+    fs::metadata(format!("/proc/{}", pid)).is_ok()
+}
+
+pub fn get_lineage(pid: u32) -> Option<Lineage> {
+    parse_lineage(&fs::read_to_string(format!("/proc/{}/stat", pid)).ok()?)
+}
+
+pub fn get_parent_pid(pid: u32) -> Option<u32> {
+    get_lineage(pid).map(|l| l.ppid)
+}
+
+/// State, parent and session from `/proc/<pid>/stat`, read after the
+/// command name since it may contain spaces and parentheses.
+fn parse_lineage(stat: &str) -> Option<Lineage> {
+    let fields: Vec<&str> = stat[stat.rfind(')')? + 1..].split_whitespace().collect();
+    Some(Lineage {
+        zombie: *fields.first()? == "Z",
+        ppid: fields.get(1)?.parse().ok()?,
+        session: fields.get(3)?.parse().ok()?,
+    })
+}
+
+/// Direct children, gathered from every thread's `children` list since a
+/// child is listed under the thread that forked it.
+pub fn get_child_pids(pid: u32) -> Vec<u32> {
+    let Ok(tasks) = fs::read_dir(format!("/proc/{}/task", pid)) else {
+        return Vec::new();
+    };
+    let mut pids: Vec<u32> = tasks
+        .flatten()
+        .filter_map(|task| fs::read_to_string(task.path().join("children")).ok())
+        .flat_map(|list| {
+            list.split_whitespace()
+                .filter_map(|p| p.parse().ok())
+                .collect::<Vec<u32>>()
+        })
+        .collect();
+    pids.sort_unstable();
+    pids.dedup();
+    pids
+}
+
+/// Nice value (field 19) and thread count (field 20) from /proc/<pid>/stat.
+fn parse_stat_nice_threads(stat: &str) -> (Option<i32>, Option<u32>) {
+    let Some(pos) = stat.rfind(')') else {
+        return (None, None);
+    };
+    let fields: Vec<&str> = stat[pos + 1..].split_whitespace().collect();
+    (
+        fields.get(16).and_then(|s| s.parse().ok()),
+        fields.get(17).and_then(|s| s.parse().ok()),
+    )
+}
+
+/// Working directory of a process (readable for our own processes, or as root).
+pub fn get_process_cwd(pid: u32) -> Option<String> {
+    fs::read_link(format!("/proc/{}/cwd", pid))
+        .ok()
+        .map(|p| p.to_string_lossy().to_string())
+}
+
+/// Thread count, nice value, working directory and environment hints for
+/// one process. cwd and environ are only readable for our own processes
+/// (or as root).
+pub fn get_process_detail(pid: u32) -> ProcessDetail {
+    let stat = fs::read_to_string(format!("/proc/{}/stat", pid)).unwrap_or_default();
+    let (nice, threads) = parse_stat_nice_threads(&stat);
+    let cwd = get_process_cwd(pid);
+    let environ = fs::read(format!("/proc/{}/environ", pid)).unwrap_or_default();
+    ProcessDetail {
+        threads,
+        nice,
+        cwd,
+        env: parse_env_hints(&environ),
+        repo: None,
+        orphan: None,
+    }
+}
+
 // ── Assemble port info ───────────────────────────────────────────────
 
 pub fn get_port_infos(filter_listening: bool) -> Vec<PortInfo> {
+    collect_port_infos(filter_listening, None)
+}
+
+pub fn resolve_user(user: &str) -> Option<String> {
+    crate::resolve_unix_user(user)
+}
+
+/// Ports of one account's processes. An unknown account owns none.
+pub fn get_user_port_infos(filter_listening: bool, user: &str) -> Vec<PortInfo> {
+    match lookup_uid(user) {
+        Some(uid) => collect_port_infos(filter_listening, Some(uid)),
+        None => Vec::new(),
+    }
+}
+
+/// Rows as they're read, for `--stream`: unsorted, with the v4/v6
+/// duplicates dropped as they turn up.
+pub fn stream_port_infos(
+    filter_listening: bool,
+    user: Option<&str>,
+    emit: &mut dyn FnMut(PortInfo) -> bool,
+) {
+    let owner = match user {
+        Some(user) => match lookup_uid(user) {
+            Some(uid) => Some(uid),
+            None => return,
+        },
+        None => None,
+    };
+    let mut seen = HashSet::new();
+    for_each_port_info(filter_listening, owner, &mut |info| {
+        !seen.insert((info.port, info.protocol.clone(), info.pid)) || emit(info)
+    });
+}
+
+fn collect_port_infos(filter_listening: bool, owner: Option<u32>) -> Vec<PortInfo> {
+    let mut infos: Vec<PortInfo> = Vec::new();
+    for_each_port_info(filter_listening, owner, &mut |info| {
+        infos.push(info);
+        true
+    });
+
+    // Sort by port number, then protocol, then pid (pid needed for dedup_by adjacency)
+    infos.sort_by(|a, b| {
+        a.port
+            .cmp(&b.port)
+            .then_with(|| a.protocol.cmp(&b.protocol))
+            .then_with(|| a.pid.cmp(&b.pid))
+    });
+
+    // Deduplicate (same port+proto+pid can appear for v4 and v6)
+    infos.dedup_by(|a, b| a.port == b.port && a.protocol == b.protocol && a.pid == b.pid);
+
+    infos
+}
+
+/// Read each socket's owner and hand the row to `emit` until it returns
+/// false.
+fn for_each_port_info(
+    filter_listening: bool,
+    owner: Option<u32>,
+    emit: &mut dyn FnMut(PortInfo) -> bool,
+) {
     let sockets = get_all_sockets();
-    let inode_map = build_inode_to_pid_map();
+    let inode_map = build_inode_to_pid_map(owner);
     let boot_time = get_boot_time();
     let clock_ticks = get_clock_ticks();
 
-    let mut infos: Vec<PortInfo> = Vec::new();
-
     for sock in &sockets {
         if filter_listening && sock.state != TcpState::Listen {
             // For UDP, show all bound sockets since UDP doesn't have LISTEN state
@@ -289,10 +465,16 @@ pub fn get_port_infos(filter_listening: bool) -> Vec<PortInfo> {
             None => continue,
         };
 
+        // Another user's process without sudo: skip it before paying for
+        // the rest of the lookups
+        let process_name = get_process_name(pid);
+        if process_name.is_empty() {
+            continue;
+        }
         let (uid, rss_bytes) = parse_proc_status(pid);
         let (start_time, cpu_seconds) = parse_proc_stat(pid, boot_time, clock_ticks);
 
-        infos.push(PortInfo {
+        let info = PortInfo {
             port: sock.local_port,
             protocol: sock
                 .protocol
@@ -300,7 +482,7 @@ pub fn get_port_infos(filter_listening: bool) -> Vec<PortInfo> {
                 .unwrap_or(&sock.protocol)
                 .to_string(),
             pid,
-            process_name: get_process_name(pid),
+            process_name,
             command: get_process_cmdline(pid),
             user: get_username(uid),
             state: sock.state,
@@ -309,30 +491,727 @@ pub fn get_port_infos(filter_listening: bool) -> Vec<PortInfo> {
             start_time,
             children: count_children(pid),
             local_addr: sock.local_addr,
+        };
+        if !emit(info) {
+            return;
+        }
+    }
+}
+
+/// Ports with a TCP listener or bound UDP socket. Reads only the socket
+/// tables, skipping the per-process fd walk, so it's cheap to poll.
+pub fn get_listening_ports() -> HashSet<u16> {
+    get_all_sockets()
+        .into_iter()
+        .filter(|s| s.local_port != 0)
+        .filter(|s| s.state == TcpState::Listen || s.protocol.starts_with("UDP"))
+        .map(|s| s.local_port)
+        .collect()
+}
+
+/// Every TCP/UDP socket on the host, including ones with no owning process.
+pub fn get_connections() -> Vec<ConnInfo> {
+    let inode_map = build_inode_to_pid_map(None);
+    get_all_sockets()
+        .into_iter()
+        .map(|sock| ConnInfo {
+            protocol: sock
+                .protocol
+                .strip_suffix('6')
+                .unwrap_or(&sock.protocol)
+                .to_string(),
+            local_port: sock.local_port,
+            remote_addr: sock.remote_addr,
+            remote_port: sock.remote_port,
+            state: sock.state,
+            pid: inode_map.get(&sock.inode).copied(),
+        })
+        .collect()
+}
+
+// ── Socket byte counters (sock_diag netlink) ────────────────────────
+
+const SOCK_DIAG_BY_FAMILY: u16 = 20;
+const INET_DIAG_INFO: u16 = 2;
+const NLMSG_HDR_LEN: usize = 16;
+/// inet_diag_msg: family/state/timer/retrans, 48-byte sockid, then
+/// expires, rqueue, wqueue, uid, inode (u32 each).
+const INET_DIAG_MSG_LEN: usize = 72;
+const INET_DIAG_SRC_OFFSET: usize = 8;
+const INET_DIAG_RQUEUE_OFFSET: usize = 56;
+const INET_DIAG_WQUEUE_OFFSET: usize = 60;
+const INET_DIAG_INODE_OFFSET: usize = 68;
+/// Offsets of tcpi_bytes_acked / tcpi_bytes_received in struct tcp_info
+/// (present since Linux 4.1/4.2).
+const TCPI_BYTES_ACKED_OFFSET: usize = 120;
+const TCPI_BYTES_RECEIVED_OFFSET: usize = 128;
+
+fn nl_align(len: usize) -> usize {
+    (len + 3) & !3
+}
+
+fn read_u16(buf: &[u8], at: usize) -> Option<u16> {
+    buf.get(at..at + 2)
+        .map(|b| u16::from_ne_bytes([b[0], b[1]]))
+}
+
+fn read_u32(buf: &[u8], at: usize) -> Option<u32> {
+    buf.get(at..at + 4)
+        .map(|b| u32::from_ne_bytes([b[0], b[1], b[2], b[3]]))
+}
+
+fn read_u64(buf: &[u8], at: usize) -> Option<u64> {
+    let b = buf.get(at..at + 8)?;
+    let mut arr = [0u8; 8];
+    arr.copy_from_slice(b);
+    Some(u64::from_ne_bytes(arr))
+}
+
+/// Build a SOCK_DIAG_BY_FAMILY dump request for all TCP sockets of `family`,
+/// asking for the tcp_info extension.
+fn build_diag_request(family: u8, seq: u32) -> Vec<u8> {
+    diag_request(
+        family,
+        libc::IPPROTO_TCP as u8,
+        u32::MAX, // all states
+        1 << (INET_DIAG_INFO - 1),
+        seq,
+    )
+}
+
+/// A SOCK_DIAG_BY_FAMILY dump request for `protocol` sockets whose state
+/// bit is set in `states`, with the `ext` extension mask.
+fn diag_request(family: u8, protocol: u8, states: u32, ext: u8, seq: u32) -> Vec<u8> {
+    let len = NLMSG_HDR_LEN + 56;
+    let mut req = Vec::with_capacity(len);
+    req.extend_from_slice(&(len as u32).to_ne_bytes());
+    req.extend_from_slice(&SOCK_DIAG_BY_FAMILY.to_ne_bytes());
+    req.extend_from_slice(&((libc::NLM_F_REQUEST | libc::NLM_F_DUMP) as u16).to_ne_bytes());
+    req.extend_from_slice(&seq.to_ne_bytes());
+    req.extend_from_slice(&0u32.to_ne_bytes());
+    // inet_diag_req_v2
+    req.push(family);
+    req.push(protocol);
+    req.push(ext);
+    req.push(0);
+    req.extend_from_slice(&states.to_ne_bytes());
+    req.resize(len, 0); // zeroed inet_diag_sockid
+    req
+}
+
+/// Hand each inet_diag_msg payload in one recv() worth of netlink messages
+/// to `on_msg`. Returns true once the dump is finished.
+fn walk_diag_messages(buf: &[u8], mut on_msg: impl FnMut(&[u8])) -> bool {
+    let mut off = 0;
+    while off + NLMSG_HDR_LEN <= buf.len() {
+        let Some(len) = read_u32(buf, off).map(|l| l as usize) else {
+            return true;
+        };
+        let msg_type = read_u16(buf, off + 4).unwrap_or(0);
+        if len < NLMSG_HDR_LEN || off + len > buf.len() {
+            return true;
+        }
+        if msg_type == libc::NLMSG_DONE as u16 || msg_type == libc::NLMSG_ERROR as u16 {
+            return true;
+        }
+
+        on_msg(&buf[off + NLMSG_HDR_LEN..off + len]);
+        off += nl_align(len);
+    }
+    false
+}
+
+/// (inode, rx, tx) from a payload carrying the tcp_info extension.
+fn tcp_counters(payload: &[u8]) -> Option<(u64, u64, u64)> {
+    let inode = read_u32(payload, INET_DIAG_INODE_OFFSET)?;
+    let mut attr = INET_DIAG_MSG_LEN;
+    while attr + 4 <= payload.len() {
+        let alen = read_u16(payload, attr).unwrap_or(0) as usize;
+        let atype = read_u16(payload, attr + 2).unwrap_or(0);
+        if alen < 4 || attr + alen > payload.len() {
+            break;
+        }
+        if atype == INET_DIAG_INFO {
+            let info = &payload[attr + 4..attr + alen];
+            if let (Some(tx), Some(rx)) = (
+                read_u64(info, TCPI_BYTES_ACKED_OFFSET),
+                read_u64(info, TCPI_BYTES_RECEIVED_OFFSET),
+            ) {
+                return Some((inode as u64, rx, tx));
+            }
+        }
+        attr += nl_align(alen);
+    }
+    None
+}
+
+fn dump_tcp_counters(family: u8) -> Vec<(u64, u64, u64)> {
+    let mut counters = Vec::new();
+    diag_dump(&build_diag_request(family, 1), |payload| {
+        counters.extend(tcp_counters(payload))
+    });
+    counters
+}
+
+/// Send one dump request and pass every message payload to `on_msg`.
+/// Returns false if the netlink socket couldn't be opened or written.
+fn diag_dump(req: &[u8], mut on_msg: impl FnMut(&[u8])) -> bool {
+    let fd = unsafe {
+        libc::socket(
+            libc::AF_NETLINK,
+            libc::SOCK_DGRAM | libc::SOCK_CLOEXEC,
+            libc::NETLINK_SOCK_DIAG,
+        )
+    };
+    if fd < 0 {
+        return false;
+    }
+
+    let sent = unsafe { libc::send(fd, req.as_ptr() as *const libc::c_void, req.len(), 0) };
+    let ok = sent == req.len() as isize;
+    if ok {
+        let mut buf = vec![0u8; 32 * 1024];
+        loop {
+            let n = unsafe { libc::recv(fd, buf.as_mut_ptr() as *mut libc::c_void, buf.len(), 0) };
+            if n <= 0 || walk_diag_messages(&buf[..n as usize], &mut on_msg) {
+                break;
+            }
+        }
+    }
+
+    unsafe { libc::close(fd) };
+    ok
+}
+
+/// Cumulative bytes sent/received for every TCP socket owned by a process.
+pub fn get_socket_traffic() -> Vec<SocketTraffic> {
+    let inode_map = build_inode_to_pid_map(None);
+    [libc::AF_INET as u8, libc::AF_INET6 as u8]
+        .into_iter()
+        .flat_map(dump_tcp_counters)
+        .filter_map(|(inode, rx, tx)| {
+            Some(SocketTraffic {
+                key: inode,
+                pid: *inode_map.get(&inode)?,
+                rx_bytes: rx,
+                tx_bytes: tx,
+            })
+        })
+        .collect()
+}
+
+// ── Change detection ─────────────────────────────────────────────────
+
+/// Bit for TCP_LISTEN in an inet_diag state mask.
+const TCP_LISTEN_STATE: u32 = 1 << 10;
+
+/// Local port (network order at the start of inet_diag_sockid) and inode.
+fn listener_key(payload: &[u8]) -> Option<(u16, u32)> {
+    let port = payload
+        .get(4..6)
+        .map(|b| u16::from_be_bytes([b[0], b[1]]))?;
+    Some((port, read_u32(payload, INET_DIAG_INODE_OFFSET)?))
+}
+
+/// Bit for TCP_CLOSE, the state of a UDP socket with no connected peer.
+const UDP_UNCONNECTED_STATE: u32 = 1 << 7;
+
+/// (family, protocol, (port, inode)) of one socket.
+type ListenerSocket = (u8, u8, (u16, u32));
+
+/// Every TCP listener and unconnected UDP socket. Connected UDP sockets (a resolver's query, say) come and
+/// go all the time and are left out. `None` when sock_diag is blocked.
+fn listener_sockets() -> Option<Vec<ListenerSocket>> {
+    let mut sockets = Vec::new();
+    for family in [libc::AF_INET as u8, libc::AF_INET6 as u8] {
+        for (protocol, states) in [
+            (libc::IPPROTO_TCP as u8, TCP_LISTEN_STATE),
+            (libc::IPPROTO_UDP as u8, UDP_UNCONNECTED_STATE),
+        ] {
+            let req = diag_request(family, protocol, states, 0, 1);
+            let sent = diag_dump(&req, |payload| {
+                sockets.extend(listener_key(payload).map(|key| (family, protocol, key)))
+            });
+            if !sent {
+                return None;
+            }
+        }
+    }
+    sockets.sort_unstable();
+    Some(sockets)
+}
+
+/// A hash of [`listener_sockets`]. The kernel filters on state, so the
+/// cost doesn't grow with the number of established connections.
+pub fn listener_signature() -> Option<u64> {
+    let mut hasher = DefaultHasher::new();
+    listener_sockets()?.hash(&mut hasher);
+    Some(hasher.finish())
+}
+
+/// sock_diag's multicast groups for destroyed TCP and UDP sockets, IPv4
+/// and IPv6 (SKNLGRP_INET_TCP_DESTROY through SKNLGRP_INET6_UDP_DESTROY).
+const DESTROY_GROUPS: u32 = 0b1111;
+
+/// Wakes as soon as a socket without a peer (a listener, or an unconnected
+/// UDP socket) is destroyed, and once a second otherwise. Procfs files
+/// never raise inotify events and the kernel has no group for new
+/// sockets, so opens are only found by the once-a-second signature check.
+/// `None` when the groups can't be joined.
+pub fn listener_changes() -> Option<ListenerFeed> {
+    let fd = unsafe {
+        libc::socket(
+            libc::AF_NETLINK,
+            libc::SOCK_DGRAM | libc::SOCK_CLOEXEC,
+            libc::NETLINK_SOCK_DIAG,
+        )
+    };
+    if fd < 0 {
+        return None;
+    }
+    let fd = unsafe { OwnedFd::from_raw_fd(fd) };
+    let mut addr: libc::sockaddr_nl = unsafe { std::mem::zeroed() };
+    addr.nl_family = libc::AF_NETLINK as libc::sa_family_t;
+    addr.nl_groups = DESTROY_GROUPS;
+    let timeout = libc::timeval {
+        tv_sec: 1,
+        tv_usec: 0,
+    };
+    let ready = unsafe {
+        libc::bind(
+            fd.as_raw_fd(),
+            &addr as *const libc::sockaddr_nl as *const libc::sockaddr,
+            std::mem::size_of::<libc::sockaddr_nl>() as libc::socklen_t,
+        ) == 0
+            && libc::setsockopt(
+                fd.as_raw_fd(),
+                libc::SOL_SOCKET,
+                libc::SO_RCVTIMEO,
+                &timeout as *const libc::timeval as *const libc::c_void,
+                std::mem::size_of::<libc::timeval>() as libc::socklen_t,
+            ) == 0
+    };
+    if !ready {
+        return None;
+    }
+
+    let (tx, rx) = mpsc::channel();
+    let stop = Arc::new(AtomicBool::new(false));
+    let thread_stop = Arc::clone(&stop);
+    std::thread::spawn(move || {
+        let mut buf = vec![0u8; 32 * 1024];
+        while !thread_stop.load(Ordering::Relaxed) {
+            let n = unsafe {
+                libc::recv(
+                    fd.as_raw_fd(),
+                    buf.as_mut_ptr() as *mut libc::c_void,
+                    buf.len(),
+                    0,
+                )
+            };
+            // A timeout (or a dropped burst) still wakes, for the opens
+            let mut wake = n <= 0;
+            if n > 0 {
+                walk_diag_messages(&buf[..n as usize], |payload| wake |= had_no_peer(payload));
+            }
+            if wake && tx.send(()).is_err() {
+                break;
+            }
+        }
+    });
+    Some(ListenerFeed::new(rx, StopOnDrop(stop)))
+}
+
+/// Whether a destroyed socket's inet_diag_msg has no remote port, as for
+/// listeners and unconnected UDP sockets.
+fn had_no_peer(payload: &[u8]) -> bool {
+    read_u16(payload, 6) == Some(0)
+}
+
+struct StopOnDrop(Arc<AtomicBool>);
+
+impl Drop for StopOnDrop {
+    fn drop(&mut self) {
+        self.0.store(true, Ordering::Relaxed);
+    }
+}
+
+// ── Listen queues ────────────────────────────────────────────────────
+
+/// For a LISTEN socket, idiag_rqueue is the accept queue length and
+/// idiag_wqueue the backlog it may grow to.
+fn listen_queue(family: u8, payload: &[u8]) -> Option<ListenQueue> {
+    let (port, _) = listener_key(payload)?;
+    let src = payload.get(INET_DIAG_SRC_OFFSET..INET_DIAG_SRC_OFFSET + 16)?;
+    let addr = if family == libc::AF_INET6 as u8 {
+        let mut octets = [0u8; 16];
+        octets.copy_from_slice(src);
+        IpAddr::V6(Ipv6Addr::from(octets))
+    } else {
+        IpAddr::V4(Ipv4Addr::new(src[0], src[1], src[2], src[3]))
+    };
+    Some(ListenQueue {
+        port,
+        addr,
+        queued: read_u32(payload, INET_DIAG_RQUEUE_OFFSET)?,
+        backlog: read_u32(payload, INET_DIAG_WQUEUE_OFFSET)?,
+    })
+}
+
+pub fn get_listen_queues() -> Vec<ListenQueue> {
+    let mut queues = Vec::new();
+    for family in [libc::AF_INET as u8, libc::AF_INET6 as u8] {
+        let req = diag_request(family, libc::IPPROTO_TCP as u8, TCP_LISTEN_STATE, 0, 1);
+        diag_dump(&req, |payload| queues.extend(listen_queue(family, payload)));
+    }
+    queues
+}
+
+pub fn somaxconn() -> Option<u32> {
+    fs::read_to_string("/proc/sys/net/core/somaxconn")
+        .ok()?
+        .trim()
+        .parse()
+        .ok()
+}
+
+/// The IPv4 sysctls, which IPv6 sockets follow too.
+pub fn local_ports() -> Option<LocalPorts> {
+    let range = fs::read_to_string("/proc/sys/net/ipv4/ip_local_port_range").ok()?;
+    let reserved =
+        fs::read_to_string("/proc/sys/net/ipv4/ip_local_reserved_ports").unwrap_or_default();
+    Some(LocalPorts {
+        ephemeral: ephemeral::parse_range(&range)?,
+        reserved: ephemeral::parse_reserved(&reserved),
+    })
+}
+
+// ── Socket options ───────────────────────────────────────────────────
+
+const INET_DIAG_SKMEMINFO: u16 = 7;
+/// SK_MEMINFO_RCVBUF and SK_MEMINFO_SNDBUF, as byte offsets into the
+/// skmeminfo array of u32s.
+const SK_MEMINFO_RCVBUF_OFFSET: usize = 4;
+const SK_MEMINFO_SNDBUF_OFFSET: usize = 12;
+
+/// The payload of the first `atype` attribute after an inet_diag_msg.
+fn diag_attr(payload: &[u8], atype: u16) -> Option<&[u8]> {
+    let mut attr = INET_DIAG_MSG_LEN;
+    while attr + 4 <= payload.len() {
+        let alen = read_u16(payload, attr)? as usize;
+        if alen < 4 || attr + alen > payload.len() {
+            return None;
+        }
+        if read_u16(payload, attr + 2)? == atype {
+            return Some(&payload[attr + 4..attr + alen]);
+        }
+        attr += nl_align(alen);
+    }
+    None
+}
+
+/// (fd, inode) of the first of `inodes` open in `pid`'s fd table.
+fn find_socket_fd(pid: u32, inodes: &[u64]) -> Option<(i32, u64)> {
+    fs::read_dir(format!("/proc/{}/fd", pid))
+        .ok()?
+        .flatten()
+        .find_map(|entry| {
+            let link = fs::read_link(entry.path()).ok()?;
+            let inode = link
+                .to_str()?
+                .strip_prefix("socket:[")?
+                .strip_suffix(']')?
+                .parse::<u64>()
+                .ok()?;
+            let fd = entry.file_name().to_str()?.parse::<i32>().ok()?;
+            inodes.contains(&inode).then_some((fd, inode))
+        })
+}
+
+/// A copy of another process's fd via pidfd_getfd (Linux 5.6+). The
+/// kernel applies the ptrace access check, so this works as root and,
+/// unless Yama forbids it, on one's own processes.
+fn borrow_fd(pid: u32, fd: i32) -> Option<OwnedFd> {
+    let pidfd = unsafe { libc::syscall(libc::SYS_pidfd_open, pid as libc::pid_t, 0) };
+    if pidfd < 0 {
+        return None;
+    }
+    let pidfd = unsafe { OwnedFd::from_raw_fd(pidfd as i32) };
+    let copy = unsafe { libc::syscall(libc::SYS_pidfd_getfd, pidfd.as_raw_fd(), fd, 0) };
+    (copy >= 0).then(|| unsafe { OwnedFd::from_raw_fd(copy as i32) })
+}
+
+fn sockopt_int(fd: i32, level: i32, name: i32) -> Option<i32> {
+    let mut value: libc::c_int = 0;
+    let mut len = std::mem::size_of::<libc::c_int>() as libc::socklen_t;
+    let rc = unsafe {
+        libc::getsockopt(
+            fd,
+            level,
+            name,
+            &mut value as *mut libc::c_int as *mut libc::c_void,
+            &mut len,
+        )
+    };
+    (rc == 0).then_some(value)
+}
+
+/// Every option, read with getsockopt. Buffer sizes are what the kernel
+/// reports, which is double what setsockopt asked for.
+fn read_socket_options(fd: i32, tcp: bool) -> SocketOptions {
+    let flag = |level, name| sockopt_int(fd, level, name).map(|v| v != 0);
+    let num = |level, name| sockopt_int(fd, level, name).and_then(|v| u32::try_from(v).ok());
+    let tcp_num = |name| num(libc::IPPROTO_TCP, name).filter(|_| tcp);
+    SocketOptions {
+        reuse_addr: flag(libc::SOL_SOCKET, libc::SO_REUSEADDR),
+        reuse_port: flag(libc::SOL_SOCKET, libc::SO_REUSEPORT),
+        no_delay: flag(libc::IPPROTO_TCP, libc::TCP_NODELAY).filter(|_| tcp),
+        keepalive: flag(libc::SOL_SOCKET, libc::SO_KEEPALIVE).filter(|_| tcp),
+        keepalive_idle: tcp_num(libc::TCP_KEEPIDLE),
+        keepalive_interval: tcp_num(libc::TCP_KEEPINTVL),
+        keepalive_count: tcp_num(libc::TCP_KEEPCNT),
+        recv_buffer: num(libc::SOL_SOCKET, libc::SO_RCVBUF),
+        send_buffer: num(libc::SOL_SOCKET, libc::SO_SNDBUF),
+    }
+}
+
+/// (rcvbuf, sndbuf) of the socket with `inode`, from sock_diag's
+/// skmeminfo, which needs no access to the process.
+fn diag_buffers(protocol: u8, inode: u64) -> Option<(u32, u32)> {
+    let mut found = None;
+    for family in [libc::AF_INET as u8, libc::AF_INET6 as u8] {
+        let req = diag_request(
+            family,
+            protocol,
+            u32::MAX,
+            1 << (INET_DIAG_SKMEMINFO - 1),
+            1,
+        );
+        diag_dump(&req, |payload| {
+            if found.is_none() && read_u32(payload, INET_DIAG_INODE_OFFSET) == Some(inode as u32) {
+                found = diag_attr(payload, INET_DIAG_SKMEMINFO).and_then(|mem| {
+                    Some((
+                        read_u32(mem, SK_MEMINFO_RCVBUF_OFFSET)?,
+                        read_u32(mem, SK_MEMINFO_SNDBUF_OFFSET)?,
+                    ))
+                });
+            }
         });
+        if found.is_some() {
+            break;
+        }
     }
+    found
+}
 
-    // Drop entries where we couldn't read process details (other user's process without sudo)
-    infos.retain(|i| !i.process_name.is_empty());
+/// Options on the row's socket: all of them from a borrowed copy of the
+/// fd when the process may be traced, otherwise only the buffer sizes.
+pub fn get_socket_options(info: &PortInfo) -> Option<SocketOptions> {
+    let tcp = info.protocol.starts_with("TCP");
+    let inodes: Vec<u64> = get_all_sockets()
+        .into_iter()
+        .filter(|s| {
+            s.inode != 0
+                && s.local_port == info.port
+                && s.protocol.starts_with(info.protocol.as_str())
+                && (!tcp || s.state == info.state)
+        })
+        .map(|s| s.inode)
+        .collect();
+    let (fd, inode) = find_socket_fd(info.pid, &inodes)?;
+    if let Some(copy) = borrow_fd(info.pid, fd) {
+        return Some(read_socket_options(copy.as_raw_fd(), tcp));
+    }
+    let protocol = if tcp {
+        libc::IPPROTO_TCP
+    } else {
+        libc::IPPROTO_UDP
+    };
+    let (recv, send) = diag_buffers(protocol as u8, inode)?;
+    Some(SocketOptions {
+        recv_buffer: Some(recv),
+        send_buffer: Some(send),
+        ..Default::default()
+    })
+}
 
-    // Sort by port number, then protocol, then pid (pid needed for dedup_by adjacency)
-    infos.sort_by(|a, b| {
-        a.port
-            .cmp(&b.port)
-            .then_with(|| a.protocol.cmp(&b.protocol))
-            .then_with(|| a.pid.cmp(&b.pid))
+// ── Diagnostics ──────────────────────────────────────────────────────
+
+/// The `hidepid=` option /proc is mounted with, when it hides processes.
+fn parse_hidepid(mounts: &str) -> Option<String> {
+    mounts
+        .lines()
+        .map(|line| line.split_whitespace().collect::<Vec<_>>())
+        .filter(|f| f.len() >= 4 && f[1] == "/proc")
+        .flat_map(|f| {
+            f[3].split(',')
+                .filter_map(|opt| opt.strip_prefix("hidepid="))
+                .map(str::to_string)
+                .collect::<Vec<_>>()
+        })
+        .find(|v| v != "0" && v != "off")
+}
+
+fn sock_diag_available() -> std::io::Result<()> {
+    let fd = unsafe {
+        libc::socket(
+            libc::AF_NETLINK,
+            libc::SOCK_DGRAM | libc::SOCK_CLOEXEC,
+            libc::NETLINK_SOCK_DIAG,
+        )
+    };
+    if fd < 0 {
+        return Err(std::io::Error::last_os_error());
+    }
+    unsafe { libc::close(fd) };
+    Ok(())
+}
+
+/// Probe every source `get_port_infos` and friends read, for `portview doctor`.
+pub fn diagnose() -> Vec<Check> {
+    let mut checks = Vec::new();
+
+    let mut sockets = 0;
+    let mut missing = Vec::new();
+    for path in [
+        "/proc/net/tcp",
+        "/proc/net/tcp6",
+        "/proc/net/udp",
+        "/proc/net/udp6",
+    ] {
+        match fs::read_to_string(path) {
+            Ok(content) => sockets += content.lines().count().saturating_sub(1),
+            Err(e) => missing.push(format!("{} ({})", path, e)),
+        }
+    }
+    checks.push(if missing.is_empty() {
+        Check::ok(
+            "socket tables",
+            format!("/proc/net/{{tcp,tcp6,udp,udp6}} readable, {} sockets", sockets),
+        )
+    } else if missing.iter().any(|m| m.starts_with("/proc/net/tcp ")) {
+        Check::fail(
+            "socket tables",
+            format!("cannot read {}", missing.join(", ")),
+            "portview needs procfs. Mount /proc, or run it on the host rather than in a sandbox that hides /proc/net.",
+        )
+    } else {
+        Check::warn(
+            "socket tables",
+            format!("cannot read {}", missing.join(", ")),
+            "Expected when IPv6 is disabled; otherwise those sockets won't be listed.",
+        )
     });
 
-    // Deduplicate (same port+proto+pid can appear for v4 and v6)
-    infos.dedup_by(|a, b| a.port == b.port && a.protocol == b.protocol && a.pid == b.pid);
+    let root = unsafe { libc::geteuid() } == 0;
+    let mut processes = 0;
+    let mut unreadable = 0;
+    if let Ok(dir) = fs::read_dir("/proc") {
+        for entry in dir.flatten() {
+            if entry.file_name().to_string_lossy().parse::<u32>().is_err() {
+                continue;
+            }
+            processes += 1;
+            if fs::read_dir(entry.path().join("fd")).is_err() {
+                unreadable += 1;
+            }
+        }
+    }
+    let inode_map = build_inode_to_pid_map(None);
+    let listeners: Vec<SocketEntry> = get_all_sockets()
+        .into_iter()
+        .filter(|s| s.state == TcpState::Listen && s.inode != 0)
+        .collect();
+    let ownerless = listeners
+        .iter()
+        .filter(|s| !inode_map.contains_key(&s.inode))
+        .count();
+    let hidepid = fs::read_to_string("/proc/mounts")
+        .ok()
+        .and_then(|m| parse_hidepid(&m));
+    let detail = format!(
+        "{} of {} processes inspectable; {} of {} listening sockets without a visible owner{}",
+        processes - unreadable,
+        processes,
+        ownerless,
+        listeners.len(),
+        hidepid
+            .as_ref()
+            .map(|v| format!(" (/proc mounted hidepid={})", v))
+            .unwrap_or_default()
+    );
+    checks.push(if ownerless == 0 {
+        Check::ok("process access", detail)
+    } else if root {
+        Check::warn(
+            "process access",
+            detail,
+            "Those sockets likely belong to another network or PID namespace (a container); run portview inside it.",
+        )
+    } else {
+        Check::warn(
+            "process access",
+            detail,
+            "Ports owned by other users show without a process. Run `sudo portview` to see every owner.",
+        )
+    });
 
-    infos
+    checks.push(if get_boot_time() != 0 {
+        Check::ok("process stats", "/proc/stat boot time readable".to_string())
+    } else {
+        Check::warn(
+            "process stats",
+            "cannot read boot time from /proc/stat".to_string(),
+            "The UPTIME column will be blank. Check that /proc/stat is readable.",
+        )
+    });
+
+    checks.push(match sock_diag_available() {
+        Ok(()) => Check::ok(
+            "traffic counters",
+            "netlink sock_diag available".to_string(),
+        ),
+        Err(e) => Check::warn(
+            "traffic counters",
+            format!("netlink sock_diag unavailable: {}", e),
+            "The NET column will stay empty. Some sandboxes and seccomp profiles block NETLINK_SOCK_DIAG.",
+        ),
+    });
+
+    checks
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
 
+    // ── parse_lineage ───────────────────────────────────────────────
+
+    #[test]
+    fn parse_lineage_after_the_command_name() {
+        let stat = "4242 (node (dev) x) Z 1 4242 4100 34817 0 -1 4194560";
+        assert_eq!(
+            parse_lineage(stat),
+            Some(Lineage {
+                zombie: true,
+                ppid: 1,
+                session: 4100
+            })
+        );
+        assert_eq!(parse_lineage("4242 (node"), None);
+    }
+
+    // ── parse_hidepid ───────────────────────────────────────────────
+
+    #[test]
+    fn parse_hidepid_finds_proc_option() {
+        let mounts = "sysfs /sys sysfs rw,nosuid 0 0\n\
+                      proc /proc proc rw,nosuid,nodev,noexec,relatime,hidepid=2,gid=10 0 0\n";
+        assert_eq!(parse_hidepid(mounts).as_deref(), Some("2"));
+        assert_eq!(parse_hidepid("proc /proc proc rw,hidepid=0 0 0\n"), None);
+        assert_eq!(parse_hidepid("proc /proc proc rw,relatime 0 0\n"), None);
+    }
+
     // ── parse_hex_addr_v4 ───────────────────────────────────────────
 
     #[test]
@@ -394,6 +1273,13 @@ mod tests {
         assert_eq!(addr, IpAddr::V6(Ipv6Addr::UNSPECIFIED));
     }
 
+    #[test]
+    fn parse_hex_addr_v6_multibyte_input() {
+        // 32 bytes, but slicing at byte 8 would land inside a character
+        let addr = parse_hex_addr_v6("0000000\u{e9}00000000000000000000000");
+        assert_eq!(addr, IpAddr::V6(Ipv6Addr::UNSPECIFIED));
+    }
+
     // ── parse_addr_port ─────────────────────────────────────────────
 
     #[test]
@@ -429,4 +1315,303 @@ mod tests {
         let (_, port) = parse_addr_port("0100007F:ZZZZ", false);
         assert_eq!(port, 0);
     }
+
+    // ── parse_stat_nice_threads ─────────────────────────────────────
+
+    #[test]
+    fn parse_stat_nice_threads_reads_fields() {
+        let stat = "1234 (my (odd) proc) S 1 1234 1234 0 -1 4194560 100 0 0 0 \
+                    5 3 0 0 20 5 12 0 4242 1000000 200 18446744073709551615";
+        assert_eq!(parse_stat_nice_threads(stat), (Some(5), Some(12)));
+    }
+
+    #[test]
+    fn parse_stat_nice_threads_negative_nice() {
+        let stat = "1 (init) S 0 1 1 0 -1 0 0 0 0 0 0 0 0 0 0 -10 1 0 1";
+        assert_eq!(parse_stat_nice_threads(stat), (Some(-10), Some(1)));
+    }
+
+    #[test]
+    fn parse_stat_nice_threads_malformed() {
+        assert_eq!(parse_stat_nice_threads(""), (None, None));
+        assert_eq!(parse_stat_nice_threads("1 (x) S 0"), (None, None));
+    }
+
+    // ── sock_diag parsing ───────────────────────────────────────────
+
+    fn parse_diag_messages(buf: &[u8], out: &mut Vec<(u64, u64, u64)>) -> bool {
+        walk_diag_messages(buf, |payload| out.extend(tcp_counters(payload)))
+    }
+
+    fn diag_message(inode: u32, acked: u64, received: u64) -> Vec<u8> {
+        let mut info = vec![0u8; 136];
+        info[TCPI_BYTES_ACKED_OFFSET..TCPI_BYTES_ACKED_OFFSET + 8]
+            .copy_from_slice(&acked.to_ne_bytes());
+        info[TCPI_BYTES_RECEIVED_OFFSET..TCPI_BYTES_RECEIVED_OFFSET + 8]
+            .copy_from_slice(&received.to_ne_bytes());
+
+        let mut payload = vec![0u8; INET_DIAG_MSG_LEN];
+        payload[INET_DIAG_INODE_OFFSET..INET_DIAG_INODE_OFFSET + 4]
+            .copy_from_slice(&inode.to_ne_bytes());
+        payload.extend_from_slice(&((info.len() + 4) as u16).to_ne_bytes());
+        payload.extend_from_slice(&INET_DIAG_INFO.to_ne_bytes());
+        payload.extend_from_slice(&info);
+
+        let mut msg = Vec::new();
+        msg.extend_from_slice(&((NLMSG_HDR_LEN + payload.len()) as u32).to_ne_bytes());
+        msg.extend_from_slice(&SOCK_DIAG_BY_FAMILY.to_ne_bytes());
+        msg.extend_from_slice(&[0u8; 10]);
+        msg.extend_from_slice(&payload);
+        msg
+    }
+
+    #[test]
+    fn build_diag_request_layout() {
+        let req = build_diag_request(libc::AF_INET as u8, 7);
+        assert_eq!(req.len(), 72);
+        assert_eq!(read_u32(&req, 0), Some(72));
+        assert_eq!(read_u16(&req, 4), Some(SOCK_DIAG_BY_FAMILY));
+        assert_eq!(read_u32(&req, 8), Some(7));
+        assert_eq!(req[16], libc::AF_INET as u8);
+        assert_eq!(req[17], libc::IPPROTO_TCP as u8);
+        assert_eq!(req[18], 2);
+    }
+
+    #[test]
+    fn parse_diag_messages_reads_counters() {
+        let mut buf = diag_message(1234, 500, 9000);
+        buf.extend(diag_message(99, 1, 2));
+        let mut out = Vec::new();
+        assert!(!parse_diag_messages(&buf, &mut out));
+        assert_eq!(out, vec![(1234, 9000, 500), (99, 2, 1)]);
+    }
+
+    #[test]
+    fn listener_request_and_key() {
+        let req = diag_request(
+            libc::AF_INET6 as u8,
+            libc::IPPROTO_TCP as u8,
+            TCP_LISTEN_STATE,
+            0,
+            3,
+        );
+        assert_eq!(req[18], 0, "no extensions needed");
+        assert_eq!(read_u32(&req, 20), Some(1 << 10));
+
+        let mut msg = diag_message(777, 0, 0);
+        msg[NLMSG_HDR_LEN + 4..NLMSG_HDR_LEN + 6].copy_from_slice(&3000u16.to_be_bytes());
+        let mut keys = Vec::new();
+        walk_diag_messages(&msg, |payload| keys.extend(listener_key(payload)));
+        assert_eq!(keys, vec![(3000, 777)]);
+    }
+
+    #[test]
+    fn listen_queue_reads_backlog() {
+        let mut msg = diag_message(777, 0, 0);
+        let payload = &mut msg[NLMSG_HDR_LEN..];
+        payload[4..6].copy_from_slice(&8080u16.to_be_bytes());
+        payload[INET_DIAG_SRC_OFFSET..INET_DIAG_SRC_OFFSET + 4].copy_from_slice(&[127, 0, 0, 1]);
+        payload[INET_DIAG_RQUEUE_OFFSET..INET_DIAG_RQUEUE_OFFSET + 4]
+            .copy_from_slice(&3u32.to_ne_bytes());
+        payload[INET_DIAG_WQUEUE_OFFSET..INET_DIAG_WQUEUE_OFFSET + 4]
+            .copy_from_slice(&4096u32.to_ne_bytes());
+        let mut queues = Vec::new();
+        walk_diag_messages(&msg, |p| {
+            queues.extend(listen_queue(libc::AF_INET as u8, p))
+        });
+        assert_eq!(
+            queues,
+            vec![ListenQueue {
+                port: 8080,
+                addr: IpAddr::V4(Ipv4Addr::LOCALHOST),
+                queued: 3,
+                backlog: 4096,
+            }]
+        );
+    }
+
+    #[test]
+    fn parse_diag_messages_stops_on_done() {
+        let mut done = vec![0u8; NLMSG_HDR_LEN];
+        done[0..4].copy_from_slice(&(NLMSG_HDR_LEN as u32).to_ne_bytes());
+        done[4..6].copy_from_slice(&(libc::NLMSG_DONE as u16).to_ne_bytes());
+        let mut out = Vec::new();
+        assert!(parse_diag_messages(&done, &mut out));
+        assert!(out.is_empty());
+    }
+
+    #[test]
+    fn parse_diag_messages_ignores_short_tcp_info() {
+        let mut msg = diag_message(5, 1, 1);
+        // Truncate tcp_info below the byte counters (pre-4.2 kernels)
+        let cut = NLMSG_HDR_LEN + INET_DIAG_MSG_LEN + 4 + 100;
+        msg.truncate(cut);
+        msg[0..4].copy_from_slice(&(cut as u32).to_ne_bytes());
+        let attr_len = (4 + 100) as u16;
+        let at = NLMSG_HDR_LEN + INET_DIAG_MSG_LEN;
+        msg[at..at + 2].copy_from_slice(&attr_len.to_ne_bytes());
+        let mut out = Vec::new();
+        parse_diag_messages(&msg, &mut out);
+        assert!(out.is_empty());
+    }
+
+    #[test]
+    fn diag_attr_finds_skmeminfo() {
+        let mut payload = vec![0u8; INET_DIAG_MSG_LEN];
+        let mut mem = vec![0u8; 36];
+        mem[SK_MEMINFO_RCVBUF_OFFSET..SK_MEMINFO_RCVBUF_OFFSET + 4]
+            .copy_from_slice(&131072u32.to_ne_bytes());
+        payload.extend_from_slice(&8u16.to_ne_bytes());
+        payload.extend_from_slice(&INET_DIAG_INFO.to_ne_bytes());
+        payload.extend_from_slice(&[0u8; 4]);
+        payload.extend_from_slice(&((mem.len() + 4) as u16).to_ne_bytes());
+        payload.extend_from_slice(&INET_DIAG_SKMEMINFO.to_ne_bytes());
+        payload.extend_from_slice(&mem);
+        let found = diag_attr(&payload, INET_DIAG_SKMEMINFO).unwrap();
+        assert_eq!(read_u32(found, SK_MEMINFO_RCVBUF_OFFSET), Some(131072));
+        assert_eq!(
+            diag_attr(&payload[..INET_DIAG_MSG_LEN], INET_DIAG_INFO),
+            None
+        );
+    }
+
+    // ── socket options ──────────────────────────────────────────────
+
+    #[test]
+    fn read_options_from_a_listener() {
+        // std sets SO_REUSEADDR on Unix listeners
+        let listener = std::net::TcpListener::bind("127.0.0.1:0").unwrap();
+        let opts = read_socket_options(listener.as_raw_fd(), true);
+        assert_eq!(opts.reuse_addr, Some(true));
+        assert_eq!(opts.reuse_port, Some(false));
+        assert_eq!(opts.keepalive, Some(false));
+        assert!(opts.recv_buffer.is_some_and(|b| b > 0));
+        let udp = std::net::UdpSocket::bind("127.0.0.1:0").unwrap();
+        let opts = read_socket_options(udp.as_raw_fd(), false);
+        assert_eq!(opts.no_delay, None);
+        assert_eq!(opts.keepalive_idle, None);
+    }
+
+    // ── change detection ────────────────────────────────────────────
+
+    #[test]
+    fn connected_udp_churn_stays_out_of_the_signature() {
+        let server = std::net::UdpSocket::bind("127.0.0.1:0").unwrap();
+        let server_port = server.local_addr().unwrap().port();
+        let mut churned = Vec::new();
+        for _ in 0..5 {
+            let client = std::net::UdpSocket::bind("127.0.0.1:0").unwrap();
+            client.connect(server.local_addr().unwrap()).unwrap();
+            churned.push(client.local_addr().unwrap().port());
+            let Some(sockets) = listener_sockets() else {
+                return; // sock_diag blocked here
+            };
+            let ports: Vec<u16> = sockets.iter().map(|(_, _, (port, _))| *port).collect();
+            assert!(ports.contains(&server_port));
+            assert!(
+                !churned.iter().any(|p| ports.contains(p)),
+                "connected sockets {:?} in {:?}",
+                churned,
+                ports
+            );
+        }
+    }
+
+    #[test]
+    fn destroyed_listener_wakes_the_feed() {
+        let Some(feed) = listener_changes() else {
+            return; // sock_diag multicast blocked here
+        };
+        let listener = std::net::TcpListener::bind("127.0.0.1:0").unwrap();
+        while feed.wakes.try_recv().is_ok() {}
+        let start = std::time::Instant::now();
+        drop(listener);
+        feed.wakes.recv_timeout(Duration::from_secs(2)).unwrap();
+        // Well before the once-a-second wake
+        assert!(start.elapsed() < Duration::from_millis(500));
+    }
+}
+
+// ── Property tests ───────────────────────────────────────────────────
+//
+// `cargo test --features fuzz`: random and kernel-shaped /proc/net lines
+// must never panic the collector, and well-formed ones must read back
+// exactly.
+
+#[cfg(all(test, feature = "fuzz"))]
+mod fuzz {
+    use super::*;
+    use proptest::prelude::*;
+
+    /// `addr:port` as the kernel prints it: each 32-bit word of the
+    /// address in host (little-endian) order, port in big-endian hex.
+    fn kernel_addr(addr: IpAddr, port: u16) -> String {
+        let words: Vec<String> = match addr {
+            IpAddr::V4(v4) => vec![format!("{:08X}", u32::from_le_bytes(v4.octets()))],
+            IpAddr::V6(v6) => v6
+                .octets()
+                .chunks(4)
+                .map(|w| format!("{:08X}", u32::from_le_bytes([w[0], w[1], w[2], w[3]])))
+                .collect(),
+        };
+        format!("{}:{:04X}", words.concat(), port)
+    }
+
+    fn any_addr(ipv6: bool) -> BoxedStrategy<IpAddr> {
+        if ipv6 {
+            any::<[u8; 16]>()
+                .prop_map(|o| IpAddr::V6(Ipv6Addr::from(o)))
+                .boxed()
+        } else {
+            any::<[u8; 4]>()
+                .prop_map(|o| IpAddr::V4(Ipv4Addr::from(o)))
+                .boxed()
+        }
+    }
+
+    proptest! {
+        #[test]
+        fn addr_port_never_panics(s in "\\PC*", ipv6 in any::<bool>()) {
+            parse_addr_port(&s, ipv6);
+        }
+
+        #[test]
+        fn hex_addr_v6_never_panics(s in "[0-9A-F\u{e9}]{24,32}") {
+            // Around 32 bytes with the odd two-byte character
+            parse_hex_addr_v6(&s);
+        }
+
+        #[test]
+        fn proc_net_text_never_panics(
+            lines in prop::collection::vec("[0-9A-Fa-f: \\t]{0,160}|\\PC{0,160}", 0..8),
+            ipv6 in any::<bool>(),
+        ) {
+            let text = lines.join("\n");
+            parse_proc_net_text(&text, "TCP", ipv6);
+            parse_proc_net_text(&text, "UDP", ipv6);
+        }
+
+        #[test]
+        fn kernel_rows_read_back(
+            (ipv6, local, remote) in any::<bool>().prop_flat_map(|v6| (Just(v6), any_addr(v6), any_addr(v6))),
+            local_port in any::<u16>(),
+            remote_port in any::<u16>(),
+            inode in any::<u64>(),
+        ) {
+            let row = format!(
+                "   0: {} {} 0A 00000000:00000000 00:00000000 00000000  1000        0 {} 1 0000000000000000 100 0 0 10 0",
+                kernel_addr(local, local_port),
+                kernel_addr(remote, remote_port),
+                inode
+            );
+            let text = format!("  sl  local_address rem_address   st ...\n{}\n", row);
+            let entries = parse_proc_net_text(&text, if ipv6 { "TCP6" } else { "TCP" }, ipv6);
+            prop_assert_eq!(entries.len(), 1);
+            let e = &entries[0];
+            prop_assert_eq!((e.local_addr, e.local_port), (local, local_port));
+            prop_assert_eq!((e.remote_addr, e.remote_port), (remote, remote_port));
+            prop_assert_eq!(e.state, TcpState::Listen);
+            prop_assert_eq!(e.inode, inode);
+        }
+    }
 }
@@ -1,7 +1,28 @@
+use std::collections::HashSet;
 use std::net::{IpAddr, Ipv4Addr, Ipv6Addr};
 use std::time::{Duration, UNIX_EPOCH};
 
-use crate::{get_username, PortInfo, TcpState};
+use crate::backlog::ListenQueue;
+use crate::bandwidth::SocketTraffic;
+use crate::collector::Capabilities;
+use crate::doctor::Check;
+use crate::ephemeral::LocalPorts;
+use crate::orphan::Lineage;
+use crate::refresh::ListenerFeed;
+use crate::sockopts::SocketOptions;
+use crate::{get_username, parse_env_hints, ConnInfo, PortInfo, ProcessDetail, TcpState};
+
+// ── Backend ──────────────────────────────────────────────────────────
+
+pub const BACKEND_NAME: &str = "libproc";
+
+/// macOS walks each process's fds through libproc; UDP sockets are always
+/// reported as bound.
+pub const CAPABILITIES: Capabilities = Capabilities {
+    supports_remote_addr: true,
+    supports_udp_state: false,
+    needs_privileges: true,
+};
 
 // ── Constants ────────────────────────────────────────────────────────
 
@@ -9,6 +30,7 @@ const PROC_ALL_PIDS: u32 = 1;
 const PROC_PIDLISTFDS: i32 = 1;
 const PROC_PIDTASKALLINFO: i32 = 2;
 const PROC_PIDFDSOCKETINFO: i32 = 3;
+const PROC_PIDVNODEPATHINFO: i32 = 9;
 const PROX_FDTYPE_SOCKET: u32 = 2;
 const SOCKINFO_TCP: i32 = 2;
 const SOCKINFO_IN: i32 = 1;
@@ -241,6 +263,32 @@ struct ProcTaskAllInfo {
     ptinfo: ProcTaskInfo,
 }
 
+#[repr(C)]
+#[derive(Clone, Copy)]
+struct VnodeInfo {
+    vi_stat: VinfoStat,
+    vi_type: i32,
+    vi_pad: i32,
+    vi_fsid: [i32; 2],
+}
+
+#[repr(C)]
+#[derive(Clone, Copy)]
+struct VnodeInfoPath {
+    vip_vi: VnodeInfo,
+    vip_path: [u8; MAXPATHLEN as usize],
+}
+
+#[repr(C)]
+#[derive(Clone, Copy)]
+struct ProcVnodePathInfo {
+    pvi_cdir: VnodeInfoPath,
+    pvi_rdir: VnodeInfoPath,
+}
+
+const _: () = assert!(std::mem::size_of::<VinfoStat>() == 136);
+const _: () = assert!(std::mem::size_of::<VnodeInfoPath>() == 1176);
+const _: () = assert!(std::mem::size_of::<ProcVnodePathInfo>() == 2352);
 const _: () = assert!(std::mem::size_of::<ProcBsdInfo>() == 136);
 const _: () = assert!(std::mem::size_of::<ProcTaskInfo>() == 96);
 const _: () = assert!(std::mem::size_of::<ProcTaskAllInfo>() == 232);
@@ -334,6 +382,91 @@ fn get_task_all_info(pid: i32) -> Option<ProcTaskAllInfo> {
     }
 }
 
+/// Working directory of a process (readable for our own processes, or as root).
+pub fn get_process_cwd(pid: u32) -> Option<String> {
+    let pid = pid as i32;
+    let mut info: ProcVnodePathInfo = unsafe { std::mem::zeroed() };
+    let ret = unsafe {
+        proc_pidinfo(
+            pid,
+            PROC_PIDVNODEPATHINFO,
+            0,
+            &mut info as *mut ProcVnodePathInfo as *mut libc::c_void,
+            std::mem::size_of::<ProcVnodePathInfo>() as i32,
+        )
+    };
+    if ret <= 0 {
+        return None;
+    }
+    let path = &info.pvi_cdir.vip_path;
+    let len = path.iter().position(|&b| b == 0).unwrap_or(path.len());
+    (len > 0).then(|| String::from_utf8_lossy(&path[..len]).to_string())
+}
+
+/// Raw KERN_PROCARGS2 block: argc, exec path, argv, then the environment.
+/// Only readable for our own processes (or as root).
+fn get_procargs(pid: i32) -> Vec<u8> {
+    let mut argmax: libc::c_int = 0;
+    let mut size = std::mem::size_of::<libc::c_int>();
+    let mut mib = [libc::CTL_KERN, libc::KERN_ARGMAX];
+    let ret = unsafe {
+        libc::sysctl(
+            mib.as_mut_ptr(),
+            2,
+            &mut argmax as *mut libc::c_int as *mut libc::c_void,
+            &mut size,
+            std::ptr::null_mut(),
+            0,
+        )
+    };
+    if ret != 0 || argmax <= 0 {
+        return vec![];
+    }
+
+    let mut buf = vec![0u8; argmax as usize];
+    let mut size = buf.len();
+    let mut mib = [libc::CTL_KERN, libc::KERN_PROCARGS2, pid];
+    let ret = unsafe {
+        libc::sysctl(
+            mib.as_mut_ptr(),
+            3,
+            buf.as_mut_ptr() as *mut libc::c_void,
+            &mut size,
+            std::ptr::null_mut(),
+            0,
+        )
+    };
+    if ret != 0 {
+        return vec![];
+    }
+    buf.truncate(size);
+    buf
+}
+
+/// Skip argc, the exec path (plus its NUL padding) and argv in a
+/// KERN_PROCARGS2 block, returning the NUL-separated environment.
+fn procargs_env(buf: &[u8]) -> &[u8] {
+    if buf.len() < 4 {
+        return &[];
+    }
+    let argc = i32::from_ne_bytes([buf[0], buf[1], buf[2], buf[3]]).max(0) as usize;
+    let mut pos = 4;
+    // exec path
+    while pos < buf.len() && buf[pos] != 0 {
+        pos += 1;
+    }
+    while pos < buf.len() && buf[pos] == 0 {
+        pos += 1;
+    }
+    for _ in 0..argc {
+        while pos < buf.len() && buf[pos] != 0 {
+            pos += 1;
+        }
+        pos += 1;
+    }
+    buf.get(pos..).unwrap_or(&[])
+}
+
 fn get_pid_path(pid: i32) -> String {
     let mut buf = [0u8; MAXPATHLEN as usize];
     let ret = unsafe { proc_pidpath(pid, buf.as_mut_ptr() as *mut libc::c_void, MAXPATHLEN) };
@@ -354,6 +487,50 @@ fn count_children(pid: i32) -> u32 {
     count as u32
 }
 
+/// Signal 0 checks for the process without touching it; EPERM means it
+/// exists but belongs to someone else.
+pub fn process_alive(pid: u32) -> bool {
+    (unsafe { libc::kill(pid as i32, 0) } == 0)
+        || std::io::Error::last_os_error().raw_os_error() == Some(libc::EPERM)
+}
+
+/// `SZOMB` in the BSD process status.
+const SZOMB: u32 = 5;
+
+pub fn get_lineage(pid: u32) -> Option<Lineage> {
+    let info = get_task_all_info(pid as i32)?;
+    let session = unsafe { libc::getsid(pid as i32) };
+    Some(Lineage {
+        zombie: info.pbsd.pbi_status == SZOMB,
+        ppid: info.pbsd.pbi_ppid,
+        session: u32::try_from(session).ok()?,
+    })
+}
+
+pub fn get_parent_pid(pid: u32) -> Option<u32> {
+    get_task_all_info(pid as i32).map(|info| info.pbsd.pbi_ppid)
+}
+
+pub fn get_child_pids(pid: u32) -> Vec<u32> {
+    let size = unsafe { proc_listchildpids(pid as i32, std::ptr::null_mut(), 0) };
+    if size <= 0 {
+        return Vec::new();
+    }
+    // Sized generously for children forked between the two calls; unused
+    // slots stay zero.
+    let mut buf = vec![0i32; size as usize + 16];
+    let bytes = (buf.len() * std::mem::size_of::<i32>()) as i32;
+    let ret =
+        unsafe { proc_listchildpids(pid as i32, buf.as_mut_ptr() as *mut libc::c_void, bytes) };
+    if ret <= 0 {
+        return Vec::new();
+    }
+    buf.into_iter()
+        .filter(|&p| p > 0)
+        .map(|p| p as u32)
+        .collect()
+}
+
 fn extract_addr(addr_union: &InAddrUnion, vflag: u8) -> IpAddr {
     if vflag & INI_IPV4 != 0 {
         let s_addr = unsafe { addr_union.ina_46.i46a_addr4 };
@@ -366,6 +543,52 @@ fn extract_addr(addr_union: &InAddrUnion, vflag: u8) -> IpAddr {
     }
 }
 
+struct DecodedSocket {
+    protocol: &'static str,
+    state: TcpState,
+    local_port: u16,
+    local_addr: IpAddr,
+    remote_port: u16,
+    remote_addr: IpAddr,
+}
+
+/// Decode an AF_INET/AF_INET6 socket into addresses and state. Returns `None`
+/// for any other family or socket kind.
+fn decode_inet_socket(si: &SocketInfo) -> Option<DecodedSocket> {
+    if si.soi_family != libc::AF_INET as i32 && si.soi_family != libc::AF_INET6 as i32 {
+        return None;
+    }
+
+    if si.soi_kind == SOCKINFO_TCP {
+        let tcp: TcpSockInfo =
+            unsafe { std::ptr::read_unaligned(si.soi_proto.as_ptr() as *const TcpSockInfo) };
+        let ini = &tcp.tcpsi_ini;
+        Some(DecodedSocket {
+            protocol: "TCP",
+            state: TcpState::from_tsi(tcp.tcpsi_state),
+            local_port: u16::from_be(ini.insi_lport as u16),
+            local_addr: extract_addr(&ini.insi_laddr, ini.insi_vflag),
+            remote_port: u16::from_be(ini.insi_fport as u16),
+            remote_addr: extract_addr(&ini.insi_faddr, ini.insi_vflag),
+        })
+    } else if si.soi_kind == SOCKINFO_IN {
+        // UDP socket
+        let in_info: InSockInfo =
+            unsafe { std::ptr::read_unaligned(si.soi_proto.as_ptr() as *const InSockInfo) };
+        Some(DecodedSocket {
+            protocol: "UDP",
+            // UDP doesn't have LISTEN — treat bound sockets as listening
+            state: TcpState::Listen,
+            local_port: u16::from_be(in_info.insi_lport as u16),
+            local_addr: extract_addr(&in_info.insi_laddr, in_info.insi_vflag),
+            remote_port: u16::from_be(in_info.insi_fport as u16),
+            remote_addr: extract_addr(&in_info.insi_faddr, in_info.insi_vflag),
+        })
+    } else {
+        None
+    }
+}
+
 fn process_name_from_path(path: &str) -> String {
     if path.is_empty() {
         return String::new();
@@ -380,6 +603,18 @@ fn cstr_from_bytes(bytes: &[u8]) -> String {
 
 // ── Main entry point ─────────────────────────────────────────────────
 
+pub fn resolve_user(user: &str) -> Option<String> {
+    crate::resolve_unix_user(user)
+}
+
+/// Ports of one account's processes. proc_pidinfo has to be asked per
+/// socket anyway, so this filters the full list.
+pub fn get_user_port_infos(filter_listening: bool, user: &str) -> Vec<PortInfo> {
+    let mut infos = get_port_infos(filter_listening);
+    infos.retain(|i| i.user == user);
+    infos
+}
+
 pub fn get_port_infos(filter_listening: bool) -> Vec<PortInfo> {
     let pids = list_all_pids();
     let mut infos: Vec<PortInfo> = Vec::new();
@@ -409,32 +644,13 @@ pub fn get_port_infos(filter_listening: bool) -> Vec<PortInfo> {
                 None => continue, // EPERM or other error — silently skip
             };
 
-            let si = &sock_info.psi;
-
-            // Only interested in AF_INET and AF_INET6
-            if si.soi_family != libc::AF_INET as i32 && si.soi_family != libc::AF_INET6 as i32 {
-                continue;
-            }
-
-            let (protocol, state, local_port, local_addr) = if si.soi_kind == SOCKINFO_TCP {
-                let tcp: TcpSockInfo = unsafe {
-                    std::ptr::read_unaligned(si.soi_proto.as_ptr() as *const TcpSockInfo)
-                };
-                let state = TcpState::from_tsi(tcp.tcpsi_state);
-                let port = u16::from_be(tcp.tcpsi_ini.insi_lport as u16);
-                let addr = extract_addr(&tcp.tcpsi_ini.insi_laddr, tcp.tcpsi_ini.insi_vflag);
-                ("TCP".to_string(), state, port, addr)
-            } else if si.soi_kind == SOCKINFO_IN {
-                // UDP socket
-                let in_info: InSockInfo =
-                    unsafe { std::ptr::read_unaligned(si.soi_proto.as_ptr() as *const InSockInfo) };
-                let port = u16::from_be(in_info.insi_lport as u16);
-                let addr = extract_addr(&in_info.insi_laddr, in_info.insi_vflag);
-                // UDP doesn't have LISTEN — treat bound sockets as listening
-                ("UDP".to_string(), TcpState::Listen, port, addr)
-            } else {
+            let Some(decoded) = decode_inet_socket(&sock_info.psi) else {
                 continue;
             };
+            let protocol = decoded.protocol.to_string();
+            let state = decoded.state;
+            let local_port = decoded.local_port;
+            let local_addr = decoded.local_addr;
 
             if local_port == 0 {
                 continue;
@@ -535,10 +751,199 @@ pub fn get_port_infos(filter_listening: bool) -> Vec<PortInfo> {
     infos
 }
 
+/// Every TCP/UDP socket visible through per-process fd tables. Unlike Linux
+/// and Windows, sockets with no owning process (TIME_WAIT) are not visible.
+pub fn get_connections() -> Vec<ConnInfo> {
+    let mut conns = Vec::new();
+    for pid in list_all_pids() {
+        for fd_info in list_fds(pid) {
+            if fd_info.proc_fdtype != PROX_FDTYPE_SOCKET {
+                continue;
+            }
+            let Some(sock_info) = get_socket_info(pid, fd_info.proc_fd) else {
+                continue;
+            };
+            let Some(decoded) = decode_inet_socket(&sock_info.psi) else {
+                continue;
+            };
+            conns.push(ConnInfo {
+                protocol: decoded.protocol.to_string(),
+                local_port: decoded.local_port,
+                remote_addr: decoded.remote_addr,
+                remote_port: decoded.remote_port,
+                state: decoded.state,
+                pid: Some(pid as u32),
+            });
+        }
+    }
+    conns
+}
+
+/// Ports with a TCP listener or bound UDP socket. macOS has no system-wide
+/// socket table here, so this still walks fds, but skips per-process stats.
+pub fn get_listening_ports() -> HashSet<u16> {
+    get_connections()
+        .into_iter()
+        .filter(|c| c.local_port != 0)
+        .filter(|c| c.state == TcpState::Listen || c.protocol == "UDP")
+        .map(|c| c.local_port)
+        .collect()
+}
+
+/// Thread count, nice value, working directory and environment hints for
+/// one process.
+pub fn get_process_detail(pid: u32) -> ProcessDetail {
+    let task = get_task_all_info(pid as i32);
+    let procargs = get_procargs(pid as i32);
+    ProcessDetail {
+        threads: task.map(|t| t.ptinfo.pti_threadnum.max(0) as u32),
+        nice: task.map(|t| t.pbsd.pbi_nice),
+        cwd: get_process_cwd(pid),
+        env: parse_env_hints(procargs_env(&procargs)),
+        repo: None,
+        orphan: None,
+    }
+}
+
+/// Per-socket byte counters aren't collected on macOS; throughput columns
+/// show `-`.
+pub fn get_socket_traffic() -> Vec<SocketTraffic> {
+    Vec::new()
+}
+
+/// No cheap listener signature on macOS; watch mode refreshes on its timer.
+pub fn listener_signature() -> Option<u64> {
+    None
+}
+
+pub fn listener_changes() -> Option<ListenerFeed> {
+    None
+}
+
+/// Accept queues aren't collected on macOS; `netstat -L` shows them.
+pub fn get_listen_queues() -> Vec<ListenQueue> {
+    Vec::new()
+}
+
+pub fn somaxconn() -> Option<u32> {
+    None
+}
+
+pub fn local_ports() -> Option<LocalPorts> {
+    None
+}
+
+/// `TF_NODELAY` in a TCP control block's flags.
+const TF_NODELAY: u32 = 0x4;
+
+/// The socket's `so_options` bits and buffer high-water marks, from the
+/// same proc_pidfdinfo call the port scan makes. Keepalive timers aren't
+/// exposed there.
+pub fn get_socket_options(info: &PortInfo) -> Option<SocketOptions> {
+    let pid = info.pid as i32;
+    list_fds(pid)
+        .iter()
+        .filter(|fd| fd.proc_fdtype == PROX_FDTYPE_SOCKET)
+        .filter_map(|fd| get_socket_info(pid, fd.proc_fd))
+        .find_map(|sock| {
+            let si = &sock.psi;
+            let decoded = decode_inet_socket(si)?;
+            if decoded.local_port != info.port
+                || decoded.protocol != info.protocol
+                || decoded.state != info.state
+            {
+                return None;
+            }
+            let options = si.soi_options as u16 as i32;
+            let tcp = si.soi_kind == SOCKINFO_TCP;
+            let no_delay = tcp.then(|| {
+                let tcp: TcpSockInfo = unsafe {
+                    std::ptr::read_unaligned(si.soi_proto.as_ptr() as *const TcpSockInfo)
+                };
+                tcp.tcpsi_flags & TF_NODELAY != 0
+            });
+            Some(SocketOptions {
+                reuse_addr: Some(options & libc::SO_REUSEADDR != 0),
+                reuse_port: Some(options & libc::SO_REUSEPORT != 0),
+                no_delay,
+                keepalive: tcp.then_some(options & libc::SO_KEEPALIVE != 0),
+                recv_buffer: Some(si.soi_rcv.sbi_hiwat),
+                send_buffer: Some(si.soi_snd.sbi_hiwat),
+                ..Default::default()
+            })
+        })
+}
+
+/// Probe the libproc calls `get_port_infos` relies on, for `portview doctor`.
+pub fn diagnose() -> Vec<Check> {
+    let mut checks = Vec::new();
+    let pids = list_all_pids();
+    if pids.is_empty() {
+        checks.push(Check::fail(
+            "process list",
+            "proc_listpids returned no processes".to_string(),
+            "portview can't enumerate processes here; a sandbox (App Sandbox, some CI runners) may be blocking libproc.",
+        ));
+        return checks;
+    }
+    checks.push(Check::ok(
+        "process list",
+        format!("proc_listpids sees {} processes", pids.len()),
+    ));
+
+    let unreadable = pids
+        .iter()
+        .filter(
+            |&&pid| unsafe { proc_pidinfo(pid, PROC_PIDLISTFDS, 0, std::ptr::null_mut(), 0) } <= 0,
+        )
+        .count();
+    let detail = format!(
+        "{} of {} processes have readable file descriptors",
+        pids.len() - unreadable,
+        pids.len()
+    );
+    checks.push(if unreadable == 0 {
+        Check::ok("process access", detail)
+    } else if unsafe { libc::geteuid() } == 0 {
+        Check::warn(
+            "process access",
+            detail,
+            "Even root can't inspect some system processes under SIP; their ports won't be listed.",
+        )
+    } else {
+        Check::warn(
+            "process access",
+            detail,
+            "Ports owned by other users are hidden. Run `sudo portview` to see every listener.",
+        )
+    });
+
+    let listeners = get_listening_ports().len();
+    checks.push(Check::ok(
+        "socket info",
+        format!("{} listening ports decoded", listeners),
+    ));
+    checks
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
 
+    // ── procargs_env ────────────────────────────────────────────────
+
+    #[test]
+    fn procargs_env_skips_path_and_argv() {
+        let mut buf = 2i32.to_ne_bytes().to_vec();
+        buf.extend_from_slice(b"/usr/local/bin/node\0\0\0\0node\0server.js\0PORT=3000\0HOME=/x\0");
+        assert_eq!(procargs_env(&buf), b"PORT=3000\0HOME=/x\0");
+    }
+
+    #[test]
+    fn procargs_env_short_buffer() {
+        assert!(procargs_env(&[1, 0]).is_empty());
+    }
+
     // ── process_name_from_path ──────────────────────────────────────
 
     #[test]
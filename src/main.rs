@@ -1,29 +1,112 @@
-use clap::{Parser, Subcommand};
+use clap::{Args, Parser, Subcommand};
 use crossterm::style::{Attribute, Color, Print, ResetColor, SetAttribute, SetForegroundColor};
 use crossterm::ExecutableCommand;
+use std::collections::HashMap;
 use std::io::{self, IsTerminal, Write};
 use std::net::IpAddr;
+use std::path::{Path, PathBuf};
 use std::sync::atomic::{AtomicBool, Ordering};
-use std::time::{Duration, SystemTime, UNIX_EPOCH};
+#[cfg(unix)]
+use std::sync::Mutex;
+use std::sync::OnceLock;
+use std::time::{Duration, Instant, SystemTime, UNIX_EPOCH};
 
 #[cfg(target_os = "linux")]
 mod linux;
 #[cfg(target_os = "linux")]
-use linux::get_port_infos;
+use linux as os;
 
 #[cfg(target_os = "macos")]
 mod macos;
 #[cfg(target_os = "macos")]
-use macos::get_port_infos;
+use macos as os;
 
 #[cfg(target_os = "windows")]
 mod windows;
 #[cfg(target_os = "windows")]
-use windows::get_port_infos;
-
+use windows as os;
+
+mod actions;
+mod alerts;
+mod audit;
+mod backlog;
+mod bandwidth;
+mod batch;
+mod cards;
+mod clipboard;
+mod clock;
+mod collector;
+mod config;
+mod confirm;
+mod daemon;
+mod declared;
+mod diff;
+mod dns;
 mod docker;
+mod doctor;
+#[cfg(all(target_os = "linux", feature = "ebpf"))]
+mod ebpf;
+mod ephemeral;
+mod errors;
+mod events;
+mod expect;
+mod export;
+mod fixture;
+mod fuzzy;
+mod here;
+mod hold;
+mod hooks;
+mod keymap;
+mod legacy;
+mod notify;
+mod orphan;
+mod pager;
+mod peers;
+mod pipes;
+mod project;
+mod quic;
+mod recording;
+mod refresh;
+mod remember;
+mod resolver;
+mod respawn;
+mod reuseport;
+mod runtime;
+mod selftest;
+mod shellhook;
+mod signals;
+mod sockopts;
+mod states;
+mod status;
+mod suggest;
+mod summary;
+mod tcpstats;
+mod template;
+mod title;
 mod tui;
-use docker::{get_docker_port_map, DockerPortMap, DockerPortOwner};
+mod udpservice;
+mod units;
+mod verify;
+mod why;
+
+use alerts::AlertRule;
+use batch::KillFilter;
+use docker::{
+    get_docker_port_map, ContainerNetwork, ContainerState, DockerPortMap, DockerPortOwner,
+    PortConflict,
+};
+use hooks::EventHooks;
+use notify::NotifyRule;
+use peers::PeerSummary;
+use project::RepoInfo;
+use quic::Quic;
+use resolver::Resolver;
+use respawn::RestartCommand;
+use runtime::Runtime;
+use signals::Signal;
+use sockopts::SocketOptions;
+use template::Template;
+use udpservice::{ServiceReply, UdpService};
 
 #[cfg(not(any(target_os = "linux", target_os = "macos", target_os = "windows")))]
 compile_error!("portview only supports Linux, macOS, and Windows");
@@ -35,90 +118,565 @@ compile_error!("portview only supports Linux, macOS, and Windows");
     name = "portview",
     about = "See what's on your ports, then act on it.",
     version,
-    after_help = "Examples:\n  portview                   Show all listening ports\n  portview 3000              Inspect port 3000 in detail\n  portview watch --docker    Interactive watch with Docker context\n  portview kill 3000 --force Force-kill process(es) on port 3000\n\nLegacy flags (--watch, --kill) are still supported."
+    after_help = "Examples:\n  portview                   Show all listening ports\n  portview 3000              Inspect port 3000 in detail\n  portview 5432 --peers      Inspect port 5432 and list connected hosts\n  portview node --cwd        Tell identical dev servers apart by directory\n  portview watch --docker    Interactive watch with Docker context\n  portview watch --notify 'state=opened'  Desktop notification for new listeners\n  portview watch 8080 --on-open 'systemctl reload nginx'  Run a command when a port comes up\n  portview watch --record s.pvr  Record a session; play it back with portview replay s.pvr\n  portview watch --json --changes-only >> ports.log  Log only ticks where listeners changed\n  portview watch --format ndjson  One JSON object per port per line, for log pipelines\n  portview watch --plain     Redraw a plain table every second (serial consoles, CI, screen readers)\n  portview watch 3000 5432 6379  A compact status card per port for a corner terminal\n  portview kill 3000 --force Force-kill process(es) on port 3000\n  portview kill 3000 --respawn  Restart the dev server on 3000 with the same command\n  portview kill 80 --signal HUP  Reload nginx without stopping it\n  portview kill 3000-3010 --yes  Kill every dev server on 3000-3010 in one go\n  portview watch --no-confirm --confirm-force  Kill with one key, but type the PID for SIGKILL\n  portview hold 3000 --for 5m Reserve port 3000 for five minutes\n  portview suggest -n 3      Print three free ports in 3000-9999\n  portview why 3000          Explain why binding port 3000 fails\n  portview diff before.json after.json  Listeners opened, closed or changed between two --json snapshots\n  portview states            Count TCP sockets per state, port and process\n  portview pipes docker      Named pipes and the processes serving them (Windows)\n  portview doctor            Check why portview shows nothing (permissions, procfs, Docker)\n  portview expect dev.toml   Check that every service in a manifest is up\n  portview expect --watch    Live uptime dashboard for the services in .portview.toml\n  portview expect --format vscode  Failing services as file:line diagnostics for editor tasks\n  portview status --ports 3000,5432  One-line summary for tmux/prompts\n  portview daemon            Keep $XDG_RUNTIME_DIR/portview/status.json current for editors\n  portview here              Ports this project declares (package.json, compose, .env) and their status\n  eval \"$(portview hook zsh)\"  Warn on cd when a project's port is already taken\n  portview --template '{{port}} {{process}}'  One line per port for scripts\n  portview pid 4321 --children  Ports held by a process and its children\n  portview --mine            Only ports opened by your own processes\n  portview --session         Only ports opened by what you started in this terminal\n  portview --runtime         Tag each listener with its runtime (node, python, java, ...)\n  portview --quic            Spot QUIC/HTTP3 servers among UDP listeners\n  portview --plain-table     Columns without box drawing (screen readers, diff)\n  portview list --paginate   Page a long table through $PAGER, like git\n  portview --all --stream --json  NDJSON rows as they're read, for huge listings\n  portview --units binary,exact  Memory in KiB/MiB with two decimals\n  portview --timestamps absolute  Show when each process started instead of uptime\n\nLegacy flags (--watch, --kill) still work and print the subcommand that replaces them."
 )]
 struct Cli {
     /// UX-first subcommands
     #[command(subcommand)]
     command: Option<Command>,
 
-    /// Port number to inspect, or 'scan' to list all
+    /// Port number to inspect, process name to search for, or 'scan' to list all
     target: Option<String>,
 
     /// Kill the process on the specified port
-    #[arg(short, long, hide = true)]
+    #[arg(short, long, hide = true, conflicts_with_all = ["target", "all", "user", "mine", "session", "exact"])]
     kill: Option<u16>,
 
     /// Force kill (SIGKILL instead of SIGTERM)
     #[arg(short, long)]
     force: bool,
 
+    /// Live-refresh the display every second
+    #[arg(short, long, hide = true, conflicts_with_all = ["template", "width"])]
+    watch: bool,
+
+    /// When inspecting a port, list connected peers grouped by remote host
+    #[arg(long)]
+    peers: bool,
+
+    /// Don't reverse-resolve peer addresses to hostnames
+    #[arg(long)]
+    no_dns: bool,
+
+    #[command(flatten)]
+    list: ListArgs,
+
+    /// Print the JSON Schema describing --json output and exit
+    #[arg(long)]
+    schema: bool,
+
+    /// Memory units: jedec (default, 1024 as KB), binary (KiB), decimal (kB)
+    /// or bytes; add ",exact" for two decimals. Also read from PORTVIEW_UNITS.
+    #[arg(long, global = true, value_name = "UNITS", value_parser = parse_units)]
+    units: Option<units::ByteFormat>,
+
+    /// Show process start times as uptime (relative, default) or as local
+    /// date and time (absolute)
+    #[arg(long, global = true, value_name = "STYLE", value_parser = parse_timestamps)]
+    timestamps: Option<clock::TimestampStyle>,
+
+    /// Kill from the TUI without the y/n popup
+    #[arg(long, global = true)]
+    no_confirm: bool,
+
+    /// Require typing the PID before any SIGKILL (TUI, prompts and kill --force)
+    #[arg(long, global = true)]
+    confirm_force: bool,
+
+    /// Read ports from a JSON file (same shape as --json) instead of the OS;
+    /// kills only remove rows. For tests and demos.
+    #[arg(long, global = true, hide = true, value_name = "FILE")]
+    fixture: Option<PathBuf>,
+}
+
+/// Filter, sort and format flags for the port table, shared by
+/// `portview list` and the bare `portview [target]` form.
+#[derive(Args, Debug, Clone, Default, PartialEq)]
+struct ListArgs {
     /// Show all ports including non-listening
     #[arg(short, long)]
     all: bool,
 
-    /// Output as JSON
+    /// Only list ports owned by this user (name, or uid on Unix)
+    #[arg(long, value_name = "NAME")]
+    user: Option<String>,
+
+    /// Only list ports owned by the current user (same as --user $USER)
+    #[arg(long, conflicts_with = "user")]
+    mine: bool,
+
+    /// Only list ports opened by programs started from this shell
     #[arg(long)]
-    json: bool,
+    session: bool,
+
+    /// Match a name target as a substring only, without fuzzy matching
+    #[arg(long)]
+    exact: bool,
 
     /// Enrich output with Docker container ownership when available
     #[arg(long)]
     docker: bool,
 
-    /// Don't use colors
+    /// Sort rows by port (default), proto, pid, user, process, uptime, mem or command
+    #[arg(long, value_name = "KEY", value_parser = parse_sort_key)]
+    sort: Option<SortKey>,
+
+    /// Reverse the sort order
     #[arg(long)]
-    no_color: bool,
+    reverse: bool,
 
-    /// Live-refresh the display every second
-    #[arg(short, long, hide = true)]
-    watch: bool,
+    /// Add a CWD column with each process's working directory
+    #[arg(long)]
+    cwd: bool,
+
+    /// Add a RUNTIME column (node, python, java, go, ...) guessed from the command
+    #[arg(long)]
+    runtime: bool,
+
+    /// Probe UDP listeners for QUIC and add an APP column (QUIC/HTTP3)
+    #[arg(long)]
+    quic: bool,
 
     /// Don't truncate the command column (use full terminal width)
     #[arg(long)]
     wide: bool,
+
+    /// Lay the table out for COLS columns instead of the terminal's width
+    /// (e.g. when piping into a pager)
+    #[arg(long, value_name = "COLS", value_parser = clap::value_parser!(u16).range(40..))]
+    width: Option<u16>,
+
+    /// Output as JSON
+    #[arg(long)]
+    json: bool,
+
+    /// Output format: table (default), json, or ndjson for one JSON object
+    /// per line
+    #[arg(long, value_name = "FORMAT", value_parser = parse_output_format, conflicts_with = "json")]
+    format: Option<OutputFormat>,
+
+    /// Print one line per port using a format string, e.g. '{{port}} {{process}}'
+    #[arg(long, value_name = "FORMAT", conflicts_with_all = ["json", "format"])]
+    template: Option<String>,
+
+    /// Space-aligned columns without box-drawing characters
+    #[arg(long)]
+    plain_table: bool,
+
+    /// Print rows as they're read, unsorted, in fixed-width columns (or
+    /// one JSON object per line with --json), for huge --all listings
+    #[arg(long, conflicts_with_all = ["template", "sort", "reverse", "docker", "quic", "cwd", "runtime", "wide", "paginate"])]
+    stream: bool,
+
+    /// With --plain-table, label every value: `PORT: 3000  PROTO: TCP ...`
+    #[arg(long, requires = "plain_table")]
+    header_per_row: bool,
+
+    /// Send the output through $PAGER (less by default) when it's a terminal
+    #[arg(long)]
+    paginate: bool,
+
+    /// Never page, even when the config turns paging on
+    #[arg(long, conflicts_with = "paginate")]
+    no_pager: bool,
+
+    /// Don't use colors
+    #[arg(long)]
+    no_color: bool,
 }
 
-#[derive(Subcommand, Debug)]
+#[derive(Subcommand, Debug, Clone, PartialEq)]
 enum Command {
+    /// List ports as a table (the default when no subcommand is given)
+    List {
+        /// Process name, container or port number to filter rows by
+        filter: Option<String>,
+        #[command(flatten)]
+        args: ListArgs,
+    },
     /// Live-refresh the display (interactive TUI by default)
     Watch {
         /// Port number or process name filter
         target: Option<String>,
+        /// More ports to watch; with two or more, each gets a compact card
+        #[arg(value_name = "PORT", conflicts_with_all = ["json", "format", "plain", "tcp_stats"])]
+        more_ports: Vec<u16>,
         /// Show all ports including non-listening
         #[arg(short, long)]
         all: bool,
         /// Output as JSON (streaming in watch mode)
         #[arg(long)]
         json: bool,
+        /// Output format: table (the TUI), json (one array per tick) or
+        /// ndjson (one object per port per line)
+        #[arg(long, value_name = "FORMAT", value_parser = parse_output_format, conflicts_with = "json")]
+        format: Option<OutputFormat>,
         /// Enable Docker ownership context
         #[arg(long)]
         docker: bool,
+        /// How often to re-read `docker ps` with --docker (e.g. 5s, 30s)
+        #[arg(long, value_name = "DURATION", default_value = "5s", value_parser = parse_duration_arg, requires = "docker")]
+        docker_refresh: Duration,
         /// Force kill (default for d in TUI / kill prompts)
         #[arg(short, long)]
         force: bool,
         /// Don't truncate the command column
         #[arg(long)]
         wide: bool,
+        /// Don't reverse-resolve peer addresses in the detail view
+        #[arg(long)]
+        no_dns: bool,
+        /// Start with the CWD column shown (toggle with c)
+        #[arg(long)]
+        cwd: bool,
+        /// Start with the RUNTIME column shown (toggle with t)
+        #[arg(long)]
+        runtime: bool,
+        /// Add an RTT/RETX column from eBPF probes (Linux, `ebpf` feature; needs root)
+        #[arg(long, conflicts_with_all = ["json", "format", "plain"])]
+        tcp_stats: bool,
+        /// Only list ports owned by this user (name, or uid on Unix)
+        #[arg(long, value_name = "NAME")]
+        user: Option<String>,
+        /// Only list ports owned by the current user
+        #[arg(long, conflicts_with = "user")]
+        mine: bool,
+        /// Only list ports opened by programs started from this shell
+        #[arg(long)]
+        session: bool,
+        /// Match the target as a substring only, without fuzzy matching
+        #[arg(long)]
+        exact: bool,
+        /// Desktop notification rule, e.g. "port=22 state=opened" (repeatable)
+        #[arg(long, value_name = "RULE", value_parser = parse_notify_rule)]
+        notify: Vec<NotifyRule>,
+        /// Shell command to run when a listener opens (PORTVIEW_* env vars describe it)
+        #[arg(long, value_name = "CMD")]
+        on_open: Option<String>,
+        /// Shell command to run when a listener closes
+        #[arg(long, value_name = "CMD")]
+        on_close: Option<String>,
+        /// URL to POST a JSON event to when a listener opens or closes
+        #[arg(long, value_name = "URL")]
+        webhook: Option<String>,
+        /// Highlight processes over a threshold, e.g. "mem>1GB" or "cpu>80%" (repeatable)
+        #[arg(long, value_name = "RULE", value_parser = parse_alert_rule)]
+        alert: Vec<AlertRule>,
+        /// Send a desktop notification when an --alert starts
+        #[arg(long, requires = "alert")]
+        alert_notify: bool,
+        /// Shell command to run when an --alert starts
+        #[arg(long, value_name = "CMD", requires = "alert")]
+        on_alert: Option<String>,
+        /// Ring the terminal bell when a watched port opens, closes or changes owner
+        #[arg(long)]
+        bell: bool,
+        /// Keep the terminal title set to a summary, e.g. "portview: 14 ports, 3000 ✓"
+        #[arg(long)]
+        title: bool,
+        /// Save every refresh to FILE for `portview replay`
+        #[arg(long, value_name = "FILE")]
+        record: Option<PathBuf>,
+        /// With --json, skip ticks where no listener opened, closed or changed
+        #[arg(long, requires = "json")]
+        changes_only: bool,
+        /// Redraw a plain table every second instead of the interactive TUI
+        #[arg(long, conflicts_with_all = ["json", "format"])]
+        plain: bool,
+        /// With --plain, draw the table without box-drawing characters
+        #[arg(long, requires = "plain")]
+        plain_table: bool,
+        /// With --plain-table, label every value: `PORT: 3000  PROTO: TCP ...`
+        #[arg(long, requires = "plain_table")]
+        header_per_row: bool,
         /// Disable all colors
         #[arg(long)]
         no_color: bool,
     },
-    /// Kill process(es) bound to a port
+    /// Kill process(es) bound to a port, a range of ports or a filter
     Kill {
-        /// Port to kill
-        port: u16,
+        /// Port to kill, or a range like 3000-3010
+        #[arg(
+            value_name = "PORT",
+            value_parser = parse_port_range_arg,
+            required_unless_present = "filter"
+        )]
+        ports: Option<(u16, u16)>,
+        /// Kill every listener matching a rule like "process=node" (keys: port, process, proto, user)
+        #[arg(long, value_name = "RULE", value_parser = KillFilter::parse)]
+        filter: Option<KillFilter>,
+        /// Kill a range or filter match without asking first
+        #[arg(short, long)]
+        yes: bool,
+        /// Print per-process results as JSON
+        #[arg(long)]
+        json: bool,
         /// Force kill (SIGKILL / TerminateProcess)
         #[arg(short, long)]
         force: bool,
+        /// Send this signal instead: a name like HUP or USR1, or a number (Unix)
+        #[arg(short, long, value_name = "SIG", value_parser = Signal::parse, conflicts_with = "force")]
+        signal: Option<Signal>,
         /// Show Docker ownership context before killing
         #[arg(long)]
         docker: bool,
+        /// Re-run the killed process's command in its original directory
+        #[arg(long)]
+        respawn: bool,
+        /// Disable all colors
+        #[arg(long)]
+        no_color: bool,
+    },
+    /// Bind and hold port(s) open until released
+    Hold {
+        /// Port(s) to hold
+        #[arg(required = true)]
+        ports: Vec<u16>,
+        /// Hold TCP (default when neither --tcp nor --udp is given)
+        #[arg(long)]
+        tcp: bool,
+        /// Hold UDP
+        #[arg(long)]
+        udp: bool,
+        /// Address to bind
+        #[arg(long, default_value = "0.0.0.0")]
+        bind: IpAddr,
+        /// Release automatically after a duration (e.g. 30s, 5m, 1h)
+        #[arg(long = "for", value_name = "DURATION", value_parser = parse_duration_arg)]
+        duration: Option<Duration>,
+        /// Disable all colors
+        #[arg(long)]
+        no_color: bool,
+    },
+    /// Print unbound port(s) in a range, for scripts that need a free port
+    Suggest {
+        /// Inclusive port range to search (e.g. 3000-3999)
+        #[arg(long, default_value = "3000-9999", value_parser = parse_port_range_arg)]
+        range: (u16, u16),
+        /// Number of ports to return
+        #[arg(short = 'n', long, default_value_t = 1)]
+        count: usize,
+        /// Pick ports at random instead of the lowest free ones
+        #[arg(long)]
+        random: bool,
+        /// Also skip ports published by Docker containers
+        #[arg(long)]
+        docker: bool,
+        /// Output as JSON
+        #[arg(long)]
+        json: bool,
+    },
+    /// Explain why binding a port might fail (EADDRINUSE diagnostics)
+    Why {
+        /// Port to diagnose
+        port: u16,
+        /// Check Docker-published ports too
+        #[arg(long)]
+        docker: bool,
+        /// Output as JSON
+        #[arg(long)]
+        json: bool,
+        /// Disable all colors
+        #[arg(long)]
+        no_color: bool,
+    },
+    /// Check that the services in a manifest are listening
+    Expect {
+        /// Manifest with `[[service]]` tables (default: the nearest .portview.toml)
+        manifest: Option<PathBuf>,
+        /// Keep checking in a live dashboard with uptime history
+        #[arg(short, long, conflicts_with_all = ["json", "format"])]
+        watch: bool,
+        /// Time between checks in --watch mode (e.g. 5s, 1m)
+        #[arg(long, default_value = "2s", value_parser = parse_duration_arg, requires = "watch")]
+        interval: Duration,
+        /// Output as JSON
+        #[arg(long)]
+        json: bool,
+        /// Output format: text, json, or vscode (`file:line:col: severity: message`
+        /// for editor problem matchers)
+        #[arg(long, value_name = "FORMAT", value_parser = expect::ExpectFormat::parse, conflicts_with = "json")]
+        format: Option<expect::ExpectFormat>,
+        /// Disable all colors
+        #[arg(long)]
+        no_color: bool,
+    },
+    /// Summarize TCP sockets per state, port and process
+    States {
+        /// Only show this port (local or remote)
+        port: Option<u16>,
+        /// Output as JSON
+        #[arg(long)]
+        json: bool,
+        /// Disable all colors
+        #[arg(long)]
+        no_color: bool,
+    },
+    /// List named pipes and the processes serving them (Windows)
+    Pipes {
+        /// Only pipes whose name or process contains this
+        filter: Option<String>,
+        /// Output as JSON
+        #[arg(long)]
+        json: bool,
+        /// Disable all colors
+        #[arg(long)]
+        no_color: bool,
+    },
+    /// Check that every data source works here and explain how to fix those that don't
+    Doctor {
+        /// Output as JSON
+        #[arg(long)]
+        json: bool,
+        /// Disable all colors
+        #[arg(long)]
+        no_color: bool,
+    },
+    /// Play back a `watch --record` file in the TUI (Space pauses, ←/→ step)
+    Replay {
+        /// Recording to play
+        file: PathBuf,
+        /// Disable all colors
+        #[arg(long)]
+        no_color: bool,
+    },
+    /// List the ports a process has open (the reverse of `portview <port>`)
+    Pid {
+        /// Process ID
+        pid: u32,
+        /// Include the ports of its child processes, recursively
+        #[arg(short, long)]
+        children: bool,
+        /// Show all ports including non-listening
+        #[arg(short, long)]
+        all: bool,
+        /// Output as JSON
+        #[arg(long)]
+        json: bool,
+        /// Disable all colors
+        #[arg(long)]
+        no_color: bool,
+    },
+    /// One-line up/down summary of selected ports, for tmux and shell prompts
+    Status {
+        /// Comma-separated ports to check (e.g. 3000,5432,6379)
+        #[arg(long, value_delimiter = ',', required = true)]
+        ports: Vec<u16>,
+        /// Use 🟢/🔴 instead of ✓/✗
+        #[arg(long)]
+        emoji: bool,
+        /// Color with tmux #[fg=…] markup instead of ANSI escapes
+        #[arg(long, conflicts_with_all = ["color", "no_color"])]
+        tmux: bool,
+        /// Always use ANSI colors, even when output is captured (shell prompts)
+        #[arg(long, conflicts_with = "no_color")]
+        color: bool,
+        /// Disable all colors
+        #[arg(long)]
+        no_color: bool,
+    },
+    /// Keep a JSON snapshot of the ports in a file, for editors and status bars
+    Daemon {
+        /// File to write (default: $XDG_RUNTIME_DIR/portview/status.json)
+        #[arg(short, long, value_name = "FILE")]
+        output: Option<PathBuf>,
+        /// Time between samples (e.g. 2s, 30s); listener changes write sooner
+        #[arg(long, default_value = "2s", value_parser = parse_duration_arg)]
+        interval: Duration,
+        /// Include non-listening sockets
+        #[arg(short, long)]
+        all: bool,
+        /// Add Docker ownership to each port
+        #[arg(long)]
+        docker: bool,
+        /// Write one snapshot and exit, leaving the file in place
+        #[arg(long)]
+        once: bool,
+    },
+    /// Bind sockets and check the collector reports them correctly
+    #[command(hide = true)]
+    Selftest {
+        /// Output as JSON
+        #[arg(long)]
+        json: bool,
+        /// Disable all colors
+        #[arg(long)]
+        no_color: bool,
+    },
+    /// Compare two saved `--json` snapshots: listeners added, removed or changed
+    Diff {
+        /// Earlier snapshot (`portview --json > before.json`, or a daemon status file)
+        before: PathBuf,
+        /// Later snapshot
+        after: PathBuf,
+        /// Compare only which ports are open, not the processes holding them
+        #[arg(long)]
+        ports_only: bool,
+        /// Output as JSON
+        #[arg(long)]
+        json: bool,
+        /// Disable all colors
+        #[arg(long)]
+        no_color: bool,
+    },
+    /// Show the ports this directory's project declares and what holds them
+    Here {
+        /// Output as JSON
+        #[arg(long)]
+        json: bool,
+        /// Disable all colors
+        #[arg(long)]
+        no_color: bool,
+    },
+    /// Print shell functions that warn when a project's port is taken on cd
+    Hook {
+        /// Shell to print the hook for: zsh, bash or fish
+        #[arg(value_parser = shellhook::Shell::parse, required_unless_present = "check")]
+        shell: Option<shellhook::Shell>,
+        /// Check the current directory's declared ports (what the hook runs)
+        #[arg(long, hide = true, conflicts_with = "shell")]
+        check: bool,
+    },
+    /// Show the kills, signals and container restarts portview has carried out
+    Log {
+        /// Number of most recent entries to show
+        #[arg(short = 'n', long, default_value_t = 20)]
+        lines: usize,
+        /// Log to read (default: ~/.local/state/portview/actions.log)
+        #[arg(long, value_name = "FILE")]
+        file: Option<PathBuf>,
+        /// Output as JSON
+        #[arg(long)]
+        json: bool,
         /// Disable all colors
         #[arg(long)]
         no_color: bool,
     },
 }
 
+fn parse_port_range_arg(s: &str) -> Result<(u16, u16), String> {
+    suggest::parse_port_range(s)
+        .ok_or_else(|| format!("invalid port range '{}' (try 3000-3999)", s))
+}
+
+fn parse_alert_rule(s: &str) -> Result<AlertRule, String> {
+    AlertRule::parse(s)
+}
+
+fn parse_units(s: &str) -> Result<units::ByteFormat, String> {
+    units::ByteFormat::parse(s)
+}
+
+fn parse_output_format(s: &str) -> Result<OutputFormat, String> {
+    OutputFormat::parse(s)
+}
+
+fn parse_sort_key(s: &str) -> Result<SortKey, String> {
+    SortKey::parse(s)
+}
+
+fn parse_timestamps(s: &str) -> Result<clock::TimestampStyle, String> {
+    clock::TimestampStyle::parse(s)
+}
+
+fn parse_notify_rule(s: &str) -> Result<NotifyRule, String> {
+    NotifyRule::parse(s)
+}
+
+fn parse_duration_arg(s: &str) -> Result<Duration, String> {
+    parse_duration(s).ok_or_else(|| format!("invalid duration '{}' (try 30s, 5m, 1h)", s))
+}
+
 // ── Data types ───────────────────────────────────────────────────────
 
 #[derive(Debug, Clone)]
@@ -137,7 +695,134 @@ pub(crate) struct PortInfo {
     pub(crate) local_addr: IpAddr,
 }
 
-#[derive(Debug, Clone, Copy, PartialEq)]
+/// Rows for tests: start from [`PortInfo::listener`] and set only the
+/// fields a test cares about.
+#[cfg(test)]
+impl PortInfo {
+    /// `process` listening on 0.0.0.0:`port`/TCP as `dev`, with its name
+    /// as the command line and no usage recorded.
+    pub(crate) fn listener(port: u16, pid: u32, process: &str) -> Self {
+        Self {
+            port,
+            protocol: "TCP".to_string(),
+            pid,
+            process_name: process.to_string(),
+            command: process.to_string(),
+            user: "dev".to_string(),
+            state: TcpState::Listen,
+            memory_bytes: 0,
+            cpu_seconds: 0.0,
+            start_time: None,
+            children: 0,
+            local_addr: IpAddr::V4(std::net::Ipv4Addr::UNSPECIFIED),
+        }
+    }
+
+    pub(crate) fn protocol(mut self, protocol: &str) -> Self {
+        self.protocol = protocol.to_string();
+        self
+    }
+
+    pub(crate) fn command(mut self, command: &str) -> Self {
+        self.command = command.to_string();
+        self
+    }
+
+    pub(crate) fn user(mut self, user: &str) -> Self {
+        self.user = user.to_string();
+        self
+    }
+
+    pub(crate) fn usage(mut self, memory_bytes: u64, cpu_seconds: f64) -> Self {
+        self.memory_bytes = memory_bytes;
+        self.cpu_seconds = cpu_seconds;
+        self
+    }
+
+    pub(crate) fn started(mut self, start_time: SystemTime) -> Self {
+        self.start_time = Some(start_time);
+        self
+    }
+
+    pub(crate) fn children(mut self, children: u32) -> Self {
+        self.children = children;
+        self
+    }
+
+    pub(crate) fn addr(mut self, addr: IpAddr) -> Self {
+        self.local_addr = addr;
+        self
+    }
+}
+
+/// Per-process facts that are too costly to gather for every row of the
+/// table; fetched only when a single port is inspected.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub(crate) struct ProcessDetail {
+    pub(crate) threads: Option<u32>,
+    /// Unix nice value (-20..19). Windows priority classes are mapped onto
+    /// the same scale.
+    pub(crate) nice: Option<i32>,
+    pub(crate) cwd: Option<String>,
+    /// Environment variables that hint at which project/instance this is
+    /// (see [`ENV_HINT_VARS`]). Empty when the environment can't be read.
+    pub(crate) env: Vec<(String, String)>,
+    /// Git checkout containing `cwd`; filled in by [`load_process_detail`].
+    pub(crate) repo: Option<RepoInfo>,
+    /// Zombie or abandoned owner; filled in by [`load_process_detail`].
+    pub(crate) orphan: Option<orphan::Orphan>,
+}
+
+/// Environment variables worth surfacing for a generic `node`/`python`
+/// listener, in display order.
+#[cfg(unix)]
+pub(crate) const ENV_HINT_VARS: &[&str] = &[
+    "PORT",
+    "HOST",
+    "NODE_ENV",
+    "RAILS_ENV",
+    "RACK_ENV",
+    "APP_ENV",
+    "FLASK_APP",
+    "FLASK_ENV",
+    "DJANGO_SETTINGS_MODULE",
+    "VIRTUAL_ENV",
+    "CONDA_DEFAULT_ENV",
+    "COMPOSE_PROJECT_NAME",
+];
+
+/// Pick the [`ENV_HINT_VARS`] out of a NUL-separated `KEY=value` block (the
+/// format of /proc/<pid>/environ and the macOS procargs env section).
+#[cfg(unix)]
+pub(crate) fn parse_env_hints(block: &[u8]) -> Vec<(String, String)> {
+    let mut found: Vec<(String, String)> = block
+        .split(|&b| b == 0)
+        .filter_map(|entry| {
+            let entry = String::from_utf8_lossy(entry);
+            let (key, value) = entry.split_once('=')?;
+            ENV_HINT_VARS
+                .contains(&key)
+                .then(|| (key.to_string(), value.to_string()))
+        })
+        .collect();
+    found.sort_by_key(|(k, _)| ENV_HINT_VARS.iter().position(|v| v == k));
+    found.dedup_by(|a, b| a.0 == b.0);
+    found
+}
+
+/// A single socket as the kernel sees it, including sockets with no owning
+/// process (TIME_WAIT and friends). Used for connection-level diagnostics.
+#[derive(Debug, Clone)]
+pub(crate) struct ConnInfo {
+    pub(crate) protocol: String,
+    pub(crate) local_port: u16,
+    pub(crate) remote_addr: IpAddr,
+    pub(crate) remote_port: u16,
+    pub(crate) state: TcpState,
+    pub(crate) pid: Option<u32>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
 pub(crate) enum TcpState {
     Listen,
     Established,
@@ -227,6 +912,24 @@ impl TcpState {
             TcpState::Unknown => "UNKNOWN",
         }
     }
+
+    /// Inverse of [`as_str`](Self::as_str); unrecognized names map to `Unknown`.
+    pub(crate) fn from_name(s: &str) -> Self {
+        match s {
+            "LISTEN" => TcpState::Listen,
+            "ESTABLISHED" => TcpState::Established,
+            "TIME_WAIT" => TcpState::TimeWait,
+            "CLOSE_WAIT" => TcpState::CloseWait,
+            "FIN_WAIT1" => TcpState::FinWait1,
+            "FIN_WAIT2" => TcpState::FinWait2,
+            "SYN_SENT" => TcpState::SynSent,
+            "SYN_RECV" => TcpState::SynRecv,
+            "CLOSING" => TcpState::Closing,
+            "LAST_ACK" => TcpState::LastAck,
+            "CLOSE" => TcpState::Close,
+            _ => TcpState::Unknown,
+        }
+    }
 }
 
 impl std::fmt::Display for TcpState {
@@ -235,10 +938,77 @@ impl std::fmt::Display for TcpState {
     }
 }
 
+// ── Data sources ─────────────────────────────────────────────────────
+//
+// Shorthands for the active [`collector::PortCollector`]: the platform
+// module, or a `--fixture` file standing in for it.
+
+/// Ports from the active collector, limited to the `--user` account and
+/// the `--session` shell when given.
+pub(crate) fn get_port_infos(filter_listening: bool) -> Vec<PortInfo> {
+    port_infos_from(collector::active(), filter_listening)
+}
+
+/// [`get_port_infos`] from a given backend, still honouring `--user` and
+/// `--session`.
+pub(crate) fn port_infos_from(
+    backend: &dyn collector::PortCollector,
+    filter_listening: bool,
+) -> Vec<PortInfo> {
+    let mut infos = match collector::user_filter() {
+        Some(user) => backend.user_port_infos(filter_listening, user),
+        None => backend.port_infos(filter_listening),
+    };
+    let mut session = collector::SessionCheck::default();
+    infos.retain(|i| session.admits(backend, i.pid));
+    infos
+}
+
+/// [`get_port_infos`], handed to `emit` as the collector reads them.
+fn stream_port_infos(filter_listening: bool, emit: &mut dyn FnMut(PortInfo) -> bool) {
+    let backend = collector::active();
+    let mut session = collector::SessionCheck::default();
+    backend.stream_port_infos(filter_listening, collector::user_filter(), &mut |info| {
+        !session.admits(backend, info.pid) || emit(info)
+    })
+}
+
+pub(crate) fn get_listening_ports() -> std::collections::HashSet<u16> {
+    collector::active().listening_ports()
+}
+
+pub(crate) fn get_connections() -> Vec<ConnInfo> {
+    collector::active().connections()
+}
+
+pub(crate) fn get_process_cwd(pid: u32) -> Option<String> {
+    collector::active().process_cwd(pid)
+}
+
+pub(crate) fn get_process_detail(pid: u32) -> ProcessDetail {
+    collector::active().process_detail(pid)
+}
+
 // ── Shared helpers ───────────────────────────────────────────────────
 
+/// The account name for a uid, looked up once per run: `--all` can list
+/// thousands of sockets owned by a handful of users.
 #[cfg(unix)]
 pub(crate) fn get_username(uid: u32) -> String {
+    static NAMES: OnceLock<Mutex<HashMap<u32, String>>> = OnceLock::new();
+    let names = NAMES.get_or_init(|| Mutex::new(HashMap::new()));
+    if let Some(name) = names.lock().ok().and_then(|n| n.get(&uid).cloned()) {
+        return name;
+    }
+    let name = lookup_username(uid);
+    if let Ok(mut names) = names.lock() {
+        names.insert(uid, name.clone());
+    }
+    name
+}
+
+#[cfg(unix)]
+fn lookup_username(uid: u32) -> String {
     let mut buf = vec![0u8; 1024];
     let mut pwd: libc::passwd = unsafe { std::mem::zeroed() };
     let mut result: *mut libc::passwd = std::ptr::null_mut();
@@ -259,18 +1029,119 @@ pub(crate) fn get_username(uid: u32) -> String {
     }
 }
 
-#[cfg(target_os = "linux")]
-pub(crate) fn get_clock_ticks() -> u64 {
-    unsafe { libc::sysconf(libc::_SC_CLK_TCK) as u64 }
+/// The uid for an account name, or a numeric uid as given.
+#[cfg(unix)]
+pub(crate) fn lookup_uid(name: &str) -> Option<u32> {
+    if let Ok(uid) = name.parse() {
+        return Some(uid);
+    }
+    let cname = std::ffi::CString::new(name).ok()?;
+    let mut buf = vec![0u8; 1024];
+    let mut pwd: libc::passwd = unsafe { std::mem::zeroed() };
+    let mut result: *mut libc::passwd = std::ptr::null_mut();
+    let ret = unsafe {
+        libc::getpwnam_r(
+            cname.as_ptr(),
+            &mut pwd,
+            buf.as_mut_ptr() as *mut libc::c_char,
+            buf.len(),
+            &mut result,
+        )
+    };
+    (ret == 0 && !result.is_null()).then_some(pwd.pw_uid)
 }
 
-// ── Formatting helpers ───────────────────────────────────────────────
-
-pub(crate) fn format_uptime(start: Option<SystemTime>) -> String {
-    let start = match start {
-        Some(s) => s,
-        None => return "-".to_string(),
-    };
+/// `--user` on Unix: an account name, or a uid shown as its name (or as
+/// the number, when it has no passwd entry).
+#[cfg(unix)]
+pub(crate) fn resolve_unix_user(user: &str) -> Option<String> {
+    match user.parse() {
+        Ok(uid) => Some(get_username(uid)),
+        Err(_) => lookup_uid(user).map(|_| user.to_string()),
+    }
+}
+
+/// The account portview runs as, spelled the way the USER column shows it.
+pub(crate) fn current_user() -> String {
+    #[cfg(unix)]
+    {
+        get_username(unsafe { libc::geteuid() })
+    }
+    #[cfg(windows)]
+    {
+        std::env::var("USERNAME").unwrap_or_default()
+    }
+}
+
+/// Apply `--user`/`--mine` to the collector, or exit when the account
+/// doesn't exist.
+fn install_user_filter(user: Option<&str>, mine: bool) {
+    let name = match user {
+        Some(name) => name.to_string(),
+        None if mine => current_user(),
+        None => return,
+    };
+    match collector::active().resolve_user(&name) {
+        Some(account) => collector::restrict_to_user(account),
+        // Our own account exists even when a fixture doesn't list it
+        None if user.is_none() => collector::restrict_to_user(name),
+        None => errors::fail(
+            errors::ErrorCode::NoSuchUser,
+            &format!("no such user '{}'", name),
+        ),
+    }
+}
+
+/// Apply `--session`. The shell it was typed into is portview's parent.
+fn install_session_filter(session: bool) {
+    if !session {
+        return;
+    }
+    #[cfg(unix)]
+    let shell = Some(unsafe { libc::getppid() } as u32);
+    #[cfg(windows)]
+    let shell = os::get_parent_pid(std::process::id());
+    match shell {
+        Some(shell) => collector::restrict_to_session(shell),
+        None => errors::fail(
+            errors::ErrorCode::IoError,
+            "can't find the shell portview was started from",
+        ),
+    }
+}
+
+#[cfg(target_os = "linux")]
+pub(crate) fn get_clock_ticks() -> u64 {
+    unsafe { libc::sysconf(libc::_SC_CLK_TCK) as u64 }
+}
+
+/// Parse a short human duration such as `500ms`, `30`, `30s`, `5m`, `2h`
+/// or `1d`. A bare number is taken as seconds.
+pub(crate) fn parse_duration(s: &str) -> Option<Duration> {
+    let s = s.trim();
+    let split = s.find(|c: char| !c.is_ascii_digit()).unwrap_or(s.len());
+    let (num, unit) = s.split_at(split);
+    let n: u64 = num.parse().ok()?;
+    if unit == "ms" {
+        return Some(Duration::from_millis(n));
+    }
+    let multiplier = match unit {
+        "" | "s" => 1,
+        "m" => 60,
+        "h" => 3600,
+        "d" => 86400,
+        _ => return None,
+    };
+    n.checked_mul(multiplier).map(Duration::from_secs)
+}
+
+// ── Formatting helpers ───────────────────────────────────────────────
+
+pub(crate) fn format_uptime(start: Option<SystemTime>) -> String {
+    let start = match start {
+        Some(s) => s,
+        None => return "-".to_string(),
+    };
 
     let elapsed = match SystemTime::now().duration_since(start) {
         Ok(d) => d,
@@ -293,25 +1164,47 @@ pub(crate) fn format_uptime(start: Option<SystemTime>) -> String {
     }
 }
 
-pub(crate) fn format_bytes(bytes: u64) -> String {
-    if bytes == 0 {
-        return "-".to_string();
-    }
-    const KB: u64 = 1024;
-    const MB: u64 = 1024 * KB;
-    const GB: u64 = 1024 * MB;
-
-    if bytes >= GB {
-        format!("{:.1} GB", bytes as f64 / GB as f64)
-    } else if bytes >= MB {
-        format!("{:.0} MB", bytes as f64 / MB as f64)
-    } else if bytes >= KB {
-        format!("{:.0} KB", bytes as f64 / KB as f64)
-    } else {
-        format!("{} B", bytes)
+/// The UPTIME column: uptime, or the local start time with
+/// `--timestamps absolute`.
+pub(crate) fn format_start(start: Option<SystemTime>) -> String {
+    match clock::active() {
+        clock::TimestampStyle::Relative => format_uptime(start),
+        clock::TimestampStyle::Absolute => start
+            .and_then(clock::LocalTime::from_system)
+            .map(|t| t.display())
+            .unwrap_or_else(|| "-".to_string()),
+    }
+}
+
+/// Header for the column filled by [`format_start`].
+pub(crate) fn start_header() -> &'static str {
+    match clock::active() {
+        clock::TimestampStyle::Relative => "UPTIME",
+        clock::TimestampStyle::Absolute => "STARTED",
+    }
+}
+
+/// The detail view's `Started:` value: `3h 12m ago`, or with
+/// `--timestamps absolute`, `2026-10-16 11:02:40 (3h 12m ago)`.
+pub(crate) fn format_started(start: Option<SystemTime>) -> String {
+    let uptime = format_uptime(start);
+    if uptime == "-" {
+        return uptime;
+    }
+    match clock::active() {
+        clock::TimestampStyle::Relative => format!("{} ago", uptime),
+        clock::TimestampStyle::Absolute => match start.and_then(clock::LocalTime::from_system) {
+            Some(t) => format!("{} ({} ago)", t.display(), uptime),
+            None => format!("{} ago", uptime),
+        },
     }
 }
 
+/// Memory size in the `--units` chosen for this run.
+pub(crate) fn format_bytes(bytes: u64) -> String {
+    units::active().format(bytes)
+}
+
 pub(crate) fn truncate_cmd(cmd: &str, max_len: usize) -> String {
     if cmd.len() > max_len {
         let mut end = max_len.saturating_sub(1);
@@ -357,6 +1250,27 @@ pub(crate) fn wrap_cmd(cmd: &str, width: usize) -> Vec<String> {
     lines
 }
 
+/// `width` characters of `cmd` from character `offset` on, with `…`
+/// standing in for whatever is cut off on either side.
+pub(crate) fn scroll_cmd(cmd: &str, offset: usize, width: usize) -> String {
+    let chars: Vec<char> = cmd.chars().collect();
+    let start = offset.min(chars.len());
+    let mut shown: Vec<char> = chars[start..].iter().take(width).copied().collect();
+    if start + width < chars.len() {
+        if let Some(last) = shown.last_mut() {
+            *last = '…';
+        }
+    }
+    if start > 0 {
+        match shown.first_mut() {
+            Some(first) => *first = '…',
+            None if width > 0 => shown.push('…'),
+            None => {}
+        }
+    }
+    shown.into_iter().collect()
+}
+
 pub(crate) fn format_addr(addr: &IpAddr) -> String {
     match addr {
         IpAddr::V4(v4) if v4.is_unspecified() => "*".to_string(),
@@ -381,6 +1295,8 @@ pub(crate) struct ColorConfig {
     uptime: String,
     mem: String,
     command: String,
+    /// A color name, or `auto` for a color per runtime.
+    runtime: String,
 }
 
 impl Default for ColorConfig {
@@ -394,6 +1310,7 @@ impl Default for ColorConfig {
             uptime: "dimmed".into(),
             mem: "dimmed".into(),
             command: "white".into(),
+            runtime: "auto".into(),
         }
     }
 }
@@ -410,7 +1327,8 @@ impl ColorConfig {
             if let Some((key, value)) = pair.split_once('=') {
                 let key = key.trim();
                 let value = value.trim();
-                if !is_valid_color(value) {
+                let auto_runtime = key == "runtime" && value == "auto";
+                if !is_valid_color(value) && !auto_runtime {
                     continue;
                 }
                 match key {
@@ -422,12 +1340,21 @@ impl ColorConfig {
                     "uptime" => config.uptime = value.into(),
                     "mem" => config.mem = value.into(),
                     "command" => config.command = value.into(),
+                    "runtime" => config.runtime = value.into(),
                     _ => {}
                 }
             }
         }
         config
     }
+
+    fn runtime_color(&self, runtime: Runtime) -> &str {
+        if self.runtime == "auto" {
+            runtime.color()
+        } else {
+            &self.runtime
+        }
+    }
 }
 
 fn is_valid_color(s: &str) -> bool {
@@ -513,6 +1440,8 @@ pub(crate) struct StyleConfig {
     pub(crate) uptime: ratatui::style::Style,
     pub(crate) mem: ratatui::style::Style,
     pub(crate) command: ratatui::style::Style,
+    /// `None` colors each runtime differently.
+    pub(crate) runtime: Option<ratatui::style::Style>,
 }
 
 impl StyleConfig {
@@ -526,9 +1455,15 @@ impl StyleConfig {
             uptime: color_name_to_ratatui_style(&cc.uptime),
             mem: color_name_to_ratatui_style(&cc.mem),
             command: color_name_to_ratatui_style(&cc.command),
+            runtime: (cc.runtime != "auto").then(|| color_name_to_ratatui_style(&cc.runtime)),
         }
     }
 
+    pub(crate) fn runtime_style(&self, runtime: Runtime) -> ratatui::style::Style {
+        self.runtime
+            .unwrap_or_else(|| color_name_to_ratatui_style(runtime.color()))
+    }
+
     pub(crate) fn btop_default() -> Self {
         use ratatui::style::{Color, Modifier, Style};
         Self {
@@ -542,13 +1477,14 @@ impl StyleConfig {
             uptime: Style::default().fg(Color::Rgb(100, 110, 120)),
             mem: Style::default().fg(Color::Rgb(160, 140, 200)),
             command: Style::default().fg(Color::Rgb(170, 175, 180)),
+            runtime: None,
         }
     }
 }
 
 // ── Crossterm styled write helper ────────────────────────────────────
 
-fn write_styled(w: &mut impl Write, text: &str, color_name: &str, use_color: bool) {
+pub(crate) fn write_styled(w: &mut impl Write, text: &str, color_name: &str, use_color: bool) {
     if !use_color {
         let _ = write!(w, "{}", text);
         return;
@@ -595,10 +1531,10 @@ fn measure_column_widths(infos: &[PortInfo]) -> [usize; 7] {
         .max(7);
     let uptime_w = infos
         .iter()
-        .map(|i| format_uptime(i.start_time).len())
+        .map(|i| format_start(i.start_time).len())
         .max()
         .unwrap_or(0)
-        .max(6);
+        .max(start_header().len());
     let mem_w = infos
         .iter()
         .map(|i| format_bytes(i.memory_bytes).len())
@@ -608,7 +1544,13 @@ fn measure_column_widths(infos: &[PortInfo]) -> [usize; 7] {
     [port_w, proto_w, pid_w, user_w, proc_w, uptime_w, mem_w]
 }
 
-fn write_table_border(out: &mut impl Write, widths: &[usize], left: &str, mid: &str, right: &str) {
+pub(crate) fn write_table_border(
+    out: &mut impl Write,
+    widths: &[usize],
+    left: &str,
+    mid: &str,
+    right: &str,
+) {
     let _ = write!(out, "{}", left);
     for (i, &w) in widths.iter().enumerate() {
         let _ = write!(out, "{}", "─".repeat(w + 2));
@@ -621,30 +1563,449 @@ fn write_table_border(out: &mut impl Write, widths: &[usize], left: &str, mid: &
 
 // ── Display functions ────────────────────────────────────────────────
 
+/// How the one-shot table is drawn.
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum TableLayout {
+    /// Unicode box-drawing borders.
+    Boxed,
+    /// `--plain-table`: space-aligned columns under one header line.
+    Columns,
+    /// `--plain-table --header-per-row`: `PORT: 3000  PROTO: TCP ...` lines.
+    Labeled,
+}
+
+/// `--format`: how ports are printed.
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum OutputFormat {
+    Table,
+    /// One array, the same as `--json`.
+    Json,
+    /// One object per line.
+    Ndjson,
+}
+
+impl OutputFormat {
+    fn parse(s: &str) -> Result<Self, String> {
+        match s.to_ascii_lowercase().as_str() {
+            "table" => Ok(OutputFormat::Table),
+            "json" => Ok(OutputFormat::Json),
+            "ndjson" | "jsonl" => Ok(OutputFormat::Ndjson),
+            _ => Err(format!("unknown format '{}' (table, json, ndjson)", s)),
+        }
+    }
+
+    fn is_json(self) -> bool {
+        self != OutputFormat::Table
+    }
+}
+
+/// `--sort`: the column the table is ordered by.
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum SortKey {
+    Port,
+    Proto,
+    Pid,
+    User,
+    Process,
+    Uptime,
+    Mem,
+    Command,
+}
+
+impl SortKey {
+    fn parse(s: &str) -> Result<Self, String> {
+        match s.to_ascii_lowercase().as_str() {
+            "port" => Ok(SortKey::Port),
+            "proto" | "protocol" => Ok(SortKey::Proto),
+            "pid" => Ok(SortKey::Pid),
+            "user" => Ok(SortKey::User),
+            "process" | "name" => Ok(SortKey::Process),
+            "uptime" => Ok(SortKey::Uptime),
+            "mem" | "memory" => Ok(SortKey::Mem),
+            "command" | "cmd" => Ok(SortKey::Command),
+            _ => Err(format!(
+                "unknown sort key '{}' (port, proto, pid, user, process, uptime, mem, command)",
+                s
+            )),
+        }
+    }
+
+    /// Order rows by this key, ascending unless `reverse`; ties stay in
+    /// port order. Uptime ascending puts the newest process first.
+    fn sort(self, infos: &mut [PortInfo], reverse: bool) {
+        infos.sort_by(|a, b| {
+            let ord = match self {
+                SortKey::Port => a.port.cmp(&b.port),
+                SortKey::Proto => a.protocol.cmp(&b.protocol),
+                SortKey::Pid => a.pid.cmp(&b.pid),
+                SortKey::User => a.user.cmp(&b.user),
+                SortKey::Process => a
+                    .process_name
+                    .to_lowercase()
+                    .cmp(&b.process_name.to_lowercase()),
+                SortKey::Uptime => b.start_time.cmp(&a.start_time),
+                SortKey::Mem => a.memory_bytes.cmp(&b.memory_bytes),
+                SortKey::Command => a.command.cmp(&b.command),
+            };
+            let ord = ord.then_with(|| a.port.cmp(&b.port));
+            if reverse {
+                ord.reverse()
+            } else {
+                ord
+            }
+        });
+    }
+}
+
+impl TableLayout {
+    fn from_flags(plain_table: bool, header_per_row: bool) -> Self {
+        match (plain_table, header_per_row) {
+            (false, _) => TableLayout::Boxed,
+            (true, false) => TableLayout::Columns,
+            (true, true) => TableLayout::Labeled,
+        }
+    }
+}
+
+/// The APP cell for a UDP listener that answered the QUIC probe.
+const QUIC_APP: &str = "QUIC/HTTP3";
+
+/// The APP cell for a local resolver answering DNS.
+const RESOLVER_APP: &str = "DNS resolver";
+
+/// Optional one-shot table columns, shown between MEM and COMMAND.
+#[derive(Default)]
+struct ExtraColumns {
+    /// Set when any shown port has a label in the config file.
+    labels: bool,
+    runtime: bool,
+    /// Container state per port, with `--docker` when any shown port is
+    /// published by a container.
+    health: Option<HashMap<u16, ContainerState>>,
+    /// `8080→80/tcp (web)` per published port, alongside `health`.
+    mappings: Option<HashMap<u16, String>>,
+    /// Rows that clash with a published port, by (port, PID); they get a
+    /// warning badge in the MAPPING column.
+    conflicts: HashMap<(u16, u32), PortConflict>,
+    /// Zombie or abandoned owners, when any shown row has one.
+    orphans: Option<HashMap<u32, orphan::Orphan>>,
+    /// QUIC servers by port, with `--quic`.
+    quic: HashMap<u16, Quic>,
+    /// Set when any shown row is a QUIC server, a local DNS resolver or a
+    /// well-known UDP service.
+    apps: bool,
+    cwds: Option<HashMap<u32, String>>,
+}
+
+impl ExtraColumns {
+    fn new(
+        infos: &[PortInfo],
+        runtime: bool,
+        quic: bool,
+        docker_map: Option<&DockerPortMap>,
+        cwds: Option<HashMap<u32, String>>,
+    ) -> Self {
+        let labels = infos
+            .iter()
+            .any(|i| config::active().label(i.port).is_some());
+        let health = docker_map.and_then(|map| {
+            let states: HashMap<u16, ContainerState> = infos
+                .iter()
+                .filter_map(|i| docker::port_state(map, i.port).map(|s| (i.port, s)))
+                .collect();
+            (!states.is_empty()).then_some(states)
+        });
+        let conflicts: HashMap<(u16, u32), PortConflict> = docker_map
+            .map(|map| {
+                infos
+                    .iter()
+                    .filter_map(|i| Some(((i.port, i.pid), docker::port_conflict(i, map)?)))
+                    .collect()
+            })
+            .unwrap_or_default();
+        let mappings = docker_map.and_then(|map| {
+            let cells: HashMap<u16, String> = infos
+                .iter()
+                .filter_map(|i| docker::port_mappings(map, i.port).map(|m| (i.port, m)))
+                .collect();
+            (!cells.is_empty() || !conflicts.is_empty()).then_some(cells)
+        });
+        let orphans = orphan::scan(infos);
+        let quic = if quic {
+            quic::scan(infos)
+        } else {
+            HashMap::new()
+        };
+        let apps = infos.iter().any(|i| app_of(i, &quic).is_some());
+        Self {
+            labels,
+            runtime,
+            health,
+            mappings,
+            conflicts,
+            orphans: (!orphans.is_empty()).then_some(orphans),
+            quic,
+            apps,
+            cwds,
+        }
+    }
+
+    fn headers(&self) -> Vec<&'static str> {
+        let mut headers = Vec::new();
+        if self.labels {
+            headers.push("LABEL");
+        }
+        if self.runtime {
+            headers.push("RUNTIME");
+        }
+        if self.health.is_some() {
+            headers.push("HEALTH");
+        }
+        if self.mappings.is_some() {
+            headers.push("MAPPING");
+        }
+        if self.orphans.is_some() {
+            headers.push("ORPHAN");
+        }
+        if self.apps {
+            headers.push("APP");
+        }
+        if self.cwds.is_some() {
+            headers.push("CWD");
+        }
+        headers
+    }
+
+    fn cells(&self, info: &PortInfo) -> Vec<String> {
+        let mut cells = Vec::new();
+        if self.labels {
+            cells.push(config::active().label(info.port).unwrap_or("-").to_string());
+        }
+        if self.runtime {
+            cells.push(runtime_cell(info));
+        }
+        if let Some(ref health) = self.health {
+            let state = health.get(&info.port).map_or("-", |s| s.as_str());
+            cells.push(state.to_string());
+        }
+        if let Some(ref mappings) = self.mappings {
+            cells.push(self.mapping(mappings, info));
+        }
+        if let Some(ref orphans) = self.orphans {
+            let flag = orphans.get(&info.pid).map_or("-", |o| o.label());
+            cells.push(flag.to_string());
+        }
+        if self.apps {
+            cells.push(self.app(info).unwrap_or("-").to_string());
+        }
+        if let Some(ref cwds) = self.cwds {
+            cells.push(cwd_cell(info.pid, cwds, MAX_CWD_WIDTH));
+        }
+        cells
+    }
+
+    fn widths(&self, infos: &[PortInfo]) -> Vec<usize> {
+        let mut widths = Vec::new();
+        if self.labels {
+            let longest = infos
+                .iter()
+                .filter_map(|i| config::active().label(i.port))
+                .map(|l| l.chars().count())
+                .max()
+                .unwrap_or(0);
+            widths.push(longest.max("LABEL".len()));
+        }
+        if self.runtime {
+            widths.push("RUNTIME".len());
+        }
+        if let Some(ref health) = self.health {
+            let longest = health.values().map(|s| s.as_str().len()).max();
+            widths.push(longest.unwrap_or(0).max("HEALTH".len()));
+        }
+        if let Some(ref mappings) = self.mappings {
+            let longest = infos
+                .iter()
+                .map(|i| self.mapping(mappings, i).chars().count())
+                .max();
+            widths.push(longest.unwrap_or(0).max("MAPPING".len()));
+        }
+        if self.orphans.is_some() {
+            widths.push("ORPHAN".len().max("orphaned".len()));
+        }
+        if self.apps {
+            let longest = infos.iter().filter_map(|i| self.app(i)).map(str::len).max();
+            widths.push(longest.unwrap_or(0).max("APP".len()));
+        }
+        if let Some(ref cwds) = self.cwds {
+            widths.push(measure_cwd_width(infos, cwds));
+        }
+        widths
+    }
+
+    /// The APP cell of a row.
+    fn app(&self, info: &PortInfo) -> Option<&'static str> {
+        app_of(info, &self.quic)
+    }
+
+    /// The MAPPING cell of a row, led by a badge when it conflicts.
+    fn mapping(&self, mappings: &HashMap<u16, String>, info: &PortInfo) -> String {
+        let mapping = mappings.get(&info.port);
+        match (self.conflicts.get(&(info.port, info.pid)), mapping) {
+            (Some(conflict), Some(mapping)) => format!("\u{26a0} {} {}", conflict.badge(), mapping),
+            (Some(conflict), None) => format!("\u{26a0} {}", conflict.badge()),
+            (None, mapping) => mapping.map_or("-", String::as_str).to_string(),
+        }
+    }
+
+    /// Color names per extra column; RUNTIME, HEALTH, MAPPING, ORPHAN and APP
+    /// vary by row.
+    fn color_names<'a>(&self, info: &PortInfo, colors: &'a ColorConfig) -> Vec<&'a str> {
+        let mut names = Vec::new();
+        if self.labels {
+            names.push("bold");
+        }
+        if self.runtime {
+            names.push(runtime::detect(info).map_or("dimmed", |rt| colors.runtime_color(rt)));
+        }
+        if let Some(ref health) = self.health {
+            names.push(health.get(&info.port).map_or("dimmed", |s| s.color()));
+        }
+        if let Some(ref mappings) = self.mappings {
+            names.push(if self.conflicts.contains_key(&(info.port, info.pid)) {
+                "red"
+            } else if mappings.contains_key(&info.port) {
+                "cyan"
+            } else {
+                "dimmed"
+            });
+        }
+        if let Some(ref orphans) = self.orphans {
+            names.push(orphans.get(&info.pid).map_or("dimmed", |o| o.color()));
+        }
+        if self.apps {
+            names.push(if self.app(info).is_some() {
+                "cyan"
+            } else {
+                "dimmed"
+            });
+        }
+        if self.cwds.is_some() {
+            names.push("dimmed");
+        }
+        names
+    }
+}
+
+/// What a row serves: a QUIC server, then a local resolver on its DNS
+/// port (TCP too), then a well-known UDP service by port.
+fn app_of(info: &PortInfo, quic: &HashMap<u16, Quic>) -> Option<&'static str> {
+    let udp = info.protocol.starts_with("UDP");
+    if udp && quic.contains_key(&info.port) {
+        return Some(QUIC_APP);
+    }
+    if Resolver::detect(info).is_some_and(|r| r.serves_dns(info)) {
+        return Some(RESOLVER_APP);
+    }
+    udpservice::identify(info).map(UdpService::as_str)
+}
+
+/// `node`, `python`, … or `-` when the runtime isn't recognised.
+fn runtime_cell(info: &PortInfo) -> String {
+    runtime::detect(info)
+        .map_or("-", Runtime::as_str)
+        .to_string()
+}
+
+/// Cell text for the non-command columns, in header order.
+fn row_cells(info: &PortInfo, extra: &ExtraColumns) -> Vec<String> {
+    let pid_str = if info.pid == 0 {
+        "-".to_string()
+    } else {
+        info.pid.to_string()
+    };
+    let mut cells = vec![
+        info.port.to_string(),
+        info.protocol.clone(),
+        pid_str,
+        info.user.clone(),
+        info.process_name.clone(),
+        format_start(info.start_time),
+        format_bytes(info.memory_bytes),
+    ];
+    cells.extend(extra.cells(info));
+    cells
+}
+
 fn display_table(
     infos: &[PortInfo],
+    extra: &ExtraColumns,
     use_color: bool,
     colors: &ColorConfig,
     wide: bool,
     cmd_width: usize,
+    layout: TableLayout,
 ) {
     if infos.is_empty() {
         let mut out = io::stdout();
-        write_styled(&mut out, "No listening ports found.\n", "dimmed", use_color);
+        match collector::user_filter() {
+            Some(user) => write_styled(
+                &mut out,
+                &format!("No listening ports owned by {}.\n", user),
+                "dimmed",
+                use_color,
+            ),
+            None if collector::session_filter().is_some() => write_styled(
+                &mut out,
+                "No listening ports opened from this shell.\n",
+                "dimmed",
+                use_color,
+            ),
+            None => write_styled(&mut out, "No listening ports found.\n", "dimmed", use_color),
+        }
+        if collector::active().capabilities().needs_privileges {
+            write_styled(
+                &mut out,
+                "Other users' ports may be hidden; try sudo, or run `portview doctor`.\n",
+                "dimmed",
+                use_color,
+            );
+        }
+        return;
+    }
+
+    if layout != TableLayout::Boxed {
+        display_plain_table(infos, extra, use_color, colors, layout);
         return;
     }
 
     let mut out = io::stdout();
 
-    let col_widths = measure_column_widths(infos);
     let actual_cmd_w = cmd_width.max(7);
 
-    let mut widths = [0usize; 8];
-    widths[..7].copy_from_slice(&col_widths);
-    widths[7] = actual_cmd_w;
-    let headers = [
-        "PORT", "PROTO", "PID", "USER", "PROCESS", "UPTIME", "MEM", "COMMAND",
+    let mut widths: Vec<usize> = measure_column_widths(infos).to_vec();
+    let mut headers = vec![
+        "PORT",
+        "PROTO",
+        "PID",
+        "USER",
+        "PROCESS",
+        start_header(),
+        "MEM",
+    ];
+    let base_colors = vec![
+        colors.port.as_str(),
+        colors.proto.as_str(),
+        colors.pid.as_str(),
+        colors.user.as_str(),
+        colors.process.as_str(),
+        colors.uptime.as_str(),
+        colors.mem.as_str(),
     ];
+    widths.extend(extra.widths(infos));
+    headers.extend(extra.headers());
+    let base_cols = widths.len();
+    widths.push(actual_cmd_w);
+    headers.push("COMMAND");
 
     // Top border
     write_table_border(&mut out, &widths, "╭", "┬", "╮");
@@ -668,34 +2029,11 @@ fn display_table(
     write_table_border(&mut out, &widths, "├", "┼", "┤");
 
     // Data rows
-    let color_names = [
-        &colors.port,
-        &colors.proto,
-        &colors.pid,
-        &colors.user,
-        &colors.process,
-        &colors.uptime,
-        &colors.mem,
-        &colors.command,
-    ];
-
     for info in infos {
-        let uptime_str = format_uptime(info.start_time);
-        let mem_str = format_bytes(info.memory_bytes);
-        let pid_str = if info.pid == 0 {
-            "-".to_string()
-        } else {
-            info.pid.to_string()
-        };
-        let base_values = [
-            info.port.to_string(),
-            info.protocol.clone(),
-            pid_str,
-            info.user.clone(),
-            info.process_name.clone(),
-            uptime_str,
-            mem_str,
-        ];
+        let base_values = row_cells(info, extra);
+        let mut color_names = base_colors.clone();
+        color_names.extend(extra.color_names(info, colors));
+        color_names.push(colors.command.as_str());
 
         let cmd_lines = if wide {
             wrap_cmd(&info.command, actual_cmd_w)
@@ -706,7 +2044,12 @@ fn display_table(
         for (line_idx, cmd_line) in cmd_lines.iter().enumerate() {
             let _ = write!(out, "│");
 
-            for (i, (&w, val)) in widths.iter().take(7).zip(base_values.iter()).enumerate() {
+            for (i, (&w, val)) in widths
+                .iter()
+                .take(base_cols)
+                .zip(base_values.iter())
+                .enumerate()
+            {
                 let _ = write!(out, " ");
                 let current = if line_idx == 0 { val.as_str() } else { "" };
                 // Right-align UPTIME (5) and MEM (6) columns
@@ -721,7 +2064,7 @@ fn display_table(
 
             let _ = write!(out, " ");
             let padded_cmd = format!("{:<width$}", cmd_line, width = actual_cmd_w);
-            write_styled(&mut out, &padded_cmd, color_names[7], use_color);
+            write_styled(&mut out, &padded_cmd, color_names[base_cols], use_color);
             let _ = writeln!(out, " │");
         }
     }
@@ -730,155 +2073,639 @@ fn display_table(
     write_table_border(&mut out, &widths, "╰", "┴", "╯");
 }
 
-fn display_detail(info: &PortInfo, use_color: bool) {
+/// The table without box-drawing characters, for screen readers, diff
+/// tools and terminals with poor Unicode support. Commands are printed in
+/// full, and lines carry no trailing padding.
+fn display_plain_table(
+    infos: &[PortInfo],
+    extra: &ExtraColumns,
+    use_color: bool,
+    colors: &ColorConfig,
+    layout: TableLayout,
+) {
     let mut out = io::stdout();
-    let bind_str = format!("{}:{}", format_addr(&info.local_addr), info.port);
-    let uptime = format_uptime(info.start_time);
-    let is_docker = info.pid == 0;
-
-    let _ = writeln!(out);
-    if use_color {
-        write_styled(&mut out, "Port", "bold", true);
-        let _ = write!(out, " ");
-        write_styled(&mut out, &info.port.to_string(), "cyan", true);
-        let _ = write!(out, " ");
-        write_styled(&mut out, &format!("({})", info.protocol), "dimmed", true);
-        let _ = write!(out, " ");
-        write_styled(&mut out, "—", "dimmed", true);
-        let _ = write!(out, " ");
-        write_styled(&mut out, &info.process_name, "green", true);
-        if is_docker {
-            let _ = write!(out, " ");
-            write_styled(&mut out, "[container]", "cyan", true);
-        } else {
-            let _ = write!(out, " ");
-            write_styled(&mut out, &format!("(PID {})", info.pid), "yellow", true);
-        }
+    let mut widths: Vec<usize> = measure_column_widths(infos).to_vec();
+    let mut headers = vec![
+        "PORT",
+        "PROTO",
+        "PID",
+        "USER",
+        "PROCESS",
+        start_header(),
+        "MEM",
+    ];
+    let base_colors = vec![
+        colors.port.as_str(),
+        colors.proto.as_str(),
+        colors.pid.as_str(),
+        colors.user.as_str(),
+        colors.process.as_str(),
+        colors.uptime.as_str(),
+        colors.mem.as_str(),
+    ];
+    widths.extend(extra.widths(infos));
+    headers.extend(extra.headers());
+    headers.push("COMMAND");
+
+    if layout == TableLayout::Columns {
+        let header_cells: Vec<String> = headers.iter().map(|h| h.to_string()).collect();
+        let line = plain_table_line(&header_cells, &widths);
+        write_styled(&mut out, &line, "bold", use_color);
         let _ = writeln!(out);
-    } else if is_docker {
-        let _ = writeln!(
-            out,
-            "Port {} ({}) — {} [container]",
-            info.port, info.protocol, info.process_name,
-        );
-    } else {
-        let _ = writeln!(
-            out,
-            "Port {} ({}) — {} (PID {})",
-            info.port, info.protocol, info.process_name, info.pid,
+    }
+
+    for info in infos {
+        let mut cells = row_cells(info, extra);
+        cells.push(info.command.clone());
+        let mut color_names = base_colors.clone();
+        color_names.extend(extra.color_names(info, colors));
+        color_names.push(colors.command.as_str());
+        write_plain_row(
+            &mut out,
+            &headers,
+            &cells,
+            &widths,
+            &color_names,
+            layout,
+            use_color,
         );
     }
+}
 
-    if is_docker {
-        let rows: &[(&str, String)] = &[
-            ("Bind:", bind_str),
-            ("Image:", info.command.clone()),
-            ("State:", info.state.to_string()),
-        ];
-        for (label, value) in rows {
-            if use_color {
+/// One `--plain-table` row, labelled with `headers` in the
+/// [`TableLayout::Labeled`] layout.
+fn write_plain_row(
+    out: &mut impl Write,
+    headers: &[&str],
+    cells: &[String],
+    widths: &[usize],
+    color_names: &[&str],
+    layout: TableLayout,
+    use_color: bool,
+) {
+    if layout == TableLayout::Labeled {
+        for (i, (header, cell)) in headers.iter().zip(cells).enumerate() {
+            if i > 0 {
                 let _ = write!(out, "  ");
-                write_styled(&mut out, label, "dimmed", true);
-                let _ = writeln!(out, "  {}", value);
-            } else {
-                let _ = writeln!(out, "  {:<9} {}", label, value);
             }
+            let _ = write!(out, "{}: ", header);
+            write_styled(out, cell, color_names[i], use_color);
         }
-    } else {
-        let rows: &[(&str, String)] = &[
-            ("Bind:", bind_str),
-            ("Command:", info.command.clone()),
-            ("User:", info.user.clone()),
-            (
-                "Started:",
-                if use_color {
-                    uptime.clone()
-                } else {
-                    format!("{} ago", uptime)
-                },
-            ),
-            ("Memory:", format_bytes(info.memory_bytes)),
-            ("CPU time:", format!("{:.1}s", info.cpu_seconds)),
-            ("Children:", info.children.to_string()),
-            ("State:", info.state.to_string()),
-        ];
-
-        for (label, value) in rows {
-            if use_color {
+        let _ = writeln!(out);
+    } else if use_color {
+        for (i, cell) in cells.iter().enumerate() {
+            if i > 0 {
                 let _ = write!(out, "  ");
-                write_styled(&mut out, label, "dimmed", true);
-                let _ = writeln!(out, "  {}", value);
-            } else {
-                let _ = writeln!(out, "  {:<9} {}", label, value);
             }
+            let padded = pad_plain_cell(cell, i, widths.get(i).copied(), i + 1 == cells.len());
+            write_styled(out, &padded, color_names[i], true);
         }
+        let _ = writeln!(out);
+    } else {
+        let _ = writeln!(out, "{}", plain_table_line(cells, widths));
     }
 }
 
-fn display_docker_context(port: u16, docker_map: &DockerPortMap, use_color: bool) {
+// ── Streaming ────────────────────────────────────────────────────────
+
+/// `--stream` widths for PORT through UPTIME/STARTED and MEM. Rows are
+/// printed before the rest are read, so nothing can be measured; a longer
+/// value pushes the rest of its line out.
+fn stream_widths() -> Vec<usize> {
+    let started = format_start(Some(SystemTime::now())).chars().count();
+    vec![5, 5, 7, 12, 15, started.max(8), 7]
+}
+
+/// `--stream`: rows in the order the collector reads them, flushed one at
+/// a time, as fixed-width columns or NDJSON. Stops at the first failed
+/// write, such as a closed pipe.
+fn display_stream(config: &RunConfig, use_color: bool, colors: &ColorConfig) -> io::Result<()> {
+    let mut out = io::stdout().lock();
+    let headers = [
+        "PORT",
+        "PROTO",
+        "PID",
+        "USER",
+        "PROCESS",
+        start_header(),
+        "MEM",
+        "COMMAND",
+    ];
+    let color_names = [
+        colors.port.as_str(),
+        colors.proto.as_str(),
+        colors.pid.as_str(),
+        colors.user.as_str(),
+        colors.process.as_str(),
+        colors.uptime.as_str(),
+        colors.mem.as_str(),
+        colors.command.as_str(),
+    ];
+    let widths = stream_widths();
+    let layout = match config.layout {
+        TableLayout::Labeled => TableLayout::Labeled,
+        _ => TableLayout::Columns,
+    };
+    if !config.json && layout == TableLayout::Columns {
+        let header: Vec<String> = headers.iter().map(|h| h.to_string()).collect();
+        write_styled(
+            &mut out,
+            &plain_table_line(&header, &widths),
+            "bold",
+            use_color,
+        );
+        writeln!(out)?;
+        out.flush()?;
+    }
+
+    let target = config.target.as_deref().filter(|t| *t != "scan");
+    let mut result = Ok(());
+    stream_port_infos(!config.all, &mut |info| {
+        if target.is_some_and(|t| !matches_target(&info, t)) {
+            return true;
+        }
+        let mut line = Vec::new();
+        if config.json {
+            line.extend(port_info_json(&info, None, None).as_bytes());
+            line.push(b'\n');
+        } else {
+            let mut cells = row_cells(&info, &ExtraColumns::default());
+            cells.push(info.command);
+            write_plain_row(
+                &mut line,
+                &headers,
+                &cells,
+                &widths,
+                &color_names,
+                layout,
+                use_color,
+            );
+        }
+        result = out.write_all(&line).and_then(|_| out.flush());
+        result.is_ok()
+    });
+    result
+}
+
+/// One `--plain-table` line: cells joined by two spaces, padded to `widths`
+/// except for the last.
+fn plain_table_line(cells: &[String], widths: &[usize]) -> String {
+    cells
+        .iter()
+        .enumerate()
+        .map(|(i, cell)| pad_plain_cell(cell, i, widths.get(i).copied(), i + 1 == cells.len()))
+        .collect::<Vec<_>>()
+        .join("  ")
+}
+
+fn pad_plain_cell(cell: &str, col: usize, width: Option<usize>, last: bool) -> String {
+    match width {
+        _ if last => cell.to_string(),
+        // UPTIME and MEM are right-aligned, as in the boxed table
+        Some(w) if col == 5 || col == 6 => format!("{:>w$}", cell, w = w),
+        Some(w) => format!("{:<w$}", cell, w = w),
+        None => cell.to_string(),
+    }
+}
+
+// ── Working directories ──────────────────────────────────────────────
+
+/// Longest CWD shown in the one-shot table; longer paths keep their tail.
+const MAX_CWD_WIDTH: usize = 32;
+
+/// Working directory for every distinct PID in `infos` that we can read.
+pub(crate) fn collect_cwds(infos: &[PortInfo]) -> HashMap<u32, String> {
+    let mut cwds = HashMap::new();
+    for info in infos {
+        if info.pid == 0 || cwds.contains_key(&info.pid) {
+            continue;
+        }
+        if let Some(cwd) = get_process_cwd(info.pid) {
+            cwds.insert(info.pid, cwd);
+        }
+    }
+    cwds
+}
+
+/// Abbreviate `home` to `~` and keep the last `max` characters, since the
+/// project folder is at the end of the path.
+pub(crate) fn shorten_path(path: &str, home: Option<&str>, max: usize) -> String {
+    let path = match home {
+        Some(h) if !h.is_empty() && h != "/" && path == h => "~".to_string(),
+        Some(h) if !h.is_empty() && h != "/" && path.starts_with(&format!("{}/", h)) => {
+            format!("~{}", &path[h.len()..])
+        }
+        _ => path.to_string(),
+    };
+    let len = path.chars().count();
+    if len <= max || max < 2 {
+        return path;
+    }
+    let tail: String = path.chars().skip(len - (max - 1)).collect();
+    // Prefer cutting at a directory boundary
+    let tail = match tail.find('/') {
+        Some(i) if i > 0 => &tail[i..],
+        _ => &tail,
+    };
+    format!("…{}", tail)
+}
+
+/// Table cell for a PID's working directory: shortened to `max` characters,
+/// or `-` if unknown.
+pub(crate) fn cwd_cell(pid: u32, cwds: &HashMap<u32, String>, max: usize) -> String {
+    match cwds.get(&pid) {
+        Some(cwd) => shorten_path(cwd, std::env::var("HOME").ok().as_deref(), max),
+        None => "-".to_string(),
+    }
+}
+
+fn measure_cwd_width(infos: &[PortInfo], cwds: &HashMap<u32, String>) -> usize {
+    infos
+        .iter()
+        .map(|i| cwd_cell(i.pid, cwds, MAX_CWD_WIDTH).chars().count())
+        .max()
+        .unwrap_or(0)
+        .max(3)
+}
+
+fn display_detail(info: &PortInfo, detail: Option<&ProcessDetail>, use_color: bool) {
+    let mut out = io::stdout();
+    let bind_str = format!("{}:{}", format_addr(&info.local_addr), info.port);
+    let is_docker = info.pid == 0;
+
+    let _ = writeln!(out);
+    if use_color {
+        write_styled(&mut out, "Port", "bold", true);
+        let _ = write!(out, " ");
+        write_styled(&mut out, &info.port.to_string(), "cyan", true);
+        let _ = write!(out, " ");
+        write_styled(&mut out, &format!("({})", info.protocol), "dimmed", true);
+        let _ = write!(out, " ");
+        write_styled(&mut out, "—", "dimmed", true);
+        let _ = write!(out, " ");
+        write_styled(&mut out, &info.process_name, "green", true);
+        if is_docker {
+            let _ = write!(out, " ");
+            write_styled(&mut out, "[container]", "cyan", true);
+        } else {
+            let _ = write!(out, " ");
+            write_styled(&mut out, &format!("(PID {})", info.pid), "yellow", true);
+        }
+        let _ = writeln!(out);
+    } else if is_docker {
+        let _ = writeln!(
+            out,
+            "Port {} ({}) — {} [container]",
+            info.port, info.protocol, info.process_name,
+        );
+    } else {
+        let _ = writeln!(
+            out,
+            "Port {} ({}) — {} (PID {})",
+            info.port, info.protocol, info.process_name, info.pid,
+        );
+    }
+
+    let port_label = config::active()
+        .label(info.port)
+        .map(|l| ("Label:", l.to_string()));
+    if is_docker {
+        let mut rows: Vec<(&str, String)> = vec![
+            ("Bind:", bind_str),
+            ("Image:", info.command.clone()),
+            ("State:", info.state.to_string()),
+        ];
+        rows.splice(0..0, port_label);
+        rows.extend(resolver::detail_rows(info));
+        for (label, value) in &rows {
+            if use_color {
+                let _ = write!(out, "  ");
+                write_styled(&mut out, label, "dimmed", true);
+                let _ = writeln!(out, "  {}", value);
+            } else {
+                let _ = writeln!(out, "  {:<9} {}", label, value);
+            }
+        }
+    } else {
+        let mut rows: Vec<(&str, String)> = vec![
+            ("Bind:", bind_str),
+            ("Command:", info.command.clone()),
+            ("User:", info.user.clone()),
+            ("Started:", format_started(info.start_time)),
+            ("Memory:", format_bytes(info.memory_bytes)),
+            ("CPU time:", format!("{:.1}s", info.cpu_seconds)),
+            ("Children:", info.children.to_string()),
+            ("State:", info.state.to_string()),
+        ];
+        if let Some(rt) = runtime::detect(info) {
+            rows.insert(2, ("Runtime:", rt.as_str().to_string()));
+        }
+        rows.splice(0..0, port_label);
+        rows.extend(backlog::detail_row(info).map(|b| ("Backlog:", b)));
+        rows.extend(ephemeral::detail_row(info).map(|e| ("Ephemeral:", e)));
+        rows.extend(sockopts::lookup(info).map_or_else(Vec::new, |o| o.rows()));
+        rows.extend(udpservice::detail_rows(info));
+        rows.extend(resolver::detail_rows(info));
+        if let Some(detail) = detail {
+            rows.extend(detail_rows(detail));
+        }
+
+        for (label, value) in &rows {
+            if use_color {
+                let _ = write!(out, "  ");
+                write_styled(&mut out, label, "dimmed", true);
+                let _ = writeln!(out, "  {}", value);
+            } else {
+                let _ = writeln!(out, "  {:<9} {}", label, value);
+            }
+        }
+    }
+}
+
+/// Whether `info` matches a CLI target: a port number, a case-insensitive
+/// substring of the process name or command, or (unless `--exact`) a fuzzy
+/// match on the process or executable name.
+pub(crate) fn matches_target(info: &PortInfo, target: &str) -> bool {
+    if let Ok(port) = target.parse::<u16>() {
+        return info.port == port;
+    }
+    contains_target(info, target) || fuzzy_score(info, target).is_some()
+}
+
+fn contains_target(info: &PortInfo, target: &str) -> bool {
+    let t = target.to_lowercase();
+    info.process_name.to_lowercase().contains(&t) || info.command.to_lowercase().contains(&t)
+}
+
+/// The better fuzzy score of the process name and the executable's file
+/// name; `None` under `--exact`. Commands aren't fuzzy-matched, since a
+/// long command line contains almost any short subsequence.
+fn fuzzy_score(info: &PortInfo, target: &str) -> Option<u32> {
+    if !fuzzy::enabled() {
+        return None;
+    }
+    let exe = info.command.split_whitespace().next().unwrap_or("");
+    let exe = exe.rsplit(['/', '\\']).next().unwrap_or(exe);
+    fuzzy::score(target, &info.process_name).max(fuzzy::score(target, exe))
+}
+
+/// Order name-search results best first and describe the fuzzy ones:
+/// `postgres 124, pgbouncer 38`. Substring matches keep their order ahead
+/// of them. `None` when every match was a substring match.
+fn rank_fuzzy_matches(matches: &mut [PortInfo], target: &str) -> Option<String> {
+    let rank = |info: &PortInfo| {
+        if contains_target(info, target) {
+            None
+        } else {
+            Some(std::cmp::Reverse(fuzzy_score(info, target).unwrap_or(0)))
+        }
+    };
+    matches.sort_by_key(|info| rank(info));
+    let mut scored: Vec<String> = Vec::new();
+    for info in matches.iter() {
+        let Some(score) = (!contains_target(info, target))
+            .then(|| fuzzy_score(info, target))
+            .flatten()
+        else {
+            continue;
+        };
+        let entry = format!("{} {}", info.process_name, score);
+        if !scored.contains(&entry) {
+            scored.push(entry);
+        }
+    }
+    (!scored.is_empty()).then(|| scored.join(", "))
+}
+
+/// Platform process details plus the git checkout found above its cwd.
+pub(crate) fn load_process_detail(pid: u32) -> ProcessDetail {
+    let mut detail = get_process_detail(pid);
+    detail.repo = detail
+        .cwd
+        .as_deref()
+        .and_then(|cwd| project::find_repo(Path::new(cwd)));
+    detail.orphan = orphan::detect(pid);
+    detail
+}
+
+/// Label/value rows for the optional process details, skipping unknowns.
+pub(crate) fn detail_rows(detail: &ProcessDetail) -> Vec<(&'static str, String)> {
+    let mut rows = Vec::new();
+    if let Some(threads) = detail.threads {
+        rows.push(("Threads:", threads.to_string()));
+    }
+    if let Some(nice) = detail.nice {
+        rows.push(("Nice:", nice.to_string()));
+    }
+    if let Some(ref cwd) = detail.cwd {
+        rows.push(("CWD:", cwd.clone()));
+    }
+    if let Some(ref repo) = detail.repo {
+        rows.push(("Repo:", project::format_repo(repo)));
+    }
+    if !detail.env.is_empty() {
+        let vars: Vec<String> = detail
+            .env
+            .iter()
+            .map(|(k, v)| format!("{}={}", k, v))
+            .collect();
+        rows.push(("Env:", vars.join(" ")));
+    }
+    if let Some(orphan) = detail.orphan {
+        rows.push(("Orphan:", orphan.explain()));
+        rows.push(("Fix:", orphan.remedy()));
+    }
+    rows
+}
+
+fn display_docker_context(info: &PortInfo, docker_map: &DockerPortMap, use_color: bool) {
+    let port = info.port;
+    let mut out = io::stdout();
+    for (label, value) in docker::conflict_rows(info, docker_map) {
+        if use_color {
+            let _ = write!(out, "  ");
+            let color = if label == "Conflict:" {
+                "red"
+            } else {
+                "dimmed"
+            };
+            write_styled(&mut out, label, color, true);
+            let _ = writeln!(out, "  {}", value);
+        } else {
+            let _ = writeln!(out, "  {:<9} {}", label, value);
+        }
+    }
     let Some(owners) = docker_map.get(&port) else {
         return;
     };
+    let mut ids: Vec<&str> = owners.iter().map(|o| o.container_id.as_str()).collect();
+    ids.dedup();
+    let networks = docker::container_networks(&ids);
 
-    let mut out = io::stdout();
     if use_color {
         let _ = write!(out, "  ");
         write_styled(&mut out, "Docker:", "dimmed", true);
         let _ = writeln!(out);
         for owner in owners {
+            let state = owner.container_state();
             let _ = write!(out, "    ");
             write_styled(&mut out, &owner.container_name, "green", true);
             let _ = write!(
                 out,
-                " ({}) [{}] -> {} {}/{}",
+                " ({}) [{}] -> {} {}/{} ",
                 short_container_id(&owner.container_id),
                 owner.image,
                 port,
                 owner.container_port,
                 owner.protocol
             );
+            write_styled(&mut out, state.as_str(), state.color(), true);
+            if let Some(usage) = container_usage(owner) {
+                let _ = write!(out, " ");
+                write_styled(&mut out, &usage, "dimmed", true);
+            }
             let _ = writeln!(out);
+            write_container_networks(&mut out, &networks, owner, true);
         }
     } else {
         let _ = writeln!(out, "  Docker:");
         for owner in owners {
-            let _ = writeln!(
+            let _ = write!(
                 out,
-                "    {} ({}) [{}] -> {} {}/{}",
+                "    {} ({}) [{}] -> {} {}/{} {}",
                 owner.container_name,
                 short_container_id(&owner.container_id),
                 owner.image,
                 port,
                 owner.container_port,
-                owner.protocol
+                owner.protocol,
+                owner.container_state().as_str()
             );
+            if let Some(usage) = container_usage(owner) {
+                let _ = write!(out, " {}", usage);
+            }
+            let _ = writeln!(out);
+            write_container_networks(&mut out, &networks, owner, false);
         }
     }
 }
 
-fn docker_brief_tag(port: u16, docker_map: &DockerPortMap) -> Option<String> {
-    let owners = docker_map.get(&port)?;
-    let first = owners.first()?;
-    if owners.len() == 1 {
-        Some(first.container_name.clone())
+/// `network: myapp_default 172.18.0.3:80` lines under a container, so
+/// its address on each network sits next to the published port.
+fn write_container_networks(
+    out: &mut impl Write,
+    networks: &HashMap<String, Vec<ContainerNetwork>>,
+    owner: &DockerPortOwner,
+    use_color: bool,
+) {
+    for network in networks.get(&owner.container_id).into_iter().flatten() {
+        let _ = write!(out, "      ");
+        write_styled(out, "network:", "dimmed", use_color);
+        let _ = writeln!(out, " {}", network.describe(owner.container_port));
+    }
+}
+
+/// `· 1.2% CPU · 48 MB` from `docker stats`, when sampled.
+pub(crate) fn container_usage(owner: &DockerPortOwner) -> Option<String> {
+    owner.stats.map(|s| {
+        format!(
+            "\u{b7} {:.1}% CPU \u{b7} {}",
+            s.cpu_percent,
+            format_bytes(s.memory_bytes)
+        )
+    })
+}
+
+fn display_peers(peers: &[PeerSummary], use_color: bool) {
+    let mut out = io::stdout();
+    let total: usize = peers.iter().map(|p| p.count).sum();
+    let summary = if peers.is_empty() {
+        "none".to_string()
+    } else {
+        format!(
+            "{} host{}, {} connection{}",
+            peers.len(),
+            if peers.len() == 1 { "" } else { "s" },
+            total,
+            if total == 1 { "" } else { "s" }
+        )
+    };
+    if use_color {
+        let _ = write!(out, "  ");
+        write_styled(&mut out, "Peers:", "dimmed", true);
+        let _ = writeln!(out, "  {}", summary);
     } else {
-        Some(format!("{}+{}", first.container_name, owners.len() - 1))
+        let _ = writeln!(out, "  {:<9} {}", "Peers:", summary);
+    }
+    for peer in peers {
+        let _ = writeln!(out, "    {}", peers::format_peer(peer));
     }
 }
 
-fn annotate_infos_with_docker(infos: &mut [PortInfo], docker_map: &DockerPortMap) {
-    for info in infos {
-        if info.pid == 0 {
-            continue;
-        }
-        let Some(tag) = docker_brief_tag(info.port, docker_map) else {
+/// Without `--docker`, tag rows held by `docker-proxy` or a container VM
+/// with the container they forward to. Returns whether there were any.
+fn annotate_forwarded_ports(infos: &mut [PortInfo]) -> bool {
+    let forwarded = docker::forwarded_ports(infos);
+    for info in infos.iter_mut() {
+        let Some(fwd) = forwarded.get(&(info.port, info.pid)) else {
             continue;
         };
-        if info.command.contains("[docker:") {
-            continue;
+        if !info.command.contains("[docker:") {
+            info.command = format!("{} [{}]", info.command, fwd.tag());
         }
-        info.command = format!("{} [docker:{}]", info.command, tag);
     }
+    !forwarded.is_empty()
+}
+
+/// Flag listeners whose accept queue is full or was clamped by
+/// net.core.somaxconn, below the scan table.
+fn print_backlog_warnings(use_color: bool) {
+    let backend = collector::active();
+    let queues = backend.listen_queues();
+    let somaxconn = backend.somaxconn();
+    let list = |ports: Vec<u16>| {
+        ports
+            .iter()
+            .map(u16::to_string)
+            .collect::<Vec<_>>()
+            .join(", ")
+    };
+    let mut out = io::stdout();
+    let full = backlog::flagged(&queues, somaxconn, backlog::BacklogIssue::Full);
+    if !full.is_empty() {
+        write_styled(
+            &mut out,
+            &format!(
+                "  Backlog: accept queue full on {}; new connections are being dropped\n",
+                list(full)
+            ),
+            "red",
+            use_color,
+        );
+    }
+    let capped = backlog::flagged(&queues, somaxconn, backlog::BacklogIssue::Capped);
+    if let (false, Some(limit)) = (capped.is_empty(), somaxconn) {
+        write_styled(
+            &mut out,
+            &format!(
+                "  Backlog: {} capped at net.core.somaxconn ({}); raise it if these servers drop connections under load\n",
+                list(capped),
+                limit
+            ),
+            "yellow",
+            use_color,
+        );
+    }
+}
+
+fn print_forwarded_hint(use_color: bool) {
+    let mut out = io::stdout();
+    write_styled(
+        &mut out,
+        "  Docker:  some ports are forwarded to containers; add --docker for their state, health and actions\n",
+        "dimmed",
+        use_color,
+    );
 }
 
 /// Create synthetic PortInfo entries for Docker-published ports that have no
@@ -910,7 +2737,7 @@ pub(crate) fn synthesize_docker_entries(
                 command,
                 user: "docker".to_string(),
                 state: TcpState::Listen,
-                memory_bytes: 0,
+                memory_bytes: owner.stats.map_or(0, |s| s.memory_bytes),
                 cpu_seconds: 0.0,
                 start_time: None,
                 children: 0,
@@ -933,9 +2760,48 @@ pub(crate) fn synthesize_docker_entries(
     synthetic
 }
 
-fn prompt_kill(pid: u32, force: bool) -> bool {
-    print!("\n  Kill process {}? [y/N] ", pid);
-    if io::stdout().flush().is_err() {
+/// `up 3d 4h · user postgres · 40 MB`, so a kill confirmation shows what's
+/// about to go away.
+pub(crate) fn kill_summary(info: &PortInfo) -> String {
+    let mut parts = Vec::new();
+    if info.start_time.is_some() {
+        parts.push(format!("up {}", format_uptime(info.start_time)));
+    }
+    if !info.user.is_empty() {
+        parts.push(format!("user {}", info.user));
+    }
+    parts.push(format_bytes(info.memory_bytes));
+    parts.join(" · ")
+}
+
+/// Killing a parent doesn't take its children with it.
+pub(crate) fn orphan_warning(children: u32) -> Option<String> {
+    (children > 0).then(|| {
+        format!(
+            "{} child process{} will be orphaned",
+            children,
+            if children == 1 { "" } else { "es" }
+        )
+    })
+}
+
+fn prompt_kill(info: &PortInfo, force: bool, use_color: bool) -> bool {
+    let mut out = io::stdout();
+    let _ = write!(out, "\n  Kill {} (PID {})? ", info.process_name, info.pid);
+    write_styled(&mut out, &kill_summary(info), "dimmed", use_color);
+    let _ = writeln!(out);
+    if let Some(warning) = orphan_warning(info.children) {
+        let _ = write!(out, "  ");
+        write_styled(&mut out, &format!("! {}", warning), "yellow", use_color);
+        let _ = writeln!(out);
+    }
+    let type_back = confirm::active().type_back(force, &info.user, &current_user());
+    if let Some(type_back) = type_back {
+        let _ = write!(out, "  Type {} to force-kill it: ", type_back.what());
+    } else {
+        let _ = write!(out, "  [y/N] ");
+    }
+    if out.flush().is_err() {
         return false;
     }
 
@@ -944,15 +2810,118 @@ fn prompt_kill(pid: u32, force: bool) -> bool {
         return false;
     }
 
-    if input.trim().eq_ignore_ascii_case("y") {
-        do_kill(pid, force);
+    let confirmed = if let Some(type_back) = type_back {
+        type_back.matches(&input, info.pid, info.port)
+    } else {
+        input.trim().eq_ignore_ascii_case("y")
+    };
+    if confirmed {
+        let restart = RestartCommand::capture(info);
+        let signal = Signal::for_force(force);
+        let target =
+            batch::Target::holding(info.pid, &info.process_name, std::slice::from_ref(info));
+        if do_kill(&target, signal) {
+            report_verdicts(&[(info.pid, vec![info.port])], signal, use_color);
+            if let Some(ref restart) = restart {
+                print_restart_hint(restart, use_color);
+            }
+        }
         return true;
     }
     false
 }
 
+/// Wait for killed processes to go and say how each kill turned out:
+/// `✓ PID 4242 exited; port 3000 is free`, or a warning when it didn't.
+fn report_verdicts(
+    targets: &[(u32, Vec<u16>)],
+    signal: Signal,
+    use_color: bool,
+) -> Vec<verify::Verdict> {
+    let verdicts = verify::wait(targets);
+    let mut out = io::stdout();
+    for ((pid, ports), verdict) in targets.iter().zip(&verdicts) {
+        if verdict.settled() {
+            write_styled(&mut out, "  ✓", "green", use_color);
+        } else {
+            write_styled(&mut out, "  !", "yellow", use_color);
+        }
+        let _ = writeln!(out, " {}", verdict.describe(*pid, ports, signal));
+    }
+    verdicts
+}
+
+/// `kill --force` under `--confirm-force`: ask for the PID on stdin,
+/// refusing outright when there's no terminal to type it at. The prompt
+/// goes to stderr so it stays out of `--json` output.
+fn confirm_force_kill(pid: u32) -> bool {
+    if !atty_stdin() {
+        errors::fail(
+            errors::ErrorCode::InvalidArgument,
+            "--confirm-force needs the PID typed at a terminal; run interactively or drop the setting",
+        );
+    }
+    let mut out = io::stderr();
+    let _ = write!(out, "  Type {} to force-kill it: ", pid);
+    let _ = out.flush();
+    let mut input = String::new();
+    let confirmed = io::stdin().read_line(&mut input).is_ok() && confirm::pid_matches(&input, pid);
+    if !confirmed {
+        let _ = writeln!(out, "  Skipped PID {}", pid);
+    }
+    confirmed
+}
+
+/// A picker answer: a row number from 1 to `count`, as an index.
+fn parse_pick(input: &str, count: usize) -> Option<usize> {
+    let n: usize = input.trim().trim_start_matches('#').parse().ok()?;
+    (1..=count).contains(&n).then(|| n - 1)
+}
+
+/// After a name search with several hits, number them and ask which one to
+/// inspect. `None` on an empty answer or end of input.
+fn pick_row(rows: &[PortInfo], use_color: bool) -> Option<&PortInfo> {
+    let mut out = io::stdout();
+    let _ = writeln!(out);
+    for (i, info) in rows.iter().enumerate() {
+        let _ = write!(out, "  ");
+        write_styled(&mut out, &format!("{:>2})", i + 1), "cyan", use_color);
+        let pid = if info.pid == 0 {
+            "-".to_string()
+        } else {
+            info.pid.to_string()
+        };
+        let _ = writeln!(
+            out,
+            " :{:<5} {:<6} {} (PID {})",
+            info.port, info.protocol, info.process_name, pid
+        );
+    }
+    loop {
+        let _ = write!(out, "  Inspect which? [1-{}, Enter to quit] ", rows.len());
+        out.flush().ok()?;
+        let mut input = String::new();
+        if io::stdin().read_line(&mut input).ok()? == 0 || input.trim().is_empty() {
+            return None;
+        }
+        match parse_pick(&input, rows.len()) {
+            Some(i) => return Some(&rows[i]),
+            None => write_styled(&mut out, "  Not a row number.\n", "yellow", use_color),
+        }
+    }
+}
+
+/// `Restart with: cd '/srv/app' && node server.js`, printed after a kill so
+/// the exact command doesn't have to be reconstructed by hand.
+fn print_restart_hint(restart: &RestartCommand, use_color: bool) {
+    let mut out = io::stdout();
+    let _ = write!(out, "  Restart with: ");
+    write_styled(&mut out, &restart.shell_line(), "cyan", use_color);
+    let _ = writeln!(out);
+}
+
 #[cfg(unix)]
-pub(crate) fn kill_process(pid: u32, force: bool) -> io::Result<&'static str> {
+pub(crate) fn send_signal(pid: u32, signal: Signal) -> io::Result<String> {
     if pid == 0 {
         return Err(io::Error::new(
             io::ErrorKind::InvalidInput,
@@ -965,22 +2934,20 @@ pub(crate) fn kill_process(pid: u32, force: bool) -> io::Result<&'static str> {
             format!("PID {} exceeds safe range", pid),
         ));
     }
-
-    let signal = if force { libc::SIGKILL } else { libc::SIGTERM };
-    let signal_name = if force { "SIGKILL" } else { "SIGTERM" };
+    let number = signal.number().unwrap_or(libc::SIGTERM);
 
     // Note: TOCTOU — the PID could have been recycled between reading /proc
     // and sending the signal. This is inherent to all kill-by-port tools.
-    let result = unsafe { libc::kill(pid as i32, signal) };
+    let result = unsafe { libc::kill(pid as i32, number) };
     if result == 0 {
-        Ok(signal_name)
+        Ok(signal.name())
     } else {
         Err(io::Error::last_os_error())
     }
 }
 
 #[cfg(windows)]
-pub(crate) fn kill_process(pid: u32, _force: bool) -> io::Result<&'static str> {
+pub(crate) fn send_signal(pid: u32, signal: Signal) -> io::Result<String> {
     use windows_sys::Win32::Foundation::CloseHandle;
     use windows_sys::Win32::System::Threading::{OpenProcess, TerminateProcess, PROCESS_TERMINATE};
 
@@ -990,6 +2957,12 @@ pub(crate) fn kill_process(pid: u32, _force: bool) -> io::Result<&'static str> {
             "Refusing to terminate PID 0",
         ));
     }
+    if !signal.supported_on_windows() {
+        return Err(io::Error::new(
+            io::ErrorKind::Unsupported,
+            signals::windows_fallback(signal),
+        ));
+    }
 
     unsafe {
         let handle = OpenProcess(PROCESS_TERMINATE, 0, pid);
@@ -1009,36 +2982,50 @@ pub(crate) fn kill_process(pid: u32, _force: bool) -> io::Result<&'static str> {
         if let Some(err) = term_err {
             Err(err)
         } else {
-            Ok("TerminateProcess")
+            Ok("TerminateProcess".to_string())
         }
     }
 }
 
-pub(crate) fn do_kill(pid: u32, force: bool) {
-    match kill_process(pid, force) {
+/// Returns whether the signal was delivered.
+pub(crate) fn do_kill(target: &batch::Target, signal: Signal) -> bool {
+    let pid = target.pid;
+    match actions::active().signal(target, signal) {
         Ok(action) => {
             let mut out = io::stdout();
             write_styled(&mut out, "  ✓", "green", true);
-            let msg = match action {
+            let msg = match action.as_str() {
                 "TerminateProcess" => format!(" Terminated PID {}", pid),
                 _ => format!(" Sent {} to PID {}", action, pid),
             };
             let _ = writeln!(out, "{}", msg);
+            true
         }
         Err(err) => {
             let mut out = io::stderr();
             write_styled(&mut out, "  ✗", "red", true);
-            if err.kind() == io::ErrorKind::InvalidInput {
+            if matches!(
+                err.kind(),
+                io::ErrorKind::InvalidInput | io::ErrorKind::Unsupported
+            ) {
                 let _ = writeln!(out, " {}", err);
             } else {
-                let _ = writeln!(out, " Failed to kill PID {}: {}", pid, err);
+                let _ = writeln!(out, " Failed to signal PID {}: {}", pid, err);
             }
+            false
         }
     }
 }
 
-fn json_escape(s: &str) -> String {
-    let mut escaped = String::with_capacity(s.len());
+/// Version stamped on every `--json` port object. Bump it when a field is
+/// removed or changes type; adding fields doesn't require a bump.
+pub(crate) const JSON_SCHEMA_VERSION: u32 = 1;
+
+/// JSON Schema for the port list printed by `--json` (see `--schema`).
+const PORTS_SCHEMA: &str = include_str!("../schema/ports.schema.json");
+
+pub(crate) fn json_escape(s: &str) -> String {
+    let mut escaped = String::with_capacity(s.len());
     for c in s.chars() {
         match c {
             '"' => escaped.push_str("\\\""),
@@ -1063,19 +3050,86 @@ pub(crate) fn short_container_id(id: &str) -> &str {
 }
 
 fn docker_owner_json(owner: &DockerPortOwner) -> String {
+    let health = owner
+        .health
+        .as_deref()
+        .map(|h| format!("\"{}\"", json_escape(h)))
+        .unwrap_or_else(|| "null".to_string());
+    let (cpu, memory) = match owner.stats {
+        Some(s) => (format!("{:.2}", s.cpu_percent), s.memory_bytes.to_string()),
+        None => ("null".to_string(), "null".to_string()),
+    };
+    let compose = owner
+        .compose
+        .as_ref()
+        .map(|c| {
+            format!(
+                r#"{{"project":"{}","service":"{}","working_dir":"{}"}}"#,
+                json_escape(&c.project),
+                json_escape(&c.service),
+                json_escape(&c.working_dir)
+            )
+        })
+        .unwrap_or_else(|| "null".to_string());
     format!(
-        r#"{{"container_id":"{}","container":"{}","image":"{}","container_port":{},"protocol":"{}"}}"#,
+        r#"{{"container_id":"{}","container":"{}","image":"{}","container_port":{},"protocol":"{}","state":"{}","health":{},"cpu_percent":{},"memory_bytes":{},"compose":{}}}"#,
         json_escape(&owner.container_id),
         json_escape(&owner.container_name),
         json_escape(&owner.image),
         owner.container_port,
         json_escape(&owner.protocol),
+        json_escape(&owner.state),
+        health,
+        cpu,
+        memory,
+        compose,
+    )
+}
+
+/// Optional data merged into each JSON object: peers, process details,
+/// socket options and UDP probes when a single port is inspected,
+/// working directories with `--cwd`, QUIC probes with `--quic`.
+#[derive(Default)]
+struct JsonExtras {
+    peers: Option<Vec<PeerSummary>>,
+    details: HashMap<u32, ProcessDetail>,
+    /// By PID and port; rows whose options couldn't be read are missing.
+    socket_options: HashMap<(u32, u16), SocketOptions>,
+    /// QUIC servers by port; when set, every UDP row gets a `quic` key.
+    quic: Option<HashMap<u16, Quic>>,
+    /// Replies of well-known UDP services, by PID and port.
+    service_replies: HashMap<(u32, u16), ServiceReply>,
+    cwds: HashMap<u32, String>,
+}
+
+fn process_detail_json(detail: &ProcessDetail) -> String {
+    let opt = |v: Option<String>| v.unwrap_or_else(|| "null".to_string());
+    let env: Vec<String> = detail
+        .env
+        .iter()
+        .map(|(k, v)| format!(r#""{}":"{}""#, json_escape(k), json_escape(v)))
+        .collect();
+    format!(
+        r#","threads":{},"nice":{},"cwd":{},"env":{{{}}},"repo":{}"#,
+        opt(detail.threads.map(|t| t.to_string())),
+        opt(detail.nice.map(|n| n.to_string())),
+        opt(detail
+            .cwd
+            .as_ref()
+            .map(|c| format!(r#""{}""#, json_escape(c)))),
+        env.join(","),
+        opt(detail.repo.as_ref().map(project::repo_json))
     )
 }
 
-fn port_info_json(info: &PortInfo, docker_owners: Option<&[DockerPortOwner]>) -> String {
+fn port_info_json(
+    info: &PortInfo,
+    docker_owners: Option<&[DockerPortOwner]>,
+    extras: Option<&JsonExtras>,
+) -> String {
     let mut json = format!(
-        r#"{{"port":{},"protocol":"{}","pid":{},"process":"{}","command":"{}","user":"{}","state":"{}","memory_bytes":{},"cpu_seconds":{:.1},"children":{}"#,
+        r#"{{"schema_version":{},"port":{},"protocol":"{}","pid":{},"process":"{}","command":"{}","user":"{}","state":"{}","memory_bytes":{},"cpu_seconds":{:.1},"children":{},"runtime":{}"#,
+        JSON_SCHEMA_VERSION,
         info.port,
         json_escape(&info.protocol),
         info.pid,
@@ -1086,8 +3140,31 @@ fn port_info_json(info: &PortInfo, docker_owners: Option<&[DockerPortOwner]>) ->
         info.memory_bytes,
         info.cpu_seconds,
         info.children,
+        runtime::detect(info).map_or("null".to_string(), |rt| format!(r#""{}""#, rt.as_str())),
     );
 
+    let start_secs = info
+        .start_time
+        .and_then(|t| t.duration_since(UNIX_EPOCH).ok())
+        .map(|d| d.as_secs());
+    match start_secs {
+        Some(secs) => json.push_str(&format!(r#","start_time":{}"#, secs)),
+        None => json.push_str(r#","start_time":null"#),
+    }
+    if clock::active() == clock::TimestampStyle::Absolute {
+        match info.start_time.and_then(clock::LocalTime::from_system) {
+            Some(t) => json.push_str(&format!(r#","started":"{}""#, t.rfc3339())),
+            None => json.push_str(r#","started":null"#),
+        }
+    }
+
+    if let Some(label) = config::active().label(info.port) {
+        json.push_str(&format!(r#","label":"{}""#, json_escape(label)));
+    }
+    if let Some(resolver) = Resolver::detect(info) {
+        json.push_str(&format!(r#","resolver":"{}""#, resolver.name()));
+    }
+
     if let Some(owners) = docker_owners {
         json.push_str(r#","docker":["#);
         for (i, owner) in owners.iter().enumerate() {
@@ -1097,30 +3174,129 @@ fn port_info_json(info: &PortInfo, docker_owners: Option<&[DockerPortOwner]>) ->
             json.push_str(&docker_owner_json(owner));
         }
         json.push(']');
+        let mappings: Vec<String> = owners
+            .iter()
+            .map(|o| format!("\"{}\"", json_escape(&o.mapping(info.port, "->"))))
+            .collect();
+        json.push_str(&format!(r#","mappings":[{}]"#, mappings.join(",")));
+    }
+
+    if let Some(extras) = extras.filter(|e| e.details.contains_key(&info.pid)) {
+        json.push_str(&process_detail_json(&extras.details[&info.pid]));
+        let options = extras.socket_options.get(&(info.pid, info.port));
+        json.push_str(&format!(
+            r#","socket_options":{}"#,
+            options.map_or("null".to_string(), SocketOptions::json)
+        ));
+    } else if let Some(cwd) = extras.and_then(|e| e.cwds.get(&info.pid)) {
+        json.push_str(&format!(r#","cwd":"{}""#, json_escape(cwd)));
+    }
+
+    if info.protocol.starts_with("UDP") {
+        json.push_str(&format!(
+            r#","service":{}"#,
+            udpservice::identify(info)
+                .map_or("null".to_string(), |s| format!(r#""{}""#, s.as_str()))
+        ));
+    }
+    if let Some(extras) = extras.filter(|e| e.details.contains_key(&info.pid)) {
+        if udpservice::identify(info).is_some() {
+            let reply = extras.service_replies.get(&(info.pid, info.port));
+            json.push_str(&format!(
+                r#","service_reply":{}"#,
+                reply.map_or("null".to_string(), ServiceReply::json)
+            ));
+        }
+    }
+
+    if let Some(quic) = extras.and_then(|e| e.quic.as_ref()) {
+        if info.protocol.starts_with("UDP") {
+            json.push_str(&format!(
+                r#","quic":{}"#,
+                quic.get(&info.port).map_or("null".to_string(), Quic::json)
+            ));
+        }
+    }
+
+    if let Some(peers) = extras.and_then(|e| e.peers.as_deref()) {
+        json.push_str(r#","peers":"#);
+        json.push_str(&peers::peers_json(peers));
     }
 
     json.push('}');
     json
 }
 
-fn display_json(infos: &[PortInfo], docker_map: Option<&DockerPortMap>) -> io::Result<()> {
-    let mut json = String::from("[");
-    for (i, info) in infos.iter().enumerate() {
-        if i > 0 {
-            json.push(',');
+fn display_json(
+    infos: &[PortInfo],
+    docker_map: Option<&DockerPortMap>,
+    extras: Option<&JsonExtras>,
+) -> io::Result<()> {
+    if ndjson() {
+        let mut out = io::stdout().lock();
+        for json in port_json_objects(infos, docker_map, extras) {
+            writeln!(out, "{}", json)?;
         }
-        let docker_owners = docker_map.map(|map| {
-            map.get(&info.port)
-                .map(|owners| owners.as_slice())
-                .unwrap_or(&[][..])
-        });
-        json.push_str(&port_info_json(info, docker_owners));
+        return Ok(());
     }
-    json.push_str("]\n");
+    let mut json = ports_json(infos, docker_map, extras);
+    json.push('\n');
     io::stdout().write_all(json.as_bytes())
 }
 
-// ── Watch-mode helpers (JSON watch only) ─────────────────────────────
+/// `--json` with nothing to show: an empty array, or no lines at all for
+/// NDJSON.
+fn display_empty_json() {
+    if !ndjson() {
+        println!("[]");
+    }
+}
+
+/// One JSON object per port.
+fn port_json_objects<'a>(
+    infos: impl IntoIterator<Item = &'a PortInfo>,
+    docker_map: Option<&DockerPortMap>,
+    extras: Option<&JsonExtras>,
+) -> Vec<String> {
+    infos
+        .into_iter()
+        .map(|info| {
+            let docker_owners = docker_map.map(|map| {
+                map.get(&info.port)
+                    .map(|owners| owners.as_slice())
+                    .unwrap_or(&[][..])
+            });
+            port_info_json(info, docker_owners, extras)
+        })
+        .collect()
+}
+
+fn ports_json<'a>(
+    infos: impl IntoIterator<Item = &'a PortInfo>,
+    docker_map: Option<&DockerPortMap>,
+    extras: Option<&JsonExtras>,
+) -> String {
+    format!(
+        "[{}]",
+        port_json_objects(infos, docker_map, extras).join(",")
+    )
+}
+
+/// The `--json` array for an exported TUI view, with working directories
+/// where known.
+pub(crate) fn export_json(
+    infos: &[&PortInfo],
+    docker_map: Option<&DockerPortMap>,
+    cwds: &HashMap<u32, String>,
+) -> String {
+    let extras = JsonExtras {
+        cwds: cwds.clone(),
+        ..Default::default()
+    };
+    ports_json(infos.iter().copied(), docker_map, Some(&extras))
+}
+
+// ── Long-running loop helpers (JSON watch, hold) ─────────────────────
 
 static RUNNING: AtomicBool = AtomicBool::new(true);
 
@@ -1140,6 +3316,33 @@ unsafe extern "system" fn handle_ctrl(ctrl_type: u32) -> i32 {
     }
 }
 
+/// Register a SIGINT / console-ctrl handler that clears `RUNNING` so long-lived
+/// loops can exit cleanly instead of being killed mid-write.
+pub(crate) fn install_interrupt_handler() {
+    #[cfg(unix)]
+    unsafe {
+        libc::signal(
+            libc::SIGINT,
+            handle_sigint as *const () as libc::sighandler_t,
+        );
+    }
+    #[cfg(windows)]
+    unsafe {
+        windows_sys::Win32::System::Console::SetConsoleCtrlHandler(
+            Some(handle_ctrl),
+            1, // TRUE — add handler
+        );
+    }
+}
+
+pub(crate) fn is_running() -> bool {
+    RUNNING.load(Ordering::SeqCst)
+}
+
+pub(crate) fn stop_running() {
+    RUNNING.store(false, Ordering::SeqCst);
+}
+
 #[cfg(unix)]
 pub(crate) fn chrono_free_time() -> String {
     // Get wall-clock HH:MM:SS without pulling in chrono
@@ -1175,37 +3378,157 @@ pub(crate) fn chrono_free_time() -> String {
 
 // ── Terminal width (for one-shot display) ────────────────────────────
 
+/// `--width`: the table width to lay out for instead of the terminal's.
+static WIDTH_OVERRIDE: OnceLock<u16> = OnceLock::new();
+
+/// Set by `--format ndjson`: JSON ports go one object per line, not in an
+/// array.
+static NDJSON: OnceLock<bool> = OnceLock::new();
+
+fn ndjson() -> bool {
+    NDJSON.get().copied().unwrap_or(false)
+}
+
+fn install_format(format: Option<OutputFormat>) {
+    let _ = NDJSON.set(format == Some(OutputFormat::Ndjson));
+}
+
 fn get_terminal_width() -> Option<u16> {
-    crossterm::terminal::size().ok().map(|(w, _)| w)
+    WIDTH_OVERRIDE
+        .get()
+        .copied()
+        .or_else(|| crossterm::terminal::size().ok().map(|(w, _)| w))
 }
 
 #[derive(Debug, Clone)]
 struct RunConfig {
     target: Option<String>,
+    /// `watch 3000 5432 ...`: the ports to draw as cards.
+    cards: Vec<u16>,
     force: bool,
     all: bool,
     json: bool,
     docker: bool,
+    /// How long watch mode reuses a `docker ps` read.
+    docker_refresh: Duration,
     watch: bool,
     wide: bool,
+    peers: bool,
+    dns: bool,
+    cwd: bool,
+    runtime: bool,
+    /// `watch --tcp-stats`: attach the eBPF probes.
+    tcp_stats: bool,
+    /// `--quic`: probe UDP listeners.
+    quic: bool,
+    template: Option<Template>,
+    notify: Vec<NotifyRule>,
+    hooks: EventHooks,
+    alerts: Vec<AlertRule>,
+    alert_notify: bool,
+    /// `watch --bell`: ring when a watched listener changes.
+    bell: bool,
+    /// `watch --title`: keep the terminal title set to a summary.
+    title: bool,
+    record: Option<PathBuf>,
+    plain: bool,
+    changes_only: bool,
+    layout: TableLayout,
+    sort: Option<SortKey>,
+    reverse: bool,
+    /// Print a table even when the target is a port number.
+    list: bool,
+    /// `--paginate` / `--no-pager`; unset defers to the config.
+    paginate: Option<bool>,
+    /// `--stream`: print rows as the collector reads them.
+    stream: bool,
 }
 
 impl RunConfig {
-    fn from_legacy(cli: &Cli) -> Self {
+    /// `portview list [filter]`: always a table, even for a port number.
+    fn from_list(filter: Option<String>, args: &ListArgs, template: Option<Template>) -> Self {
+        Self {
+            target: filter,
+            cards: Vec::new(),
+            force: false,
+            all: args.all,
+            json: args.json || args.format.is_some_and(OutputFormat::is_json),
+            docker: args.docker,
+            docker_refresh: docker::PORT_MAP_REFRESH,
+            watch: false,
+            wide: args.wide,
+            peers: false,
+            dns: true,
+            cwd: args.cwd,
+            runtime: args.runtime,
+            tcp_stats: false,
+            quic: args.quic,
+            template,
+            notify: Vec::new(),
+            hooks: EventHooks::default(),
+            alerts: Vec::new(),
+            alert_notify: false,
+            bell: false,
+            title: false,
+            record: None,
+            plain: false,
+            changes_only: false,
+            layout: TableLayout::from_flags(args.plain_table, args.header_per_row),
+            sort: args.sort,
+            reverse: args.reverse,
+            list: true,
+            paginate: match (args.paginate, args.no_pager) {
+                (true, _) => Some(true),
+                (_, true) => Some(false),
+                _ => None,
+            },
+            stream: args.stream,
+        }
+    }
+
+    /// Page the output: tables only, never a port inspection or picker
+    /// that prompts on stdin, and only onto a terminal.
+    fn pages(&self) -> bool {
+        let table = self.list || matches!(self.target.as_deref(), None | Some("scan"));
+        table
+            && !self.watch
+            && !self.stream
+            && self.paginate.unwrap_or_else(|| config::active().paginate())
+            && atty_stdout()
+    }
+
+    /// Apply `--sort`/`--reverse`; without either, rows keep their order.
+    fn sort_rows(&self, infos: &mut [PortInfo]) {
+        if self.sort.is_some() || self.reverse {
+            self.sort.unwrap_or(SortKey::Port).sort(infos, self.reverse);
+        }
+    }
+
+    /// The bare `portview [target]` form: the list flags plus port
+    /// inspection. `--watch` and `--kill` were rewritten to subcommands by
+    /// then.
+    fn from_legacy(cli: &Cli, template: Option<Template>) -> Self {
         Self {
-            target: cli.target.clone(),
             force: cli.force,
-            all: cli.all,
-            json: cli.json,
-            docker: cli.docker,
-            watch: cli.watch,
-            wide: cli.wide,
+            peers: cli.peers,
+            dns: !cli.no_dns,
+            list: false,
+            ..Self::from_list(cli.target.clone(), &cli.list, template)
         }
     }
 }
 
-fn run_kill_mode(port: u16, force: bool, docker: bool, use_color: bool) {
-    let infos = get_port_infos(false);
+/// The rows a kill picks its processes from, the same whatever the output
+/// format. A single port takes every socket on it; a range or filter takes
+/// listeners only, since it would otherwise catch outbound connections from
+/// browsers and editors on ephemeral ports.
+fn kill_candidates(range: Option<(u16, u16)>, filter: Option<&KillFilter>) -> Vec<PortInfo> {
+    let single_port = filter.is_none() && range.is_some_and(|(lo, hi)| lo == hi);
+    get_port_infos(!single_port)
+}
+
+fn run_kill_mode(port: u16, signal: Signal, docker: bool, respawn: bool, use_color: bool) {
+    let infos = kill_candidates(Some((port, port)), None);
     let matches: Vec<&PortInfo> = infos.iter().filter(|i| i.port == port).collect();
     let docker_map = if docker {
         Some(get_docker_port_map())
@@ -1218,143 +3541,1545 @@ fn run_kill_mode(port: u16, force: bool, docker: bool, use_color: bool) {
         std::process::exit(1);
     }
 
-    for info in matches {
-        display_detail(info, use_color);
+    // Capture before killing: a dead process's cwd can't be read
+    let mut restarts: Vec<(u32, RestartCommand)> = Vec::new();
+    for info in &matches {
+        if restarts.iter().all(|(pid, _)| *pid != info.pid) {
+            if let Some(restart) = RestartCommand::capture(info) {
+                restarts.push((info.pid, restart));
+            }
+        }
+    }
+    if respawn && !signal.stops_process() {
+        errors::fail(
+            errors::ErrorCode::InvalidArgument,
+            &format!(
+                "--respawn can't follow {}, which leaves the process running",
+                signal.name()
+            ),
+        );
+    }
+    #[cfg(not(unix))]
+    if !signal.supported_on_windows() {
+        errors::fail(
+            errors::ErrorCode::InvalidArgument,
+            &signals::windows_fallback(signal),
+        );
+    }
+
+    let mut targets: Vec<(u32, Vec<u16>)> = Vec::new();
+    for info in &matches {
+        match targets.iter_mut().find(|(pid, _)| *pid == info.pid) {
+            Some((_, ports)) if !ports.contains(&info.port) => ports.push(info.port),
+            Some(_) => {}
+            None => targets.push((info.pid, vec![info.port])),
+        }
+    }
+    if respawn {
+        let pids: Vec<String> = targets.iter().map(|(pid, _)| pid.to_string()).collect();
+        if pids.len() != 1 {
+            errors::fail(
+                errors::ErrorCode::InvalidArgument,
+                &format!(
+                    "--respawn needs exactly one process on port {} (found PIDs {})",
+                    port,
+                    pids.join(", ")
+                ),
+            );
+        }
+        if restarts.is_empty() {
+            errors::fail(
+                errors::ErrorCode::PermissionDenied,
+                &format!(
+                    "--respawn can't read how PID {} was started, so it wouldn't be restarted",
+                    pids[0]
+                ),
+            );
+        }
+    }
+
+    let mut killed = Vec::new();
+    for &info in &matches {
+        display_detail(info, None, use_color);
         if let Some(ref map) = docker_map {
-            display_docker_context(info.port, map, use_color);
+            display_docker_context(info, map, use_color);
+        }
+        if killed.contains(&info.pid) {
+            continue;
+        }
+        if signal == Signal::Kill && confirm::active().typed_force && !confirm_force_kill(info.pid)
+        {
+            continue;
+        }
+        let target = batch::Target::holding(info.pid, &info.process_name, matches.iter().copied());
+        if do_kill(&target, signal) {
+            killed.push(info.pid);
+        }
+    }
+    if signal.stops_process() {
+        targets.retain(|(pid, _)| killed.contains(pid));
+        report_verdicts(&targets, signal, use_color);
+    }
+
+    let restarts: Vec<&RestartCommand> = restarts
+        .iter()
+        .filter(|(pid, _)| killed.contains(pid))
+        .map(|(_, r)| r)
+        .collect();
+    if !respawn {
+        if !signal.stops_process() {
+            return;
+        }
+        for restart in restarts {
+            print_restart_hint(restart, use_color);
+        }
+        return;
+    }
+    let Some(restart) = restarts.first() else {
+        std::process::exit(1);
+    };
+
+    if !respawn::wait_for_release(port, Duration::from_secs(5)) {
+        eprintln!(
+            "error: port {} is still in use after 5s; not restarting (try --force)",
+            port
+        );
+        print_restart_hint(restart, use_color);
+        std::process::exit(1);
+    }
+    let mut out = io::stdout();
+    let _ = write!(out, "  Restarting: ");
+    write_styled(&mut out, &restart.shell_line(), "cyan", use_color);
+    let _ = writeln!(out);
+    let _ = out.flush();
+    match restart.run() {
+        Ok(status) => std::process::exit(status.code().unwrap_or(1)),
+        Err(err) => {
+            eprintln!("error: cannot run `{}`: {}", restart.shell_line(), err);
+            std::process::exit(1);
         }
-        do_kill(info.pid, force);
     }
 }
 
-fn run_watch_mode(config: &RunConfig, no_color: bool, use_color: bool, colors: &ColorConfig) {
-    if config.json {
-        // JSON watch: emit one JSON array per tick, no terminal escapes
-        // Register signal/ctrl handler for clean exit
-        #[cfg(unix)]
-        unsafe {
-            libc::signal(
-                libc::SIGINT,
-                handle_sigint as *const () as libc::sighandler_t,
+/// `kill 3000-3010` or `kill --filter`: print the plan, confirm it once
+/// unless `confirmed`, then signal each process and report how it went.
+fn run_batch_kill(
+    range: Option<(u16, u16)>,
+    filter: Option<&KillFilter>,
+    signal: Signal,
+    confirmed: bool,
+    json: bool,
+    use_color: bool,
+) {
+    #[cfg(not(unix))]
+    if !signal.supported_on_windows() {
+        errors::fail(
+            errors::ErrorCode::InvalidArgument,
+            &signals::windows_fallback(signal),
+        );
+    }
+    let targets = batch::plan(&kill_candidates(range, filter), range, filter);
+    if targets.is_empty() {
+        errors::fail(
+            errors::ErrorCode::NoMatch,
+            "no process matches the port, range or filter",
+        );
+    }
+
+    let mut out = io::stdout();
+    if !json {
+        let _ = writeln!(
+            out,
+            "\n  Sending {} to {} {}:",
+            signal.name(),
+            targets.len(),
+            if targets.len() == 1 {
+                "process"
+            } else {
+                "processes"
+            }
+        );
+        for target in &targets {
+            let ports: Vec<String> = target.ports.iter().map(|p| p.to_string()).collect();
+            let _ = write!(out, "    PID {:<7} {:<16} ", target.pid, target.process);
+            write_styled(&mut out, &ports.join(", "), "dimmed", use_color);
+            let _ = writeln!(out);
+        }
+    }
+    if !confirmed {
+        if json || !atty_stdin() {
+            errors::fail(
+                errors::ErrorCode::InvalidArgument,
+                "a range or filter kill needs --yes when it can't ask at a terminal",
             );
         }
-        #[cfg(windows)]
-        unsafe {
-            windows_sys::Win32::System::Console::SetConsoleCtrlHandler(
-                Some(handle_ctrl),
-                1, // TRUE — add handler
+        let _ = write!(out, "  Proceed? [y/N] ");
+        let _ = out.flush();
+        let mut input = String::new();
+        if io::stdin().read_line(&mut input).is_err() || !input.trim().eq_ignore_ascii_case("y") {
+            std::process::exit(1);
+        }
+    }
+
+    let mut results = Vec::new();
+    for target in targets {
+        let outcome = if signal == Signal::Kill
+            && confirm::active().typed_force
+            && !confirm_force_kill(target.pid)
+        {
+            batch::Outcome::Skipped
+        } else {
+            match actions::active().signal(&target, signal) {
+                Ok(_) => batch::Outcome::Sent,
+                Err(err) => batch::Outcome::Failed(err.to_string()),
+            }
+        };
+        results.push((target, outcome));
+    }
+
+    let sent = results
+        .iter()
+        .filter(|(_, o)| *o == batch::Outcome::Sent)
+        .count();
+    let stopped: Vec<(u32, Vec<u16>)> = results
+        .iter()
+        .filter(|(_, o)| *o == batch::Outcome::Sent && signal.stops_process())
+        .map(|(t, _)| (t.pid, t.ports.clone()))
+        .collect();
+    if json {
+        let verdicts = verify::wait(&stopped);
+        let checked: Vec<(u32, &verify::Verdict)> =
+            stopped.iter().map(|(pid, _)| *pid).zip(&verdicts).collect();
+        println!(
+            "{}",
+            batch::results_json(&signal.name(), &results, &checked)
+        );
+    } else {
+        for (target, outcome) in &results {
+            let (mark, color, text) = match outcome {
+                batch::Outcome::Sent => ("  ✓", "green", "sent".to_string()),
+                batch::Outcome::Skipped => ("  -", "yellow", "skipped".to_string()),
+                batch::Outcome::Failed(err) => ("  ✗", "red", err.clone()),
+            };
+            write_styled(&mut out, mark, color, use_color);
+            let _ = writeln!(out, " PID {} ({}): {}", target.pid, target.process, text);
+        }
+        let _ = writeln!(
+            out,
+            "  Sent {} to {} of {} {}",
+            signal.name(),
+            sent,
+            results.len(),
+            if results.len() == 1 {
+                "process"
+            } else {
+                "processes"
+            }
+        );
+        report_verdicts(&stopped, signal, use_color);
+    }
+    if sent < results.len() {
+        std::process::exit(1);
+    }
+}
+
+fn run_suggest_mode(range: (u16, u16), count: usize, random: bool, docker: bool, json: bool) {
+    let mut taken: std::collections::HashSet<u16> =
+        get_port_infos(false).iter().map(|i| i.port).collect();
+    if docker {
+        taken.extend(get_docker_port_map().keys());
+    }
+
+    let ports = suggest::find_free_ports(range, count, random, &taken);
+    if json {
+        let items: Vec<String> = ports.iter().map(|p| p.to_string()).collect();
+        println!("[{}]", items.join(","));
+    } else {
+        for port in &ports {
+            println!("{}", port);
+        }
+    }
+
+    if ports.is_empty() {
+        errors::fail(
+            errors::ErrorCode::NoMatch,
+            &format!("no free port in {}-{}", range.0, range.1),
+        );
+    }
+}
+
+fn run_why_mode(port: u16, docker: bool, json: bool, use_color: bool) {
+    let mut infos = get_port_infos(false);
+    let conns = get_connections();
+    let docker_map = if docker {
+        Some(get_docker_port_map())
+    } else {
+        None
+    };
+    if let Some(ref map) = docker_map {
+        infos.extend(synthesize_docker_entries(&infos, map));
+    }
+    let docker_owners = docker_map
+        .as_ref()
+        .and_then(|map| map.get(&port))
+        .map(|owners| owners.as_slice());
+    let ctx = why::BindContext {
+        needs_privilege: needs_privilege_to_bind(port),
+        excluded: why::excluded_ranges(port),
+        local_ports: collector::active().local_ports(),
+    };
+
+    let findings = why::explain(port, &infos, &conns, docker_owners, &ctx);
+    if json {
+        println!("{}", why::findings_json(port, &findings));
+    } else {
+        why::display_findings(port, &findings, use_color);
+    }
+}
+
+fn run_expect_mode(
+    manifest: Option<&Path>,
+    watch: Option<Duration>,
+    format: expect::ExpectFormat,
+    no_color: bool,
+    use_color: bool,
+) {
+    let path = match manifest {
+        Some(path) => path.to_path_buf(),
+        None => match std::env::current_dir()
+            .ok()
+            .and_then(|cwd| config::find_project_config(&cwd))
+        {
+            Some(path) => path,
+            None => errors::fail(
+                errors::ErrorCode::InvalidArgument,
+                &format!(
+                    "no manifest given and no {} found in this directory or its parents",
+                    config::PROJECT_FILE
+                ),
+            ),
+        },
+    };
+    let source = path.display().to_string();
+    let manifest = match expect::Manifest::load(&path) {
+        Ok(manifest) => manifest,
+        Err(err) if format == expect::ExpectFormat::Vscode => {
+            let err = err.strip_prefix(&format!("{}: ", source)).unwrap_or(&err);
+            println!("{}", expect::parse_error_vscode(&source, err));
+            std::process::exit(2);
+        }
+        Err(err) => errors::fail(errors::ErrorCode::ConfigError, &err),
+    };
+    if let Some(interval) = watch {
+        if let Err(e) = tui::run_expect_dashboard(manifest, source, interval, no_color) {
+            errors::fail(errors::ErrorCode::IoError, &e.to_string());
+        }
+        return;
+    }
+    let infos = get_port_infos(true);
+    let outcomes = expect::check(&manifest, &infos, expect::run_probe);
+    match format {
+        expect::ExpectFormat::Json => println!("{}", expect::outcomes_json(&outcomes)),
+        expect::ExpectFormat::Vscode => print!("{}", expect::outcomes_vscode(&source, &outcomes)),
+        expect::ExpectFormat::Text => expect::display_outcomes(&outcomes, use_color),
+    }
+    if !expect::all_up(&outcomes) {
+        std::process::exit(1);
+    }
+}
+
+fn run_states_mode(port: Option<u16>, json: bool, use_color: bool) {
+    let infos = get_port_infos(false);
+    let conns = get_connections();
+    let listening: std::collections::HashSet<u16> = infos
+        .iter()
+        .filter(|i| i.state == TcpState::Listen && i.protocol == "TCP")
+        .map(|i| i.port)
+        .collect();
+    let names: std::collections::HashMap<u32, String> = infos
+        .iter()
+        .map(|i| (i.pid, i.process_name.clone()))
+        .collect();
+
+    let mut rows = states::summarize(&conns, &listening, &names);
+    if let Some(port) = port {
+        rows.retain(|r| r.port == port);
+    }
+
+    if json {
+        println!("{}", states::states_json(&rows));
+    } else {
+        states::display_states(&rows, use_color);
+    }
+}
+
+/// `root` followed by its descendants, breadth-first. PIDs already seen
+/// are skipped, so a reused PID can't loop.
+fn process_tree(root: u32, children_of: impl Fn(u32) -> Vec<u32>) -> Vec<u32> {
+    let mut pids = vec![root];
+    let mut next = 0;
+    while next < pids.len() {
+        for child in children_of(pids[next]) {
+            if !pids.contains(&child) {
+                pids.push(child);
+            }
+        }
+        next += 1;
+    }
+    pids
+}
+
+fn run_pid_mode(
+    pid: u32,
+    children: bool,
+    all: bool,
+    json: bool,
+    use_color: bool,
+    colors: &ColorConfig,
+) -> io::Result<()> {
+    let pids = if children {
+        process_tree(pid, |p| collector::active().child_pids(p))
+    } else {
+        vec![pid]
+    };
+    let mut infos = get_port_infos(!all);
+    infos.retain(|i| pids.contains(&i.pid));
+
+    if json {
+        display_json(&infos, None, None)?;
+    } else if infos.is_empty() {
+        let mut out = io::stdout();
+        let whose = if children {
+            format!("PID {} or its children", pid)
+        } else {
+            format!("PID {}", pid)
+        };
+        if use_color {
+            let _ = write!(out, "\n  ");
+            write_styled(&mut out, "○", "dimmed", true);
+            let _ = write!(out, " No ports held by ");
+            write_styled(&mut out, &whose, "bold", true);
+            let _ = writeln!(out);
+        } else {
+            let _ = writeln!(out, "\n  No ports held by {}", whose);
+        }
+    } else {
+        let name = infos
+            .iter()
+            .find(|i| i.pid == pid)
+            .map(|i| format!("{} (PID {})", i.process_name, pid))
+            .unwrap_or_else(|| format!("PID {}", pid));
+        let mut out = io::stdout();
+        write_styled(
+            &mut out,
+            &format!(
+                "\n {} port{} held by {}{}\n",
+                infos.len(),
+                if infos.len() == 1 { "" } else { "s" },
+                name,
+                if children { " and its children" } else { "" }
+            ),
+            "bold",
+            use_color,
+        );
+        let extra = ExtraColumns::new(&infos, false, false, None, None);
+        let cmd_width = compute_cmd_width(&infos, &extra);
+        for info in &mut infos {
+            info.command = truncate_cmd(&info.command, cmd_width);
+        }
+        display_table(
+            &infos,
+            &extra,
+            use_color,
+            colors,
+            false,
+            cmd_width,
+            TableLayout::Boxed,
+        );
+    }
+    if infos.is_empty() {
+        if json {
+            errors::report(
+                errors::ErrorCode::NoMatch,
+                &format!("no ports held by PID {}", pid),
             );
         }
+        std::process::exit(1);
+    }
+    Ok(())
+}
+
+fn run_pipes_mode(filter: Option<&str>, json: bool, use_color: bool) {
+    let Some(mut pipes) = pipes::list() else {
+        errors::fail(
+            errors::ErrorCode::InvalidArgument,
+            "named pipes are a Windows feature; on this system, local services show up as ports",
+        );
+    };
+    if let Some(filter) = filter {
+        pipes.retain(|p| p.matches(filter));
+    }
+    if json {
+        println!("{}", pipes::pipes_json(&pipes));
+    } else {
+        pipes::display_pipes(&pipes, use_color);
+    }
+    if filter.is_some() && pipes.is_empty() {
+        std::process::exit(1);
+    }
+}
+
+fn run_doctor_mode(json: bool, use_color: bool) {
+    let backend = collector::active();
+    let mut checks = vec![doctor::Check::ok(
+        "backend",
+        format!("{} ({})", backend.name(), backend.capabilities().describe()),
+    )];
+    checks.extend(os::diagnose());
+    checks.push(doctor::check_docker());
+    checks.extend(doctor::check_helpers());
+    if json {
+        println!("{}", doctor::checks_json(&checks));
+    } else {
+        doctor::display_checks("portview doctor", &checks, use_color);
+    }
+    if doctor::has_failures(&checks) {
+        std::process::exit(1);
+    }
+}
+
+fn run_selftest_mode(json: bool, use_color: bool) {
+    let checks = selftest::run();
+    if json {
+        println!("{}", doctor::checks_json(&checks));
+    } else {
+        let title = format!("portview selftest ({})", collector::active().name());
+        doctor::display_checks(&title, &checks, use_color);
+    }
+    if doctor::has_failures(&checks) {
+        std::process::exit(1);
+    }
+}
+
+fn run_status_mode(ports: &[u16], emoji: bool, color: status::StatusColor) {
+    let up = get_listening_ports();
+    status::write_status(&mut io::stdout(), ports, &up, emoji, color);
+    if ports.iter().any(|p| !up.contains(p)) {
+        std::process::exit(1);
+    }
+}
+
+fn run_daemon_mode(path: &Path, interval: Duration, all: bool, docker: bool, once: bool) {
+    install_interrupt_handler();
+    let watcher = if once {
+        None
+    } else {
+        eprintln!(
+            "Writing {} every {:?} (Ctrl+C to stop)",
+            path.display(),
+            interval
+        );
+        refresh::ChangeWatcher::spawn()
+    };
+
+    loop {
+        let infos = get_port_infos(!all);
+        let docker_map = docker.then(|| docker::cached_port_map(docker::PORT_MAP_REFRESH));
+        let json = daemon::snapshot_json(
+            &ports_json(&infos, docker_map.as_ref(), None),
+            SystemTime::now(),
+            interval,
+        );
+        if let Err(e) = daemon::write_atomic(path, &json) {
+            eprintln!("error: cannot write {}: {}", path.display(), e);
+            std::process::exit(1);
+        }
+        if once {
+            return;
+        }
+
+        // Sleep out the interval, waking early if listeners change
+        let deadline = Instant::now() + interval;
+        while RUNNING.load(Ordering::SeqCst)
+            && Instant::now() < deadline
+            && !watcher.as_ref().is_some_and(refresh::ChangeWatcher::take)
+        {
+            std::thread::sleep(refresh::CHECK_INTERVAL);
+        }
+        if !RUNNING.load(Ordering::SeqCst) {
+            break;
+        }
+    }
+    // A missing file tells readers nothing is keeping it current
+    let _ = std::fs::remove_file(path);
+}
+
+fn run_diff_mode(before: &Path, after: &Path, ports_only: bool, json: bool, use_color: bool) {
+    let load = |path: &Path| {
+        let text = std::fs::read_to_string(path).unwrap_or_else(|e| {
+            errors::fail(
+                errors::ErrorCode::IoError,
+                &format!("cannot read {}: {}", path.display(), e),
+            )
+        });
+        fixture::parse_snapshot(&text).unwrap_or_else(|e| {
+            errors::fail(
+                errors::ErrorCode::InvalidArgument,
+                &format!("{}: {}", path.display(), e),
+            )
+        })
+    };
+    let (before, after) = (load(before), load(after));
+    let changes = diff::diff(&before, &after, ports_only);
+    if json {
+        println!("{}", diff::diff_json(&changes));
+    } else {
+        diff::display_diff(&changes, use_color);
+    }
+    if !changes.is_empty() {
+        std::process::exit(1);
+    }
+}
+
+fn run_here_mode(json: bool, use_color: bool) {
+    let dir = std::env::current_dir().unwrap_or_else(|e| {
+        errors::fail(
+            errors::ErrorCode::IoError,
+            &format!("cannot read the current directory: {}", e),
+        )
+    });
+    let declared = declared::declared_ports(&dir);
+    if declared.is_empty() {
+        errors::fail(
+            errors::ErrorCode::NoMatch,
+            &format!(
+                "no ports declared in {} (looked at {})",
+                dir.display(),
+                declared::SOURCES
+            ),
+        );
+    }
+    let infos = get_port_infos(true);
+    let rows: Vec<here::PortStatus> = declared
+        .into_iter()
+        .map(|declared| {
+            let holder = infos
+                .iter()
+                .find(|i| i.port == declared.port && i.pid != 0)
+                .map(|i| here::Holder {
+                    pid: i.pid,
+                    process: i.process_name.clone(),
+                    in_project: get_process_cwd(i.pid)
+                        .is_some_and(|cwd| Path::new(&cwd).starts_with(&dir)),
+                });
+            here::PortStatus { declared, holder }
+        })
+        .collect();
+    if json {
+        println!("{}", here::here_json(&dir, &rows));
+    } else {
+        here::display_here(&dir, &rows, use_color);
+    }
+}
+
+fn run_log_mode(lines: usize, file: Option<&Path>, json: bool, use_color: bool) {
+    let Some(path) = file.map(Path::to_path_buf).or_else(audit::default_path) else {
+        errors::fail(
+            errors::ErrorCode::InvalidArgument,
+            "no home directory to find the action log in; pass --file",
+        );
+    };
+    let entries = audit::read(&path, lines).unwrap_or_else(|e| {
+        errors::fail(
+            errors::ErrorCode::IoError,
+            &format!("cannot read {}: {}", path.display(), e),
+        )
+    });
+    if json {
+        println!("{}", audit::log_json(&entries));
+    } else {
+        audit::display_log(&path, &entries, use_color);
+    }
+}
+
+/// `portview hook --check`: one line per declared port that's in use.
+/// Reads only the socket tables unless something clashes, and stays
+/// quiet otherwise, since it runs on every cd.
+fn run_hook_check() {
+    let Ok(dir) = std::env::current_dir() else {
+        return;
+    };
+    let declared = declared::declared_ports(&dir);
+    if declared.is_empty() {
+        return;
+    }
+    let listening = get_listening_ports();
+    let taken: Vec<&declared::DeclaredPort> = declared
+        .iter()
+        .filter(|d| listening.contains(&d.port))
+        .collect();
+    if taken.is_empty() {
+        return;
+    }
+    let infos = get_port_infos(true);
+    for declared in taken {
+        let owner = infos
+            .iter()
+            .find(|i| i.port == declared.port && i.pid != 0)
+            .map(|i| (i.process_name.as_str(), i.pid));
+        println!("{}", shellhook::warning(declared, owner));
+    }
+}
+
+/// Whether the current user needs elevated privileges to bind `port`.
+#[cfg(target_os = "linux")]
+fn needs_privilege_to_bind(port: u16) -> bool {
+    let unprivileged_start: u16 =
+        std::fs::read_to_string("/proc/sys/net/ipv4/ip_unprivileged_port_start")
+            .ok()
+            .and_then(|s| s.trim().parse().ok())
+            .unwrap_or(1024);
+    port < unprivileged_start && unsafe { libc::geteuid() } != 0
+}
+
+/// macOS (since 10.14) and Windows let any user bind low ports.
+#[cfg(not(target_os = "linux"))]
+fn needs_privilege_to_bind(_port: u16) -> bool {
+    false
+}
+
+/// Desktop notification (with `--alert-notify`) and hooks for new alerts.
+pub(crate) fn deliver_alerts(alerts: &[alerts::Alert], desktop: bool, hooks: &EventHooks) {
+    if desktop {
+        for alert in alerts {
+            notify::send_notification("portview alert", &alert.describe());
+        }
+    }
+    hooks.fire_alerts(alerts);
+}
+
+fn tui_style_config(no_color: bool, colors: &ColorConfig) -> StyleConfig {
+    if no_color {
+        StyleConfig::default()
+    } else if std::env::var("PORTVIEW_COLORS").is_ok() {
+        StyleConfig::from_color_config(colors)
+    } else {
+        StyleConfig::btop_default()
+    }
+}
+
+/// What `watch --json --changes-only` compares between ticks: which
+/// sockets are open and who owns them, not CPU or memory, which move on
+/// every tick.
+type WatchKey = (u16, String, u32, String, &'static str, IpAddr);
+
+fn watch_key(infos: &[&PortInfo]) -> Vec<WatchKey> {
+    let mut key: Vec<WatchKey> = infos
+        .iter()
+        .map(|i| {
+            (
+                i.port,
+                i.protocol.clone(),
+                i.pid,
+                i.process_name.clone(),
+                i.state.as_str(),
+                i.local_addr,
+            )
+        })
+        .collect();
+    key.sort();
+    key
+}
+
+/// Event and alert lines kept under the table in `watch --plain`.
+const PLAIN_RECENT_LINES: usize = 5;
+
+fn run_watch_mode(config: &RunConfig, no_color: bool, use_color: bool, colors: &ColorConfig) {
+    let mut recorder =
+        config
+            .record
+            .as_deref()
+            .map(|path| match recording::Recorder::create(path) {
+                Ok(recorder) => recorder,
+                Err(e) => {
+                    eprintln!("error: cannot record to {}: {}", path.display(), e);
+                    std::process::exit(1);
+                }
+            });
+
+    // Plain watch and cards clear the screen and redraw, like watch(1)
+    let redraw = config.plain || !config.cards.is_empty();
+    if config.json || redraw {
+        // JSON watch emits one array per tick with no terminal escapes
+        install_interrupt_handler();
+        let mut tracker = events::PortTracker::default();
+        let mut monitor = alerts::AlertMonitor::new(config.alerts.clone());
+        let tracking = !config.notify.is_empty() || !config.hooks.is_empty() || config.bell;
+        let mut recent: Vec<String> = Vec::new();
+        let mut last_key: Option<Vec<WatchKey>> = None;
+        let mut cards = cards::CardTracker::default();
+        let mut window_title = title::TitleWriter::default();
+        let watcher = refresh::ChangeWatcher::spawn();
+        let container_events = if config.docker {
+            docker::EventWatcher::spawn()
+        } else {
+            None
+        };
 
         while RUNNING.load(Ordering::SeqCst) {
-            if write_display_safe(config, use_color, colors).is_err() {
+            let mut lines: Vec<String> = container_events
+                .as_ref()
+                .map_or_else(Vec::new, docker::EventWatcher::take)
+                .iter()
+                .map(docker::ContainerEvent::describe)
+                .collect();
+            if !lines.is_empty() {
+                docker::invalidate_port_map();
+            }
+            let infos = (tracking
+                || !monitor.is_empty()
+                || recorder.is_some()
+                || config.changes_only
+                || config.title
+                || !config.cards.is_empty())
+            .then(|| get_port_infos(!config.all));
+            let watched: Vec<&PortInfo> = infos
+                .iter()
+                .flatten()
+                .filter(|i| {
+                    if config.cards.is_empty() {
+                        config
+                            .target
+                            .as_deref()
+                            .is_none_or(|t| matches_target(i, t))
+                    } else {
+                        config.cards.contains(&i.port)
+                    }
+                })
+                .collect();
+            let unchanged = config.changes_only && {
+                let key = watch_key(&watched);
+                let same = last_key.as_ref() == Some(&key);
+                last_key = Some(key);
+                same
+            };
+
+            if redraw {
+                print!("\x1b[H\x1b[2J");
+            }
+            if !config.cards.is_empty() {
+                let width = get_terminal_width().map_or(80, usize::from);
+                let now = chrono_free_time();
+                let infos = infos.as_deref().unwrap_or_default();
+                print!(
+                    "{}",
+                    cards::render(&cards.update(&config.cards, infos, &now), width, use_color)
+                );
+            } else if config.plain {
+                let title = match config.target.as_deref() {
+                    Some(target) => format!("portview watch {}", target),
+                    None => "portview watch".to_string(),
+                };
+                println!("Every 1s: {}  (Ctrl+C to quit)", title);
+            }
+            if config.cards.is_empty()
+                && !unchanged
+                && write_display_safe(config, use_color, colors).is_err()
+            {
                 break; // broken pipe
             }
+            if config.title {
+                let ports = if config.cards.is_empty() {
+                    config
+                        .target
+                        .as_deref()
+                        .and_then(|t| t.parse().ok())
+                        .into_iter()
+                        .collect()
+                } else {
+                    config.cards.clone()
+                };
+                let marks: Vec<(u16, bool)> = ports
+                    .into_iter()
+                    .map(|port| (port, watched.iter().any(|i| i.port == port)))
+                    .collect();
+                let text = title::text(watched.len(), &marks);
+                // Like the bell, kept off stdout for JSON
+                let _ = if redraw {
+                    window_title.update(&mut io::stdout(), text)
+                } else {
+                    window_title.update(&mut io::stderr(), text)
+                };
+            }
+            if let Some(ref infos) = infos {
+                if let Some(ref mut r) = recorder {
+                    if let Err(e) = r.record(infos) {
+                        lines.push(format!("Recording stopped: {}", e));
+                        recorder = None;
+                    }
+                }
+                if tracking {
+                    let events = tracker.update(watched.iter().copied());
+                    for event in notify::notify_matching(&config.notify, &events) {
+                        lines.push(event.describe());
+                    }
+                    config.hooks.fire(&events);
+                    if config.bell && !events.is_empty() {
+                        // Not on stdout for JSON, which may be piped
+                        if redraw {
+                            print!("\x07");
+                        } else {
+                            eprint!("\x07");
+                        }
+                    }
+                }
+                let fired = monitor.update(watched, Instant::now());
+                for alert in &fired {
+                    lines.push(format!("Alert: {}", alert.describe()));
+                }
+                deliver_alerts(&fired, config.alert_notify, &config.hooks);
+            }
+            if redraw {
+                // The next clear would wipe one-off messages, so keep the
+                // latest few under the table
+                recent.extend(lines);
+                let excess = recent.len().saturating_sub(PLAIN_RECENT_LINES);
+                recent.drain(..excess);
+                if !recent.is_empty() {
+                    println!("\nRecent:");
+                    for line in &recent {
+                        println!("  {}", line);
+                    }
+                }
+                if io::stdout().flush().is_err() {
+                    break;
+                }
+            } else {
+                for line in &lines {
+                    eprintln!("{}", line);
+                }
+            }
 
+            // Sleep out the second, waking early if listeners change, a
+            // container starts or stops or, in plain mode, the terminal is
+            // resized. The terminal's own size, not `--width`, so a resize
+            // is seen whatever the table is laid out for.
+            let terminal_width = || crossterm::terminal::size().ok().map(|(w, _)| w);
+            let width = terminal_width();
             for _ in 0..20 {
-                if !RUNNING.load(Ordering::SeqCst) {
+                if !RUNNING.load(Ordering::SeqCst)
+                    || watcher.as_ref().is_some_and(refresh::ChangeWatcher::take)
+                    || container_events
+                        .as_ref()
+                        .is_some_and(docker::EventWatcher::pending)
+                    || (redraw && terminal_width() != width)
+                {
                     break;
                 }
-                std::thread::sleep(Duration::from_millis(50));
+                std::thread::sleep(refresh::CHECK_INTERVAL);
             }
         }
-    } else {
-        // Interactive TUI mode
-        let has_env_colors = std::env::var("PORTVIEW_COLORS").is_ok();
-        let style_config = if no_color {
-            StyleConfig::default()
-        } else if has_env_colors {
-            StyleConfig::from_color_config(colors)
+        let _ = if redraw {
+            window_title.restore(&mut io::stdout())
         } else {
-            StyleConfig::btop_default()
+            window_title.restore(&mut io::stderr())
         };
-
-        if let Err(e) = tui::run_tui(
-            config.target.as_deref(),
-            config.all,
-            config.wide,
-            config.force,
+    } else {
+        // Interactive TUI mode
+        let style_config = tui_style_config(no_color, colors);
+        let opts = tui::TuiOptions {
+            target: config.target.as_deref(),
+            show_all: config.all,
+            wide: config.wide,
+            force: config.force,
             no_color,
-            config.docker,
-            style_config,
-        ) {
+            docker: config.docker,
+            docker_refresh: config.docker_refresh,
+            dns: config.dns,
+            cwd: config.cwd,
+            runtime: config.runtime,
+            tcp_stats: config.tcp_stats,
+            notify: &config.notify,
+            hooks: &config.hooks,
+            alerts: &config.alerts,
+            alert_notify: config.alert_notify,
+            bell: config.bell,
+            title: config.title,
+            record: recorder,
+            replay: None,
+            remembered: remember::load(),
+        };
+        if let Err(e) = tui::run_tui(opts, style_config) {
             eprintln!("TUI error: {}", e);
             std::process::exit(1);
         }
     }
 }
 
+fn run_replay_mode(file: &Path, no_color: bool, colors: &ColorConfig) {
+    let snapshots = match recording::load(file) {
+        Ok(snapshots) => snapshots,
+        Err(e) => {
+            eprintln!("error: cannot read {}: {}", file.display(), e);
+            std::process::exit(1);
+        }
+    };
+    let Some(replay) = recording::Replay::new(snapshots) else {
+        eprintln!("error: {} contains no snapshots", file.display());
+        std::process::exit(1);
+    };
+
+    let hooks = EventHooks::default();
+    let opts = tui::TuiOptions {
+        target: None,
+        show_all: false,
+        wide: false,
+        force: false,
+        no_color,
+        docker: false,
+        docker_refresh: docker::PORT_MAP_REFRESH,
+        dns: false,
+        cwd: false,
+        runtime: false,
+        tcp_stats: false,
+        notify: &[],
+        hooks: &hooks,
+        alerts: &[],
+        alert_notify: false,
+        bell: false,
+        title: false,
+        record: None,
+        replay: Some(replay),
+        remembered: None,
+    };
+    if let Err(e) = tui::run_tui(opts, tui_style_config(no_color, colors)) {
+        eprintln!("TUI error: {}", e);
+        std::process::exit(1);
+    }
+}
+
 // ── Main ─────────────────────────────────────────────────────────────
 
-fn main() {
-    let cli = Cli::parse();
-    let colors = ColorConfig::from_env();
+/// A listener on `port` whose process the backend couldn't see: another
+/// user's, without the privileges to look.
+fn port_owner_hidden(port: u16) -> bool {
+    let backend = collector::active();
+    backend.capabilities().needs_privileges
+        && !is_elevated()
+        && backend.listening_ports().contains(&port)
+}
+
+/// Whether portview runs as root, or from an elevated (Administrator)
+/// prompt on Windows.
+#[cfg(unix)]
+pub(crate) fn is_elevated() -> bool {
+    unsafe { libc::geteuid() == 0 }
+}
+
+#[cfg(windows)]
+pub(crate) fn is_elevated() -> bool {
+    use windows_sys::Win32::Foundation::{CloseHandle, HANDLE};
+    use windows_sys::Win32::Security::{
+        GetTokenInformation, TokenElevation, TOKEN_ELEVATION, TOKEN_QUERY,
+    };
+    use windows_sys::Win32::System::Threading::{GetCurrentProcess, OpenProcessToken};
+
+    unsafe {
+        let mut token: HANDLE = std::ptr::null_mut();
+        if OpenProcessToken(GetCurrentProcess(), TOKEN_QUERY, &mut token) == 0 {
+            return false;
+        }
+        let mut elevation = TOKEN_ELEVATION { TokenIsElevated: 0 };
+        let mut size = 0u32;
+        let ok = GetTokenInformation(
+            token,
+            TokenElevation,
+            &mut elevation as *mut TOKEN_ELEVATION as *mut _,
+            std::mem::size_of::<TOKEN_ELEVATION>() as u32,
+            &mut size,
+        );
+        CloseHandle(token);
+        ok != 0 && elevation.TokenIsElevated != 0
+    }
+}
+
+/// Whether a process's row is missing what permissions kept from it: no
+/// command line, or neither memory nor start time.
+pub(crate) fn partially_read(info: &PortInfo) -> bool {
+    info.pid != 0
+        && (info.command.is_empty() || (info.memory_bytes == 0 && info.start_time.is_none()))
+}
+
+/// Whether the command line asked for JSON output, so errors use it too.
+fn wants_json(cli: &Cli) -> bool {
+    cli.list.json
+        || matches!(
+            &cli.command,
+            Some(
+                Command::Watch { json: true, .. }
+                    | Command::Watch {
+                        format: Some(OutputFormat::Json | OutputFormat::Ndjson),
+                        ..
+                    }
+                    | Command::Pid { json: true, .. }
+                    | Command::Suggest { json: true, .. }
+                    | Command::Why { json: true, .. }
+                    | Command::Expect { json: true, .. }
+                    | Command::Expect {
+                        format: Some(expect::ExpectFormat::Json),
+                        ..
+                    }
+                    | Command::States { json: true, .. }
+                    | Command::Pipes { json: true, .. }
+                    | Command::Here { json: true, .. }
+                    | Command::Diff { json: true, .. }
+                    | Command::Log { json: true, .. }
+                    | Command::Selftest { json: true, .. }
+                    | Command::Doctor { json: true, .. }
+                    | Command::Kill { json: true, .. }
+            )
+        )
+        || cli.list.format.is_some_and(OutputFormat::is_json)
+        || matches!(&cli.command, Some(Command::List { args, .. })
+            if args.json || args.format.is_some_and(OutputFormat::is_json))
+}
+
+/// `Cli::parse`, except that a rejected command line carrying `--json`
+/// is reported as a JSON error.
+fn parse_cli() -> Cli {
+    use clap::error::ErrorKind;
+    match Cli::try_parse() {
+        Ok(cli) => cli,
+        Err(e) if matches!(e.kind(), ErrorKind::DisplayHelp | ErrorKind::DisplayVersion) => {
+            e.exit()
+        }
+        Err(e) if std::env::args().any(|a| a == "--json") => {
+            let text = e.to_string();
+            let first = text.lines().next().unwrap_or_default();
+            errors::install_json(true);
+            errors::fail(
+                errors::ErrorCode::InvalidArgument,
+                first.strip_prefix("error: ").unwrap_or(first),
+            )
+        }
+        Err(e) => e.exit(),
+    }
+}
+
+fn main() {
+    let cli = parse_cli();
+    errors::install_json(wants_json(&cli));
+    let colors = ColorConfig::from_env();
+
+    if cli.schema {
+        print!("{}", PORTS_SCHEMA);
+        return;
+    }
+
+    let byte_format = match cli.units {
+        Some(format) => format,
+        None => match std::env::var("PORTVIEW_UNITS") {
+            Ok(val) => units::ByteFormat::parse(&val).unwrap_or_else(|e| {
+                errors::fail(
+                    errors::ErrorCode::InvalidArgument,
+                    &format!("PORTVIEW_UNITS: {}", e),
+                )
+            }),
+            Err(_) => units::ByteFormat::default(),
+        },
+    };
+    units::install(byte_format);
+    clock::install(cli.timestamps.unwrap_or_default());
+    match config::load() {
+        Ok(config) => config::install(config),
+        Err(e) => errors::fail(errors::ErrorCode::ConfigError, &e),
+    }
+    confirm::install(confirm::Confirm::resolve(
+        cli.no_confirm,
+        cli.confirm_force,
+        config::active(),
+    ));
+
+    if let Some(ref path) = cli.fixture {
+        let loaded = std::fs::read_to_string(path)
+            .map_err(|e| e.to_string())
+            .and_then(|text| fixture::FixtureCollector::parse(&text))
+            .and_then(|c| {
+                actions::install(Box::new(c.executor()))?;
+                collector::install(Box::new(c))
+            });
+        if let Err(e) = loaded {
+            errors::fail(
+                errors::ErrorCode::InvalidArgument,
+                &format!("cannot load fixture {}: {}", path.display(), e),
+            );
+        }
+    }
+
+    // Simulated kills against a fixture aren't worth a line in the log
+    if cli.fixture.is_none() {
+        if let Some(path) = audit::default_path() {
+            audit::enable(path);
+        }
+    }
 
-    if let Some(command) = &cli.command {
+    // --watch + --kill is not allowed
+    if cli.watch && cli.kill.is_some() {
+        errors::fail(
+            errors::ErrorCode::InvalidArgument,
+            "--watch and --kill cannot be used together",
+        );
+    }
+    legacy::warn(
+        &legacy::notices(&cli),
+        !cli.list.no_color && io::stderr().is_terminal(),
+    );
+    let command = cli.command.clone().or_else(|| legacy::rewrite(&cli));
+    if let Some(command) = &command {
         match command {
             Command::Watch {
                 target,
+                more_ports,
                 all,
                 json,
+                format,
                 docker,
+                docker_refresh,
                 force,
                 wide,
+                no_dns,
+                cwd,
+                runtime,
+                tcp_stats,
+                user,
+                mine,
+                session,
+                exact,
+                notify,
+                on_open,
+                on_close,
+                webhook,
+                alert,
+                alert_notify,
+                on_alert,
+                bell,
+                title,
+                record,
+                changes_only,
+                plain,
+                plain_table,
+                header_per_row,
                 no_color,
             } => {
+                install_user_filter(user.as_deref(), *mine);
+                install_session_filter(*session);
+                fuzzy::install_exact(*exact);
+                install_format(*format);
                 let use_color = !no_color && atty_stdout();
+                let cards = if more_ports.is_empty() {
+                    Vec::new()
+                } else {
+                    match target.as_deref().map(str::parse::<u16>) {
+                        Some(Ok(first)) => std::iter::once(first)
+                            .chain(more_ports.iter().copied())
+                            .collect(),
+                        _ => errors::fail(
+                            errors::ErrorCode::InvalidArgument,
+                            "watching several targets takes port numbers only",
+                        ),
+                    }
+                };
                 let config = RunConfig {
-                    target: target.clone(),
+                    target: if cards.is_empty() {
+                        target.clone()
+                    } else {
+                        None
+                    },
+                    cards,
                     force: *force,
                     all: *all,
-                    json: *json,
+                    json: *json || format.is_some_and(OutputFormat::is_json),
                     docker: *docker,
+                    docker_refresh: *docker_refresh,
                     watch: true,
                     wide: *wide,
+                    peers: false,
+                    dns: !no_dns,
+                    cwd: *cwd,
+                    runtime: *runtime,
+                    tcp_stats: *tcp_stats,
+                    quic: false,
+                    template: None,
+                    notify: notify.clone(),
+                    hooks: EventHooks {
+                        on_open: on_open.clone(),
+                        on_close: on_close.clone(),
+                        on_alert: on_alert.clone(),
+                        webhook: webhook.clone(),
+                    },
+                    alerts: alert.clone(),
+                    alert_notify: *alert_notify,
+                    bell: *bell,
+                    title: *title,
+                    record: record.clone(),
+                    plain: *plain,
+                    changes_only: *changes_only,
+                    layout: TableLayout::from_flags(*plain_table, *header_per_row),
+                    sort: None,
+                    reverse: false,
+                    list: false,
+                    paginate: None,
+                    stream: false,
                 };
                 run_watch_mode(&config, *no_color, use_color, &colors);
                 return;
             }
+            Command::List { filter, args } => {
+                let use_color = !args.no_color && atty_stdout();
+                let config = RunConfig::from_list(filter.clone(), args, prepare_list(args));
+                run_list(&config, use_color, &colors);
+                return;
+            }
             Command::Kill {
-                port,
+                ports,
+                filter,
+                yes,
+                json,
                 force,
+                signal,
+                docker,
+                respawn,
+                no_color,
+            } => {
+                let use_color = !no_color && atty_stdout();
+                let signal = signal.unwrap_or(Signal::for_force(*force));
+                let single = match (ports, filter) {
+                    (Some((lo, hi)), None) if lo == hi => Some(*lo),
+                    _ => None,
+                };
+                if *respawn && single.is_none() {
+                    errors::fail(
+                        errors::ErrorCode::InvalidArgument,
+                        "--respawn works on a single port, not a range or filter",
+                    );
+                }
+                match single {
+                    Some(port) if !*json => {
+                        run_kill_mode(port, signal, *docker, *respawn, use_color)
+                    }
+                    _ => run_batch_kill(
+                        *ports,
+                        filter.as_ref(),
+                        signal,
+                        single.is_some() || *yes,
+                        *json,
+                        use_color,
+                    ),
+                }
+                return;
+            }
+            Command::Hold {
+                ports,
+                tcp,
+                udp,
+                bind,
+                duration,
+                no_color,
+            } => {
+                let use_color = !no_color && atty_stdout();
+                // Neither flag means TCP only; both flags hold both protocols
+                let tcp = *tcp || !*udp;
+                hold::run_hold_mode(ports, *bind, tcp, *udp, *duration, use_color);
+                return;
+            }
+            Command::Suggest {
+                range,
+                count,
+                random,
+                docker,
+                json,
+            } => {
+                run_suggest_mode(*range, *count, *random, *docker, *json);
+                return;
+            }
+            Command::Expect {
+                manifest,
+                watch,
+                interval,
+                json,
+                format,
+                no_color,
+            } => {
+                let use_color = !no_color && atty_stdout();
+                let watch = watch.then_some(*interval);
+                let format = match format {
+                    Some(format) => *format,
+                    None if *json => expect::ExpectFormat::Json,
+                    None => expect::ExpectFormat::Text,
+                };
+                run_expect_mode(manifest.as_deref(), watch, format, *no_color, use_color);
+                return;
+            }
+            Command::Why {
+                port,
+                docker,
+                json,
+                no_color,
+            } => {
+                let use_color = !no_color && atty_stdout();
+                run_why_mode(*port, *docker, *json, use_color);
+                return;
+            }
+            Command::States {
+                port,
+                json,
+                no_color,
+            } => {
+                let use_color = !no_color && atty_stdout();
+                run_states_mode(*port, *json, use_color);
+                return;
+            }
+            Command::Pipes {
+                filter,
+                json,
+                no_color,
+            } => {
+                let use_color = !no_color && atty_stdout();
+                run_pipes_mode(filter.as_deref(), *json, use_color);
+                return;
+            }
+            Command::Doctor { json, no_color } => {
+                let use_color = !no_color && atty_stdout();
+                run_doctor_mode(*json, use_color);
+                return;
+            }
+            Command::Pid {
+                pid,
+                children,
+                all,
+                json,
+                no_color,
+            } => {
+                let use_color = !no_color && atty_stdout();
+                if let Err(err) = run_pid_mode(*pid, *children, *all, *json, use_color, &colors) {
+                    if err.kind() != io::ErrorKind::BrokenPipe {
+                        eprintln!("Failed to write output: {}", err);
+                        std::process::exit(1);
+                    }
+                }
+                return;
+            }
+            Command::Replay { file, no_color } => {
+                run_replay_mode(file, *no_color, &colors);
+                return;
+            }
+            Command::Status {
+                ports,
+                emoji,
+                tmux,
+                color,
+                no_color,
+            } => {
+                let color = if *tmux {
+                    status::StatusColor::Tmux
+                } else if *color || (!no_color && atty_stdout()) {
+                    status::StatusColor::Ansi
+                } else {
+                    status::StatusColor::Off
+                };
+                run_status_mode(ports, *emoji, color);
+                return;
+            }
+            Command::Daemon {
+                output,
+                interval,
+                all,
                 docker,
+                once,
+            } => {
+                let path = output.clone().unwrap_or_else(daemon::default_path);
+                run_daemon_mode(&path, *interval, *all, *docker, *once);
+                return;
+            }
+            Command::Selftest { json, no_color } => {
+                let use_color = !no_color && atty_stdout();
+                run_selftest_mode(*json, use_color);
+                return;
+            }
+            Command::Diff {
+                before,
+                after,
+                ports_only,
+                json,
                 no_color,
             } => {
                 let use_color = !no_color && atty_stdout();
-                run_kill_mode(*port, *force, *docker, use_color);
+                run_diff_mode(before, after, *ports_only, *json, use_color);
+                return;
+            }
+            Command::Here { json, no_color } => {
+                let use_color = !no_color && atty_stdout();
+                run_here_mode(*json, use_color);
+                return;
+            }
+            Command::Log {
+                lines,
+                file,
+                json,
+                no_color,
+            } => {
+                let use_color = !no_color && atty_stdout();
+                run_log_mode(*lines, file.as_deref(), *json, use_color);
+                return;
+            }
+            Command::Hook { shell, check } => {
+                match shell {
+                    Some(shell) => print!("{}", shell.script()),
+                    None if *check => run_hook_check(),
+                    None => {}
+                }
                 return;
             }
         }
     }
 
     // Legacy flag/positional mode remains supported
-    let use_color = !cli.no_color && atty_stdout();
-    let config = RunConfig::from_legacy(&cli);
+    let use_color = !cli.list.no_color && atty_stdout();
+    let config = RunConfig::from_legacy(&cli, prepare_list(&cli.list));
+    run_list(&config, use_color, &colors);
+}
 
-    // --watch + --kill is not allowed
-    if config.watch && cli.kill.is_some() {
-        eprintln!("error: --watch and --kill cannot be used together");
-        std::process::exit(2);
+/// Install the process-wide parts of the list flags (`--user`, `--session`, `--exact`)
+/// and parse `--template`, exiting on a bad one.
+fn prepare_list(args: &ListArgs) -> Option<Template> {
+    install_user_filter(args.user.as_deref(), args.mine);
+    install_session_filter(args.session);
+    fuzzy::install_exact(args.exact);
+    if let Some(width) = args.width {
+        let _ = WIDTH_OVERRIDE.set(width);
     }
-    // --kill mode (not compatible with watch)
-    if let Some(port) = cli.kill {
-        run_kill_mode(port, config.force, config.docker, use_color);
-        return;
+    install_format(args.format);
+    match args.template.as_deref().map(Template::parse).transpose() {
+        Ok(template) => template,
+        Err(err) => errors::fail(errors::ErrorCode::InvalidArgument, &err.to_string()),
     }
+}
 
-    if config.watch {
-        run_watch_mode(&config, cli.no_color, use_color, &colors);
-    } else if let Err(err) = run_display(&config, use_color, &colors) {
+fn run_list(config: &RunConfig, use_color: bool, colors: &ColorConfig) {
+    let _pager = if config.pages() { pager::start() } else { None };
+    if let Err(err) = run_display(config, use_color, colors) {
         if err.kind() != io::ErrorKind::BrokenPipe {
-            eprintln!("Failed to write output: {}", err);
-            std::process::exit(1);
+            errors::fail(
+                errors::ErrorCode::IoError,
+                &format!("failed to write output: {}", err),
+            );
         }
     }
 }
 
 /// Compute available width for the command column based on actual data.
 /// Accounts for the real widths of all other columns + table borders/padding.
-fn compute_cmd_width(infos: &[PortInfo]) -> usize {
+fn compute_cmd_width(infos: &[PortInfo], extra: &ExtraColumns) -> usize {
     let cols = get_terminal_width().unwrap_or(143) as usize;
 
     if infos.is_empty() {
@@ -1362,10 +5087,13 @@ fn compute_cmd_width(infos: &[PortInfo]) -> usize {
     }
 
     let col_widths = measure_column_widths(infos);
-    let data_width: usize = col_widths.iter().sum();
+    let extra_widths = extra.widths(infos);
+    let data_width: usize = col_widths.iter().sum::<usize>() + extra_widths.iter().sum::<usize>();
+    let columns = 8 + extra_widths.len();
 
-    // Box-drawing style: 9 vertical borders + 1 space padding on each side of each of 8 columns
-    let chrome = 9 + (8 * 2);
+    // Box-drawing style: one vertical border per column plus the closing one,
+    // and 1 space padding on each side of each column
+    let chrome = (columns + 1) + (columns * 2);
 
     cols.saturating_sub(data_width + chrome).max(20)
 }
@@ -1377,8 +5105,17 @@ fn write_display_safe(config: &RunConfig, use_color: bool, colors: &ColorConfig)
 }
 
 fn run_display(config: &RunConfig, use_color: bool, colors: &ColorConfig) -> io::Result<()> {
+    if config.stream {
+        return display_stream(config, use_color, colors);
+    }
     let docker_map = if config.docker {
-        Some(get_docker_port_map())
+        let mut map = if config.watch {
+            docker::cached_port_map(config.docker_refresh)
+        } else {
+            get_docker_port_map()
+        };
+        docker::attach_stats(&mut map, config.docker_refresh);
+        Some(map)
     } else {
         None
     };
@@ -1387,15 +5124,34 @@ fn run_display(config: &RunConfig, use_color: bool, colors: &ColorConfig) -> io:
         None | Some("scan") => {
             // Default: show table of listening ports
             let mut infos = get_port_infos(!config.all);
+            let mut proxies = false;
             if let Some(ref map) = docker_map {
-                annotate_infos_with_docker(&mut infos, map);
                 infos.extend(synthesize_docker_entries(&infos, map));
+            } else {
+                proxies = annotate_forwarded_ports(&mut infos);
             }
-            if config.json {
-                display_json(&infos, docker_map.as_ref())?;
+            config.sort_rows(&mut infos);
+            let wants_cwd = config.cwd || config.template.as_ref().is_some_and(Template::uses_cwd);
+            let extra = ExtraColumns::new(
+                &infos,
+                config.runtime,
+                config.quic,
+                docker_map.as_ref(),
+                wants_cwd.then(|| collect_cwds(&infos)),
+            );
+            if let Some(ref tpl) = config.template {
+                template::display_template(&infos, tpl, extra.cwds.as_ref())?;
+            } else if config.json {
+                let extras = JsonExtras {
+                    quic: config.quic.then_some(extra.quic),
+                    cwds: extra.cwds.unwrap_or_default(),
+                    ..Default::default()
+                };
+                display_json(&infos, docker_map.as_ref(), Some(&extras))?;
             } else {
-                let cmd_width = compute_cmd_width(&infos);
-                if !config.wide {
+                let mut infos = reuseport::collapse(&infos);
+                let cmd_width = compute_cmd_width(&infos, &extra);
+                if !config.wide && config.layout == TableLayout::Boxed {
                     for info in &mut infos {
                         info.command = truncate_cmd(&info.command, cmd_width);
                     }
@@ -1413,7 +5169,15 @@ fn run_display(config: &RunConfig, use_color: bool, colors: &ColorConfig) -> io:
                         true,
                     );
                 }
-                display_table(&infos, use_color, colors, config.wide, cmd_width);
+                display_table(
+                    &infos,
+                    &extra,
+                    use_color,
+                    colors,
+                    config.wide,
+                    cmd_width,
+                    config.layout,
+                );
                 if use_color && !infos.is_empty() && !config.watch {
                     let mut out = io::stdout();
                     write_styled(&mut out, "  Inspect: portview <port>\n", "dimmed", true);
@@ -1423,25 +5187,38 @@ fn run_display(config: &RunConfig, use_color: bool, colors: &ColorConfig) -> io:
                         "dimmed",
                         true,
                     );
+                    if proxies {
+                        print_forwarded_hint(true);
+                    }
+                }
+                if !config.watch {
+                    print_backlog_warnings(use_color);
                 }
             }
         }
         Some(target) => {
-            // Try to parse as port number
-            if let Ok(port) = target.parse::<u16>() {
+            // Try to parse as port number; `list` filters by it instead
+            if let Some(port) = target.parse::<u16>().ok().filter(|_| !config.list) {
                 let mut infos = get_port_infos(false);
+                let mut proxies = false;
                 if let Some(ref map) = docker_map {
                     infos.extend(
                         synthesize_docker_entries(&infos, map)
                             .into_iter()
                             .filter(|i| i.port == port),
                     );
+                } else {
+                    infos.retain(|i| i.port == port);
+                    proxies = annotate_forwarded_ports(&mut infos);
                 }
                 let matches: Vec<&PortInfo> = infos.iter().filter(|i| i.port == port).collect();
 
                 if matches.is_empty() {
+                    let hidden = port_owner_hidden(port);
                     if config.json {
-                        println!("[]");
+                        display_empty_json();
+                    } else if config.template.is_some() {
+                        // Scripts get empty output and a non-zero exit
                     } else {
                         let mut out = io::stdout();
                         if use_color {
@@ -1453,23 +5230,86 @@ fn run_display(config: &RunConfig, use_color: bool, colors: &ColorConfig) -> io:
                         } else {
                             let _ = writeln!(out, "\n  Nothing on port {}", port);
                         }
+                        if hidden {
+                            write_styled(
+                                &mut out,
+                                "  Something is listening, but its process belongs to another user; try sudo.\n",
+                                "yellow",
+                                use_color,
+                            );
+                        }
                     }
                     if !config.watch {
+                        if config.json && hidden {
+                            errors::report(
+                                errors::ErrorCode::PermissionDenied,
+                                &format!("port {} is held by a process this user can't see", port),
+                            );
+                        } else if config.json {
+                            errors::report(
+                                errors::ErrorCode::NoSuchPort,
+                                &format!("nothing on port {}", port),
+                            );
+                        }
                         std::process::exit(1);
                     }
                     return Ok(());
                 }
 
+                if let Some(ref tpl) = config.template {
+                    let owned: Vec<PortInfo> = matches.into_iter().cloned().collect();
+                    let cwds = tpl.uses_cwd().then(|| collect_cwds(&owned));
+                    return template::display_template(&owned, tpl, cwds.as_ref());
+                }
+
+                let mut extras = JsonExtras {
+                    details: matches
+                        .iter()
+                        .filter(|i| i.pid != 0)
+                        .map(|i| (i.pid, load_process_detail(i.pid)))
+                        .collect(),
+                    ..Default::default()
+                };
+                let backend = collector::active();
+                if config.peers && !backend.capabilities().supports_remote_addr {
+                    eprintln!(
+                        "note: the {} backend doesn't report remote addresses; --peers ignored",
+                        backend.name()
+                    );
+                } else if config.peers {
+                    let mut peers = peers::aggregate_peers(&get_connections(), port);
+                    if config.dns {
+                        peers::resolve_hostnames(&mut peers, dns::LOOKUP_TIMEOUT);
+                    }
+                    extras.peers = Some(peers);
+                }
+
                 if config.json {
+                    extras.socket_options = matches
+                        .iter()
+                        .filter_map(|i| Some(((i.pid, i.port), sockopts::lookup(i)?)))
+                        .collect();
                     let owned: Vec<PortInfo> = matches.into_iter().cloned().collect();
-                    display_json(&owned, docker_map.as_ref())?;
+                    extras.quic = Some(quic::scan(&owned));
+                    extras.service_replies = owned
+                        .iter()
+                        .filter_map(|i| Some(((i.pid, i.port), udpservice::probe(i)?)))
+                        .collect();
+                    display_json(&owned, docker_map.as_ref(), Some(&extras))?;
                 } else {
                     for info in &matches {
-                        display_detail(info, use_color);
+                        display_detail(info, extras.details.get(&info.pid), use_color);
                         if let Some(ref map) = docker_map {
-                            display_docker_context(info.port, map, use_color);
+                            display_docker_context(info, map, use_color);
                         }
                     }
+                    if let Some(ref peers) = extras.peers {
+                        display_peers(peers, use_color);
+                    }
+                    if proxies {
+                        println!();
+                        print_forwarded_hint(use_color);
+                    }
 
                     // Offer to kill interactively (only when NOT watching, not synthetic)
                     if !config.watch
@@ -1478,26 +5318,41 @@ fn run_display(config: &RunConfig, use_color: bool, colors: &ColorConfig) -> io:
                         && atty_stdout()
                         && atty_stdin()
                     {
-                        prompt_kill(matches[0].pid, config.force);
+                        prompt_kill(matches[0], config.force, use_color);
                     }
                 }
             } else {
                 // Search by process name — filter on full command, then truncate for display
                 let mut infos = get_port_infos(!config.all);
                 if let Some(ref map) = docker_map {
-                    annotate_infos_with_docker(&mut infos, map);
                     infos.extend(synthesize_docker_entries(&infos, map));
+                } else {
+                    annotate_forwarded_ports(&mut infos);
                 }
-                let target_lower = target.to_lowercase();
+                let container_ports = docker_map
+                    .as_ref()
+                    .map(|map| docker::ports_for_target(map, target))
+                    .unwrap_or_default();
                 let mut matches: Vec<PortInfo> = infos
                     .drain(..)
-                    .filter(|i| {
-                        i.process_name.to_lowercase().contains(&target_lower)
-                            || i.command.to_lowercase().contains(&target_lower)
-                    })
+                    .filter(|i| matches_target(i, target) || container_ports.contains(&i.port))
                     .collect();
+                let fuzzy_note = rank_fuzzy_matches(&mut matches, target);
+                config.sort_rows(&mut matches);
 
-                if matches.is_empty() {
+                if matches.is_empty() && config.template.is_some() {
+                    if !config.watch {
+                        std::process::exit(1);
+                    }
+                } else if matches.is_empty() && config.json {
+                    display_empty_json();
+                    if !config.watch {
+                        errors::fail(
+                            errors::ErrorCode::NoMatch,
+                            &format!("no ports found for '{}'", target),
+                        );
+                    }
+                } else if matches.is_empty() {
                     let mut out = io::stdout();
                     if use_color {
                         let _ = write!(out, "\n  ");
@@ -1508,14 +5363,44 @@ fn run_display(config: &RunConfig, use_color: bool, colors: &ColorConfig) -> io:
                     } else {
                         let _ = writeln!(out, "\n  No ports found for '{}'", target);
                     }
+                    if docker_map.is_none() && target.contains([':', '/']) {
+                        write_styled(
+                            &mut out,
+                            "  Container names and images only match with --docker\n",
+                            "dimmed",
+                            use_color,
+                        );
+                    }
                     if !config.watch {
                         std::process::exit(1);
                     }
+                } else if let Some(ref tpl) = config.template {
+                    let cwds = tpl.uses_cwd().then(|| collect_cwds(&matches));
+                    template::display_template(&matches, tpl, cwds.as_ref())?;
                 } else if config.json {
-                    display_json(&matches, docker_map.as_ref())?;
+                    let extras = JsonExtras {
+                        cwds: if config.cwd {
+                            collect_cwds(&matches)
+                        } else {
+                            HashMap::new()
+                        },
+                        ..Default::default()
+                    };
+                    display_json(&matches, docker_map.as_ref(), Some(&extras))?;
                 } else {
-                    let cmd_width = compute_cmd_width(&matches);
-                    if !config.wide {
+                    // Keep full commands for the detail view of a picked row
+                    let pickable =
+                        (!config.watch && matches.len() > 1 && atty_stdout() && atty_stdin())
+                            .then(|| matches.clone());
+                    let extra = ExtraColumns::new(
+                        &matches,
+                        config.runtime,
+                        config.quic,
+                        docker_map.as_ref(),
+                        config.cwd.then(|| collect_cwds(&matches)),
+                    );
+                    let cmd_width = compute_cmd_width(&matches, &extra);
+                    if !config.wide && config.layout == TableLayout::Boxed {
                         for info in &mut matches {
                             info.command = truncate_cmd(&info.command, cmd_width);
                         }
@@ -1537,7 +5422,36 @@ fn run_display(config: &RunConfig, use_color: bool, colors: &ColorConfig) -> io:
                         let _ = writeln!(out, "'");
                     }
 
-                    display_table(&matches, use_color, colors, config.wide, cmd_width);
+                    display_table(
+                        &matches,
+                        &extra,
+                        use_color,
+                        colors,
+                        config.wide,
+                        cmd_width,
+                        config.layout,
+                    );
+                    if let Some(note) = fuzzy_note {
+                        write_styled(
+                            &mut io::stdout(),
+                            &format!("  Fuzzy matches: {} (--exact to turn off)\n", note),
+                            "dimmed",
+                            use_color,
+                        );
+                    }
+                    let picked = pickable
+                        .as_deref()
+                        .and_then(|rows| pick_row(rows, use_color));
+                    if let Some(info) = picked {
+                        let detail = (info.pid != 0).then(|| load_process_detail(info.pid));
+                        display_detail(info, detail.as_ref(), use_color);
+                        if let Some(ref map) = docker_map {
+                            display_docker_context(info, map, use_color);
+                        }
+                        if info.pid != 0 {
+                            prompt_kill(info, config.force, use_color);
+                        }
+                    }
                 }
             }
         }
@@ -1550,7 +5464,7 @@ fn atty_stdout() -> bool {
     io::stdout().is_terminal()
 }
 
-fn atty_stdin() -> bool {
+pub(crate) fn atty_stdin() -> bool {
     io::stdin().is_terminal()
 }
 
@@ -1558,6 +5472,7 @@ fn atty_stdin() -> bool {
 mod tests {
     use super::*;
     use std::net::{Ipv4Addr, Ipv6Addr};
+    use std::path::PathBuf;
 
     #[test]
     fn short_container_id_truncates_to_12() {
@@ -1565,27 +5480,27 @@ mod tests {
         assert_eq!(short_container_id("shortid"), "shortid");
     }
 
-    // ── kill_process ────────────────────────────────────────────────
+    // ── send_signal ─────────────────────────────────────────────────
 
     #[cfg(unix)]
     #[test]
-    fn kill_process_rejects_pid_zero() {
-        let err = kill_process(0, false).expect_err("PID 0 must be rejected");
+    fn send_signal_rejects_pid_zero() {
+        let err = send_signal(0, Signal::Term).expect_err("PID 0 must be rejected");
         assert_eq!(err.kind(), io::ErrorKind::InvalidInput);
     }
 
     #[cfg(unix)]
     #[test]
-    fn kill_process_rejects_pid_over_i32_max() {
-        let err = kill_process((i32::MAX as u32) + 1, false)
+    fn send_signal_rejects_pid_over_i32_max() {
+        let err = send_signal((i32::MAX as u32) + 1, Signal::Term)
             .expect_err("out-of-range PID must be rejected");
         assert_eq!(err.kind(), io::ErrorKind::InvalidInput);
     }
 
     #[cfg(windows)]
     #[test]
-    fn kill_process_rejects_pid_zero() {
-        let err = kill_process(0, false).expect_err("PID 0 must be rejected");
+    fn send_signal_rejects_pid_zero() {
+        let err = send_signal(0, Signal::Term).expect_err("PID 0 must be rejected");
         assert_eq!(err.kind(), io::ErrorKind::InvalidInput);
     }
 
@@ -1628,6 +5543,174 @@ mod tests {
         assert!(result.contains("GB"));
     }
 
+    // ── shorten_path ────────────────────────────────────────────────
+
+    #[test]
+    fn shorten_path_abbreviates_home() {
+        assert_eq!(
+            shorten_path("/home/mark/code/shop", Some("/home/mark"), 32),
+            "~/code/shop"
+        );
+        assert_eq!(shorten_path("/home/mark", Some("/home/mark"), 32), "~");
+        // Prefix must end at a path boundary
+        assert_eq!(
+            shorten_path("/home/marker/x", Some("/home/mark"), 32),
+            "/home/marker/x"
+        );
+        assert_eq!(shorten_path("/srv/app", Some("/"), 32), "/srv/app");
+    }
+
+    #[test]
+    fn shorten_path_keeps_tail() {
+        assert_eq!(
+            shorten_path("/var/www/clients/acme/site", None, 12),
+            "…/acme/site"
+        );
+        assert_eq!(shorten_path("/srv", None, 12), "/srv");
+    }
+
+    #[test]
+    fn cwd_cell_unknown_is_dash() {
+        let cwds: HashMap<u32, String> = [(1, "/srv/app".to_string())].into_iter().collect();
+        assert_eq!(cwd_cell(2, &cwds, 32), "-");
+        assert!(cwd_cell(1, &cwds, 32).ends_with("/srv/app"));
+    }
+
+    // ── parse_env_hints / detail_rows ───────────────────────────────
+
+    #[cfg(unix)]
+    #[test]
+    fn parse_env_hints_picks_known_vars_in_order() {
+        let block = b"HOME=/root\0NODE_ENV=development\0PATH=/bin\0PORT=3000\0\0";
+        assert_eq!(
+            parse_env_hints(block),
+            vec![
+                ("PORT".to_string(), "3000".to_string()),
+                ("NODE_ENV".to_string(), "development".to_string()),
+            ]
+        );
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn parse_env_hints_keeps_first_duplicate_and_equals_in_value() {
+        let block = b"PORT=1\0PORT=2\0DJANGO_SETTINGS_MODULE=a=b\0";
+        assert_eq!(
+            parse_env_hints(block),
+            vec![
+                ("PORT".to_string(), "1".to_string()),
+                ("DJANGO_SETTINGS_MODULE".to_string(), "a=b".to_string()),
+            ]
+        );
+    }
+
+    #[test]
+    fn detail_rows_skip_unknowns() {
+        let detail = ProcessDetail {
+            threads: Some(4),
+            nice: None,
+            cwd: None,
+            env: vec![("PORT".to_string(), "3000".to_string())],
+            repo: None,
+            orphan: None,
+        };
+        assert_eq!(
+            detail_rows(&detail),
+            vec![
+                ("Threads:", "4".to_string()),
+                ("Env:", "PORT=3000".to_string())
+            ]
+        );
+    }
+
+    #[test]
+    fn process_detail_json_shape() {
+        let detail = ProcessDetail {
+            threads: Some(2),
+            nice: Some(-5),
+            cwd: Some("/srv/app".to_string()),
+            env: vec![("NODE_ENV".to_string(), "production".to_string())],
+            repo: Some(RepoInfo {
+                name: "app".to_string(),
+                root: PathBuf::from("/srv/app"),
+                branch: Some("main".to_string()),
+            }),
+            orphan: None,
+        };
+        assert_eq!(
+            process_detail_json(&detail),
+            r#","threads":2,"nice":-5,"cwd":"/srv/app","env":{"NODE_ENV":"production"},"repo":{"name":"app","root":"/srv/app","branch":"main"}"#
+        );
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn usernames_are_cached_and_fall_back_to_the_uid() {
+        let uid = unsafe { libc::geteuid() };
+        let name = get_username(uid);
+        assert_eq!(get_username(uid), name);
+        assert_eq!(lookup_uid(&name), Some(uid));
+        assert_eq!(get_username(4_000_000_000), "4000000000");
+    }
+
+    #[test]
+    fn schema_covers_every_port_field() {
+        let info = PortInfo::listener(3000, 42, "node")
+            .command("next dev")
+            .user("mark")
+            .usage(1024, 1.5)
+            .addr(IpAddr::V4(Ipv4Addr::LOCALHOST));
+        let extras = JsonExtras {
+            peers: Some(Vec::new()),
+            details: HashMap::from([(42, ProcessDetail::default())]),
+            ..Default::default()
+        };
+        let json = port_info_json(&info, Some(&[]), Some(&extras));
+        assert!(json.starts_with(&format!(r#"{{"schema_version":{},"#, JSON_SCHEMA_VERSION)));
+
+        // Every top-level key we emit must be documented in the schema
+        let mut depth = 0;
+        let mut keys = Vec::new();
+        for (i, c) in json.char_indices() {
+            match c {
+                '{' | '[' => depth += 1,
+                '}' | ']' => depth -= 1,
+                '"' if depth == 1 && json[..i].ends_with([',', '{']) => {
+                    let end = json[i + 1..].find('"').unwrap();
+                    keys.push(&json[i + 1..i + 1 + end]);
+                }
+                _ => {}
+            }
+        }
+        assert!(keys.contains(&"peers") && keys.contains(&"repo"));
+        assert!(keys.contains(&"socket_options"));
+        let udp = info.protocol("UDP");
+        let extras = JsonExtras {
+            quic: Some(HashMap::new()),
+            ..extras
+        };
+        let udp = PortInfo { port: 5353, ..udp };
+        let json = port_info_json(&udp, None, Some(&extras));
+        assert!(json.contains(r#","quic":null"#), "{}", json);
+        assert!(
+            json.contains(r#","service":"mDNS","service_reply":null"#),
+            "{}",
+            json
+        );
+        keys.extend(["quic", "service", "service_reply", "resolver"]);
+        for key in keys {
+            assert!(
+                PORTS_SCHEMA.contains(&format!(r#""{}": {{"#, key)),
+                "schema is missing {}",
+                key
+            );
+        }
+        assert!(PORTS_SCHEMA.contains(&format!(
+            r#""schema_version": {{ "const": {} }}"#,
+            JSON_SCHEMA_VERSION
+        )));
+    }
+
     // ── json_escape ─────────────────────────────────────────────────
 
     #[test]
@@ -1723,6 +5806,27 @@ mod tests {
 
     // ── truncate_cmd ────────────────────────────────────────────────
 
+    #[test]
+    fn parse_pick_answers() {
+        assert_eq!(parse_pick("1\n", 3), Some(0));
+        assert_eq!(parse_pick(" #3 ", 3), Some(2));
+        assert_eq!(parse_pick("0", 3), None);
+        assert_eq!(parse_pick("4", 3), None);
+        assert_eq!(parse_pick("node", 3), None);
+    }
+
+    #[test]
+    fn process_tree_walks_descendants_once() {
+        let children = |pid: u32| match pid {
+            10 => vec![11, 12],
+            11 => vec![13],
+            13 => vec![10],
+            _ => Vec::new(),
+        };
+        assert_eq!(process_tree(10, children), vec![10, 11, 12, 13]);
+        assert_eq!(process_tree(99, children), vec![99]);
+    }
+
     #[test]
     fn truncate_cmd_short() {
         assert_eq!(truncate_cmd("abc", 10), "abc");
@@ -1765,6 +5869,29 @@ mod tests {
         assert!(result.ends_with('…'));
     }
 
+    #[test]
+    fn plain_table_line_pads_all_but_last() {
+        let cells: Vec<String> = [
+            "3000",
+            "TCP",
+            "4242",
+            "dev",
+            "node",
+            "2m",
+            "1 MB",
+            "node a.js",
+        ]
+        .iter()
+        .map(|c| c.to_string())
+        .collect();
+        assert_eq!(
+            plain_table_line(&cells, &[4, 5, 4, 4, 7, 6, 4]),
+            "3000  TCP    4242  dev   node         2m  1 MB  node a.js"
+        );
+        assert_eq!(TableLayout::from_flags(true, true), TableLayout::Labeled);
+        assert_eq!(TableLayout::from_flags(false, true), TableLayout::Boxed);
+    }
+
     // ── wrap_cmd ───────────────────────────────────────────────────
 
     #[test]
@@ -1788,6 +5915,18 @@ mod tests {
         );
     }
 
+    // ── scroll_cmd ─────────────────────────────────────────────────
+
+    #[test]
+    fn scroll_cmd_marks_hidden_text() {
+        assert_eq!(scroll_cmd("abcdefghij", 0, 10), "abcdefghij");
+        assert_eq!(scroll_cmd("abcdefghij", 0, 5), "abcd…");
+        assert_eq!(scroll_cmd("abcdefghij", 3, 5), "…efg…");
+        assert_eq!(scroll_cmd("abcdefghij", 5, 5), "…ghij");
+        assert_eq!(scroll_cmd("abc", 8, 5), "…");
+        assert_eq!(scroll_cmd("café au lait", 2, 5), "…é a…");
+    }
+
     // ── format_addr ─────────────────────────────────────────────────
 
     #[test]
@@ -1937,6 +6076,81 @@ mod tests {
         );
     }
 
+    // ── kill confirmation ───────────────────────────────────────────
+
+    #[test]
+    fn kill_summary_shows_uptime_owner_and_memory() {
+        let mut info = PortInfo::listener(5432, 1203, "postgres")
+            .user("postgres")
+            .usage(40 * 1024 * 1024, 0.0)
+            .started(SystemTime::now() - Duration::from_secs(90 * 60))
+            .children(6);
+        assert_eq!(kill_summary(&info), "up 1h 30m · user postgres · 40 MB");
+        info.start_time = None;
+        info.user = String::new();
+        assert_eq!(kill_summary(&info), "40 MB");
+    }
+
+    // ── watch --changes-only ────────────────────────────────────────
+
+    #[test]
+    fn watch_key_ignores_usage_and_order() {
+        let node = PortInfo::listener(3000, 4242, "node")
+            .command("node server.js")
+            .usage(80 * 1024 * 1024, 1.0)
+            .addr(IpAddr::V4(Ipv4Addr::LOCALHOST));
+        let redis = PortInfo {
+            port: 6379,
+            process_name: "redis-server".to_string(),
+            pid: 900,
+            ..node.clone()
+        };
+        let mut busier = node.clone();
+        busier.memory_bytes *= 2;
+        busier.cpu_seconds += 5.0;
+        assert_eq!(watch_key(&[&node, &redis]), watch_key(&[&redis, &busier]));
+
+        let restarted = PortInfo {
+            pid: 4300,
+            ..node.clone()
+        };
+        assert_ne!(watch_key(&[&node]), watch_key(&[&restarted]));
+        assert_ne!(watch_key(&[&node]), watch_key(&[&node, &redis]));
+    }
+
+    #[test]
+    fn orphan_warning_only_with_children() {
+        assert_eq!(orphan_warning(0), None);
+        assert_eq!(
+            orphan_warning(1).as_deref(),
+            Some("1 child process will be orphaned")
+        );
+        assert_eq!(
+            orphan_warning(6).as_deref(),
+            Some("6 child processes will be orphaned")
+        );
+    }
+
+    // ── parse_duration ──────────────────────────────────────────────
+
+    #[test]
+    fn parse_duration_units() {
+        assert_eq!(parse_duration("30"), Some(Duration::from_secs(30)));
+        assert_eq!(parse_duration("30s"), Some(Duration::from_secs(30)));
+        assert_eq!(parse_duration("5m"), Some(Duration::from_secs(300)));
+        assert_eq!(parse_duration("2h"), Some(Duration::from_secs(7200)));
+        assert_eq!(parse_duration("1d"), Some(Duration::from_secs(86400)));
+        assert_eq!(parse_duration("250ms"), Some(Duration::from_millis(250)));
+    }
+
+    #[test]
+    fn parse_duration_invalid() {
+        assert_eq!(parse_duration(""), None);
+        assert_eq!(parse_duration("s"), None);
+        assert_eq!(parse_duration("10x"), None);
+        assert_eq!(parse_duration("-5"), None);
+    }
+
     // ── color_name_to_style ─────────────────────────────────────────
 
     #[test]
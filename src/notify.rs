@@ -0,0 +1,303 @@
+use std::process::{Command, Stdio};
+
+use crate::events::{PortEvent, PortEventKind};
+use crate::suggest::parse_port_range;
+
+// ── Rules ────────────────────────────────────────────────────────────
+
+/// A `--notify` rule such as `port=22 state=opened`. Every condition given
+/// must match; omitted keys match anything.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub(crate) struct NotifyRule {
+    ports: Option<(u16, u16)>,
+    kind: Option<PortEventKind>,
+    /// Lowercased substring of the process name.
+    process: Option<String>,
+    protocol: Option<String>,
+}
+
+impl NotifyRule {
+    /// Parse `key=value` pairs separated by spaces or commas. Keys: `port`
+    /// (a number or range like `8000-8999`), `state` (`opened`/`closed`),
+    /// `process` and `proto`. `any` matches every event.
+    pub(crate) fn parse(src: &str) -> Result<Self, String> {
+        let mut rule = NotifyRule::default();
+        let tokens: Vec<&str> = src
+            .split(|c: char| c.is_whitespace() || c == ',')
+            .filter(|t| !t.is_empty())
+            .collect();
+        if tokens.is_empty() {
+            return Err("empty notify rule (use 'any' to match every event)".to_string());
+        }
+        if tokens == ["any"] {
+            return Ok(rule);
+        }
+
+        for token in tokens {
+            let (key, value) = token
+                .split_once('=')
+                .ok_or_else(|| format!("expected key=value in notify rule, got '{}'", token))?;
+            match key {
+                "port" => {
+                    let range = value
+                        .parse::<u16>()
+                        .ok()
+                        .map(|p| (p, p))
+                        .or_else(|| parse_port_range(value))
+                        .ok_or_else(|| format!("invalid port '{}' in notify rule", value))?;
+                    rule.ports = Some(range);
+                }
+                "state" | "event" => {
+                    rule.kind = Some(match value {
+                        "opened" | "open" => PortEventKind::Opened,
+                        "closed" | "close" => PortEventKind::Closed,
+                        _ => {
+                            return Err(format!(
+                                "invalid state '{}' in notify rule (use opened or closed)",
+                                value
+                            ))
+                        }
+                    });
+                }
+                "process" => rule.process = Some(value.to_lowercase()),
+                "proto" | "protocol" => rule.protocol = Some(value.to_uppercase()),
+                _ => {
+                    return Err(format!(
+                        "unknown key '{}' in notify rule (use port, state, process or proto)",
+                        key
+                    ))
+                }
+            }
+        }
+        Ok(rule)
+    }
+
+    pub(crate) fn matches(&self, event: &PortEvent) -> bool {
+        self.ports
+            .is_none_or(|(lo, hi)| (lo..=hi).contains(&event.port))
+            && self.kind.is_none_or(|k| k == event.kind)
+            && self
+                .process
+                .as_ref()
+                .is_none_or(|p| event.process.to_lowercase().contains(p))
+            && self
+                .protocol
+                .as_ref()
+                .is_none_or(|p| event.protocol.starts_with(p.as_str()))
+    }
+}
+
+/// Writes the rule back in the form [`NotifyRule::parse`] reads.
+impl std::fmt::Display for NotifyRule {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let mut tokens = Vec::new();
+        match self.ports {
+            Some((lo, hi)) if lo == hi => tokens.push(format!("port={}", lo)),
+            Some((lo, hi)) => tokens.push(format!("port={}-{}", lo, hi)),
+            None => {}
+        }
+        if let Some(kind) = self.kind {
+            tokens.push(format!("state={}", kind.as_str()));
+        }
+        if let Some(ref p) = self.process {
+            tokens.push(format!("process={}", p));
+        }
+        if let Some(ref p) = self.protocol {
+            tokens.push(format!("proto={}", p));
+        }
+        if tokens.is_empty() {
+            return write!(f, "any");
+        }
+        write!(f, "{}", tokens.join(" "))
+    }
+}
+
+/// Send a desktop notification for each event matched by any rule.
+/// Returns the events that fired, so callers can echo them.
+pub(crate) fn notify_matching<'a>(
+    rules: &[NotifyRule],
+    events: &'a [PortEvent],
+) -> Vec<&'a PortEvent> {
+    let fired: Vec<&PortEvent> = events
+        .iter()
+        .filter(|e| rules.iter().any(|r| r.matches(e)))
+        .collect();
+    for event in &fired {
+        send_notification("portview", &event.describe());
+    }
+    fired
+}
+
+// ── Platform delivery ────────────────────────────────────────────────
+
+/// Fire-and-forget: the helper runs on a background thread so a slow or
+/// missing notifier never stalls the watch loop. Failures are ignored.
+pub(crate) fn send_notification(title: &str, body: &str) {
+    let mut cmd = notification_command(title, body);
+    cmd.stdin(Stdio::null())
+        .stdout(Stdio::null())
+        .stderr(Stdio::null());
+    std::thread::spawn(move || {
+        let _ = cmd.status();
+    });
+}
+
+#[cfg(target_os = "linux")]
+fn notification_command(title: &str, body: &str) -> Command {
+    let mut cmd = Command::new("notify-send");
+    cmd.args(["--app-name=portview", title, body]);
+    cmd
+}
+
+#[cfg(target_os = "macos")]
+fn notification_command(title: &str, body: &str) -> Command {
+    let script = format!(
+        "display notification {} with title {}",
+        applescript_string(body),
+        applescript_string(title)
+    );
+    let mut cmd = Command::new("osascript");
+    cmd.args(["-e", &script]);
+    cmd
+}
+
+#[cfg(target_os = "windows")]
+fn notification_command(title: &str, body: &str) -> Command {
+    // Toasts need a registered AppUserModelID; borrow PowerShell's
+    let script = format!(
+        "[Windows.UI.Notifications.ToastNotificationManager, Windows.UI.Notifications, ContentType = WindowsRuntime] > $null; \
+         $t = [Windows.UI.Notifications.ToastNotificationManager]::GetTemplateContent([Windows.UI.Notifications.ToastTemplateType]::ToastText02); \
+         $x = $t.GetElementsByTagName('text'); \
+         $x.Item(0).AppendChild($t.CreateTextNode({})) > $null; \
+         $x.Item(1).AppendChild($t.CreateTextNode({})) > $null; \
+         [Windows.UI.Notifications.ToastNotificationManager]::CreateToastNotifier('{{1AC14E77-02E7-4E5D-B744-2EB1AE5198B7}}\\WindowsPowerShell\\v1.0\\powershell.exe').Show([Windows.UI.Notifications.ToastNotification]::new($t))",
+        powershell_string(title),
+        powershell_string(body)
+    );
+    let mut cmd = Command::new("powershell");
+    cmd.args(["-NoProfile", "-NonInteractive", "-Command", &script]);
+    cmd
+}
+
+#[cfg(any(target_os = "macos", test))]
+fn applescript_string(s: &str) -> String {
+    format!("\"{}\"", s.replace('\\', "\\\\").replace('"', "\\\""))
+}
+
+#[cfg(any(target_os = "windows", test))]
+fn powershell_string(s: &str) -> String {
+    format!("'{}'", s.replace('\'', "''"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn event(kind: PortEventKind, port: u16, process: &str) -> PortEvent {
+        PortEvent {
+            kind,
+            port,
+            protocol: "TCP".to_string(),
+            pid: 42,
+            process: process.to_string(),
+        }
+    }
+
+    #[test]
+    fn parse_and_match_port_state() {
+        let rule = NotifyRule::parse("port=22 state=opened").unwrap();
+        assert!(rule.matches(&event(PortEventKind::Opened, 22, "sshd")));
+        assert!(!rule.matches(&event(PortEventKind::Closed, 22, "sshd")));
+        assert!(!rule.matches(&event(PortEventKind::Opened, 2222, "sshd")));
+    }
+
+    #[test]
+    fn parse_range_process_and_proto() {
+        let rule = NotifyRule::parse("port=8000-8999,process=Python,proto=tcp").unwrap();
+        assert!(rule.matches(&event(PortEventKind::Closed, 8080, "python3")));
+        assert!(!rule.matches(&event(PortEventKind::Closed, 8080, "node")));
+        assert!(!rule.matches(&event(PortEventKind::Closed, 9000, "python3")));
+    }
+
+    #[test]
+    fn any_matches_everything() {
+        let rule = NotifyRule::parse("any").unwrap();
+        assert!(rule.matches(&event(PortEventKind::Opened, 1, "x")));
+    }
+
+    #[test]
+    fn parse_errors() {
+        assert!(NotifyRule::parse("").is_err());
+        assert!(NotifyRule::parse("port=abc").is_err());
+        assert!(NotifyRule::parse("state=listening").is_err());
+        assert!(NotifyRule::parse("colour=red").is_err());
+        assert!(NotifyRule::parse("port22").is_err());
+    }
+
+    #[test]
+    fn script_quoting() {
+        assert_eq!(
+            applescript_string(r#"say "hi" \o/"#),
+            r#""say \"hi\" \\o/""#
+        );
+        assert_eq!(powershell_string("it's"), "'it''s'");
+    }
+}
+
+// ── Property tests ───────────────────────────────────────────────────
+//
+// `cargo test --features fuzz`: no `--notify` text panics the parser, and
+// every rule it accepts prints back to text that parses the same.
+
+#[cfg(all(test, feature = "fuzz"))]
+mod fuzz {
+    use super::*;
+    use proptest::prelude::*;
+
+    fn port_range() -> impl Strategy<Value = (u16, u16)> {
+        (1u16..=u16::MAX).prop_flat_map(|lo| (Just(lo), lo..=u16::MAX))
+    }
+
+    proptest! {
+        #[test]
+        fn rule_never_panics(s in "\\PC*") {
+            let _ = NotifyRule::parse(&s);
+        }
+
+        #[test]
+        fn rules_round_trip(
+            ports in prop::option::of(port_range()),
+            kind in prop::option::of(prop_oneof![
+                Just(PortEventKind::Opened),
+                Just(PortEventKind::Closed),
+            ]),
+            process in prop::option::of("[A-Za-z0-9._-]{0,12}"),
+            protocol in prop::option::of("[A-Za-z0-9]{1,5}"),
+            separator in "[ \\t,]{1,3}",
+        ) {
+            let mut tokens = Vec::new();
+            if let Some((lo, hi)) = ports {
+                tokens.push(format!("port={}-{}", lo, hi));
+            }
+            if let Some(kind) = kind {
+                tokens.push(format!("state={}", kind.as_str()));
+            }
+            if let Some(ref p) = process {
+                tokens.push(format!("process={}", p));
+            }
+            if let Some(ref p) = protocol {
+                tokens.push(format!("proto={}", p));
+            }
+            if tokens.is_empty() {
+                tokens.push("any".to_string());
+            }
+
+            let rule = NotifyRule::parse(&tokens.join(&separator)).unwrap();
+            prop_assert_eq!(rule.ports, ports);
+            prop_assert_eq!(rule.kind, kind);
+            prop_assert_eq!(&rule.process, &process.map(|p| p.to_lowercase()));
+            prop_assert_eq!(&rule.protocol, &protocol.map(|p| p.to_uppercase()));
+            prop_assert_eq!(NotifyRule::parse(&rule.to_string()), Ok(rule));
+        }
+    }
+}
@@ -0,0 +1,166 @@
+use std::collections::HashMap;
+
+use crate::{collector, PortInfo};
+
+// ── Orphaned listeners ───────────────────────────────────────────────
+//
+// The hardest "address already in use" cases are sockets whose owner
+// nobody is watching: a zombie whose parent never reaped it (common with
+// PID 1 inside a container that isn't an init), or a dev server left
+// running after the terminal that started it was closed. The table flags
+// them and the detail view says what usually frees the port.
+
+/// Where a process sits in the process tree, as far as orphan checks go.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub(crate) struct Lineage {
+    /// Exited but not yet reaped (`Z` in ps).
+    pub(crate) zombie: bool,
+    pub(crate) ppid: u32,
+    /// Session ID: the PID of the shell or daemon that started the
+    /// session.
+    pub(crate) session: u32,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub(crate) enum Orphan {
+    /// Exited, but its parent hasn't reaped it; threads that are still
+    /// running keep the socket bound.
+    Zombie { parent: u32 },
+    /// The session that started it, usually a terminal, is gone.
+    Abandoned { session: u32, parent: u32 },
+}
+
+impl Orphan {
+    /// `session_alive` says whether the session leader still exists.
+    pub(crate) fn classify(pid: u32, lineage: Lineage, session_alive: bool) -> Option<Self> {
+        if lineage.zombie {
+            return Some(Orphan::Zombie {
+                parent: lineage.ppid,
+            });
+        }
+        // Session 0 holds kernel threads; daemons lead their own session
+        let detached = lineage.session != 0 && lineage.session != pid;
+        (detached && !session_alive).then_some(Orphan::Abandoned {
+            session: lineage.session,
+            parent: lineage.ppid,
+        })
+    }
+
+    /// The table flag.
+    pub(crate) fn label(self) -> &'static str {
+        match self {
+            Orphan::Zombie { .. } => "zombie",
+            Orphan::Abandoned { .. } => "orphaned",
+        }
+    }
+
+    /// Color name for the flag.
+    pub(crate) fn color(self) -> &'static str {
+        match self {
+            Orphan::Zombie { .. } => "red",
+            Orphan::Abandoned { .. } => "yellow",
+        }
+    }
+
+    pub(crate) fn explain(self) -> String {
+        match self {
+            Orphan::Zombie { parent } => format!(
+                "zombie: it has exited, but its parent (PID {}) hasn't reaped it, so the socket stays bound",
+                parent
+            ),
+            Orphan::Abandoned { session, parent } => format!(
+                "orphaned: the session that started it (SID {}) is gone, usually a closed terminal; PID {} adopted it",
+                session, parent
+            ),
+        }
+    }
+
+    pub(crate) fn remedy(self) -> String {
+        match self {
+            Orphan::Zombie { parent: 0 | 1 } => "its parent is PID 1: in a container, restart the container (and run it with --init so children get reaped); on a host, only a reboot frees it".to_string(),
+            Orphan::Zombie { parent } => format!(
+                "kill or restart the parent, PID {}, so the zombie is reaped and the port freed",
+                parent
+            ),
+            Orphan::Abandoned { .. } => {
+                "nothing will stop it on its own; kill it if it's a leftover dev server, or run it under a service manager".to_string()
+            }
+        }
+    }
+}
+
+/// The orphan status of one process, via the active collector.
+pub(crate) fn detect(pid: u32) -> Option<Orphan> {
+    if pid == 0 {
+        return None;
+    }
+    let collector = collector::active();
+    let lineage = collector.process_lineage(pid)?;
+    let session_alive = lineage.session == 0
+        || lineage.session == pid
+        || collector.process_alive(lineage.session).unwrap_or(true);
+    Orphan::classify(pid, lineage, session_alive)
+}
+
+/// Orphaned processes among the rows, by PID.
+pub(crate) fn scan(infos: &[PortInfo]) -> HashMap<u32, Orphan> {
+    let mut orphans = HashMap::new();
+    for info in infos {
+        if info.pid != 0 && !orphans.contains_key(&info.pid) {
+            if let Some(orphan) = detect(info.pid) {
+                orphans.insert(info.pid, orphan);
+            }
+        }
+    }
+    orphans
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn lineage(zombie: bool, ppid: u32, session: u32) -> Lineage {
+        Lineage {
+            zombie,
+            ppid,
+            session,
+        }
+    }
+
+    #[test]
+    fn classify_processes() {
+        // A daemon leading its own session is fine, with or without init as parent
+        assert_eq!(Orphan::classify(900, lineage(false, 1, 900), false), None);
+        // Started from a terminal that's still open
+        assert_eq!(
+            Orphan::classify(4242, lineage(false, 4200, 4100), true),
+            None
+        );
+        assert_eq!(
+            Orphan::classify(4242, lineage(false, 1, 4100), false),
+            Some(Orphan::Abandoned {
+                session: 4100,
+                parent: 1
+            })
+        );
+        assert_eq!(
+            Orphan::classify(4242, lineage(true, 77, 4100), true),
+            Some(Orphan::Zombie { parent: 77 })
+        );
+        assert_eq!(Orphan::classify(2, lineage(false, 0, 0), false), None);
+    }
+
+    #[test]
+    fn remedies_depend_on_the_parent() {
+        assert!(Orphan::Zombie { parent: 1 }.remedy().contains("--init"));
+        assert!(Orphan::Zombie { parent: 77 }.remedy().contains("PID 77"));
+        assert_eq!(
+            Orphan::Abandoned {
+                session: 4100,
+                parent: 1
+            }
+            .label(),
+            "orphaned"
+        );
+    }
+}
@@ -0,0 +1,118 @@
+use std::io::{self, Write};
+use std::process::Child;
+#[cfg(unix)]
+use std::process::{Command, Stdio};
+
+#[cfg(unix)]
+use crate::config;
+
+// ── Pager ────────────────────────────────────────────────────────────
+//
+// Long tables can go through a pager the way git's output does: stdout is
+// pointed at the pager's stdin for the rest of the run, so the table code
+// doesn't know it's there. Colors are decided before the switch, and
+// `less` gets `LESS=FRX` unless the user set LESS: pass colors through,
+// quit straight away when the table fits on one screen, and leave it on
+// screen afterwards.
+
+/// The pager command: `PORTVIEW_PAGER`, then `[pager] command`, then
+/// `$PAGER`, then `less`. Empty or `cat` means no pager.
+#[cfg(unix)]
+pub(crate) fn resolve(
+    portview_pager: Option<String>,
+    configured: Option<String>,
+    pager: Option<String>,
+) -> Option<String> {
+    let command = portview_pager
+        .or(configured)
+        .or(pager)
+        .unwrap_or_else(|| "less".to_string());
+    let command = command.trim();
+    (!command.is_empty() && command != "cat").then(|| command.to_string())
+}
+
+/// A running pager. Dropping it flushes the output, hands stdout back and
+/// waits for the user to quit the pager.
+pub(crate) struct Pager {
+    child: Child,
+    #[cfg(unix)]
+    saved_stdout: i32,
+}
+
+/// Start the pager and send stdout to it. `None` when there is no pager
+/// or it can't be started; output then goes to the terminal as usual.
+#[cfg(unix)]
+pub(crate) fn start() -> Option<Pager> {
+    use std::os::unix::io::AsRawFd;
+
+    let command = resolve(
+        std::env::var("PORTVIEW_PAGER").ok(),
+        config::active().pager.clone(),
+        std::env::var("PAGER").ok(),
+    )?;
+    let mut shell = Command::new("sh");
+    shell.arg("-c").arg(&command).stdin(Stdio::piped());
+    if std::env::var_os("LESS").is_none() {
+        shell.env("LESS", "FRX");
+    }
+    let mut child = shell.spawn().ok()?;
+    let stdin = child.stdin.take()?;
+    let _ = io::stdout().flush();
+    // SAFETY: plain descriptor calls; fd 1 keeps pointing at a valid file
+    // throughout, and the saved copy is restored on drop.
+    let saved_stdout = unsafe { libc::dup(1) };
+    if saved_stdout < 0 || unsafe { libc::dup2(stdin.as_raw_fd(), 1) } < 0 {
+        drop(stdin);
+        let _ = child.wait();
+        return None;
+    }
+    drop(stdin);
+    Some(Pager {
+        child,
+        saved_stdout,
+    })
+}
+
+/// Windows consoles have no pager to hand off to.
+#[cfg(not(unix))]
+pub(crate) fn start() -> Option<Pager> {
+    None
+}
+
+impl Drop for Pager {
+    fn drop(&mut self) {
+        let _ = io::stdout().flush();
+        // Restoring fd 1 closes the last write end, so the pager sees EOF
+        #[cfg(unix)]
+        unsafe {
+            libc::dup2(self.saved_stdout, 1);
+            libc::close(self.saved_stdout);
+        }
+        let _ = self.child.wait();
+    }
+}
+
+#[cfg(all(test, unix))]
+mod tests {
+    use super::*;
+
+    fn some(s: &str) -> Option<String> {
+        Some(s.to_string())
+    }
+
+    #[test]
+    fn pager_command_precedence() {
+        assert_eq!(resolve(None, None, None), some("less"));
+        assert_eq!(resolve(None, None, some("most")), some("most"));
+        assert_eq!(
+            resolve(None, some("less -RS"), some("most")),
+            some("less -RS")
+        );
+        assert_eq!(
+            resolve(some("bat -p"), some("less -RS"), None),
+            some("bat -p")
+        );
+        assert_eq!(resolve(some(""), None, some("most")), None);
+        assert_eq!(resolve(None, None, some("cat")), None);
+    }
+}
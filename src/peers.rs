@@ -0,0 +1,253 @@
+use std::collections::HashMap;
+use std::net::IpAddr;
+use std::time::Duration;
+
+use crate::dns::{region_hint, resolve_all, scope_hint};
+use crate::{format_addr, json_escape, ConnInfo, TcpState};
+
+/// Connections from one remote host to a local port.
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) struct PeerSummary {
+    pub(crate) remote_addr: IpAddr,
+    /// Reverse-DNS name, filled in by [`resolve_hostnames`].
+    pub(crate) hostname: Option<String>,
+    pub(crate) count: usize,
+    /// Distinct remote ports, ascending (only the first few are kept).
+    pub(crate) remote_ports: Vec<u16>,
+    /// Socket count per state, most common first.
+    pub(crate) states: Vec<(TcpState, usize)>,
+}
+
+/// Maximum remote ports remembered per peer; the rest are only counted.
+const MAX_REMOTE_PORTS: usize = 4;
+
+/// Collapse IPv4-mapped IPv6 addresses so dual-stack peers group together.
+fn canonical(addr: IpAddr) -> IpAddr {
+    match addr {
+        IpAddr::V6(v6) => v6.to_ipv4_mapped().map(IpAddr::V4).unwrap_or(addr),
+        v4 => v4,
+    }
+}
+
+/// Aggregate the TCP connections on `port` by remote host, busiest first.
+pub(crate) fn aggregate_peers(conns: &[ConnInfo], port: u16) -> Vec<PeerSummary> {
+    let mut by_host: HashMap<IpAddr, (Vec<u16>, HashMap<TcpState, usize>)> = HashMap::new();
+    for c in conns {
+        if c.protocol != "TCP"
+            || c.local_port != port
+            || c.state == TcpState::Listen
+            || c.remote_port == 0
+        {
+            continue;
+        }
+        let entry = by_host.entry(canonical(c.remote_addr)).or_default();
+        entry.0.push(c.remote_port);
+        *entry.1.entry(c.state).or_insert(0) += 1;
+    }
+
+    let mut peers: Vec<PeerSummary> = by_host
+        .into_iter()
+        .map(|(remote_addr, (mut ports, states))| {
+            let count = ports.len();
+            ports.sort_unstable();
+            ports.dedup();
+            ports.truncate(MAX_REMOTE_PORTS);
+            let mut states: Vec<(TcpState, usize)> = states.into_iter().collect();
+            states.sort_by(|a, b| b.1.cmp(&a.1).then_with(|| a.0.as_str().cmp(b.0.as_str())));
+            PeerSummary {
+                remote_addr,
+                hostname: None,
+                count,
+                remote_ports: ports,
+                states,
+            }
+        })
+        .collect();
+
+    peers.sort_by(|a, b| {
+        b.count
+            .cmp(&a.count)
+            .then_with(|| a.remote_addr.cmp(&b.remote_addr))
+    });
+    peers
+}
+
+/// Attach reverse-DNS names to `peers`, waiting at most `timeout`.
+pub(crate) fn resolve_hostnames(peers: &mut [PeerSummary], timeout: Duration) {
+    let addrs: Vec<IpAddr> = peers.iter().map(|p| p.remote_addr).collect();
+    let names = resolve_all(&addrs, timeout);
+    for peer in peers {
+        peer.hostname = names.get(&peer.remote_addr).cloned();
+    }
+}
+
+/// One-line description of a peer, e.g.
+/// `10.0.0.5 (laptop.lan, LAN)  ×3  ESTABLISHED 2, TIME_WAIT 1` or
+/// `8.8.8.8 (dns.google, North America)  ×1  ESTABLISHED 1`.
+pub(crate) fn format_peer(peer: &PeerSummary) -> String {
+    let states: Vec<String> = peer
+        .states
+        .iter()
+        .map(|(s, n)| format!("{} {}", s, n))
+        .collect();
+    let hints: Vec<&str> = peer
+        .hostname
+        .as_deref()
+        .into_iter()
+        .chain(scope_hint(&peer.remote_addr))
+        .chain(region_hint(&peer.remote_addr))
+        .collect();
+    let host = if hints.is_empty() {
+        format_addr(&peer.remote_addr)
+    } else {
+        format!("{} ({})", format_addr(&peer.remote_addr), hints.join(", "))
+    };
+    format!("{}  ×{}  {}", host, peer.count, states.join(", "))
+}
+
+fn json_opt_str(value: Option<&str>) -> String {
+    value
+        .map(|v| format!(r#""{}""#, json_escape(v)))
+        .unwrap_or_else(|| "null".to_string())
+}
+
+pub(crate) fn peers_json(peers: &[PeerSummary]) -> String {
+    let items: Vec<String> = peers
+        .iter()
+        .map(|p| {
+            let ports: Vec<String> = p.remote_ports.iter().map(|r| r.to_string()).collect();
+            let states: Vec<String> = p
+                .states
+                .iter()
+                .map(|(s, n)| format!(r#""{}":{}"#, s, n))
+                .collect();
+            format!(
+                r#"{{"remote_addr":"{}","hostname":{},"scope":{},"region":{},"count":{},"remote_ports":[{}],"states":{{{}}}}}"#,
+                json_escape(&format_addr(&p.remote_addr)),
+                json_opt_str(p.hostname.as_deref()),
+                json_opt_str(scope_hint(&p.remote_addr)),
+                json_opt_str(region_hint(&p.remote_addr)),
+                p.count,
+                ports.join(","),
+                states.join(",")
+            )
+        })
+        .collect();
+    format!("[{}]", items.join(","))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::net::{Ipv4Addr, Ipv6Addr};
+
+    fn conn(local: u16, remote: IpAddr, rport: u16, state: TcpState) -> ConnInfo {
+        ConnInfo {
+            protocol: "TCP".to_string(),
+            local_port: local,
+            remote_addr: remote,
+            remote_port: rport,
+            state,
+            pid: Some(1),
+        }
+    }
+
+    #[test]
+    fn aggregate_peers_groups_by_host() {
+        let a = IpAddr::V4(Ipv4Addr::new(10, 0, 0, 5));
+        let b = IpAddr::V4(Ipv4Addr::new(10, 0, 0, 6));
+        let conns = [
+            conn(5432, IpAddr::V4(Ipv4Addr::UNSPECIFIED), 0, TcpState::Listen),
+            conn(5432, a, 50001, TcpState::Established),
+            conn(5432, a, 50002, TcpState::Established),
+            conn(5432, a, 50003, TcpState::TimeWait),
+            conn(5432, b, 40000, TcpState::Established),
+            conn(8080, b, 40001, TcpState::Established),
+        ];
+        let peers = aggregate_peers(&conns, 5432);
+        assert_eq!(peers.len(), 2);
+        assert_eq!(peers[0].remote_addr, a);
+        assert_eq!(peers[0].count, 3);
+        assert_eq!(peers[0].remote_ports, vec![50001, 50002, 50003]);
+        assert_eq!(
+            peers[0].states,
+            vec![(TcpState::Established, 2), (TcpState::TimeWait, 1)]
+        );
+        assert_eq!(peers[1].remote_addr, b);
+    }
+
+    #[test]
+    fn aggregate_peers_merges_v4_mapped() {
+        let v4 = Ipv4Addr::new(192, 168, 1, 10);
+        let conns = [
+            conn(80, IpAddr::V4(v4), 1000, TcpState::Established),
+            conn(
+                80,
+                IpAddr::V6(v4.to_ipv6_mapped()),
+                1001,
+                TcpState::Established,
+            ),
+            conn(
+                80,
+                IpAddr::V6(Ipv6Addr::LOCALHOST),
+                1002,
+                TcpState::Established,
+            ),
+        ];
+        let peers = aggregate_peers(&conns, 80);
+        assert_eq!(peers.len(), 2);
+        assert_eq!(peers[0].remote_addr, IpAddr::V4(v4));
+        assert_eq!(peers[0].count, 2);
+    }
+
+    #[test]
+    fn format_peer_line() {
+        let peer = PeerSummary {
+            remote_addr: IpAddr::V4(Ipv4Addr::new(10, 0, 0, 5)),
+            hostname: Some("laptop.lan".to_string()),
+            count: 3,
+            remote_ports: vec![50001],
+            states: vec![(TcpState::Established, 2), (TcpState::TimeWait, 1)],
+        };
+        assert_eq!(
+            format_peer(&peer),
+            "10.0.0.5 (laptop.lan, LAN)  ×3  ESTABLISHED 2, TIME_WAIT 1"
+        );
+    }
+
+    #[test]
+    fn format_peer_public_without_hostname() {
+        let peer = PeerSummary {
+            remote_addr: IpAddr::V4(Ipv4Addr::new(203, 0, 113, 9)),
+            hostname: None,
+            count: 1,
+            remote_ports: vec![443],
+            states: vec![(TcpState::Established, 1)],
+        };
+        assert_eq!(format_peer(&peer), "203.0.113.9  ×1  ESTABLISHED 1");
+        let peer = PeerSummary {
+            remote_addr: IpAddr::V4(Ipv4Addr::new(8, 8, 8, 8)),
+            hostname: Some("dns.google".to_string()),
+            ..peer
+        };
+        assert_eq!(
+            format_peer(&peer),
+            "8.8.8.8 (dns.google, North America)  ×1  ESTABLISHED 1"
+        );
+    }
+
+    #[test]
+    fn peers_json_shape() {
+        let peer = PeerSummary {
+            remote_addr: IpAddr::V4(Ipv4Addr::new(10, 0, 0, 5)),
+            hostname: None,
+            count: 1,
+            remote_ports: vec![50001],
+            states: vec![(TcpState::Established, 1)],
+        };
+        assert_eq!(
+            peers_json(&[peer]),
+            r#"[{"remote_addr":"10.0.0.5","hostname":null,"scope":"LAN","region":null,"count":1,"remote_ports":[50001],"states":{"ESTABLISHED":1}}]"#
+        );
+    }
+}
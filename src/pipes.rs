@@ -0,0 +1,213 @@
+use std::io::{self, Write};
+
+use crossterm::style::{Attribute, SetAttribute};
+use crossterm::ExecutableCommand;
+
+use crate::{json_escape, write_styled, write_table_border};
+
+// ── Named pipes ──────────────────────────────────────────────────────
+//
+// Much of Windows' local IPC never touches TCP: the Docker engine, SQL
+// Server and MySQL all answer on named pipes under `\\.\pipe\`. `portview
+// pipes` lists them with the process serving each, the way the main table
+// lists ports.
+
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) struct NamedPipe {
+    /// Without the `\\.\pipe\` prefix.
+    pub(crate) name: String,
+    /// The server process, when the pipe had a free instance to ask.
+    pub(crate) pid: Option<u32>,
+    pub(crate) process: String,
+    /// Instances currently open, as the pipe filesystem reports it.
+    pub(crate) instances: Option<u32>,
+}
+
+impl NamedPipe {
+    /// What a well-known pipe belongs to.
+    pub(crate) fn service(&self) -> Option<&'static str> {
+        let name = self.name.to_ascii_lowercase();
+        let service = match name.as_str() {
+            "docker_engine" | "dockerdesktoplinuxengine" | "docker_engine_windows" => {
+                "Docker Engine API"
+            }
+            "mysql" => "MySQL",
+            "mysqlx" => "MySQL X Protocol",
+            "openssh-ssh-agent" => "OpenSSH agent",
+            "lsass" => "Local Security Authority",
+            "spoolss" => "Print Spooler",
+            _ if name == "sql\\query" || name.starts_with("mssql$") => "SQL Server",
+            _ if name.starts_with("pshost.") => "PowerShell host",
+            _ if name.starts_with("pgsignal_") => "PostgreSQL",
+            _ => return None,
+        };
+        Some(service)
+    }
+
+    /// Whether `filter` appears in the pipe or process name, ignoring case.
+    pub(crate) fn matches(&self, filter: &str) -> bool {
+        let filter = filter.to_lowercase();
+        self.name.to_lowercase().contains(&filter) || self.process.to_lowercase().contains(&filter)
+    }
+}
+
+/// Every named pipe on the machine, sorted by name; `None` where there
+/// are none to list.
+pub(crate) fn list() -> Option<Vec<NamedPipe>> {
+    #[cfg(windows)]
+    {
+        let mut pipes = crate::os::list_named_pipes();
+        pipes.sort_by_key(|p| p.name.to_lowercase());
+        Some(pipes)
+    }
+    #[cfg(not(windows))]
+    {
+        None
+    }
+}
+
+// ── Output ───────────────────────────────────────────────────────────
+
+pub(crate) fn display_pipes(pipes: &[NamedPipe], use_color: bool) {
+    let mut out = io::stdout();
+    if pipes.is_empty() {
+        write_styled(&mut out, "No named pipes found.\n", "dimmed", use_color);
+        return;
+    }
+
+    let headers = ["PIPE", "PID", "PROCESS", "INSTANCES", "SERVICE"];
+    let values: Vec<[String; 5]> = pipes
+        .iter()
+        .map(|p| {
+            [
+                p.name.clone(),
+                p.pid.map_or_else(|| "-".into(), |pid| pid.to_string()),
+                p.process.clone(),
+                p.instances.map_or_else(|| "-".into(), |n| n.to_string()),
+                p.service().unwrap_or("-").to_string(),
+            ]
+        })
+        .collect();
+    let mut widths = headers.map(|h| h.chars().count());
+    for v in &values {
+        for (w, cell) in widths.iter_mut().zip(v.iter()) {
+            *w = (*w).max(cell.chars().count());
+        }
+    }
+    let colors = ["cyan", "yellow", "bold", "none", "green"];
+
+    let _ = writeln!(out);
+    write_table_border(&mut out, &widths, "╭", "┬", "╮");
+    let _ = write!(out, "│");
+    for (&w, &h) in widths.iter().zip(headers.iter()) {
+        let _ = write!(out, " ");
+        if use_color {
+            let _ = out.execute(SetAttribute(Attribute::Bold));
+        }
+        let _ = write!(out, "{:<width$}", h, width = w);
+        if use_color {
+            let _ = out.execute(SetAttribute(Attribute::Reset));
+        }
+        let _ = write!(out, " │");
+    }
+    let _ = writeln!(out);
+    write_table_border(&mut out, &widths, "├", "┼", "┤");
+
+    for v in &values {
+        let _ = write!(out, "│");
+        for (i, (&w, cell)) in widths.iter().zip(v.iter()).enumerate() {
+            let _ = write!(out, " ");
+            // Right-align INSTANCES
+            let padded = if i == 3 {
+                format!("{:>width$}", cell, width = w)
+            } else {
+                format!("{:<width$}", cell, width = w)
+            };
+            let color = if cell == "-" { "dimmed" } else { colors[i] };
+            write_styled(&mut out, &padded, color, use_color);
+            let _ = write!(out, " │");
+        }
+        let _ = writeln!(out);
+    }
+    write_table_border(&mut out, &widths, "╰", "┴", "╯");
+    let unknown = pipes.iter().filter(|p| p.pid.is_none()).count();
+    if unknown > 0 {
+        write_styled(
+            &mut out,
+            &format!(
+                "  {} pipe{} without a known server: every instance busy, or access denied\n",
+                unknown,
+                if unknown == 1 { "" } else { "s" }
+            ),
+            "dimmed",
+            use_color,
+        );
+    }
+}
+
+pub(crate) fn pipes_json(pipes: &[NamedPipe]) -> String {
+    let optional = |n: Option<u32>| n.map_or_else(|| "null".to_string(), |n| n.to_string());
+    let items: Vec<String> = pipes
+        .iter()
+        .map(|p| {
+            format!(
+                r#"{{"name":"{}","path":"{}","pid":{},"process":"{}","instances":{},"service":{}}}"#,
+                json_escape(&p.name),
+                json_escape(&format!(r"\\.\pipe\{}", p.name)),
+                optional(p.pid),
+                json_escape(&p.process),
+                optional(p.instances),
+                p.service()
+                    .map_or_else(|| "null".to_string(), |s| format!("\"{}\"", s))
+            )
+        })
+        .collect();
+    format!("[{}]", items.join(","))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn pipe(name: &str, pid: Option<u32>, process: &str) -> NamedPipe {
+        NamedPipe {
+            name: name.to_string(),
+            pid,
+            process: process.to_string(),
+            instances: Some(1),
+        }
+    }
+
+    #[test]
+    fn well_known_pipes() {
+        let docker = pipe("docker_engine", Some(4120), "com.docker.backend.exe");
+        assert_eq!(docker.service(), Some("Docker Engine API"));
+        assert!(docker.matches("DOCKER"));
+        assert!(docker.matches("backend"));
+        assert!(!docker.matches("mysql"));
+        assert_eq!(
+            pipe(r"MSSQL$SQLEXPRESS\sql\query", None, "-").service(),
+            Some("SQL Server")
+        );
+        assert_eq!(
+            pipe("PSHost.1337.9120.DefaultAppDomain.pwsh", None, "-").service(),
+            Some("PowerShell host")
+        );
+        assert_eq!(pipe("chrome.sync.123", None, "-").service(), None);
+    }
+
+    #[test]
+    fn json_shape() {
+        let pipes = [
+            pipe("docker_engine", Some(4120), "com.docker.backend.exe"),
+            NamedPipe {
+                instances: None,
+                ..pipe("busy", None, "-")
+            },
+        ];
+        assert_eq!(
+            pipes_json(&pipes),
+            r#"[{"name":"docker_engine","path":"\\\\.\\pipe\\docker_engine","pid":4120,"process":"com.docker.backend.exe","instances":1,"service":"Docker Engine API"},{"name":"busy","path":"\\\\.\\pipe\\busy","pid":null,"process":"-","instances":null,"service":null}]"#
+        );
+    }
+}
@@ -0,0 +1,167 @@
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use crate::json_escape;
+
+/// The git checkout a process is running from.
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) struct RepoInfo {
+    /// Name of the checkout directory (what people usually call the project).
+    pub(crate) name: String,
+    pub(crate) root: PathBuf,
+    /// Branch name, or a short commit hash when HEAD is detached.
+    pub(crate) branch: Option<String>,
+}
+
+// ── Discovery ────────────────────────────────────────────────────────
+
+/// Walk up from `start` to the nearest directory containing `.git`.
+pub(crate) fn find_repo(start: &Path) -> Option<RepoInfo> {
+    let mut dir = Some(start);
+    while let Some(d) = dir {
+        let dot_git = d.join(".git");
+        if let Some(git_dir) = resolve_git_dir(&dot_git) {
+            let name = d
+                .file_name()
+                .map(|n| n.to_string_lossy().to_string())
+                .unwrap_or_else(|| d.to_string_lossy().to_string());
+            return Some(RepoInfo {
+                name,
+                root: d.to_path_buf(),
+                branch: read_head(&git_dir),
+            });
+        }
+        dir = d.parent();
+    }
+    None
+}
+
+/// `.git` is usually a directory, but worktrees and submodules use a file
+/// containing `gitdir: <path>`.
+fn resolve_git_dir(dot_git: &Path) -> Option<PathBuf> {
+    if dot_git.is_dir() {
+        return Some(dot_git.to_path_buf());
+    }
+    let contents = fs::read_to_string(dot_git).ok()?;
+    let target = contents.trim().strip_prefix("gitdir:")?.trim();
+    let path = Path::new(target);
+    Some(if path.is_absolute() {
+        path.to_path_buf()
+    } else {
+        dot_git.parent()?.join(path)
+    })
+}
+
+fn read_head(git_dir: &Path) -> Option<String> {
+    parse_head(&fs::read_to_string(git_dir.join("HEAD")).ok()?)
+}
+
+fn parse_head(head: &str) -> Option<String> {
+    let head = head.trim();
+    if let Some(reference) = head.strip_prefix("ref:") {
+        let reference = reference.trim();
+        return Some(
+            reference
+                .strip_prefix("refs/heads/")
+                .unwrap_or(reference)
+                .to_string(),
+        );
+    }
+    // Detached HEAD: show a short hash like `git status` does
+    (head.len() >= 7 && head.chars().all(|c| c.is_ascii_hexdigit())).then(|| head[..7].to_string())
+}
+
+// ── Output ───────────────────────────────────────────────────────────
+
+/// `shop-web (feature/login)`, or just the name when HEAD is unreadable.
+pub(crate) fn format_repo(repo: &RepoInfo) -> String {
+    match repo.branch {
+        Some(ref branch) => format!("{} ({})", repo.name, branch),
+        None => repo.name.clone(),
+    }
+}
+
+pub(crate) fn repo_json(repo: &RepoInfo) -> String {
+    format!(
+        r#"{{"name":"{}","root":"{}","branch":{}}}"#,
+        json_escape(&repo.name),
+        json_escape(&repo.root.to_string_lossy()),
+        repo.branch
+            .as_ref()
+            .map(|b| format!(r#""{}""#, json_escape(b)))
+            .unwrap_or_else(|| "null".to_string())
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn scratch_dir(name: &str) -> PathBuf {
+        let dir =
+            std::env::temp_dir().join(format!("portview-project-{}-{}", name, std::process::id()));
+        let _ = fs::remove_dir_all(&dir);
+        fs::create_dir_all(&dir).unwrap();
+        dir
+    }
+
+    #[test]
+    fn parse_head_branch_and_detached() {
+        assert_eq!(
+            parse_head("ref: refs/heads/feature/login\n"),
+            Some("feature/login".to_string())
+        );
+        assert_eq!(
+            parse_head("3f2a9c1d0e8b7a6f5e4d3c2b1a0f9e8d7c6b5a49\n"),
+            Some("3f2a9c1".to_string())
+        );
+        assert_eq!(parse_head("garbage"), None);
+    }
+
+    #[test]
+    fn find_repo_walks_up_from_subdirectory() {
+        let root = scratch_dir("walk").join("shop-web");
+        fs::create_dir_all(root.join(".git")).unwrap();
+        fs::write(root.join(".git/HEAD"), "ref: refs/heads/main\n").unwrap();
+        let nested = root.join("packages/api/src");
+        fs::create_dir_all(&nested).unwrap();
+
+        let repo = find_repo(&nested).expect("repo should be found");
+        assert_eq!(repo.name, "shop-web");
+        assert_eq!(repo.root, root);
+        assert_eq!(repo.branch.as_deref(), Some("main"));
+        assert_eq!(format_repo(&repo), "shop-web (main)");
+    }
+
+    #[test]
+    fn find_repo_follows_worktree_gitdir_file() {
+        let base = scratch_dir("worktree");
+        let git_dir = base.join("main/.git/worktrees/hotfix");
+        fs::create_dir_all(&git_dir).unwrap();
+        fs::write(git_dir.join("HEAD"), "ref: refs/heads/hotfix\n").unwrap();
+        let checkout = base.join("hotfix");
+        fs::create_dir_all(&checkout).unwrap();
+        fs::write(
+            checkout.join(".git"),
+            format!("gitdir: {}\n", git_dir.display()),
+        )
+        .unwrap();
+
+        let repo = find_repo(&checkout).expect("worktree should be found");
+        assert_eq!(repo.name, "hotfix");
+        assert_eq!(repo.branch.as_deref(), Some("hotfix"));
+    }
+
+    #[test]
+    fn repo_json_shape() {
+        let repo = RepoInfo {
+            name: "api".to_string(),
+            root: PathBuf::from("/srv/api"),
+            branch: None,
+        };
+        assert_eq!(
+            repo_json(&repo),
+            r#"{"name":"api","root":"/srv/api","branch":null}"#
+        );
+    }
+}
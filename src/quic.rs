@@ -0,0 +1,270 @@
+use std::collections::hash_map::RandomState;
+use std::collections::HashMap;
+use std::hash::{BuildHasher, Hasher};
+use std::net::{IpAddr, Ipv4Addr, Ipv6Addr, SocketAddr, UdpSocket};
+use std::time::Duration;
+
+use crate::{collector, json_escape, PortInfo};
+
+// ── QUIC detection ───────────────────────────────────────────────────
+//
+// UDP listeners say nothing about what they speak, but a QUIC server has
+// to answer a long-header packet carrying a version it doesn't know with
+// a Version Negotiation packet listing the versions it does (RFC 9000
+// §6). One 1200-byte datagram with a reserved version is enough to tell
+// a QUIC (in practice HTTP/3) endpoint such as a dev proxy on UDP 443
+// from DNS, mDNS or a game server, without starting a handshake.
+
+/// How long to wait for the Version Negotiation reply.
+pub(crate) const PROBE_TIMEOUT: Duration = Duration::from_millis(300);
+
+/// Servers ignore Initial packets shorter than this.
+const MIN_DATAGRAM: usize = 1200;
+
+/// A version matching 0x?a?a?a?a is reserved and never supported, so the
+/// server always negotiates.
+const RESERVED_VERSION: u32 = 0x1a2a_3a4a;
+
+const CID_LEN: usize = 8;
+
+/// A UDP listener that answered as a QUIC server.
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) struct Quic {
+    /// Versions from the server's Version Negotiation packet, in its
+    /// order, without the reserved ones it may add as grease.
+    pub(crate) versions: Vec<u32>,
+}
+
+/// `v1`, `v2`, `draft-29`, or the hex value.
+pub(crate) fn version_name(version: u32) -> String {
+    match version {
+        0x0000_0001 => "v1".to_string(),
+        0x6b33_43cf => "v2".to_string(),
+        v if v >> 8 == 0x00ff_0000 => format!("draft-{}", v & 0xff),
+        v => format!("0x{:08x}", v),
+    }
+}
+
+/// The inverse of [`version_name`].
+pub(crate) fn parse_version(name: &str) -> Option<u32> {
+    match name {
+        "v1" => Some(0x0000_0001),
+        "v2" => Some(0x6b33_43cf),
+        _ => match (name.strip_prefix("draft-"), name.strip_prefix("0x")) {
+            (Some(draft), _) => draft.parse::<u8>().ok().map(|d| 0xff00_0000 | d as u32),
+            (_, Some(hex)) => u32::from_str_radix(hex, 16).ok(),
+            _ => None,
+        },
+    }
+}
+
+fn is_reserved(version: u32) -> bool {
+    version & 0x0f0f_0f0f == 0x0a0a_0a0a
+}
+
+impl Quic {
+    /// `QUIC/HTTP3 (v1, v2)`.
+    pub(crate) fn describe(&self) -> String {
+        if self.versions.is_empty() {
+            return "QUIC/HTTP3".to_string();
+        }
+        let names: Vec<String> = self.versions.iter().map(|&v| version_name(v)).collect();
+        format!("QUIC/HTTP3 ({})", names.join(", "))
+    }
+
+    /// `{"versions":["v1","v2"]}`.
+    pub(crate) fn json(&self) -> String {
+        let names: Vec<String> = self
+            .versions
+            .iter()
+            .map(|&v| format!("\"{}\"", json_escape(&version_name(v))))
+            .collect();
+        format!(r#"{{"versions":[{}]}}"#, names.join(","))
+    }
+}
+
+/// A long-header Initial packet with the reserved version, padded to the
+/// minimum datagram size.
+fn version_probe(dcid: &[u8; CID_LEN], scid: &[u8; CID_LEN]) -> Vec<u8> {
+    let mut packet = vec![0xc0];
+    packet.extend(RESERVED_VERSION.to_be_bytes());
+    packet.push(CID_LEN as u8);
+    packet.extend(dcid);
+    packet.push(CID_LEN as u8);
+    packet.extend(scid);
+    packet.resize(MIN_DATAGRAM, 0);
+    packet
+}
+
+/// The versions in a Version Negotiation reply to [`version_probe`]; the
+/// reply echoes our connection IDs swapped. `None` for anything else.
+fn parse_negotiation(reply: &[u8], dcid: &[u8], scid: &[u8]) -> Option<Vec<u32>> {
+    let (&first, rest) = reply.split_first()?;
+    if first & 0x80 == 0 || rest.get(..4)? != [0, 0, 0, 0] {
+        return None;
+    }
+    let rest = &rest[4..];
+    let (&dlen, rest) = rest.split_first()?;
+    let (reply_dcid, rest) = rest.split_at_checked(dlen as usize)?;
+    let (&slen, rest) = rest.split_first()?;
+    let (reply_scid, rest) = rest.split_at_checked(slen as usize)?;
+    if reply_dcid != scid || reply_scid != dcid || rest.is_empty() || rest.len() % 4 != 0 {
+        return None;
+    }
+    Some(
+        rest.chunks_exact(4)
+            .map(|c| u32::from_be_bytes([c[0], c[1], c[2], c[3]]))
+            .filter(|&v| !is_reserved(v))
+            .collect(),
+    )
+}
+
+fn connection_id() -> [u8; CID_LEN] {
+    RandomState::new().build_hasher().finish().to_be_bytes()
+}
+
+/// Send the probe to `addr` and wait up to `timeout` for the reply.
+pub(crate) fn probe_addr(addr: SocketAddr, timeout: Duration) -> Option<Quic> {
+    let local: SocketAddr = match addr {
+        SocketAddr::V4(_) => (Ipv4Addr::UNSPECIFIED, 0).into(),
+        SocketAddr::V6(_) => (Ipv6Addr::UNSPECIFIED, 0).into(),
+    };
+    let socket = UdpSocket::bind(local).ok()?;
+    socket.connect(addr).ok()?;
+    socket.set_read_timeout(Some(timeout)).ok()?;
+    let (dcid, scid) = (connection_id(), connection_id());
+    socket.send(&version_probe(&dcid, &scid)).ok()?;
+    let mut buf = [0u8; 1500];
+    let len = socket.recv(&mut buf).ok()?;
+    parse_negotiation(&buf[..len], &dcid, &scid).map(|versions| Quic { versions })
+}
+
+/// Where to reach a listener from this host: its own address, or
+/// loopback for a wildcard bind.
+fn probe_target(info: &PortInfo) -> SocketAddr {
+    let ip = match info.local_addr {
+        IpAddr::V4(a) if a.is_unspecified() => IpAddr::V4(Ipv4Addr::LOCALHOST),
+        IpAddr::V6(a) if a.is_unspecified() => IpAddr::V6(Ipv6Addr::LOCALHOST),
+        ip => ip,
+    };
+    SocketAddr::new(ip, info.port)
+}
+
+/// Probe a UDP row on the local host.
+pub(crate) fn probe(info: &PortInfo) -> Option<Quic> {
+    probe_addr(probe_target(info), PROBE_TIMEOUT)
+}
+
+/// Whether the row is worth probing: a bound UDP socket on a real port.
+fn is_candidate(info: &PortInfo) -> bool {
+    info.protocol.starts_with("UDP") && info.port != 0
+}
+
+/// The QUIC server behind a UDP row, via the active collector.
+pub(crate) fn detect(info: &PortInfo) -> Option<Quic> {
+    if !is_candidate(info) {
+        return None;
+    }
+    collector::active().probe_quic(info)
+}
+
+/// QUIC servers among the rows, by port. Probes run in parallel, so the
+/// whole scan waits one timeout at most.
+pub(crate) fn scan(infos: &[PortInfo]) -> HashMap<u16, Quic> {
+    let mut candidates: Vec<&PortInfo> = infos.iter().filter(|i| is_candidate(i)).collect();
+    candidates.sort_by_key(|i| i.port);
+    candidates.dedup_by_key(|i| i.port);
+    std::thread::scope(|scope| {
+        let probes: Vec<_> = candidates
+            .into_iter()
+            .map(|info| scope.spawn(move || detect(info).map(|q| (info.port, q))))
+            .collect();
+        probes
+            .into_iter()
+            .filter_map(|p| p.join().ok().flatten())
+            .collect()
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn probe_is_a_padded_long_header() {
+        let packet = version_probe(&[1; CID_LEN], &[2; CID_LEN]);
+        assert_eq!(packet.len(), MIN_DATAGRAM);
+        assert_eq!(packet[0] & 0xc0, 0xc0);
+        assert_eq!(&packet[1..5], &RESERVED_VERSION.to_be_bytes());
+        assert!(is_reserved(RESERVED_VERSION));
+        assert_eq!(packet[5], CID_LEN as u8);
+        assert_eq!(&packet[6..14], &[1; CID_LEN]);
+        assert_eq!(&packet[15..23], &[2; CID_LEN]);
+    }
+
+    fn negotiation(dcid: &[u8], scid: &[u8], versions: &[u32]) -> Vec<u8> {
+        let mut reply = vec![0x80, 0, 0, 0, 0, dcid.len() as u8];
+        reply.extend(dcid);
+        reply.push(scid.len() as u8);
+        reply.extend(scid);
+        for v in versions {
+            reply.extend(v.to_be_bytes());
+        }
+        reply
+    }
+
+    #[test]
+    fn parse_version_negotiation() {
+        let (ours, theirs) = ([1; CID_LEN], [2; CID_LEN]);
+        let reply = negotiation(&theirs, &ours, &[1, 0x6b33_43cf, 0x3a4a_5a6a]);
+        assert_eq!(
+            parse_negotiation(&reply, &ours, &theirs),
+            Some(vec![1, 0x6b33_43cf])
+        );
+        // Connection IDs that weren't ours
+        assert_eq!(parse_negotiation(&reply, &theirs, &ours), None);
+        // A DNS answer or other short-header junk
+        assert_eq!(
+            parse_negotiation(&[0x12, 0x34, 0, 0, 0], &ours, &theirs),
+            None
+        );
+        assert_eq!(parse_negotiation(&reply[..20], &ours, &theirs), None);
+    }
+
+    #[test]
+    fn describe_versions() {
+        let quic = Quic {
+            versions: vec![1, 0x6b33_43cf, 0xff00_001d, 0x5157_5046],
+        };
+        assert_eq!(quic.describe(), "QUIC/HTTP3 (v1, v2, draft-29, 0x51575046)");
+        for v in &quic.versions {
+            assert_eq!(parse_version(&version_name(*v)), Some(*v));
+        }
+        assert_eq!(parse_version("h3"), None);
+        assert_eq!(Quic { versions: vec![1] }.json(), r#"{"versions":["v1"]}"#);
+    }
+
+    #[test]
+    fn probe_a_local_server() {
+        let server = UdpSocket::bind("127.0.0.1:0").unwrap();
+        let addr = server.local_addr().unwrap();
+        let answer = std::thread::spawn(move || {
+            let mut buf = [0u8; 1500];
+            let (len, from) = server.recv_from(&mut buf).unwrap();
+            assert_eq!(len, MIN_DATAGRAM);
+            let dcid = &buf[6..6 + CID_LEN];
+            let scid = &buf[7 + CID_LEN..7 + 2 * CID_LEN];
+            server
+                .send_to(&negotiation(scid, dcid, &[1]), from)
+                .unwrap();
+        });
+        let quic = probe_addr(addr, Duration::from_secs(2));
+        answer.join().unwrap();
+        assert_eq!(quic, Some(Quic { versions: vec![1] }));
+
+        // Nothing answers on a socket that only reads
+        let silent = UdpSocket::bind("127.0.0.1:0").unwrap();
+        let addr = silent.local_addr().unwrap();
+        assert_eq!(probe_addr(addr, Duration::from_millis(50)), None);
+    }
+}
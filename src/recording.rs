@@ -0,0 +1,339 @@
+use std::fs::File;
+use std::io::{self, BufRead, BufReader, BufWriter, Write};
+use std::net::IpAddr;
+use std::path::Path;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+use crate::{PortInfo, TcpState};
+
+// ── File format ──────────────────────────────────────────────────────
+//
+// A text file so recordings can be grepped and attached to bug reports:
+//
+//   portview-recording 1
+//   @ <unix millis> <row count>
+//   <port>\t<proto>\t<pid>\t<state>\t<mem>\t<cpu>\t<start secs|->\t<children>\t<addr>\t<user>\t<process>\t<command>
+//
+// Text fields escape backslash, tab and newline.
+
+const HEADER: &str = "portview-recording 1";
+const FIELD_COUNT: usize = 12;
+
+/// The ports visible at one watch refresh.
+#[derive(Debug, Clone)]
+pub(crate) struct Snapshot {
+    pub(crate) at: SystemTime,
+    pub(crate) ports: Vec<PortInfo>,
+}
+
+fn escape(s: &str) -> String {
+    s.replace('\\', "\\\\")
+        .replace('\t', "\\t")
+        .replace('\n', "\\n")
+}
+
+fn unescape(s: &str) -> String {
+    let mut out = String::with_capacity(s.len());
+    let mut chars = s.chars();
+    while let Some(c) = chars.next() {
+        if c != '\\' {
+            out.push(c);
+            continue;
+        }
+        match chars.next() {
+            Some('t') => out.push('\t'),
+            Some('n') => out.push('\n'),
+            Some(other) => out.push(other),
+            None => out.push('\\'),
+        }
+    }
+    out
+}
+
+fn encode_row(info: &PortInfo) -> String {
+    let start = info
+        .start_time
+        .and_then(|t| t.duration_since(UNIX_EPOCH).ok())
+        .map(|d| d.as_secs().to_string())
+        .unwrap_or_else(|| "-".to_string());
+    [
+        info.port.to_string(),
+        escape(&info.protocol),
+        info.pid.to_string(),
+        info.state.as_str().to_string(),
+        info.memory_bytes.to_string(),
+        format!("{:.2}", info.cpu_seconds),
+        start,
+        info.children.to_string(),
+        info.local_addr.to_string(),
+        escape(&info.user),
+        escape(&info.process_name),
+        escape(&info.command),
+    ]
+    .join("\t")
+}
+
+fn decode_row(line: &str) -> Option<PortInfo> {
+    let fields: Vec<&str> = line.split('\t').collect();
+    if fields.len() != FIELD_COUNT {
+        return None;
+    }
+    Some(PortInfo {
+        port: fields[0].parse().ok()?,
+        protocol: unescape(fields[1]),
+        pid: fields[2].parse().ok()?,
+        state: TcpState::from_name(fields[3]),
+        memory_bytes: fields[4].parse().ok()?,
+        cpu_seconds: fields[5].parse().ok()?,
+        start_time: match fields[6] {
+            "-" => None,
+            secs => Some(UNIX_EPOCH + Duration::from_secs(secs.parse().ok()?)),
+        },
+        children: fields[7].parse().ok()?,
+        local_addr: fields[8].parse::<IpAddr>().ok()?,
+        user: unescape(fields[9]),
+        process_name: unescape(fields[10]),
+        command: unescape(fields[11]),
+    })
+}
+
+// ── Recording ────────────────────────────────────────────────────────
+
+/// Appends one snapshot per watch refresh to a `--record` file.
+pub(crate) struct Recorder {
+    out: BufWriter<File>,
+}
+
+impl Recorder {
+    pub(crate) fn create(path: &Path) -> io::Result<Self> {
+        let mut out = BufWriter::new(File::create(path)?);
+        writeln!(out, "{}", HEADER)?;
+        out.flush()?;
+        Ok(Self { out })
+    }
+
+    /// Flushed after every snapshot so an interrupted session is still
+    /// readable.
+    pub(crate) fn record(&mut self, ports: &[PortInfo]) -> io::Result<()> {
+        let millis = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .unwrap_or_default()
+            .as_millis();
+        writeln!(self.out, "@ {} {}", millis, ports.len())?;
+        for info in ports {
+            writeln!(self.out, "{}", encode_row(info))?;
+        }
+        self.out.flush()
+    }
+}
+
+fn invalid(line_no: usize, msg: &str) -> io::Error {
+    io::Error::new(
+        io::ErrorKind::InvalidData,
+        format!("line {}: {}", line_no, msg),
+    )
+}
+
+/// Read every snapshot from a recording. A truncated final snapshot (the
+/// recorder was killed mid-write) is dropped rather than rejected.
+pub(crate) fn load(path: &Path) -> io::Result<Vec<Snapshot>> {
+    parse(BufReader::new(File::open(path)?))
+}
+
+fn parse(reader: impl BufRead) -> io::Result<Vec<Snapshot>> {
+    let mut lines = reader.lines().enumerate();
+    match lines.next() {
+        Some((_, Ok(line))) if line == HEADER => {}
+        Some((_, Err(e))) => return Err(e),
+        _ => return Err(invalid(1, "not a portview recording")),
+    }
+
+    let mut snapshots = Vec::new();
+    while let Some((idx, line)) = lines.next() {
+        let line = line?;
+        let line_no = idx + 1;
+        let mut parts = line.split(' ');
+        let (Some("@"), Some(millis), Some(count), None) =
+            (parts.next(), parts.next(), parts.next(), parts.next())
+        else {
+            return Err(invalid(line_no, "expected snapshot header"));
+        };
+        let millis: u64 = millis
+            .parse()
+            .map_err(|_| invalid(line_no, "bad timestamp"))?;
+        let count: usize = count
+            .parse()
+            .map_err(|_| invalid(line_no, "bad row count"))?;
+
+        let mut ports = Vec::with_capacity(count);
+        for _ in 0..count {
+            let Some((idx, row)) = lines.next() else {
+                return Ok(snapshots);
+            };
+            let info = decode_row(&row?).ok_or_else(|| invalid(idx + 1, "bad port row"))?;
+            ports.push(info);
+        }
+        snapshots.push(Snapshot {
+            at: UNIX_EPOCH + Duration::from_millis(millis),
+            ports,
+        });
+    }
+    Ok(snapshots)
+}
+
+// ── Playback ─────────────────────────────────────────────────────────
+
+/// Playback position over a loaded recording; advanced once per TUI tick.
+pub(crate) struct Replay {
+    frames: Vec<Snapshot>,
+    pos: usize,
+    pub(crate) paused: bool,
+}
+
+impl Replay {
+    /// `None` for a recording with no snapshots.
+    pub(crate) fn new(frames: Vec<Snapshot>) -> Option<Self> {
+        (!frames.is_empty()).then_some(Self {
+            frames,
+            pos: 0,
+            paused: false,
+        })
+    }
+
+    pub(crate) fn current(&self) -> &Snapshot {
+        &self.frames[self.pos]
+    }
+
+    pub(crate) fn position(&self) -> (usize, usize) {
+        (self.pos + 1, self.frames.len())
+    }
+
+    /// Time since the first snapshot, as shown in the title bar.
+    pub(crate) fn elapsed(&self) -> Duration {
+        self.current()
+            .at
+            .duration_since(self.frames[0].at)
+            .unwrap_or_default()
+    }
+
+    /// Move to the next frame while playing; pauses on the last one.
+    pub(crate) fn tick(&mut self) {
+        if self.paused {
+            return;
+        }
+        if self.pos + 1 < self.frames.len() {
+            self.pos += 1;
+        }
+        if self.pos + 1 == self.frames.len() {
+            self.paused = true;
+        }
+    }
+
+    /// Step one frame forward or back, pausing playback.
+    pub(crate) fn step(&mut self, forward: bool) {
+        self.paused = true;
+        self.pos = if forward {
+            (self.pos + 1).min(self.frames.len() - 1)
+        } else {
+            self.pos.saturating_sub(1)
+        };
+    }
+
+    pub(crate) fn toggle_pause(&mut self) {
+        // Resuming at the end restarts from the beginning
+        if self.paused && self.pos + 1 == self.frames.len() {
+            self.pos = 0;
+        }
+        self.paused = !self.paused;
+    }
+}
+
+/// `+01:02:03` offset into a replay.
+pub(crate) fn format_offset(d: Duration) -> String {
+    let secs = d.as_secs();
+    format!(
+        "+{:02}:{:02}:{:02}",
+        secs / 3600,
+        (secs / 60) % 60,
+        secs % 60
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::net::Ipv4Addr;
+
+    fn info(port: u16, command: &str) -> PortInfo {
+        PortInfo::listener(port, 4242, "node")
+            .command(command)
+            .user("mark")
+            .usage(1024, 1.25)
+            .started(UNIX_EPOCH + Duration::from_secs(1_700_000_000))
+            .children(2)
+            .addr(IpAddr::V4(Ipv4Addr::LOCALHOST))
+    }
+
+    #[test]
+    fn row_roundtrip_escapes_text() {
+        let original = info(3000, "node -e \"a\tb\nc\\d\"");
+        let line = encode_row(&original);
+        assert_eq!(line.split('\t').count(), FIELD_COUNT);
+        let decoded = decode_row(&line).unwrap();
+        assert_eq!(decoded.command, original.command);
+        assert_eq!(decoded.port, 3000);
+        assert_eq!(decoded.state, TcpState::Listen);
+        assert_eq!(decoded.start_time, original.start_time);
+        assert_eq!(decoded.local_addr, original.local_addr);
+    }
+
+    #[test]
+    fn parse_recording_and_drop_truncated_tail() {
+        let text = format!(
+            "{}\n@ 1000 1\n{}\n@ 2000 0\n@ 3000 2\n{}\n",
+            HEADER,
+            encode_row(&info(3000, "a")),
+            encode_row(&info(8080, "b"))
+        );
+        let snaps = parse(text.as_bytes()).unwrap();
+        assert_eq!(snaps.len(), 2);
+        assert_eq!(snaps[0].ports.len(), 1);
+        assert!(snaps[1].ports.is_empty());
+        assert_eq!(
+            snaps[1].at.duration_since(snaps[0].at).unwrap(),
+            Duration::from_secs(1)
+        );
+    }
+
+    #[test]
+    fn parse_rejects_other_files() {
+        assert!(parse("hello\n".as_bytes()).is_err());
+        let bad = format!("{}\n@ 1000 1\nnot a row\n", HEADER);
+        let err = parse(bad.as_bytes()).unwrap_err();
+        assert!(err.to_string().contains("line 3"));
+    }
+
+    #[test]
+    fn replay_plays_pauses_and_steps() {
+        let frame = |secs| Snapshot {
+            at: UNIX_EPOCH + Duration::from_secs(secs),
+            ports: Vec::new(),
+        };
+        assert!(Replay::new(Vec::new()).is_none());
+        let mut r = Replay::new(vec![frame(100), frame(101), frame(160)]).unwrap();
+        r.tick();
+        assert_eq!(r.position(), (2, 3));
+        r.tick();
+        assert_eq!(r.position(), (3, 3));
+        assert!(r.paused, "stops at the end");
+        assert_eq!(format_offset(r.elapsed()), "+00:01:00");
+        r.step(false);
+        assert_eq!(r.position(), (2, 3));
+        r.tick();
+        assert_eq!(r.position(), (2, 3), "paused after stepping");
+        r.step(true);
+        r.toggle_pause();
+        assert_eq!(r.position(), (1, 3), "resume at end restarts");
+        assert!(!r.paused);
+    }
+}
@@ -0,0 +1,96 @@
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::mpsc::{self, RecvTimeoutError};
+use std::sync::Arc;
+use std::time::Duration;
+
+use crate::collector;
+
+// ── Change-driven refresh ────────────────────────────────────────────
+//
+// Watch mode redraws on a 1s timer. Where the backend can subscribe to
+// socket activity from the OS, a background thread waits on that feed and
+// then compares a cheap listener signature, asking for an early refresh
+// only when a listener really opened or closed. With nothing happening on
+// the network the thread sleeps, and busy connection traffic never reaches
+// the full process scan.
+
+/// How long refresh loops should wait for input before checking
+/// [`ChangeWatcher::take`] again.
+pub(crate) const CHECK_INTERVAL: Duration = Duration::from_millis(50);
+
+/// After a wake, how long to let a burst of activity settle before
+/// comparing signatures once for all of it.
+const SETTLE: Duration = Duration::from_millis(100);
+
+/// Wakes pushed by the OS when sockets may have changed.
+pub(crate) struct ListenerFeed {
+    pub(crate) wakes: mpsc::Receiver<()>,
+    /// Ends the OS subscription when dropped.
+    subscription: Box<dyn Send>,
+}
+
+impl ListenerFeed {
+    // macOS has no feed to build
+    #[cfg_attr(target_os = "macos", allow(dead_code))]
+    pub(crate) fn new(wakes: mpsc::Receiver<()>, subscription: impl Send + 'static) -> Self {
+        Self {
+            wakes,
+            subscription: Box::new(subscription),
+        }
+    }
+}
+
+pub(crate) struct ChangeWatcher {
+    changed: Arc<AtomicBool>,
+    stop: Arc<AtomicBool>,
+    _subscription: Box<dyn Send>,
+}
+
+impl ChangeWatcher {
+    /// `None` when the active collector has no feed or no listener
+    /// signature; callers keep refreshing on their timer alone.
+    pub(crate) fn spawn() -> Option<Self> {
+        let source = collector::active();
+        let feed = source.listener_changes()?;
+        let mut last = source.listener_signature()?;
+        let changed = Arc::new(AtomicBool::new(false));
+        let stop = Arc::new(AtomicBool::new(false));
+        let (thread_changed, thread_stop) = (Arc::clone(&changed), Arc::clone(&stop));
+        let wakes = feed.wakes;
+        std::thread::spawn(move || {
+            while !thread_stop.load(Ordering::Relaxed) {
+                match wakes.recv_timeout(Duration::from_secs(1)) {
+                    Ok(()) => {}
+                    Err(RecvTimeoutError::Timeout) => continue,
+                    // The subscription ended; the timer is in charge
+                    Err(RecvTimeoutError::Disconnected) => break,
+                }
+                std::thread::sleep(SETTLE);
+                while wakes.try_recv().is_ok() {}
+                let Some(signature) = source.listener_signature() else {
+                    break;
+                };
+                if signature != last {
+                    last = signature;
+                    thread_changed.store(true, Ordering::Relaxed);
+                }
+            }
+        });
+        Some(Self {
+            changed,
+            stop,
+            _subscription: feed.subscription,
+        })
+    }
+
+    /// Whether listeners changed since the last call.
+    pub(crate) fn take(&self) -> bool {
+        self.changed.swap(false, Ordering::Relaxed)
+    }
+}
+
+impl Drop for ChangeWatcher {
+    fn drop(&mut self) {
+        self.stop.store(true, Ordering::Relaxed);
+    }
+}
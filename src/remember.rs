@@ -0,0 +1,132 @@
+use std::io;
+use std::path::PathBuf;
+
+use crate::config::{self, Value};
+use crate::daemon;
+use crate::tui::{SortColumn, SortDirection};
+
+// ── Remembered TUI settings ──────────────────────────────────────────
+//
+// The sort and the a/c/t/o/v toggles a TUI session ends with are written
+// to a small file in the state directory and restored at the next
+// launch, so a preferred view doesn't have to be set up again each time.
+// Flags can only add to what's restored: `--cwd` shows the column even if
+// the last session hid it. `[tui] remember = false` turns this off, and
+// then `[tui] default_sort` decides the starting sort alone.
+
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub(crate) struct TuiState {
+    pub(crate) sort: Option<(SortColumn, SortDirection)>,
+    pub(crate) all: bool,
+    pub(crate) docker: bool,
+    pub(crate) cwd: bool,
+    pub(crate) runtime: bool,
+    pub(crate) split: bool,
+}
+
+impl TuiState {
+    /// Read a state file. It's only ever written by portview, so anything
+    /// unexpected is dropped rather than reported.
+    pub(crate) fn parse(text: &str) -> Self {
+        let mut state = Self::default();
+        let Ok(tables) = config::parse_tables(text) else {
+            return state;
+        };
+        for entry in tables
+            .into_iter()
+            .filter(|t| t.name == "tui" && !t.array)
+            .flat_map(|t| t.entries)
+        {
+            match (entry.key.as_str(), entry.value) {
+                ("sort", Value::Str(sort)) => state.sort = SortColumn::parse_sort(&sort),
+                ("all", Value::Bool(on)) => state.all = on,
+                ("docker", Value::Bool(on)) => state.docker = on,
+                ("cwd", Value::Bool(on)) => state.cwd = on,
+                ("runtime", Value::Bool(on)) => state.runtime = on,
+                ("split", Value::Bool(on)) => state.split = on,
+                _ => {}
+            }
+        }
+        state
+    }
+
+    pub(crate) fn to_text(self) -> String {
+        let mut text =
+            String::from("# Written by portview when the TUI quits; delete to reset.\n[tui]\n");
+        if let Some((column, direction)) = self.sort {
+            text.push_str(&format!(
+                "sort = \"{} {}\"\n",
+                column.name(),
+                direction.name()
+            ));
+        }
+        text.push_str(&format!(
+            "all = {}\ndocker = {}\ncwd = {}\nruntime = {}\nsplit = {}\n",
+            self.all, self.docker, self.cwd, self.runtime, self.split
+        ));
+        text
+    }
+}
+
+/// `tui.toml` in the per-user state directory.
+pub(crate) fn path() -> Option<PathBuf> {
+    config::user_state_dir().map(|d| d.join("tui.toml"))
+}
+
+fn enabled() -> bool {
+    config::active().tui_remember.unwrap_or(true)
+}
+
+/// The last session's settings; `None` when there are none or
+/// remembering is off.
+pub(crate) fn load() -> Option<TuiState> {
+    if !enabled() {
+        return None;
+    }
+    let text = std::fs::read_to_string(path()?).ok()?;
+    Some(TuiState::parse(&text))
+}
+
+pub(crate) fn save(state: &TuiState) -> io::Result<()> {
+    match path() {
+        Some(path) if enabled() => daemon::write_atomic(&path, &state.to_text()),
+        _ => Ok(()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn round_trip() {
+        let state = TuiState {
+            sort: Some((SortColumn::Mem, SortDirection::Desc)),
+            all: true,
+            docker: false,
+            cwd: true,
+            runtime: false,
+            split: true,
+        };
+        let text = state.to_text();
+        assert!(text.contains("sort = \"mem desc\"\n"), "{}", text);
+        assert_eq!(TuiState::parse(&text), state);
+        assert_eq!(
+            TuiState::parse(&TuiState::default().to_text()),
+            TuiState::default()
+        );
+    }
+
+    #[test]
+    fn junk_is_dropped() {
+        let state = TuiState::parse("[tui]\nsort = \"bogus\"\nall = 3\ncwd = true\n");
+        assert_eq!(
+            state,
+            TuiState {
+                cwd: true,
+                ..TuiState::default()
+            }
+        );
+        assert_eq!(TuiState::parse("not [toml"), TuiState::default());
+    }
+}
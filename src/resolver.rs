@@ -0,0 +1,218 @@
+use std::net::{IpAddr, Ipv4Addr};
+
+use crate::udpservice::DetailRows;
+use crate::PortInfo;
+
+// ── Local DNS resolvers ──────────────────────────────────────────────
+//
+// "What is listening on port 53?" usually has a boring answer: the
+// system's own resolver. Each one is recognised from its process (or,
+// for Docker's embedded DNS, its address) so the detail view can say
+// what it is and how to free the port without breaking name resolution.
+
+/// Docker's embedded DNS, as seen from inside a container.
+const DOCKER_DNS: IpAddr = IpAddr::V4(Ipv4Addr::new(127, 0, 0, 11));
+
+/// Who started a dnsmasq, judging by its command line.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub(crate) enum DnsmasqManager {
+    Libvirt,
+    NetworkManager,
+    Lxd,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub(crate) enum Resolver {
+    SystemdResolved,
+    Dnsmasq(Option<DnsmasqManager>),
+    /// macOS's Bonjour and DNS daemon.
+    MdnsResponder,
+    DockerDns,
+}
+
+/// The port a resolver answers on, which decides what it's doing there.
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Role {
+    Dns,
+    Llmnr,
+    Mdns,
+}
+
+fn role(port: u16) -> Option<Role> {
+    match port {
+        53 => Some(Role::Dns),
+        5355 => Some(Role::Llmnr),
+        5353 => Some(Role::Mdns),
+        _ => None,
+    }
+}
+
+impl Resolver {
+    /// The local resolver behind a row, if it is one.
+    pub(crate) fn detect(info: &PortInfo) -> Option<Self> {
+        // Random ports on 127.0.0.11 inside the container's namespace
+        if info.local_addr == DOCKER_DNS {
+            return Some(Resolver::DockerDns);
+        }
+        role(info.port)?;
+        // comm is cut to 15 characters: systemd-resolve
+        match info.process_name.as_str() {
+            "systemd-resolve" | "systemd-resolved" => Some(Resolver::SystemdResolved),
+            "dnsmasq" => Some(Resolver::Dnsmasq(dnsmasq_manager(&info.command))),
+            "mDNSResponder" => Some(Resolver::MdnsResponder),
+            _ => None,
+        }
+    }
+
+    pub(crate) fn name(self) -> &'static str {
+        match self {
+            Resolver::SystemdResolved => "systemd-resolved",
+            Resolver::Dnsmasq(_) => "dnsmasq",
+            Resolver::MdnsResponder => "mDNSResponder",
+            Resolver::DockerDns => "docker-dns",
+        }
+    }
+
+    /// Whether it's answering ordinary DNS here, as opposed to LLMNR or
+    /// mDNS.
+    pub(crate) fn serves_dns(self, info: &PortInfo) -> bool {
+        self == Resolver::DockerDns || role(info.port) == Some(Role::Dns)
+    }
+
+    pub(crate) fn explain(self, info: &PortInfo) -> String {
+        let role = role(info.port);
+        match self {
+            Resolver::SystemdResolved => match role {
+                Some(Role::Llmnr) => "systemd-resolved answering LLMNR, Windows-style name lookups on the local network".to_string(),
+                Some(Role::Mdns) => "systemd-resolved answering mDNS (.local names)".to_string(),
+                _ => "systemd-resolved's stub resolver: /etc/resolv.conf points programs at 127.0.0.53, and it forwards their lookups to the real DNS servers".to_string(),
+            },
+            Resolver::Dnsmasq(Some(DnsmasqManager::Libvirt)) => {
+                "dnsmasq serving DNS and DHCP to libvirt's virtual machines on their bridge network".to_string()
+            }
+            Resolver::Dnsmasq(Some(DnsmasqManager::NetworkManager)) => {
+                "dnsmasq started by NetworkManager as a local caching resolver (dns=dnsmasq)".to_string()
+            }
+            Resolver::Dnsmasq(Some(DnsmasqManager::Lxd)) => {
+                "dnsmasq serving DNS and DHCP to LXD containers on their bridge network".to_string()
+            }
+            Resolver::Dnsmasq(None) => "dnsmasq, a small caching DNS (and DHCP) server".to_string(),
+            Resolver::MdnsResponder => {
+                "macOS's mDNSResponder, which handles Bonjour and every DNS lookup on the Mac".to_string()
+            }
+            Resolver::DockerDns => "Docker's embedded DNS server: containers on a user-defined network resolve each other's names through 127.0.0.11".to_string(),
+        }
+    }
+
+    pub(crate) fn remedy(self, info: &PortInfo) -> String {
+        let role = role(info.port);
+        match self {
+            Resolver::SystemdResolved => match role {
+                Some(Role::Llmnr) => "set LLMNR=no in /etc/systemd/resolved.conf and restart systemd-resolved".to_string(),
+                Some(Role::Mdns) => "set MulticastDNS=no in /etc/systemd/resolved.conf and restart systemd-resolved".to_string(),
+                _ => "to free port 53, set DNSStubListener=no in /etc/systemd/resolved.conf and restart systemd-resolved; killing it breaks name resolution".to_string(),
+            },
+            Resolver::Dnsmasq(Some(DnsmasqManager::Libvirt)) => {
+                "libvirt restarts it; stop the network with `virsh net-destroy default` instead".to_string()
+            }
+            Resolver::Dnsmasq(Some(DnsmasqManager::NetworkManager)) => {
+                "set dns=default in /etc/NetworkManager/NetworkManager.conf and restart NetworkManager".to_string()
+            }
+            Resolver::Dnsmasq(Some(DnsmasqManager::Lxd)) => {
+                "LXD restarts it; set dns.mode=none on the network (`lxc network set lxdbr0 dns.mode none`) instead".to_string()
+            }
+            Resolver::Dnsmasq(None) => {
+                "`systemctl stop dnsmasq`, or add bind-interfaces and listen-address to /etc/dnsmasq.conf so it leaves other addresses free".to_string()
+            }
+            Resolver::MdnsResponder => {
+                "it's part of macOS and restarts when killed; give your server another port".to_string()
+            }
+            Resolver::DockerDns => {
+                "nothing to do: it belongs to the Docker daemon and goes away with the container's network".to_string()
+            }
+        }
+    }
+
+    /// The `Resolver:` and `Fix:` detail rows.
+    pub(crate) fn rows(self, info: &PortInfo) -> DetailRows {
+        vec![
+            ("Resolver:", self.explain(info)),
+            ("Fix:", self.remedy(info)),
+        ]
+    }
+}
+
+fn dnsmasq_manager(command: &str) -> Option<DnsmasqManager> {
+    if command.contains("libvirt") {
+        Some(DnsmasqManager::Libvirt)
+    } else if command.contains("NetworkManager") {
+        Some(DnsmasqManager::NetworkManager)
+    } else if command.contains("lxd") {
+        Some(DnsmasqManager::Lxd)
+    } else {
+        None
+    }
+}
+
+/// The resolver rows for a detail view; empty for anything else.
+pub(crate) fn detail_rows(info: &PortInfo) -> DetailRows {
+    Resolver::detect(info).map_or_else(Vec::new, |r| r.rows(info))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn row(port: u16, process: &str, command: &str, addr: IpAddr) -> PortInfo {
+        PortInfo::listener(port, 500, process)
+            .protocol("UDP")
+            .command(command)
+            .user("systemd-resolve")
+            .addr(addr)
+    }
+
+    #[test]
+    fn detect_resolvers() {
+        let stub = IpAddr::V4(Ipv4Addr::new(127, 0, 0, 53));
+        let resolved = row(53, "systemd-resolve", "/lib/systemd/systemd-resolved", stub);
+        assert_eq!(Resolver::detect(&resolved), Some(Resolver::SystemdResolved));
+        assert!(Resolver::SystemdResolved.serves_dns(&resolved));
+        assert!(Resolver::SystemdResolved
+            .remedy(&resolved)
+            .contains("DNSStubListener=no"));
+        let llmnr = row(
+            5355,
+            "systemd-resolve",
+            "",
+            IpAddr::V4(Ipv4Addr::UNSPECIFIED),
+        );
+        assert!(!Resolver::SystemdResolved.serves_dns(&llmnr));
+        assert!(Resolver::SystemdResolved
+            .remedy(&llmnr)
+            .contains("LLMNR=no"));
+
+        let libvirt = row(
+            53,
+            "dnsmasq",
+            "/usr/sbin/dnsmasq --conf-file=/var/lib/libvirt/dnsmasq/default.conf",
+            IpAddr::V4(Ipv4Addr::new(192, 168, 122, 1)),
+        );
+        assert_eq!(
+            Resolver::detect(&libvirt),
+            Some(Resolver::Dnsmasq(Some(DnsmasqManager::Libvirt)))
+        );
+        // dnsmasq's DHCP side isn't a resolver
+        assert_eq!(
+            Resolver::detect(&PortInfo {
+                port: 67,
+                ..libvirt
+            }),
+            None
+        );
+
+        let docker = row(41234, "", "", DOCKER_DNS);
+        assert_eq!(Resolver::detect(&docker), Some(Resolver::DockerDns));
+        assert!(Resolver::DockerDns.serves_dns(&docker));
+        assert_eq!(Resolver::detect(&row(53, "named", "", stub)), None);
+    }
+}
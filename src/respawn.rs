@@ -0,0 +1,112 @@
+use std::io;
+use std::process::{Command, ExitStatus};
+use std::time::{Duration, Instant};
+
+use crate::{get_listening_ports, get_process_cwd, PortInfo};
+
+// ── Restart command ──────────────────────────────────────────────────
+
+/// How a killed process was started, captured before the kill so it can be
+/// printed or re-run afterwards.
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) struct RestartCommand {
+    pub(crate) command: String,
+    pub(crate) cwd: Option<String>,
+}
+
+impl RestartCommand {
+    /// Must be called while the process is still alive; its cwd can't be
+    /// read afterwards.
+    pub(crate) fn capture(info: &PortInfo) -> Option<Self> {
+        let command = if info.command.trim().is_empty() {
+            info.process_name.trim()
+        } else {
+            info.command.trim()
+        };
+        if command.is_empty() {
+            return None;
+        }
+        Some(Self {
+            command: command.to_string(),
+            cwd: get_process_cwd(info.pid),
+        })
+    }
+
+    /// A line that can be pasted into a shell: `cd '/srv/app' && node server.js`.
+    pub(crate) fn shell_line(&self) -> String {
+        match self.cwd {
+            Some(ref cwd) => format!("cd {} && {}", quote_dir(cwd), self.command),
+            None => self.command.clone(),
+        }
+    }
+
+    /// Run the command in the foreground, in its original directory, with
+    /// the terminal attached.
+    pub(crate) fn run(&self) -> io::Result<ExitStatus> {
+        #[cfg(unix)]
+        let mut command = {
+            let mut c = Command::new("sh");
+            c.args(["-c", &self.command]);
+            c
+        };
+        #[cfg(windows)]
+        let mut command = {
+            let mut c = Command::new("cmd");
+            c.args(["/C", &self.command]);
+            c
+        };
+        if let Some(ref cwd) = self.cwd {
+            command.current_dir(cwd);
+        }
+        command.status()
+    }
+}
+
+#[cfg(unix)]
+fn quote_dir(dir: &str) -> String {
+    format!("'{}'", dir.replace('\'', r"'\''"))
+}
+
+#[cfg(windows)]
+fn quote_dir(dir: &str) -> String {
+    format!("/d \"{}\"", dir)
+}
+
+/// Wait for a killed listener to let go of `port`, so the restarted
+/// process doesn't fail with "address in use".
+pub(crate) fn wait_for_release(port: u16, timeout: Duration) -> bool {
+    let deadline = Instant::now() + timeout;
+    loop {
+        if !get_listening_ports().contains(&port) {
+            return true;
+        }
+        if Instant::now() >= deadline {
+            return false;
+        }
+        std::thread::sleep(Duration::from_millis(100));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    #[cfg(unix)]
+    use super::*;
+
+    #[cfg(unix)]
+    #[test]
+    fn shell_line_quotes_directory() {
+        let restart = RestartCommand {
+            command: "npm run dev".to_string(),
+            cwd: Some("/home/dev/it's here".to_string()),
+        };
+        assert_eq!(
+            restart.shell_line(),
+            r"cd '/home/dev/it'\''s here' && npm run dev"
+        );
+        let bare = RestartCommand {
+            command: "redis-server".to_string(),
+            cwd: None,
+        };
+        assert_eq!(bare.shell_line(), "redis-server");
+    }
+}
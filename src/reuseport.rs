@@ -0,0 +1,113 @@
+use std::collections::HashMap;
+use std::net::IpAddr;
+
+use crate::{PortInfo, TcpState};
+
+// ── SO_REUSEPORT groups ──────────────────────────────────────────────
+//
+// Two TCP listeners can only share an address and port when both set
+// SO_REUSEPORT, so listeners with the same protocol, address and port
+// form a group. Neither sock_diag nor proc_pidfdinfo report the option
+// itself; the duplicate sockets are the evidence. A pre-forked server
+// whose workers inherit one socket has a single socket and no group.
+
+/// Protocol, address and port shared by a group.
+pub(crate) type GroupKey = (String, IpAddr, u16);
+
+pub(crate) fn key(info: &PortInfo) -> Option<GroupKey> {
+    (info.state == TcpState::Listen && info.protocol.starts_with("TCP"))
+        .then(|| (info.protocol.clone(), info.local_addr, info.port))
+}
+
+/// Keep one row per group, the member with the lowest PID, in the order
+/// rows arrived. The other members come back keyed by their group.
+pub(crate) fn fold(rows: Vec<&PortInfo>) -> (Vec<&PortInfo>, HashMap<GroupKey, Vec<&PortInfo>>) {
+    let mut groups: HashMap<GroupKey, Vec<&PortInfo>> = HashMap::new();
+    for row in &rows {
+        if let Some(k) = key(row) {
+            groups.entry(k).or_default().push(row);
+        }
+    }
+    groups.retain(|_, members| members.len() > 1);
+    for members in groups.values_mut() {
+        members.sort_by_key(|m| m.pid);
+    }
+    let shown = rows
+        .into_iter()
+        .filter(|row| {
+            key(row)
+                .and_then(|k| groups.get(&k))
+                .is_none_or(|members| std::ptr::eq(members[0], *row))
+        })
+        .collect();
+    for members in groups.values_mut() {
+        members.remove(0);
+    }
+    (shown, groups)
+}
+
+/// ` ×4` after the process name, for a group of four sockets.
+pub(crate) fn tag(members: usize) -> String {
+    format!(" \u{d7}{}", members)
+}
+
+/// Table rows with each group folded into one row, its process name
+/// tagged with the group's size.
+pub(crate) fn collapse(infos: &[PortInfo]) -> Vec<PortInfo> {
+    let (shown, others) = fold(infos.iter().collect());
+    shown
+        .into_iter()
+        .map(|row| {
+            let mut row = row.clone();
+            if let Some(rest) = key(&row).and_then(|k| others.get(&k)) {
+                row.process_name.push_str(&tag(rest.len() + 1));
+            }
+            row
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn listener(port: u16, pid: u32, protocol: &str) -> PortInfo {
+        PortInfo::listener(port, pid, "nginx")
+            .protocol(protocol)
+            .command("nginx: worker process")
+            .user("www")
+    }
+
+    #[test]
+    fn fold_keeps_the_lowest_pid() {
+        let rows = vec![
+            listener(80, 12, "TCP"),
+            listener(443, 10, "TCP"),
+            listener(80, 11, "TCP"),
+            listener(80, 13, "TCP"),
+            listener(80, 9, "TCP6"),
+        ];
+        let (shown, others) = fold(rows.iter().collect());
+        let shown: Vec<(u16, u32)> = shown.iter().map(|r| (r.port, r.pid)).collect();
+        assert_eq!(shown, vec![(443, 10), (80, 11), (80, 9)]);
+        let key = key(&rows[0]).unwrap();
+        let pids: Vec<u32> = others[&key].iter().map(|r| r.pid).collect();
+        assert_eq!(pids, vec![12, 13]);
+        assert_eq!(others.len(), 1);
+
+        let table = collapse(&rows);
+        assert_eq!(table.len(), 3);
+        assert_eq!(table[1].process_name, "nginx ×3");
+        assert_eq!(table[0].process_name, "nginx");
+    }
+
+    #[test]
+    fn udp_and_connections_are_not_grouped() {
+        let mut udp = listener(5353, 1, "UDP");
+        udp.state = TcpState::Unknown;
+        let mut conn = listener(80, 2, "TCP");
+        conn.state = TcpState::Established;
+        assert_eq!(key(&udp), None);
+        assert_eq!(key(&conn), None);
+    }
+}
@@ -0,0 +1,169 @@
+use crate::PortInfo;
+
+// ── Runtime detection ────────────────────────────────────────────────
+//
+// Guessed from the process name and the command line alone, so it costs
+// nothing per refresh. Compiled Go and Rust binaries don't name their
+// language, so those are only recognised when run from a build tree
+// (`go run`, `cargo run`).
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub(crate) enum Runtime {
+    Node,
+    Deno,
+    Bun,
+    Python,
+    Ruby,
+    Java,
+    Dotnet,
+    Php,
+    Beam,
+    Go,
+    Rust,
+    Docker,
+}
+
+impl Runtime {
+    /// Short tag for the RUNTIME column and the `runtime` JSON field.
+    pub(crate) fn as_str(self) -> &'static str {
+        match self {
+            Runtime::Node => "node",
+            Runtime::Deno => "deno",
+            Runtime::Bun => "bun",
+            Runtime::Python => "python",
+            Runtime::Ruby => "ruby",
+            Runtime::Java => "java",
+            Runtime::Dotnet => "dotnet",
+            Runtime::Php => "php",
+            Runtime::Beam => "beam",
+            Runtime::Go => "go",
+            Runtime::Rust => "rust",
+            Runtime::Docker => "docker",
+        }
+    }
+
+    /// Default color name for the tag, used unless `PORTVIEW_COLORS`
+    /// sets `runtime=`.
+    pub(crate) fn color(self) -> &'static str {
+        match self {
+            Runtime::Node => "green",
+            Runtime::Deno | Runtime::Bun => "white",
+            Runtime::Python => "yellow",
+            Runtime::Ruby | Runtime::Java => "red",
+            Runtime::Dotnet | Runtime::Beam => "magenta",
+            Runtime::Php => "blue",
+            Runtime::Go => "cyan",
+            Runtime::Rust => "bright_red",
+            Runtime::Docker => "bright_blue",
+        }
+    }
+}
+
+/// The runtime behind a listener, when its name or command gives it away.
+pub(crate) fn detect(info: &PortInfo) -> Option<Runtime> {
+    let exe = info.command.split_whitespace().next().unwrap_or("");
+    let exe_name = base_name(exe);
+    let names = [base_name(&info.process_name), exe_name];
+    let any = |f: fn(&str) -> bool| names.iter().any(|n| !n.is_empty() && f(n));
+
+    if any(|n| n == "docker-proxy") {
+        Some(Runtime::Docker)
+    } else if any(|n| matches!(n, "node" | "nodejs" | "npm" | "npx" | "yarn" | "pnpm"))
+        || any(|n| n.starts_with("next-server"))
+    {
+        Some(Runtime::Node)
+    } else if any(|n| n == "deno") {
+        Some(Runtime::Deno)
+    } else if any(|n| matches!(n, "bun" | "bunx")) {
+        Some(Runtime::Bun)
+    } else if any(|n| n.starts_with("python") || n == "py")
+        || any(|n| matches!(n, "uvicorn" | "gunicorn" | "hypercorn" | "daphne"))
+    {
+        Some(Runtime::Python)
+    } else if any(|n| matches!(n, "ruby" | "puma" | "rails" | "unicorn" | "bundle")) {
+        Some(Runtime::Ruby)
+    } else if any(|n| matches!(n, "java" | "javaw" | "kotlin")) {
+        Some(Runtime::Java)
+    } else if any(|n| n == "dotnet") {
+        Some(Runtime::Dotnet)
+    } else if any(|n| n == "php" || n.starts_with("php-fpm")) {
+        Some(Runtime::Php)
+    } else if any(|n| matches!(n, "beam.smp" | "beam" | "erl" | "elixir" | "iex")) {
+        Some(Runtime::Beam)
+    } else if exe.contains("/go-build") || any(|n| n == "go") {
+        Some(Runtime::Go)
+    } else if exe.contains("/target/debug/")
+        || exe.contains("/target/release/")
+        || any(|n| n == "cargo")
+    {
+        Some(Runtime::Rust)
+    } else {
+        None
+    }
+}
+
+/// `/usr/bin/python3.12` → `python3.12`; `C:\…\node.exe` → `node`.
+fn base_name(path: &str) -> &str {
+    let name = path.rsplit(['/', '\\']).next().unwrap_or(path);
+    name.strip_suffix(".exe")
+        .or_else(|| name.strip_suffix(".EXE"))
+        .unwrap_or(name)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use std::net::{IpAddr, Ipv4Addr};
+
+    fn info(process: &str, command: &str) -> PortInfo {
+        PortInfo::listener(3000, 42, process)
+            .command(command)
+            .addr(IpAddr::V4(Ipv4Addr::LOCALHOST))
+    }
+
+    #[test]
+    fn detect_from_name_or_command() {
+        let cases = [
+            ("node", "node server.js", Some(Runtime::Node)),
+            (
+                "next-server (v14",
+                "next-server (v14.2.3)",
+                Some(Runtime::Node),
+            ),
+            (
+                "python3.12",
+                "/usr/bin/python3.12 -m http.server",
+                Some(Runtime::Python),
+            ),
+            ("gunicorn", "gunicorn app:app", Some(Runtime::Python)),
+            (
+                "java",
+                "/opt/jdk/bin/java -jar app.jar",
+                Some(Runtime::Java),
+            ),
+            (
+                "node.exe",
+                r"C:\Program Files\nodejs\node.exe app.js",
+                Some(Runtime::Node),
+            ),
+            ("main", "/tmp/go-build123/b001/exe/main", Some(Runtime::Go)),
+            (
+                "api",
+                "/home/dev/api/target/debug/api --port 8080",
+                Some(Runtime::Rust),
+            ),
+            (
+                "docker-proxy",
+                "/usr/bin/docker-proxy -proto tcp",
+                Some(Runtime::Docker),
+            ),
+            ("php-fpm8.2", "php-fpm: master process", Some(Runtime::Php)),
+            ("nginx", "nginx: master process", None),
+            ("postgres", "", None),
+        ];
+        for (process, command, expected) in cases {
+            assert_eq!(detect(&info(process, command)), expected, "{}", command);
+        }
+    }
+}
@@ -0,0 +1,186 @@
+use std::net::{IpAddr, Ipv4Addr, Ipv6Addr, SocketAddr, TcpListener, TcpStream, UdpSocket};
+use std::path::Path;
+
+use crate::collector;
+use crate::doctor::Check;
+use crate::{current_user, PortInfo, TcpState};
+
+// ── Self-test ────────────────────────────────────────────────────────
+//
+// `portview selftest` binds sockets of its own and checks that the
+// platform collector reports them with the right PID, user and state.
+// It's the end-to-end check for linux.rs, macos.rs and windows.rs, run
+// by the socket integration tests and useful on a machine where the
+// table looks wrong.
+
+const HINT: &str = "the collector missed or misread a socket this process owns; please report it with `portview doctor` output";
+
+/// Whether `row` belongs to this process and user, in `state`.
+fn verify(name: &'static str, row: Option<&PortInfo>, addr: SocketAddr, state: TcpState) -> Check {
+    let Some(row) = row else {
+        return Check::fail(name, format!("{} not found", addr), HINT);
+    };
+    let pid = std::process::id();
+    let user = current_user();
+    let found = format!(
+        "{} found: pid {}, user {}, {}",
+        addr, row.pid, row.user, row.state
+    );
+    // Windows may qualify the account with its domain
+    let same_user = row.user == user || row.user.ends_with(&format!("\\{}", user));
+    if row.pid != pid {
+        Check::fail(name, format!("{}, expected pid {}", found, pid), HINT)
+    } else if row.state != state {
+        Check::fail(name, format!("{}, expected {}", found, state), HINT)
+    } else if !same_user {
+        Check::warn(name, format!("{}, expected user {}", found, user), HINT)
+    } else {
+        Check::ok(name, found)
+    }
+}
+
+fn find<'a>(infos: &'a [PortInfo], addr: SocketAddr, protocol: &str) -> Option<&'a PortInfo> {
+    infos
+        .iter()
+        .find(|i| i.port == addr.port() && i.protocol.starts_with(protocol))
+}
+
+/// Bind, connect and look everything up again through the active
+/// collector.
+pub(crate) fn run() -> Vec<Check> {
+    let loopback = SocketAddr::new(IpAddr::V4(Ipv4Addr::LOCALHOST), 0);
+    let mut checks = Vec::new();
+
+    let tcp = match TcpListener::bind(loopback).and_then(|l| Ok((l.local_addr()?, l))) {
+        Ok(bound) => Some(bound),
+        Err(e) => {
+            checks.push(Check::fail(
+                "tcp listener",
+                format!("cannot bind 127.0.0.1: {}", e),
+                "the loopback interface is down or sockets are blocked here",
+            ));
+            None
+        }
+    };
+    let tcp6 = TcpListener::bind(SocketAddr::new(IpAddr::V6(Ipv6Addr::LOCALHOST), 0))
+        .and_then(|l| Ok((l.local_addr()?, l)))
+        .ok();
+    let udp = UdpSocket::bind(loopback)
+        .and_then(|s| Ok((s.local_addr()?, s)))
+        .ok();
+    // Keep both ends open until the lookups are done
+    let client = tcp.as_ref().and_then(|(addr, listener)| {
+        let stream = TcpStream::connect(addr).ok()?;
+        let accepted = listener.accept().ok()?;
+        Some((stream.local_addr().ok()?, stream, accepted))
+    });
+
+    let backend = collector::active();
+    let infos = backend.port_infos(true);
+    if let Some((addr, _)) = &tcp {
+        checks.push(verify(
+            "tcp listener",
+            find(&infos, *addr, "TCP"),
+            *addr,
+            TcpState::Listen,
+        ));
+        checks.push(if backend.listening_ports().contains(&addr.port()) {
+            Check::ok("listening ports", format!("{} is listed", addr.port()))
+        } else {
+            Check::fail(
+                "listening ports",
+                format!("{} missing from the quick port scan", addr.port()),
+                HINT,
+            )
+        });
+    }
+    match &tcp6 {
+        Some((addr, _)) => checks.push(verify(
+            "tcp6 listener",
+            find(&infos, *addr, "TCP"),
+            *addr,
+            TcpState::Listen,
+        )),
+        None => checks.push(Check::skip(
+            "tcp6 listener",
+            "IPv6 loopback unavailable".to_string(),
+        )),
+    }
+    match &udp {
+        Some((addr, _)) => {
+            // A bound UDP socket has no TCP state; only owner and port matter
+            let row = find(&infos, *addr, "UDP");
+            let state = row.map_or(TcpState::Listen, |r| r.state);
+            checks.push(verify("udp socket", row, *addr, state));
+        }
+        None => checks.push(Check::skip(
+            "udp socket",
+            "cannot bind UDP on 127.0.0.1".to_string(),
+        )),
+    }
+
+    if let (Some((server, _)), Some((local, _, _))) = (&tcp, &client) {
+        let conns = backend.connections();
+        let conn = conns.iter().find(|c| {
+            c.local_port == local.port()
+                && c.remote_port == server.port()
+                && c.state == TcpState::Established
+        });
+        checks.push(match conn {
+            Some(c) if c.pid == Some(std::process::id()) => Check::ok(
+                "connection",
+                format!(
+                    "{} → {} ESTABLISHED, pid {}",
+                    local,
+                    server,
+                    std::process::id()
+                ),
+            ),
+            Some(c) => Check::warn(
+                "connection",
+                format!(
+                    "{} → {} found with pid {}",
+                    local,
+                    server,
+                    c.pid.map_or("unknown".to_string(), |p| p.to_string())
+                ),
+                HINT,
+            ),
+            None => Check::fail(
+                "connection",
+                format!("{} → {} not found", local, server),
+                HINT,
+            ),
+        });
+    }
+
+    let expected = std::env::current_dir().ok();
+    checks.push(match backend.process_cwd(std::process::id()) {
+        Some(cwd)
+            if expected
+                .as_deref()
+                .is_some_and(|e| same_dir(Path::new(&cwd), e)) =>
+        {
+            Check::ok("working directory", cwd)
+        }
+        Some(cwd) => Check::fail(
+            "working directory",
+            format!(
+                "{}, expected {}",
+                cwd,
+                expected.map_or("?".to_string(), |e| e.display().to_string())
+            ),
+            HINT,
+        ),
+        None => Check::skip(
+            "working directory",
+            format!("not available from the {} backend", backend.name()),
+        ),
+    });
+    checks
+}
+
+/// Equal once symlinks (macOS's /tmp → /private/tmp) are resolved.
+fn same_dir(a: &Path, b: &Path) -> bool {
+    a == b || a.canonicalize().ok() == b.canonicalize().ok()
+}
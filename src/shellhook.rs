@@ -0,0 +1,91 @@
+use crate::declared::DeclaredPort;
+
+// ── Shell integration ────────────────────────────────────────────────
+//
+// `eval "$(portview hook zsh)"` installs a directory-change hook that
+// runs `portview hook --check`. The check reads the ports the project in
+// the new directory declares and prints one line for each that something
+// already listens on, so a clash shows up before `npm run dev` does.
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub(crate) enum Shell {
+    Zsh,
+    Bash,
+    Fish,
+}
+
+impl Shell {
+    pub(crate) fn parse(s: &str) -> Result<Self, String> {
+        match s.to_ascii_lowercase().as_str() {
+            "zsh" => Ok(Shell::Zsh),
+            "bash" => Ok(Shell::Bash),
+            "fish" => Ok(Shell::Fish),
+            _ => Err(format!("unsupported shell '{}' (zsh, bash, fish)", s)),
+        }
+    }
+
+    /// The functions to `eval` (or `source` in fish).
+    pub(crate) fn script(self) -> &'static str {
+        match self {
+            Shell::Zsh => {
+                "_portview_hook() {
+  command portview hook --check 2>/dev/null
+}
+autoload -Uz add-zsh-hook
+add-zsh-hook chpwd _portview_hook
+"
+            }
+            Shell::Bash => {
+                r#"_portview_hook() {
+  if [[ "$PWD" != "${_PORTVIEW_LAST_DIR-}" ]]; then
+    _PORTVIEW_LAST_DIR="$PWD"
+    command portview hook --check 2>/dev/null
+  fi
+}
+if [[ ";${PROMPT_COMMAND:-};" != *";_portview_hook;"* ]]; then
+  PROMPT_COMMAND="_portview_hook${PROMPT_COMMAND:+;$PROMPT_COMMAND}"
+fi
+"#
+            }
+            Shell::Fish => {
+                "function __portview_hook --on-variable PWD
+    status --is-command-substitution; and return
+    command portview hook --check 2>/dev/null
+end
+"
+            }
+        }
+    }
+}
+
+/// `portview: port 3000 from .env is taken by node (pid 4242)`.
+pub(crate) fn warning(declared: &DeclaredPort, owner: Option<(&str, u32)>) -> String {
+    let by = owner.map_or_else(String::new, |(name, pid)| {
+        format!(" by {} (pid {})", name, pid)
+    });
+    format!(
+        "portview: port {} from {} is taken{}",
+        declared.port, declared.source, by
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn warning_line() {
+        let declared = DeclaredPort {
+            port: 3000,
+            source: "Procfile (web)".to_string(),
+        };
+        assert_eq!(
+            warning(&declared, Some(("node", 4242))),
+            "portview: port 3000 from Procfile (web) is taken by node (pid 4242)"
+        );
+        assert_eq!(
+            warning(&declared, None),
+            "portview: port 3000 from Procfile (web) is taken"
+        );
+    }
+}
@@ -0,0 +1,190 @@
+// ── Signals ──────────────────────────────────────────────────────────
+//
+// `kill --signal` and the TUI's signal picker send more than TERM and
+// KILL: HUP to make nginx or a gunicorn master reload, USR1 to reopen
+// logs, STOP/CONT to pause a noisy service. Windows has no signals, so
+// only TERM and KILL work there, both as TerminateProcess.
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum Signal {
+    Hup,
+    Int,
+    Quit,
+    Kill,
+    Usr1,
+    Usr2,
+    Term,
+    Cont,
+    Stop,
+    Tstp,
+    Winch,
+    /// A signal number with no name here, e.g. a real-time signal.
+    Number(i32),
+}
+
+/// The named signals, in the picker's order.
+pub(crate) const NAMED: &[Signal] = &[
+    Signal::Term,
+    Signal::Hup,
+    Signal::Int,
+    Signal::Quit,
+    Signal::Usr1,
+    Signal::Usr2,
+    Signal::Stop,
+    Signal::Cont,
+    Signal::Tstp,
+    Signal::Winch,
+    Signal::Kill,
+];
+
+impl Signal {
+    /// SIGKILL for a force kill, SIGTERM otherwise.
+    pub(crate) fn for_force(force: bool) -> Self {
+        if force {
+            Signal::Kill
+        } else {
+            Signal::Term
+        }
+    }
+
+    /// `HUP`, `SIGHUP`, `hup` or a number like `1`.
+    pub(crate) fn parse(text: &str) -> Result<Self, String> {
+        if let Ok(n) = text.parse::<i32>() {
+            if n <= 0 {
+                return Err(format!("signal number must be positive, not {}", n));
+            }
+            return Ok(NAMED
+                .iter()
+                .copied()
+                .find(|s| s.number() == Some(n))
+                .unwrap_or(Signal::Number(n)));
+        }
+        let upper = text.to_ascii_uppercase();
+        let name = upper.strip_prefix("SIG").unwrap_or(&upper);
+        NAMED
+            .iter()
+            .copied()
+            .find(|s| s.short_name() == name)
+            .ok_or_else(|| {
+                let names: Vec<&str> = NAMED.iter().map(|s| s.short_name()).collect();
+                format!(
+                    "unknown signal '{}' (expected a number or one of {})",
+                    text,
+                    names.join(", ")
+                )
+            })
+    }
+
+    fn short_name(self) -> &'static str {
+        match self {
+            Signal::Hup => "HUP",
+            Signal::Int => "INT",
+            Signal::Quit => "QUIT",
+            Signal::Kill => "KILL",
+            Signal::Usr1 => "USR1",
+            Signal::Usr2 => "USR2",
+            Signal::Term => "TERM",
+            Signal::Cont => "CONT",
+            Signal::Stop => "STOP",
+            Signal::Tstp => "TSTP",
+            Signal::Winch => "WINCH",
+            Signal::Number(_) => "",
+        }
+    }
+
+    /// `SIGHUP`, or `signal 34` for an unnamed number.
+    pub(crate) fn name(self) -> String {
+        match self {
+            Signal::Number(n) => format!("signal {}", n),
+            named => format!("SIG{}", named.short_name()),
+        }
+    }
+
+    /// What the process does with it by default, for the picker.
+    pub(crate) fn describe(self) -> &'static str {
+        match self {
+            Signal::Hup => "reload config (nginx, gunicorn, sshd)",
+            Signal::Int => "interrupt, like Ctrl+C",
+            Signal::Quit => "quit, often with a core or thread dump",
+            Signal::Kill => "force kill; can't be caught",
+            Signal::Usr1 => "app-defined; nginx reopens its logs",
+            Signal::Usr2 => "app-defined; nginx and unicorn upgrade",
+            Signal::Term => "ask the process to exit",
+            Signal::Cont => "resume a stopped process",
+            Signal::Stop => "pause; can't be caught",
+            Signal::Tstp => "pause, like Ctrl+Z",
+            Signal::Winch => "window size changed; nginx stops workers",
+            Signal::Number(_) => "",
+        }
+    }
+
+    /// Whether the process is expected to go away, so the port frees up
+    /// and a restart hint makes sense.
+    pub(crate) fn stops_process(self) -> bool {
+        matches!(
+            self,
+            Signal::Term | Signal::Kill | Signal::Int | Signal::Quit
+        )
+    }
+
+    /// Whether Windows can carry it out (as TerminateProcess).
+    #[cfg(not(unix))]
+    pub(crate) fn supported_on_windows(self) -> bool {
+        matches!(self, Signal::Term | Signal::Kill)
+    }
+
+    #[cfg(unix)]
+    pub(crate) fn number(self) -> Option<i32> {
+        Some(match self {
+            Signal::Hup => libc::SIGHUP,
+            Signal::Int => libc::SIGINT,
+            Signal::Quit => libc::SIGQUIT,
+            Signal::Kill => libc::SIGKILL,
+            Signal::Usr1 => libc::SIGUSR1,
+            Signal::Usr2 => libc::SIGUSR2,
+            Signal::Term => libc::SIGTERM,
+            Signal::Cont => libc::SIGCONT,
+            Signal::Stop => libc::SIGSTOP,
+            Signal::Tstp => libc::SIGTSTP,
+            Signal::Winch => libc::SIGWINCH,
+            Signal::Number(n) => n,
+        })
+    }
+
+    #[cfg(not(unix))]
+    pub(crate) fn number(self) -> Option<i32> {
+        match self {
+            Signal::Number(n) => Some(n),
+            _ => None,
+        }
+    }
+}
+
+/// The message for a signal Windows can't send.
+#[cfg(not(unix))]
+pub(crate) fn windows_fallback(signal: Signal) -> String {
+    format!(
+        "Windows has no {}; only TERM and KILL work there, and both terminate the process",
+        signal.name()
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_names_and_numbers() {
+        assert_eq!(Signal::parse("HUP"), Ok(Signal::Hup));
+        assert_eq!(Signal::parse("sighup"), Ok(Signal::Hup));
+        assert_eq!(Signal::parse("usr1"), Ok(Signal::Usr1));
+        assert_eq!(Signal::parse("9"), Ok(Signal::Kill));
+        assert_eq!(Signal::parse("40"), Ok(Signal::Number(40)));
+        assert!(Signal::parse("0").is_err());
+        assert!(Signal::parse("BOOM").unwrap_err().contains("HUP"));
+        assert_eq!(Signal::Hup.name(), "SIGHUP");
+        assert_eq!(Signal::Number(40).name(), "signal 40");
+        assert!(Signal::Term.stops_process());
+        assert!(!Signal::Hup.stops_process());
+    }
+}
@@ -0,0 +1,171 @@
+use crate::{collector, format_bytes, PortInfo};
+
+// ── Socket options ───────────────────────────────────────────────────
+//
+// The options a server set on its socket explain a lot of odd behaviour:
+// a restart that can't bind without SO_REUSEADDR, a second listener that
+// only works with SO_REUSEPORT, Nagle delays without TCP_NODELAY, idle
+// connections that vanish behind a NAT without keepalive. Reading them
+// from another process takes privileges on most systems, so every field
+// is optional and the detail view shows whatever could be read.
+
+/// Options on one socket. `None` means the backend couldn't read it.
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub(crate) struct SocketOptions {
+    pub(crate) reuse_addr: Option<bool>,
+    pub(crate) reuse_port: Option<bool>,
+    /// TCP only.
+    pub(crate) no_delay: Option<bool>,
+    pub(crate) keepalive: Option<bool>,
+    /// Seconds idle before the first probe.
+    pub(crate) keepalive_idle: Option<u32>,
+    /// Seconds between probes.
+    pub(crate) keepalive_interval: Option<u32>,
+    /// Unanswered probes before the connection is dropped.
+    pub(crate) keepalive_count: Option<u32>,
+    pub(crate) recv_buffer: Option<u32>,
+    pub(crate) send_buffer: Option<u32>,
+}
+
+fn on_off(value: bool) -> &'static str {
+    if value {
+        "on"
+    } else {
+        "off"
+    }
+}
+
+impl SocketOptions {
+    pub(crate) fn is_empty(&self) -> bool {
+        *self == SocketOptions::default()
+    }
+
+    /// `SO_REUSEADDR on, SO_REUSEPORT off, TCP_NODELAY on, SO_KEEPALIVE on
+    /// (idle 7200s, every 75s, 9 probes)`, leaving out what's unknown.
+    fn describe_flags(&self) -> Option<String> {
+        let mut parts = Vec::new();
+        for (name, value) in [
+            ("SO_REUSEADDR", self.reuse_addr),
+            ("SO_REUSEPORT", self.reuse_port),
+            ("TCP_NODELAY", self.no_delay),
+            ("SO_KEEPALIVE", self.keepalive),
+        ] {
+            if let Some(value) = value {
+                parts.push(format!("{} {}", name, on_off(value)));
+            }
+        }
+        if self.keepalive == Some(true) {
+            let timers: Vec<String> = [
+                self.keepalive_idle.map(|s| format!("idle {}s", s)),
+                self.keepalive_interval.map(|s| format!("every {}s", s)),
+                self.keepalive_count.map(|n| format!("{} probes", n)),
+            ]
+            .into_iter()
+            .flatten()
+            .collect();
+            if let (Some(last), false) = (parts.last_mut(), timers.is_empty()) {
+                last.push_str(&format!(" ({})", timers.join(", ")));
+            }
+        }
+        (!parts.is_empty()).then(|| parts.join(", "))
+    }
+
+    /// `recv 128 KB, send 16 KB`.
+    fn describe_buffers(&self) -> Option<String> {
+        let parts: Vec<String> = [
+            self.recv_buffer
+                .map(|b| format!("recv {}", format_bytes(b as u64))),
+            self.send_buffer
+                .map(|b| format!("send {}", format_bytes(b as u64))),
+        ]
+        .into_iter()
+        .flatten()
+        .collect();
+        (!parts.is_empty()).then(|| parts.join(", "))
+    }
+
+    /// The `Sockopts:` and `Buffers:` detail rows, for what's known.
+    pub(crate) fn rows(&self) -> Vec<(&'static str, String)> {
+        let mut rows = Vec::new();
+        rows.extend(self.describe_flags().map(|f| ("Sockopts:", f)));
+        rows.extend(self.describe_buffers().map(|b| ("Buffers:", b)));
+        rows
+    }
+
+    /// `{"reuseaddr":true,…}` with null for anything unknown.
+    pub(crate) fn json(&self) -> String {
+        let flag = |v: Option<bool>| v.map_or("null".to_string(), |b| b.to_string());
+        let num = |v: Option<u32>| v.map_or("null".to_string(), |n| n.to_string());
+        format!(
+            r#"{{"reuseaddr":{},"reuseport":{},"nodelay":{},"keepalive":{},"keepalive_idle":{},"keepalive_interval":{},"keepalive_count":{},"recv_buffer":{},"send_buffer":{}}}"#,
+            flag(self.reuse_addr),
+            flag(self.reuse_port),
+            flag(self.no_delay),
+            flag(self.keepalive),
+            num(self.keepalive_idle),
+            num(self.keepalive_interval),
+            num(self.keepalive_count),
+            num(self.recv_buffer),
+            num(self.send_buffer)
+        )
+    }
+}
+
+/// The options on the socket behind a row, when any could be read.
+pub(crate) fn lookup(info: &PortInfo) -> Option<SocketOptions> {
+    if info.pid == 0 {
+        return None;
+    }
+    collector::active()
+        .socket_options(info)
+        .filter(|o| !o.is_empty())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn rows_show_only_what_is_known() {
+        let opts = SocketOptions {
+            reuse_addr: Some(true),
+            reuse_port: Some(false),
+            keepalive: Some(true),
+            keepalive_idle: Some(7200),
+            keepalive_count: Some(9),
+            recv_buffer: Some(131072),
+            ..Default::default()
+        };
+        assert_eq!(
+            opts.rows(),
+            vec![
+                (
+                    "Sockopts:",
+                    "SO_REUSEADDR on, SO_REUSEPORT off, SO_KEEPALIVE on (idle 7200s, 9 probes)"
+                        .to_string()
+                ),
+                ("Buffers:", format!("recv {}", format_bytes(131072))),
+            ]
+        );
+        let buffers_only = SocketOptions {
+            send_buffer: Some(16384),
+            ..Default::default()
+        };
+        assert_eq!(buffers_only.rows().len(), 1);
+        assert!(SocketOptions::default().rows().is_empty());
+        assert!(SocketOptions::default().is_empty());
+    }
+
+    #[test]
+    fn json_shape() {
+        let opts = SocketOptions {
+            no_delay: Some(true),
+            send_buffer: Some(16384),
+            ..Default::default()
+        };
+        assert_eq!(
+            opts.json(),
+            r#"{"reuseaddr":null,"reuseport":null,"nodelay":true,"keepalive":null,"keepalive_idle":null,"keepalive_interval":null,"keepalive_count":null,"recv_buffer":null,"send_buffer":16384}"#
+        );
+    }
+}
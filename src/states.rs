@@ -0,0 +1,291 @@
+use std::collections::{HashMap, HashSet};
+use std::io::{self, Write};
+
+use crossterm::style::{Attribute, SetAttribute};
+use crossterm::ExecutableCommand;
+
+use crate::{json_escape, write_styled, write_table_border, ConnInfo, TcpState};
+
+// ── Aggregation ──────────────────────────────────────────────────────
+
+/// One aggregated line: how many sockets of a process sit in a given state
+/// on a given service port.
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) struct StateRow {
+    pub(crate) port: u16,
+    /// True when `port` is the remote side (we are the client).
+    pub(crate) outbound: bool,
+    pub(crate) pid: Option<u32>,
+    pub(crate) process: String,
+    pub(crate) state: TcpState,
+    pub(crate) count: usize,
+}
+
+/// Group TCP sockets by (service port, process, state).
+///
+/// The service port is the local port when something listens on it, and the
+/// remote port otherwise, so outbound connections to :443 from thousands of
+/// ephemeral ports collapse into a single row. When neither side is a known
+/// listener (e.g. TIME_WAIT left behind by a stopped server) the lower port
+/// is assumed to be the service.
+pub(crate) fn summarize(
+    conns: &[ConnInfo],
+    listening: &HashSet<u16>,
+    names: &HashMap<u32, String>,
+) -> Vec<StateRow> {
+    let mut groups: HashMap<(u16, bool, Option<u32>, TcpState), usize> = HashMap::new();
+    for c in conns.iter().filter(|c| c.protocol == "TCP") {
+        let inbound = c.state == TcpState::Listen
+            || listening.contains(&c.local_port)
+            || (!listening.contains(&c.remote_port) && c.local_port <= c.remote_port);
+        let (port, outbound) = if inbound {
+            (c.local_port, false)
+        } else {
+            (c.remote_port, true)
+        };
+        *groups.entry((port, outbound, c.pid, c.state)).or_insert(0) += 1;
+    }
+
+    let mut rows: Vec<StateRow> = groups
+        .into_iter()
+        .map(|((port, outbound, pid, state), count)| StateRow {
+            port,
+            outbound,
+            pid,
+            process: pid
+                .and_then(|p| names.get(&p).cloned())
+                .unwrap_or_else(|| "-".to_string()),
+            state,
+            count,
+        })
+        .collect();
+
+    rows.sort_by(|a, b| {
+        b.count
+            .cmp(&a.count)
+            .then_with(|| a.port.cmp(&b.port))
+            .then_with(|| a.state.as_str().cmp(b.state.as_str()))
+            .then_with(|| a.pid.cmp(&b.pid))
+    });
+    rows
+}
+
+/// Total sockets per state, in descending order of count.
+pub(crate) fn totals(rows: &[StateRow]) -> Vec<(TcpState, usize)> {
+    let mut map: HashMap<TcpState, usize> = HashMap::new();
+    for row in rows {
+        *map.entry(row.state).or_insert(0) += row.count;
+    }
+    let mut totals: Vec<(TcpState, usize)> = map.into_iter().collect();
+    totals.sort_by(|a, b| b.1.cmp(&a.1).then_with(|| a.0.as_str().cmp(b.0.as_str())));
+    totals
+}
+
+// ── Output ───────────────────────────────────────────────────────────
+
+fn port_label(row: &StateRow) -> String {
+    if row.outbound {
+        format!("→{}", row.port)
+    } else {
+        row.port.to_string()
+    }
+}
+
+pub(crate) fn display_states(rows: &[StateRow], use_color: bool) {
+    let mut out = io::stdout();
+    if rows.is_empty() {
+        write_styled(&mut out, "No TCP sockets found.\n", "dimmed", use_color);
+        return;
+    }
+
+    let total: usize = rows.iter().map(|r| r.count).sum();
+    let summary: Vec<String> = totals(rows)
+        .iter()
+        .map(|(state, n)| format!("{} {}", state, n))
+        .collect();
+    let _ = writeln!(out);
+    write_styled(
+        &mut out,
+        &format!(" {} TCP socket{}", total, if total == 1 { "" } else { "s" }),
+        "bold",
+        use_color,
+    );
+    let _ = writeln!(out);
+    write_styled(
+        &mut out,
+        &format!("  {}\n", summary.join(" · ")),
+        "dimmed",
+        use_color,
+    );
+
+    let headers = ["PORT", "PROCESS", "PID", "STATE", "COUNT"];
+    let values: Vec<[String; 5]> = rows
+        .iter()
+        .map(|r| {
+            [
+                port_label(r),
+                r.process.clone(),
+                r.pid.map(|p| p.to_string()).unwrap_or_else(|| "-".into()),
+                r.state.to_string(),
+                r.count.to_string(),
+            ]
+        })
+        .collect();
+    let mut widths = headers.map(|h| h.chars().count());
+    for v in &values {
+        for (w, cell) in widths.iter_mut().zip(v.iter()) {
+            *w = (*w).max(cell.chars().count());
+        }
+    }
+    let colors = ["cyan", "bold", "yellow", "none", "none"];
+
+    write_table_border(&mut out, &widths, "╭", "┬", "╮");
+    let _ = write!(out, "│");
+    for (&w, &h) in widths.iter().zip(headers.iter()) {
+        let _ = write!(out, " ");
+        if use_color {
+            let _ = out.execute(SetAttribute(Attribute::Bold));
+        }
+        let _ = write!(out, "{:<width$}", h, width = w);
+        if use_color {
+            let _ = out.execute(SetAttribute(Attribute::Reset));
+        }
+        let _ = write!(out, " │");
+    }
+    let _ = writeln!(out);
+    write_table_border(&mut out, &widths, "├", "┼", "┤");
+
+    for (row, v) in rows.iter().zip(values.iter()) {
+        let _ = write!(out, "│");
+        for (i, (&w, cell)) in widths.iter().zip(v.iter()).enumerate() {
+            let _ = write!(out, " ");
+            // Right-align COUNT
+            let padded = if i == 4 {
+                format!("{:>width$}", cell, width = w)
+            } else {
+                format!("{:<width$}", cell, width = w)
+            };
+            let color = if i == 3 {
+                state_color(row.state)
+            } else {
+                colors[i]
+            };
+            write_styled(&mut out, &padded, color, use_color);
+            let _ = write!(out, " │");
+        }
+        let _ = writeln!(out);
+    }
+    write_table_border(&mut out, &widths, "╰", "┴", "╯");
+}
+
+/// Highlight states that usually point at a problem when they pile up.
+fn state_color(state: TcpState) -> &'static str {
+    match state {
+        TcpState::Listen | TcpState::Established => "green",
+        TcpState::TimeWait => "yellow",
+        TcpState::CloseWait => "red",
+        _ => "dimmed",
+    }
+}
+
+pub(crate) fn states_json(rows: &[StateRow]) -> String {
+    let items: Vec<String> = rows
+        .iter()
+        .map(|r| {
+            format!(
+                r#"{{"port":{},"direction":"{}","pid":{},"process":"{}","state":"{}","count":{}}}"#,
+                r.port,
+                if r.outbound { "outbound" } else { "inbound" },
+                r.pid
+                    .map(|p| p.to_string())
+                    .unwrap_or_else(|| "null".into()),
+                json_escape(&r.process),
+                r.state,
+                r.count
+            )
+        })
+        .collect();
+    format!("[{}]", items.join(","))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::net::{IpAddr, Ipv4Addr};
+
+    fn conn(local: u16, remote: u16, state: TcpState, pid: Option<u32>) -> ConnInfo {
+        ConnInfo {
+            protocol: "TCP".to_string(),
+            local_port: local,
+            remote_addr: IpAddr::V4(Ipv4Addr::LOCALHOST),
+            remote_port: remote,
+            state,
+            pid,
+        }
+    }
+
+    #[test]
+    fn summarize_groups_inbound_by_local_port() {
+        let conns = [
+            conn(8080, 0, TcpState::Listen, Some(1)),
+            conn(8080, 50001, TcpState::TimeWait, None),
+            conn(8080, 50002, TcpState::TimeWait, None),
+            conn(8080, 50003, TcpState::Established, Some(1)),
+        ];
+        let listening: HashSet<u16> = [8080].into_iter().collect();
+        let names: HashMap<u32, String> = [(1, "nginx".to_string())].into_iter().collect();
+        let rows = summarize(&conns, &listening, &names);
+
+        assert_eq!(rows.len(), 3);
+        assert_eq!(rows[0].state, TcpState::TimeWait);
+        assert_eq!(rows[0].count, 2);
+        assert_eq!(rows[0].process, "-");
+        assert!(rows.iter().all(|r| r.port == 8080 && !r.outbound));
+        assert!(rows
+            .iter()
+            .any(|r| r.state == TcpState::Established && r.process == "nginx"));
+    }
+
+    #[test]
+    fn summarize_groups_outbound_by_remote_port() {
+        let conns = [
+            conn(40001, 443, TcpState::Established, Some(9)),
+            conn(40002, 443, TcpState::Established, Some(9)),
+            conn(40003, 443, TcpState::TimeWait, None),
+        ];
+        let rows = summarize(&conns, &HashSet::new(), &HashMap::new());
+        assert_eq!(rows.len(), 2);
+        assert_eq!(rows[0].port, 443);
+        assert!(rows[0].outbound);
+        assert_eq!(rows[0].count, 2);
+    }
+
+    #[test]
+    fn summarize_without_listener_uses_lower_port() {
+        let conns = [conn(3000, 51584, TcpState::TimeWait, None)];
+        let rows = summarize(&conns, &HashSet::new(), &HashMap::new());
+        assert_eq!(rows[0].port, 3000);
+        assert!(!rows[0].outbound);
+    }
+
+    #[test]
+    fn summarize_ignores_udp() {
+        let mut c = conn(53, 0, TcpState::Listen, Some(1));
+        c.protocol = "UDP".to_string();
+        assert!(summarize(&[c], &HashSet::new(), &HashMap::new()).is_empty());
+    }
+
+    #[test]
+    fn totals_sum_across_rows() {
+        let conns = [
+            conn(40001, 443, TcpState::TimeWait, None),
+            conn(40002, 80, TcpState::TimeWait, None),
+            conn(40003, 80, TcpState::Established, Some(2)),
+        ];
+        let rows = summarize(&conns, &HashSet::new(), &HashMap::new());
+        assert_eq!(
+            totals(&rows),
+            vec![(TcpState::TimeWait, 2), (TcpState::Established, 1)]
+        );
+    }
+}
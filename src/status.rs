@@ -0,0 +1,88 @@
+use std::collections::HashSet;
+use std::io::Write;
+
+use crate::write_styled;
+
+// ── Status line ──────────────────────────────────────────────────────
+
+/// How `portview status` colors each port.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub(crate) enum StatusColor {
+    Off,
+    /// ANSI escapes, for terminals and shell prompts.
+    Ansi,
+    /// `#[fg=…]` markup, which tmux interprets in `status-right` output.
+    Tmux,
+}
+
+/// Write `3000✓ 5432✓ 6379✗` (or 🟢/🔴 with `emoji`) on a single line.
+pub(crate) fn write_status(
+    w: &mut impl Write,
+    ports: &[u16],
+    up: &HashSet<u16>,
+    emoji: bool,
+    color: StatusColor,
+) {
+    for (i, &port) in ports.iter().enumerate() {
+        if i > 0 {
+            let _ = write!(w, " ");
+        }
+        let is_up = up.contains(&port);
+        let mark = match (emoji, is_up) {
+            (false, true) => "\u{2713}",
+            (false, false) => "\u{2717}",
+            (true, true) => "\u{1f7e2}",
+            (true, false) => "\u{1f534}",
+        };
+        let text = format!("{}{}", port, mark);
+        match color {
+            StatusColor::Off => {
+                let _ = write!(w, "{}", text);
+            }
+            StatusColor::Ansi => {
+                write_styled(w, &text, if is_up { "green" } else { "red" }, true);
+            }
+            StatusColor::Tmux => {
+                let fg = if is_up { "green" } else { "red" };
+                let _ = write!(w, "#[fg={}]{}#[default]", fg, text);
+            }
+        }
+    }
+    let _ = writeln!(w);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn render(emoji: bool, color: StatusColor) -> String {
+        let up: HashSet<u16> = [3000, 5432].into_iter().collect();
+        let mut buf = Vec::new();
+        write_status(&mut buf, &[3000, 5432, 6379], &up, emoji, color);
+        String::from_utf8(buf).unwrap()
+    }
+
+    #[test]
+    fn plain_status_line() {
+        assert_eq!(
+            render(false, StatusColor::Off),
+            "3000\u{2713} 5432\u{2713} 6379\u{2717}\n"
+        );
+    }
+
+    #[test]
+    fn emoji_status_line() {
+        assert_eq!(
+            render(true, StatusColor::Off),
+            "3000\u{1f7e2} 5432\u{1f7e2} 6379\u{1f534}\n"
+        );
+    }
+
+    #[test]
+    fn tmux_markup() {
+        assert_eq!(
+            render(false, StatusColor::Tmux),
+            "#[fg=green]3000\u{2713}#[default] #[fg=green]5432\u{2713}#[default] #[fg=red]6379\u{2717}#[default]\n"
+        );
+    }
+}
@@ -0,0 +1,130 @@
+use std::collections::HashSet;
+use std::net::{Ipv4Addr, TcpListener, UdpSocket};
+use std::time::{SystemTime, UNIX_EPOCH};
+
+// ── Range parsing ────────────────────────────────────────────────────
+
+/// Parse `3000-3999` (inclusive) or a single port `3000`.
+pub(crate) fn parse_port_range(s: &str) -> Option<(u16, u16)> {
+    let (lo, hi) = match s.split_once('-') {
+        Some((lo, hi)) => (lo.trim().parse().ok()?, hi.trim().parse().ok()?),
+        None => {
+            let p: u16 = s.trim().parse().ok()?;
+            (p, p)
+        }
+    };
+    if lo == 0 || lo > hi {
+        return None;
+    }
+    Some((lo, hi))
+}
+
+// ── Free-port search ─────────────────────────────────────────────────
+
+/// A port is free when nothing we can see owns it and we can bind it on the
+/// wildcard address for both TCP and UDP.
+fn is_port_free(port: u16, taken: &HashSet<u16>) -> bool {
+    if taken.contains(&port) {
+        return false;
+    }
+    TcpListener::bind((Ipv4Addr::UNSPECIFIED, port)).is_ok()
+        && UdpSocket::bind((Ipv4Addr::UNSPECIFIED, port)).is_ok()
+}
+
+/// Small xorshift generator — good enough to spread suggestions without
+/// pulling in a crate.
+struct XorShift(u64);
+
+impl XorShift {
+    fn seeded() -> Self {
+        let nanos = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .unwrap_or_default()
+            .as_nanos() as u64;
+        Self((nanos ^ ((std::process::id() as u64) << 32)) | 1)
+    }
+
+    fn next(&mut self) -> u64 {
+        let mut x = self.0;
+        x ^= x << 13;
+        x ^= x >> 7;
+        x ^= x << 17;
+        self.0 = x;
+        x
+    }
+}
+
+/// Candidate order for a range: ascending, or shuffled when `random` is set.
+fn candidate_order(lo: u16, hi: u16, random: bool) -> Vec<u16> {
+    let mut ports: Vec<u16> = (lo..=hi).collect();
+    if random {
+        let mut rng = XorShift::seeded();
+        for i in (1..ports.len()).rev() {
+            let j = (rng.next() % (i as u64 + 1)) as usize;
+            ports.swap(i, j);
+        }
+    }
+    ports
+}
+
+pub(crate) fn find_free_ports(
+    range: (u16, u16),
+    count: usize,
+    random: bool,
+    taken: &HashSet<u16>,
+) -> Vec<u16> {
+    candidate_order(range.0, range.1, random)
+        .into_iter()
+        .filter(|&p| is_port_free(p, taken))
+        .take(count)
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_port_range_valid() {
+        assert_eq!(parse_port_range("3000-3999"), Some((3000, 3999)));
+        assert_eq!(parse_port_range("8080"), Some((8080, 8080)));
+        assert_eq!(parse_port_range(" 10 - 20 "), Some((10, 20)));
+    }
+
+    #[test]
+    fn parse_port_range_invalid() {
+        assert_eq!(parse_port_range("3999-3000"), None);
+        assert_eq!(parse_port_range("0-10"), None);
+        assert_eq!(parse_port_range("abc"), None);
+        assert_eq!(parse_port_range("1-70000"), None);
+    }
+
+    #[test]
+    fn candidate_order_random_is_permutation() {
+        let mut ports = candidate_order(4000, 4099, true);
+        ports.sort_unstable();
+        assert_eq!(ports, (4000..=4099).collect::<Vec<_>>());
+    }
+
+    #[test]
+    fn find_free_ports_skips_bound_ports() {
+        let held = TcpListener::bind((Ipv4Addr::UNSPECIFIED, 0)).expect("ephemeral bind");
+        let port = held.local_addr().unwrap().port();
+        assert!(find_free_ports((port, port), 1, false, &HashSet::new()).is_empty());
+
+        drop(held);
+        assert_eq!(
+            find_free_ports((port, port), 1, false, &HashSet::new()),
+            vec![port]
+        );
+    }
+
+    #[test]
+    fn find_free_ports_skips_taken_set() {
+        let held = TcpListener::bind((Ipv4Addr::UNSPECIFIED, 0)).expect("ephemeral bind");
+        let port = held.local_addr().unwrap().port();
+        drop(held);
+        let taken: HashSet<u16> = [port].into_iter().collect();
+        assert!(find_free_ports((port, port), 1, false, &taken).is_empty());
+    }
+}
@@ -0,0 +1,114 @@
+use std::collections::{HashMap, HashSet};
+
+use crate::PortInfo;
+
+// ── Summary dashboard ────────────────────────────────────────────────
+//
+// On a server with hundreds of listeners the table answers "what is on
+// port X" but not "who is using all these ports". The TUI's summary view
+// counts the rows it would show by user, by process and by state, and
+// adds up the memory of the processes behind them.
+
+/// Counts for one refresh, largest first.
+#[derive(Debug, Default, PartialEq)]
+pub(crate) struct Summary {
+    /// Rows counted: sockets, so a process with two ports counts twice.
+    pub(crate) sockets: usize,
+    pub(crate) by_user: Vec<(String, usize)>,
+    pub(crate) by_process: Vec<(String, usize)>,
+    /// `LISTEN`, `ESTABLISHED`, ...; UDP sockets are counted as `UDP`.
+    pub(crate) by_state: Vec<(String, usize)>,
+    /// Resident memory per process name, each PID counted once.
+    pub(crate) memory: Vec<(String, u64)>,
+    pub(crate) total_memory: u64,
+}
+
+pub(crate) fn summarize<'a>(infos: impl IntoIterator<Item = &'a PortInfo>) -> Summary {
+    let mut summary = Summary::default();
+    let mut users: HashMap<&str, usize> = HashMap::new();
+    let mut processes: HashMap<&str, usize> = HashMap::new();
+    let mut states: HashMap<&str, usize> = HashMap::new();
+    let mut memory: HashMap<&str, u64> = HashMap::new();
+    let mut pids = HashSet::new();
+    for info in infos {
+        summary.sockets += 1;
+        *users.entry(&info.user).or_insert(0) += 1;
+        *processes.entry(&info.process_name).or_insert(0) += 1;
+        let state = if info.protocol.starts_with("UDP") {
+            "UDP"
+        } else {
+            info.state.as_str()
+        };
+        *states.entry(state).or_insert(0) += 1;
+        // Docker rows (PID 0) have no process of their own
+        if info.pid != 0 && pids.insert(info.pid) {
+            *memory.entry(&info.process_name).or_insert(0) += info.memory_bytes;
+            summary.total_memory += info.memory_bytes;
+        }
+    }
+    summary.by_user = ranked(users);
+    summary.by_process = ranked(processes);
+    summary.by_state = ranked(states);
+    summary.memory = ranked(memory);
+    summary.memory.retain(|(_, bytes)| *bytes > 0);
+    summary
+}
+
+/// Largest first, then by name so the order holds still between refreshes.
+fn ranked<T: Ord + Copy>(map: HashMap<&str, T>) -> Vec<(String, T)> {
+    let mut rows: Vec<(String, T)> = map
+        .into_iter()
+        .map(|(name, n)| {
+            let name = if name.is_empty() { "-" } else { name };
+            (name.to_string(), n)
+        })
+        .collect();
+    rows.sort_by(|a, b| b.1.cmp(&a.1).then_with(|| a.0.cmp(&b.0)));
+    rows
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::TcpState;
+
+    fn row(port: u16, pid: u32, process: &str, user: &str, protocol: &str, mem: u64) -> PortInfo {
+        PortInfo::listener(port, pid, process)
+            .user(user)
+            .protocol(protocol)
+            .usage(mem, 0.0)
+    }
+
+    #[test]
+    fn counts_sockets_and_memory_per_process() {
+        let mut conn = row(51234, 10, "node", "alice", "TCP", 100);
+        conn.state = TcpState::Established;
+        let rows = [
+            row(3000, 10, "node", "alice", "TCP", 100),
+            row(3001, 10, "node", "alice", "TCP6", 100),
+            conn,
+            row(5432, 20, "postgres", "postgres", "TCP", 400),
+            row(5353, 30, "avahi-daemon", "avahi", "UDP", 0),
+            row(8080, 0, "web", "", "TCP", 0),
+        ];
+        let summary = summarize(&rows);
+        assert_eq!(summary.sockets, 6);
+        assert_eq!(summary.by_user[0], ("alice".to_string(), 3));
+        assert!(summary.by_user.contains(&("-".to_string(), 1)));
+        assert_eq!(summary.by_process[0], ("node".to_string(), 3));
+        assert_eq!(
+            summary.by_state,
+            [
+                ("LISTEN".to_string(), 4),
+                ("ESTABLISHED".to_string(), 1),
+                ("UDP".to_string(), 1)
+            ]
+        );
+        assert_eq!(
+            summary.memory,
+            [("postgres".to_string(), 400), ("node".to_string(), 100)],
+            "each PID once, nothing for empty ones"
+        );
+        assert_eq!(summary.total_memory, 500);
+    }
+}
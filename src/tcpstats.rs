@@ -0,0 +1,165 @@
+use std::collections::HashMap;
+
+// ── TCP stats ────────────────────────────────────────────────────────
+//
+// `watch --tcp-stats` adds an RTT/RETX column to the TUI: the average
+// smoothed round-trip time of each listener's connections over the last
+// refresh, and how many segments they retransmitted since the watch
+// started. The numbers come from eBPF programs on the kernel's TCP
+// tracepoints (see ebpf.rs), which only exist in Linux builds with the
+// `ebpf` cargo feature. Everywhere else, and when the kernel or the
+// user's privileges don't allow BPF, the watch runs without the column
+// and says why.
+
+/// Running counts for one local port since the probes were attached.
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub(crate) struct Totals {
+    pub(crate) rtt_sum_us: u64,
+    pub(crate) rtt_samples: u64,
+    pub(crate) retransmits: u64,
+}
+
+/// What the table shows for one listener.
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub(crate) struct PortStats {
+    /// Mean smoothed RTT over the last interval, in µs; `None` when no
+    /// segment arrived.
+    pub(crate) rtt_us: Option<u64>,
+    /// Retransmits since the probes were attached.
+    pub(crate) retransmits: u64,
+}
+
+impl PortStats {
+    fn between(before: Totals, now: Totals) -> Self {
+        let samples = now.rtt_samples.saturating_sub(before.rtt_samples);
+        Self {
+            rtt_us: (samples > 0)
+                .then(|| now.rtt_sum_us.saturating_sub(before.rtt_sum_us) / samples),
+            retransmits: now.retransmits,
+        }
+    }
+
+    /// `1.2ms/3`, or `-/0` with no traffic.
+    pub(crate) fn cell(&self) -> String {
+        format!(
+            "{}/{}",
+            self.rtt_us.map_or("-".to_string(), format_rtt),
+            self.retransmits
+        )
+    }
+
+    /// The detail view's `TCP:` row.
+    pub(crate) fn describe(&self) -> String {
+        let rtt = match self.rtt_us {
+            Some(us) => format!("RTT {}", format_rtt(us)),
+            None => "no traffic".to_string(),
+        };
+        format!(
+            "{}, {} retransmit{} since the watch started",
+            rtt,
+            self.retransmits,
+            if self.retransmits == 1 { "" } else { "s" }
+        )
+    }
+}
+
+/// `850µs`, `1.2ms`, `120ms` or `1.5s`.
+pub(crate) fn format_rtt(us: u64) -> String {
+    match us {
+        0..=999 => format!("{}µs", us),
+        1_000..=9_999 => format!("{:.1}ms", us as f64 / 1_000.0),
+        10_000..=999_999 => format!("{}ms", us / 1_000),
+        _ => format!("{:.1}s", us as f64 / 1_000_000.0),
+    }
+}
+
+#[cfg(all(target_os = "linux", feature = "ebpf"))]
+use crate::ebpf::Probes;
+
+/// Stand-in when the probes can't be built.
+#[cfg(not(all(target_os = "linux", feature = "ebpf")))]
+struct Probes;
+
+#[cfg(not(all(target_os = "linux", feature = "ebpf")))]
+impl Probes {
+    fn attach() -> Result<Self, String> {
+        Err(if cfg!(target_os = "linux") {
+            "this portview was built without the `ebpf` feature".to_string()
+        } else {
+            "TCP stats need eBPF, which is Linux-only".to_string()
+        })
+    }
+
+    fn totals(&self, _port: u16) -> Totals {
+        Totals::default()
+    }
+}
+
+/// Attached probes and the counts seen at the previous sample.
+pub(crate) struct Sampler {
+    probes: Probes,
+    last: HashMap<u16, Totals>,
+}
+
+impl Sampler {
+    /// Load and attach the probes, or say why they can't be.
+    pub(crate) fn attach() -> Result<Self, String> {
+        Ok(Self {
+            probes: Probes::attach()?,
+            last: HashMap::new(),
+        })
+    }
+
+    /// Stats per port since the previous call.
+    pub(crate) fn sample(
+        &mut self,
+        ports: impl IntoIterator<Item = u16>,
+    ) -> HashMap<u16, PortStats> {
+        let mut stats = HashMap::new();
+        let mut seen = HashMap::new();
+        for port in ports {
+            let now = self.probes.totals(port);
+            let before = self.last.get(&port).copied().unwrap_or_default();
+            stats.insert(port, PortStats::between(before, now));
+            seen.insert(port, now);
+        }
+        self.last = seen;
+        stats
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn stats_between_samples() {
+        let before = Totals {
+            rtt_sum_us: 1_000,
+            rtt_samples: 2,
+            retransmits: 1,
+        };
+        let now = Totals {
+            rtt_sum_us: 7_000,
+            rtt_samples: 6,
+            retransmits: 3,
+        };
+        let stats = PortStats::between(before, now);
+        assert_eq!(stats.rtt_us, Some(1_500));
+        assert_eq!(stats.cell(), "1.5ms/3");
+        let idle = PortStats::between(now, now);
+        assert_eq!(idle.cell(), "-/3");
+        assert_eq!(
+            idle.describe(),
+            "no traffic, 3 retransmits since the watch started"
+        );
+    }
+
+    #[test]
+    fn rtt_units() {
+        assert_eq!(format_rtt(850), "850µs");
+        assert_eq!(format_rtt(1_234), "1.2ms");
+        assert_eq!(format_rtt(120_400), "120ms");
+        assert_eq!(format_rtt(1_500_000), "1.5s");
+    }
+}
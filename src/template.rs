@@ -0,0 +1,231 @@
+use std::collections::HashMap;
+use std::io::{self, Write};
+
+use crate::config;
+use crate::runtime::{self, Runtime};
+use crate::{format_addr, format_bytes, format_uptime, PortInfo};
+
+// ── Fields ───────────────────────────────────────────────────────────
+
+/// Placeholders accepted inside `{{…}}`, listed in the error for a typo.
+const FIELD_NAMES: &[&str] = &[
+    "port",
+    "proto",
+    "addr",
+    "pid",
+    "process",
+    "command",
+    "user",
+    "state",
+    "uptime",
+    "mem",
+    "memory_bytes",
+    "cpu",
+    "children",
+    "runtime",
+    "label",
+    "cwd",
+];
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Field {
+    Port,
+    Proto,
+    Addr,
+    Pid,
+    Process,
+    Command,
+    User,
+    State,
+    Uptime,
+    Mem,
+    MemoryBytes,
+    Cpu,
+    Children,
+    Runtime,
+    Label,
+    Cwd,
+}
+
+impl Field {
+    fn parse(name: &str) -> Option<Self> {
+        Some(match name {
+            "port" => Field::Port,
+            "proto" | "protocol" => Field::Proto,
+            "addr" => Field::Addr,
+            "pid" => Field::Pid,
+            "process" => Field::Process,
+            "command" => Field::Command,
+            "user" => Field::User,
+            "state" => Field::State,
+            "uptime" => Field::Uptime,
+            "mem" => Field::Mem,
+            "memory_bytes" => Field::MemoryBytes,
+            "cpu" => Field::Cpu,
+            "children" => Field::Children,
+            "runtime" => Field::Runtime,
+            "label" => Field::Label,
+            "cwd" => Field::Cwd,
+            _ => return None,
+        })
+    }
+}
+
+// ── Template ─────────────────────────────────────────────────────────
+
+#[derive(Debug, Clone, PartialEq)]
+enum Part {
+    Literal(String),
+    Field(Field),
+}
+
+/// A `--template` format string, rendered once per port.
+///
+/// `{{field}}` is replaced by the field's value; `\n` and `\t` are
+/// unescaped so templates can be written without shell quoting tricks.
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) struct Template {
+    parts: Vec<Part>,
+}
+
+impl Template {
+    pub(crate) fn parse(src: &str) -> Result<Self, String> {
+        let mut parts = Vec::new();
+        let mut literal = String::new();
+        let mut rest = src;
+
+        while !rest.is_empty() {
+            if let Some(after) = rest.strip_prefix("{{") {
+                let end = after
+                    .find("}}")
+                    .ok_or_else(|| "unclosed '{{' in template".to_string())?;
+                let name = after[..end].trim();
+                let field = Field::parse(name).ok_or_else(|| {
+                    format!(
+                        "unknown template field '{}' (available: {})",
+                        name,
+                        FIELD_NAMES.join(", ")
+                    )
+                })?;
+                if !literal.is_empty() {
+                    parts.push(Part::Literal(std::mem::take(&mut literal)));
+                }
+                parts.push(Part::Field(field));
+                rest = &after[end + 2..];
+            } else if let Some(after) = rest.strip_prefix("\\n") {
+                literal.push('\n');
+                rest = after;
+            } else if let Some(after) = rest.strip_prefix("\\t") {
+                literal.push('\t');
+                rest = after;
+            } else {
+                let c = rest.chars().next().unwrap_or_default();
+                literal.push(c);
+                rest = &rest[c.len_utf8()..];
+            }
+        }
+        if !literal.is_empty() {
+            parts.push(Part::Literal(literal));
+        }
+        Ok(Self { parts })
+    }
+
+    /// Whether rendering needs working directories (only fetched on demand).
+    pub(crate) fn uses_cwd(&self) -> bool {
+        self.parts.contains(&Part::Field(Field::Cwd))
+    }
+
+    pub(crate) fn render(&self, info: &PortInfo, cwd: Option<&str>) -> String {
+        let mut line = String::new();
+        for part in &self.parts {
+            match part {
+                Part::Literal(text) => line.push_str(text),
+                Part::Field(field) => line.push_str(&field_value(*field, info, cwd)),
+            }
+        }
+        line
+    }
+}
+
+fn field_value(field: Field, info: &PortInfo, cwd: Option<&str>) -> String {
+    match field {
+        Field::Port => info.port.to_string(),
+        Field::Proto => info.protocol.clone(),
+        Field::Addr => format_addr(&info.local_addr),
+        Field::Pid => info.pid.to_string(),
+        Field::Process => info.process_name.clone(),
+        Field::Command => info.command.clone(),
+        Field::User => info.user.clone(),
+        Field::State => info.state.to_string(),
+        Field::Uptime => format_uptime(info.start_time),
+        Field::Mem => format_bytes(info.memory_bytes),
+        Field::MemoryBytes => info.memory_bytes.to_string(),
+        Field::Cpu => format!("{:.1}", info.cpu_seconds),
+        Field::Children => info.children.to_string(),
+        Field::Runtime => runtime::detect(info)
+            .map_or("-", Runtime::as_str)
+            .to_string(),
+        Field::Label => config::active().label(info.port).unwrap_or("-").to_string(),
+        Field::Cwd => cwd.unwrap_or("-").to_string(),
+    }
+}
+
+/// Print one rendered line per port.
+pub(crate) fn display_template(
+    infos: &[PortInfo],
+    template: &Template,
+    cwds: Option<&HashMap<u32, String>>,
+) -> io::Result<()> {
+    let mut out = io::stdout().lock();
+    for info in infos {
+        let cwd = cwds.and_then(|c| c.get(&info.pid)).map(String::as_str);
+        writeln!(out, "{}", template.render(info, cwd))?;
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn info() -> PortInfo {
+        PortInfo::listener(3000, 4242, "node")
+            .command("next dev")
+            .user("mark")
+            .usage(0, 14.3)
+            .children(3)
+    }
+
+    #[test]
+    fn render_fields_and_literals() {
+        let tpl = Template::parse("{{port}} {{ process }} ({{pid}}) on {{addr}}").unwrap();
+        assert_eq!(tpl.render(&info(), None), "3000 node (4242) on *");
+    }
+
+    #[test]
+    fn render_unescapes_tabs_and_newlines() {
+        let tpl = Template::parse(r"{{port}}\t{{cpu}}\n{{children}}").unwrap();
+        assert_eq!(tpl.render(&info(), None), "3000\t14.3\n3");
+    }
+
+    #[test]
+    fn render_missing_values_as_dash() {
+        let tpl = Template::parse("{{uptime}} {{mem}} {{cwd}}").unwrap();
+        assert!(tpl.uses_cwd());
+        assert_eq!(tpl.render(&info(), None), "- - -");
+        assert_eq!(tpl.render(&info(), Some("/srv/app")), "- - /srv/app");
+    }
+
+    #[test]
+    fn parse_rejects_unknown_and_unclosed() {
+        let err = Template::parse("{{prot}}").unwrap_err();
+        assert!(err.contains("unknown template field 'prot'"));
+        assert!(err.contains("proto"));
+        assert!(Template::parse("{{port").is_err());
+        // A lone brace is just text
+        assert_eq!(
+            Template::parse("{port}").unwrap().render(&info(), None),
+            "{port}"
+        );
+    }
+}
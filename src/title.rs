@@ -0,0 +1,90 @@
+use std::io::{self, Write};
+
+// ── Terminal title ───────────────────────────────────────────────────
+//
+// `watch --title` keeps the terminal's window title (OSC 0) set to a one
+// line summary, so a watch left in a background tab can be read from the
+// tab list:
+//
+//     portview: 14 ports, 3000 ✓ 5432 ✗
+//
+// The count is the rows on screen; each port being watched by number gets
+// a mark for whether anything is listening on it. The previous title is
+// pushed onto the terminal's title stack at the start and popped on exit;
+// terminals without the stack just keep the last summary.
+
+/// The summary for `shown` rows and, per watched port, whether it's up.
+pub(crate) fn text(shown: usize, watched: &[(u16, bool)]) -> String {
+    let mut title = format!(
+        "portview: {} port{}",
+        shown,
+        if shown == 1 { "" } else { "s" }
+    );
+    for (i, (port, up)) in watched.iter().enumerate() {
+        title.push_str(if i == 0 { ", " } else { " " });
+        let mark = if *up { "\u{2713}" } else { "\u{2717}" };
+        title.push_str(&format!("{} {}", port, mark));
+    }
+    title
+}
+
+/// Keeps the title in step with the latest summary, writing only when it
+/// changes.
+#[derive(Default)]
+pub(crate) struct TitleWriter {
+    last: Option<String>,
+}
+
+impl TitleWriter {
+    pub(crate) fn update(&mut self, w: &mut impl Write, title: String) -> io::Result<()> {
+        if self.last.as_ref() == Some(&title) {
+            return Ok(());
+        }
+        if self.last.is_none() {
+            // XTWINOPS: save the current title to restore on exit
+            w.write_all(b"\x1b[22;0t")?;
+        }
+        write!(w, "\x1b]0;{}\x07", title)?;
+        w.flush()?;
+        self.last = Some(title);
+        Ok(())
+    }
+
+    /// Put back the title from before the first update.
+    pub(crate) fn restore(&mut self, w: &mut impl Write) -> io::Result<()> {
+        if self.last.take().is_some() {
+            w.write_all(b"\x1b[23;0t")?;
+            w.flush()?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn summary_text() {
+        assert_eq!(text(1, &[]), "portview: 1 port");
+        assert_eq!(text(14, &[(3000, true)]), "portview: 14 ports, 3000 ✓");
+        assert_eq!(
+            text(1, &[(3000, true), (5432, false)]),
+            "portview: 1 port, 3000 ✓ 5432 ✗"
+        );
+    }
+
+    #[test]
+    fn writes_only_changes_and_restores() {
+        let mut out = Vec::new();
+        let mut writer = TitleWriter::default();
+        writer.update(&mut out, text(2, &[])).unwrap();
+        writer.update(&mut out, text(2, &[])).unwrap();
+        writer.update(&mut out, text(3, &[])).unwrap();
+        writer.restore(&mut out).unwrap();
+        assert_eq!(
+            String::from_utf8(out).unwrap(),
+            "\x1b[22;0t\x1b]0;portview: 2 ports\x07\x1b]0;portview: 3 ports\x07\x1b[23;0t"
+        );
+    }
+}
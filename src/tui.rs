@@ -1,6 +1,8 @@
-use std::collections::HashSet;
+use std::collections::{HashMap, HashSet, VecDeque};
 use std::io;
-use std::time::{Duration, Instant};
+use std::sync::{mpsc, Arc};
+use std::thread;
+use std::time::{Duration, Instant, SystemTime};
 
 use crossterm::event::{self, Event, KeyCode, KeyEventKind, KeyModifiers};
 use crossterm::terminal::{
@@ -16,25 +18,54 @@ use ratatui::widgets::{
 };
 use ratatui::Terminal;
 
+use crate::actions::{self, ActionExecutor};
+use crate::alerts::{AlertMonitor, AlertRule};
+use crate::backlog;
+use crate::bandwidth::{format_rate_pair, BandwidthSampler, Rate};
+use crate::batch::Target;
+use crate::clipboard;
+use crate::collector::{self, PortCollector};
+use crate::config;
+use crate::confirm::{self, Confirm, TypeBack};
 use crate::docker::{
-    get_docker_port_map, run_docker_action, run_docker_logs, DockerPortMap, DockerPortOwner,
+    self, exec_shell, view_compose_project, ComposeService, ContainerNetwork, ContainerState,
+    DockerPortMap, DockerPortOwner, EventWatcher, Forwarded,
 };
-#[cfg(target_os = "linux")]
-use crate::linux::get_port_infos;
-#[cfg(target_os = "macos")]
-use crate::macos::get_port_infos;
-#[cfg(target_os = "windows")]
-use crate::windows::get_port_infos;
+use crate::ephemeral;
+use crate::events::PortTracker;
+use crate::expect::{self, Manifest, ServiceStatus};
+use crate::export;
+use crate::hooks::EventHooks;
+use crate::keymap::{Action, Keymap};
+use crate::notify::{notify_matching, NotifyRule};
+use crate::orphan::{self, Orphan};
+use crate::peers::{aggregate_peers, format_peer, resolve_hostnames, PeerSummary};
+use crate::recording::{format_offset, Recorder, Replay};
+use crate::refresh::{self, ChangeWatcher};
+use crate::remember::{self, TuiState};
+use crate::resolver;
+use crate::reuseport::{self, GroupKey};
+use crate::runtime::{self, Runtime};
+use crate::signals::{self, Signal};
+use crate::sockopts::{self, SocketOptions};
+use crate::summary::{self, Summary};
+use crate::tcpstats::{self, PortStats};
+use crate::title::{self, TitleWriter};
+use crate::udpservice::{self, DetailRows};
+use crate::verify;
 
 use crate::{
-    chrono_free_time, format_addr, format_bytes, format_uptime, kill_process, short_container_id,
-    synthesize_docker_entries, truncate_cmd, wrap_cmd, PortInfo, StyleConfig,
+    chrono_free_time, collect_cwds, container_usage, current_user, cwd_cell, deliver_alerts,
+    detail_rows, format_addr, format_bytes, format_started, format_uptime, get_port_infos,
+    is_elevated, kill_summary, load_process_detail, matches_target, orphan_warning, partially_read,
+    port_infos_from, scroll_cmd, short_container_id, synthesize_docker_entries, truncate_cmd,
+    wrap_cmd, PortInfo, ProcessDetail, StyleConfig, TcpState,
 };
 
 // ── Sort types ───────────────────────────────────────────────────────
 
 #[derive(Clone, Copy, Debug, PartialEq)]
-enum SortColumn {
+pub(crate) enum SortColumn {
     Port,
     Proto,
     Pid,
@@ -42,6 +73,12 @@ enum SortColumn {
     Process,
     Uptime,
     Mem,
+    Net,
+    Label,
+    Tcp,
+    Runtime,
+    Health,
+    Cwd,
     Command,
 }
 
@@ -54,7 +91,13 @@ impl SortColumn {
             Self::User => Self::Process,
             Self::Process => Self::Uptime,
             Self::Uptime => Self::Mem,
-            Self::Mem => Self::Command,
+            Self::Mem => Self::Net,
+            Self::Net => Self::Label,
+            Self::Label => Self::Tcp,
+            Self::Tcp => Self::Runtime,
+            Self::Runtime => Self::Health,
+            Self::Health => Self::Cwd,
+            Self::Cwd => Self::Command,
             Self::Command => Self::Port,
         }
     }
@@ -68,10 +111,66 @@ impl SortColumn {
             Self::Process => Self::User,
             Self::Uptime => Self::Process,
             Self::Mem => Self::Uptime,
-            Self::Command => Self::Mem,
+            Self::Net => Self::Mem,
+            Self::Label => Self::Net,
+            Self::Tcp => Self::Label,
+            Self::Runtime => Self::Tcp,
+            Self::Health => Self::Runtime,
+            Self::Cwd => Self::Health,
+            Self::Command => Self::Cwd,
         }
     }
 
+    /// Every column, in table order.
+    const ALL: [SortColumn; 14] = [
+        Self::Port,
+        Self::Proto,
+        Self::Pid,
+        Self::User,
+        Self::Process,
+        Self::Uptime,
+        Self::Mem,
+        Self::Net,
+        Self::Label,
+        Self::Tcp,
+        Self::Runtime,
+        Self::Health,
+        Self::Cwd,
+        Self::Command,
+    ];
+
+    /// `mem`, `rtt`: the name used in the config and the state file.
+    pub(crate) fn name(self) -> &'static str {
+        match self {
+            Self::Port => "port",
+            Self::Proto => "proto",
+            Self::Pid => "pid",
+            Self::User => "user",
+            Self::Process => "process",
+            Self::Uptime => "uptime",
+            Self::Mem => "mem",
+            Self::Net => "net",
+            Self::Label => "label",
+            Self::Tcp => "rtt",
+            Self::Runtime => "runtime",
+            Self::Health => "health",
+            Self::Cwd => "cwd",
+            Self::Command => "command",
+        }
+    }
+
+    /// `mem`, `mem desc` or `port asc`.
+    pub(crate) fn parse_sort(s: &str) -> Option<(Self, SortDirection)> {
+        let mut words = s.split_whitespace();
+        let name = words.next()?.to_ascii_lowercase();
+        let column = Self::ALL.into_iter().find(|c| c.name() == name)?;
+        let direction = match words.next() {
+            None => SortDirection::Asc,
+            Some(d) => SortDirection::parse(d)?,
+        };
+        words.next().is_none().then_some((column, direction))
+    }
+
     fn label(self) -> &'static str {
         match self {
             Self::Port => "PORT",
@@ -81,27 +180,19 @@ impl SortColumn {
             Self::Process => "PROCESS",
             Self::Uptime => "UPTIME",
             Self::Mem => "MEM",
+            Self::Net => "NET",
+            Self::Label => "LABEL",
+            Self::Tcp => "RTT/RETX",
+            Self::Runtime => "RUNTIME",
+            Self::Health => "HEALTH",
+            Self::Cwd => "CWD",
             Self::Command => "COMMAND",
         }
     }
-
-    fn from_index(i: usize) -> Option<Self> {
-        match i {
-            0 => Some(Self::Port),
-            1 => Some(Self::Proto),
-            2 => Some(Self::Pid),
-            3 => Some(Self::User),
-            4 => Some(Self::Process),
-            5 => Some(Self::Uptime),
-            6 => Some(Self::Mem),
-            7 => Some(Self::Command),
-            _ => None,
-        }
-    }
 }
 
 #[derive(Clone, Copy, Debug, PartialEq)]
-enum SortDirection {
+pub(crate) enum SortDirection {
     Asc,
     Desc,
 }
@@ -120,8 +211,55 @@ impl SortDirection {
             Self::Desc => " \u{25bc}",
         }
     }
+
+    pub(crate) fn name(self) -> &'static str {
+        match self {
+            Self::Asc => "asc",
+            Self::Desc => "desc",
+        }
+    }
+
+    fn parse(s: &str) -> Option<Self> {
+        match s.to_ascii_lowercase().as_str() {
+            "asc" => Some(Self::Asc),
+            "desc" => Some(Self::Desc),
+            _ => None,
+        }
+    }
+}
+
+/// How the COMMAND column shows text wider than the column; `w` cycles.
+#[derive(Clone, Copy, Debug, PartialEq)]
+enum CommandView {
+    /// Cut off with `…`.
+    Truncate,
+    /// Continued on extra lines (`--wide`).
+    Wrap,
+    /// One line, shifted sideways with the arrow keys.
+    Scroll,
+}
+
+impl CommandView {
+    fn next(self) -> Self {
+        match self {
+            Self::Truncate => Self::Wrap,
+            Self::Wrap => Self::Scroll,
+            Self::Scroll => Self::Truncate,
+        }
+    }
+
+    fn label(self) -> &'static str {
+        match self {
+            Self::Truncate => "truncate",
+            Self::Wrap => "wrap",
+            Self::Scroll => "scroll (\u{2190}/\u{2192} to move)",
+        }
+    }
 }
 
+/// Characters the COMMAND column moves per arrow key.
+const SCROLL_STEP: usize = 8;
+
 // ── Theme ────────────────────────────────────────────────────────────
 
 struct TuiTheme {
@@ -134,8 +272,15 @@ struct TuiTheme {
     footer_key: Style,
     footer_text: Style,
     status_ok: Style,
+    /// Services that are listening but unexpected or unhealthy.
+    status_warn: Style,
+    status_down: Style,
     filter_accent: Style,
     kill_border: Style,
+    /// Rows of processes over an `--alert` threshold.
+    alert: Style,
+    /// Cells containing the `s` search text.
+    search_match: Style,
 }
 
 impl TuiTheme {
@@ -158,8 +303,14 @@ impl TuiTheme {
             footer_key: Style::default().fg(Color::Rgb(100, 200, 200)),
             footer_text: Style::default().fg(Color::Rgb(130, 135, 140)),
             status_ok: Style::default().fg(Color::Rgb(120, 200, 130)),
+            status_warn: Style::default().fg(Color::Rgb(220, 180, 80)),
+            status_down: Style::default().fg(Color::Rgb(220, 90, 90)),
             filter_accent: Style::default().fg(Color::Rgb(180, 130, 200)),
             kill_border: Style::default().fg(Color::Rgb(200, 80, 80)),
+            alert: Style::default().bg(Color::Rgb(70, 25, 30)),
+            search_match: Style::default()
+                .fg(Color::Rgb(20, 20, 20))
+                .bg(Color::Rgb(220, 180, 80)),
         }
     }
 
@@ -174,8 +325,12 @@ impl TuiTheme {
             footer_key: Style::default().add_modifier(Modifier::BOLD),
             footer_text: Style::default().add_modifier(Modifier::DIM),
             status_ok: Style::default(),
+            status_warn: Style::default().add_modifier(Modifier::BOLD),
+            status_down: Style::default().add_modifier(Modifier::REVERSED),
             filter_accent: Style::default().add_modifier(Modifier::BOLD),
             kill_border: Style::default(),
+            alert: Style::default().add_modifier(Modifier::REVERSED),
+            search_match: Style::default().add_modifier(Modifier::REVERSED),
         }
     }
 }
@@ -187,84 +342,378 @@ enum AppMode {
     Table,
     Detail,
     FilterInput,
+    SearchInput,
+    /// `:` prompt; `e` opens it with `export <path>` filled in.
+    CommandInput,
+    /// Counts by user, process and state instead of rows.
+    Summary,
+}
+
+/// How complete the picture is, shown in the title.
+#[derive(Clone, Copy, Debug, PartialEq)]
+enum Visibility {
+    /// The backend sees every process without privileges.
+    Complete,
+    /// Running as root or Administrator.
+    Elevated,
+    /// Other users' processes may be missing or only partly read.
+    Restricted,
+}
+
+impl Visibility {
+    fn detect(source: &dyn PortCollector) -> Self {
+        if !source.capabilities().needs_privileges {
+            Visibility::Complete
+        } else if is_elevated() {
+            Visibility::Elevated
+        } else {
+            Visibility::Restricted
+        }
+    }
+}
+
+/// Why the detail view's row disappeared.
+#[derive(Clone, Copy, Debug, PartialEq)]
+enum Vanished {
+    /// The process is gone.
+    Exited,
+    /// The socket closed, but the process may still be running.
+    Closed,
 }
 
 struct KillPopup {
     pid: u32,
     process_name: String,
     port: u16,
-    force: bool,
+    /// TERM or KILL from `d`/`D`; INT or QUIT from the signal picker.
+    signal: Signal,
+    /// Uptime, owner and memory, so long-lived services aren't killed by accident.
+    summary: String,
+    children: u32,
+    /// Digits typed so far, when `--confirm-force` wants the PID back or
+    /// the process isn't the user's own.
+    typed: Option<String>,
+    type_back: Option<TypeBack>,
+}
+
+impl KillPopup {
+    /// `me` is the account portview runs as.
+    fn new(info: &PortInfo, signal: Signal, confirm: Confirm, me: &str) -> Self {
+        let type_back = confirm.type_back(signal == Signal::Kill, &info.user, me);
+        Self {
+            pid: info.pid,
+            process_name: info.process_name.clone(),
+            port: info.port,
+            signal,
+            summary: kill_summary(info),
+            children: info.children,
+            typed: type_back.map(|_| String::new()),
+            type_back,
+        }
+    }
 }
 
 struct DockerPopup {
     container_name: String,
     port: u16,
-    selected: usize, // 0=Stop, 1=Restart, 2=Logs
+    compose: Option<ComposeService>,
+    selected: usize, // index into actions()
+}
+
+const DOCKER_ACTIONS: [&str; 4] = ["Stop", "Restart", "Logs", "Exec shell"];
+
+impl DockerPopup {
+    /// The per-container actions, then the compose ones when the container
+    /// belongs to a compose project.
+    fn actions(&self) -> Vec<String> {
+        let mut actions: Vec<String> = DOCKER_ACTIONS.iter().map(|a| a.to_string()).collect();
+        if let Some(compose) = &self.compose {
+            actions.push(format!("Restart service ({})", compose.service));
+            actions.push(format!("View compose project ({})", compose.project));
+        }
+        actions
+    }
+}
+
+/// A signalled process, checked on every loop turn until it has gone
+/// and let go of its port, or [`verify::TIMEOUT`] passes.
+struct KillCheck {
+    pid: u32,
+    ports: Vec<u16>,
+    signal: Signal,
+    since: Instant,
+}
+
+/// `K`: pick a signal for the row's process.
+struct SignalPopup {
+    info: PortInfo,
+    selected: usize, // index into signals::NAMED
 }
 
 enum Popup {
     Kill(KillPopup),
     Docker(DockerPopup),
+    Signal(SignalPopup),
+    /// `?`: the active key bindings.
+    Help,
+}
+
+/// Session settings taken from the command line.
+pub struct TuiOptions<'a> {
+    pub target: Option<&'a str>,
+    pub show_all: bool,
+    pub wide: bool,
+    pub force: bool,
+    pub no_color: bool,
+    pub docker: bool,
+    /// Reverse-resolve peer addresses in the detail view
+    pub dns: bool,
+    /// Start with the CWD column visible
+    pub cwd: bool,
+    /// Start with the RUNTIME column visible
+    pub runtime: bool,
+    /// How long to reuse a `docker ps` read between refreshes
+    pub docker_refresh: Duration,
+    /// Attach eBPF probes for the RTT/RETX column
+    pub tcp_stats: bool,
+    /// Desktop notification rules for listeners opening/closing
+    pub notify: &'a [NotifyRule],
+    /// Commands/webhook run when listeners open or close
+    pub hooks: &'a EventHooks,
+    /// Memory/CPU thresholds that highlight a row
+    pub alerts: &'a [AlertRule],
+    /// Send a desktop notification when an alert starts
+    pub alert_notify: bool,
+    /// Ring the bell and flash the title when a watched listener changes
+    pub bell: bool,
+    /// Keep the terminal's window title set to a summary
+    pub title: bool,
+    /// Append every refresh to a `--record` file
+    pub record: Option<Recorder>,
+    /// Play back a recording instead of reading live sockets
+    pub replay: Option<Replay>,
+    /// The last session's sort and toggles, applied over the flags
+    pub remembered: Option<TuiState>,
 }
 
 pub struct App {
+    /// Where rows and traffic come from: the installed collector,
+    /// or a scripted one in tests.
+    source: &'static dyn PortCollector,
+    /// What kills, signals and Docker actions go through.
+    actions: &'static dyn ActionExecutor,
     ports: Vec<PortInfo>,
     docker_enabled: bool,
+    docker_refresh: Duration,
+    /// Rows held by `docker-proxy` or a container VM, by (port, PID), when
+    /// `--docker` is off.
+    forwarded: HashMap<(u16, u32), Forwarded>,
+    dns_enabled: bool,
+    show_cwd: bool,
+    show_runtime: bool,
+    cwds: HashMap<u32, String>,
+    /// Zombie or abandoned owners by PID; empty in replay.
+    orphans: HashMap<u32, Orphan>,
+    /// SO_REUSEPORT groups whose members are listed under the folded row.
+    expanded_groups: HashSet<GroupKey>,
     docker_map: DockerPortMap,
     table_state: TableState,
     mode: AppMode,
     show_all: bool,
     filter_text: String,
+    /// Highlights matching cells without hiding rows; `n`/`N` jump between them.
+    search_text: String,
+    /// `y` was pressed; the next key picks what to copy.
+    yank_pending: bool,
+    command_text: String,
     popup: Option<Popup>,
     target: Option<String>,
     styles: StyleConfig,
     theme: TuiTheme,
-    wide: bool,
+    keymap: Keymap,
+    command_view: CommandView,
+    /// Characters hidden off the left of the COMMAND column in
+    /// [`CommandView::Scroll`].
+    command_offset: usize,
     default_force: bool,
+    confirm: Confirm,
+    visibility: Visibility,
+    /// Listeners in the socket tables whose process can't be seen, when
+    /// [`Visibility::Restricted`].
+    hidden_listeners: usize,
     should_quit: bool,
     last_refresh: Instant,
-    detail_index: usize,
+    /// The row the detail view follows. Refreshes find it again by port,
+    /// PID, protocol and address, so re-sorting can't swap in another.
+    detail_info: Option<PortInfo>,
+    /// Set once that row is gone; the view keeps its last values.
+    vanished: Option<Vanished>,
+    /// Lines scrolled off the top of the detail view.
+    detail_scroll: u16,
+    /// Lines the detail view had room for on the last draw.
+    detail_page: u16,
+    /// `v`: show the selected row's detail beside the table when the
+    /// terminal is wide enough.
+    split_view: bool,
+    /// Whether the last draw had room for that pane.
+    pane_shown: bool,
+    /// Width inside the border on the last draw.
+    last_width: u16,
+    detail: Option<ProcessDetail>,
+    /// The `Backlog:` row for the detail view's listener.
+    backlog: Option<String>,
+    /// The `Ephemeral:` row, when the listener's port is in the ephemeral
+    /// range.
+    ephemeral: Option<String>,
+    /// Options on the detail view's socket, when they can be read.
+    socket_options: Option<SocketOptions>,
+    /// Networks of the detail row's containers, by container ID; read once
+    /// per detail view.
+    container_networks: HashMap<String, Vec<ContainerNetwork>>,
+    /// What the detail view's UDP socket serves, by PID and port, so the
+    /// probes are sent once rather than on every refresh.
+    udp_rows: Option<((u32, u16), DetailRows)>,
+    /// eBPF probes behind the RTT/RETX column, with `--tcp-stats`.
+    tcp_sampler: Option<tcpstats::Sampler>,
+    /// RTT and retransmits per listening port, from the last refresh.
+    tcp_stats: HashMap<u16, PortStats>,
+    peers: Vec<PeerSummary>,
+    sampler: BandwidthSampler,
+    /// Throughput per PID since the previous refresh.
+    rates: HashMap<u32, Rate>,
+    notify_rules: Vec<NotifyRule>,
+    hooks: EventHooks,
+    tracker: PortTracker,
+    alerts: AlertMonitor,
+    alert_notify: bool,
+    bell: bool,
+    /// When the bell last rang, to flash the title for a moment after.
+    bell_at: Option<Instant>,
+    /// A bell for the main loop to write, since that needs the terminal.
+    bell_request: bool,
+    /// `--title`: keep the terminal title set to `window_title()`.
+    title: bool,
+    recorder: Option<Recorder>,
+    replay: Option<Replay>,
     status_message: Option<(String, Instant)>,
+    /// A kill whose outcome is still being watched for.
+    kill_check: Option<KillCheck>,
+    /// Container to open a shell in once the key handler returns, since
+    /// that needs the terminal.
+    exec_request: Option<String>,
+    /// Set by the Docker popup; the main loop shows `docker compose ps`.
+    compose_view_request: Option<ComposeService>,
     sort_column: SortColumn,
     sort_direction: SortDirection,
 }
 
 impl App {
     fn new(
-        target: Option<&str>,
-        show_all: bool,
-        wide: bool,
-        force: bool,
-        no_color: bool,
-        docker_enabled: bool,
+        opts: TuiOptions,
         styles: StyleConfig,
+        source: &'static dyn PortCollector,
+        actions: &'static dyn ActionExecutor,
     ) -> Self {
-        let theme = if no_color {
+        let theme = if opts.no_color {
             TuiTheme::no_color()
         } else {
             TuiTheme::default_btop()
         };
+        let remembered = opts.remembered;
         let mut app = Self {
+            source,
+            actions,
             ports: Vec::new(),
-            docker_enabled,
+            docker_enabled: opts.docker,
+            docker_refresh: opts.docker_refresh,
+            forwarded: HashMap::new(),
+            dns_enabled: opts.dns,
+            show_cwd: opts.cwd,
+            show_runtime: opts.runtime,
+            cwds: HashMap::new(),
+            orphans: HashMap::new(),
+            expanded_groups: HashSet::new(),
             docker_map: DockerPortMap::default(),
             table_state: TableState::default(),
             mode: AppMode::Table,
-            show_all,
+            show_all: opts.show_all,
             filter_text: String::new(),
+            search_text: String::new(),
+            yank_pending: false,
+            command_text: String::new(),
             popup: None,
-            target: target.map(|s| s.to_string()),
+            target: opts.target.map(|s| s.to_string()),
             styles,
             theme,
-            wide,
-            default_force: force,
+            // A bad [keys] table was reported when the config loaded
+            keymap: config::active().keymap().unwrap_or_default(),
+            command_view: if opts.wide {
+                CommandView::Wrap
+            } else {
+                CommandView::Truncate
+            },
+            command_offset: 0,
+            default_force: opts.force,
+            confirm: confirm::active(),
+            visibility: Visibility::detect(source),
+            hidden_listeners: 0,
             should_quit: false,
             last_refresh: Instant::now() - Duration::from_secs(2), // force immediate refresh
-            detail_index: 0,
+            detail_info: None,
+            vanished: None,
+            detail_scroll: 0,
+            detail_page: 0,
+            split_view: false,
+            pane_shown: false,
+            last_width: 0,
+            detail: None,
+            backlog: None,
+            ephemeral: None,
+            socket_options: None,
+            container_networks: HashMap::new(),
+            udp_rows: None,
+            tcp_sampler: None,
+            tcp_stats: HashMap::new(),
+            peers: Vec::new(),
+            sampler: BandwidthSampler::default(),
+            rates: HashMap::new(),
+            notify_rules: opts.notify.to_vec(),
+            hooks: opts.hooks.clone(),
+            tracker: PortTracker::default(),
+            alerts: AlertMonitor::new(opts.alerts.to_vec()),
+            alert_notify: opts.alert_notify,
+            bell: opts.bell,
+            bell_at: None,
+            bell_request: false,
+            title: opts.title,
+            recorder: opts.record,
+            replay: opts.replay,
             status_message: None,
+            kill_check: None,
+            exec_request: None,
+            compose_view_request: None,
             sort_column: SortColumn::Port,
             sort_direction: SortDirection::Asc,
         };
+        if opts.tcp_stats {
+            match tcpstats::Sampler::attach() {
+                Ok(sampler) => app.tcp_sampler = Some(sampler),
+                Err(err) => {
+                    app.status_message =
+                        Some((format!("TCP stats unavailable: {}", err), Instant::now()))
+                }
+            }
+        }
+        if let Some((column, direction)) = config::active()
+            .default_sort
+            .filter(|(c, _)| app.can_sort_by(*c))
+        {
+            app.sort_column = column;
+            app.sort_direction = direction;
+        }
+        if let Some(ref state) = remembered {
+            app.restore(state);
+        }
         app.refresh_data();
         if !app.sorted_ports().is_empty() {
             app.table_state.select(Some(0));
@@ -273,16 +722,105 @@ impl App {
     }
 
     fn refresh_data(&mut self) {
-        self.ports = get_port_infos(!self.show_all);
+        if let Some(ref replay) = self.replay {
+            // Recorded rows only; sockets, Docker and hooks stay untouched
+            self.ports = replay.current().ports.clone();
+            self.finish_refresh();
+            return;
+        }
+
+        self.ports = port_infos_from(self.source, !self.show_all);
+        // With --user, --mine or --session, other ports are left out on purpose
+        if self.visibility == Visibility::Restricted
+            && collector::user_filter().is_none()
+            && collector::session_filter().is_none()
+        {
+            let seen: HashSet<u16> = self.ports.iter().map(|i| i.port).collect();
+            self.hidden_listeners = self
+                .source
+                .listening_ports()
+                .iter()
+                .filter(|p| !seen.contains(p))
+                .count();
+        }
         self.docker_map = if self.docker_enabled {
-            get_docker_port_map()
+            let mut map = docker::cached_port_map(self.docker_refresh);
+            docker::attach_stats(&mut map, self.docker_refresh);
+            map
         } else {
             DockerPortMap::default()
         };
         if self.docker_enabled {
             let synthetic = synthesize_docker_entries(&self.ports, &self.docker_map);
             self.ports.extend(synthetic);
+        } else {
+            self.forwarded = docker::forwarded_ports(&self.ports);
+        }
+        if let Some(ref mut recorder) = self.recorder {
+            if let Err(e) = recorder.record(&self.ports) {
+                self.status_message = Some((format!("Recording stopped: {}", e), Instant::now()));
+                self.recorder = None;
+            }
+        }
+        self.rates = self
+            .sampler
+            .sample(&self.source.socket_traffic(), Instant::now());
+        if let Some(ref mut probes) = self.tcp_sampler {
+            self.tcp_stats = probes.sample(
+                self.ports
+                    .iter()
+                    .filter(|i| i.state == TcpState::Listen && i.protocol.starts_with("TCP"))
+                    .map(|i| i.port),
+            );
+        }
+        if !self.notify_rules.is_empty() || !self.hooks.is_empty() || self.bell {
+            // Events follow the CLI target, not the interactive filter
+            let target = self.target.as_deref();
+            let events = self.tracker.update(
+                self.ports
+                    .iter()
+                    .filter(|i| target.is_none_or(|t| matches_target(i, t))),
+            );
+            let notified = notify_matching(&self.notify_rules, &events);
+            let shown = if self.hooks.is_empty() && !self.bell {
+                notified.last().copied()
+            } else {
+                events.last()
+            };
+            if self.bell && !events.is_empty() {
+                self.bell_at = Some(Instant::now());
+                self.bell_request = true;
+            }
+            if let Some(event) = shown {
+                self.status_message = Some((event.describe(), Instant::now()));
+            }
+            self.hooks.fire(&events);
+        }
+        if !self.alerts.is_empty() {
+            let target = self.target.as_deref();
+            let fired = self.alerts.update(
+                self.ports
+                    .iter()
+                    .filter(|i| target.is_none_or(|t| matches_target(i, t))),
+                Instant::now(),
+            );
+            if let Some(alert) = fired.last() {
+                self.status_message =
+                    Some((format!("Alert: {}", alert.describe()), Instant::now()));
+            }
+            deliver_alerts(&fired, self.alert_notify, &self.hooks);
         }
+        self.cwds = if self.show_cwd {
+            collect_cwds(&self.ports)
+        } else {
+            HashMap::new()
+        };
+        self.orphans = orphan::scan(&self.ports);
+        self.finish_refresh();
+    }
+
+    /// Bookkeeping shared by live and replayed refreshes.
+    fn finish_refresh(&mut self) {
         self.last_refresh = Instant::now();
 
         // Clamp selection
@@ -296,18 +834,325 @@ impl App {
         } else {
             self.table_state.select(Some(0));
         }
+
+        if self.mode == AppMode::Detail || self.pane_shown {
+            self.refresh_detail();
+        }
+    }
+
+    /// Re-read peers and process details for the port shown in the detail
+    /// view. Only done while the detail view is open, since it walks every
+    /// socket on the host.
+    fn refresh_detail(&mut self) {
+        self.follow_detail_row();
+        if self.replay.is_some() {
+            // Nothing recorded beyond the table rows
+            self.peers.clear();
+            self.detail = None;
+            self.backlog = None;
+            self.socket_options = None;
+            self.udp_rows = None;
+            return;
+        }
+        if self.vanished.is_some() {
+            // Keep the last peers and details rather than blanking them
+            return;
+        }
+        let selected = self
+            .detail_info
+            .as_ref()
+            .filter(|info| info.pid != 0)
+            .map(|info| (info.port, info.pid));
+        self.peers = match selected {
+            Some((port, _)) if self.source.capabilities().supports_remote_addr => {
+                aggregate_peers(&self.source.connections(), port)
+            }
+            _ => Vec::new(),
+        };
+        self.detail = selected.map(|(_, pid)| load_process_detail(pid));
+        if self.docker_enabled {
+            let port = self.detail_info.as_ref().map(|i| i.port);
+            let missing: Vec<&str> = port
+                .and_then(|p| self.docker_owners_for_port(p))
+                .unwrap_or(&[])
+                .iter()
+                .map(|o| o.container_id.as_str())
+                .filter(|id| !self.container_networks.contains_key(*id))
+                .collect();
+            let found = docker::container_networks(&missing);
+            self.container_networks.extend(found);
+        }
+        self.backlog = self.detail_info.as_ref().and_then(backlog::detail_row);
+        self.ephemeral = self.detail_info.as_ref().and_then(ephemeral::detail_row);
+        self.socket_options = self.detail_info.as_ref().and_then(sockopts::lookup);
+        let probed = self.detail_info.as_ref().map(|i| (i.pid, i.port));
+        if self.udp_rows.as_ref().map(|(key, _)| *key) != probed {
+            self.udp_rows = self
+                .detail_info
+                .as_ref()
+                .map(|info| ((info.pid, info.port), udpservice::detail_rows(info)));
+        }
+        if self.dns_enabled {
+            // Don't block the UI: names resolved in the background show up
+            // on a later refresh.
+            resolve_hostnames(&mut self.peers, Duration::ZERO);
+        }
+    }
+
+    /// Open the detail view on `info`, scrolled to the top.
+    fn open_detail(&mut self, info: PortInfo) {
+        self.detail_info = Some(info);
+        self.vanished = None;
+        self.detail_scroll = 0;
+        self.container_networks.clear();
+        self.mode = AppMode::Detail;
+        self.refresh_detail();
+    }
+
+    /// Point the split view's pane at the selected row, loading its
+    /// details when the selection has moved.
+    fn follow_selection(&mut self) {
+        let selected = self.selected_port().cloned();
+        let same = match (&selected, &self.detail_info) {
+            (Some(a), Some(b)) => same_socket(a, b),
+            (None, None) => true,
+            _ => false,
+        };
+        if same {
+            return;
+        }
+        self.detail_info = selected;
+        self.vanished = None;
+        self.detail_scroll = 0;
+        self.container_networks.clear();
+        if self.detail_info.is_some() {
+            self.refresh_detail();
+        }
+    }
+
+    fn toggle_split(&mut self) {
+        self.split_view = !self.split_view;
+        let message = if !self.split_view {
+            "Split view off".to_string()
+        } else if self.last_width >= SPLIT_MIN_WIDTH {
+            "Split view on".to_string()
+        } else {
+            format!(
+                "Split view on once the window is {} columns wide",
+                SPLIT_MIN_WIDTH + 2
+            )
+        };
+        self.status_message = Some((message, Instant::now()));
+    }
+
+    /// Swap in the fresh copy of the detail row, or note why it's gone.
+    fn follow_detail_row(&mut self) {
+        let Some(ref shown) = self.detail_info else {
+            return;
+        };
+        let fresh = self.ports.iter().find(|i| same_socket(i, shown)).cloned();
+        match fresh {
+            Some(info) => {
+                self.detail_info = Some(info);
+                self.vanished = None;
+            }
+            None if self.vanished.is_none() => {
+                // A recording can't say whether its processes still run
+                let exited = shown.pid != 0
+                    && self.replay.is_none()
+                    && !self.ports.iter().any(|i| i.pid == shown.pid)
+                    && self.source.process_alive(shown.pid) == Some(false);
+                self.vanished = Some(if exited {
+                    Vanished::Exited
+                } else {
+                    Vanished::Closed
+                });
+            }
+            None => {}
+        }
+    }
+
+    /// Table columns in display order; LABEL only when the config file
+    /// has labels, RUNTIME and CWD only while they're toggled on.
+    fn visible_columns(&self) -> Vec<SortColumn> {
+        let mut columns = vec![
+            SortColumn::Port,
+            SortColumn::Proto,
+            SortColumn::Pid,
+            SortColumn::User,
+            SortColumn::Process,
+            SortColumn::Uptime,
+            SortColumn::Mem,
+            SortColumn::Net,
+        ];
+        if !config::active().labels.is_empty() {
+            columns.push(SortColumn::Label);
+        }
+        if self.tcp_sampler.is_some() {
+            columns.push(SortColumn::Tcp);
+        }
+        if self.show_runtime {
+            columns.push(SortColumn::Runtime);
+        }
+        if self.docker_enabled && !self.docker_map.is_empty() {
+            columns.push(SortColumn::Health);
+        }
+        if self.show_cwd {
+            columns.push(SortColumn::Cwd);
+        }
+        columns.push(SortColumn::Command);
+        columns
+    }
+
+    fn cycle_sort_column(&mut self, forward: bool) {
+        let mut col = self.sort_column;
+        loop {
+            col = if forward { col.next() } else { col.prev() };
+            if self.visible_columns().contains(&col) {
+                break;
+            }
+        }
+        self.sort_column = col;
+    }
+
+    fn toggle_cwd(&mut self) {
+        self.show_cwd = !self.show_cwd;
+        if !self.show_cwd && self.sort_column == SortColumn::Cwd {
+            self.sort_column = SortColumn::Port;
+            self.sort_direction = SortDirection::Asc;
+        }
+        self.refresh_data();
+    }
+
+    fn toggle_runtime(&mut self) {
+        self.show_runtime = !self.show_runtime;
+        if !self.show_runtime && self.sort_column == SortColumn::Runtime {
+            self.sort_column = SortColumn::Port;
+            self.sort_direction = SortDirection::Asc;
+        }
+    }
+
+    fn toggle_docker(&mut self) {
+        self.docker_enabled = !self.docker_enabled;
+        if !self.docker_enabled && self.sort_column == SortColumn::Health {
+            self.sort_column = SortColumn::Port;
+            self.sort_direction = SortDirection::Asc;
+        }
+        let state = if self.docker_enabled { "on" } else { "off" };
+        self.status_message = Some((format!("Docker context {}", state), Instant::now()));
+        self.refresh_data();
+    }
+
+    /// Whether the table can be sorted by `column` with the current
+    /// toggles, even if it has no rows to show it yet.
+    fn can_sort_by(&self, column: SortColumn) -> bool {
+        match column {
+            SortColumn::Label => !config::active().labels.is_empty(),
+            SortColumn::Tcp => self.tcp_sampler.is_some(),
+            SortColumn::Runtime => self.show_runtime,
+            SortColumn::Health => self.docker_enabled,
+            SortColumn::Cwd => self.show_cwd,
+            _ => true,
+        }
+    }
+
+    /// Apply the last session's settings on top of the flags.
+    fn restore(&mut self, state: &TuiState) {
+        self.show_all |= state.all;
+        self.docker_enabled |= state.docker;
+        self.show_cwd |= state.cwd;
+        self.show_runtime |= state.runtime;
+        self.split_view |= state.split;
+        if let Some((column, direction)) = state.sort.filter(|(c, _)| self.can_sort_by(*c)) {
+            self.sort_column = column;
+            self.sort_direction = direction;
+        }
+    }
+
+    /// The terminal title for `--title`: the rows on screen and, for a
+    /// port target, whether it has a listener.
+    fn window_title(&self) -> String {
+        let watched: Vec<(u16, bool)> = self
+            .target
+            .as_deref()
+            .and_then(|t| t.parse().ok())
+            .map(|port| (port, self.ports.iter().any(|i| i.port == port)))
+            .into_iter()
+            .collect();
+        title::text(self.sorted_ports().len(), &watched)
+    }
+
+    /// What to restore next time.
+    fn remembered(&self) -> TuiState {
+        TuiState {
+            sort: Some((self.sort_column, self.sort_direction)),
+            all: self.show_all,
+            docker: self.docker_enabled,
+            cwd: self.show_cwd,
+            runtime: self.show_runtime,
+            split: self.split_view,
+        }
+    }
+
+    fn cycle_command_view(&mut self) {
+        self.command_view = self.command_view.next();
+        self.command_offset = 0;
+        self.status_message = Some((
+            format!("Command column: {}", self.command_view.label()),
+            Instant::now(),
+        ));
+    }
+
+    /// Arrow keys: shift the COMMAND column, switching to scroll mode first
+    /// if needed. Rendering stops the offset at the longest command.
+    fn scroll_command(&mut self, right: bool) {
+        if self.command_view != CommandView::Scroll {
+            self.command_view = CommandView::Scroll;
+            self.command_offset = 0;
+        }
+        self.command_offset = if right {
+            self.command_offset + SCROLL_STEP
+        } else {
+            self.command_offset.saturating_sub(SCROLL_STEP)
+        };
+    }
+
+    fn rate_for_pid(&self, pid: u32) -> Option<Rate> {
+        self.rates.get(&pid).copied()
+    }
+
+    fn rate_total(&self, pid: u32) -> u64 {
+        self.rate_for_pid(pid).map(Rate::total).unwrap_or(0)
     }
 
     fn docker_owners_for_port(&self, port: u16) -> Option<&[DockerPortOwner]> {
         self.docker_map.get(&port).map(|owners| owners.as_slice())
     }
 
+    fn docker_popup(&self, info: &PortInfo) -> DockerPopup {
+        let compose = self
+            .docker_owners_for_port(info.port)
+            .and_then(|owners| {
+                owners
+                    .iter()
+                    .find(|o| o.container_name == info.process_name)
+            })
+            .and_then(|owner| owner.compose.clone());
+        DockerPopup {
+            container_name: info.process_name.clone(),
+            port: info.port,
+            compose,
+            selected: 0,
+        }
+    }
+
     fn docker_search_match(&self, port: u16, needle: &str) -> bool {
         self.docker_owners_for_port(port).is_some_and(|owners| {
             owners.iter().any(|owner| {
                 owner.container_name.to_lowercase().contains(needle)
                     || owner.image.to_lowercase().contains(needle)
                     || owner.container_id.to_lowercase().contains(needle)
+                    || owner.matches_target(needle)
             })
         })
     }
@@ -327,37 +1172,119 @@ impl App {
 
         // Apply CLI target filter (process name search)
         if let Some(ref target) = self.target {
-            if let Ok(port) = target.parse::<u16>() {
-                result.retain(|i| i.port == port);
-            } else {
-                let t = target.to_lowercase();
-                result.retain(|i| {
-                    i.process_name.to_lowercase().contains(&t)
-                        || i.command.to_lowercase().contains(&t)
-                        || (self.docker_enabled && self.docker_search_match(i.port, &t))
-                });
-            }
+            let by_name = target.parse::<u16>().is_err();
+            let t = target.to_lowercase();
+            result.retain(|i| {
+                matches_target(i, target)
+                    || (by_name && self.docker_enabled && self.docker_search_match(i.port, &t))
+            });
         }
 
         // Apply interactive filter
         if !self.filter_text.is_empty() {
             let f = self.filter_text.to_lowercase();
-            result.retain(|i| {
-                i.port.to_string().contains(&f)
-                    || i.protocol.to_lowercase().contains(&f)
-                    || i.pid.to_string().contains(&f)
-                    || i.process_name.to_lowercase().contains(&f)
-                    || i.command.to_lowercase().contains(&f)
-                    || i.user.to_lowercase().contains(&f)
-                    || (self.docker_enabled && self.docker_search_match(i.port, &f))
-            });
+            result.retain(|i| self.row_contains(i, &f));
         }
 
         result
     }
 
+    /// Whether any column of the row contains `needle` (already lowercase).
+    /// Shared by the `/` filter and `s` search.
+    fn row_contains(&self, i: &PortInfo, needle: &str) -> bool {
+        i.port.to_string().contains(needle)
+            || i.protocol.to_lowercase().contains(needle)
+            || i.pid.to_string().contains(needle)
+            || i.process_name.to_lowercase().contains(needle)
+            || i.command.to_lowercase().contains(needle)
+            || i.user.to_lowercase().contains(needle)
+            || config::active()
+                .label(i.port)
+                .is_some_and(|l| l.to_lowercase().contains(needle))
+            || (self.show_runtime
+                && runtime::detect(i).is_some_and(|rt| rt.as_str().contains(needle)))
+            || self
+                .cwds
+                .get(&i.pid)
+                .is_some_and(|c| c.to_lowercase().contains(needle))
+            || (self.docker_enabled && self.docker_search_match(i.port, needle))
+    }
+
+    /// Tab-separated row for pasting into a shell note or spreadsheet.
+    fn row_text(&self, info: &PortInfo) -> String {
+        let mut fields = vec![
+            info.port.to_string(),
+            info.protocol.clone(),
+            info.pid.to_string(),
+            info.user.clone(),
+            info.process_name.clone(),
+        ];
+        if let Some(cwd) = self.cwds.get(&info.pid) {
+            fields.push(cwd.clone());
+        }
+        fields.push(info.command.clone());
+        fields.join("\t")
+    }
+
+    /// Indices into `sorted_ports()` of rows matching the search.
+    fn search_matches(&self) -> Vec<usize> {
+        if self.search_text.is_empty() {
+            return Vec::new();
+        }
+        let needle = self.search_text.to_lowercase();
+        self.sorted_ports()
+            .iter()
+            .enumerate()
+            .filter(|(_, info)| self.row_contains(info, &needle))
+            .map(|(idx, _)| idx)
+            .collect()
+    }
+
+    /// Select the next (or previous) matching row, wrapping around.
+    fn jump_to_match(&mut self, forward: bool) {
+        let matches = self.search_matches();
+        if matches.is_empty() {
+            if !self.search_text.is_empty() {
+                self.status_message = Some((
+                    format!("No matches for '{}'", self.search_text),
+                    Instant::now(),
+                ));
+            }
+            return;
+        }
+        let target = match self.table_state.selected() {
+            Some(sel) if forward => matches.iter().find(|&&m| m > sel).or(matches.first()),
+            Some(sel) => matches.iter().rev().find(|&&m| m < sel).or(matches.last()),
+            None => matches.first(),
+        };
+        self.table_state.select(target.copied());
+    }
+
+    /// SO_REUSEPORT groups in the filtered rows: the members other than
+    /// the row each group is folded into.
+    fn reuse_members(&self) -> HashMap<GroupKey, Vec<&PortInfo>> {
+        reuseport::fold(self.filtered_ports()).1
+    }
+
+    fn toggle_reuse_group(&mut self) {
+        let Some(key) = self.selected_port().and_then(reuseport::key) else {
+            return;
+        };
+        if !self.reuse_members().contains_key(&key) {
+            return;
+        }
+        if !self.expanded_groups.remove(&key) {
+            self.expanded_groups.insert(key);
+        }
+        let count = self.sorted_ports().len();
+        if let Some(i) = self.table_state.selected() {
+            self.table_state
+                .select(Some(i.min(count.saturating_sub(1))));
+        }
+    }
+
     fn sorted_ports(&self) -> Vec<&PortInfo> {
-        let mut result = self.filtered_ports();
+        let (mut result, members) = reuseport::fold(self.filtered_ports());
         let dir = self.sort_direction;
         result.sort_by(|a, b| {
             let cmp = match self.sort_column {
@@ -380,6 +1307,34 @@ impl App {
                     }
                 }
                 SortColumn::Mem => a.memory_bytes.cmp(&b.memory_bytes),
+                SortColumn::Net => self.rate_total(a.pid).cmp(&self.rate_total(b.pid)),
+                // Unlabelled ports sort last
+                SortColumn::Label => {
+                    match (
+                        config::active().label(a.port),
+                        config::active().label(b.port),
+                    ) {
+                        (Some(la), Some(lb)) => la.to_lowercase().cmp(&lb.to_lowercase()),
+                        (Some(_), None) => std::cmp::Ordering::Less,
+                        (None, Some(_)) => std::cmp::Ordering::Greater,
+                        (None, None) => std::cmp::Ordering::Equal,
+                    }
+                }
+                // Most retransmits first in Desc, then the slowest
+                SortColumn::Tcp => {
+                    let key = |port| {
+                        self.tcp_stats
+                            .get(&port)
+                            .map(|s: &PortStats| (s.retransmits, s.rtt_us))
+                    };
+                    key(a.port).cmp(&key(b.port))
+                }
+                SortColumn::Runtime => runtime::detect(a)
+                    .map(Runtime::as_str)
+                    .cmp(&runtime::detect(b).map(Runtime::as_str)),
+                SortColumn::Health => docker::port_state(&self.docker_map, a.port)
+                    .cmp(&docker::port_state(&self.docker_map, b.port)),
+                SortColumn::Cwd => self.cwds.get(&a.pid).cmp(&self.cwds.get(&b.pid)),
                 SortColumn::Command => a.command.to_lowercase().cmp(&b.command.to_lowercase()),
             };
             if dir == SortDirection::Desc {
@@ -388,7 +1343,121 @@ impl App {
                 cmp
             }
         });
-        result
+        if members.is_empty() {
+            return result;
+        }
+        // Expanded groups list their other members under the folded row
+        let mut rows = Vec::with_capacity(result.len());
+        for row in result {
+            rows.push(row);
+            let expanded = reuseport::key(row)
+                .filter(|k| self.expanded_groups.contains(k))
+                .and_then(|k| members.get(&k));
+            if let Some(rest) = expanded {
+                rows.extend(rest);
+            }
+        }
+        rows
+    }
+
+    /// `d`/`D` on a row: the Docker popup for a container, otherwise a kill,
+    /// confirmed first unless `--no-confirm` says not to ask. A SIGKILL
+    /// that wants the port or PID typed back asks regardless.
+    fn start_kill(&mut self, info: &PortInfo, signal: Signal) {
+        if info.pid == 0 {
+            self.popup = Some(Popup::Docker(self.docker_popup(info)));
+            return;
+        }
+        let popup = KillPopup::new(info, signal, self.confirm, &current_user());
+        if self.confirm.asks(popup.type_back) {
+            self.popup = Some(Popup::Kill(popup));
+        } else {
+            self.run_kill(popup);
+        }
+    }
+
+    /// `K` on a row: the signal picker. Windows has only TerminateProcess,
+    /// which `d` already covers.
+    fn start_signal(&mut self, info: &PortInfo) {
+        let message = if info.pid == 0 {
+            "Containers have no PID to signal; use the Docker actions instead"
+        } else if cfg!(windows) {
+            "Windows has no signals; d terminates the process"
+        } else {
+            self.popup = Some(Popup::Signal(SignalPopup {
+                info: info.clone(),
+                selected: 0,
+            }));
+            return;
+        };
+        self.status_message = Some((message.to_string(), Instant::now()));
+    }
+
+    /// Signals that stop the process (TERM, KILL, INT, QUIT) go through the
+    /// usual kill confirmation; the rest are sent straight away, since they
+    /// leave the process running.
+    fn run_signal(&mut self, info: &PortInfo, signal: Signal) {
+        if signal.stops_process() {
+            self.start_kill(info, signal);
+            return;
+        }
+        let message = match self.deliver(info.pid, &info.process_name, signal) {
+            Ok(name) => format!("Sent {} to PID {}", name, info.pid),
+            Err(err) => format!("Failed to signal PID {}: {}", info.pid, err),
+        };
+        self.status_message = Some((message, Instant::now()));
+    }
+
+    /// Send `signal` to `pid`, naming it with the ports it holds on screen.
+    fn deliver(&self, pid: u32, process: &str, signal: Signal) -> io::Result<String> {
+        let target = Target::holding(pid, process, &self.ports);
+        self.actions.signal(&target, signal)
+    }
+
+    fn run_kill(&mut self, popup: KillPopup) {
+        let message = match self.deliver(popup.pid, &popup.process_name, popup.signal) {
+            Ok(action) => {
+                self.watch_kill(popup.pid, popup.port, popup.signal);
+                if action == "TerminateProcess" {
+                    format!("Terminated PID {}", popup.pid)
+                } else {
+                    format!("Sent {} to PID {}", action, popup.pid)
+                }
+            }
+            Err(err) => format!("Failed to kill PID {}: {}", popup.pid, err),
+        };
+        self.status_message = Some((message, Instant::now()));
+        // Refresh immediately to reflect killed process
+        self.refresh_data();
+    }
+
+    fn watch_kill(&mut self, pid: u32, port: u16, signal: Signal) {
+        self.kill_check = Some(KillCheck {
+            pid,
+            ports: vec![port],
+            signal,
+            since: Instant::now(),
+        });
+    }
+
+    /// Replace "Sent SIGTERM" with how the kill turned out, once the
+    /// process has gone or the wait is over.
+    fn check_kill(&mut self) {
+        let Some(ref check) = self.kill_check else {
+            return;
+        };
+        let verdict = verify::check(check.pid, &check.ports);
+        if !verdict.settled() && check.since.elapsed() < verify::TIMEOUT {
+            return;
+        }
+        let message = verdict.describe(check.pid, &check.ports, check.signal);
+        self.status_message = Some((message, Instant::now()));
+        self.kill_check = None;
+        self.refresh_data();
+    }
+
+    fn scroll_detail(&mut self, lines: i32) {
+        self.detail_scroll = (self.detail_scroll as i32 + lines).clamp(0, u16::MAX as i32) as u16;
     }
 
     fn selected_port(&self) -> Option<&PortInfo> {
@@ -432,11 +1501,22 @@ impl App {
 
 // ── Rendering ────────────────────────────────────────────────────────
 
+/// How long the title stays highlighted after `--bell` rings.
+const BELL_FLASH: Duration = Duration::from_secs(2);
+
 fn build_title_line(app: &App) -> Line<'_> {
     let visible_ports = app.sorted_ports();
     let port_count = visible_ports.len();
-    let mut spans = vec![
-        Span::styled(" portview", app.theme.title),
+    // A rung bell flashes the name for a moment, for terminals that
+    // mute it
+    let flashing = app.bell_at.is_some_and(|at| at.elapsed() < BELL_FLASH);
+    let name_style = if flashing {
+        app.theme.status_warn.add_modifier(Modifier::REVERSED)
+    } else {
+        app.theme.title
+    };
+    let mut spans = vec![
+        Span::styled(" portview", name_style),
         Span::styled("  ", app.theme.footer_text),
         Span::styled(
             format!(
@@ -449,6 +1529,21 @@ fn build_title_line(app: &App) -> Line<'_> {
         Span::raw(" "),
     ];
 
+    match app.visibility {
+        Visibility::Complete => {}
+        Visibility::Elevated => spans.push(Span::styled(
+            if cfg!(windows) { "[admin] " } else { "[root] " },
+            app.theme.status_ok,
+        )),
+        Visibility::Restricted => {
+            let label = match app.hidden_listeners {
+                0 => "[restricted] ".to_string(),
+                n => format!("[restricted: {} hidden] ", n),
+            };
+            spans.push(Span::styled(label, app.theme.status_warn));
+        }
+    }
+
     if app.show_all {
         spans.push(Span::styled(
             "(all) ",
@@ -463,6 +1558,24 @@ fn build_title_line(app: &App) -> Line<'_> {
         ));
     }
 
+    if !app.search_text.is_empty() && app.mode != AppMode::SearchInput {
+        let matches = app.search_matches();
+        let label = match app
+            .table_state
+            .selected()
+            .and_then(|sel| matches.iter().position(|&m| m == sel))
+        {
+            Some(pos) => format!(
+                "[search: {} {}/{}] ",
+                app.search_text,
+                pos + 1,
+                matches.len()
+            ),
+            None => format!("[search: {} {} found] ", app.search_text, matches.len()),
+        };
+        spans.push(Span::styled(label, app.theme.filter_accent));
+    }
+
     if let Some(ref target) = app.target {
         spans.push(Span::styled(
             format!("[target: {}] ", target),
@@ -481,6 +1594,22 @@ fn build_title_line(app: &App) -> Line<'_> {
         ));
     }
 
+    if let Some(ref replay) = app.replay {
+        let (pos, total) = replay.position();
+        spans.push(Span::styled(
+            format!(
+                "[replay {}/{} {} {}] ",
+                pos,
+                total,
+                format_offset(replay.elapsed()),
+                if replay.paused { "paused" } else { "playing" }
+            ),
+            app.theme.filter_accent,
+        ));
+    } else if app.recorder.is_some() {
+        spans.push(Span::styled("[rec] ", app.theme.kill_border));
+    }
+
     if let Some((ref msg, at)) = app.status_message {
         if at.elapsed() < Duration::from_secs(3) {
             spans.push(Span::styled(msg.clone(), app.theme.status_ok));
@@ -504,27 +1633,95 @@ fn build_footer_line(app: &App) -> Line<'_> {
             Span::styled("Esc", app.theme.footer_key),
             Span::styled(" cancel ", app.theme.footer_text),
         ])
+    } else if app.mode == AppMode::CommandInput {
+        Line::from(vec![
+            Span::styled(" :", app.theme.filter_accent),
+            Span::raw(&app.command_text),
+            Span::styled("\u{2588}", app.theme.filter_accent),
+            Span::styled("  Enter", app.theme.footer_key),
+            Span::styled(" run  ", app.theme.footer_text),
+            Span::styled("Esc", app.theme.footer_key),
+            Span::styled(" cancel ", app.theme.footer_text),
+        ])
+    } else if app.yank_pending {
+        Line::from(vec![
+            Span::styled(" copy: ", app.theme.filter_accent),
+            Span::styled("y", app.theme.footer_key),
+            Span::styled(" row  ", app.theme.footer_text),
+            Span::styled("c", app.theme.footer_key),
+            Span::styled(" command  ", app.theme.footer_text),
+            Span::styled("p", app.theme.footer_key),
+            Span::styled(" port  ", app.theme.footer_text),
+            Span::styled("Esc", app.theme.footer_key),
+            Span::styled(" cancel ", app.theme.footer_text),
+        ])
+    } else if app.mode == AppMode::SearchInput {
+        Line::from(vec![
+            Span::styled(" search: ", app.theme.filter_accent),
+            Span::raw(&app.search_text),
+            Span::styled("\u{2588}", app.theme.filter_accent),
+            Span::styled("  Enter", app.theme.footer_key),
+            Span::styled(" find  ", app.theme.footer_text),
+            Span::styled("Esc", app.theme.footer_key),
+            Span::styled(" cancel ", app.theme.footer_text),
+        ])
     } else {
-        let mut spans = vec![
-            Span::styled(" j/k", app.theme.footer_key),
-            Span::styled(" move  ", app.theme.footer_text),
-            Span::styled("Enter", app.theme.footer_key),
-            Span::styled(" inspect  ", app.theme.footer_text),
-            Span::styled("d/D", app.theme.footer_key),
-            Span::styled(" action  ", app.theme.footer_text),
-            Span::styled("/", app.theme.footer_key),
-            Span::styled(" filter  ", app.theme.footer_text),
-            Span::styled("</>/r", app.theme.footer_key),
-            Span::styled(" sort  ", app.theme.footer_text),
-            Span::styled("a", app.theme.footer_key),
-            Span::styled(" all  ", app.theme.footer_text),
-            Span::styled("q", app.theme.footer_key),
-            Span::styled(" quit  ", app.theme.footer_text),
+        let mut spans = vec![Span::raw(" ")];
+        let summary_hints: &[(&[Action], &str)] = &[
+            (&[Action::Summary], " table  "),
+            (&[Action::ToggleAll], " all  "),
+            (&[Action::Help], " help  "),
+            (&[Action::Quit], " quit  "),
+        ];
+        let table_hints: &[(&[Action], &str)] = &[
+            (&[Action::Down, Action::Up][..], " move  "),
+            (&[Action::Inspect], " inspect  "),
+            (
+                &[Action::Kill, Action::ForceKill, Action::Signal],
+                " action  ",
+            ),
+            (&[Action::Filter], " filter  "),
+            (
+                &[Action::Search, Action::NextMatch, Action::PrevMatch],
+                " search  ",
+            ),
+            (&[Action::Copy], " copy  "),
+            (&[Action::Export], " export  "),
+            (
+                &[Action::SortPrev, Action::SortNext, Action::ReverseSort],
+                " sort  ",
+            ),
+            (&[Action::ToggleAll], " all  "),
+            (&[Action::ToggleCwd], " cwd  "),
+            (&[Action::ToggleRuntime], " runtime  "),
+            (&[Action::CommandView], " command  "),
+            (&[Action::SplitView], " split  "),
+            (&[Action::Help], " help  "),
+            (&[Action::Quit], " quit  "),
         ];
-        if app.docker_enabled {
+        let hints = if app.mode == AppMode::Summary {
+            summary_hints
+        } else {
+            table_hints
+        };
+        for (actions, label) in hints {
+            let keys = app.keymap.hint(actions);
+            if !keys.is_empty() {
+                spans.push(Span::styled(keys, app.theme.footer_key));
+                spans.push(Span::styled(*label, app.theme.footer_text));
+            }
+        }
+        if app.docker_enabled && app.mode != AppMode::Summary {
             spans.push(Span::styled("docker", app.theme.footer_key));
             spans.push(Span::styled(" filterable  ", app.theme.footer_text));
         }
+        if app.replay.is_some() {
+            spans.push(Span::styled("Space", app.theme.footer_key));
+            spans.push(Span::styled(" pause  ", app.theme.footer_text));
+            spans.push(Span::styled("\u{2190}/\u{2192}", app.theme.footer_key));
+            spans.push(Span::styled(" step  ", app.theme.footer_text));
+            return Line::from(spans);
+        }
         spans.push(Span::styled(
             format!("Updated {} ", time),
             app.theme.footer_text,
@@ -533,6 +1730,15 @@ fn build_footer_line(app: &App) -> Line<'_> {
     }
 }
 
+/// Whether two rows are the same socket, for following one across
+/// refreshes.
+fn same_socket(a: &PortInfo, b: &PortInfo) -> bool {
+    a.port == b.port && a.pid == b.pid && a.protocol == b.protocol && a.local_addr == b.local_addr
+}
+
+/// Narrower than this, the split view's pane would squeeze the table.
+const SPLIT_MIN_WIDTH: u16 = 140;
+
 fn render(frame: &mut ratatui::Frame, app: &mut App) {
     let area = frame.area();
 
@@ -552,33 +1758,91 @@ fn render(frame: &mut ratatui::Frame, app: &mut App) {
     let inner = block.inner(area);
     frame.render_widget(block, area);
 
+    app.last_width = inner.width;
+    app.pane_shown = false;
     match app.mode {
-        AppMode::Table | AppMode::FilterInput => render_table(frame, app, inner),
-        AppMode::Detail => render_detail(frame, app, inner),
+        AppMode::Table | AppMode::FilterInput | AppMode::SearchInput | AppMode::CommandInput
+            if app.split_view && inner.width >= SPLIT_MIN_WIDTH =>
+        {
+            app.pane_shown = true;
+            let [table, pane] =
+                Layout::horizontal([Constraint::Fill(3), Constraint::Fill(2)]).areas(inner);
+            render_table(frame, app, table);
+            app.follow_selection();
+            let divider = Block::default()
+                .borders(Borders::LEFT)
+                .border_style(app.theme.border);
+            let pane_inner = divider.inner(pane);
+            frame.render_widget(divider, pane);
+            render_detail(frame, app, pane_inner, true);
+        }
+        AppMode::Table | AppMode::FilterInput | AppMode::SearchInput | AppMode::CommandInput => {
+            render_table(frame, app, inner)
+        }
+        AppMode::Detail => render_detail(frame, app, inner, false),
+        AppMode::Summary => render_summary(frame, app, inner),
     }
 
     // Popup overlay
     match &app.popup {
         Some(Popup::Kill(_)) => render_kill_popup(frame, app, area),
         Some(Popup::Docker(_)) => render_docker_popup(frame, app, area),
+        Some(Popup::Signal(_)) => render_signal_popup(frame, app, area),
+        Some(Popup::Help) => render_help_popup(frame, app, area),
         None => {}
     }
 }
 
+fn container_state_style(theme: &TuiTheme, state: ContainerState) -> Style {
+    match state {
+        ContainerState::Healthy | ContainerState::Running => theme.status_ok,
+        s if s.is_bad() => theme.status_down,
+        _ => theme.status_warn,
+    }
+}
+
+const CWD_COLUMN_WIDTH: usize = 24;
+/// Longer labels are cut off; the detail view shows them in full.
+const MAX_LABEL_WIDTH: usize = 20;
+
 fn render_table(frame: &mut ratatui::Frame, app: &mut App, area: Rect) {
     let ports = app.sorted_ports();
-    let wide = app.wide;
+    let view = app.command_view;
 
-    let widths = [
-        Constraint::Length(6),
-        Constraint::Length(5),
-        Constraint::Length(7),
-        Constraint::Length(8),
-        Constraint::Length(10),
-        Constraint::Length(8),
-        Constraint::Length(8),
-        Constraint::Fill(1),
-    ];
+    let columns = app.visible_columns();
+    // `--units bytes` or `exact` can outgrow the default MEM width
+    let mem_width = ports
+        .iter()
+        .map(|info| format_bytes(info.memory_bytes).len())
+        .max()
+        .unwrap_or(0)
+        .max(8) as u16;
+    let label_width = config::active()
+        .labels
+        .values()
+        .map(|l| l.chars().count())
+        .max()
+        .unwrap_or(0)
+        .clamp(5, MAX_LABEL_WIDTH) as u16;
+    let widths: Vec<Constraint> = columns
+        .iter()
+        .map(|col| match col {
+            SortColumn::Port => Constraint::Length(6),
+            SortColumn::Proto => Constraint::Length(5),
+            SortColumn::Pid => Constraint::Length(7),
+            SortColumn::User => Constraint::Length(8),
+            SortColumn::Process => Constraint::Length(10),
+            SortColumn::Uptime => Constraint::Length(8),
+            SortColumn::Mem => Constraint::Length(mem_width),
+            SortColumn::Net => Constraint::Length(13),
+            SortColumn::Label => Constraint::Length(label_width),
+            SortColumn::Tcp => Constraint::Length(10),
+            SortColumn::Runtime => Constraint::Length(7),
+            SortColumn::Health => Constraint::Length(10),
+            SortColumn::Cwd => Constraint::Length(CWD_COLUMN_WIDTH as u16),
+            SortColumn::Command => Constraint::Fill(1),
+        })
+        .collect();
 
     // Compute cmd_width by replicating ratatui's Table layout: first split off the
     // highlight-symbol area, then lay out columns with spacing in the remainder.
@@ -589,19 +1853,10 @@ fn render_table(frame: &mut ratatui::Frame, app: &mut App, area: Rect) {
     };
     let [_, columns_area] = Layout::horizontal([Constraint::Length(hl_width), Constraint::Fill(0)])
         .areas(Rect::new(0, 0, area.width, 1));
-    let col_rects = Layout::horizontal(widths).spacing(1).split(columns_area);
-    let cmd_width = (col_rects[7].width as usize).max(10);
-
-    let columns = [
-        SortColumn::Port,
-        SortColumn::Proto,
-        SortColumn::Pid,
-        SortColumn::User,
-        SortColumn::Process,
-        SortColumn::Uptime,
-        SortColumn::Mem,
-        SortColumn::Command,
-    ];
+    let col_rects = Layout::horizontal(widths.iter().copied())
+        .spacing(1)
+        .split(columns_area);
+    let cmd_width = (col_rects[columns.len() - 1].width as usize).max(10);
 
     let header_cells: Vec<Cell> = columns
         .iter()
@@ -622,20 +1877,78 @@ fn render_table(frame: &mut ratatui::Frame, app: &mut App, area: Rect) {
         .collect();
     let header = Row::new(header_cells).height(1);
 
-    let rows: Vec<Row> = ports
+    let needle = (!app.search_text.is_empty()).then(|| app.search_text.to_lowercase());
+    let mark = |text: &str, style: Style| match needle {
+        Some(ref n) if text.to_lowercase().contains(n.as_str()) => {
+            style.patch(app.theme.search_match)
+        }
+        _ => style,
+    };
+
+    let groups = app.reuse_members();
+    // Each row's COMMAND text, and whether it's an expanded group's member
+    let commands: Vec<(String, bool)> = ports
         .iter()
         .map(|info| {
             let mut command_text = info.command.clone();
+            let group = reuseport::key(info).and_then(|k| groups.get(&k).map(|m| (k, m)));
+            let is_member = group
+                .as_ref()
+                .is_some_and(|(_, members)| members.iter().any(|m| std::ptr::eq(*m, *info)));
+            if let (Some((key, members)), false) = (&group, is_member) {
+                // Up front, where the narrow PROCESS column can't cut it off
+                let marker = if app.expanded_groups.contains(key) {
+                    "\u{25be}"
+                } else {
+                    "\u{25b8}"
+                };
+                command_text = format!(
+                    "{}{} {}",
+                    marker,
+                    reuseport::tag(members.len() + 1),
+                    command_text
+                );
+            }
+            if let Some(orphan) = app.orphans.get(&info.pid) {
+                command_text = format!("[{}] {}", orphan.label(), command_text);
+            }
+            let conflict = app
+                .docker_enabled
+                .then(|| docker::port_conflict(info, &app.docker_map))
+                .flatten();
+            if let Some(conflict) = conflict {
+                command_text = format!("[\u{26a0} {}] {}", conflict.badge(), command_text);
+            }
             if app.docker_enabled && info.pid != 0 {
                 if let Some(tag) = app.docker_tag_for_port(info.port) {
                     command_text.push_str(&format!(" [ctr:{}]", tag));
                 }
+            } else if let Some(fwd) = app.forwarded.get(&(info.port, info.pid)) {
+                match &fwd.container {
+                    Some(name) => command_text.push_str(&format!(" [ctr:{}]", name)),
+                    None => command_text.push_str(&format!(" [{}]", fwd.via.label())),
+                }
             }
+            (command_text, is_member)
+        })
+        .collect();
+    // Scrolling stops once the longest command's end is in view
+    let longest = commands
+        .iter()
+        .map(|(text, _)| text.chars().count())
+        .max()
+        .unwrap_or(0);
+    let offset = app.command_offset.min(longest.saturating_sub(cmd_width));
 
-            let cmd_lines = if wide {
-                wrap_cmd(&command_text, cmd_width)
-            } else {
-                vec![truncate_cmd(&command_text, cmd_width)]
+    let rows: Vec<Row> = ports
+        .iter()
+        .zip(&commands)
+        .map(|(info, (command_text, is_member))| {
+            let is_member = *is_member;
+            let cmd_lines = match view {
+                CommandView::Wrap => wrap_cmd(command_text, cmd_width),
+                CommandView::Truncate => vec![truncate_cmd(command_text, cmd_width)],
+                CommandView::Scroll => vec![scroll_cmd(command_text, offset, cmd_width)],
             };
             let row_height = cmd_lines.len().max(1) as u16;
             let cmd_text = Text::from(cmd_lines.into_iter().map(Line::from).collect::<Vec<_>>());
@@ -647,6 +1960,8 @@ fn render_table(frame: &mut ratatui::Frame, app: &mut App, area: Rect) {
                 app.docker_enabled && !is_synthetic && app.docker_map.contains_key(&info.port);
             let process_style = if is_synthetic {
                 docker_blue
+            } else if app.orphans.contains_key(&info.pid) {
+                app.theme.kill_border
             } else if has_docker {
                 app.theme.status_ok.add_modifier(Modifier::BOLD)
             } else {
@@ -654,6 +1969,8 @@ fn render_table(frame: &mut ratatui::Frame, app: &mut App, area: Rect) {
             };
             let process_text = if has_docker {
                 format!("{}*", info.process_name)
+            } else if is_member {
+                format!("\u{2514} {}", info.process_name)
             } else {
                 info.process_name.clone()
             };
@@ -663,19 +1980,76 @@ fn render_table(frame: &mut ratatui::Frame, app: &mut App, area: Rect) {
                 info.pid.to_string()
             };
 
-            Row::new(vec![
-                Cell::from(info.port.to_string()).style(app.styles.port),
-                Cell::from(info.protocol.clone()).style(app.styles.proto),
-                Cell::from(pid_str).style(app.styles.pid),
-                Cell::from(info.user.clone()).style(app.styles.user),
-                Cell::from(process_text).style(process_style),
+            let port_str = info.port.to_string();
+            let mut cells = vec![
+                Cell::from(port_str.clone()).style(mark(&port_str, app.styles.port)),
+                Cell::from(info.protocol.clone()).style(mark(&info.protocol, app.styles.proto)),
+                Cell::from(pid_str.clone()).style(mark(&pid_str, app.styles.pid)),
+                Cell::from(info.user.clone()).style(mark(&info.user, app.styles.user)),
+                Cell::from(process_text).style(mark(&info.process_name, process_style)),
                 Cell::from(Line::from(format_uptime(info.start_time)).alignment(Alignment::Right))
                     .style(app.styles.uptime),
                 Cell::from(Line::from(format_bytes(info.memory_bytes)).alignment(Alignment::Right))
                     .style(app.styles.mem),
-                Cell::from(cmd_text).style(app.styles.command),
-            ])
-            .height(row_height)
+                Cell::from(
+                    Line::from(format_rate_pair(app.rate_for_pid(info.pid)))
+                        .alignment(Alignment::Right),
+                )
+                .style(app.styles.mem),
+            ];
+            if columns.contains(&SortColumn::Label) {
+                let label = config::active().label(info.port).unwrap_or("-");
+                cells.push(Cell::from(label.to_string()).style(mark(label, app.styles.process)));
+            }
+            if columns.contains(&SortColumn::Tcp) {
+                let text = app
+                    .tcp_stats
+                    .get(&info.port)
+                    .filter(|_| info.state == TcpState::Listen)
+                    .map_or("-".to_string(), PortStats::cell);
+                let style = if text.ends_with("/0") || text == "-" {
+                    app.theme.footer_text
+                } else {
+                    app.styles.mem
+                };
+                cells.push(Cell::from(Line::from(text).alignment(Alignment::Right)).style(style));
+            }
+            if app.show_runtime {
+                let detected = runtime::detect(info);
+                let tag = detected.map_or("-", Runtime::as_str);
+                let style =
+                    detected.map_or(app.theme.footer_text, |rt| app.styles.runtime_style(rt));
+                cells.push(Cell::from(tag).style(mark(tag, style)));
+            }
+            if columns.contains(&SortColumn::Health) {
+                let state = docker::port_state(&app.docker_map, info.port);
+                let text = state.map_or("-", ContainerState::as_str);
+                let style = state.map_or(app.theme.footer_text, |s| {
+                    container_state_style(&app.theme, s)
+                });
+                cells.push(Cell::from(text).style(mark(text, style)));
+            }
+            if app.show_cwd {
+                let full_cwd = app.cwds.get(&info.pid).map(String::as_str).unwrap_or("");
+                cells.push(
+                    Cell::from(cwd_cell(info.pid, &app.cwds, CWD_COLUMN_WIDTH))
+                        .style(mark(full_cwd, app.theme.footer_text)),
+                );
+            }
+            cells.push(Cell::from(cmd_text).style(mark(command_text, app.styles.command)));
+            if app.visibility != Visibility::Complete && partially_read(info) {
+                // Grayed out: blanks here mean "not allowed to see", not zero
+                cells = cells
+                    .into_iter()
+                    .map(|c| c.style(app.theme.footer_text))
+                    .collect();
+            }
+            let row = Row::new(cells).height(row_height);
+            if app.alerts.is_alerting(info.pid) {
+                row.style(app.theme.alert)
+            } else {
+                row
+            }
         })
         .collect();
 
@@ -685,22 +2059,137 @@ fn render_table(frame: &mut ratatui::Frame, app: &mut App, area: Rect) {
         .highlight_symbol(app.theme.highlight_symbol);
 
     frame.render_stateful_widget(table, area, &mut app.table_state);
+    app.command_offset = offset;
 }
 
-fn render_detail(frame: &mut ratatui::Frame, app: &App, area: Rect) {
-    let ports = app.sorted_ports();
-    let info = match ports.get(app.detail_index) {
-        Some(i) => i,
-        None => {
-            let p =
-                Paragraph::new("Port no longer available.").style(Style::default().fg(Color::Red));
-            frame.render_widget(p, area);
-            return;
-        }
+/// Peer hosts listed in the detail view before collapsing into "… N more".
+const MAX_DETAIL_PEERS: usize = 8;
+
+/// Four panels of bars: two by two when there's room, stacked otherwise.
+fn render_summary(frame: &mut ratatui::Frame, app: &App, area: Rect) {
+    let summary = summary::summarize(app.filtered_ports());
+    let counts = |rows: &[(String, usize)]| -> Vec<(String, String, u64)> {
+        rows.iter()
+            .map(|(name, n)| (name.clone(), n.to_string(), *n as u64))
+            .collect()
+    };
+    let panels = [
+        (" By user ".to_string(), counts(&summary.by_user)),
+        (" By process ".to_string(), counts(&summary.by_process)),
+        (" By state ".to_string(), counts(&summary.by_state)),
+        (
+            format!(" Memory {} ", format_bytes(summary.total_memory).trim()),
+            summary
+                .memory
+                .iter()
+                .map(|(name, bytes)| (name.clone(), format_bytes(*bytes), *bytes))
+                .collect(),
+        ),
+    ];
+
+    let areas: Vec<Rect> = if area.width >= 60 {
+        let [top, bottom] =
+            Layout::vertical([Constraint::Fill(1), Constraint::Fill(1)]).areas(area);
+        [top, bottom]
+            .into_iter()
+            .flat_map(|row| {
+                let [left, right] =
+                    Layout::horizontal([Constraint::Fill(1), Constraint::Fill(1)]).areas(row);
+                [left, right]
+            })
+            .collect()
+    } else {
+        Layout::vertical([Constraint::Fill(1); 4])
+            .split(area)
+            .to_vec()
+    };
+    for ((title, rows), panel) in panels.into_iter().zip(areas) {
+        let block = Block::default()
+            .borders(Borders::ALL)
+            .border_type(BorderType::Rounded)
+            .border_style(app.theme.border)
+            .title(title)
+            .title_style(app.theme.title);
+        let inner = block.inner(panel);
+        let lines = summary_lines(app, &summary, &rows, inner);
+        frame.render_widget(Paragraph::new(lines).block(block), panel);
+    }
+}
+
+/// `name  value  ████` per row, bars scaled to the largest, with a last
+/// line saying how many didn't fit.
+fn summary_lines(
+    app: &App,
+    summary: &Summary,
+    rows: &[(String, String, u64)],
+    area: Rect,
+) -> Vec<Line<'static>> {
+    if rows.is_empty() {
+        let text = if summary.sockets == 0 {
+            " No ports"
+        } else {
+            " Not available"
+        };
+        return vec![Line::from(Span::styled(text, app.theme.footer_text))];
+    }
+    let height = area.height as usize;
+    let shown = if rows.len() > height {
+        height.saturating_sub(1)
+    } else {
+        rows.len()
+    };
+    let name_w = rows[..shown]
+        .iter()
+        .map(|(name, ..)| name.chars().count())
+        .max()
+        .unwrap_or(0)
+        .min(area.width as usize / 3);
+    let value_w = rows[..shown]
+        .iter()
+        .map(|(_, value, _)| value.chars().count())
+        .max()
+        .unwrap_or(0);
+    let bar_w = (area.width as usize).saturating_sub(name_w + value_w + 4);
+    let max = rows.iter().map(|(.., n)| *n).max().unwrap_or(0).max(1);
+
+    let mut lines: Vec<Line> = rows[..shown]
+        .iter()
+        .map(|(name, value, n)| {
+            let filled = ((*n as f64 / max as f64) * bar_w as f64).round() as usize;
+            Line::from(vec![
+                Span::raw(format!(
+                    " {:<nw$} ",
+                    truncate_cmd(name, name_w),
+                    nw = name_w
+                )),
+                Span::styled(
+                    format!("{:>vw$} ", value, vw = value_w),
+                    app.theme.footer_key,
+                ),
+                Span::styled("\u{2588}".repeat(filled.max(1)), app.theme.status_ok),
+            ])
+        })
+        .collect();
+    if shown < rows.len() {
+        lines.push(Line::from(Span::styled(
+            format!(" +{} more", rows.len() - shown),
+            app.theme.footer_text,
+        )));
+    }
+    lines
+}
+
+/// The detail view, full screen or, with `pane`, beside the table. The
+/// pane has no key line of its own: keys go to the table.
+fn render_detail(frame: &mut ratatui::Frame, app: &mut App, area: Rect, pane: bool) {
+    let Some(info) = app.detail_info.clone() else {
+        let p = Paragraph::new("Port no longer available.").style(Style::default().fg(Color::Red));
+        frame.render_widget(p, area);
+        return;
     };
+    let info = &info;
 
     let bind_str = format!("{}:{}", format_addr(&info.local_addr), info.port);
-    let uptime = format_uptime(info.start_time);
     let is_docker = info.pid == 0;
     let docker_blue = Style::default().fg(Color::Rgb(110, 190, 220));
 
@@ -722,8 +2211,18 @@ fn render_detail(frame: &mut ratatui::Frame, app: &App, area: Rect) {
     let title_line = Line::from(title_spans);
 
     let label_style = app.theme.footer_text;
+    let banner = app.vanished.map(|vanished| {
+        let text = match vanished {
+            Vanished::Exited => format!("  Process exited (PID {})", info.pid),
+            Vanished::Closed => format!("  Port {} is no longer open", info.port),
+        };
+        Line::from(vec![
+            Span::styled(text, app.theme.status_down),
+            Span::styled(" \u{2014} showing its last known state", label_style),
+        ])
+    });
 
-    let rows: Vec<(&str, String)> = if is_docker {
+    let mut rows: Vec<(&str, String)> = if is_docker {
         vec![
             ("Bind:", bind_str),
             ("Image:", info.command.clone()),
@@ -734,15 +2233,54 @@ fn render_detail(frame: &mut ratatui::Frame, app: &App, area: Rect) {
             ("Bind:", bind_str),
             ("Command:", info.command.clone()),
             ("User:", info.user.clone()),
-            ("Started:", format!("{} ago", uptime)),
+            ("Started:", format_started(info.start_time)),
             ("Memory:", format_bytes(info.memory_bytes)),
             ("CPU time:", format!("{:.1}s", info.cpu_seconds)),
+            ("Traffic:", format_rate_pair(app.rate_for_pid(info.pid))),
             ("Children:", info.children.to_string()),
             ("State:", info.state.to_string()),
         ]
     };
+    if let Some(rt) = runtime::detect(info).filter(|_| !is_docker) {
+        rows.insert(2, ("Runtime:", rt.as_str().to_string()));
+    }
+    if let Some(label) = config::active().label(info.port) {
+        rows.insert(0, ("Label:", label.to_string()));
+    }
+    if let Some(ref backlog) = app.backlog {
+        if !is_docker {
+            rows.push(("Backlog:", backlog.clone()));
+        }
+    }
+    if let Some(ref ephemeral) = app.ephemeral {
+        if !is_docker {
+            rows.push(("Ephemeral:", ephemeral.clone()));
+        }
+    }
+    if let Some(options) = app.socket_options.filter(|_| !is_docker) {
+        rows.extend(options.rows());
+    }
+    if let Some((_, udp_rows)) = app.udp_rows.as_ref().filter(|_| !is_docker) {
+        rows.extend(udp_rows.iter().cloned());
+    }
+    rows.extend(resolver::detail_rows(info));
+    if app.docker_enabled {
+        rows.extend(docker::conflict_rows(info, &app.docker_map));
+    }
+    if let Some(stats) = app.tcp_stats.get(&info.port) {
+        if info.state == TcpState::Listen && !is_docker {
+            rows.push(("TCP:", stats.describe()));
+        }
+    }
+    if let Some(ref detail) = app.detail {
+        if !is_docker {
+            rows.extend(detail_rows(detail));
+        }
+    }
 
-    let mut lines = vec![Line::default(), title_line, Line::default()];
+    let mut lines = vec![Line::default(), title_line];
+    lines.extend(banner);
+    lines.push(Line::default());
     for (label, value) in &rows {
         lines.push(Line::from(vec![
             Span::raw("  "),
@@ -751,6 +2289,63 @@ fn render_detail(frame: &mut ratatui::Frame, app: &App, area: Rect) {
         ]));
     }
 
+    if !is_docker {
+        let total: usize = app.peers.iter().map(|p| p.count).sum();
+        let summary = if app.peers.is_empty() {
+            "none".to_string()
+        } else {
+            format!(
+                "{} host{}, {} connection{}",
+                app.peers.len(),
+                if app.peers.len() == 1 { "" } else { "s" },
+                total,
+                if total == 1 { "" } else { "s" }
+            )
+        };
+        lines.push(Line::from(vec![
+            Span::raw("  "),
+            Span::styled(format!("{:<10}", "Peers:"), label_style),
+            Span::raw(summary),
+        ]));
+        for peer in app.peers.iter().take(MAX_DETAIL_PEERS) {
+            lines.push(Line::from(vec![Span::raw(format!(
+                "    {}",
+                format_peer(peer)
+            ))]));
+        }
+        if app.peers.len() > MAX_DETAIL_PEERS {
+            lines.push(Line::from(vec![Span::styled(
+                format!("    … {} more", app.peers.len() - MAX_DETAIL_PEERS),
+                label_style,
+            )]));
+        }
+    }
+
+    let forwarded = (!app.docker_enabled)
+        .then(|| app.forwarded.get(&(info.port, info.pid)))
+        .flatten();
+    if let Some(fwd) = forwarded {
+        let mut spans = vec![
+            Span::raw("  "),
+            Span::styled(format!("{:<10}", "Docker:"), label_style),
+        ];
+        if let Some(name) = &fwd.container {
+            spans.push(Span::styled(name.clone(), app.theme.status_ok));
+            spans.push(Span::raw(format!(" via {}", fwd.via.label())));
+        } else {
+            spans.push(Span::raw(format!(
+                "{} forwards this port to a container",
+                fwd.via.label()
+            )));
+        }
+        spans.push(Span::styled(
+            " (start with --docker for state and actions)",
+            app.theme.footer_text,
+        ));
+        lines.push(Line::default());
+        lines.push(Line::from(spans));
+    }
+
     if app.docker_enabled {
         lines.push(Line::default());
         let owners = app.docker_owners_for_port(info.port).unwrap_or(&[]);
@@ -779,8 +2374,26 @@ fn render_detail(frame: &mut ratatui::Frame, app: &App, area: Rect) {
                         owner.container_port,
                         owner.protocol
                     )),
+                    Span::raw(" "),
+                    Span::styled(
+                        owner.container_state().as_str(),
+                        container_state_style(&app.theme, owner.container_state()),
+                    ),
+                    Span::styled(
+                        container_usage(owner)
+                            .map(|u| format!(" {}", u))
+                            .unwrap_or_default(),
+                        app.theme.footer_text,
+                    ),
                 ]));
                 if seen.insert(owner.container_name.clone()) {
+                    let networks = app.container_networks.get(&owner.container_id);
+                    for network in networks.into_iter().flatten() {
+                        lines.push(Line::from(vec![
+                            Span::styled("      network: ", label_style),
+                            Span::raw(network.describe(owner.container_port)),
+                        ]));
+                    }
                     lines.push(Line::from(vec![Span::raw(format!(
                         "      docker logs --tail 100 {}",
                         owner.container_name
@@ -794,31 +2407,53 @@ fn render_detail(frame: &mut ratatui::Frame, app: &App, area: Rect) {
         }
     }
 
-    lines.push(Line::default());
-    if is_docker {
-        lines.push(Line::from(vec![
-            Span::styled("  Esc", app.theme.footer_key),
-            Span::styled(" back  ", app.theme.footer_text),
-            Span::styled("d", app.theme.footer_key),
-            Span::styled(" stop/restart/logs  ", app.theme.footer_text),
-            Span::styled("q", app.theme.footer_key),
-            Span::styled(" quit", app.theme.footer_text),
-        ]));
-    } else {
-        lines.push(Line::from(vec![
-            Span::styled("  Esc", app.theme.footer_key),
-            Span::styled(" back  ", app.theme.footer_text),
-            Span::styled("d", app.theme.footer_key),
-            Span::styled(" kill  ", app.theme.footer_text),
-            Span::styled("D", app.theme.footer_key),
-            Span::styled(" force kill  ", app.theme.footer_text),
-            Span::styled("q", app.theme.footer_key),
-            Span::styled(" quit", app.theme.footer_text),
-        ]));
+    if pane {
+        frame.render_widget(Paragraph::new(lines), area);
+        return;
+    }
+
+    let scroll_keys = match app.keymap.hint(&[Action::Down, Action::Up]) {
+        k if k.is_empty() => "PgUp/PgDn".to_string(),
+        k => format!("{}/PgUp/PgDn", k),
+    };
+    let mut keys = vec![
+        Span::styled("  Esc", app.theme.footer_key),
+        Span::styled(" back  ", app.theme.footer_text),
+        Span::styled(scroll_keys, app.theme.footer_key),
+        Span::styled(" scroll  ", app.theme.footer_text),
+    ];
+    let mut hint = |action: Action, label: &'static str| {
+        let key = app.keymap.hint(&[action]);
+        if !key.is_empty() {
+            keys.push(Span::styled(key, app.theme.footer_key));
+            keys.push(Span::styled(label, app.theme.footer_text));
+        }
+    };
+    if app.vanished.is_none() && is_docker {
+        hint(Action::Kill, " stop/restart/logs  ");
+    } else if app.vanished.is_none() {
+        hint(Action::Kill, " kill  ");
+        hint(Action::ForceKill, " force kill  ");
+        hint(Action::Signal, " signal  ");
+    }
+    hint(Action::Quit, " quit");
+
+    // The key line stays put while the rest scrolls
+    let [body, key_area] =
+        Layout::vertical([Constraint::Fill(1), Constraint::Length(1)]).areas(area);
+    let bottom = (lines.len() as u16).saturating_sub(body.height);
+    let scroll = app.detail_scroll.min(bottom);
+    if bottom > 0 {
+        keys.push(Span::styled(
+            format!("  ({}/{})", scroll + body.height, lines.len()),
+            app.theme.footer_text,
+        ));
     }
 
-    let paragraph = Paragraph::new(lines);
-    frame.render_widget(paragraph, area);
+    frame.render_widget(Paragraph::new(lines).scroll((scroll, 0)), body);
+    frame.render_widget(Paragraph::new(Line::from(keys)), key_area);
+    app.detail_scroll = scroll;
+    app.detail_page = body.height;
 }
 
 fn render_kill_popup(frame: &mut ratatui::Frame, app: &App, area: Rect) {
@@ -827,29 +2462,59 @@ fn render_kill_popup(frame: &mut ratatui::Frame, app: &App, area: Rect) {
         _ => return,
     };
 
-    let signal = if popup.force { "SIGKILL" } else { "SIGTERM" };
+    let signal = popup.signal.name();
 
-    let text = vec![
+    let mut text = vec![
         Line::default(),
         Line::from(vec![
             Span::raw("  Kill "),
             Span::styled(&popup.process_name, app.theme.status_ok),
             Span::raw(format!(" (PID {}) on port {}?", popup.pid, popup.port)),
         ]),
+        Line::from(Span::styled(
+            format!("  {}", popup.summary),
+            app.theme.footer_text,
+        )),
         Line::from(vec![Span::raw(format!("  Signal: {}", signal))]),
-        Line::default(),
-        Line::from(vec![
+    ];
+    if let Some(warning) = orphan_warning(popup.children) {
+        text.push(Line::from(Span::styled(
+            format!("  ! {}", warning),
+            Style::default().fg(Color::Rgb(220, 180, 80)),
+        )));
+    }
+    text.push(Line::default());
+    if let (Some(typed), Some(type_back)) = (&popup.typed, popup.type_back) {
+        if type_back == TypeBack::PortOrPid {
+            text.push(Line::from(Span::styled(
+                "  ! Owned by root or another user",
+                Style::default().fg(Color::Rgb(220, 180, 80)),
+            )));
+        }
+        text.push(Line::from(vec![
+            Span::raw(format!("  Type {} to confirm: ", type_back.what())),
+            Span::raw(typed.as_str()),
+            Span::styled("\u{2588}", app.theme.filter_accent),
+        ]));
+        text.push(Line::from(vec![
+            Span::raw("  "),
+            Span::styled("Enter", app.theme.footer_key),
+            Span::styled(" confirm   ", app.theme.footer_text),
+            Span::styled("Esc", app.theme.footer_key),
+            Span::styled(" cancel", app.theme.footer_text),
+        ]));
+    } else {
+        text.push(Line::from(vec![
             Span::raw("  "),
             Span::styled("y/Enter", app.theme.footer_key),
             Span::styled(" confirm   ", app.theme.footer_text),
             Span::styled("n/Esc", app.theme.footer_key),
             Span::styled(" cancel", app.theme.footer_text),
-        ]),
-        Line::default(),
-    ];
+        ]));
+    }
 
-    let popup_width = 50u16.min(area.width.saturating_sub(4));
-    let popup_height = 6u16.min(area.height.saturating_sub(4));
+    let popup_width = 54u16.min(area.width.saturating_sub(4));
+    let popup_height = (text.len() as u16 + 2).min(area.height.saturating_sub(4));
     let x = (area.width.saturating_sub(popup_width)) / 2;
     let y = (area.height.saturating_sub(popup_height)) / 2;
     let popup_area = Rect::new(x, y, popup_width, popup_height);
@@ -867,52 +2532,49 @@ fn render_kill_popup(frame: &mut ratatui::Frame, app: &App, area: Rect) {
     frame.render_widget(paragraph, popup_area);
 }
 
-fn render_docker_popup(frame: &mut ratatui::Frame, app: &App, area: Rect) {
+fn render_signal_popup(frame: &mut ratatui::Frame, app: &App, area: Rect) {
     let popup = match &app.popup {
-        Some(Popup::Docker(p)) => p,
+        Some(Popup::Signal(p)) => p,
         _ => return,
     };
 
-    let actions = ["Stop", "Restart", "Logs"];
-    let docker_blue = Style::default().fg(Color::Rgb(110, 190, 220));
-
-    let mut lines = vec![
+    let mut text = vec![
         Line::default(),
         Line::from(vec![
-            Span::raw("  Container: "),
-            Span::styled(&popup.container_name, app.theme.status_ok),
-            Span::raw(format!(" (port {})", popup.port)),
+            Span::raw("  Signal "),
+            Span::styled(&popup.info.process_name, app.theme.status_ok),
+            Span::raw(format!(
+                " (PID {}) on port {}",
+                popup.info.pid, popup.info.port
+            )),
         ]),
         Line::default(),
     ];
-
-    for (i, action) in actions.iter().enumerate() {
-        let marker = if i == popup.selected { "> " } else { "  " };
-        let style = if i == popup.selected {
-            docker_blue.add_modifier(Modifier::BOLD)
+    for (i, signal) in signals::NAMED.iter().enumerate() {
+        let (marker, style) = if i == popup.selected {
+            ("> ", app.theme.kill_border.add_modifier(Modifier::BOLD))
         } else {
-            app.theme.footer_text
+            ("  ", app.theme.footer_text)
         };
-        lines.push(Line::from(vec![
+        text.push(Line::from(vec![
             Span::raw("  "),
-            Span::styled(format!("{}{}", marker, action), style),
+            Span::styled(format!("{}{:<9}", marker, signal.name()), style),
+            Span::styled(signal.describe(), app.theme.footer_text),
         ]));
     }
-
-    lines.push(Line::default());
-    lines.push(Line::from(vec![
+    text.push(Line::default());
+    text.push(Line::from(vec![
         Span::raw("  "),
         Span::styled("j/k", app.theme.footer_key),
         Span::styled(" navigate  ", app.theme.footer_text),
         Span::styled("Enter", app.theme.footer_key),
-        Span::styled(" confirm  ", app.theme.footer_text),
+        Span::styled(" send  ", app.theme.footer_text),
         Span::styled("Esc", app.theme.footer_key),
         Span::styled(" cancel", app.theme.footer_text),
     ]));
-    lines.push(Line::default());
 
-    let popup_width = 50u16.min(area.width.saturating_sub(4));
-    let popup_height = 9u16.min(area.height.saturating_sub(4));
+    let popup_width = 60u16.min(area.width.saturating_sub(4));
+    let popup_height = (text.len() as u16 + 2).min(area.height.saturating_sub(2));
     let x = (area.width.saturating_sub(popup_width)) / 2;
     let y = (area.height.saturating_sub(popup_height)) / 2;
     let popup_area = Rect::new(x, y, popup_width, popup_height);
@@ -920,168 +2582,378 @@ fn render_docker_popup(frame: &mut ratatui::Frame, app: &App, area: Rect) {
     let block = Block::default()
         .borders(Borders::ALL)
         .border_type(BorderType::Rounded)
-        .border_style(docker_blue)
-        .title(" Docker Container ")
+        .border_style(app.theme.kill_border)
+        .title(" Send Signal ")
         .title_alignment(Alignment::Center)
-        .title_style(docker_blue.add_modifier(Modifier::BOLD));
+        .title_style(app.theme.kill_border.add_modifier(Modifier::BOLD));
 
     frame.render_widget(Clear, popup_area);
-    let paragraph = Paragraph::new(lines).block(block);
-    frame.render_widget(paragraph, popup_area);
+    frame.render_widget(Paragraph::new(text).block(block), popup_area);
 }
 
-// ── Event handling ───────────────────────────────────────────────────
-
-fn handle_key(app: &mut App, code: KeyCode, modifiers: KeyModifiers) {
-    // Global: Ctrl+C always quits
-    if modifiers.contains(KeyModifiers::CONTROL) && code == KeyCode::Char('c') {
-        app.should_quit = true;
-        return;
+fn render_help_popup(frame: &mut ratatui::Frame, app: &App, area: Rect) {
+    let mut rows = app.keymap.help_rows();
+    rows.push(("1-9".to_string(), "Sort by column N"));
+    rows.push(("Ctrl+C".to_string(), "Quit"));
+    let key_width = rows
+        .iter()
+        .map(|(k, _)| k.chars().count())
+        .max()
+        .unwrap_or(0);
+    let mut text = vec![Line::default()];
+    for (keys, help) in rows {
+        text.push(Line::from(vec![
+            Span::styled(
+                format!("  {:<w$}  ", keys, w = key_width),
+                app.theme.footer_key,
+            ),
+            Span::styled(help, app.theme.footer_text),
+        ]));
     }
+    text.push(Line::default());
+    text.push(Line::from(Span::styled(
+        "  Remap under [keys] in the config file. Any key closes.",
+        app.theme.footer_text,
+    )));
+
+    let popup_width = 64u16.min(area.width.saturating_sub(4));
+    let popup_height = (text.len() as u16 + 2).min(area.height.saturating_sub(2));
+    let x = (area.width.saturating_sub(popup_width)) / 2;
+    let y = (area.height.saturating_sub(popup_height)) / 2;
+    let popup_area = Rect::new(x, y, popup_width, popup_height);
 
-    // Popup takes priority
-    match &app.popup {
-        Some(Popup::Kill(_)) => {
-            handle_kill_popup_key(app, code);
-            return;
-        }
-        Some(Popup::Docker(_)) => {
-            handle_docker_popup_key(app, code);
+    let block = Block::default()
+        .borders(Borders::ALL)
+        .border_type(BorderType::Rounded)
+        .border_style(app.theme.border)
+        .title(" Keys ")
+        .title_alignment(Alignment::Center)
+        .title_style(app.theme.title);
+
+    frame.render_widget(Clear, popup_area);
+    frame.render_widget(Paragraph::new(text).block(block), popup_area);
+}
+
+fn render_docker_popup(frame: &mut ratatui::Frame, app: &App, area: Rect) {
+    let popup = match &app.popup {
+        Some(Popup::Docker(p)) => p,
+        _ => return,
+    };
+
+    let docker_blue = Style::default().fg(Color::Rgb(110, 190, 220));
+
+    let mut lines = vec![
+        Line::default(),
+        Line::from(vec![
+            Span::raw("  Container: "),
+            Span::styled(&popup.container_name, app.theme.status_ok),
+            Span::raw(format!(" (port {})", popup.port)),
+        ]),
+        Line::default(),
+    ];
+
+    let actions = popup.actions();
+    for (i, action) in actions.iter().enumerate() {
+        let marker = if i == popup.selected { "> " } else { "  " };
+        let style = if i == popup.selected {
+            docker_blue.add_modifier(Modifier::BOLD)
+        } else {
+            app.theme.footer_text
+        };
+        lines.push(Line::from(vec![
+            Span::raw("  "),
+            Span::styled(format!("{}{}", marker, action), style),
+        ]));
+    }
+
+    lines.push(Line::default());
+    lines.push(Line::from(vec![
+        Span::raw("  "),
+        Span::styled("j/k", app.theme.footer_key),
+        Span::styled(" navigate  ", app.theme.footer_text),
+        Span::styled("Enter", app.theme.footer_key),
+        Span::styled(" confirm  ", app.theme.footer_text),
+        Span::styled("Esc", app.theme.footer_key),
+        Span::styled(" cancel", app.theme.footer_text),
+    ]));
+    lines.push(Line::default());
+
+    let popup_width = 50u16.min(area.width.saturating_sub(4));
+    let popup_height = (lines.len() as u16 + 2).min(area.height.saturating_sub(4));
+    let x = (area.width.saturating_sub(popup_width)) / 2;
+    let y = (area.height.saturating_sub(popup_height)) / 2;
+    let popup_area = Rect::new(x, y, popup_width, popup_height);
+
+    let block = Block::default()
+        .borders(Borders::ALL)
+        .border_type(BorderType::Rounded)
+        .border_style(docker_blue)
+        .title(" Docker Container ")
+        .title_alignment(Alignment::Center)
+        .title_style(docker_blue.add_modifier(Modifier::BOLD));
+
+    frame.render_widget(Clear, popup_area);
+    let paragraph = Paragraph::new(lines).block(block);
+    frame.render_widget(paragraph, popup_area);
+}
+
+// ── Event handling ───────────────────────────────────────────────────
+
+/// One terminal event. Split from the main loop so tests can feed a
+/// scripted sequence through the same path.
+fn handle_event(app: &mut App, event: Event) {
+    if let Event::Key(key) = event {
+        // Only handle Press events (not Release/Repeat)
+        if key.kind == KeyEventKind::Press {
+            handle_key(app, key.code, key.modifiers);
+        }
+    }
+}
+
+fn handle_key(app: &mut App, code: KeyCode, modifiers: KeyModifiers) {
+    // Global: Ctrl+C always quits
+    if modifiers.contains(KeyModifiers::CONTROL) && code == KeyCode::Char('c') {
+        app.should_quit = true;
+        return;
+    }
+
+    // Popup takes priority
+    match &app.popup {
+        Some(Popup::Kill(_)) => {
+            handle_kill_popup_key(app, code);
+            return;
+        }
+        Some(Popup::Docker(_)) => {
+            handle_docker_popup_key(app, code);
+            return;
+        }
+        Some(Popup::Signal(_)) => {
+            handle_signal_popup_key(app, code);
+            return;
+        }
+        Some(Popup::Help) => {
+            app.popup = None;
             return;
         }
         None => {}
     }
 
+    if app.yank_pending {
+        handle_yank_key(app, code);
+        return;
+    }
+
+    let typing = matches!(
+        app.mode,
+        AppMode::FilterInput | AppMode::SearchInput | AppMode::CommandInput
+    );
+    if app.replay.is_some() && !typing && handle_replay_key(app, code) {
+        return;
+    }
+
     match app.mode {
         AppMode::Table => handle_table_key(app, code),
         AppMode::Detail => handle_detail_key(app, code),
         AppMode::FilterInput => handle_filter_key(app, code),
+        AppMode::SearchInput => handle_search_key(app, code),
+        AppMode::CommandInput => handle_command_key(app, code),
+        AppMode::Summary => handle_summary_key(app, code),
     }
 }
 
-fn handle_table_key(app: &mut App, code: KeyCode) {
+/// Playback keys, and the actions a recording can't support. Returns
+/// whether the key was consumed.
+fn handle_replay_key(app: &mut App, code: KeyCode) -> bool {
+    let Some(ref mut replay) = app.replay else {
+        return false;
+    };
     match code {
-        KeyCode::Char('q') | KeyCode::Esc => app.should_quit = true,
-        KeyCode::Char('j') | KeyCode::Down => app.select_next(),
-        KeyCode::Char('k') | KeyCode::Up => app.select_prev(),
-        KeyCode::Char('g') | KeyCode::Home => app.select_first(),
-        KeyCode::Char('G') | KeyCode::End => app.select_last(),
-        KeyCode::Enter => {
-            if let Some(idx) = app.table_state.selected() {
-                app.detail_index = idx;
-                app.mode = AppMode::Detail;
+        KeyCode::Char(' ') => replay.toggle_pause(),
+        KeyCode::Right | KeyCode::Char('.') => replay.step(true),
+        KeyCode::Left | KeyCode::Char(',') => replay.step(false),
+        KeyCode::Char('d') | KeyCode::Char('D') => {
+            app.status_message = Some((
+                "Replay is read-only: nothing to kill".to_string(),
+                Instant::now(),
+            ));
+            return true;
+        }
+        _ => return false,
+    }
+    app.refresh_data();
+    true
+}
+
+fn handle_table_key(app: &mut App, code: KeyCode) {
+    // Esc clears an active search before it quits
+    if code == KeyCode::Esc && !app.search_text.is_empty() {
+        app.search_text.clear();
+        return;
+    }
+    if let KeyCode::Char(c @ '1'..='9') = code {
+        let idx = (c as usize) - ('1' as usize);
+        if let Some(&col) = app.visible_columns().get(idx) {
+            if app.sort_column == col {
+                app.sort_direction = app.sort_direction.toggle();
+            } else {
+                app.sort_column = col;
+                app.sort_direction = SortDirection::Asc;
             }
         }
-        KeyCode::Char('d') => {
+        return;
+    }
+    let Some(action) = app.keymap.action(code) else {
+        return;
+    };
+    match action {
+        Action::Quit => app.should_quit = true,
+        Action::Down => app.select_next(),
+        Action::Up => app.select_prev(),
+        Action::Top => app.select_first(),
+        Action::ToggleGroup => app.toggle_reuse_group(),
+        Action::Bottom => app.select_last(),
+        Action::Inspect => {
             if let Some(info) = app.selected_port().cloned() {
-                if info.pid == 0 {
-                    app.popup = Some(Popup::Docker(DockerPopup {
-                        container_name: info.process_name.clone(),
-                        port: info.port,
-                        selected: 0,
-                    }));
-                } else {
-                    app.popup = Some(Popup::Kill(KillPopup {
-                        pid: info.pid,
-                        process_name: info.process_name.clone(),
-                        port: info.port,
-                        force: app.default_force,
-                    }));
-                }
+                app.open_detail(info);
             }
         }
-        KeyCode::Char('D') => {
+        Action::Kill | Action::ForceKill => {
             if let Some(info) = app.selected_port().cloned() {
-                if info.pid == 0 {
-                    app.popup = Some(Popup::Docker(DockerPopup {
-                        container_name: info.process_name.clone(),
-                        port: info.port,
-                        selected: 0,
-                    }));
-                } else {
-                    app.popup = Some(Popup::Kill(KillPopup {
-                        pid: info.pid,
-                        process_name: info.process_name.clone(),
-                        port: info.port,
-                        force: true,
-                    }));
-                }
+                app.start_kill(
+                    &info,
+                    Signal::for_force(action == Action::ForceKill || app.default_force),
+                );
+            }
+        }
+        Action::Signal => {
+            if let Some(info) = app.selected_port().cloned() {
+                app.start_signal(&info);
             }
         }
-        KeyCode::Char('/') => {
+        Action::Filter => {
             app.mode = AppMode::FilterInput;
             app.filter_text.clear();
         }
-        KeyCode::Char('a') => {
+        Action::Search => {
+            app.mode = AppMode::SearchInput;
+            app.search_text.clear();
+        }
+        Action::NextMatch => app.jump_to_match(true),
+        Action::PrevMatch => app.jump_to_match(false),
+        Action::Copy => app.yank_pending = true,
+        Action::Export => {
+            app.mode = AppMode::CommandInput;
+            app.command_text = format!("export {}", export::default_path());
+        }
+        Action::Command => {
+            app.mode = AppMode::CommandInput;
+            app.command_text.clear();
+        }
+        Action::ToggleAll => {
             app.show_all = !app.show_all;
             app.refresh_data();
         }
-        KeyCode::Char('<') => {
-            app.sort_column = app.sort_column.prev();
+        Action::SortPrev => {
+            app.cycle_sort_column(false);
         }
-        KeyCode::Char('>') => {
-            app.sort_column = app.sort_column.next();
+        Action::SortNext => {
+            app.cycle_sort_column(true);
         }
-        KeyCode::Char('r') => {
-            app.sort_direction = app.sort_direction.toggle();
+        Action::ToggleCwd => {
+            app.toggle_cwd();
         }
-        KeyCode::Char(c @ '1'..='8') => {
-            let idx = (c as usize) - ('1' as usize);
-            if let Some(col) = SortColumn::from_index(idx) {
-                if app.sort_column == col {
-                    app.sort_direction = app.sort_direction.toggle();
-                } else {
-                    app.sort_column = col;
-                    app.sort_direction = SortDirection::Asc;
-                }
-            }
+        Action::ToggleRuntime => {
+            app.toggle_runtime();
         }
-        _ => {}
+        Action::ToggleDocker => app.toggle_docker(),
+        Action::Summary => app.mode = AppMode::Summary,
+        Action::SplitView => app.toggle_split(),
+        Action::ReverseSort => {
+            app.sort_direction = app.sort_direction.toggle();
+        }
+        Action::CommandView => app.cycle_command_view(),
+        Action::ScrollLeft => app.scroll_command(false),
+        Action::ScrollRight => app.scroll_command(true),
+        Action::Help => app.popup = Some(Popup::Help),
     }
 }
 
 fn handle_detail_key(app: &mut App, code: KeyCode) {
     match code {
-        KeyCode::Esc => app.mode = AppMode::Table,
-        KeyCode::Char('q') => app.should_quit = true,
-        KeyCode::Char('d') => {
-            let ports = app.sorted_ports();
-            if let Some(info) = ports.get(app.detail_index) {
-                if info.pid == 0 {
-                    app.popup = Some(Popup::Docker(DockerPopup {
-                        container_name: info.process_name.clone(),
-                        port: info.port,
-                        selected: 0,
-                    }));
-                } else {
-                    app.popup = Some(Popup::Kill(KillPopup {
-                        pid: info.pid,
-                        process_name: info.process_name.clone(),
-                        port: info.port,
-                        force: app.default_force,
-                    }));
-                }
+        KeyCode::Esc => {
+            app.mode = AppMode::Table;
+            return;
+        }
+        KeyCode::PageDown => {
+            app.scroll_detail(app.detail_page.max(1) as i32);
+            return;
+        }
+        KeyCode::PageUp => {
+            app.scroll_detail(-(app.detail_page.max(1) as i32));
+            return;
+        }
+        _ => {}
+    }
+    let Some(action) = app.keymap.action(code) else {
+        return;
+    };
+    match action {
+        Action::Quit => app.should_quit = true,
+        Action::Copy => app.yank_pending = true,
+        Action::Help => app.popup = Some(Popup::Help),
+        Action::Down => app.scroll_detail(1),
+        Action::Up => app.scroll_detail(-1),
+        Action::SplitView => {
+            app.toggle_split();
+            if app.split_view && app.last_width >= SPLIT_MIN_WIDTH {
+                app.mode = AppMode::Table;
             }
         }
-        KeyCode::Char('D') => {
-            let ports = app.sorted_ports();
-            if let Some(info) = ports.get(app.detail_index) {
-                if info.pid == 0 {
-                    app.popup = Some(Popup::Docker(DockerPopup {
-                        container_name: info.process_name.clone(),
-                        port: info.port,
-                        selected: 0,
-                    }));
-                } else {
-                    app.popup = Some(Popup::Kill(KillPopup {
-                        pid: info.pid,
-                        process_name: info.process_name.clone(),
-                        port: info.port,
-                        force: true,
-                    }));
-                }
+        Action::Top => app.detail_scroll = 0,
+        // Rendering clamps this to the last screenful
+        Action::Bottom => app.detail_scroll = u16::MAX,
+        Action::Kill | Action::ForceKill | Action::Signal if app.vanished.is_some() => {
+            app.status_message = Some((
+                "Nothing to act on: the port is gone".to_string(),
+                Instant::now(),
+            ));
+        }
+        Action::Kill | Action::ForceKill => {
+            if let Some(info) = app.detail_info.clone() {
+                app.start_kill(
+                    &info,
+                    Signal::for_force(action == Action::ForceKill || app.default_force),
+                );
             }
         }
+        Action::Signal => {
+            if let Some(info) = app.detail_info.clone() {
+                app.start_signal(&info);
+            }
+        }
+        _ => {}
+    }
+}
+
+/// The summary keeps the toggles that change what it counts; everything
+/// that works on a row needs the table.
+fn handle_summary_key(app: &mut App, code: KeyCode) {
+    if code == KeyCode::Esc {
+        app.mode = AppMode::Table;
+        return;
+    }
+    let Some(action) = app.keymap.action(code) else {
+        return;
+    };
+    match action {
+        Action::Quit => app.should_quit = true,
+        Action::Summary => app.mode = AppMode::Table,
+        Action::Help => app.popup = Some(Popup::Help),
+        Action::ToggleAll => {
+            app.show_all = !app.show_all;
+            app.refresh_data();
+        }
+        Action::ToggleDocker => app.toggle_docker(),
         _ => {}
     }
 }
@@ -1117,22 +2989,145 @@ fn handle_filter_key(app: &mut App, code: KeyCode) {
     }
 }
 
+/// What to copy: `y` the whole row, `c` the full command line, `p` the port.
+fn handle_yank_key(app: &mut App, code: KeyCode) {
+    app.yank_pending = false;
+    let info = if app.mode == AppMode::Detail {
+        app.detail_info.as_ref()
+    } else {
+        app.selected_port()
+    };
+    let Some(info) = info else {
+        return;
+    };
+    let (what, text) = match code {
+        KeyCode::Char('y') => ("row", app.row_text(info)),
+        KeyCode::Char('c') => ("command", info.command.clone()),
+        KeyCode::Char('p') => ("port", info.port.to_string()),
+        _ => return,
+    };
+    let message = match clipboard::copy(&text) {
+        Ok(via) => format!("Copied {} ({})", what, via),
+        Err(e) => format!("Copy failed: {}", e),
+    };
+    app.status_message = Some((message, Instant::now()));
+}
+
+fn handle_command_key(app: &mut App, code: KeyCode) {
+    match code {
+        KeyCode::Enter => {
+            app.mode = AppMode::Table;
+            let command = std::mem::take(&mut app.command_text);
+            run_command(app, command.trim());
+        }
+        KeyCode::Esc => {
+            app.command_text.clear();
+            app.mode = AppMode::Table;
+        }
+        KeyCode::Backspace => {
+            app.command_text.pop();
+        }
+        KeyCode::Char(c) => {
+            app.command_text.push(c);
+        }
+        _ => {}
+    }
+}
+
+/// `:export [PATH]` writes the visible rows, in view order, as JSON (or CSV
+/// for a `.csv` path); `:q` quits.
+fn run_command(app: &mut App, command: &str) {
+    let (name, arg) = command
+        .split_once(char::is_whitespace)
+        .map(|(n, a)| (n, a.trim()))
+        .unwrap_or((command, ""));
+    let message = match name {
+        "" => return,
+        "q" | "quit" => {
+            app.should_quit = true;
+            return;
+        }
+        "export" => {
+            let path = if arg.is_empty() {
+                export::default_path()
+            } else {
+                arg.to_string()
+            };
+            let path = export::expand_path(&path);
+            let ports = app.sorted_ports();
+            let docker_map = app.docker_enabled.then_some(&app.docker_map);
+            match export::write_view(&path, &ports, docker_map, &app.cwds) {
+                Ok(format) => format!(
+                    "Exported {} row{} as {} to {}",
+                    ports.len(),
+                    if ports.len() == 1 { "" } else { "s" },
+                    format.as_str(),
+                    path.display()
+                ),
+                Err(e) => format!("Export failed: {}: {}", path.display(), e),
+            }
+        }
+        other => format!("Unknown command: {}", other),
+    };
+    app.status_message = Some((message, Instant::now()));
+}
+
+fn handle_search_key(app: &mut App, code: KeyCode) {
+    match code {
+        KeyCode::Enter => {
+            app.mode = AppMode::Table;
+            // Stay on the selected row if it matches, else find the next one
+            let matches = app.search_matches();
+            let on_match = app
+                .table_state
+                .selected()
+                .is_some_and(|sel| matches.contains(&sel));
+            if !on_match {
+                app.jump_to_match(true);
+            }
+        }
+        KeyCode::Esc => {
+            app.search_text.clear();
+            app.mode = AppMode::Table;
+        }
+        KeyCode::Backspace => {
+            app.search_text.pop();
+        }
+        KeyCode::Char(c) => {
+            app.search_text.push(c);
+        }
+        _ => {}
+    }
+}
+
 fn handle_kill_popup_key(app: &mut App, code: KeyCode) {
+    let Some(Popup::Kill(ref mut popup)) = app.popup else {
+        return;
+    };
+    if let Some(ref mut typed) = popup.typed {
+        match code {
+            KeyCode::Char(c) if c.is_ascii_digit() => typed.push(c),
+            KeyCode::Backspace => {
+                typed.pop();
+            }
+            KeyCode::Enter
+                if popup
+                    .type_back
+                    .is_some_and(|t| t.matches(typed, popup.pid, popup.port)) =>
+            {
+                if let Some(Popup::Kill(popup)) = app.popup.take() {
+                    app.run_kill(popup);
+                }
+            }
+            KeyCode::Esc => app.popup = None,
+            _ => {}
+        }
+        return;
+    }
     match code {
         KeyCode::Char('y') | KeyCode::Enter => {
             if let Some(Popup::Kill(popup)) = app.popup.take() {
-                app.status_message = Some((
-                    match kill_process(popup.pid, popup.force) {
-                        Ok("TerminateProcess") => {
-                            format!("Terminated PID {}", popup.pid)
-                        }
-                        Ok(action) => format!("Sent {} to PID {}", action, popup.pid),
-                        Err(err) => format!("Failed to kill PID {}: {}", popup.pid, err),
-                    },
-                    Instant::now(),
-                ));
-                // Refresh immediately to reflect killed process
-                app.refresh_data();
+                app.run_kill(popup);
             }
         }
         KeyCode::Char('n') | KeyCode::Esc => {
@@ -1142,11 +3137,32 @@ fn handle_kill_popup_key(app: &mut App, code: KeyCode) {
     }
 }
 
+fn handle_signal_popup_key(app: &mut App, code: KeyCode) {
+    let Some(Popup::Signal(ref mut popup)) = app.popup else {
+        return;
+    };
+    match code {
+        KeyCode::Char('j') | KeyCode::Down => {
+            popup.selected = (popup.selected + 1).min(signals::NAMED.len() - 1);
+        }
+        KeyCode::Char('k') | KeyCode::Up => {
+            popup.selected = popup.selected.saturating_sub(1);
+        }
+        KeyCode::Enter => {
+            if let Some(Popup::Signal(popup)) = app.popup.take() {
+                app.run_signal(&popup.info, signals::NAMED[popup.selected]);
+            }
+        }
+        KeyCode::Esc => app.popup = None,
+        _ => {}
+    }
+}
+
 fn handle_docker_popup_key(app: &mut App, code: KeyCode) {
     match code {
         KeyCode::Char('j') | KeyCode::Down => {
             if let Some(Popup::Docker(ref mut p)) = app.popup {
-                p.selected = (p.selected + 1).min(2);
+                p.selected = (p.selected + 1).min(p.actions().len() - 1);
             }
         }
         KeyCode::Char('k') | KeyCode::Up => {
@@ -1156,11 +3172,33 @@ fn handle_docker_popup_key(app: &mut App, code: KeyCode) {
         }
         KeyCode::Enter => {
             if let Some(Popup::Docker(popup)) = app.popup.take() {
+                match (popup.selected, popup.compose) {
+                    (3, _) => {
+                        app.exec_request = Some(popup.container_name);
+                        return;
+                    }
+                    (5, Some(compose)) => {
+                        app.compose_view_request = Some(compose);
+                        return;
+                    }
+                    (4, Some(compose)) => {
+                        let msg = app.actions.compose_restart(&compose);
+                        app.status_message = Some((msg, Instant::now()));
+                        docker::invalidate_port_map();
+                        app.refresh_data();
+                        return;
+                    }
+                    _ => {}
+                }
+                if popup.selected < 2 {
+                    // Show the stopped or restarted container right away
+                    docker::invalidate_port_map();
+                }
                 let msg = match popup.selected {
-                    0 => run_docker_action("stop", &popup.container_name),
-                    1 => run_docker_action("restart", &popup.container_name),
+                    0 => app.actions.docker_action("stop", &popup.container_name),
+                    1 => app.actions.docker_action("restart", &popup.container_name),
                     2 => {
-                        let logs = run_docker_logs(&popup.container_name);
+                        let logs = app.actions.docker_logs(&popup.container_name);
                         format!("Logs: {}", logs.lines().last().unwrap_or("(empty)"))
                     }
                     _ => String::new(),
@@ -1176,17 +3214,311 @@ fn handle_docker_popup_key(app: &mut App, code: KeyCode) {
     }
 }
 
-// ── Main entry point ─────────────────────────────────────────────────
+// ── Expect dashboard ─────────────────────────────────────────────────
+//
+// `portview expect --watch`: the manifest's services with their current
+// status, how long they've held it, the share of checks that passed and
+// a strip of recent results. Checks run on a worker thread so a slow
+// health probe never blocks the keyboard.
+
+/// Results kept for the history strip.
+const HISTORY_LEN: usize = 30;
+
+/// The latest check for one service: status, listener and detail.
+type Reading = (ServiceStatus, Option<(u32, String)>, String);
+
+struct ServiceRow {
+    reading: Option<Reading>,
+    /// When the status last changed.
+    since: Instant,
+    checks: u32,
+    passed: u32,
+    history: VecDeque<ServiceStatus>,
+}
 
-pub fn run_tui(
-    target: Option<&str>,
-    show_all: bool,
-    wide: bool,
-    force: bool,
+struct Dashboard {
+    manifest: Arc<Manifest>,
+    source: String,
+    rows: Vec<ServiceRow>,
+    theme: TuiTheme,
+    last_check: Option<Instant>,
+    should_quit: bool,
+}
+
+impl Dashboard {
+    fn new(manifest: Arc<Manifest>, source: String, no_color: bool) -> Self {
+        let now = Instant::now();
+        let rows = manifest
+            .services
+            .iter()
+            .map(|_| ServiceRow {
+                reading: None,
+                since: now,
+                checks: 0,
+                passed: 0,
+                history: VecDeque::with_capacity(HISTORY_LEN),
+            })
+            .collect();
+        Self {
+            manifest,
+            source,
+            rows,
+            theme: if no_color {
+                TuiTheme::no_color()
+            } else {
+                TuiTheme::default_btop()
+            },
+            last_check: None,
+            should_quit: false,
+        }
+    }
+
+    /// Fold one round of checks (in manifest order) into the rows.
+    fn apply(&mut self, readings: Vec<Reading>, now: Instant) {
+        for (row, reading) in self.rows.iter_mut().zip(readings) {
+            let status = reading.0;
+            if row.reading.as_ref().map(|r| r.0) != Some(status) {
+                row.since = now;
+            }
+            row.checks += 1;
+            if status == ServiceStatus::Up {
+                row.passed += 1;
+            }
+            if row.history.len() == HISTORY_LEN {
+                row.history.pop_front();
+            }
+            row.history.push_back(status);
+            row.reading = Some(reading);
+        }
+        self.last_check = Some(now);
+    }
+
+    fn up_count(&self) -> usize {
+        self.rows
+            .iter()
+            .filter(|r| matches!(r.reading, Some((ServiceStatus::Up, ..))))
+            .count()
+    }
+
+    fn status_style(&self, status: ServiceStatus) -> Style {
+        match status {
+            ServiceStatus::Up => self.theme.status_ok,
+            ServiceStatus::Missing => self.theme.status_down,
+            ServiceStatus::Unexpected | ServiceStatus::Unhealthy => self.theme.status_warn,
+        }
+    }
+}
+
+fn render_dashboard(frame: &mut ratatui::Frame, dash: &Dashboard) {
+    let area = frame.area();
+    frame.render_widget(Clear, area);
+
+    let total = dash.rows.len();
+    let up = dash.up_count();
+    let summary_style = if up == total {
+        dash.theme.status_ok
+    } else {
+        dash.theme.status_warn
+    };
+    let title = Line::from(vec![
+        Span::styled(" portview expect", dash.theme.title),
+        Span::styled("  ", dash.theme.footer_text),
+        Span::styled(format!("{}/{} up ", up, total), summary_style),
+        Span::styled(format!(" {} ", dash.source), dash.theme.footer_text),
+    ]);
+    let checked = match dash.last_check {
+        Some(_) => format!("Checked {} ", chrono_free_time()),
+        None => "Checking\u{2026} ".to_string(),
+    };
+    let footer = Line::from(vec![
+        Span::styled(" r", dash.theme.footer_key),
+        Span::styled(" check now  ", dash.theme.footer_text),
+        Span::styled("q", dash.theme.footer_key),
+        Span::styled(" quit  ", dash.theme.footer_text),
+        Span::styled(checked, dash.theme.footer_text),
+    ]);
+    let block = Block::default()
+        .borders(Borders::ALL)
+        .border_type(BorderType::Rounded)
+        .border_style(dash.theme.border)
+        .title_top(title)
+        .title_bottom(footer);
+    let inner = block.inner(area);
+    frame.render_widget(block, area);
+
+    let header = Row::new(
+        [
+            "", "SERVICE", "PORT", "STATUS", "FOR", "UPTIME", "HISTORY", "OWNER", "DETAIL",
+        ]
+        .map(|h| Cell::from(Span::styled(h, dash.theme.header_active))),
+    );
+    let rows: Vec<Row> = dash
+        .manifest
+        .services
+        .iter()
+        .zip(&dash.rows)
+        .map(|(service, row)| {
+            let addr = format!("{}/{}", service.port, service.proto.as_str());
+            let Some((status, ref owner, ref detail)) = row.reading else {
+                return Row::new(vec![
+                    Cell::from(" "),
+                    Cell::from(service.name.clone()),
+                    Cell::from(addr),
+                    Cell::from(Span::styled("checking", dash.theme.footer_text)),
+                ]);
+            };
+            let style = dash.status_style(status);
+            let (mark, _) = status.icon();
+            let held = format_uptime(Some(SystemTime::now() - row.since.elapsed()));
+            let uptime = format!(
+                "{:.1}%",
+                f64::from(row.passed) * 100.0 / f64::from(row.checks)
+            );
+            let history: Vec<Span> = row
+                .history
+                .iter()
+                .map(|&s| {
+                    let bar = if s == ServiceStatus::Up {
+                        "\u{2587}"
+                    } else {
+                        "\u{2581}"
+                    };
+                    Span::styled(bar, dash.status_style(s))
+                })
+                .collect();
+            let owner = owner
+                .as_ref()
+                .map(|(pid, name)| format!("{} ({})", name, pid))
+                .unwrap_or_else(|| "-".to_string());
+            Row::new(vec![
+                Cell::from(Span::styled(mark, style)),
+                Cell::from(service.name.clone()),
+                Cell::from(addr),
+                Cell::from(Span::styled(status.as_str(), style)),
+                Cell::from(held),
+                Cell::from(uptime),
+                Cell::from(Line::from(history)),
+                Cell::from(owner),
+                Cell::from(Span::styled(detail.clone(), dash.theme.footer_text)),
+            ])
+        })
+        .collect();
+
+    let name_w = dash
+        .manifest
+        .services
+        .iter()
+        .map(|s| s.name.chars().count())
+        .max()
+        .unwrap_or(0)
+        .max(7) as u16;
+    let widths = [
+        Constraint::Length(1),
+        Constraint::Length(name_w),
+        Constraint::Length(9),
+        Constraint::Length(10),
+        Constraint::Length(8),
+        Constraint::Length(7),
+        Constraint::Length(HISTORY_LEN as u16),
+        Constraint::Length(20),
+        Constraint::Min(10),
+    ];
+    let table = Table::new(rows, widths).header(header).column_spacing(2);
+    frame.render_widget(table, inner);
+}
+
+/// Run `portview expect --watch` until the user quits.
+pub(crate) fn run_expect_dashboard(
+    manifest: Manifest,
+    source: String,
+    interval: Duration,
     no_color: bool,
-    docker: bool,
-    styles: StyleConfig,
 ) -> io::Result<()> {
+    let manifest = Arc::new(manifest);
+    let (results_tx, results_rx) = mpsc::channel::<Vec<Reading>>();
+    let (wake_tx, wake_rx) = mpsc::channel::<()>();
+    let worker_manifest = Arc::clone(&manifest);
+    thread::spawn(move || loop {
+        let infos = get_port_infos(true);
+        let readings = expect::check(&worker_manifest, &infos, expect::run_probe)
+            .into_iter()
+            .map(|o| (o.status, o.owner, o.detail))
+            .collect();
+        if results_tx.send(readings).is_err() {
+            break;
+        }
+        // Sleep until the next round, an early wake-up, or shutdown
+        if let Err(mpsc::RecvTimeoutError::Disconnected) = wake_rx.recv_timeout(interval) {
+            break;
+        }
+    });
+
+    enable_raw_mode()?;
+    let mut stdout = io::stdout();
+    stdout.execute(EnterAlternateScreen)?;
+    let backend = CrosstermBackend::new(stdout);
+    let mut terminal = Terminal::new(backend)?;
+    terminal.clear()?;
+
+    let mut dash = Dashboard::new(manifest, source, no_color);
+    // A service starting or stopping shows up without waiting a full interval
+    let watcher = ChangeWatcher::spawn();
+
+    loop {
+        while let Ok(readings) = results_rx.try_recv() {
+            dash.apply(readings, Instant::now());
+        }
+        terminal.draw(|frame| render_dashboard(frame, &dash))?;
+        if dash.should_quit {
+            break;
+        }
+        if watcher.as_ref().is_some_and(ChangeWatcher::take) {
+            let _ = wake_tx.send(());
+        }
+        if event::poll(refresh::CHECK_INTERVAL)? {
+            if let Event::Key(key) = event::read()? {
+                if key.kind != KeyEventKind::Press {
+                    continue;
+                }
+                match key.code {
+                    KeyCode::Char('q') | KeyCode::Esc => dash.should_quit = true,
+                    KeyCode::Char('c') if key.modifiers.contains(KeyModifiers::CONTROL) => {
+                        dash.should_quit = true
+                    }
+                    KeyCode::Char('r') => {
+                        let _ = wake_tx.send(());
+                    }
+                    _ => {}
+                }
+            }
+        }
+    }
+
+    disable_raw_mode()?;
+    terminal.backend_mut().execute(LeaveAlternateScreen)?;
+    terminal.show_cursor()?;
+    Ok(())
+}
+
+// ── Main entry point ─────────────────────────────────────────────────
+
+/// Give the terminal to a child process for the length of `f`, then
+/// redraw from scratch.
+fn suspend_terminal<T>(
+    terminal: &mut Terminal<CrosstermBackend<io::Stdout>>,
+    f: impl FnOnce() -> T,
+) -> io::Result<T> {
+    disable_raw_mode()?;
+    terminal.backend_mut().execute(LeaveAlternateScreen)?;
+    terminal.show_cursor()?;
+    let result = f();
+    enable_raw_mode()?;
+    terminal.backend_mut().execute(EnterAlternateScreen)?;
+    terminal.clear()?;
+    Ok(result)
+}
+
+pub fn run_tui(opts: TuiOptions, styles: StyleConfig) -> io::Result<()> {
     // Setup terminal
     enable_raw_mode()?;
     let mut stdout = io::stdout();
@@ -1196,38 +3528,90 @@ pub fn run_tui(
     let mut terminal = Terminal::new(backend)?;
     terminal.clear()?;
 
-    let mut app = App::new(target, show_all, wide, force, no_color, docker, styles);
+    let mut app = App::new(opts, styles, collector::active(), actions::active());
 
     let tick_rate = Duration::from_secs(1);
+    // Replays advance on the timer; only live sessions watch for changes
+    let watcher = if app.replay.is_none() {
+        ChangeWatcher::spawn()
+    } else {
+        None
+    };
+    let mut container_events = None;
+    // Whether `container_events` was started for the current Docker toggle
+    let mut following_docker = false;
+    let mut window_title = TitleWriter::default();
 
     loop {
         terminal.draw(|frame| render(frame, &mut app))?;
+        if app.title {
+            window_title.update(terminal.backend_mut(), app.window_title())?;
+        }
 
         if app.should_quit {
             break;
         }
 
-        // Refresh data every tick
-        if app.last_refresh.elapsed() >= tick_rate {
+        if following_docker != app.docker_enabled && app.replay.is_none() {
+            following_docker = app.docker_enabled;
+            container_events = following_docker.then(EventWatcher::spawn).flatten();
+        }
+
+        // Refresh data every tick, or as soon as a listener opens or closes
+        // or a container starts or stops
+        let mut changed = watcher.as_ref().is_some_and(ChangeWatcher::take);
+        let events = container_events
+            .as_ref()
+            .map_or_else(Vec::new, EventWatcher::take);
+        if let Some(event) = events.last() {
+            docker::invalidate_port_map();
+            app.status_message = Some((event.describe(), Instant::now()));
+            changed = true;
+        }
+        if changed || app.last_refresh.elapsed() >= tick_rate {
+            if let Some(ref mut replay) = app.replay {
+                replay.tick();
+            }
             app.refresh_data();
         }
+        if std::mem::take(&mut app.bell_request) {
+            let backend = terminal.backend_mut();
+            io::Write::write_all(backend, b"\x07")?;
+            io::Write::flush(backend)?;
+        }
+
+        app.check_kill();
 
         // Wait for events with timeout to next tick
-        let remaining = tick_rate
+        let mut remaining = tick_rate
             .checked_sub(app.last_refresh.elapsed())
             .unwrap_or(Duration::ZERO);
+        if watcher.is_some() || container_events.is_some() || app.kill_check.is_some() {
+            remaining = remaining.min(refresh::CHECK_INTERVAL);
+        }
 
         if event::poll(remaining)? {
-            if let Event::Key(key) = event::read()? {
-                // Only handle Press events (not Release/Repeat)
-                if key.kind == KeyEventKind::Press {
-                    handle_key(&mut app, key.code, key.modifiers);
-                }
-            }
+            handle_event(&mut app, event::read()?);
+        }
+
+        if let Some(container) = app.exec_request.take() {
+            let msg = suspend_terminal(&mut terminal, || exec_shell(&container))?;
+            app.status_message = Some((msg, Instant::now()));
+            app.refresh_data();
         }
+        if let Some(compose) = app.compose_view_request.take() {
+            let msg = suspend_terminal(&mut terminal, || view_compose_project(&compose))?;
+            app.status_message = Some((msg, Instant::now()));
+            app.refresh_data();
+        }
+    }
+
+    if app.replay.is_none() {
+        let _ = remember::save(&app.remembered());
     }
 
     // Restore terminal
+    window_title.restore(terminal.backend_mut())?;
     disable_raw_mode()?;
     terminal.backend_mut().execute(LeaveAlternateScreen)?;
     terminal.show_cursor()?;
@@ -1240,45 +3624,88 @@ pub fn run_tui(
 #[cfg(test)]
 mod tests {
     use super::*;
-    use std::net::{IpAddr, Ipv4Addr};
+    use crossterm::event::KeyEvent;
+    use ratatui::backend::TestBackend;
+
+    use std::sync::Mutex;
     use std::time::SystemTime;
 
     fn make_port_info(port: u16, name: &str, cmd: &str) -> PortInfo {
-        PortInfo {
-            port,
-            protocol: "TCP".to_string(),
-            pid: port as u32 * 100,
-            process_name: name.to_string(),
-            command: cmd.to_string(),
-            user: "test".to_string(),
-            state: crate::TcpState::Listen,
-            memory_bytes: 1024 * 1024,
-            cpu_seconds: 1.0,
-            start_time: Some(SystemTime::now() - Duration::from_secs(60)),
-            children: 0,
-            local_addr: IpAddr::V4(Ipv4Addr::UNSPECIFIED),
-        }
+        PortInfo::listener(port, port as u32 * 100, name)
+            .command(cmd)
+            .user("test")
+            .usage(1024 * 1024, 1.0)
+            .started(SystemTime::now() - Duration::from_secs(60))
     }
 
     fn make_test_app(ports: Vec<PortInfo>) -> App {
         App {
+            source: collector::active(),
+            actions: actions::active(),
             ports,
             docker_enabled: false,
+            docker_refresh: docker::PORT_MAP_REFRESH,
+            forwarded: HashMap::new(),
+            dns_enabled: false,
+            show_cwd: false,
+            show_runtime: false,
+            cwds: HashMap::new(),
+            orphans: HashMap::new(),
+            expanded_groups: HashSet::new(),
             docker_map: DockerPortMap::default(),
             table_state: TableState::default(),
             mode: AppMode::Table,
             show_all: false,
             filter_text: String::new(),
+            search_text: String::new(),
+            yank_pending: false,
+            command_text: String::new(),
             popup: None,
             target: None,
             styles: StyleConfig::default(),
             theme: TuiTheme::no_color(),
-            wide: false,
+            keymap: Keymap::default(),
+            command_view: CommandView::Truncate,
+            command_offset: 0,
             default_force: false,
+            confirm: Confirm::default(),
+            visibility: Visibility::Complete,
+            hidden_listeners: 0,
             should_quit: false,
             last_refresh: Instant::now(),
-            detail_index: 0,
+            detail_info: None,
+            vanished: None,
+            detail_scroll: 0,
+            detail_page: 0,
+            split_view: false,
+            pane_shown: false,
+            last_width: 0,
+            detail: None,
+            backlog: None,
+            ephemeral: None,
+            socket_options: None,
+            container_networks: HashMap::new(),
+            udp_rows: None,
+            tcp_sampler: None,
+            tcp_stats: HashMap::new(),
+            peers: Vec::new(),
+            sampler: BandwidthSampler::default(),
+            rates: HashMap::new(),
+            notify_rules: Vec::new(),
+            hooks: EventHooks::default(),
+            tracker: PortTracker::default(),
+            alerts: AlertMonitor::new(Vec::new()),
+            alert_notify: false,
+            bell: false,
+            bell_at: None,
+            bell_request: false,
+            title: false,
+            recorder: None,
+            replay: None,
             status_message: None,
+            kill_check: None,
+            exec_request: None,
+            compose_view_request: None,
             sort_column: SortColumn::Port,
             sort_direction: SortDirection::Asc,
         }
@@ -1339,6 +3766,19 @@ mod tests {
         assert_eq!(filtered[0].port, 3000);
     }
 
+    #[test]
+    fn window_title_marks_a_port_target() {
+        let mut app = make_test_app(vec![
+            make_port_info(3000, "node", "next dev"),
+            make_port_info(5432, "postgres", "postgres"),
+        ]);
+        assert_eq!(app.window_title(), "portview: 2 ports");
+        app.target = Some("3000".to_string());
+        assert_eq!(app.window_title(), "portview: 1 port, 3000 ✓");
+        app.target = Some("8080".to_string());
+        assert_eq!(app.window_title(), "portview: 0 ports, 8080 ✗");
+    }
+
     #[test]
     fn filtered_ports_case_insensitive() {
         let mut app = make_test_app(vec![
@@ -1372,6 +3812,10 @@ mod tests {
                 image: "nginx:latest".to_string(),
                 container_port: 80,
                 protocol: "TCP".to_string(),
+                state: "running".to_string(),
+                health: None,
+                stats: None,
+                compose: None,
             }],
         );
 
@@ -1381,6 +3825,40 @@ mod tests {
         assert_eq!(filtered[0].port, 3000);
     }
 
+    #[test]
+    fn health_column_sorts_broken_containers_last() {
+        let mut app = make_test_app(vec![
+            make_port_info(3000, "node", "next dev"),
+            make_port_info(8080, "nginx", "nginx"),
+            make_port_info(9000, "api", "api"),
+        ]);
+        assert!(!app.visible_columns().contains(&SortColumn::Health));
+        app.docker_enabled = true;
+        for (port, state, health) in [
+            (3000, "restarting", None),
+            (8080, "running", Some("healthy")),
+        ] {
+            app.docker_map.insert(
+                port,
+                vec![DockerPortOwner {
+                    container_id: format!("{:016}", port),
+                    container_name: format!("c{}", port),
+                    image: "img".to_string(),
+                    container_port: 80,
+                    protocol: "TCP".to_string(),
+                    state: state.to_string(),
+                    health: health.map(str::to_string),
+                    stats: None,
+                    compose: None,
+                }],
+            );
+        }
+        assert!(app.visible_columns().contains(&SortColumn::Health));
+        app.sort_column = SortColumn::Health;
+        let order: Vec<u16> = app.sorted_ports().iter().map(|i| i.port).collect();
+        assert_eq!(order, [9000, 8080, 3000]);
+    }
+
     #[test]
     fn filtered_ports_target_matches_docker_image() {
         let mut app = make_test_app(vec![make_port_info(5432, "postgres", "postgres")]);
@@ -1393,6 +3871,10 @@ mod tests {
                 image: "postgres:16".to_string(),
                 container_port: 5432,
                 protocol: "TCP".to_string(),
+                state: "running".to_string(),
+                health: None,
+                stats: None,
+                compose: None,
             }],
         );
         app.target = Some("postgres:16".to_string());
@@ -1461,6 +3943,23 @@ mod tests {
         assert_eq!(sorted[2].port, 6379); // lowest mem
     }
 
+    #[test]
+    fn sorted_ports_by_net_total() {
+        let p1 = make_port_info(3000, "node", "next dev");
+        let p2 = make_port_info(5432, "postgres", "postgres");
+        let p3 = make_port_info(6379, "redis", "redis-server");
+
+        let mut app = make_test_app(vec![p1, p2, p3]);
+        app.rates.insert(300_000, Rate { rx: 10, tx: 10 });
+        app.rates.insert(543_200, Rate { rx: 5000, tx: 0 });
+        app.sort_column = SortColumn::Net;
+        app.sort_direction = SortDirection::Desc;
+        let sorted = app.sorted_ports();
+        assert_eq!(sorted[0].port, 5432);
+        assert_eq!(sorted[1].port, 3000);
+        assert_eq!(sorted[2].port, 6379); // no traffic sampled
+    }
+
     #[test]
     fn sorted_ports_uptime_none_sorts_last() {
         let mut p1 = make_port_info(3000, "node", "next dev");
@@ -1482,6 +3981,53 @@ mod tests {
         assert_eq!(col.next(), SortColumn::Proto);
         assert_eq!(col.prev(), SortColumn::Command);
         assert_eq!(SortColumn::Command.next(), SortColumn::Port);
+        assert_eq!(SortColumn::Net.next(), SortColumn::Label);
+        assert_eq!(SortColumn::Cwd.prev(), SortColumn::Health);
+        assert_eq!(SortColumn::Health.prev(), SortColumn::Runtime);
+    }
+
+    #[test]
+    fn sort_names_parse_back() {
+        for column in SortColumn::ALL {
+            assert_eq!(
+                SortColumn::parse_sort(column.name()),
+                Some((column, SortDirection::Asc))
+            );
+        }
+        assert_eq!(
+            SortColumn::parse_sort("MEM desc"),
+            Some((SortColumn::Mem, SortDirection::Desc))
+        );
+        assert_eq!(SortColumn::parse_sort("mem sideways"), None);
+        assert_eq!(SortColumn::parse_sort("mem desc asc"), None);
+        assert_eq!(SortColumn::parse_sort(""), None);
+    }
+
+    #[test]
+    fn restore_turns_toggles_on_and_keeps_a_sort_it_can_show() {
+        let mut app = make_test_app(vec![]);
+        app.restore(&TuiState {
+            sort: Some((SortColumn::Cwd, SortDirection::Desc)),
+            cwd: true,
+            ..TuiState::default()
+        });
+        assert!(app.show_cwd);
+        assert_eq!(
+            (app.sort_column, app.sort_direction),
+            (SortColumn::Cwd, SortDirection::Desc)
+        );
+        assert_eq!(
+            app.remembered().sort,
+            Some((SortColumn::Cwd, SortDirection::Desc))
+        );
+
+        // A health sort without the Docker toggle has nothing to sort by
+        let mut app = make_test_app(vec![]);
+        app.restore(&TuiState {
+            sort: Some((SortColumn::Health, SortDirection::Asc)),
+            ..TuiState::default()
+        });
+        assert_eq!(app.sort_column, SortColumn::Port);
     }
 
     #[test]
@@ -1491,9 +4037,961 @@ mod tests {
     }
 
     #[test]
-    fn sort_column_from_index() {
-        assert_eq!(SortColumn::from_index(0), Some(SortColumn::Port));
-        assert_eq!(SortColumn::from_index(7), Some(SortColumn::Command));
-        assert_eq!(SortColumn::from_index(8), None);
+    fn visible_columns_follow_cwd_toggle() {
+        let mut app = make_test_app(vec![]);
+        assert_eq!(app.visible_columns().len(), 9);
+        assert_eq!(app.visible_columns()[8], SortColumn::Command);
+        app.show_cwd = true;
+        assert_eq!(app.visible_columns().len(), 10);
+        assert_eq!(app.visible_columns()[8], SortColumn::Cwd);
+    }
+
+    #[test]
+    fn cycle_sort_column_skips_hidden_cwd() {
+        let mut app = make_test_app(vec![]);
+        app.sort_column = SortColumn::Net;
+        app.cycle_sort_column(true);
+        assert_eq!(app.sort_column, SortColumn::Command);
+        app.cycle_sort_column(false);
+        assert_eq!(app.sort_column, SortColumn::Net);
+
+        app.show_cwd = true;
+        app.cycle_sort_column(true);
+        assert_eq!(app.sort_column, SortColumn::Cwd);
+    }
+
+    #[test]
+    fn reuseport_groups_fold_and_expand() {
+        let worker = |pid: u32| {
+            let mut info = make_port_info(8080, "envoy", "envoy -c envoy.yaml");
+            info.pid = pid;
+            info
+        };
+        let mut app = make_test_app(vec![
+            worker(30),
+            make_port_info(3000, "node", "next dev"),
+            worker(10),
+            worker(20),
+        ]);
+        let rows: Vec<(u16, u32)> = app.sorted_ports().iter().map(|i| (i.port, i.pid)).collect();
+        assert_eq!(rows, vec![(3000, 300000), (8080, 10)]);
+
+        app.table_state.select(Some(1));
+        handle_key(&mut app, KeyCode::Char(' '), KeyModifiers::NONE);
+        let rows: Vec<u32> = app.sorted_ports().iter().map(|i| i.pid).collect();
+        assert_eq!(rows, vec![300000, 10, 20, 30]);
+
+        // Collapsing from a member row keeps the selection in range
+        app.table_state.select(Some(3));
+        handle_key(&mut app, KeyCode::Char(' '), KeyModifiers::NONE);
+        assert_eq!(app.sorted_ports().len(), 2);
+        assert_eq!(app.table_state.selected(), Some(1));
+
+        // Rows that aren't in a group ignore the key
+        app.table_state.select(Some(0));
+        handle_key(&mut app, KeyCode::Char(' '), KeyModifiers::NONE);
+        assert!(app.expanded_groups.is_empty());
+    }
+
+    #[test]
+    fn docker_popup_exec_defers_to_the_main_loop() {
+        let mut app = make_test_app(vec![]);
+        app.popup = Some(Popup::Docker(DockerPopup {
+            container_name: "web".to_string(),
+            port: 8080,
+            compose: None,
+            selected: 0,
+        }));
+        for _ in 0..5 {
+            handle_docker_popup_key(&mut app, KeyCode::Down);
+        }
+        match app.popup {
+            Some(Popup::Docker(ref p)) => assert_eq!(DOCKER_ACTIONS[p.selected], "Exec shell"),
+            _ => panic!("popup closed"),
+        }
+        handle_docker_popup_key(&mut app, KeyCode::Enter);
+        assert!(app.popup.is_none());
+        assert_eq!(app.exec_request.as_deref(), Some("web"));
+        assert!(app.status_message.is_none());
+    }
+
+    #[test]
+    fn docker_popup_offers_compose_actions() {
+        let mut info = make_port_info(3000, "shop-web-1", "node server.js");
+        info.pid = 0;
+        let mut app = make_test_app(vec![info.clone()]);
+        let compose = ComposeService {
+            project: "shop".to_string(),
+            service: "web".to_string(),
+            working_dir: "/home/dev/shop".to_string(),
+            config_files: vec!["/home/dev/shop/compose.yaml".to_string()],
+        };
+        app.docker_map.insert(
+            3000,
+            vec![DockerPortOwner {
+                container_id: "0123456789abcdef".to_string(),
+                container_name: "shop-web-1".to_string(),
+                image: "shop-web".to_string(),
+                container_port: 3000,
+                protocol: "TCP".to_string(),
+                state: "running".to_string(),
+                health: None,
+                stats: None,
+                compose: Some(compose.clone()),
+            }],
+        );
+        let popup = app.docker_popup(&info);
+        assert_eq!(
+            popup.actions()[4..],
+            ["Restart service (web)", "View compose project (shop)"]
+        );
+        app.popup = Some(Popup::Docker(popup));
+        for _ in 0..10 {
+            handle_docker_popup_key(&mut app, KeyCode::Down);
+        }
+        handle_docker_popup_key(&mut app, KeyCode::Enter);
+        assert!(app.popup.is_none());
+        assert_eq!(app.compose_view_request, Some(compose));
+        assert!(app.exec_request.is_none());
+
+        app.docker_map.clear();
+        assert_eq!(
+            app.docker_popup(&info).actions().len(),
+            DOCKER_ACTIONS.len()
+        );
+    }
+
+    #[test]
+    fn replay_keys_step_frames_and_block_kill() {
+        use crate::recording::Snapshot;
+        let frame = |ports: Vec<PortInfo>| Snapshot {
+            at: SystemTime::now(),
+            ports,
+        };
+        let mut app = make_test_app(vec![]);
+        app.replay = Replay::new(vec![
+            frame(vec![make_port_info(3000, "node", "node server.js")]),
+            frame(vec![
+                make_port_info(3000, "node", "node server.js"),
+                make_port_info(8080, "python3", "python3 -m http.server"),
+            ]),
+        ]);
+        app.refresh_data();
+        assert_eq!(app.ports.len(), 1);
+
+        handle_key(&mut app, KeyCode::Right, KeyModifiers::NONE);
+        assert_eq!(app.ports.len(), 2);
+        assert!(app.replay.as_ref().unwrap().paused);
+
+        handle_key(&mut app, KeyCode::Char('d'), KeyModifiers::NONE);
+        assert!(app.popup.is_none());
+
+        handle_key(&mut app, KeyCode::Left, KeyModifiers::NONE);
+        assert_eq!(app.ports.len(), 1);
+    }
+
+    #[test]
+    fn detail_view_follows_its_row_and_notes_when_it_goes() {
+        let mut app = make_test_app(vec![
+            make_port_info(3000, "node", "node server.js"),
+            make_port_info(8080, "python3", "python3 -m http.server"),
+        ]);
+        app.table_state.select(Some(1));
+        handle_key(&mut app, KeyCode::Enter, KeyModifiers::NONE);
+        assert_eq!(app.detail_info.as_ref().map(|i| i.port), Some(8080));
+
+        // A new row sorting first doesn't move the view to another port
+        let mut grown = make_port_info(8080, "python3", "python3 -m http.server");
+        grown.memory_bytes = 4096;
+        app.ports = vec![make_port_info(80, "nginx", "nginx"), grown];
+        app.refresh_detail();
+        let info = app.detail_info.as_ref().unwrap();
+        assert_eq!((info.port, info.memory_bytes), (8080, 4096));
+        assert_eq!(app.vanished, None);
+
+        app.ports.retain(|i| i.port != 8080);
+        app.refresh_detail();
+        assert!(app.vanished.is_some());
+        assert_eq!(app.detail_info.as_ref().map(|i| i.port), Some(8080));
+        handle_key(&mut app, KeyCode::Char('d'), KeyModifiers::NONE);
+        assert!(app.popup.is_none());
+
+        handle_key(&mut app, KeyCode::Char('k'), KeyModifiers::NONE);
+        assert_eq!(app.detail_scroll, 0);
+        handle_key(&mut app, KeyCode::Char('j'), KeyModifiers::NONE);
+        handle_key(&mut app, KeyCode::Down, KeyModifiers::NONE);
+        assert_eq!(app.detail_scroll, 2);
+        handle_key(&mut app, KeyCode::Home, KeyModifiers::NONE);
+        assert_eq!(app.detail_scroll, 0);
+    }
+
+    #[test]
+    fn remapped_keys_drive_the_table_and_help() {
+        let mut app = make_test_app(vec![make_port_info(3000, "node", "node server.js")]);
+        app.keymap = Keymap::build(
+            crate::keymap::Preset::Default,
+            &[(Action::Kill, vec![KeyCode::Char('x')])],
+        )
+        .unwrap();
+        app.table_state.select(Some(0));
+        handle_key(&mut app, KeyCode::Char('d'), KeyModifiers::NONE);
+        assert!(app.popup.is_none());
+        handle_key(&mut app, KeyCode::Char('x'), KeyModifiers::NONE);
+        assert!(matches!(app.popup, Some(Popup::Kill(_))));
+        app.popup = None;
+
+        handle_key(&mut app, KeyCode::Char('?'), KeyModifiers::NONE);
+        assert!(matches!(app.popup, Some(Popup::Help)));
+        // Any key closes the overlay without acting
+        handle_key(&mut app, KeyCode::Char('q'), KeyModifiers::NONE);
+        assert!(app.popup.is_none());
+        assert!(!app.should_quit);
+    }
+
+    #[test]
+    fn confirm_force_wants_the_pid_typed() {
+        let mut app = make_test_app(vec![make_port_info(3000, "node", "node server.js")]);
+        app.confirm = Confirm {
+            kill: false,
+            typed_force: true,
+            shared_force: false,
+        };
+        app.table_state.select(Some(0));
+        handle_key(&mut app, KeyCode::Char('D'), KeyModifiers::NONE);
+        let Some(Popup::Kill(ref popup)) = app.popup else {
+            panic!("force kill must ask for the PID");
+        };
+        assert_eq!(popup.typed.as_deref(), Some(""));
+
+        for c in "3001".chars() {
+            handle_key(&mut app, KeyCode::Char(c), KeyModifiers::NONE);
+        }
+        handle_key(&mut app, KeyCode::Char('y'), KeyModifiers::NONE);
+        handle_key(&mut app, KeyCode::Enter, KeyModifiers::NONE);
+        // Wrong PID: still open
+        let Some(Popup::Kill(ref popup)) = app.popup else {
+            panic!("a wrong PID must not kill");
+        };
+        assert_eq!(popup.typed.as_deref(), Some("3001"));
+        handle_key(&mut app, KeyCode::Esc, KeyModifiers::NONE);
+        assert!(app.popup.is_none());
+    }
+
+    #[test]
+    fn signal_picker_hands_kill_to_the_confirmation() {
+        let mut app = make_test_app(vec![
+            make_port_info(3000, "node", "node server.js"),
+            PortInfo {
+                pid: 0,
+                ..make_port_info(5432, "docker", "postgres")
+            },
+        ]);
+        app.confirm = Confirm {
+            kill: false,
+            typed_force: true,
+            shared_force: false,
+        };
+        app.table_state.select(Some(1));
+        handle_key(&mut app, KeyCode::Char('K'), KeyModifiers::NONE);
+        assert!(app.popup.is_none());
+        assert!(app.status_message.is_some());
+
+        app.table_state.select(Some(0));
+        handle_key(&mut app, KeyCode::Char('K'), KeyModifiers::NONE);
+        for _ in 0..signals::NAMED.len() + 2 {
+            handle_key(&mut app, KeyCode::Char('j'), KeyModifiers::NONE);
+        }
+        let Some(Popup::Signal(ref popup)) = app.popup else {
+            panic!("K must open the signal picker");
+        };
+        assert_eq!(signals::NAMED[popup.selected], Signal::Kill);
+        handle_key(&mut app, KeyCode::Enter, KeyModifiers::NONE);
+        let Some(Popup::Kill(ref popup)) = app.popup else {
+            panic!("SIGKILL must still ask for the PID");
+        };
+        assert!(popup.signal == Signal::Kill && popup.typed.is_some());
+    }
+
+    #[test]
+    fn signal_picker_confirms_every_signal_that_stops_the_process() {
+        let mut app = make_test_app(vec![make_port_info(3000, "node", "node server.js")]);
+        for (presses, signal) in [(2, Signal::Int), (3, Signal::Quit)] {
+            app.table_state.select(Some(0));
+            handle_key(&mut app, KeyCode::Char('K'), KeyModifiers::NONE);
+            for _ in 0..presses {
+                handle_key(&mut app, KeyCode::Char('j'), KeyModifiers::NONE);
+            }
+            handle_key(&mut app, KeyCode::Enter, KeyModifiers::NONE);
+            let Some(Popup::Kill(ref popup)) = app.popup else {
+                panic!("{} must ask first", signal.name());
+            };
+            assert_eq!(popup.signal, signal);
+            handle_key(&mut app, KeyCode::Esc, KeyModifiers::NONE);
+        }
+        assert_eq!(app.ports.len(), 1);
+    }
+
+    #[test]
+    fn kill_check_reports_once_the_wait_is_over() {
+        let mut app = make_test_app(vec![make_port_info(3000, "node", "node server.js")]);
+        app.kill_check = Some(KillCheck {
+            pid: 300000,
+            ports: vec![3000],
+            signal: Signal::Term,
+            since: Instant::now() - verify::TIMEOUT,
+        });
+        app.check_kill();
+        assert!(app.kill_check.is_none());
+        let (message, _) = app.status_message.as_ref().unwrap();
+        assert!(message.contains("3000"), "{}", message);
+    }
+
+    #[test]
+    fn command_view_cycles_and_arrows_scroll() {
+        let mut app = make_test_app(vec![make_port_info(3000, "node", "node server.js")]);
+        assert_eq!(app.command_view, CommandView::Truncate);
+        handle_key(&mut app, KeyCode::Char('w'), KeyModifiers::NONE);
+        assert_eq!(app.command_view, CommandView::Wrap);
+
+        handle_key(&mut app, KeyCode::Right, KeyModifiers::NONE);
+        assert_eq!(app.command_view, CommandView::Scroll);
+        assert_eq!(app.command_offset, SCROLL_STEP);
+        handle_key(&mut app, KeyCode::Char('l'), KeyModifiers::NONE);
+        handle_key(&mut app, KeyCode::Left, KeyModifiers::NONE);
+        assert_eq!(app.command_offset, SCROLL_STEP);
+        handle_key(&mut app, KeyCode::Char('h'), KeyModifiers::NONE);
+        handle_key(&mut app, KeyCode::Char('h'), KeyModifiers::NONE);
+        assert_eq!(app.command_offset, 0);
+
+        handle_key(&mut app, KeyCode::Char('w'), KeyModifiers::NONE);
+        assert_eq!(app.command_view, CommandView::Truncate);
+    }
+
+    #[test]
+    fn search_jumps_between_matches_without_hiding_rows() {
+        let mut app = make_test_app(vec![
+            make_port_info(3000, "node", "node server.js"),
+            make_port_info(5432, "postgres", "postgres -D /data"),
+            make_port_info(8080, "node", "node worker.js"),
+        ]);
+        app.table_state.select(Some(0));
+
+        handle_key(&mut app, KeyCode::Char('s'), KeyModifiers::NONE);
+        for c in "node".chars() {
+            handle_key(&mut app, KeyCode::Char(c), KeyModifiers::NONE);
+        }
+        handle_key(&mut app, KeyCode::Enter, KeyModifiers::NONE);
+        assert_eq!(app.sorted_ports().len(), 3, "search doesn't filter");
+        assert_eq!(app.search_matches(), vec![0, 2]);
+        assert_eq!(app.table_state.selected(), Some(0));
+
+        handle_key(&mut app, KeyCode::Char('n'), KeyModifiers::NONE);
+        assert_eq!(app.table_state.selected(), Some(2));
+        handle_key(&mut app, KeyCode::Char('n'), KeyModifiers::NONE);
+        assert_eq!(app.table_state.selected(), Some(0), "wraps around");
+        handle_key(&mut app, KeyCode::Char('N'), KeyModifiers::NONE);
+        assert_eq!(app.table_state.selected(), Some(2));
+
+        handle_key(&mut app, KeyCode::Esc, KeyModifiers::NONE);
+        assert!(app.search_text.is_empty());
+        assert!(!app.should_quit, "first Esc only clears the search");
+    }
+
+    #[test]
+    fn yank_waits_for_a_target_key() {
+        let mut app = make_test_app(vec![make_port_info(3000, "node", "node server.js")]);
+        app.table_state.select(Some(0));
+        app.cwds.insert(300_000, "/srv/web".to_string());
+
+        handle_key(&mut app, KeyCode::Char('y'), KeyModifiers::NONE);
+        assert!(app.yank_pending);
+        handle_key(&mut app, KeyCode::Esc, KeyModifiers::NONE);
+        assert!(!app.yank_pending);
+        assert!(!app.should_quit, "Esc cancels the copy, not the app");
+
+        let info = app.selected_port().unwrap().clone();
+        assert_eq!(
+            app.row_text(&info),
+            "3000\tTCP\t300000\ttest\tnode\t/srv/web\tnode server.js"
+        );
+    }
+
+    #[test]
+    fn export_command_writes_visible_rows_in_view_order() {
+        let mut app = make_test_app(vec![
+            make_port_info(5432, "postgres", "postgres -D /data"),
+            make_port_info(3000, "node", "node server.js"),
+            make_port_info(8080, "nginx", "nginx"),
+        ]);
+        app.filter_text = "o".to_string();
+        let path = std::env::temp_dir().join(format!("portview-export-{}.csv", std::process::id()));
+
+        handle_key(&mut app, KeyCode::Char('e'), KeyModifiers::NONE);
+        assert!(app.mode == AppMode::CommandInput);
+        assert!(app.command_text.starts_with("export portview-"));
+        app.command_text = format!("export {}", path.display());
+        handle_key(&mut app, KeyCode::Enter, KeyModifiers::NONE);
+        assert!(app.mode == AppMode::Table);
+
+        let csv = std::fs::read_to_string(&path).unwrap();
+        let _ = std::fs::remove_file(&path);
+        let ports: Vec<&str> = csv
+            .lines()
+            .skip(1)
+            .map(|l| l.split(',').next().unwrap())
+            .collect();
+        assert_eq!(ports, ["3000", "5432"]);
+        assert!(app
+            .status_message
+            .as_ref()
+            .unwrap()
+            .0
+            .starts_with("Exported 2 rows as CSV"));
+
+        handle_key(&mut app, KeyCode::Char(':'), KeyModifiers::NONE);
+        for c in "frobnicate".chars() {
+            handle_key(&mut app, KeyCode::Char(c), KeyModifiers::NONE);
+        }
+        handle_key(&mut app, KeyCode::Enter, KeyModifiers::NONE);
+        assert_eq!(
+            app.status_message.as_ref().unwrap().0,
+            "Unknown command: frobnicate"
+        );
+    }
+
+    #[test]
+    fn dashboard_tracks_history_and_uptime() {
+        let manifest = Manifest::parse("[[service]]\nname = \"web\"\nport = 3000").unwrap();
+        let mut dash = Dashboard::new(Arc::new(manifest), "dev.toml".to_string(), true);
+        let start = Instant::now();
+        let up = || {
+            vec![(
+                ServiceStatus::Up,
+                Some((42, "node".to_string())),
+                String::new(),
+            )]
+        };
+        let down = || vec![(ServiceStatus::Missing, None, "not listening".to_string())];
+
+        dash.apply(up(), start);
+        dash.apply(up(), start + Duration::from_secs(2));
+        assert_eq!(dash.rows[0].since, start);
+        dash.apply(down(), start + Duration::from_secs(4));
+        assert_eq!(dash.rows[0].since, start + Duration::from_secs(4));
+        assert_eq!((dash.rows[0].passed, dash.rows[0].checks), (2, 3));
+        assert_eq!(dash.up_count(), 0);
+
+        for i in 0..HISTORY_LEN {
+            dash.apply(up(), start + Duration::from_secs(6 + i as u64));
+        }
+        assert_eq!(dash.rows[0].history.len(), HISTORY_LEN);
+        assert!(dash.rows[0].history.iter().all(|&s| s == ServiceStatus::Up));
+        assert_eq!(dash.up_count(), 1);
+    }
+
+    // ── Scripted sessions ───────────────────────────────────────────
+
+    /// Serves fixed rows and records actions instead of carrying them out,
+    /// so a scripted session never touches a real process or container.
+    struct Scripted {
+        rows: Mutex<Vec<PortInfo>>,
+        sent: Mutex<Vec<(u32, Signal)>>,
+        docker: Mutex<Vec<String>>,
+    }
+
+    impl PortCollector for Scripted {
+        fn name(&self) -> &'static str {
+            "scripted"
+        }
+
+        fn capabilities(&self) -> collector::Capabilities {
+            collector::Capabilities {
+                supports_remote_addr: false,
+                supports_udp_state: false,
+                needs_privileges: false,
+            }
+        }
+
+        fn port_infos(&self, filter_listening: bool) -> Vec<PortInfo> {
+            let rows = self.rows.lock().unwrap();
+            rows.iter()
+                .filter(|i| !filter_listening || i.state == TcpState::Listen)
+                .cloned()
+                .collect()
+        }
+
+        fn connections(&self) -> Vec<crate::ConnInfo> {
+            Vec::new()
+        }
+    }
+
+    impl ActionExecutor for Scripted {
+        fn signal(&self, target: &Target, signal: Signal) -> io::Result<String> {
+            self.sent.lock().unwrap().push((target.pid, signal));
+            if signal.stops_process() {
+                self.rows.lock().unwrap().retain(|i| i.pid != target.pid);
+            }
+            Ok(signal.name())
+        }
+
+        fn docker_action(&self, action: &str, container: &str) -> String {
+            self.docker
+                .lock()
+                .unwrap()
+                .push(format!("{} {}", action, container));
+            format!("docker {} {}: OK", action, container)
+        }
+
+        fn docker_logs(&self, container: &str) -> String {
+            self.docker_action("logs", container);
+            "listening on :80".to_string()
+        }
+
+        fn compose_restart(&self, compose: &ComposeService) -> String {
+            self.docker_action("compose restart", &compose.service)
+        }
+    }
+
+    /// The app `portview` starts with no flags, reading from `rows`.
+    fn scripted_app(rows: Vec<PortInfo>) -> (App, &'static Scripted) {
+        let source: &'static Scripted = Box::leak(Box::new(Scripted {
+            rows: Mutex::new(rows),
+            sent: Mutex::new(Vec::new()),
+            docker: Mutex::new(Vec::new()),
+        }));
+        let opts = TuiOptions {
+            target: None,
+            show_all: false,
+            wide: false,
+            force: false,
+            no_color: true,
+            docker: false,
+            dns: false,
+            cwd: false,
+            runtime: false,
+            docker_refresh: docker::PORT_MAP_REFRESH,
+            tcp_stats: false,
+            notify: &[],
+            hooks: &EventHooks::default(),
+            alerts: &[],
+            alert_notify: false,
+            bell: false,
+            title: false,
+            record: None,
+            replay: None,
+            remembered: None,
+        };
+        (
+            App::new(opts, StyleConfig::default(), source, source),
+            source,
+        )
+    }
+
+    /// Feed `script` through the event path. Words that name a key as the
+    /// `[keys]` config does (`Enter`, `Esc`, `Down`, `d`) are pressed;
+    /// anything else is typed a character at a time.
+    fn drive(app: &mut App, script: &str) {
+        for word in script.split_whitespace() {
+            let codes = match crate::keymap::parse_key(word) {
+                Ok(code) => vec![code],
+                Err(_) => word.chars().map(KeyCode::Char).collect(),
+            };
+            for code in codes {
+                handle_event(app, Event::Key(KeyEvent::new(code, KeyModifiers::NONE)));
+            }
+        }
+    }
+
+    fn visible(app: &App) -> Vec<u16> {
+        app.sorted_ports().iter().map(|i| i.port).collect()
+    }
+
+    #[test]
+    fn scripted_filter_and_kill() {
+        let (mut app, source) = scripted_app(vec![
+            make_port_info(3000, "node", "node server.js"),
+            make_port_info(5432, "postgres", "postgres"),
+            make_port_info(8080, "python3", "python3 -m http.server"),
+        ]);
+        assert_eq!(visible(&app), [3000, 5432, 8080]);
+
+        drive(&mut app, "/ python Enter");
+        assert_eq!(visible(&app), [8080]);
+        drive(&mut app, "d");
+        assert!(matches!(app.popup, Some(Popup::Kill(ref p)) if p.pid == 808000));
+        assert!(source.sent.lock().unwrap().is_empty());
+
+        drive(&mut app, "y");
+        assert_eq!(*source.sent.lock().unwrap(), [(808000, Signal::Term)]);
+        assert!(app.popup.is_none());
+        assert!(app
+            .status_message
+            .as_ref()
+            .is_some_and(|(m, _)| m == "Sent SIGTERM to PID 808000"));
+        // The refresh after the kill no longer finds the row
+        assert!(visible(&app).is_empty());
+        drive(
+            &mut app,
+            "/ Backspace Backspace Backspace Backspace Backspace Backspace Enter",
+        );
+        assert_eq!(visible(&app), [3000, 5432]);
+    }
+
+    #[test]
+    fn scripted_cancel_and_sort_send_nothing() {
+        let (mut app, source) = scripted_app(vec![
+            make_port_info(3000, "node", "node server.js"),
+            PortInfo {
+                memory_bytes: 512 * 1024 * 1024,
+                ..make_port_info(5432, "postgres", "postgres")
+            },
+        ]);
+        // Reverse the port sort, so the first row is now 5432
+        drive(&mut app, "r D");
+        assert!(
+            matches!(app.popup, Some(Popup::Kill(ref p)) if p.pid == 543200 && p.signal == Signal::Kill)
+        );
+        drive(&mut app, "Esc Down d n");
+        assert!(app.popup.is_none());
+        assert!(source.sent.lock().unwrap().is_empty());
+
+        drive(&mut app, "q");
+        assert!(app.should_quit);
+    }
+
+    #[test]
+    fn scripted_force_kill_of_another_users_process_wants_the_port() {
+        let (mut app, source) = scripted_app(vec![
+            make_port_info(5432, "postgres", "postgres").user("postgres-not-me")
+        ]);
+        app.confirm.kill = false;
+        drive(&mut app, "D");
+        assert!(matches!(app.popup, Some(Popup::Kill(ref p))
+            if p.type_back == Some(TypeBack::PortOrPid)));
+        // y no longer confirms, and neither does a wrong number
+        drive(&mut app, "y Enter 5433 Enter");
+        assert!(app.popup.is_some());
+        assert!(source.sent.lock().unwrap().is_empty());
+
+        drive(&mut app, "Backspace 2 Enter");
+        assert_eq!(*source.sent.lock().unwrap(), [(543200, Signal::Kill)]);
+        assert!(app.popup.is_none());
+    }
+
+    #[test]
+    fn scripted_docker_actions_go_through_the_executor() {
+        let (mut app, source) = scripted_app(vec![PortInfo {
+            pid: 0,
+            ..make_port_info(8080, "web", "nginx:latest")
+        }]);
+        drive(&mut app, "d");
+        assert!(matches!(app.popup, Some(Popup::Docker(_))));
+        drive(&mut app, "Enter d Down Down Enter");
+        assert_eq!(*source.docker.lock().unwrap(), ["stop web", "logs web"]);
+        assert!(app
+            .status_message
+            .as_ref()
+            .is_some_and(|(m, _)| m == "Logs: listening on :80"));
+        assert!(source.sent.lock().unwrap().is_empty());
+    }
+
+    #[test]
+    fn scripted_bell_rings_when_a_listener_goes() {
+        let (mut app, source) = scripted_app(vec![
+            make_port_info(3000, "node", "node server.js"),
+            make_port_info(5432, "postgres", "postgres"),
+        ]);
+        app.bell = true;
+        app.refresh_data();
+        assert!(!app.bell_request, "the first refresh is the baseline");
+
+        source.rows.lock().unwrap().retain(|i| i.port != 3000);
+        app.refresh_data();
+        assert!(std::mem::take(&mut app.bell_request));
+        assert!(app.bell_at.is_some());
+        assert!(app
+            .status_message
+            .as_ref()
+            .is_some_and(|(m, _)| m.contains("3000")));
+
+        app.refresh_data();
+        assert!(!app.bell_request, "nothing changed since");
+    }
+
+    // ── Render snapshots ────────────────────────────────────────────
+
+    /// The whole screen as text, one string per row with trailing spaces
+    /// kept so column widths show. The footer clock is masked.
+    fn screen(app: &mut App, width: u16, height: u16) -> Vec<String> {
+        let mut terminal = Terminal::new(TestBackend::new(width, height)).unwrap();
+        terminal.draw(|frame| render(frame, app)).unwrap();
+        let buffer = terminal.backend().buffer();
+        (0..height)
+            .map(|y| {
+                let row: String = (0..width).map(|x| buffer[(x, y)].symbol()).collect();
+                match row.find("Updated ") {
+                    Some(at) => {
+                        let clock = at + "Updated ".len();
+                        format!("{}--:--:--{}", &row[..clock], &row[clock + 8..])
+                    }
+                    None => row,
+                }
+            })
+            .collect()
+    }
+
+    fn snapshot_app() -> App {
+        let mut app = make_test_app(vec![
+            PortInfo {
+                start_time: None,
+                ..make_port_info(3000, "node", "node server.js --port 3000")
+            },
+            PortInfo {
+                start_time: None,
+                memory_bytes: 48 * 1024 * 1024,
+                ..make_port_info(5432, "postgres", "postgres -D /var/lib/postgresql/data")
+            },
+        ]);
+        app.table_state.select(Some(0));
+        app
+    }
+
+    /// Compare a rendered screen line by line, printing both on mismatch
+    /// so a layout change is easy to read off the test output.
+    fn assert_screen(actual: &[String], expected: &[&str]) {
+        assert!(
+            actual.len() == expected.len() && actual.iter().zip(expected).all(|(a, e)| a == e),
+            "screen differs\n--- expected\n{}\n--- actual\n{}",
+            expected.join("\n"),
+            actual.join("\n")
+        );
+    }
+
+    /// A border line without the fill after its title, so long lines
+    /// can be compared without spelling out every `─`.
+    fn border_text(line: &str) -> &str {
+        line.trim_end_matches(['─', '╮', '╯'])
+    }
+
+    #[test]
+    fn table_snapshot_with_selection() {
+        let mut app = snapshot_app();
+        assert_screen(
+            &screen(&mut app, 100, 5),
+            &[
+                "╭ portview  2 ports ───────────────────────────────────────────────────────────────────────────────╮",
+                "│  PORT ▲ PROTO PID     USER     PROCESS    UPTIME   MEM      NET           COMMAND                │",
+                "││ 3000   TCP   300000  test     node              -     1 MB             - node server.js --port …│",
+                "│  5432   TCP   543200  test     postgres          -    48 MB             - postgres -D /var/lib/p…│",
+                "╰ j/k move  Enter inspect  d/D/K action  / filter  s/n/N search  y copy  e export  </>/r sort  a al╯",
+            ],
+        );
+    }
+
+    #[test]
+    fn restricted_title_and_grayed_partial_rows() {
+        let mut app = snapshot_app();
+        app.ports[1].memory_bytes = 0;
+        app.visibility = Visibility::Restricted;
+        app.hidden_listeners = 3;
+        let mut terminal = Terminal::new(TestBackend::new(100, 5)).unwrap();
+        terminal.draw(|frame| render(frame, &mut app)).unwrap();
+        let lines = screen(&mut app, 100, 5);
+        assert_eq!(
+            border_text(&lines[0]),
+            "╭ portview  2 ports [restricted: 3 hidden] "
+        );
+        // PID cells: the full row plain, the one without stats dimmed
+        let buffer = terminal.backend().buffer();
+        assert!(!buffer[(16, 2)].modifier.contains(Modifier::DIM));
+        assert!(buffer[(16, 3)].modifier.contains(Modifier::DIM));
+
+        app.visibility = Visibility::Elevated;
+        let lines = screen(&mut app, 100, 5);
+        let badge = if cfg!(windows) { "[admin]" } else { "[root]" };
+        assert_eq!(
+            border_text(&lines[0]),
+            format!("╭ portview  2 ports {} ", badge)
+        );
+    }
+
+    #[test]
+    fn table_snapshot_gives_the_highlight_width_to_commands() {
+        // Nothing selected: no highlight symbol, so COMMAND gets its two
+        // columns back and the truncation point moves with it
+        let mut app = snapshot_app();
+        app.table_state.select(None);
+        assert_screen(
+            &screen(&mut app, 100, 5),
+            &[
+                "╭ portview  2 ports ───────────────────────────────────────────────────────────────────────────────╮",
+                "│PORT ▲ PROTO PID     USER     PROCESS    UPTIME   MEM      NET           COMMAND                  │",
+                "│3000   TCP   300000  test     node              -     1 MB             - node server.js --port 30…│",
+                "│5432   TCP   543200  test     postgres          -    48 MB             - postgres -D /var/lib/pos…│",
+                "╰ j/k move  Enter inspect  d/D/K action  / filter  s/n/N search  y copy  e export  </>/r sort  a al╯",
+            ],
+        );
+    }
+
+    #[test]
+    fn title_and_footer_snapshot() {
+        let mut app = snapshot_app();
+        app.show_all = true;
+        app.search_text = "post".to_string();
+        app.table_state.select(Some(1));
+        let lines = screen(&mut app, 180, 4);
+        assert_eq!(
+            border_text(&lines[0]),
+            "╭ portview  2 ports (all) [search: post 1/1] "
+        );
+        assert_eq!(
+            border_text(&lines[3]),
+            "╰ j/k move  Enter inspect  d/D/K action  / filter  s/n/N search  y copy  e export  \
+             </>/r sort  a all  c cwd  t runtime  w command  v split  ? help  q quit  Updated --:--:-- "
+        );
+
+        app.mode = AppMode::FilterInput;
+        app.filter_text = "5".to_string();
+        assert_screen(
+            &screen(&mut app, 80, 4),
+            &[
+                "╭ portview  1 port (all) [filter: 5] [search: post 1 found] ───────────────────╮",
+                "│  PORT ▲ PROTO PID     USER     PROCESS    UPTIME   MEM      NET           COM│",
+                "││ 5432   TCP   543200  test     postgres          -    48 MB             - pos│",
+                "╰ /5█  Enter apply  Esc cancel ────────────────────────────────────────────────╯",
+            ],
+        );
+    }
+
+    #[test]
+    fn detail_snapshot() {
+        let mut app = snapshot_app();
+        app.detail_info = Some(app.ports[1].clone());
+        app.mode = AppMode::Detail;
+        let lines = screen(&mut app, 80, 16);
+        assert_screen(
+            &lines[1..15],
+            &[
+                "│                                                                              │",
+                "│Port 5432 (TCP) — postgres (PID 543200)                                       │",
+                "│                                                                              │",
+                "│  Bind:     *:5432                                                            │",
+                "│  Command:  postgres -D /var/lib/postgresql/data                              │",
+                "│  User:     test                                                              │",
+                "│  Started:  -                                                                 │",
+                "│  Memory:   48 MB                                                             │",
+                "│  CPU time: 1.0s                                                              │",
+                "│  Traffic:  -                                                                 │",
+                "│  Children: 0                                                                 │",
+                "│  State:    LISTEN                                                            │",
+                "│  Peers:    none                                                              │",
+                "│  Esc back  j/k/PgUp/PgDn scroll  d kill  D force kill  K signal  q quit      │",
+            ],
+        );
+    }
+
+    #[test]
+    fn kill_popup_snapshot() {
+        let mut app = snapshot_app();
+        handle_key(&mut app, KeyCode::Char('d'), KeyModifiers::NONE);
+        assert_screen(
+            &screen(&mut app, 80, 14),
+            &[
+                "╭ portview  2 ports ───────────────────────────────────────────────────────────╮",
+                "│  PORT ▲ PROTO PID     USER     PROCESS    UPTIME   MEM      NET           COM│",
+                "││ 3000   TCP   300000  test     node              -     1 MB             - nod│",
+                "│  5432   TCP╭─────────────────── Kill Process ───────────────────╮       - pos│",
+                "│            │                                                    │            │",
+                "│            │  Kill node (PID 300000) on port 3000?              │            │",
+                "│            │  user test · 1 MB                                  │            │",
+                "│            │  Signal: SIGTERM                                   │            │",
+                "│            │                                                    │            │",
+                "│            │  y/Enter confirm   n/Esc cancel                    │            │",
+                "│            ╰────────────────────────────────────────────────────╯            │",
+                "│                                                                              │",
+                "│                                                                              │",
+                "╰ j/k move  Enter inspect  d/D/K action  / filter  s/n/N search  y copy  e expo╯",
+            ],
+        );
+    }
+
+    #[test]
+    fn summary_snapshot() {
+        let mut app = snapshot_app();
+        handle_key(&mut app, KeyCode::Char('T'), KeyModifiers::NONE);
+        assert_screen(
+            &screen(&mut app, 80, 12),
+            &[
+                "╭ portview  2 ports ───────────────────────────────────────────────────────────╮",
+                "│╭ By user ────────────────────────────╮╭ By process ─────────────────────────╮│",
+                "││ test 2 ████████████████████████████ ││ node     1 ████████████████████████ ││",
+                "││                                     ││ postgres 1 ████████████████████████ ││",
+                "││                                     ││                                     ││",
+                "│╰─────────────────────────────────────╯╰─────────────────────────────────────╯│",
+                "│╭ By state ───────────────────────────╮╭ Memory 49 MB ───────────────────────╮│",
+                "││ LISTEN 2 ██████████████████████████ ││ postgres 48 MB ████████████████████ ││",
+                "││                                     ││ node      1 MB █                    ││",
+                "││                                     ││                                     ││",
+                "│╰─────────────────────────────────────╯╰─────────────────────────────────────╯│",
+                "╰ T table  a all  ? help  q quit  Updated --:--:-- ────────────────────────────╯",
+            ],
+        );
+
+        // Row keys do nothing here; T or Esc goes back to the table
+        handle_key(&mut app, KeyCode::Char('d'), KeyModifiers::NONE);
+        assert!(app.popup.is_none());
+        handle_key(&mut app, KeyCode::Esc, KeyModifiers::NONE);
+        assert!(app.mode == AppMode::Table);
+        handle_key(&mut app, KeyCode::Char('T'), KeyModifiers::NONE);
+        handle_key(&mut app, KeyCode::Char('T'), KeyModifiers::NONE);
+        assert!(app.mode == AppMode::Table);
+    }
+
+    #[test]
+    fn split_view_follows_the_selection() {
+        let mut app = snapshot_app();
+        screen(&mut app, 160, 10);
+        handle_key(&mut app, KeyCode::Char('v'), KeyModifiers::NONE);
+        let lines = screen(&mut app, 160, 10);
+        assert!(lines[0].contains("Split view on ─"), "{}", lines[0]);
+        assert!(
+            lines[2].ends_with("│Port 3000 (TCP) — node (PID 300000)                           │")
+        );
+        assert!(lines[5].contains("│  Command:  node server.js --port 3000 "));
+
+        // The pane follows the table, and the keys still go to the table
+        handle_key(&mut app, KeyCode::Char('j'), KeyModifiers::NONE);
+        let lines = screen(&mut app, 160, 10);
+        assert!(lines[2].contains("│Port 5432 (TCP) — postgres (PID 543200)"));
+        assert!(app.mode == AppMode::Table);
+
+        // Too narrow for both: the table alone, Enter for the full view
+        let lines = screen(&mut app, 120, 10);
+        assert!(!lines.iter().any(|l| l.contains("Port 5432 (TCP)")));
+        handle_key(&mut app, KeyCode::Char('v'), KeyModifiers::NONE);
+        assert!(border_text(&screen(&mut app, 120, 10)[0]).ends_with("Split view off "));
+    }
+
+    #[test]
+    fn help_popup_snapshot() {
+        let mut app = snapshot_app();
+        handle_key(&mut app, KeyCode::Char('?'), KeyModifiers::NONE);
+        let lines = screen(&mut app, 80, 30);
+        assert_screen(
+            &lines[1..5],
+            &[
+                "│  PORT ╭──────────────────────────── Keys ────────────────────────────╮    COM│",
+                "││ 3000 │                                                              │  - nod│",
+                "│  5432 │  j, ↓     Next row (scroll down in the detail view)          │  - pos│",
+                "│       │  k, ↑     Previous row (scroll up in the detail view)        │       │",
+            ],
+        );
+        assert_screen(
+            &lines[27..29],
+            &[
+                "│       │  v        Detail pane beside the table (wide terminals)      │       │",
+                "│       ╰──────────────────────────────────────────────────────────────╯       │",
+            ],
+        );
     }
 }
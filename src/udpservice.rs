@@ -0,0 +1,531 @@
+use std::collections::hash_map::RandomState;
+use std::hash::{BuildHasher, Hasher};
+use std::net::{IpAddr, Ipv4Addr, Ipv6Addr, SocketAddr, UdpSocket};
+use std::time::{Duration, Instant};
+
+use crate::{collector, json_escape, quic, PortInfo};
+
+// ── Well-known UDP services ──────────────────────────────────────────
+//
+// `--all` lists plenty of UDP sockets that mean nothing by port number
+// alone. The common ones are named from the port, and when one is
+// inspected it's asked a harmless question in its own protocol: a DNS
+// query, a DNS-SD browse for mDNS (which also lists what the host
+// advertises), an SSDP M-SEARCH. DHCP is never probed, since even a
+// DISCOVER can hand out a lease.
+
+/// How long to wait for each reply.
+const PROBE_TIMEOUT: Duration = Duration::from_millis(300);
+
+const MDNS_GROUP: Ipv4Addr = Ipv4Addr::new(224, 0, 0, 251);
+
+/// The DNS-SD meta-query listing every advertised service type.
+const SERVICE_TYPES: &str = "_services._dns-sd._udp.local";
+
+const TYPE_A: u16 = 1;
+const TYPE_PTR: u16 = 12;
+
+/// A UDP service recognised from its port.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub(crate) enum UdpService {
+    Dns,
+    DhcpServer,
+    DhcpClient,
+    Ntp,
+    Mdns,
+    Llmnr,
+    Ssdp,
+}
+
+impl UdpService {
+    pub(crate) fn from_port(port: u16) -> Option<Self> {
+        match port {
+            53 => Some(Self::Dns),
+            67 => Some(Self::DhcpServer),
+            68 => Some(Self::DhcpClient),
+            123 => Some(Self::Ntp),
+            5353 => Some(Self::Mdns),
+            5355 => Some(Self::Llmnr),
+            1900 => Some(Self::Ssdp),
+            _ => None,
+        }
+    }
+
+    /// The APP cell and `service` JSON value.
+    pub(crate) fn as_str(self) -> &'static str {
+        match self {
+            Self::Dns => "DNS",
+            Self::DhcpServer => "DHCP server",
+            Self::DhcpClient => "DHCP client",
+            Self::Ntp => "NTP",
+            Self::Mdns => "mDNS",
+            Self::Llmnr => "LLMNR",
+            Self::Ssdp => "SSDP",
+        }
+    }
+
+    /// What the service is for, in the detail view.
+    fn purpose(self) -> &'static str {
+        match self {
+            Self::Dns => "name resolution",
+            Self::DhcpServer => "hands out addresses",
+            Self::DhcpClient => "leases this host's address",
+            Self::Ntp => "time sync",
+            Self::Mdns => "Bonjour/Avahi service discovery",
+            Self::Llmnr => "Windows local name resolution",
+            Self::Ssdp => "UPnP device discovery",
+        }
+    }
+
+    fn probed(self) -> bool {
+        matches!(self, Self::Dns | Self::Mdns | Self::Ssdp)
+    }
+}
+
+/// The well-known service on a UDP row, from its port.
+pub(crate) fn identify(info: &PortInfo) -> Option<UdpService> {
+    if !info.protocol.starts_with("UDP") {
+        return None;
+    }
+    UdpService::from_port(info.port)
+}
+
+/// What a service said when probed.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub(crate) struct ServiceReply {
+    /// The SSDP `SERVER` header.
+    pub(crate) server: Option<String>,
+    /// mDNS instances as `name (_type._proto)`, or bare types with no
+    /// instance found.
+    pub(crate) advertised: Vec<String>,
+}
+
+impl ServiceReply {
+    /// `{"server":…,"advertised":[…]}`.
+    pub(crate) fn json(&self) -> String {
+        let advertised: Vec<String> = self
+            .advertised
+            .iter()
+            .map(|a| format!("\"{}\"", json_escape(a)))
+            .collect();
+        format!(
+            r#"{{"server":{},"advertised":[{}]}}"#,
+            self.server
+                .as_ref()
+                .map_or("null".to_string(), |s| format!("\"{}\"", json_escape(s))),
+            advertised.join(",")
+        )
+    }
+}
+
+/// Ask the service on a UDP row a question in its own protocol, via the
+/// active collector. `None` when it isn't probed or didn't answer.
+pub(crate) fn probe(info: &PortInfo) -> Option<ServiceReply> {
+    let service = identify(info).filter(|s| s.probed())?;
+    collector::active().probe_udp_service(info, service)
+}
+
+/// Detail view rows as (label, value).
+pub(crate) type DetailRows = Vec<(&'static str, String)>;
+
+/// The `Serves:` row, plus `Adverts:` for mDNS. QUIC wins over the port
+/// name, since anything can listen on any port.
+pub(crate) fn detail_rows(info: &PortInfo) -> DetailRows {
+    if let Some(q) = quic::detect(info) {
+        return vec![("Serves:", q.describe())];
+    }
+    let Some(service) = identify(info) else {
+        return Vec::new();
+    };
+    describe(service, probe(info).as_ref())
+}
+
+fn describe(service: UdpService, reply: Option<&ServiceReply>) -> DetailRows {
+    let mut serves = format!("{} ({})", service.as_str(), service.purpose());
+    match reply {
+        Some(reply) => {
+            if let Some(ref server) = reply.server {
+                serves.push_str(&format!(" — {}", server));
+            } else if service == UdpService::Dns {
+                serves.push_str(", answering queries");
+            }
+        }
+        None if service.probed() => serves.push_str(", no answer to a probe"),
+        None => {}
+    }
+    let mut rows = vec![("Serves:", serves)];
+    if let Some(reply) = reply.filter(|r| !r.advertised.is_empty()) {
+        rows.push(("Adverts:", reply.advertised.join(", ")));
+    }
+    rows
+}
+
+// ── Probes ───────────────────────────────────────────────────────────
+
+/// A DNS query for `names`, all of type `qtype`. mDNS takes the same
+/// shape without recursion.
+fn dns_query(id: u16, names: &[&str], qtype: u16, recursion: bool) -> Vec<u8> {
+    let mut msg = id.to_be_bytes().to_vec();
+    msg.extend(if recursion { 0x0100u16 } else { 0 }.to_be_bytes());
+    msg.extend((names.len() as u16).to_be_bytes());
+    msg.extend([0; 6]);
+    for name in names {
+        for label in name.split('.').filter(|l| !l.is_empty()) {
+            msg.push(label.len().min(63) as u8);
+            msg.extend(&label.as_bytes()[..label.len().min(63)]);
+        }
+        msg.push(0);
+        msg.extend(qtype.to_be_bytes());
+        msg.extend(1u16.to_be_bytes());
+    }
+    msg
+}
+
+/// The name at `pos`, following compression pointers, and the offset
+/// just past it.
+fn read_name(msg: &[u8], mut pos: usize) -> Option<(String, usize)> {
+    let mut labels: Vec<String> = Vec::new();
+    let mut end = None;
+    for _ in 0..64 {
+        let len = *msg.get(pos)? as usize;
+        match len {
+            0 => {
+                let name = labels.join(".");
+                return Some((name, end.unwrap_or(pos + 1)));
+            }
+            l if l & 0xc0 == 0xc0 => {
+                let target = ((l & 0x3f) << 8) | *msg.get(pos + 1)? as usize;
+                end.get_or_insert(pos + 2);
+                pos = target;
+            }
+            l => {
+                let label = msg.get(pos + 1..pos + 1 + l)?;
+                labels.push(String::from_utf8_lossy(label).into_owned());
+                pos += 1 + l;
+            }
+        }
+    }
+    None
+}
+
+/// PTR records (owner, target) from a response to query `id`, or `None`
+/// if it isn't one.
+fn parse_ptrs(msg: &[u8], id: u16) -> Option<Vec<(String, String)>> {
+    let header = msg.get(..12)?;
+    if header[..2] != id.to_be_bytes() || header[2] & 0x80 == 0 {
+        return None;
+    }
+    let count = |i: usize| u16::from_be_bytes([header[i], header[i + 1]]) as usize;
+    let mut pos = 12;
+    for _ in 0..count(4) {
+        pos = read_name(msg, pos)?.1 + 4;
+    }
+    let mut ptrs = Vec::new();
+    for _ in 0..count(6) + count(8) + count(10) {
+        let (owner, after) = read_name(msg, pos)?;
+        let fixed = msg.get(after..after + 10)?;
+        let rtype = u16::from_be_bytes([fixed[0], fixed[1]]);
+        let rdlen = u16::from_be_bytes([fixed[8], fixed[9]]) as usize;
+        let rdata = after + 10;
+        if rtype == TYPE_PTR {
+            ptrs.push((owner, read_name(msg, rdata)?.0));
+        }
+        pos = rdata + rdlen;
+    }
+    Some(ptrs)
+}
+
+fn query_id() -> u16 {
+    RandomState::new().build_hasher().finish() as u16
+}
+
+fn unspecified(addr: &SocketAddr) -> SocketAddr {
+    match addr {
+        SocketAddr::V4(_) => (Ipv4Addr::UNSPECIFIED, 0).into(),
+        SocketAddr::V6(_) => (Ipv6Addr::UNSPECIFIED, 0).into(),
+    }
+}
+
+/// Send `packet` to every address and return the first reply `accept`
+/// takes.
+fn exchange<T>(
+    targets: &[SocketAddr],
+    packet: &[u8],
+    accept: impl Fn(&[u8]) -> Option<T>,
+) -> Option<T> {
+    let socket = UdpSocket::bind(unspecified(targets.first()?)).ok()?;
+    for target in targets {
+        let _ = socket.send_to(packet, target);
+    }
+    let deadline = Instant::now() + PROBE_TIMEOUT;
+    let mut buf = [0u8; 9000];
+    loop {
+        let left = deadline.checked_duration_since(Instant::now())?;
+        socket.set_read_timeout(Some(left)).ok()?;
+        let (len, _) = socket.recv_from(&mut buf).ok()?;
+        if let Some(found) = accept(&buf[..len]) {
+            return Some(found);
+        }
+    }
+}
+
+/// Where to reach a listener: its own address, or loopback for a
+/// wildcard bind.
+fn local_target(info: &PortInfo) -> SocketAddr {
+    let ip = match info.local_addr {
+        IpAddr::V4(a) if a.is_unspecified() => IpAddr::V4(Ipv4Addr::LOCALHOST),
+        IpAddr::V6(a) if a.is_unspecified() => IpAddr::V6(Ipv6Addr::LOCALHOST),
+        ip => ip,
+    };
+    SocketAddr::new(ip, info.port)
+}
+
+/// The address this host sends multicast from. mDNS responders usually
+/// ignore the loopback interface, so a wildcard listener is asked there
+/// too.
+fn multicast_source() -> Option<IpAddr> {
+    let socket = UdpSocket::bind((Ipv4Addr::UNSPECIFIED, 0)).ok()?;
+    socket.connect((MDNS_GROUP, 5353)).ok()?;
+    let ip = socket.local_addr().ok()?.ip();
+    (!ip.is_unspecified()).then_some(ip)
+}
+
+/// `instance (_type._proto)`, from a PTR `target` under `service_type`.
+fn instance_name(service_type: &str, target: &str) -> String {
+    let short = service_type.trim_end_matches(".local");
+    match target.strip_suffix(service_type) {
+        Some(instance) if !instance.is_empty() => {
+            format!("{} ({})", instance.trim_end_matches('.'), short)
+        }
+        _ => short.to_string(),
+    }
+}
+
+/// Browse the responder with legacy unicast queries (RFC 6762 §6.7): the
+/// service types first, then the instances of each.
+fn probe_mdns(info: &PortInfo) -> Option<ServiceReply> {
+    let mut targets = vec![local_target(info)];
+    if info.local_addr.is_unspecified() {
+        targets.extend(multicast_source().map(|ip| SocketAddr::new(ip, info.port)));
+    }
+    let id = query_id();
+    let types = exchange(
+        &targets,
+        &dns_query(id, &[SERVICE_TYPES], TYPE_PTR, false),
+        |reply| parse_ptrs(reply, id),
+    )?;
+    let mut types: Vec<String> = types
+        .into_iter()
+        .filter(|(owner, _)| owner.eq_ignore_ascii_case(SERVICE_TYPES))
+        .map(|(_, target)| target)
+        .collect();
+    types.sort();
+    types.dedup();
+    if types.is_empty() {
+        return Some(ServiceReply::default());
+    }
+
+    let names: Vec<&str> = types.iter().map(String::as_str).collect();
+    let id = query_id();
+    let instances = exchange(&targets, &dns_query(id, &names, TYPE_PTR, false), |reply| {
+        parse_ptrs(reply, id)
+    })
+    .unwrap_or_default();
+    let mut advertised = Vec::new();
+    for service_type in &types {
+        let found: Vec<String> = instances
+            .iter()
+            .filter(|(owner, _)| owner.eq_ignore_ascii_case(service_type))
+            .map(|(_, target)| instance_name(service_type, target))
+            .collect();
+        if found.is_empty() {
+            advertised.push(instance_name(service_type, service_type));
+        } else {
+            advertised.extend(found);
+        }
+    }
+    advertised.dedup();
+    Some(ServiceReply {
+        server: None,
+        advertised,
+    })
+}
+
+/// Any well-formed answer to an `A localhost` query counts.
+fn probe_dns(info: &PortInfo) -> Option<ServiceReply> {
+    let id = query_id();
+    exchange(
+        &[local_target(info)],
+        &dns_query(id, &["localhost"], TYPE_A, true),
+        |reply| parse_ptrs(reply, id).map(|_| ServiceReply::default()),
+    )
+}
+
+/// The `SERVER` header of an SSDP response.
+fn ssdp_server(reply: &[u8]) -> Option<String> {
+    let text = std::str::from_utf8(reply).ok()?;
+    if !text.starts_with("HTTP/1.1 200") {
+        return None;
+    }
+    text.lines()
+        .filter_map(|l| l.split_once(':'))
+        .find(|(k, _)| k.trim().eq_ignore_ascii_case("server"))
+        .map(|(_, v)| v.trim().to_string())
+}
+
+/// A unicast M-SEARCH (UPnP 1.1); answered straight away, with no MX
+/// delay.
+fn probe_ssdp(info: &PortInfo) -> Option<ServiceReply> {
+    let search = "M-SEARCH * HTTP/1.1\r\nHOST: 239.255.255.250:1900\r\nMAN: \"ssdp:discover\"\r\nMX: 1\r\nST: ssdp:all\r\n\r\n";
+    exchange(&[local_target(info)], search.as_bytes(), |reply| {
+        ssdp_server(reply).map(|server| ServiceReply {
+            server: Some(server),
+            advertised: Vec::new(),
+        })
+    })
+}
+
+/// Probe a UDP service on the local host.
+pub(crate) fn probe_local(info: &PortInfo, service: UdpService) -> Option<ServiceReply> {
+    match service {
+        UdpService::Dns => probe_dns(info),
+        UdpService::Mdns => probe_mdns(info),
+        UdpService::Ssdp => probe_ssdp(info),
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn services_by_port() {
+        assert_eq!(UdpService::from_port(5353), Some(UdpService::Mdns));
+        assert_eq!(UdpService::from_port(68).unwrap().as_str(), "DHCP client");
+        assert_eq!(UdpService::from_port(5354), None);
+        assert!(!UdpService::DhcpServer.probed());
+    }
+
+    fn encode(name: &str) -> Vec<u8> {
+        let query = dns_query(0, &[name], 0, false);
+        query[12..query.len() - 4].to_vec()
+    }
+
+    /// The response to `query` with PTR `answers`; an instance target is
+    /// compressed against its owner name.
+    fn ptr_response(query: &[u8], answers: &[(&str, &str)]) -> Vec<u8> {
+        let mut msg = query.to_vec();
+        msg[2] |= 0x84;
+        msg[6..8].copy_from_slice(&(answers.len() as u16).to_be_bytes());
+        for (owner, target) in answers {
+            let owner_at = msg.len();
+            msg.extend(encode(owner));
+            msg.extend(TYPE_PTR.to_be_bytes());
+            msg.extend([0, 1, 0, 0, 0x11, 0x94]);
+            let rdata = match target.strip_suffix(owner) {
+                Some(instance) => {
+                    let label = instance.trim_end_matches('.');
+                    let mut rdata = vec![label.len() as u8];
+                    rdata.extend(label.as_bytes());
+                    rdata.extend([0xc0 | (owner_at >> 8) as u8, owner_at as u8]);
+                    rdata
+                }
+                None => encode(target),
+            };
+            msg.extend((rdata.len() as u16).to_be_bytes());
+            msg.extend(rdata);
+        }
+        msg
+    }
+
+    #[test]
+    fn parse_compressed_ptr_answers() {
+        let query = dns_query(0x1234, &["_ssh._tcp.local"], TYPE_PTR, false);
+        assert_eq!(&query[..6], &[0x12, 0x34, 0, 0, 0, 1]);
+        let reply = ptr_response(&query, &[("_ssh._tcp.local", "laptop._ssh._tcp.local")]);
+        let ptrs = parse_ptrs(&reply, 0x1234).unwrap();
+        assert_eq!(
+            ptrs,
+            vec![(
+                "_ssh._tcp.local".to_string(),
+                "laptop._ssh._tcp.local".to_string()
+            )]
+        );
+        assert_eq!(instance_name(&ptrs[0].0, &ptrs[0].1), "laptop (_ssh._tcp)");
+        // Another query's answer, or the query itself
+        assert_eq!(parse_ptrs(&reply, 0x4321), None);
+        assert_eq!(parse_ptrs(&query, 0x1234), None);
+        // A pointer loop ends instead of spinning
+        assert_eq!(read_name(&[0xc0, 0], 0), None);
+    }
+
+    #[test]
+    fn ssdp_server_header() {
+        let reply = b"HTTP/1.1 200 OK\r\nCACHE-CONTROL: max-age=1800\r\nServer: Linux/6.1 UPnP/1.0 MiniDLNA/1.3.3\r\n\r\n";
+        assert_eq!(
+            ssdp_server(reply).as_deref(),
+            Some("Linux/6.1 UPnP/1.0 MiniDLNA/1.3.3")
+        );
+        assert_eq!(ssdp_server(b"M-SEARCH * HTTP/1.1\r\n\r\n"), None);
+    }
+
+    #[test]
+    fn rows_describe_the_reply() {
+        let reply = ServiceReply {
+            server: None,
+            advertised: vec!["laptop (_ssh._tcp)".to_string(), "_http._tcp".to_string()],
+        };
+        assert_eq!(
+            describe(UdpService::Mdns, Some(&reply)),
+            vec![
+                (
+                    "Serves:",
+                    "mDNS (Bonjour/Avahi service discovery)".to_string()
+                ),
+                ("Adverts:", "laptop (_ssh._tcp), _http._tcp".to_string()),
+            ]
+        );
+        assert_eq!(
+            describe(UdpService::Dns, None)[0].1,
+            "DNS (name resolution), no answer to a probe"
+        );
+        assert_eq!(
+            describe(UdpService::DhcpServer, None)[0].1,
+            "DHCP server (hands out addresses)"
+        );
+        assert_eq!(
+            reply.json(),
+            r#"{"server":null,"advertised":["laptop (_ssh._tcp)","_http._tcp"]}"#
+        );
+    }
+
+    #[test]
+    fn browse_a_local_responder() {
+        let server = UdpSocket::bind("127.0.0.1:0").unwrap();
+        let port = server.local_addr().unwrap().port();
+        let responder = std::thread::spawn(move || {
+            let mut buf = [0u8; 1500];
+            for answers in [
+                [(SERVICE_TYPES, "_ssh._tcp.local")],
+                [("_ssh._tcp.local", "laptop._ssh._tcp.local")],
+            ] {
+                let (len, from) = server.recv_from(&mut buf).unwrap();
+                server
+                    .send_to(&ptr_response(&buf[..len], &answers), from)
+                    .unwrap();
+            }
+        });
+        let info = PortInfo::listener(port, 1, "avahi-daemon")
+            .protocol("UDP")
+            .command("")
+            .user("")
+            .addr(IpAddr::V4(Ipv4Addr::LOCALHOST));
+        let reply = probe_mdns(&info);
+        responder.join().unwrap();
+        assert_eq!(
+            reply.unwrap().advertised,
+            vec!["laptop (_ssh._tcp)".to_string()]
+        );
+    }
+}
@@ -0,0 +1,115 @@
+use std::sync::OnceLock;
+
+// ── Memory units ─────────────────────────────────────────────────────
+
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub(crate) enum ByteUnits {
+    /// Powers of 1024 labelled KB/MB/GB, as portview has always shown.
+    #[default]
+    Jedec,
+    /// Powers of 1024 labelled KiB/MiB/GiB.
+    Binary,
+    /// Powers of 1000 labelled kB/MB/GB, matching disk and network tools.
+    Decimal,
+    /// The raw byte count.
+    Bytes,
+}
+
+/// How memory sizes are shown in tables, detail views, templates and the
+/// TUI. JSON always carries raw bytes.
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub(crate) struct ByteFormat {
+    pub(crate) units: ByteUnits,
+    /// Two decimals at every scale instead of rounding to whole KB/MB.
+    pub(crate) exact: bool,
+}
+
+impl ByteFormat {
+    /// `binary`, `decimal,exact`, `bytes` … as given to `--units` or
+    /// `PORTVIEW_UNITS`.
+    pub(crate) fn parse(s: &str) -> Result<Self, String> {
+        let mut format = Self::default();
+        for part in s.split(',').map(str::trim).filter(|p| !p.is_empty()) {
+            match part {
+                "jedec" => format.units = ByteUnits::Jedec,
+                "binary" | "iec" => format.units = ByteUnits::Binary,
+                "decimal" | "si" => format.units = ByteUnits::Decimal,
+                "bytes" | "raw" => format.units = ByteUnits::Bytes,
+                "exact" => format.exact = true,
+                other => {
+                    return Err(format!(
+                        "unknown unit '{}' (expected jedec, binary, decimal, bytes or exact)",
+                        other
+                    ))
+                }
+            }
+        }
+        Ok(format)
+    }
+
+    pub(crate) fn format(&self, bytes: u64) -> String {
+        if bytes == 0 {
+            return "-".to_string();
+        }
+        let (base, labels): (f64, [&str; 3]) = match self.units {
+            ByteUnits::Jedec => (1024.0, ["KB", "MB", "GB"]),
+            ByteUnits::Binary => (1024.0, ["KiB", "MiB", "GiB"]),
+            ByteUnits::Decimal => (1000.0, ["kB", "MB", "GB"]),
+            ByteUnits::Bytes => return format!("{} B", bytes),
+        };
+        let value = bytes as f64;
+        let (scaled, label, precision) = if value >= base * base * base {
+            (value / (base * base * base), labels[2], 1)
+        } else if value >= base * base {
+            (value / (base * base), labels[1], 0)
+        } else if value >= base {
+            (value / base, labels[0], 0)
+        } else {
+            return format!("{} B", bytes);
+        };
+        let precision = if self.exact { 2 } else { precision };
+        format!("{:.*} {}", precision, scaled, label)
+    }
+}
+
+static ACTIVE: OnceLock<ByteFormat> = OnceLock::new();
+
+/// Set the format for the rest of the run; the first call wins.
+pub(crate) fn install(format: ByteFormat) {
+    let _ = ACTIVE.set(format);
+}
+
+pub(crate) fn active() -> ByteFormat {
+    ACTIVE.get().copied().unwrap_or_default()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_units_and_exact() {
+        assert_eq!(
+            ByteFormat::parse("decimal, exact").unwrap(),
+            ByteFormat {
+                units: ByteUnits::Decimal,
+                exact: true
+            }
+        );
+        assert_eq!(ByteFormat::parse("").unwrap(), ByteFormat::default());
+        assert!(ByteFormat::parse("furlongs").is_err());
+    }
+
+    #[test]
+    fn format_each_unit() {
+        let fmt = |units, exact| ByteFormat { units, exact };
+        let bytes = 89_128_960;
+        assert_eq!(fmt(ByteUnits::Jedec, false).format(bytes), "85 MB");
+        assert_eq!(fmt(ByteUnits::Binary, false).format(bytes), "85 MiB");
+        assert_eq!(fmt(ByteUnits::Decimal, false).format(bytes), "89 MB");
+        assert_eq!(fmt(ByteUnits::Bytes, false).format(bytes), "89128960 B");
+        assert_eq!(fmt(ByteUnits::Binary, true).format(1536), "1.50 KiB");
+        assert_eq!(fmt(ByteUnits::Decimal, true).format(999), "999 B");
+        assert_eq!(fmt(ByteUnits::Bytes, true).format(0), "-");
+    }
+}
@@ -0,0 +1,171 @@
+use std::time::{Duration, Instant};
+
+use crate::signals::Signal;
+use crate::{collector, TcpState};
+
+// ── Post-kill verification ───────────────────────────────────────────
+//
+// "Sent SIGTERM" only says the signal was delivered. After a kill the CLI
+// polls for a few seconds (the TUI checks on every loop turn instead of
+// blocking) and reports whether the process actually went away and the
+// port came free. Sockets left in TIME_WAIT are mentioned, since they
+// are what a quick restart without SO_REUSEADDR trips over.
+
+/// How long to wait for a killed process to let go.
+pub(crate) const TIMEOUT: Duration = Duration::from_secs(3);
+
+/// What a kill left behind, at one moment.
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) struct Verdict {
+    /// `None` when the backend can't tell whether the process lives.
+    pub(crate) exited: Option<bool>,
+    /// The killed ports something still listens on.
+    pub(crate) still_bound: Vec<u16>,
+    /// Sockets in TIME_WAIT on the killed ports.
+    pub(crate) time_wait: usize,
+}
+
+impl Verdict {
+    /// Nothing left to wait for: the process is gone (or can't be seen)
+    /// and its ports are free.
+    pub(crate) fn settled(&self) -> bool {
+        self.exited != Some(false) && self.still_bound.is_empty()
+    }
+
+    /// `PID 4242 exited; port 3000 is free`, or what's still holding on.
+    pub(crate) fn describe(&self, pid: u32, ports: &[u16], signal: Signal) -> String {
+        let ports_text = port_list(ports);
+        let mut text = match (self.exited, self.still_bound.is_empty()) {
+            (Some(false), _) => {
+                let mut text = format!("PID {} is still running after {}s", pid, TIMEOUT.as_secs());
+                if signal != Signal::Kill {
+                    text.push_str(" (it may be shutting down slowly; force-kill if it hangs)");
+                }
+                text
+            }
+            (Some(true), true) => format!("PID {} exited; {} free", pid, ports_text),
+            (None, true) => format!("{} free", capitalize(&ports_text)),
+            (Some(true), false) => format!(
+                "PID {} exited, but {} still bound (by a child or another process?)",
+                pid,
+                port_list(&self.still_bound)
+            ),
+            (None, false) => format!(
+                "{} still bound after {}s",
+                capitalize(&port_list(&self.still_bound)),
+                TIMEOUT.as_secs()
+            ),
+        };
+        if self.time_wait > 0 {
+            text.push_str(&format!(
+                " ({} socket{} in TIME_WAIT)",
+                self.time_wait,
+                if self.time_wait == 1 { "" } else { "s" }
+            ));
+        }
+        text
+    }
+}
+
+/// `port 3000 is` or `ports 3000, 3001 are`.
+fn port_list(ports: &[u16]) -> String {
+    let list: Vec<String> = ports.iter().map(|p| p.to_string()).collect();
+    if ports.len() == 1 {
+        format!("port {} is", list[0])
+    } else {
+        format!("ports {} are", list.join(", "))
+    }
+}
+
+fn capitalize(text: &str) -> String {
+    let mut chars = text.chars();
+    match chars.next() {
+        Some(first) => first.to_uppercase().chain(chars).collect(),
+        None => String::new(),
+    }
+}
+
+/// Look once.
+pub(crate) fn check(pid: u32, ports: &[u16]) -> Verdict {
+    let collector = collector::active();
+    let listening = collector.listening_ports();
+    let time_wait = collector
+        .connections()
+        .iter()
+        .filter(|c| c.state == TcpState::TimeWait && ports.contains(&c.local_port))
+        .count();
+    Verdict {
+        exited: collector.process_alive(pid).map(|alive| !alive),
+        still_bound: ports
+            .iter()
+            .copied()
+            .filter(|p| listening.contains(p))
+            .collect(),
+        time_wait,
+    }
+}
+
+/// Poll every killed process until all have settled or [`TIMEOUT`]
+/// passes, and return the last look at each.
+pub(crate) fn wait(targets: &[(u32, Vec<u16>)]) -> Vec<Verdict> {
+    let deadline = Instant::now() + TIMEOUT;
+    loop {
+        let verdicts: Vec<Verdict> = targets
+            .iter()
+            .map(|(pid, ports)| check(*pid, ports))
+            .collect();
+        if verdicts.iter().all(Verdict::settled) || Instant::now() >= deadline {
+            return verdicts;
+        }
+        std::thread::sleep(Duration::from_millis(100));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn verdict_messages() {
+        let gone = Verdict {
+            exited: Some(true),
+            still_bound: vec![],
+            time_wait: 2,
+        };
+        assert!(gone.settled());
+        assert_eq!(
+            gone.describe(4242, &[3000], Signal::Term),
+            "PID 4242 exited; port 3000 is free (2 sockets in TIME_WAIT)"
+        );
+
+        let hung = Verdict {
+            exited: Some(false),
+            still_bound: vec![3000],
+            time_wait: 0,
+        };
+        assert!(!hung.settled());
+        assert!(hung
+            .describe(4242, &[3000], Signal::Term)
+            .starts_with("PID 4242 is still running after 3s"));
+
+        let inherited = Verdict {
+            exited: Some(true),
+            still_bound: vec![3001],
+            time_wait: 0,
+        };
+        assert_eq!(
+            inherited.describe(7, &[3000, 3001], Signal::Kill),
+            "PID 7 exited, but port 3001 is still bound (by a child or another process?)"
+        );
+
+        let unknown = Verdict {
+            exited: None,
+            still_bound: vec![],
+            time_wait: 0,
+        };
+        assert_eq!(
+            unknown.describe(7, &[3000, 3001], Signal::Kill),
+            "Ports 3000, 3001 are free"
+        );
+    }
+}
@@ -0,0 +1,573 @@
+use std::collections::{BTreeSet, HashSet};
+use std::io::{self, Write};
+
+use crate::docker::{DockerPortOwner, PortForwarder};
+use crate::ephemeral::LocalPorts;
+use crate::{format_addr, json_escape, write_styled, ConnInfo, PortInfo, TcpState};
+
+// ── Findings ─────────────────────────────────────────────────────────
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub(crate) enum Severity {
+    /// A bind on this port will fail.
+    Blocking,
+    /// A bind may fail depending on how the new server sets its socket options.
+    Warning,
+    /// Context only.
+    Info,
+}
+
+impl Severity {
+    fn as_str(self) -> &'static str {
+        match self {
+            Severity::Blocking => "blocking",
+            Severity::Warning => "warning",
+            Severity::Info => "info",
+        }
+    }
+
+    fn icon(self) -> (&'static str, &'static str) {
+        match self {
+            Severity::Blocking => ("✗", "red"),
+            Severity::Warning => ("!", "yellow"),
+            Severity::Info => ("○", "dimmed"),
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub(crate) struct Finding {
+    pub(crate) severity: Severity,
+    pub(crate) message: String,
+}
+
+impl Finding {
+    fn new(severity: Severity, message: String) -> Self {
+        Self { severity, message }
+    }
+}
+
+/// Context about the caller that affects whether a bind can succeed.
+pub(crate) struct BindContext {
+    /// True when this user needs elevated privileges to bind the port.
+    pub(crate) needs_privilege: bool,
+    /// Windows port exclusion ranges that cover the port.
+    pub(crate) excluded: Vec<ExcludedRange>,
+    /// The ephemeral range and reserved ports, where the backend knows them.
+    pub(crate) local_ports: Option<LocalPorts>,
+}
+
+// ── Windows port exclusions ──────────────────────────────────────────
+//
+// Hyper-V, WSL2 and Docker Desktop have WinNAT reserve blocks of ports at
+// boot. Nothing listens on them, yet a bind fails with WSAEACCES, so the
+// port looks free and still can't be used.
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub(crate) struct ExcludedRange {
+    pub(crate) protocol: &'static str,
+    pub(crate) start: u16,
+    pub(crate) end: u16,
+    /// Added by an administrator (`*` in netsh) rather than reserved by
+    /// Hyper-V or WinNAT.
+    pub(crate) administered: bool,
+}
+
+impl ExcludedRange {
+    pub(crate) fn contains(&self, port: u16) -> bool {
+        (self.start..=self.end).contains(&port)
+    }
+}
+
+/// The ranges in `netsh int ipv4 show excludedportrange` output. Headers
+/// are localized, so only the number rows are read.
+fn parse_excluded_ranges(protocol: &'static str, output: &str) -> Vec<ExcludedRange> {
+    output
+        .lines()
+        .filter_map(|line| {
+            let mut fields = line.split_whitespace();
+            let start = fields.next()?.parse().ok()?;
+            let end = fields.next()?.parse().ok()?;
+            Some(ExcludedRange {
+                protocol,
+                start,
+                end,
+                administered: fields.next() == Some("*"),
+            })
+        })
+        .collect()
+}
+
+/// The exclusion ranges covering `port`, for TCP and UDP; always empty
+/// outside Windows.
+pub(crate) fn excluded_ranges(port: u16) -> Vec<ExcludedRange> {
+    [("tcp", "TCP"), ("udp", "UDP")]
+        .into_iter()
+        .flat_map(|(arg, name)| parse_excluded_ranges(name, &netsh_exclusions(arg)))
+        .filter(|r| r.contains(port))
+        .collect()
+}
+
+#[cfg(windows)]
+fn netsh_exclusions(protocol: &str) -> String {
+    let output = std::process::Command::new("netsh")
+        .args(["int", "ipv4", "show", "excludedportrange"])
+        .arg(format!("protocol={}", protocol))
+        .output();
+    match output {
+        Ok(out) if out.status.success() => String::from_utf8_lossy(&out.stdout).into_owned(),
+        _ => String::new(),
+    }
+}
+
+#[cfg(not(windows))]
+fn netsh_exclusions(_protocol: &str) -> String {
+    String::new()
+}
+
+fn exclusion_finding(port: u16, range: &ExcludedRange) -> Finding {
+    let span = if range.start == range.end {
+        range.start.to_string()
+    } else {
+        format!("{}-{}", range.start, range.end)
+    };
+    let message = if range.administered {
+        format!(
+            "Port {} is in an administered {} port exclusion ({}). Binds fail with access denied (WSAEACCES) even though nothing listens. Remove it with `netsh int ipv4 delete excludedportrange protocol={} startport={} numberofports={}` as administrator.",
+            port,
+            range.protocol,
+            span,
+            range.protocol.to_lowercase(),
+            range.start,
+            u32::from(range.end) - u32::from(range.start) + 1
+        )
+    } else {
+        format!(
+            "Port {} is in a {} range Windows has reserved ({}), usually for Hyper-V, WSL2 or Docker Desktop. Binds fail with access denied (WSAEACCES) even though nothing listens. Pick a port outside it, or restart WinNAT as administrator (`net stop winnat` then `net start winnat`) and reserve the port first with `netsh int ipv4 add excludedportrange protocol={} startport={} numberofports=1`.",
+            port,
+            range.protocol,
+            span,
+            range.protocol.to_lowercase(),
+            port
+        )
+    };
+    Finding::new(Severity::Blocking, message)
+}
+
+fn plural(n: usize) -> &'static str {
+    if n == 1 {
+        ""
+    } else {
+        "s"
+    }
+}
+
+/// Explain, in plain language, why binding `port` might fail right now.
+pub(crate) fn explain(
+    port: u16,
+    infos: &[PortInfo],
+    conns: &[ConnInfo],
+    docker_owners: Option<&[DockerPortOwner]>,
+    ctx: &BindContext,
+) -> Vec<Finding> {
+    let mut findings = Vec::new();
+    let owners: Vec<&PortInfo> = infos.iter().filter(|i| i.port == port).collect();
+    let tcp: Vec<&ConnInfo> = conns
+        .iter()
+        .filter(|c| c.local_port == port && c.protocol == "TCP")
+        .collect();
+
+    // 1. Direct owners: a LISTEN socket or a bound UDP socket
+    for info in owners.iter().filter(|i| i.state == TcpState::Listen) {
+        let bind = format!("{}:{}", format_addr(&info.local_addr), port);
+        let scope = if info.local_addr.is_loopback() {
+            " Even though it's loopback-only, binding the wildcard address on the same port will still fail."
+        } else {
+            ""
+        };
+        if let Some(via) = PortForwarder::detect(info) {
+            let container = docker_owners
+                .and_then(|o| o.first())
+                .map(|o| format!(" for container '{}'", o.container_name))
+                .unwrap_or_default();
+            let holder = if via == PortForwarder::Proxy {
+                "proxy"
+            } else {
+                "VM"
+            };
+            findings.push(Finding::new(
+                Severity::Blocking,
+                format!(
+                    "{} (PID {}) is publishing {}{}. Stop the container instead of killing the {}.",
+                    via.label(),
+                    info.pid,
+                    bind,
+                    container,
+                    holder
+                ),
+            ));
+        } else if info.pid == 0 {
+            findings.push(Finding::new(
+                Severity::Blocking,
+                format!(
+                    "Docker container '{}' publishes {}/{}.",
+                    info.process_name, port, info.protocol
+                ),
+            ));
+        } else {
+            findings.push(Finding::new(
+                Severity::Blocking,
+                format!(
+                    "{} (PID {}, user {}) is {} on {}/{}.{}",
+                    info.process_name,
+                    info.pid,
+                    info.user,
+                    if info.protocol == "UDP" {
+                        "bound"
+                    } else {
+                        "listening"
+                    },
+                    bind,
+                    info.protocol,
+                    scope
+                ),
+            ));
+        }
+    }
+
+    // 2. Docker publishes the port but no host process is visible (VM-based Docker)
+    if let Some(docker) = docker_owners {
+        let visible_proxy = owners
+            .iter()
+            .any(|i| i.pid == 0 || PortForwarder::detect(i).is_some());
+        if !visible_proxy {
+            for owner in docker {
+                findings.push(Finding::new(
+                    Severity::Blocking,
+                    format!(
+                        "Docker container '{}' ({}) publishes {} -> {}/{}.",
+                        owner.container_name,
+                        owner.image,
+                        port,
+                        owner.container_port,
+                        owner.protocol
+                    ),
+                ));
+            }
+        }
+    }
+
+    let count_state =
+        |states: &[TcpState]| tcp.iter().filter(|c| states.contains(&c.state)).count();
+    let has_listener = tcp.iter().any(|c| c.state == TcpState::Listen);
+
+    // 3. TIME_WAIT: the classic "nothing is listening but bind fails"
+    let time_wait = count_state(&[TcpState::TimeWait]);
+    if time_wait > 0 {
+        findings.push(Finding::new(
+            Severity::Warning,
+            format!(
+                "{} socket{} in TIME_WAIT on :{}. Servers that don't set SO_REUSEADDR get EADDRINUSE until these expire (usually within 60s).",
+                time_wait,
+                plural(time_wait),
+                port
+            ),
+        ));
+    }
+
+    // 4. Connections still being torn down
+    let closing = count_state(&[
+        TcpState::FinWait1,
+        TcpState::FinWait2,
+        TcpState::Closing,
+        TcpState::LastAck,
+    ]);
+    if closing > 0 {
+        findings.push(Finding::new(
+            Severity::Warning,
+            format!(
+                "{} connection{} on :{} still closing (FIN_WAIT/CLOSING/LAST_ACK). They hold the port until the peer finishes the shutdown.",
+                closing,
+                plural(closing),
+                port
+            ),
+        ));
+    }
+
+    // 5. CLOSE_WAIT: the local app never closed its side
+    let close_wait = count_state(&[TcpState::CloseWait]);
+    if close_wait > 0 {
+        findings.push(Finding::new(
+            Severity::Warning,
+            format!(
+                "{} connection{} in CLOSE_WAIT on :{}{}. The peer hung up but the process never closed its socket — usually a leak in the app.",
+                close_wait,
+                plural(close_wait),
+                port,
+                owning_pids_suffix(&tcp, &[TcpState::CloseWait])
+            ),
+        ));
+    }
+
+    // 6. Accepted connections outliving their listener (e.g. inherited by a child)
+    let established = count_state(&[TcpState::Established]);
+    if established > 0 && !has_listener {
+        findings.push(Finding::new(
+            Severity::Warning,
+            format!(
+                "{} established connection{} still use :{} with no listener{}. A restarted server without SO_REUSEADDR will fail to bind.",
+                established,
+                plural(established),
+                port,
+                owning_pids_suffix(&tcp, &[TcpState::Established])
+            ),
+        ));
+    }
+
+    // 7. Reserved by Windows (Hyper-V, WSL2, WinNAT)
+    for range in &ctx.excluded {
+        findings.push(exclusion_finding(port, range));
+    }
+
+    // 8. Ephemeral range: outgoing connections can take the port
+    if let Some((warn, message)) = ctx.local_ports.as_ref().and_then(|l| l.explain(port)) {
+        let severity = if warn {
+            Severity::Warning
+        } else {
+            Severity::Info
+        };
+        findings.push(Finding::new(severity, message));
+    }
+
+    // 9. Privileged ports
+    if ctx.needs_privilege {
+        findings.push(Finding::new(
+            Severity::Warning,
+            format!(
+                "Port {} is privileged. Binding it needs root (or CAP_NET_BIND_SERVICE on Linux).",
+                port
+            ),
+        ));
+    }
+
+    if findings.iter().all(|f| f.severity == Severity::Info) {
+        findings.push(Finding::new(
+            Severity::Info,
+            format!(
+                "Nothing visible is using port {}. A bind should succeed; if it doesn't, the owner may be in another network namespace, container, or VM, or hidden from this user (try sudo).",
+                port
+            ),
+        ));
+    }
+
+    findings
+}
+
+fn owning_pids_suffix(conns: &[&ConnInfo], states: &[TcpState]) -> String {
+    let pids: BTreeSet<u32> = conns
+        .iter()
+        .filter(|c| states.contains(&c.state))
+        .filter_map(|c| c.pid)
+        .collect();
+    if pids.is_empty() {
+        return String::new();
+    }
+    let list: Vec<String> = pids.iter().map(|p| p.to_string()).collect();
+    format!(" (PID {})", list.join(", "))
+}
+
+// ── Output ───────────────────────────────────────────────────────────
+
+pub(crate) fn display_findings(port: u16, findings: &[Finding], use_color: bool) {
+    let mut out = io::stdout();
+    let _ = writeln!(out);
+    if use_color {
+        write_styled(&mut out, "Why port ", "bold", true);
+        write_styled(&mut out, &port.to_string(), "cyan", true);
+        write_styled(&mut out, " may fail to bind", "bold", true);
+        let _ = writeln!(out);
+    } else {
+        let _ = writeln!(out, "Why port {} may fail to bind", port);
+    }
+
+    let mut seen = HashSet::new();
+    for finding in findings {
+        if !seen.insert(finding.message.clone()) {
+            continue;
+        }
+        let (icon, color) = finding.severity.icon();
+        let _ = write!(out, "  ");
+        write_styled(&mut out, icon, color, use_color);
+        let _ = writeln!(out, " {}", finding.message);
+    }
+}
+
+pub(crate) fn findings_json(port: u16, findings: &[Finding]) -> String {
+    let items: Vec<String> = findings
+        .iter()
+        .map(|f| {
+            format!(
+                r#"{{"severity":"{}","message":"{}"}}"#,
+                f.severity.as_str(),
+                json_escape(&f.message)
+            )
+        })
+        .collect();
+    let blocked = findings.iter().any(|f| f.severity == Severity::Blocking);
+    format!(
+        r#"{{"port":{},"blocked":{},"findings":[{}]}}"#,
+        port,
+        blocked,
+        items.join(",")
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::net::{IpAddr, Ipv4Addr};
+
+    fn listener(port: u16, name: &str, addr: IpAddr) -> PortInfo {
+        PortInfo::listener(port, 42, name).user("me").addr(addr)
+    }
+
+    fn conn(port: u16, state: TcpState, pid: Option<u32>) -> ConnInfo {
+        ConnInfo {
+            protocol: "TCP".to_string(),
+            local_port: port,
+            remote_addr: IpAddr::V4(Ipv4Addr::new(10, 0, 0, 2)),
+            remote_port: 50000,
+            state,
+            pid,
+        }
+    }
+
+    const CTX: BindContext = BindContext {
+        needs_privilege: false,
+        excluded: Vec::new(),
+        local_ports: None,
+    };
+
+    #[test]
+    fn explain_nothing_on_port() {
+        let findings = explain(3000, &[], &[], None, &CTX);
+        assert_eq!(findings.len(), 1);
+        assert_eq!(findings[0].severity, Severity::Info);
+    }
+
+    #[test]
+    fn explain_listener_is_blocking() {
+        let infos = [listener(3000, "node", IpAddr::V4(Ipv4Addr::LOCALHOST))];
+        let findings = explain(3000, &infos, &[], None, &CTX);
+        assert_eq!(findings[0].severity, Severity::Blocking);
+        assert!(findings[0].message.contains("node (PID 42"));
+        assert!(findings[0].message.contains("loopback-only"));
+    }
+
+    #[test]
+    fn explain_time_wait_without_listener() {
+        let conns = [
+            conn(3000, TcpState::TimeWait, None),
+            conn(3000, TcpState::TimeWait, None),
+            conn(3001, TcpState::TimeWait, None),
+        ];
+        let findings = explain(3000, &[], &conns, None, &CTX);
+        assert_eq!(findings.len(), 1);
+        assert_eq!(findings[0].severity, Severity::Warning);
+        assert!(findings[0].message.starts_with("2 sockets in TIME_WAIT"));
+    }
+
+    #[test]
+    fn explain_close_wait_names_pid() {
+        let conns = [conn(3000, TcpState::CloseWait, Some(7))];
+        let findings = explain(3000, &[], &conns, None, &CTX);
+        assert!(findings[0].message.contains("CLOSE_WAIT"));
+        assert!(findings[0].message.contains("(PID 7)"));
+    }
+
+    #[test]
+    fn explain_docker_proxy() {
+        let infos = [listener(
+            8080,
+            "docker-proxy",
+            IpAddr::V4(Ipv4Addr::UNSPECIFIED),
+        )];
+        let owners = [DockerPortOwner {
+            container_id: "abc".to_string(),
+            container_name: "web".to_string(),
+            image: "nginx".to_string(),
+            container_port: 80,
+            protocol: "TCP".to_string(),
+            state: "running".to_string(),
+            health: None,
+            stats: None,
+            compose: None,
+        }];
+        let findings = explain(8080, &infos, &[], Some(&owners), &CTX);
+        assert_eq!(findings.len(), 1);
+        assert!(findings[0].message.contains("container 'web'"));
+    }
+
+    #[test]
+    fn explain_privileged_port() {
+        let ctx = BindContext {
+            needs_privilege: true,
+            excluded: Vec::new(),
+            local_ports: None,
+        };
+        let findings = explain(80, &[], &[], None, &ctx);
+        assert!(findings[0].message.contains("privileged"));
+    }
+
+    #[test]
+    fn explain_windows_port_exclusion() {
+        let output = "\r\nProtocol tcp Port Exclusion Ranges\r\n\r\nStart Port    End Port\r\n----------    --------\r\n      5357        5357\r\n     49709       49808\r\n     50000       50059     *\r\n\r\n* - Administered port exclusions.\r\n";
+        let ranges = parse_excluded_ranges("TCP", output);
+        assert_eq!(ranges.len(), 3);
+        assert!(ranges[2].administered && !ranges[1].administered);
+        let ctx = BindContext {
+            needs_privilege: false,
+            excluded: ranges.into_iter().filter(|r| r.contains(49710)).collect(),
+            local_ports: None,
+        };
+        let findings = explain(49710, &[], &[], None, &ctx);
+        assert_eq!(findings.len(), 1);
+        assert_eq!(findings[0].severity, Severity::Blocking);
+        assert!(findings[0].message.contains("(49709-49808)"));
+        assert!(findings[0].message.contains("net stop winnat"));
+    }
+
+    #[test]
+    fn explain_ephemeral_port() {
+        let ctx = BindContext {
+            needs_privilege: false,
+            excluded: Vec::new(),
+            local_ports: Some(LocalPorts {
+                ephemeral: (32768, 60999),
+                reserved: vec![(45000, 45000)],
+            }),
+        };
+        let findings = explain(40000, &[], &[], None, &ctx);
+        assert_eq!(findings.len(), 1);
+        assert_eq!(findings[0].severity, Severity::Warning);
+        assert!(findings[0]
+            .message
+            .contains("ip_local_reserved_ports=45000,40000"));
+
+        // Reserved: context, plus the usual all-clear
+        let findings = explain(45000, &[], &[], None, &ctx);
+        assert_eq!(findings.len(), 2);
+        assert!(findings.iter().all(|f| f.severity == Severity::Info));
+        assert!(findings[1].message.starts_with("Nothing visible"));
+        assert_eq!(explain(8080, &[], &[], None, &ctx).len(), 1);
+    }
+
+    #[test]
+    fn findings_json_shape() {
+        let findings = vec![Finding::new(Severity::Blocking, "a \"b\"".to_string())];
+        assert_eq!(
+            findings_json(1, &findings),
+            r#"{"port":1,"blocked":true,"findings":[{"severity":"blocking","message":"a \"b\""}]}"#
+        );
+    }
+}
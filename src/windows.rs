@@ -1,29 +1,69 @@
-use std::collections::HashMap;
+use std::collections::hash_map::DefaultHasher;
+use std::collections::{HashMap, HashSet};
+use std::hash::{Hash, Hasher};
 use std::net::{IpAddr, Ipv4Addr, Ipv6Addr};
+use std::sync::{mpsc, Mutex, OnceLock};
 use std::time::{Duration, SystemTime, UNIX_EPOCH};
 
 use windows_sys::Win32::Foundation::{
-    CloseHandle, ERROR_INSUFFICIENT_BUFFER, HANDLE, INVALID_HANDLE_VALUE,
+    CloseHandle, GetLastError, ERROR_ACCESS_DENIED, ERROR_INSUFFICIENT_BUFFER, HANDLE,
+    INVALID_HANDLE_VALUE, STILL_ACTIVE,
 };
 use windows_sys::Win32::NetworkManagement::IpHelper::{
     GetExtendedTcpTable, GetExtendedUdpTable, MIB_TCP6ROW_OWNER_PID, MIB_TCP6TABLE_OWNER_PID,
     MIB_TCPROW_OWNER_PID, MIB_TCPTABLE_OWNER_PID, MIB_UDP6ROW_OWNER_PID, MIB_UDP6TABLE_OWNER_PID,
-    MIB_UDPROW_OWNER_PID, MIB_UDPTABLE_OWNER_PID, TCP_TABLE_OWNER_PID_ALL, UDP_TABLE_OWNER_PID,
+    MIB_UDPROW_OWNER_PID, MIB_UDPTABLE_OWNER_PID, TCP_TABLE_OWNER_PID_ALL,
+    TCP_TABLE_OWNER_PID_LISTENER, UDP_TABLE_OWNER_PID,
 };
 use windows_sys::Win32::Networking::WinSock::{AF_INET, AF_INET6};
 use windows_sys::Win32::Security::{
-    GetTokenInformation, LookupAccountSidW, TokenUser, TOKEN_QUERY, TOKEN_USER,
+    GetLengthSid, GetTokenInformation, LookupAccountNameW, LookupAccountSidW, TokenUser,
+    TOKEN_QUERY, TOKEN_USER,
+};
+use windows_sys::Win32::Storage::FileSystem::{
+    CreateFileW, FILE_READ_ATTRIBUTES, FILE_SHARE_READ, FILE_SHARE_WRITE, OPEN_EXISTING,
+};
+use windows_sys::Win32::System::Diagnostics::Etw::{
+    CloseTrace, ControlTraceW, EnableTraceEx2, OpenTraceW, ProcessTrace, StartTraceW,
+    CONTROLTRACE_HANDLE, EVENT_CONTROL_CODE_ENABLE_PROVIDER, EVENT_RECORD,
+    EVENT_TRACE_CONTROL_STOP, EVENT_TRACE_LOGFILEW, EVENT_TRACE_PROPERTIES,
+    EVENT_TRACE_REAL_TIME_MODE, PROCESS_TRACE_MODE_EVENT_RECORD, PROCESS_TRACE_MODE_REAL_TIME,
+    TRACE_LEVEL_INFORMATION, WNODE_FLAG_TRACED_GUID,
 };
 use windows_sys::Win32::System::Diagnostics::ToolHelp::{
     CreateToolhelp32Snapshot, Process32FirstW, Process32NextW, PROCESSENTRY32W, TH32CS_SNAPPROCESS,
 };
+use windows_sys::Win32::System::Pipes::GetNamedPipeServerProcessId;
 use windows_sys::Win32::System::ProcessStatus::{K32GetProcessMemoryInfo, PROCESS_MEMORY_COUNTERS};
 use windows_sys::Win32::System::Threading::{
-    GetProcessTimes, OpenProcess, OpenProcessToken, QueryFullProcessImageNameW,
-    PROCESS_QUERY_INFORMATION, PROCESS_VM_READ,
+    GetExitCodeProcess, GetPriorityClass, GetProcessTimes, OpenProcess, OpenProcessToken,
+    QueryFullProcessImageNameW, ABOVE_NORMAL_PRIORITY_CLASS, BELOW_NORMAL_PRIORITY_CLASS,
+    HIGH_PRIORITY_CLASS, IDLE_PRIORITY_CLASS, NORMAL_PRIORITY_CLASS, PROCESS_QUERY_INFORMATION,
+    PROCESS_QUERY_LIMITED_INFORMATION, PROCESS_VM_READ, REALTIME_PRIORITY_CLASS,
 };
 
-use crate::{PortInfo, TcpState};
+use crate::backlog::ListenQueue;
+use crate::bandwidth::SocketTraffic;
+use crate::collector::Capabilities;
+use crate::doctor::Check;
+use crate::ephemeral::LocalPorts;
+use crate::orphan::Lineage;
+use crate::pipes::NamedPipe;
+use crate::refresh::ListenerFeed;
+use crate::sockopts::SocketOptions;
+use crate::{ConnInfo, PortInfo, ProcessDetail, TcpState};
+
+// ── Backend ──────────────────────────────────────────────────────────
+
+pub const BACKEND_NAME: &str = "IP Helper";
+
+/// Windows reads the IP Helper tables; UDP sockets are always reported as
+/// bound.
+pub const CAPABILITIES: Capabilities = Capabilities {
+    supports_remote_addr: true,
+    supports_udp_state: false,
+    needs_privileges: true,
+};
 
 // ── Socket enumeration ──────────────────────────────────────────────
 
@@ -31,6 +71,8 @@ struct RawSocket {
     protocol: String,
     local_addr: IpAddr,
     local_port: u16,
+    remote_addr: IpAddr,
+    remote_port: u16,
     state: TcpState,
     pid: u32,
 }
@@ -82,10 +124,19 @@ fn get_tcp4_sockets() -> Vec<RawSocket> {
             addr_bytes[2],
             addr_bytes[3],
         ));
+        let remote_bytes = row.dwRemoteAddr.to_ne_bytes();
+        let remote_addr = IpAddr::V4(Ipv4Addr::new(
+            remote_bytes[0],
+            remote_bytes[1],
+            remote_bytes[2],
+            remote_bytes[3],
+        ));
         sockets.push(RawSocket {
             protocol: "TCP".to_string(),
             local_addr: addr,
             local_port: port,
+            remote_addr,
+            remote_port: u16::from_be((row.dwRemotePort & 0xFFFF) as u16),
             state: TcpState::from_mib(row.dwState),
             pid: row.dwOwningPid,
         });
@@ -137,6 +188,8 @@ fn get_tcp6_sockets() -> Vec<RawSocket> {
             protocol: "TCP".to_string(),
             local_addr: addr,
             local_port: port,
+            remote_addr: IpAddr::V6(Ipv6Addr::from(row.ucRemoteAddr)),
+            remote_port: u16::from_be((row.dwRemotePort & 0xFFFF) as u16),
             state: TcpState::from_mib(row.dwState),
             pid: row.dwOwningPid,
         });
@@ -194,6 +247,8 @@ fn get_udp4_sockets() -> Vec<RawSocket> {
             protocol: "UDP".to_string(),
             local_addr: addr,
             local_port: port,
+            remote_addr: IpAddr::V4(Ipv4Addr::UNSPECIFIED),
+            remote_port: 0,
             state: TcpState::Listen, // UDP has no state — treat bound as listening
             pid: row.dwOwningPid,
         });
@@ -245,6 +300,8 @@ fn get_udp6_sockets() -> Vec<RawSocket> {
             protocol: "UDP".to_string(),
             local_addr: addr,
             local_port: port,
+            remote_addr: IpAddr::V6(Ipv6Addr::UNSPECIFIED),
+            remote_port: 0,
             state: TcpState::Listen,
             pid: row.dwOwningPid,
         });
@@ -363,6 +420,17 @@ fn get_process_username(handle: HANDLE) -> String {
 
     let token_user = buf.as_ptr() as *const TOKEN_USER;
     let sid = unsafe { (*token_user).User.Sid };
+    unsafe { CloseHandle(token) };
+
+    // LookupAccountSidW can go to a domain controller, so each account is
+    // looked up once per run
+    static NAMES: OnceLock<Mutex<HashMap<Vec<u8>, String>>> = OnceLock::new();
+    let names = NAMES.get_or_init(|| Mutex::new(HashMap::new()));
+    let key = unsafe { std::slice::from_raw_parts(sid as *const u8, GetLengthSid(sid) as usize) }
+        .to_vec();
+    if let Some(name) = names.lock().ok().and_then(|n| n.get(&key).cloned()) {
+        return name;
+    }
 
     let mut name_buf = [0u16; 256];
     let mut name_len = name_buf.len() as u32;
@@ -382,13 +450,15 @@ fn get_process_username(handle: HANDLE) -> String {
         )
     };
 
-    unsafe { CloseHandle(token) };
-
-    if ret != 0 && name_len > 0 {
+    let name = if ret != 0 && name_len > 0 {
         String::from_utf16_lossy(&name_buf[..name_len as usize])
     } else {
         String::new()
+    };
+    if let Ok(mut names) = names.lock() {
+        names.insert(key, name.clone());
     }
+    name
 }
 
 fn build_child_count_map() -> HashMap<u32, u32> {
@@ -417,8 +487,116 @@ fn build_child_count_map() -> HashMap<u32, u32> {
     children_count
 }
 
+/// Windows has no zombies or sessions in the Unix sense.
+pub fn get_lineage(_pid: u32) -> Option<Lineage> {
+    None
+}
+
+/// Winsock options can only be read through a handle in the owning
+/// process, so they aren't available for other processes' sockets.
+pub fn get_socket_options(_info: &PortInfo) -> Option<SocketOptions> {
+    None
+}
+
+/// Access denied still means the process is there; otherwise an open
+/// handle must not have an exit code yet.
+pub fn process_alive(pid: u32) -> bool {
+    let handle = unsafe { OpenProcess(PROCESS_QUERY_LIMITED_INFORMATION, 0, pid) };
+    if handle.is_null() {
+        return unsafe { GetLastError() } == ERROR_ACCESS_DENIED;
+    }
+    let mut code = 0u32;
+    let ok = unsafe { GetExitCodeProcess(handle, &mut code) };
+    unsafe { CloseHandle(handle) };
+    ok != 0 && code == STILL_ACTIVE as u32
+}
+
+pub fn get_child_pids(pid: u32) -> Vec<u32> {
+    let mut pids = Vec::new();
+
+    let snapshot = unsafe { CreateToolhelp32Snapshot(TH32CS_SNAPPROCESS, 0) };
+    if snapshot == INVALID_HANDLE_VALUE {
+        return pids;
+    }
+
+    let mut entry: PROCESSENTRY32W = unsafe { std::mem::zeroed() };
+    entry.dwSize = std::mem::size_of::<PROCESSENTRY32W>() as u32;
+
+    if unsafe { Process32FirstW(snapshot, &mut entry) } != 0 {
+        loop {
+            // PIDs are reused, so a stale parent id can point at us
+            if entry.th32ParentProcessID == pid && entry.th32ProcessID != pid {
+                pids.push(entry.th32ProcessID);
+            }
+            if unsafe { Process32NextW(snapshot, &mut entry) } == 0 {
+                break;
+            }
+        }
+    }
+
+    unsafe { CloseHandle(snapshot) };
+    pids
+}
+
+pub fn get_parent_pid(pid: u32) -> Option<u32> {
+    let snapshot = unsafe { CreateToolhelp32Snapshot(TH32CS_SNAPPROCESS, 0) };
+    if snapshot == INVALID_HANDLE_VALUE {
+        return None;
+    }
+
+    let mut entry: PROCESSENTRY32W = unsafe { std::mem::zeroed() };
+    entry.dwSize = std::mem::size_of::<PROCESSENTRY32W>() as u32;
+
+    let mut parent = None;
+    if unsafe { Process32FirstW(snapshot, &mut entry) } != 0 {
+        loop {
+            if entry.th32ProcessID == pid {
+                parent = Some(entry.th32ParentProcessID);
+                break;
+            }
+            if unsafe { Process32NextW(snapshot, &mut entry) } == 0 {
+                break;
+            }
+        }
+    }
+
+    unsafe { CloseHandle(snapshot) };
+    parent
+}
+
 // ── Main entry point ─────────────────────────────────────────────────
 
+/// `--user` on Windows: an account the system (or its domain) knows.
+/// There are no numeric uids, so a number is looked up as a name.
+pub fn resolve_user(user: &str) -> Option<String> {
+    let wide: Vec<u16> = user.encode_utf16().chain(Some(0)).collect();
+    let mut sid = [0u8; 68]; // SECURITY_MAX_SID_SIZE
+    let mut sid_len = sid.len() as u32;
+    let mut domain = [0u16; 256];
+    let mut domain_len = domain.len() as u32;
+    let mut sid_type = 0;
+    let found = unsafe {
+        LookupAccountNameW(
+            std::ptr::null(),
+            wide.as_ptr(),
+            sid.as_mut_ptr() as *mut _,
+            &mut sid_len,
+            domain.as_mut_ptr(),
+            &mut domain_len,
+            &mut sid_type,
+        )
+    };
+    (found != 0).then(|| user.to_string())
+}
+
+/// Ports of one account's processes. Account names are case-insensitive
+/// on Windows.
+pub fn get_user_port_infos(filter_listening: bool, user: &str) -> Vec<PortInfo> {
+    let mut infos = get_port_infos(filter_listening);
+    infos.retain(|i| i.user.eq_ignore_ascii_case(user));
+    infos
+}
+
 pub fn get_port_infos(filter_listening: bool) -> Vec<PortInfo> {
     let sockets = get_all_sockets();
     let child_map = build_child_count_map();
@@ -471,6 +649,10 @@ pub fn get_port_infos(filter_listening: bool) -> Vec<PortInfo> {
             }
 
             let (name, path) = get_process_name_and_path(limited);
+            if name.is_empty() {
+                unsafe { CloseHandle(limited) };
+                continue;
+            }
             let (start_time, cpu_seconds) = get_process_times(limited);
             let user = get_process_username(limited);
             let children = child_map.get(&pid).copied().unwrap_or(0);
@@ -500,6 +682,11 @@ pub fn get_port_infos(filter_listening: bool) -> Vec<PortInfo> {
         }
 
         let (name, path) = get_process_name_and_path(handle);
+        if name.is_empty() {
+            // Dropped below anyway; don't look up its account
+            unsafe { CloseHandle(handle) };
+            continue;
+        }
         let memory_bytes = get_process_memory(handle);
         let (start_time, cpu_seconds) = get_process_times(handle);
         let user = get_process_username(handle);
@@ -548,6 +735,428 @@ pub fn get_port_infos(filter_listening: bool) -> Vec<PortInfo> {
     infos
 }
 
+/// Ports with a TCP listener or bound UDP socket. Reads only the socket
+/// tables without opening any process, so it's cheap to poll.
+pub fn get_listening_ports() -> HashSet<u16> {
+    get_all_sockets()
+        .into_iter()
+        .filter(|s| s.local_port != 0)
+        .filter(|s| s.state == TcpState::Listen || s.protocol == "UDP")
+        .map(|s| s.local_port)
+        .collect()
+}
+
+/// Every TCP/UDP socket on the host, including ones with no owning process.
+pub fn get_connections() -> Vec<ConnInfo> {
+    get_all_sockets()
+        .into_iter()
+        .map(|sock| ConnInfo {
+            protocol: sock.protocol,
+            local_port: sock.local_port,
+            remote_addr: sock.remote_addr,
+            remote_port: sock.remote_port,
+            state: sock.state,
+            // TIME_WAIT rows are reported against PID 0 (System Idle)
+            pid: if sock.pid == 0 { None } else { Some(sock.pid) },
+        })
+        .collect()
+}
+
+fn get_thread_count(pid: u32) -> Option<u32> {
+    let snapshot = unsafe { CreateToolhelp32Snapshot(TH32CS_SNAPPROCESS, 0) };
+    if snapshot == INVALID_HANDLE_VALUE {
+        return None;
+    }
+
+    let mut entry: PROCESSENTRY32W = unsafe { std::mem::zeroed() };
+    entry.dwSize = std::mem::size_of::<PROCESSENTRY32W>() as u32;
+
+    let mut threads = None;
+    if unsafe { Process32FirstW(snapshot, &mut entry) } != 0 {
+        loop {
+            if entry.th32ProcessID == pid {
+                threads = Some(entry.cntThreads);
+                break;
+            }
+            if unsafe { Process32NextW(snapshot, &mut entry) } == 0 {
+                break;
+            }
+        }
+    }
+
+    unsafe { CloseHandle(snapshot) };
+    threads
+}
+
+/// Map a priority class onto the Unix nice scale so both platforms read the
+/// same way.
+fn priority_class_to_nice(class: u32) -> Option<i32> {
+    match class {
+        IDLE_PRIORITY_CLASS => Some(19),
+        BELOW_NORMAL_PRIORITY_CLASS => Some(10),
+        NORMAL_PRIORITY_CLASS => Some(0),
+        ABOVE_NORMAL_PRIORITY_CLASS => Some(-5),
+        HIGH_PRIORITY_CLASS => Some(-10),
+        REALTIME_PRIORITY_CLASS => Some(-20),
+        _ => None,
+    }
+}
+
+/// The working directory lives in the target's PEB and isn't read on Windows.
+pub fn get_process_cwd(_pid: u32) -> Option<String> {
+    None
+}
+
+/// Thread count and priority for one process. The working directory and
+/// environment live in the target's PEB and aren't read here.
+pub fn get_process_detail(pid: u32) -> ProcessDetail {
+    let handle = unsafe { OpenProcess(PROCESS_QUERY_LIMITED_INFORMATION, 0, pid) };
+    let nice = if handle.is_null() {
+        None
+    } else {
+        let class = unsafe { GetPriorityClass(handle) };
+        unsafe { CloseHandle(handle) };
+        priority_class_to_nice(class)
+    };
+    ProcessDetail {
+        threads: get_thread_count(pid),
+        nice,
+        cwd: None,
+        env: Vec::new(),
+        repo: None,
+        orphan: None,
+    }
+}
+
+/// Per-socket byte counters aren't collected on Windows; throughput columns
+/// show `-`.
+pub fn get_socket_traffic() -> Vec<SocketTraffic> {
+    Vec::new()
+}
+
+/// A hash of the sorted listener and UDP tables. The listener class has
+/// Windows filter out established connections, and no process is opened,
+/// so it's cheap to check after each [`listener_changes`] wake.
+pub fn listener_signature() -> Option<u64> {
+    let mut hasher = DefaultHasher::new();
+    for family in [AF_INET as u32, AF_INET6 as u32] {
+        listener_table(family, true)?.hash(&mut hasher);
+        listener_table(family, false)?.hash(&mut hasher);
+    }
+    Some(hasher.finish())
+}
+
+// ── TCPIP event feed ─────────────────────────────────────────────────
+
+/// Microsoft-Windows-TCPIP, {2F07E2EE-15DB-40F1-90EF-9D7BA282188A}.
+const TCPIP_PROVIDER: windows_sys::core::GUID =
+    windows_sys::core::GUID::from_u128(0x2f07e2ee_15db_40f1_90ef_9d7ba282188a);
+
+/// EVENT_TRACE_PROPERTIES followed by room for the session name, as
+/// StartTrace and ControlTrace want them.
+#[repr(C)]
+struct TraceProperties {
+    props: EVENT_TRACE_PROPERTIES,
+    name: [u16; 64],
+}
+
+impl TraceProperties {
+    fn new() -> Box<Self> {
+        let mut this = Box::new(Self {
+            props: EVENT_TRACE_PROPERTIES::default(),
+            name: [0; 64],
+        });
+        this.props.Wnode.BufferSize = std::mem::size_of::<Self>() as u32;
+        this.props.Wnode.Flags = WNODE_FLAG_TRACED_GUID;
+        this.props.Wnode.ClientContext = 1; // QueryPerformanceCounter timestamps
+        this.props.LogFileMode = EVENT_TRACE_REAL_TIME_MODE;
+        // Deliver within a second rather than when a buffer fills
+        this.props.FlushTimer = 1;
+        this.props.LoggerNameOffset = std::mem::offset_of!(Self, name) as u32;
+        this
+    }
+}
+
+/// The real-time session, stopped when the watcher is dropped so it
+/// doesn't outlive portview.
+struct TraceSession {
+    handle: CONTROLTRACE_HANDLE,
+    name: Vec<u16>,
+}
+
+impl Drop for TraceSession {
+    fn drop(&mut self) {
+        let mut props = TraceProperties::new();
+        unsafe {
+            ControlTraceW(
+                self.handle,
+                self.name.as_ptr(),
+                &mut props.props,
+                EVENT_TRACE_CONTROL_STOP,
+            );
+        }
+    }
+}
+
+unsafe extern "system" fn on_tcpip_event(record: *mut EVENT_RECORD) {
+    let wakes = (*record).UserContext as *const mpsc::Sender<()>;
+    // The receiver only goes away as the session is stopped
+    let _ = (*wakes).send(());
+}
+
+/// Wakes on every event from the TCPIP provider, through an ETW real-time
+/// session of our own. Opening one needs an administrator (or a member of
+/// Performance Log Users); `None` otherwise, and watch mode refreshes on
+/// its timer. Events cover TCP endpoints; a UDP bind shows up on the
+/// next timer refresh.
+pub fn listener_changes() -> Option<ListenerFeed> {
+    let name: Vec<u16> = format!("portview-{}", std::process::id())
+        .encode_utf16()
+        .chain(Some(0))
+        .collect();
+    let mut props = TraceProperties::new();
+    let mut handle = CONTROLTRACE_HANDLE::default();
+    if unsafe { StartTraceW(&mut handle, name.as_ptr(), &mut props.props) } != 0 {
+        return None;
+    }
+    let session = TraceSession { handle, name };
+    let enabled = unsafe {
+        EnableTraceEx2(
+            handle,
+            &TCPIP_PROVIDER,
+            EVENT_CONTROL_CODE_ENABLE_PROVIDER,
+            TRACE_LEVEL_INFORMATION as u8,
+            0,
+            0,
+            0,
+            std::ptr::null(),
+        )
+    };
+    if enabled != 0 {
+        return None;
+    }
+
+    let (tx, rx) = mpsc::channel();
+    // Owned by the consumer thread, which frees it once ProcessTrace returns
+    let wakes = Box::into_raw(Box::new(tx));
+    let mut logfile = EVENT_TRACE_LOGFILEW::default();
+    let mut logger_name = session.name.clone();
+    logfile.LoggerName = logger_name.as_mut_ptr();
+    logfile.Anonymous1.ProcessTraceMode =
+        PROCESS_TRACE_MODE_REAL_TIME | PROCESS_TRACE_MODE_EVENT_RECORD;
+    logfile.Anonymous2.EventRecordCallback = Some(on_tcpip_event);
+    logfile.Context = wakes as *mut std::ffi::c_void;
+    let trace = unsafe { OpenTraceW(&mut logfile) };
+    if trace.Value == u64::MAX {
+        drop(unsafe { Box::from_raw(wakes) });
+        return None;
+    }
+    let wakes = wakes as usize;
+    std::thread::spawn(move || unsafe {
+        // Returns once the session is stopped
+        ProcessTrace(&trace, 1, std::ptr::null(), std::ptr::null());
+        CloseTrace(trace);
+        drop(Box::from_raw(wakes as *mut mpsc::Sender<()>));
+    });
+    Some(ListenerFeed::new(rx, session))
+}
+
+/// Windows doesn't expose per-listener accept queues.
+pub fn get_listen_queues() -> Vec<ListenQueue> {
+    Vec::new()
+}
+
+pub fn somaxconn() -> Option<u32> {
+    None
+}
+
+pub fn local_ports() -> Option<LocalPorts> {
+    None
+}
+
+/// Raw TCP listener (or UDP) table for `family`, sorted so the bytes only
+/// change when a socket opens or closes.
+fn listener_table(family: u32, tcp: bool) -> Option<Vec<u8>> {
+    let query = |buf: *mut std::ffi::c_void, size: &mut u32| unsafe {
+        if tcp {
+            GetExtendedTcpTable(buf, size, 1, family, TCP_TABLE_OWNER_PID_LISTENER, 0)
+        } else {
+            GetExtendedUdpTable(buf, size, 1, family, UDP_TABLE_OWNER_PID, 0)
+        }
+    };
+    let mut size: u32 = 0;
+    if query(std::ptr::null_mut(), &mut size) != ERROR_INSUFFICIENT_BUFFER {
+        return None;
+    }
+    // The table can grow between the size query and the read
+    for _ in 0..3 {
+        let mut buf = vec![0u8; size as usize];
+        match query(buf.as_mut_ptr() as *mut _, &mut size) {
+            0 => return Some(buf),
+            ERROR_INSUFFICIENT_BUFFER => continue,
+            _ => return None,
+        }
+    }
+    None
+}
+
+/// Probe the IP Helper tables and process access, for `portview doctor`.
+pub fn diagnose() -> Vec<Check> {
+    let mut checks = Vec::new();
+
+    // A size query answers ERROR_INSUFFICIENT_BUFFER when the API works
+    let mut size: u32 = 0;
+    let tcp = unsafe {
+        GetExtendedTcpTable(
+            std::ptr::null_mut(),
+            &mut size,
+            0,
+            AF_INET as u32,
+            TCP_TABLE_OWNER_PID_ALL,
+            0,
+        )
+    };
+    let mut size: u32 = 0;
+    let udp = unsafe {
+        GetExtendedUdpTable(
+            std::ptr::null_mut(),
+            &mut size,
+            0,
+            AF_INET as u32,
+            UDP_TABLE_OWNER_PID,
+            0,
+        )
+    };
+    for (name, api, ret) in [
+        ("tcp table", "GetExtendedTcpTable", tcp),
+        ("udp table", "GetExtendedUdpTable", udp),
+    ] {
+        checks.push(if ret == ERROR_INSUFFICIENT_BUFFER || ret == 0 {
+            Check::ok(name, format!("{} available", api))
+        } else {
+            Check::fail(
+                name,
+                format!("{} failed with error {}", api, ret),
+                "The IP Helper API is unavailable; check that the TCP/IP stack and the \"IP Helper\" service are running.",
+            )
+        });
+    }
+
+    let pids: HashSet<u32> = get_all_sockets()
+        .iter()
+        .map(|s| s.pid)
+        .filter(|&pid| pid != 0)
+        .collect();
+    let unreadable = pids
+        .iter()
+        .filter(|&&pid| {
+            let handle = unsafe { OpenProcess(PROCESS_QUERY_LIMITED_INFORMATION, 0, pid) };
+            if handle.is_null() {
+                return true;
+            }
+            unsafe { CloseHandle(handle) };
+            false
+        })
+        .count();
+    let detail = format!(
+        "{} of {} socket-owning processes can be opened",
+        pids.len() - unreadable,
+        pids.len()
+    );
+    checks.push(if unreadable == 0 {
+        Check::ok("process access", detail)
+    } else {
+        Check::warn(
+            "process access",
+            detail,
+            "Names and stats of protected or other users' processes are blank. Run from an elevated (Administrator) terminal.",
+        )
+    });
+    checks
+}
+
+// ── Named pipes ──────────────────────────────────────────────────────
+
+/// Every pipe under `\\.\pipe\`. Listing the directory doesn't touch the
+/// pipes; asking for the server process opens one instance for a moment,
+/// so pipes with every instance busy come back without an owner.
+pub fn list_named_pipes() -> Vec<NamedPipe> {
+    let Ok(entries) = std::fs::read_dir(r"\\.\pipe\") else {
+        return Vec::new();
+    };
+    let names = process_names();
+    entries
+        .flatten()
+        .map(|entry| {
+            let name = entry.file_name().to_string_lossy().into_owned();
+            // The pipe filesystem reports open instances as the file size
+            let instances = entry.metadata().ok().map(|m| m.len() as u32);
+            let pid = pipe_server_pid(&name);
+            let process = pid
+                .and_then(|p| names.get(&p).cloned())
+                .unwrap_or_else(|| "-".to_string());
+            NamedPipe {
+                name,
+                pid,
+                process,
+                instances,
+            }
+        })
+        .collect()
+}
+
+fn pipe_server_pid(name: &str) -> Option<u32> {
+    let path: Vec<u16> = format!(r"\\.\pipe\{}", name)
+        .encode_utf16()
+        .chain(std::iter::once(0))
+        .collect();
+    let handle = unsafe {
+        CreateFileW(
+            path.as_ptr(),
+            FILE_READ_ATTRIBUTES,
+            FILE_SHARE_READ | FILE_SHARE_WRITE,
+            std::ptr::null(),
+            OPEN_EXISTING,
+            0,
+            std::ptr::null_mut(),
+        )
+    };
+    if handle == INVALID_HANDLE_VALUE {
+        return None;
+    }
+    let mut pid = 0u32;
+    let ok = unsafe { GetNamedPipeServerProcessId(handle, &mut pid) };
+    unsafe { CloseHandle(handle) };
+    (ok != 0 && pid != 0).then_some(pid)
+}
+
+/// Executable name per PID, from one process snapshot.
+fn process_names() -> HashMap<u32, String> {
+    let mut names = HashMap::new();
+
+    let snapshot = unsafe { CreateToolhelp32Snapshot(TH32CS_SNAPPROCESS, 0) };
+    if snapshot == INVALID_HANDLE_VALUE {
+        return names;
+    }
+
+    let mut entry: PROCESSENTRY32W = unsafe { std::mem::zeroed() };
+    entry.dwSize = std::mem::size_of::<PROCESSENTRY32W>() as u32;
+
+    if unsafe { Process32FirstW(snapshot, &mut entry) } != 0 {
+        loop {
+            let exe = &entry.szExeFile;
+            let len = exe.iter().position(|&c| c == 0).unwrap_or(exe.len());
+            names.insert(entry.th32ProcessID, String::from_utf16_lossy(&exe[..len]));
+            if unsafe { Process32NextW(snapshot, &mut entry) } == 0 {
+                break;
+            }
+        }
+    }
+
+    unsafe { CloseHandle(snapshot) };
+    names
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -621,4 +1230,32 @@ mod tests {
         let expected = UNIX_EPOCH + Duration::from_secs(1);
         assert_eq!(result, Some(expected));
     }
+
+    // ── TCPIP event feed ────────────────────────────────────────────
+
+    #[test]
+    fn trace_properties_point_at_the_name() {
+        let props = TraceProperties::new();
+        let base = &*props as *const TraceProperties as usize;
+        let name = props.name.as_ptr() as usize;
+        assert_eq!(props.props.LoggerNameOffset as usize, name - base);
+        assert_eq!(
+            props.props.Wnode.BufferSize as usize,
+            std::mem::size_of::<TraceProperties>()
+        );
+    }
+
+    #[test]
+    fn tcp_listener_wakes_the_feed_and_the_session_stops() {
+        let Some(feed) = listener_changes() else {
+            return; // not elevated
+        };
+        while feed.wakes.try_recv().is_ok() {}
+        let listener = std::net::TcpListener::bind("127.0.0.1:0").unwrap();
+        feed.wakes.recv_timeout(Duration::from_secs(5)).unwrap();
+        drop(listener);
+        drop(feed);
+        // Dropping stopped the session, so the name is free again
+        assert!(listener_changes().is_some());
+    }
 }
@@ -0,0 +1,808 @@
+//! End-to-end runs of the binary against `tests/fixtures/dev.json`, so the
+//! table, filters, JSON and kill paths are covered on machines with no
+//! interesting ports.
+
+use std::process::{Command, Output};
+
+fn portview(args: &[&str]) -> Output {
+    command(args).output().expect("run portview")
+}
+
+fn command(args: &[&str]) -> Command {
+    let mut command = Command::new(env!("CARGO_BIN_EXE_portview"));
+    command.args(args).args([
+        "--fixture",
+        concat!(env!("CARGO_MANIFEST_DIR"), "/tests/fixtures/dev.json"),
+    ]);
+    command
+}
+
+fn stdout(out: &Output) -> String {
+    String::from_utf8_lossy(&out.stdout).into_owned()
+}
+
+#[test]
+fn scan_lists_listeners_only() {
+    let out = portview(&["--no-color"]);
+    assert!(out.status.success());
+    let text = stdout(&out);
+    for name in ["node", "postgres", "python3", "avahi-daemon"] {
+        assert!(text.contains(name), "missing {}:\n{}", name, text);
+    }
+    assert!(!text.contains("51234"), "established row shown:\n{}", text);
+
+    let all = stdout(&portview(&["--no-color", "--all"]));
+    assert!(all.contains("51234"));
+}
+
+#[test]
+fn session_skips_processes_from_other_shells() {
+    // The fixture records no parent processes, so nothing leads back here
+    let out = portview(&["--session", "--no-color"]);
+    assert!(out.status.success());
+    assert!(stdout(&out).contains("No listening ports opened from this shell."));
+    assert_eq!(
+        stdout(&portview(&["list", "--session", "--json"])).trim(),
+        "[]"
+    );
+}
+
+#[test]
+fn user_filter_takes_a_name_or_uid() {
+    for user in ["dev", "1000"] {
+        let out = portview(&["--user", user, "--no-color"]);
+        assert!(out.status.success(), "--user {}", user);
+        let text = stdout(&out);
+        assert!(text.contains("3000") && text.contains("8080"), "{}", text);
+        assert!(!text.contains("postgres"), "{}", text);
+    }
+    for user in ["nobody-here", "4321"] {
+        let out = portview(&["--user", user, "--no-color"]);
+        assert_eq!(out.status.code(), Some(2), "--user {}", user);
+        assert!(String::from_utf8_lossy(&out.stderr).contains("no such user"));
+    }
+}
+
+#[test]
+fn name_filter_matches_process_and_command() {
+    let text = stdout(&portview(&["python", "--no-color"]));
+    assert!(text.contains("8080"));
+    assert!(!text.contains("postgres"));
+
+    let out = portview(&["nginx", "--no-color"]);
+    assert!(!out.status.success());
+}
+
+#[test]
+fn json_output_round_trips() {
+    let text = stdout(&portview(&["--json"]));
+    assert!(text.contains(r#""port":3000,"protocol":"TCP","pid":4242,"process":"node""#));
+    assert!(text.contains(r#""port":5353,"protocol":"UDP""#));
+    assert!(
+        text.contains(r#""children":2,"runtime":"node""#),
+        "{}",
+        text
+    );
+    assert!(
+        text.contains(r#""children":1,"runtime":null,"start_time":null"#),
+        "{}",
+        text
+    );
+}
+
+#[test]
+fn ndjson_prints_one_object_per_line() {
+    let text = stdout(&portview(&["--format", "ndjson"]));
+    let lines: Vec<&str> = text.lines().collect();
+    assert_eq!(lines.len(), 4, "{}", text);
+    assert!(lines[0].starts_with(r#"{"schema_version":1,"port":3000,"#));
+    assert!(lines.iter().all(|l| l.ends_with('}')));
+    assert_eq!(
+        stdout(&portview(&["--format", "json"])),
+        stdout(&portview(&["--json"]))
+    );
+
+    // No match: nothing on stdout, the error on stderr as JSON
+    let out = portview(&["list", "nginx", "--format", "ndjson"]);
+    assert_eq!(out.status.code(), Some(1));
+    assert_eq!(stdout(&out), "");
+    assert!(String::from_utf8_lossy(&out.stderr).contains(r#""code":"no_match""#));
+}
+
+#[test]
+fn template_and_status() {
+    let text = stdout(&portview(&["--template", "{{port}}={{process}}"]));
+    assert_eq!(
+        text.lines().collect::<Vec<_>>(),
+        [
+            "3000=node",
+            "5353=avahi-daemon",
+            "5432=postgres",
+            "8080=python3"
+        ]
+    );
+
+    let out = portview(&["status", "--ports", "3000,9999"]);
+    assert_eq!(stdout(&out).trim(), "3000✓ 9999✗");
+    assert_eq!(out.status.code(), Some(1));
+}
+
+#[test]
+fn kill_is_mocked() {
+    let out = portview(&["kill", "3000", "--no-color"]);
+    assert!(out.status.success());
+    let text = stdout(&out);
+    assert!(text.contains("PID 4242"), "{}", text);
+    assert!(
+        text.contains("PID 4242 exited; port 3000 is free"),
+        "{}",
+        text
+    );
+    let restart = text.lines().find(|l| l.contains("Restart with:"));
+    assert!(
+        restart.is_some_and(|l| l.contains("/home/dev/web") && l.ends_with("node server.js")),
+        "{}",
+        text
+    );
+
+    let out = portview(&["kill", "9999"]);
+    assert_eq!(out.status.code(), Some(1));
+
+    // A reload signal leaves the process running: no restart hint
+    let out = portview(&["kill", "3000", "--signal", "hup", "--no-color"]);
+    assert!(out.status.success());
+    let text = stdout(&out);
+    assert!(text.contains("Sent SIGHUP to PID 4242"), "{}", text);
+    assert!(!text.contains("Restart with:"), "{}", text);
+
+    let out = portview(&["kill", "3000", "--signal", "BOOM"]);
+    assert_eq!(out.status.code(), Some(2));
+}
+
+#[test]
+fn batch_kill_plans_then_reports() {
+    // No terminal to confirm at: refuse without --yes
+    let out = portview(&["kill", "3000-9000", "--no-color"]);
+    assert_eq!(out.status.code(), Some(2));
+    assert!(stdout(&out).contains("PID 5100"));
+
+    let out = portview(&["kill", "3000-9000", "--yes", "--no-color"]);
+    assert!(out.status.success());
+    let text = stdout(&out);
+    assert!(
+        text.contains("Sent SIGTERM to 4 of 4 processes"),
+        "{}",
+        text
+    );
+    assert!(
+        text.contains("PID 5100 exited; port 8080 is free"),
+        "{}",
+        text
+    );
+
+    let out = portview(&["kill", "--filter", "process=node", "--yes", "--json"]);
+    assert_eq!(
+        stdout(&out).trim(),
+        r#"{"ok":true,"signal":"SIGTERM","targets":[{"pid":4242,"process":"node","ports":[3000],"status":"sent","error":null,"exited":true,"port_free":true}]}"#
+    );
+
+    let out = portview(&["kill", "--filter", "process=nope", "--yes", "--json"]);
+    assert_eq!(out.status.code(), Some(1));
+    assert!(String::from_utf8_lossy(&out.stderr).contains(r#""code":"no_match""#));
+
+    // One port resolves the same with or without --json: 51234 is only
+    // node's outbound connection
+    assert!(stdout(&portview(&["kill", "51234", "--no-color"])).contains("PID 4242"));
+    let out = portview(&["kill", "51234", "--json"]);
+    assert!(out.status.success());
+    assert!(stdout(&out).contains(r#""pid":4242"#), "{}", stdout(&out));
+}
+
+#[test]
+fn inspect_shows_socket_options() {
+    let text = stdout(&portview(&["5432", "--no-color"]));
+    assert!(
+        text.contains("Sockopts: SO_REUSEADDR on, SO_REUSEPORT off, TCP_NODELAY on, SO_KEEPALIVE on (idle 7200s, every 75s, 9 probes)"),
+        "{}",
+        text
+    );
+    assert!(
+        text.contains("Buffers:  recv 128 KB, send 16 KB"),
+        "{}",
+        text
+    );
+
+    let json = stdout(&portview(&["5432", "--json"]));
+    assert!(
+        json.contains(r#""socket_options":{"reuseaddr":true,"#),
+        "{}",
+        json
+    );
+    let json = stdout(&portview(&["8080", "--json"]));
+    assert!(json.contains(r#""socket_options":null"#), "{}", json);
+}
+
+#[test]
+fn stream_prints_fixed_columns_and_ndjson() {
+    let text = stdout(&portview(&["--stream", "--no-color"]));
+    let lines: Vec<&str> = text.lines().collect();
+    assert!(lines[0].starts_with("PORT   PROTO  PID"), "{}", text);
+    assert_eq!(lines.len(), 5, "{}", text);
+    // Columns line up without measuring the rows
+    let command_at = lines[0].find("COMMAND").unwrap();
+    assert_eq!(
+        lines[1].find("node server.js"),
+        Some(command_at),
+        "{}",
+        text
+    );
+
+    let json = stdout(&portview(&["--stream", "--json", "--all"]));
+    assert_eq!(json.lines().count(), 5);
+    assert!(json
+        .lines()
+        .all(|l| l.starts_with(r#"{"schema_version":"#) && l.ends_with('}')));
+
+    let filtered = stdout(&portview(&["--stream", "--json", "python"]));
+    assert_eq!(filtered.lines().count(), 1, "{}", filtered);
+
+    let out = portview(&["--stream", "--sort", "pid"]);
+    assert_eq!(out.status.code(), Some(2));
+}
+
+#[test]
+fn quic_listeners_are_labelled() {
+    let run = |args: &[&str]| {
+        let out = Command::new(env!("CARGO_BIN_EXE_portview"))
+            .args(args)
+            .args([
+                "--fixture",
+                concat!(env!("CARGO_MANIFEST_DIR"), "/tests/fixtures/quic.json"),
+            ])
+            .output()
+            .expect("run portview");
+        stdout(&out)
+    };
+
+    let table = run(&["--quic", "--no-color", "--plain-table"]);
+    assert!(table.lines().next().unwrap().contains("APP"), "{}", table);
+    let udp = table
+        .lines()
+        .find(|l| l.contains("UDP") && l.contains("443"));
+    assert!(udp.unwrap().contains("QUIC/HTTP3"), "{}", table);
+    // Without --quic only the well-known ports are named
+    let table = run(&["--no-color", "--plain-table"]);
+    assert!(!table.contains("QUIC/HTTP3"), "{}", table);
+    assert!(table.contains("mDNS"), "{}", table);
+
+    let detail = run(&["443", "--no-color"]);
+    assert!(
+        detail.contains("Serves:   QUIC/HTTP3 (v1, v2)"),
+        "{}",
+        detail
+    );
+    assert_eq!(detail.matches("Serves:").count(), 1);
+
+    let json = run(&["--quic", "--json"]);
+    assert!(
+        json.contains(r#""quic":{"versions":["v1","v2"]}"#),
+        "{}",
+        json
+    );
+    assert!(json.contains(r#""process":"avahi-daemon""#));
+    assert_eq!(json.matches(r#""quic""#).count(), 2);
+}
+
+#[test]
+fn well_known_udp_services_are_named() {
+    let table = stdout(&portview(&["--no-color", "--plain-table"]));
+    let mdns = table.lines().find(|l| l.contains("5353")).unwrap();
+    assert!(mdns.contains("mDNS"), "{}", table);
+
+    let detail = stdout(&portview(&["5353", "--no-color"]));
+    assert!(
+        detail.contains("Serves:   mDNS (Bonjour/Avahi service discovery)"),
+        "{}",
+        detail
+    );
+    assert!(
+        detail.contains("Adverts:  dev-laptop (_workstation._tcp), dev-laptop (_ssh._tcp)"),
+        "{}",
+        detail
+    );
+
+    let json = stdout(&portview(&["--json"]));
+    assert!(json.contains(r#""service":"mDNS""#), "{}", json);
+    assert_eq!(json.matches(r#""service""#).count(), 1);
+    let json = stdout(&portview(&["5353", "--json"]));
+    assert!(json.contains(r#""service_reply":{"server":null,"advertised":["#));
+}
+
+#[test]
+fn local_resolvers_are_explained() {
+    let run = |args: &[&str]| {
+        let out = Command::new(env!("CARGO_BIN_EXE_portview"))
+            .args(args)
+            .args([
+                "--fixture",
+                concat!(env!("CARGO_MANIFEST_DIR"), "/tests/fixtures/resolvers.json"),
+            ])
+            .output()
+            .expect("run portview");
+        stdout(&out)
+    };
+
+    let table = run(&["--no-color", "--plain-table"]);
+    assert_eq!(table.matches("DNS resolver").count(), 3, "{}", table);
+    let llmnr = table.lines().find(|l| l.contains("5355")).unwrap();
+    assert!(llmnr.contains("LLMNR"), "{}", table);
+
+    let detail = run(&["53", "--no-color"]);
+    assert!(
+        detail.contains("Resolver: systemd-resolved's stub resolver"),
+        "{}",
+        detail
+    );
+    assert!(detail.contains("DNSStubListener=no"), "{}", detail);
+    assert!(detail.contains("virsh net-destroy default"), "{}", detail);
+    let detail = run(&["3000", "--no-color"]);
+    assert!(!detail.contains("Resolver:"), "{}", detail);
+
+    let json = run(&["--json"]);
+    assert_eq!(json.matches(r#""resolver":"systemd-resolved""#).count(), 3);
+    assert!(json.contains(r#""resolver":"dnsmasq""#), "{}", json);
+}
+
+#[test]
+fn config_labels_show_in_table_json_and_detail() {
+    let labeled = |args: &[&str]| {
+        let out = command(args)
+            .env(
+                "PORTVIEW_CONFIG",
+                concat!(env!("CARGO_MANIFEST_DIR"), "/tests/fixtures/labels.toml"),
+            )
+            .output()
+            .expect("run portview");
+        stdout(&out)
+    };
+
+    let table = labeled(&["--no-color", "--plain-table"]);
+    assert!(table.lines().next().unwrap().contains("LABEL"));
+    assert!(table.contains("storefront"), "{}", table);
+
+    let json = labeled(&["--json"]);
+    assert!(json.contains(r#""label":"storefront""#), "{}", json);
+    assert_eq!(json.matches(r#""label""#).count(), 1);
+
+    assert!(labeled(&["3000", "--no-color"]).contains("Label:    storefront"));
+    assert_eq!(
+        labeled(&["--template", "{{port}} {{label}}", "5432"]),
+        "5432 -\n"
+    );
+}
+
+#[test]
+fn expect_reports_manifest_mismatches() {
+    let manifest = concat!(env!("CARGO_MANIFEST_DIR"), "/tests/fixtures/services.toml");
+    let out = portview(&["expect", manifest, "--no-color"]);
+    assert_eq!(out.status.code(), Some(1));
+    let text = stdout(&out);
+    assert!(
+        text.contains("✓ web   3000/tcp  node (pid 4242)"),
+        "{}",
+        text
+    );
+    assert!(text.contains("! api   8080/tcp  python3 (pid 5100), expected java"));
+    assert!(text.contains("✗ mail  8025/tcp  not listening"));
+    assert!(text.contains("2 of 4 services up."));
+
+    let json = stdout(&portview(&["expect", manifest, "--json"]));
+    assert!(json.starts_with(r#"{"ok":false,"services":[{"name":"web""#));
+    assert!(json.contains(r#""name":"mail","port":8025,"proto":"tcp","status":"missing""#));
+}
+
+#[test]
+fn list_subcommand_matches_bare_scan() {
+    assert_eq!(
+        stdout(&portview(&["list", "--no-color"])),
+        stdout(&portview(&["--no-color"]))
+    );
+    // A port number filters the table instead of opening the detail view
+    let text = stdout(&portview(&["list", "5432", "--template", "{{port}}"]));
+    assert_eq!(text, "5432\n");
+
+    let text = stdout(&portview(&[
+        "list",
+        "--sort",
+        "mem",
+        "--reverse",
+        "--template",
+        "{{process}}",
+    ]));
+    assert_eq!(text, "node\npostgres\npython3\navahi-daemon\n");
+    let bare = stdout(&portview(&[
+        "--sort",
+        "mem",
+        "--reverse",
+        "--template",
+        "{{process}}",
+    ]));
+    assert_eq!(bare, text);
+    assert!(!portview(&["list", "--sort", "colour"]).status.success());
+}
+
+#[test]
+fn json_errors_carry_codes() {
+    let out = portview(&["9999", "--json"]);
+    assert_eq!(out.status.code(), Some(1));
+    assert_eq!(stdout(&out), "[]\n");
+    assert_eq!(
+        String::from_utf8_lossy(&out.stderr),
+        "{\"error\":\"nothing on port 9999\",\"code\":\"no_such_port\"}\n"
+    );
+
+    let out = portview(&["nginx", "--json"]);
+    assert!(String::from_utf8_lossy(&out.stderr).contains(r#""code":"no_match""#));
+
+    let out = portview(&["list", "--sort", "colour", "--json"]);
+    assert_eq!(out.status.code(), Some(2));
+    let err = String::from_utf8_lossy(&out.stderr);
+    assert!(err.starts_with(r#"{"error":""#), "{}", err);
+    assert!(err.contains(r#""code":"invalid_argument""#), "{}", err);
+
+    let dir = std::env::temp_dir().join(format!("portview-errors-{}", std::process::id()));
+    std::fs::create_dir_all(&dir).unwrap();
+    let out = command(&["expect", "--json"])
+        .current_dir(&dir)
+        .output()
+        .expect("run portview");
+    std::fs::remove_dir_all(&dir).unwrap();
+    assert_eq!(out.status.code(), Some(2));
+    let err = String::from_utf8_lossy(&out.stderr);
+    assert!(err.starts_with(r#"{"error":"no manifest given"#), "{}", err);
+    assert!(err.contains(r#""code":"invalid_argument""#), "{}", err);
+
+    // Without --json, errors stay prose
+    let out = portview(&["list", "--sort", "colour"]);
+    assert!(String::from_utf8_lossy(&out.stderr).starts_with("error:"));
+}
+
+#[test]
+fn width_overrides_terminal_detection() {
+    let full = "/usr/lib/postgresql/16/bin/postgres -D /var/lib/postgresql";
+    let wide = stdout(&portview(&["list", "--no-color", "--width", "200"]));
+    assert!(wide.contains(full), "{}", wide);
+    let narrow = stdout(&portview(&["list", "--no-color", "--width", "100"]));
+    assert!(!narrow.contains(full), "{}", narrow);
+    assert!(
+        narrow.lines().all(|l| l.chars().count() <= 100),
+        "{}",
+        narrow
+    );
+    // Watch mode follows the terminal instead
+    assert!(!portview(&["watch", "--plain", "--width", "100"])
+        .status
+        .success());
+    let out = portview(&["--watch", "--width", "100"]);
+    assert!(!out.status.success());
+    assert!(String::from_utf8_lossy(&out.stderr).contains("--width"));
+}
+
+#[test]
+fn daemon_once_writes_status_file() {
+    let path = std::env::temp_dir().join(format!("portview-status-{}.json", std::process::id()));
+    let out = portview(&["daemon", "--once", "--output", path.to_str().unwrap()]);
+    assert!(
+        out.status.success(),
+        "{}",
+        String::from_utf8_lossy(&out.stderr)
+    );
+    let json = std::fs::read_to_string(&path).unwrap();
+    std::fs::remove_file(&path).unwrap();
+    assert!(
+        json.starts_with(r#"{"schema_version":1,"updated":"#),
+        "{}",
+        json
+    );
+    assert!(json.contains(r#","interval_ms":2000,"#));
+    assert!(json.contains(r#","ports":[{"schema_version":1,"port":3000,"#));
+}
+
+#[test]
+fn expect_vscode_format_points_at_services() {
+    let manifest = concat!(env!("CARGO_MANIFEST_DIR"), "/tests/fixtures/services.toml");
+    let out = portview(&["expect", manifest, "--format", "vscode"]);
+    assert_eq!(out.status.code(), Some(1));
+    let text = stdout(&out);
+    let lines: Vec<&str> = text.lines().collect();
+    assert_eq!(lines.len(), 2, "{}", text);
+    assert!(lines.iter().all(|l| l.starts_with(manifest)));
+    assert!(text
+        .contains("services.toml:12:1: error: api (8080/tcp) unexpected: held by python3 (pid 5100), expected java"));
+    assert!(text.contains("services.toml:17:1: error: mail (8025/tcp) missing: not listening"));
+}
+
+#[test]
+fn hook_check_warns_about_declared_ports() {
+    let dir = std::env::temp_dir().join(format!("portview-hook-{}", std::process::id()));
+    std::fs::create_dir_all(&dir).unwrap();
+    std::fs::write(dir.join(".env"), "PORT=3000\n").unwrap();
+    std::fs::write(
+        dir.join("package.json"),
+        r#"{"scripts":{"dev":"vite --port 5173"}}"#,
+    )
+    .unwrap();
+    let out = command(&["hook", "--check"])
+        .current_dir(&dir)
+        .output()
+        .expect("run portview");
+    std::fs::remove_dir_all(&dir).unwrap();
+    assert!(out.status.success());
+    assert_eq!(
+        stdout(&out),
+        "portview: port 3000 from .env is taken by node (pid 4242)\n"
+    );
+}
+
+#[test]
+fn here_reports_declared_ports() {
+    let dir = std::env::temp_dir().join(format!("portview-here-{}", std::process::id()));
+    std::fs::create_dir_all(&dir).unwrap();
+    std::fs::write(dir.join(".env"), "PORT=3000\n").unwrap();
+    std::fs::write(
+        dir.join("docker-compose.yml"),
+        "services:\n  mail:\n    ports:\n      - \"8025:8025\"\n",
+    )
+    .unwrap();
+    let run = |args: &[&str]| {
+        command(args)
+            .current_dir(&dir)
+            .output()
+            .expect("run portview")
+    };
+    let text = stdout(&run(&["here", "--no-color"]));
+    let json = stdout(&run(&["here", "--json"]));
+    std::fs::remove_file(dir.join(".env")).unwrap();
+    std::fs::remove_file(dir.join("docker-compose.yml")).unwrap();
+    let empty = run(&["here", "--json"]);
+    std::fs::remove_dir_all(&dir).unwrap();
+    assert_eq!(empty.status.code(), Some(1));
+    let err = String::from_utf8_lossy(&empty.stderr);
+    assert!(
+        err.starts_with(r#"{"error":"no ports declared in "#),
+        "{}",
+        err
+    );
+    assert!(err.contains(r#""code":"no_match""#), "{}", err);
+    assert!(
+        text.contains("● 3000  .env                       node (pid 4242)"),
+        "{}",
+        text
+    );
+    assert!(
+        text.contains("○ 8025  docker-compose.yml (mail)  free"),
+        "{}",
+        text
+    );
+    assert!(text.contains("1 of 2 ports listening; 1 held by processes outside this project."));
+    assert!(json.contains(
+        r#"{"port":8025,"source":"docker-compose.yml (mail)","listening":false,"pid":null"#
+    ));
+}
+
+#[test]
+fn diff_compares_snapshots() {
+    let dir = std::env::temp_dir().join(format!("portview-diff-{}", std::process::id()));
+    std::fs::create_dir_all(&dir).unwrap();
+    let before = dir.join("before.json");
+    let after = dir.join("after.json");
+    std::fs::write(
+        &before,
+        r#"[{"port":3000,"protocol":"TCP","pid":4242,"process":"node"},{"port":5432,"protocol":"TCP","pid":1203,"process":"postgres"}]"#,
+    )
+    .unwrap();
+    std::fs::write(
+        &after,
+        r#"{"updated":1,"ports":[{"port":3000,"protocol":"TCP","pid":4300,"process":"node"},{"port":8080,"protocol":"TCP","pid":5100,"process":"python3"}]}"#,
+    )
+    .unwrap();
+    let (b, a) = (before.to_str().unwrap(), after.to_str().unwrap());
+
+    let out = portview(&["diff", b, a, "--no-color"]);
+    assert_eq!(out.status.code(), Some(1));
+    let text = stdout(&out);
+    assert!(
+        text.contains("~ 3000/TCP  node (pid 4242) → node (pid 4300)"),
+        "{}",
+        text
+    );
+    assert!(text.contains("- 5432/TCP  postgres (pid 1203)"));
+    assert!(text.contains("+ 8080/TCP  python3 (pid 5100)"));
+    assert!(text.contains("1 added, 1 removed, 1 changed."));
+
+    let json = stdout(&portview(&["diff", b, a, "--ports-only", "--json"]));
+    assert!(
+        json.starts_with(r#"{"same":false,"added":[{"port":8080"#),
+        "{}",
+        json
+    );
+    assert!(json.ends_with("\"changed\":[]}\n"));
+    assert_eq!(portview(&["diff", b, b]).status.code(), Some(0));
+
+    let missing = dir.join("missing.json");
+    let out = portview(&["diff", b, missing.to_str().unwrap(), "--json"]);
+    assert!(String::from_utf8_lossy(&out.stderr).contains(r#""code":"io_error""#));
+    std::fs::write(&after, "not json").unwrap();
+    let out = portview(&["diff", b, a, "--json"]);
+    assert_eq!(out.status.code(), Some(2));
+    assert!(String::from_utf8_lossy(&out.stderr).contains(r#""code":"invalid_argument""#));
+    std::fs::remove_dir_all(&dir).unwrap();
+}
+
+#[test]
+fn log_reads_the_action_log() {
+    let dir = std::env::temp_dir().join(format!("portview-log-{}", std::process::id()));
+    std::fs::create_dir_all(&dir).unwrap();
+
+    // Fixture kills are simulated and leave the log alone
+    let out = command(&["kill", "3000", "--no-color"])
+        .env("XDG_STATE_HOME", &dir)
+        .output()
+        .expect("run portview");
+    assert!(out.status.success());
+    assert!(!dir.join("portview").exists());
+
+    let log = dir.join("actions.log");
+    std::fs::write(
+        &log,
+        "1700000000\talice\tSIGTERM\tPID 4242 (node :3000)\tok\n\
+         1700000060\tbob\tdocker stop\tcontainer web\tdocker stop web failed: denied\n",
+    )
+    .unwrap();
+    let path = log.to_str().unwrap();
+    let text = stdout(&portview(&["log", "--file", path, "--no-color"]));
+    assert!(
+        text.contains("alice  SIGTERM      PID 4242 (node :3000)  ok"),
+        "{}",
+        text
+    );
+    assert!(
+        text.contains("bob    docker stop  container web"),
+        "{}",
+        text
+    );
+
+    let json = stdout(&portview(&["log", "--file", path, "-n", "1", "--json"]));
+    assert!(json.starts_with(r#"[{"at":1700000060,"#), "{}", json);
+    assert!(json.contains(r#""target":"container web","ok":false,"#));
+    std::fs::remove_dir_all(&dir).unwrap();
+}
+
+// ── Schema ───────────────────────────────────────────────────────────
+
+#[test]
+fn json_output_matches_the_schema() {
+    let schema: serde_json::Value = serde_json::from_str(include_str!(concat!(
+        env!("CARGO_MANIFEST_DIR"),
+        "/schema/ports.schema.json"
+    )))
+    .unwrap();
+    for args in [
+        &["--json", "--all"][..],
+        &["3000", "--json"],
+        &["8080", "--json"],
+        &["5353", "--json"],
+    ] {
+        let out = portview(args);
+        let json: serde_json::Value = serde_json::from_str(&stdout(&out))
+            .unwrap_or_else(|e| panic!("{:?}: {}\n{}", args, e, stdout(&out)));
+        if let Err(e) = validate(&schema, &schema, &json, "$") {
+            panic!("{:?}: {}", args, e);
+        }
+    }
+}
+
+/// Check `value` against `schema`, for the keywords ports.schema.json
+/// uses. Anything else in the schema fails the test rather than passing
+/// unchecked.
+fn validate(
+    root: &serde_json::Value,
+    schema: &serde_json::Value,
+    value: &serde_json::Value,
+    path: &str,
+) -> Result<(), String> {
+    use serde_json::Value;
+    let Value::Object(keywords) = schema else {
+        return Err(format!("{}: schema is not an object", path));
+    };
+    for (keyword, rule) in keywords {
+        match keyword.as_str() {
+            "$schema" | "$id" | "$defs" | "title" | "description" => {}
+            "$ref" => {
+                let name = rule.as_str().unwrap().trim_start_matches("#/$defs/");
+                validate(root, &root["$defs"][name], value, path)?;
+            }
+            "type" => {
+                let types: Vec<&str> = match rule {
+                    Value::Array(types) => types.iter().filter_map(Value::as_str).collect(),
+                    other => vec![other.as_str().unwrap()],
+                };
+                let ok = types.iter().any(|t| match *t {
+                    "null" => value.is_null(),
+                    "boolean" => value.is_boolean(),
+                    "integer" => value.is_i64() || value.is_u64(),
+                    "number" => value.is_number(),
+                    "string" => value.is_string(),
+                    "array" => value.is_array(),
+                    "object" => value.is_object(),
+                    other => panic!("unknown type {}", other),
+                });
+                if !ok {
+                    return Err(format!("{}: {} is not {:?}", path, value, types));
+                }
+            }
+            "enum" => {
+                if !rule.as_array().unwrap().contains(value) {
+                    return Err(format!("{}: {} not in {}", path, value, rule));
+                }
+            }
+            "const" => {
+                if rule != value {
+                    return Err(format!("{}: {} is not {}", path, value, rule));
+                }
+            }
+            "minimum" | "maximum" => {
+                if let (Some(n), Some(bound)) = (value.as_f64(), rule.as_f64()) {
+                    let ok = if keyword == "minimum" {
+                        n >= bound
+                    } else {
+                        n <= bound
+                    };
+                    if !ok {
+                        return Err(format!("{}: {} breaks {} {}", path, n, keyword, bound));
+                    }
+                }
+            }
+            "required" => {
+                for key in rule.as_array().unwrap() {
+                    if value.get(key.as_str().unwrap()).is_none() {
+                        return Err(format!("{}: missing {}", path, key));
+                    }
+                }
+            }
+            "properties" => {
+                for (key, sub) in rule.as_object().unwrap() {
+                    if let Some(field) = value.get(key) {
+                        validate(root, sub, field, &format!("{}.{}", path, key))?;
+                    }
+                }
+            }
+            "additionalProperties" => {
+                let known = keywords.get("properties").and_then(Value::as_object);
+                for (key, field) in value.as_object().into_iter().flatten() {
+                    if known.is_none_or(|k| !k.contains_key(key)) {
+                        validate(root, rule, field, &format!("{}.{}", path, key))?;
+                    }
+                }
+            }
+            "items" => {
+                for (i, item) in value.as_array().into_iter().flatten().enumerate() {
+                    validate(root, rule, item, &format!("{}[{}]", path, i))?;
+                }
+            }
+            "oneOf" => {
+                let matched = rule
+                    .as_array()
+                    .unwrap()
+                    .iter()
+                    .filter(|sub| validate(root, sub, value, path).is_ok())
+                    .count();
+                if matched != 1 {
+                    return Err(format!("{}: {} matches {} of oneOf", path, value, matched));
+                }
+            }
+            other => panic!("schema keyword {} isn't checked", other),
+        }
+    }
+    Ok(())
+}
@@ -0,0 +1,90 @@
+//! End-to-end runs of the binary against sockets this test binds, so the
+//! platform collectors (linux.rs, macos.rs, windows.rs) are exercised on
+//! whatever machine runs the suite.
+
+use std::net::{TcpListener, UdpSocket};
+use std::process::{Command, Output};
+
+fn portview(args: &[&str]) -> Output {
+    Command::new(env!("CARGO_BIN_EXE_portview"))
+        .args(args)
+        .output()
+        .expect("run portview")
+}
+
+fn stdout(out: &Output) -> String {
+    String::from_utf8_lossy(&out.stdout).into_owned()
+}
+
+#[test]
+fn selftest_finds_its_own_sockets() {
+    let out = portview(&["selftest", "--json"]);
+    let text = stdout(&out);
+    assert!(out.status.success(), "{}", text);
+    assert!(
+        text.starts_with(r#"{"ok":true,"checks":[{"name":"tcp listener","status":"ok""#),
+        "{}",
+        text
+    );
+    assert!(
+        text.contains(r#"{"name":"connection","status":"ok""#),
+        "{}",
+        text
+    );
+}
+
+#[test]
+fn inspect_reports_test_listener() {
+    let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+    let port = listener.local_addr().unwrap().port().to_string();
+    let text = stdout(&portview(&[&port, "--json"]));
+    assert!(
+        text.contains(&format!(
+            r#""port":{},"protocol":"TCP","pid":{},"#,
+            port,
+            std::process::id()
+        )),
+        "{}",
+        text
+    );
+    assert!(text.contains(r#""state":"LISTEN""#), "{}", text);
+
+    let why = stdout(&portview(&["why", &port, "--json"]));
+    assert!(why.contains(r#""blocked":true"#), "{}", why);
+    assert!(
+        why.contains(&format!("(PID {}", std::process::id())),
+        "{}",
+        why
+    );
+}
+
+#[test]
+fn pid_lists_test_sockets() {
+    let tcp = TcpListener::bind("127.0.0.1:0").unwrap();
+    let udp = UdpSocket::bind("127.0.0.1:0").unwrap();
+    let pid = std::process::id().to_string();
+    let text = stdout(&portview(&["pid", &pid, "--json"]));
+    for (port, proto) in [
+        (tcp.local_addr().unwrap().port(), "TCP"),
+        (udp.local_addr().unwrap().port(), "UDP"),
+    ] {
+        assert!(
+            text.contains(&format!(
+                r#""port":{},"protocol":"{}","pid":{}"#,
+                port, proto, pid
+            )),
+            "{}",
+            text
+        );
+    }
+}
+
+#[test]
+fn released_port_disappears() {
+    let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+    let port = listener.local_addr().unwrap().port().to_string();
+    assert!(portview(&[&port, "--json"]).status.success());
+    drop(listener);
+    let out = portview(&[&port, "--json"]);
+    assert!(!stdout(&out).contains(&format!(r#""pid":{}"#, std::process::id())));
+}